@@ -8,6 +8,7 @@ use std::time::Duration;
 use http::Uri;
 use parking_lot::RwLock;
 
+use super::dns::DnsProvider;
 use super::key_pair::KeyPair;
 use super::{ChallengeType, LETS_ENCRYPT_PRODUCTION};
 
@@ -21,6 +22,8 @@ pub struct AcmeConfig {
     pub(crate) challenge_type: ChallengeType,
     pub(crate) cache_path: Option<PathBuf>,
     pub(crate) keys_for_http01: Option<Arc<RwLock<HashMap<String, String>>>>,
+    pub(crate) dns_provider: Option<Arc<dyn DnsProvider>>,
+    pub(crate) dns_propagation: Duration,
     pub(crate) before_expired: Duration,
 }
 
@@ -53,6 +56,8 @@ pub struct AcmeConfigBuilder {
     pub(crate) challenge_type: ChallengeType,
     pub(crate) cache_path: Option<PathBuf>,
     pub(crate) keys_for_http01: Option<Arc<RwLock<HashMap<String, String>>>>,
+    pub(crate) dns_provider: Option<Arc<dyn DnsProvider>>,
+    pub(crate) dns_propagation: Duration,
     pub(crate) before_expired: Duration,
 }
 
@@ -67,6 +72,8 @@ impl AcmeConfigBuilder {
             challenge_type: ChallengeType::TlsAlpn01,
             cache_path: None,
             keys_for_http01: None,
+            dns_provider: None,
+            dns_propagation: Duration::from_secs(30),
             before_expired: Duration::from_secs(12 * 60 * 60),
         }
     }
@@ -115,6 +122,7 @@ impl AcmeConfigBuilder {
         Self {
             challenge_type: ChallengeType::Http01,
             keys_for_http01: Some(Default::default()),
+            dns_provider: None,
             ..self
         }
     }
@@ -124,9 +132,27 @@ impl AcmeConfigBuilder {
         Self {
             challenge_type: ChallengeType::TlsAlpn01,
             keys_for_http01: None,
+            dns_provider: None,
             ..self
         }
     }
+    /// Sets the challenge type Dns01 with the provider to manage TXT records.
+    #[inline]
+    pub fn dns01_challenge(self, provider: impl DnsProvider) -> Self {
+        Self {
+            challenge_type: ChallengeType::Dns01,
+            keys_for_http01: None,
+            dns_provider: Some(Arc::new(provider)),
+            ..self
+        }
+    }
+    /// Sets the duration to wait for the TXT records propagated before triggering `DNS-01` challenge.
+    ///
+    /// Defaults to 30 seconds.
+    #[inline]
+    pub fn dns_propagation(self, dns_propagation: Duration) -> Self {
+        Self { dns_propagation, ..self }
+    }
 
     /// Sets the cache path for caching certificates.
     ///
@@ -155,6 +181,9 @@ impl AcmeConfigBuilder {
         if self.domains.is_empty() {
             return Err(IoError::other("at least one domain name is expected"));
         }
+        if self.challenge_type != ChallengeType::Dns01 && self.domains.iter().any(|d| d.starts_with("*.")) {
+            return Err(IoError::other("wildcard domain name requires `DNS-01` challenge"));
+        }
         let Self {
             directory_name,
             directory_url,
@@ -163,6 +192,8 @@ impl AcmeConfigBuilder {
             challenge_type,
             cache_path,
             keys_for_http01,
+            dns_provider,
+            dns_propagation,
             before_expired,
         } = self;
 
@@ -175,6 +206,8 @@ impl AcmeConfigBuilder {
             challenge_type,
             cache_path,
             keys_for_http01,
+            dns_provider,
+            dns_propagation,
             before_expired,
         })
     }
@@ -207,4 +240,29 @@ mod tests {
         assert_eq!(acme_config.cache_path, Some(PathBuf::from("test_cache_path")));
         assert_eq!(acme_config.before_expired, Duration::from_secs(24 * 60 * 60));
     }
+
+    #[test]
+    fn test_acme_config_wildcard_domain() {
+        struct NoopDnsProvider;
+        #[crate::async_trait]
+        impl DnsProvider for NoopDnsProvider {
+            async fn add_txt_record(&self, _name: &str, _value: &str) -> crate::Result<()> {
+                Ok(())
+            }
+            async fn remove_txt_record(&self, _name: &str, _value: &str) -> crate::Result<()> {
+                Ok(())
+            }
+        }
+
+        assert!(AcmeConfig::builder().add_domain("*.example.com").build().is_err());
+        let acme_config = AcmeConfig::builder()
+            .add_domain("*.example.com")
+            .dns01_challenge(NoopDnsProvider)
+            .dns_propagation(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert_eq!(acme_config.challenge_type, ChallengeType::Dns01);
+        assert!(acme_config.dns_provider.is_some());
+        assert_eq!(acme_config.dns_propagation, Duration::from_secs(5));
+    }
 }
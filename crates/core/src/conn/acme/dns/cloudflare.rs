@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+
+use bytes::Bytes;
+use http::Method;
+use http::header::{AUTHORIZATION, CONTENT_TYPE};
+use http_body_util::Full;
+use parking_lot::Mutex;
+use serde::Deserialize;
+
+use super::super::client::{HyperClient, https_client};
+use super::{DnsProvider, send_request};
+use crate::{Error, async_trait};
+
+const CLOUDFLARE_API: &str = "https://api.cloudflare.com/client/v4";
+
+/// [`DnsProvider`] for Cloudflare.
+///
+/// The API token needs the `Zone.DNS` edit permission of the zone.
+pub struct CloudflareDnsProvider {
+    api_token: String,
+    zone_id: String,
+    client: HyperClient,
+    record_ids: Mutex<HashMap<(String, String), String>>,
+}
+
+impl Debug for CloudflareDnsProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CloudflareDnsProvider")
+            .field("zone_id", &self.zone_id)
+            .finish()
+    }
+}
+
+impl CloudflareDnsProvider {
+    /// Create a new `CloudflareDnsProvider` with API token and the id of the zone contains the domains.
+    pub fn new(api_token: impl Into<String>, zone_id: impl Into<String>) -> Self {
+        Self {
+            api_token: api_token.into(),
+            zone_id: zone_id.into(),
+            client: https_client(),
+            record_ids: Default::default(),
+        }
+    }
+
+    async fn request<T>(&self, method: Method, url: String, body: Option<serde_json::Value>) -> crate::Result<T>
+    where
+        T: for<'de> Deserialize<'de> + Default,
+    {
+        let body = match body {
+            Some(body) => Full::from(body.to_string()),
+            None => Full::new(Bytes::new()),
+        };
+        let req = hyper::Request::builder()
+            .method(method)
+            .uri(url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.api_token))
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .map_err(|e| Error::other(format!("failed to build http request: {}", e)))?;
+        let (status, body) = send_request(&self.client, req).await?;
+        let res: CloudflareResponse<T> = serde_json::from_slice(&body)
+            .map_err(|e| Error::other(format!("response is not a valid json: {}", e)))?;
+        if !res.success || !status.is_success() {
+            let errors = res
+                .errors
+                .iter()
+                .map(|e| format!("{}: {}", e.code, e.message))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(Error::other(format!(
+                "cloudflare request failed: status = {}, errors = [{}]",
+                status, errors
+            )));
+        }
+        Ok(res.result.unwrap_or_default())
+    }
+
+    async fn find_record_id(&self, name: &str, value: &str) -> crate::Result<Option<String>> {
+        let url = format!(
+            "{}/zones/{}/dns_records?type=TXT&name={}",
+            CLOUDFLARE_API, self.zone_id, name
+        );
+        let records: Vec<DnsRecord> = self.request(Method::GET, url, None).await?;
+        Ok(records
+            .into_iter()
+            .find(|record| record.content.trim_matches('"') == value)
+            .map(|record| record.id))
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct CloudflareResponse<T> {
+    success: bool,
+    #[serde(default)]
+    errors: Vec<CloudflareError>,
+    result: Option<T>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CloudflareError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize, Default, Debug)]
+struct DnsRecord {
+    id: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[async_trait]
+impl DnsProvider for CloudflareDnsProvider {
+    async fn add_txt_record(&self, name: &str, value: &str) -> crate::Result<()> {
+        let url = format!("{}/zones/{}/dns_records", CLOUDFLARE_API, self.zone_id);
+        let body = serde_json::json!({
+            "type": "TXT",
+            "name": name,
+            "content": value,
+            "ttl": 120,
+        });
+        let record: DnsRecord = self.request(Method::POST, url, Some(body)).await?;
+        tracing::debug!(name, record_id = %record.id, "cloudflare txt record added");
+        self.record_ids
+            .lock()
+            .insert((name.to_owned(), value.to_owned()), record.id);
+        Ok(())
+    }
+
+    async fn remove_txt_record(&self, name: &str, value: &str) -> crate::Result<()> {
+        let record_id = self.record_ids.lock().remove(&(name.to_owned(), value.to_owned()));
+        let record_id = match record_id {
+            Some(record_id) => record_id,
+            None => match self.find_record_id(name, value).await? {
+                Some(record_id) => record_id,
+                None => return Ok(()),
+            },
+        };
+        let url = format!(
+            "{}/zones/{}/dns_records/{}",
+            CLOUDFLARE_API, self.zone_id, record_id
+        );
+        self.request::<serde_json::Value>(Method::DELETE, url, None).await?;
+        tracing::debug!(name, %record_id, "cloudflare txt record removed");
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! DNS providers for `DNS-01` challenge.
+//!
+//! `DNS-01` challenge proves the control of a domain by putting a TXT record under `_acme-challenge.<domain>`,
+//! it is the only challenge type which can be used to issue wildcard certificates.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "acme-cloudflare")]
+//! # async fn run() {
+//! use salvo_core::prelude::*;
+//! use salvo_core::conn::acme::dns::CloudflareDnsProvider;
+//!
+//! let router = Router::new();
+//! let acceptor = TcpListener::new("0.0.0.0:443")
+//!     .acme()
+//!     .cache_path("acme/letsencrypt")
+//!     .add_domain("salvo.rs")
+//!     .add_domain("*.salvo.rs")
+//!     .dns01_challenge(CloudflareDnsProvider::new("api-token", "zone-id"))
+//!     .bind()
+//!     .await;
+//! Server::new(acceptor).serve(router).await;
+//! # }
+//! ```
+use crate::async_trait;
+
+#[cfg(any(feature = "acme-cloudflare", feature = "acme-route53"))]
+use super::client::HyperClient;
+
+cfg_feature! {
+    #![feature = "acme-cloudflare"]
+    mod cloudflare;
+    pub use cloudflare::CloudflareDnsProvider;
+}
+cfg_feature! {
+    #![feature = "acme-route53"]
+    mod route53;
+    pub use route53::Route53DnsProvider;
+}
+
+/// Provider to manage the TXT records used by `DNS-01` challenge.
+#[async_trait]
+pub trait DnsProvider: Send + Sync + 'static {
+    /// Add a TXT record with `name` and `value`.
+    ///
+    /// The `name` is the full domain name like `_acme-challenge.example.com`, without the trailing dot.
+    async fn add_txt_record(&self, name: &str, value: &str) -> crate::Result<()>;
+
+    /// Remove the TXT record with `name` and `value` added by [`DnsProvider::add_txt_record`].
+    async fn remove_txt_record(&self, name: &str, value: &str) -> crate::Result<()>;
+}
+
+/// Get the TXT record name for `DNS-01` challenge of `domain`.
+pub(crate) fn txt_record_name(domain: &str) -> String {
+    format!("_acme-challenge.{}", domain.trim_start_matches("*."))
+}
+
+#[cfg(any(feature = "acme-cloudflare", feature = "acme-route53"))]
+async fn send_request(
+    client: &HyperClient,
+    req: hyper::Request<http_body_util::Full<bytes::Bytes>>,
+) -> crate::Result<(http::StatusCode, bytes::Bytes)> {
+    use http_body_util::BodyExt;
+
+    let res = client
+        .request(req)
+        .await
+        .map_err(|e| crate::Error::other(format!("failed to send http request: {}", e)))?;
+    let status = res.status();
+    let body = res.into_body().collect().await?.to_bytes();
+    Ok((status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_txt_record_name() {
+        assert_eq!(txt_record_name("example.com"), "_acme-challenge.example.com");
+        assert_eq!(txt_record_name("*.example.com"), "_acme-challenge.example.com");
+    }
+}
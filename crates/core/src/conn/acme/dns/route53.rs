@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use http::Method;
+use http::header::{AUTHORIZATION, CONTENT_TYPE, HOST};
+use http_body_util::Full;
+use parking_lot::Mutex;
+use ring::{digest, hmac};
+
+use super::super::client::{HyperClient, https_client};
+use super::{DnsProvider, send_request};
+use crate::{Error, async_trait};
+
+const ROUTE53_HOST: &str = "route53.amazonaws.com";
+const ROUTE53_REGION: &str = "us-east-1";
+const ROUTE53_SERVICE: &str = "route53";
+
+/// [`DnsProvider`] for AWS Route 53.
+///
+/// The credentials need the `route53:ChangeResourceRecordSets` permission of the hosted zone.
+pub struct Route53DnsProvider {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    hosted_zone_id: String,
+    client: HyperClient,
+    // Route 53 manages the record set as a whole, all values of the same name are kept here.
+    records: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl Debug for Route53DnsProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Route53DnsProvider")
+            .field("access_key_id", &self.access_key_id)
+            .field("hosted_zone_id", &self.hosted_zone_id)
+            .finish()
+    }
+}
+
+impl Route53DnsProvider {
+    /// Create a new `Route53DnsProvider` with AWS credentials and the id of the hosted zone contains the domains.
+    pub fn new(
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+        hosted_zone_id: impl Into<String>,
+    ) -> Self {
+        let hosted_zone_id: String = hosted_zone_id.into();
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            hosted_zone_id: hosted_zone_id.trim_start_matches("/hostedzone/").to_owned(),
+            client: https_client(),
+            records: Default::default(),
+        }
+    }
+
+    /// Sets the session token for temporary credentials.
+    #[inline]
+    pub fn session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    async fn change_record_set(&self, action: &str, name: &str, values: &[String]) -> crate::Result<()> {
+        let records = values
+            .iter()
+            .map(|value| format!("<ResourceRecord><Value>\"{}\"</Value></ResourceRecord>", value))
+            .collect::<String>();
+        let body = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><ChangeResourceRecordSetsRequest xmlns="https://route53.amazonaws.com/doc/2013-04-01/"><ChangeBatch><Changes><Change><Action>{action}</Action><ResourceRecordSet><Name>{name}</Name><Type>TXT</Type><TTL>60</TTL><ResourceRecords>{records}</ResourceRecords></ResourceRecordSet></Change></Changes></ChangeBatch></ChangeResourceRecordSetsRequest>"#
+        );
+        let path = format!("/2013-04-01/hostedzone/{}/rrset", self.hosted_zone_id);
+        let (amz_date, date) = amz_date(SystemTime::now());
+        let payload_hash = hex(digest::digest(&digest::SHA256, body.as_bytes()).as_ref());
+
+        let mut headers = vec![
+            ("content-type", "text/xml".to_owned()),
+            ("host", ROUTE53_HOST.to_owned()),
+            ("x-amz-content-sha256", payload_hash.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(session_token) = &self.session_token {
+            headers.push(("x-amz-security-token", session_token.clone()));
+        }
+        let canonical_headers = headers
+            .iter()
+            .map(|(name, value)| format!("{name}:{value}\n"))
+            .collect::<String>();
+        let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+        let canonical_request = format!("POST\n{path}\n\n{canonical_headers}\n{signed_headers}\n{payload_hash}");
+        let scope = format!("{date}/{ROUTE53_REGION}/{ROUTE53_SERVICE}/aws4_request");
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref())
+        );
+        let key = signing_key(&self.secret_access_key, &date, ROUTE53_REGION, ROUTE53_SERVICE);
+        let signature = hex(hmac::sign(&key, string_to_sign.as_bytes()).as_ref());
+
+        let mut req = hyper::Request::builder()
+            .method(Method::POST)
+            .uri(format!("https://{ROUTE53_HOST}{path}"))
+            .header(
+                AUTHORIZATION,
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                    self.access_key_id
+                ),
+            )
+            .header(CONTENT_TYPE, "text/xml")
+            .header(HOST, ROUTE53_HOST)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date);
+        if let Some(session_token) = &self.session_token {
+            req = req.header("x-amz-security-token", session_token);
+        }
+        let req = req
+            .body(Full::from(body))
+            .map_err(|e| Error::other(format!("failed to build http request: {}", e)))?;
+        let (status, body) = send_request(&self.client, req).await?;
+        if !status.is_success() {
+            return Err(Error::other(format!(
+                "route53 request failed: status = {}, body = {}",
+                status,
+                String::from_utf8_lossy(&body)
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DnsProvider for Route53DnsProvider {
+    async fn add_txt_record(&self, name: &str, value: &str) -> crate::Result<()> {
+        let values = {
+            let mut records = self.records.lock();
+            let values = records.entry(name.to_owned()).or_default();
+            if !values.iter().any(|v| v == value) {
+                values.push(value.to_owned());
+            }
+            values.clone()
+        };
+        self.change_record_set("UPSERT", name, &values).await?;
+        tracing::debug!(name, "route53 txt record added");
+        Ok(())
+    }
+
+    async fn remove_txt_record(&self, name: &str, value: &str) -> crate::Result<()> {
+        let (old_values, values) = {
+            let mut records = self.records.lock();
+            let Some(values) = records.get_mut(name) else {
+                return Ok(());
+            };
+            let old_values = values.clone();
+            values.retain(|v| v != value);
+            let values = values.clone();
+            if values.is_empty() {
+                records.remove(name);
+            }
+            (old_values, values)
+        };
+        if values.is_empty() {
+            self.change_record_set("DELETE", name, &old_values).await?;
+        } else {
+            self.change_record_set("UPSERT", name, &values).await?;
+        }
+        tracing::debug!(name, "route53 txt record removed");
+        Ok(())
+    }
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> hmac::Key {
+    let sign = |key: &[u8], data: &str| hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes());
+    let key = sign(format!("AWS4{secret_access_key}").as_bytes(), date);
+    let key = sign(key.as_ref(), region);
+    let key = sign(key.as_ref(), service);
+    let key = sign(key.as_ref(), "aws4_request");
+    hmac::Key::new(hmac::HMAC_SHA256, key.as_ref())
+}
+
+/// Returns the `x-amz-date` format time and the date part of it.
+fn amz_date(time: SystemTime) -> (String, String) {
+    let secs = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (days, rem) = (secs / 86400, secs % 86400);
+    // Convert days since epoch to civil date, see: <http://howardhinnant.github.io/date_algorithms.html>
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    let date = format!("{year:04}{month:02}{day:02}");
+    (
+        format!("{date}T{:02}{:02}{:02}Z", rem / 3600, rem % 3600 / 60, rem % 60),
+        date,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_amz_date() {
+        let time = UNIX_EPOCH + Duration::from_secs(1440938160);
+        assert_eq!(amz_date(time), ("20150830T123600Z".to_owned(), "20150830".to_owned()));
+        let time = UNIX_EPOCH + Duration::from_secs(951782400);
+        assert_eq!(amz_date(time), ("20000229T000000Z".to_owned(), "20000229".to_owned()));
+    }
+
+    #[test]
+    fn test_signing_key() {
+        // Example from AWS Signature Version 4 documentation.
+        let key = signing_key("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY", "20150830", "us-east-1", "iam");
+        let signature = hmac::sign(&key, b"");
+        let expected = hmac::sign(
+            &hmac::Key::new(
+                hmac::HMAC_SHA256,
+                &[
+                    0xc4, 0xaf, 0xb1, 0xcc, 0x57, 0x71, 0xd8, 0x71, 0x76, 0x3a, 0x39, 0x3e, 0x44, 0xb7, 0x03, 0x57,
+                    0x1b, 0x55, 0xcc, 0x28, 0x42, 0x4d, 0x1a, 0x5e, 0x86, 0xda, 0x6e, 0xd3, 0xc1, 0x54, 0xa4, 0xb9,
+                ],
+            ),
+            b"",
+        );
+        assert_eq!(signature.as_ref(), expected.as_ref());
+    }
+}
@@ -20,6 +20,7 @@ use crate::http::{HttpConnection, Version};
 use crate::Router;
 
 use super::config::{AcmeConfig, AcmeConfigBuilder};
+use super::dns::DnsProvider;
 use super::resolver::{ResolveServerCert, ACME_TLS_ALPN_NAME};
 use super::{AcmeCache, AcmeClient, ChallengeType, Http01Handler, WELL_KNOWN_PATH};
 
@@ -118,6 +119,27 @@ impl<T> AcmeListener<T> {
         }
     }
 
+    /// Use `DNS-01` challenge with the provider to manage TXT records.
+    ///
+    /// It is required to issue wildcard certificates like `*.example.com`.
+    #[inline]
+    pub fn dns01_challenge(self, provider: impl DnsProvider) -> Self {
+        Self {
+            config_builder: self.config_builder.dns01_challenge(provider),
+            ..self
+        }
+    }
+    /// Sets the duration to wait for the TXT records propagated before triggering `DNS-01` challenge.
+    ///
+    /// Defaults to 30 seconds.
+    #[inline]
+    pub fn dns_propagation(self, dns_propagation: Duration) -> Self {
+        Self {
+            config_builder: self.config_builder.dns_propagation(dns_propagation),
+            ..self
+        }
+    }
+
     /// Sets the cache path for caching certificates.
     ///
     /// This is not a necessary option. If you do not configure the cache path,
@@ -366,6 +388,7 @@ where
             conn,
             local_addr,
             remote_addr,
+            extensions,
             ..
         } = self.inner.accept(fuse_factory).await?;
         let fusewire = conn.fusewire();
@@ -374,6 +397,7 @@ where
             local_addr,
             remote_addr,
             http_scheme: Scheme::HTTPS,
+            extensions,
         })
     }
 }
@@ -53,10 +53,15 @@
 //!     Server::new(acceptor).serve(router).await;
 //! }
 //! ```
+//!
+//! * DNS-01
+//!
+//! `DNS-01` challenge is required to issue wildcard certificates, see the [`dns`] module.
 
 pub mod cache;
 mod client;
 mod config;
+pub mod dns;
 mod issuer;
 mod jose;
 mod key_pair;
@@ -95,6 +100,9 @@ const CHALLENGE_TYPE_HTTP_01: &str = "http-01";
 /// TLS-ALPN-01 challenge
 const CHALLENGE_TYPE_TLS_ALPN_01: &str = "tls-alpn-01";
 
+/// DNS-01 challenge
+const CHALLENGE_TYPE_DNS_01: &str = "dns-01";
+
 /// Challenge type
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 #[non_exhaustive]
@@ -107,12 +115,17 @@ pub enum ChallengeType {
     ///
     /// Reference: <https://letsencrypt.org/docs/challenge-types/#tls-alpn-01>
     TlsAlpn01,
+    /// DNS-01
+    ///
+    /// Reference: <https://letsencrypt.org/docs/challenge-types/#dns-01-challenge>
+    Dns01,
 }
 impl Display for ChallengeType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             ChallengeType::Http01 => f.write_str(CHALLENGE_TYPE_HTTP_01),
             ChallengeType::TlsAlpn01 => f.write_str(CHALLENGE_TYPE_TLS_ALPN_01),
+            ChallengeType::Dns01 => f.write_str(CHALLENGE_TYPE_DNS_01),
         }
     }
 }
@@ -0,0 +1,151 @@
+//! Verified TLS client certificate.
+use std::net::IpAddr;
+
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use crate::extract::{Extractible, Metadata};
+use crate::http::{Request, StatusError};
+
+/// The certificate chain presented by the client and verified during mutual TLS handshake.
+///
+/// It is inserted into the extensions of every request on the connection and injected into the `Depot`, so it
+/// can be used as a handler parameter, or got by `depot.obtain::<ClientCert>()`. If the client does not
+/// present a certificate, extracting it returns `401 Unauthorized`.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::conn::ClientCert;
+///
+/// #[handler]
+/// async fn hello(cert: ClientCert, res: &mut Response) {
+///     if cert.subject_alt_names().iter().any(|name| name == "admin.example.com") {
+///         res.render("Hello admin");
+///     } else {
+///         res.render(StatusError::forbidden());
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ClientCert {
+    chain: Vec<Vec<u8>>,
+    common_name: Option<String>,
+    subject_alt_names: Vec<String>,
+}
+
+impl ClientCert {
+    /// Create a new `ClientCert` from DER encoded certificate chain, the first one is the leaf certificate.
+    ///
+    /// Returns `None` if the chain is empty or the leaf certificate is invalid.
+    pub fn from_der_chain(chain: Vec<Vec<u8>>) -> Option<Self> {
+        let (_, leaf) = X509Certificate::from_der(chain.first()?).ok()?;
+        let common_name = leaf
+            .subject()
+            .iter_common_name()
+            .next()
+            .and_then(|cn| cn.as_str().ok())
+            .map(ToOwned::to_owned);
+        let subject_alt_names = leaf
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|san| {
+                san.value
+                    .general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(name)
+                        | GeneralName::RFC822Name(name)
+                        | GeneralName::URI(name) => Some((*name).to_owned()),
+                        GeneralName::IPAddress(ip) => match ip.len() {
+                            4 => <[u8; 4]>::try_from(*ip).ok().map(|ip| IpAddr::from(ip).to_string()),
+                            16 => <[u8; 16]>::try_from(*ip).ok().map(|ip| IpAddr::from(ip).to_string()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(ClientCert {
+            chain,
+            common_name,
+            subject_alt_names,
+        })
+    }
+
+    /// DER encoded leaf certificate.
+    #[inline]
+    pub fn leaf(&self) -> &[u8] {
+        &self.chain[0]
+    }
+
+    /// DER encoded certificate chain, the first one is the leaf certificate.
+    #[inline]
+    pub fn chain(&self) -> &[Vec<u8>] {
+        &self.chain
+    }
+
+    /// Common name of the leaf certificate subject.
+    #[inline]
+    pub fn common_name(&self) -> Option<&str> {
+        self.common_name.as_deref()
+    }
+
+    /// DNS names, emails, URIs and IP addresses in the subject alternative name extension of the leaf certificate.
+    #[inline]
+    pub fn subject_alt_names(&self) -> &[String] {
+        &self.subject_alt_names
+    }
+}
+
+impl<'ex> Extractible<'ex> for ClientCert {
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    #[allow(refining_impl_trait)]
+    async fn extract(req: &'ex mut Request) -> Result<Self, StatusError> {
+        req.extensions()
+            .get::<ClientCert>()
+            .cloned()
+            .ok_or_else(|| StatusError::unauthorized().brief("Client certificate is required."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use x509_parser::pem::parse_x509_pem;
+
+    use super::*;
+
+    #[test]
+    fn test_client_cert() {
+        let pem = std::fs::read("certs/cert.pem").unwrap();
+        let (_, pem) = parse_x509_pem(&pem).unwrap();
+        let cert = ClientCert::from_der_chain(vec![pem.contents.clone()]).unwrap();
+        assert_eq!(cert.common_name(), Some("testserver.com"));
+        assert_eq!(
+            cert.subject_alt_names(),
+            ["testserver.com", "second.testserver.com", "localhost"]
+        );
+        assert_eq!(cert.leaf(), &pem.contents[..]);
+        assert!(ClientCert::from_der_chain(vec![]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_extract_client_cert() {
+        let mut req = Request::new();
+        assert!(ClientCert::extract(&mut req).await.is_err());
+
+        let pem = std::fs::read("certs/cert.pem").unwrap();
+        let (_, pem) = parse_x509_pem(&pem).unwrap();
+        let cert = ClientCert::from_der_chain(vec![pem.contents]).unwrap();
+        req.extensions_mut().insert(cert);
+        let cert = ClientCert::extract(&mut req).await.unwrap();
+        assert_eq!(cert.common_name(), Some("testserver.com"));
+    }
+}
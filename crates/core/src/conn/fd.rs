@@ -0,0 +1,204 @@
+//! Listener for inherited sockets, such as the ones passed by systemd socket activation.
+//!
+//! With systemd socket activation, the listening sockets are created by systemd and passed to the service
+//! process through `LISTEN_FDS` and `LISTEN_PID` environment variables. The same way can be used to pass
+//! listening sockets to a new process for zero-downtime restart.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_core::conn::fd::FdListener;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let acceptor = FdListener::listen_fd(0).unwrap().bind().await;
+//!     Server::new(acceptor).serve(Router::new()).await;
+//! }
+//! ```
+use std::io::{Error as IoError, Result as IoResult};
+use std::net::TcpListener as StdTcpListener;
+use std::os::fd::OwnedFd;
+use std::os::unix::net::UnixListener as StdUnixListener;
+use std::sync::{Mutex, OnceLock};
+
+use listenfd::ListenFd;
+use tokio::net::{TcpStream, UnixStream};
+
+use super::joined::JoinedStream;
+use super::tcp::TcpAcceptor;
+use super::unix::UnixAcceptor;
+use super::{Accepted, Acceptor, Holding, Listener, StraightStream};
+use crate::fuse::ArcFuseFactory;
+
+/// The sockets passed by systemd socket activation, read from the environment only once.
+fn inherited() -> &'static Mutex<ListenFd> {
+    static INHERITED: OnceLock<Mutex<ListenFd>> = OnceLock::new();
+    INHERITED.get_or_init(|| Mutex::new(ListenFd::from_env()))
+}
+
+/// Get the number of sockets passed by systemd socket activation.
+///
+/// It returns `0` if `LISTEN_PID` is not the current process id or `LISTEN_FDS` is not set. Like
+/// `sd_listen_fds`, the environment variables are unset once read, so that they are not inherited by
+/// the child processes.
+pub fn listen_fds_count() -> usize {
+    inherited().lock().unwrap_or_else(|e| e.into_inner()).len()
+}
+
+#[derive(Debug)]
+enum Inner {
+    Tcp(StdTcpListener),
+    Unix(StdUnixListener),
+    Fd(OwnedFd),
+}
+
+/// `FdListener` is used to create a listener from an inherited listening socket, both TCP and Unix socket
+/// are supported.
+#[derive(Debug)]
+pub struct FdListener {
+    inner: Inner,
+}
+impl FdListener {
+    /// Creates a new `FdListener` from the `index`th socket passed by systemd socket activation.
+    ///
+    /// Each socket can be taken only once, an error is returned if it is already taken.
+    pub fn listen_fd(index: usize) -> IoResult<Self> {
+        let mut fds = inherited().lock().unwrap_or_else(|e| e.into_inner());
+        let not_found =
+            || IoError::other(format!("no inherited socket available at index {index}"));
+        match fds.take_tcp_listener(index) {
+            Ok(Some(listener)) => return Ok(listener.into()),
+            Ok(None) => return Err(not_found()),
+            // The socket is not a TCP socket, it is not taken.
+            Err(_) => {}
+        }
+        fds.take_unix_listener(index)?
+            .map(Into::into)
+            .ok_or_else(not_found)
+    }
+}
+impl From<StdTcpListener> for FdListener {
+    #[inline]
+    fn from(listener: StdTcpListener) -> Self {
+        FdListener {
+            inner: Inner::Tcp(listener),
+        }
+    }
+}
+impl From<StdUnixListener> for FdListener {
+    #[inline]
+    fn from(listener: StdUnixListener) -> Self {
+        FdListener {
+            inner: Inner::Unix(listener),
+        }
+    }
+}
+impl From<OwnedFd> for FdListener {
+    /// Creates a new `FdListener` from a listening stream socket, TCP or Unix socket is detected when it
+    /// is bound.
+    #[inline]
+    fn from(fd: OwnedFd) -> Self {
+        FdListener {
+            inner: Inner::Fd(fd),
+        }
+    }
+}
+
+impl Listener for FdListener {
+    type Acceptor = FdAcceptor;
+
+    async fn try_bind(self) -> crate::Result<Self::Acceptor> {
+        match self.inner {
+            Inner::Tcp(tcp) => Ok(FdAcceptor::Tcp(tcp.try_into()?)),
+            Inner::Unix(unix) => Ok(FdAcceptor::Unix(unix.try_into()?)),
+            Inner::Fd(fd) => {
+                let tcp = StdTcpListener::from(fd);
+                if tcp.local_addr().is_ok() {
+                    Ok(FdAcceptor::Tcp(tcp.try_into()?))
+                } else {
+                    // The address family is not inet, take it as a Unix socket.
+                    let unix = StdUnixListener::from(OwnedFd::from(tcp));
+                    Ok(FdAcceptor::Unix(unix.try_into()?))
+                }
+            }
+        }
+    }
+}
+
+/// `FdAcceptor` is used to accept connections from an inherited listening socket.
+pub enum FdAcceptor {
+    /// TCP socket acceptor.
+    Tcp(TcpAcceptor),
+    /// Unix socket acceptor.
+    Unix(UnixAcceptor),
+}
+
+impl Acceptor for FdAcceptor {
+    type Conn = JoinedStream<StraightStream<TcpStream>, StraightStream<UnixStream>>;
+
+    #[inline]
+    fn holdings(&self) -> &[Holding] {
+        match self {
+            FdAcceptor::Tcp(acceptor) => acceptor.holdings(),
+            FdAcceptor::Unix(acceptor) => acceptor.holdings(),
+        }
+    }
+
+    #[inline]
+    async fn accept(
+        &mut self,
+        fuse_factory: Option<ArcFuseFactory>,
+    ) -> IoResult<Accepted<Self::Conn>> {
+        match self {
+            FdAcceptor::Tcp(acceptor) => Ok(acceptor
+                .accept(fuse_factory)
+                .await?
+                .map_conn(JoinedStream::A)),
+            FdAcceptor::Unix(acceptor) => Ok(acceptor
+                .accept(fuse_factory)
+                .await?
+                .map_conn(JoinedStream::B)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fd_listener() {
+        let std_listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = std_listener.local_addr().unwrap();
+        let mut acceptor = FdListener::from(OwnedFd::from(std_listener)).bind().await;
+        assert!(matches!(acceptor, FdAcceptor::Tcp(_)));
+        tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_i32(150).await.unwrap();
+        });
+        let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
+        assert_eq!(conn.read_i32().await.unwrap(), 150);
+
+        let sock_file = "/tmp/test-salvo-fd.sock";
+        let _ = std::fs::remove_file(sock_file);
+        let std_listener = StdUnixListener::bind(sock_file).unwrap();
+        let mut acceptor = FdListener::from(OwnedFd::from(std_listener)).bind().await;
+        assert!(matches!(acceptor, FdAcceptor::Unix(_)));
+        tokio::spawn(async move {
+            let mut stream = UnixStream::connect(sock_file).await.unwrap();
+            stream.write_i32(518).await.unwrap();
+        });
+        let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
+        assert_eq!(conn.read_i32().await.unwrap(), 518);
+        std::fs::remove_file(sock_file).unwrap();
+    }
+
+    #[test]
+    fn test_listen_fds() {
+        assert_eq!(listen_fds_count(), 0);
+        assert!(FdListener::listen_fd(0).is_err());
+    }
+}
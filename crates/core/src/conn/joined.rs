@@ -138,7 +138,10 @@ where
         }
     }
     fn fusewire(&self) -> Option<ArcFusewire> {
-        None
+        match self {
+            JoinedStream::A(a) => a.fusewire(),
+            JoinedStream::B(b) => b.fusewire(),
+        }
     }
 }
 
@@ -0,0 +1,335 @@
+//! ConnLimitListener and it's implements.
+//!
+//! `ConnLimitListener` limits the number of concurrent connections, both in total and per source IP, it
+//! protects the server from connection exhaustion without an external load balancer. Connections over the
+//! limits are rejected as soon as they are accepted.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_core::conn::limit::{ConnLimitListener, RejectMode};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let acceptor = ConnLimitListener::new(TcpListener::new("0.0.0.0:5800"))
+//!         .max_connections(10_000)
+//!         .max_connections_per_ip(100)
+//!         .reject_mode(RejectMode::ServiceUnavailable)
+//!         .bind()
+//!         .await;
+//!     Server::new(acceptor).serve(Router::new()).await;
+//! }
+//! ```
+use std::collections::HashMap;
+use std::io::{IoSlice, Result as IoResult};
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use parking_lot::Mutex;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio_util::sync::CancellationToken;
+
+use crate::conn::{Accepted, Acceptor, Holding, HttpBuilder, Listener};
+use crate::fuse::{ArcFuseFactory, ArcFusewire};
+use crate::http::HttpConnection;
+use crate::service::HyperHandler;
+
+const SERVICE_UNAVAILABLE_RESPONSE: &[u8] =
+    b"HTTP/1.1 503 Service Unavailable\r\nconnection: close\r\ncontent-length: 0\r\n\r\n";
+
+/// How to reject the connections over the limits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RejectMode {
+    /// Close the connection immediately.
+    #[default]
+    Close,
+    /// Write an HTTP/1.1 `503 Service Unavailable` response and then close the connection.
+    ///
+    /// The response is written without TLS handshake, so it should only be used for plaintext HTTP/1 listeners.
+    ServiceUnavailable,
+}
+
+/// `ConnLimitListener` is used to limit the number of concurrent connections.
+///
+/// View [module level documentation](index.html) for more details.
+pub struct ConnLimitListener<T> {
+    inner: T,
+    max_connections: Option<usize>,
+    max_connections_per_ip: Option<usize>,
+    reject_mode: RejectMode,
+}
+impl<T> ConnLimitListener<T> {
+    /// Create new `ConnLimitListener` from inner listener, there is no limit by default.
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            max_connections: None,
+            max_connections_per_ip: None,
+            reject_mode: RejectMode::Close,
+        }
+    }
+
+    /// Set the maximum number of concurrent connections.
+    #[inline]
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// Set the maximum number of concurrent connections from the same source IP.
+    ///
+    /// Connections without an IP address, like Unix socket connections, are not limited by it.
+    #[inline]
+    pub fn max_connections_per_ip(mut self, max: usize) -> Self {
+        self.max_connections_per_ip = Some(max);
+        self
+    }
+
+    /// Set how to reject the connections over the limits. Defaults to [`RejectMode::Close`].
+    #[inline]
+    pub fn reject_mode(mut self, reject_mode: RejectMode) -> Self {
+        self.reject_mode = reject_mode;
+        self
+    }
+}
+
+impl<T> Listener for ConnLimitListener<T>
+where
+    T: Listener + Send,
+    T::Acceptor: Send + 'static,
+{
+    type Acceptor = ConnLimitAcceptor<T::Acceptor>;
+
+    async fn try_bind(self) -> crate::Result<Self::Acceptor> {
+        Ok(ConnLimitAcceptor {
+            inner: self.inner.try_bind().await?,
+            max_connections: self.max_connections,
+            max_connections_per_ip: self.max_connections_per_ip,
+            reject_mode: self.reject_mode,
+            counter: Default::default(),
+        })
+    }
+}
+
+#[derive(Default, Debug)]
+struct Counter {
+    total: usize,
+    per_ip: HashMap<IpAddr, usize>,
+}
+
+/// `ConnLimitAcceptor` is used to limit the number of concurrent connections.
+pub struct ConnLimitAcceptor<A> {
+    inner: A,
+    max_connections: Option<usize>,
+    max_connections_per_ip: Option<usize>,
+    reject_mode: RejectMode,
+    counter: Arc<Mutex<Counter>>,
+}
+
+impl<A> ConnLimitAcceptor<A> {
+    /// Get the number of current alive connections.
+    pub fn alive_connections(&self) -> usize {
+        self.counter.lock().total
+    }
+
+    fn try_acquire(&self, ip: Option<IpAddr>) -> Option<ConnGuard> {
+        let mut counter = self.counter.lock();
+        if self.max_connections.is_some_and(|max| counter.total >= max) {
+            return None;
+        }
+        if let (Some(ip), Some(max)) = (ip, self.max_connections_per_ip) {
+            let count = counter.per_ip.entry(ip).or_default();
+            if *count >= max {
+                return None;
+            }
+            *count += 1;
+        }
+        counter.total += 1;
+        Some(ConnGuard {
+            counter: self.counter.clone(),
+            ip: ip.filter(|_| self.max_connections_per_ip.is_some()),
+        })
+    }
+}
+
+impl<A> Acceptor for ConnLimitAcceptor<A>
+where
+    A: Acceptor + Send + 'static,
+{
+    type Conn = LimitedStream<A::Conn>;
+
+    #[inline]
+    fn holdings(&self) -> &[Holding] {
+        self.inner.holdings()
+    }
+
+    async fn accept(
+        &mut self,
+        fuse_factory: Option<ArcFuseFactory>,
+    ) -> IoResult<Accepted<Self::Conn>> {
+        loop {
+            let accepted = self.inner.accept(fuse_factory.clone()).await?;
+            let ip = accepted
+                .remote_addr
+                .clone()
+                .into_std()
+                .map(|addr| addr.ip());
+            if let Some(guard) = self.try_acquire(ip) {
+                return Ok(accepted.map_conn(|conn| LimitedStream {
+                    inner: conn,
+                    _guard: guard,
+                }));
+            }
+            tracing::debug!(remote_addr = %accepted.remote_addr, "connection rejected due to limits");
+            if self.reject_mode == RejectMode::ServiceUnavailable {
+                let mut conn = accepted.conn;
+                tokio::spawn(async move {
+                    let _ = conn.write_all(SERVICE_UNAVAILABLE_RESPONSE).await;
+                    let _ = conn.shutdown().await;
+                });
+            }
+        }
+    }
+}
+
+struct ConnGuard {
+    counter: Arc<Mutex<Counter>>,
+    ip: Option<IpAddr>,
+}
+impl Drop for ConnGuard {
+    fn drop(&mut self) {
+        let mut counter = self.counter.lock();
+        counter.total -= 1;
+        if let Some(ip) = &self.ip {
+            if let Some(count) = counter.per_ip.get_mut(ip) {
+                *count -= 1;
+                if *count == 0 {
+                    counter.per_ip.remove(ip);
+                }
+            }
+        }
+    }
+}
+
+/// A stream counted by [`ConnLimitAcceptor`], the count is released when the connection is closed.
+pub struct LimitedStream<C> {
+    inner: C,
+    _guard: ConnGuard,
+}
+
+impl<C> HttpConnection for LimitedStream<C>
+where
+    C: HttpConnection + Send + 'static,
+{
+    async fn serve(
+        self,
+        handler: HyperHandler,
+        builder: Arc<HttpBuilder>,
+        graceful_stop_token: Option<CancellationToken>,
+    ) -> IoResult<()> {
+        let Self { inner, _guard } = self;
+        inner.serve(handler, builder, graceful_stop_token).await
+    }
+    fn fusewire(&self) -> Option<ArcFusewire> {
+        self.inner.fusewire()
+    }
+}
+
+impl<C> AsyncRead for LimitedStream<C>
+where
+    C: AsyncRead + Unpin,
+{
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+    }
+}
+
+impl<C> AsyncWrite for LimitedStream<C>
+where
+    C: AsyncWrite + Unpin,
+{
+    #[inline]
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<IoResult<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write_vectored(cx, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<IoResult<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpStream;
+
+    use super::*;
+    use crate::conn::TcpListener;
+
+    #[tokio::test]
+    async fn test_conn_limit_listener() {
+        let mut acceptor = ConnLimitListener::new(TcpListener::new("127.0.0.1:0"))
+            .max_connections_per_ip(1)
+            .reject_mode(RejectMode::ServiceUnavailable)
+            .bind()
+            .await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+
+        let _first = TcpStream::connect(addr).await.unwrap();
+        let accepted = acceptor.accept(None).await.unwrap();
+        assert_eq!(acceptor.alive_connections(), 1);
+
+        let accept = tokio::spawn(async move {
+            let accepted = acceptor.accept(None).await.unwrap();
+            (acceptor, accepted)
+        });
+        let mut second = TcpStream::connect(addr).await.unwrap();
+        let mut response = String::new();
+        second.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 503"));
+
+        // The count is released after the first connection is dropped.
+        drop(accepted);
+        let mut third = TcpStream::connect(addr).await.unwrap();
+        third.write_all(b"ping").await.unwrap();
+        let (acceptor, mut accepted) = accept.await.unwrap();
+        let mut buf = [0; 4];
+        accepted.conn.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+        assert_eq!(acceptor.alive_connections(), 1);
+    }
+}
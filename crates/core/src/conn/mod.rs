@@ -6,6 +6,7 @@
 use std::fmt::{self, Display, Formatter};
 use std::io::Result as IoResult;
 
+use http::Extensions;
 use http::uri::Scheme;
 use tokio::io::{AsyncRead, AsyncWrite};
 
@@ -54,12 +55,23 @@ cfg_feature! {
     #![unix]
     pub mod unix;
 }
+cfg_feature! {
+    #![all(unix, feature = "socket-activation")]
+    pub mod fd;
+    pub use fd::FdListener;
+}
 pub mod addr;
 pub use addr::SocketAddr;
 
 pub mod tcp;
 pub use tcp::TcpListener;
 
+pub mod proxy_protocol;
+pub use proxy_protocol::ProxyProtocolListener;
+
+pub mod limit;
+pub use limit::ConnLimitListener;
+
 mod joined;
 pub use joined::JoinedListener;
 
@@ -78,6 +90,18 @@ pub trait IntoConfigStream<C> {
     fn into_stream(self) -> Self::Stream;
 }
 
+cfg_feature! {
+    #![any(feature = "rustls", feature = "openssl")]
+    pub mod client_cert;
+    pub use client_cert::ClientCert;
+}
+
+cfg_feature! {
+    #![any(feature = "rustls", feature = "native-tls", feature = "openssl")]
+    pub mod watch;
+    pub use watch::watch_config;
+}
+
 /// [`Acceptor`]'s return type.
 ///
 /// The `Accepted` struct represents an accepted connection and contains information such as the connection itself,
@@ -95,12 +119,31 @@ where
     pub remote_addr: SocketAddr,
     /// HTTP scheme.
     pub http_scheme: Scheme,
+    /// Connection level extensions, they are cloned into the extensions of every request on this connection.
+    pub extensions: Extensions,
 }
 
 impl<C> Accepted<C>
 where
     C: HttpConnection + AsyncRead + AsyncWrite + Unpin + Send + 'static,
 {
+    /// Create a new `Accepted` with empty connection level extensions.
+    #[inline]
+    pub fn new(
+        conn: C,
+        local_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        http_scheme: Scheme,
+    ) -> Self {
+        Accepted {
+            conn,
+            local_addr,
+            remote_addr,
+            http_scheme,
+            extensions: Extensions::new(),
+        }
+    }
+
     /// Map connection and returns a new `Accepted`.
     #[inline]
     pub fn map_conn<T>(self, wrap_fn: impl FnOnce(C) -> T) -> Accepted<T>
@@ -112,12 +155,14 @@ where
             local_addr,
             remote_addr,
             http_scheme,
+            extensions,
         } = self;
         Accepted {
             conn: wrap_fn(conn),
             local_addr,
             remote_addr,
             http_scheme,
+            extensions,
         }
     }
 }
@@ -163,6 +163,7 @@ where
             conn,
             local_addr,
             remote_addr,
+            extensions,
             ..
         } = self.inner.accept(fuse_factory.clone()).await?;
         let fusewire = conn.fusewire();
@@ -172,6 +173,7 @@ where
             local_addr,
             remote_addr,
             http_scheme: Scheme::HTTPS,
+            extensions,
         })
     }
 }
@@ -7,7 +7,7 @@ use std::path::Path;
 
 use futures_util::stream::{Once, Stream, once};
 use openssl::pkey::PKey;
-use openssl::ssl::{SslAcceptor, SslMethod};
+use openssl::ssl::{SslAcceptor, SslMethod, SslVerifyMode};
 use openssl::x509::X509;
 
 use crate::conn::IntoConfigStream;
@@ -100,6 +100,17 @@ fn alpn_protocols() -> Vec<u8> {
     alpn_protocols.into_iter().flatten().collect()
 }
 
+/// Tls client authentication configuration.
+#[derive(Clone, Debug)]
+pub enum TlsClientAuth {
+    /// No client auth.
+    Off,
+    /// Allow any anonymous or authenticated client.
+    Optional(Vec<u8>),
+    /// Allow any authenticated client.
+    Required(Vec<u8>),
+}
+
 type BuilderModifier = Box<dyn FnMut(&mut SslAcceptorBuilder) + Send + 'static>;
 /// Builder to set the configuration for the Tls server.
 #[non_exhaustive]
@@ -108,6 +119,8 @@ pub struct OpensslConfig {
     pub keycert: Keycert,
     /// Builder modifier.
     pub builder_modifier: Option<BuilderModifier>,
+    /// Client auth.
+    pub client_auth: TlsClientAuth,
     /// Protocols through ALPN (Application-Layer Protocol Negotiation).
     pub alpn_protocols: Vec<u8>,
 }
@@ -126,6 +139,7 @@ impl OpensslConfig {
         OpensslConfig {
             keycert,
             builder_modifier: None,
+            client_auth: TlsClientAuth::Off,
             alpn_protocols: alpn_protocols(),
         }
     }
@@ -139,6 +153,49 @@ impl OpensslConfig {
         self
     }
 
+    /// Sets the trust anchor for optional Tls client authentication via file path.
+    ///
+    /// Anonymous and authenticated clients will be accepted. If no trust anchor is provided by any
+    /// of the `client_auth_` methods, then client authentication is disabled by default.
+    pub fn client_auth_optional_path(mut self, path: impl AsRef<Path>) -> IoResult<Self> {
+        let mut data = vec![];
+        let mut file = File::open(path)?;
+        file.read_to_end(&mut data)?;
+        self.client_auth = TlsClientAuth::Optional(data);
+        Ok(self)
+    }
+
+    /// Sets the trust anchor for optional Tls client authentication via bytes slice.
+    ///
+    /// Anonymous and authenticated clients will be accepted. If no trust anchor is provided by any
+    /// of the `client_auth_` methods, then client authentication is disabled by default.
+    pub fn client_auth_optional(mut self, trust_anchor: impl Into<Vec<u8>>) -> Self {
+        self.client_auth = TlsClientAuth::Optional(trust_anchor.into());
+        self
+    }
+
+    /// Sets the trust anchor for required Tls client authentication via file path.
+    ///
+    /// Only authenticated clients will be accepted. If no trust anchor is provided by any of the
+    /// `client_auth_` methods, then client authentication is disabled by default.
+    pub fn client_auth_required_path(mut self, path: impl AsRef<Path>) -> IoResult<Self> {
+        let mut data = vec![];
+        let mut file = File::open(path)?;
+        file.read_to_end(&mut data)?;
+        self.client_auth = TlsClientAuth::Required(data);
+        Ok(self)
+    }
+
+    /// Sets the trust anchor for required Tls client authentication via bytes slice.
+    ///
+    /// Only authenticated clients will be accepted. If no trust anchor is provided by any of the
+    /// `client_auth_` methods, then client authentication is disabled by default.
+    #[inline]
+    pub fn client_auth_required(mut self, trust_anchor: impl Into<Vec<u8>>) -> Self {
+        self.client_auth = TlsClientAuth::Required(trust_anchor.into());
+        self
+    }
+
     /// Set specific protocols through ALPN (Application-Layer Protocol Negotiation).
     #[inline]
     pub fn alpn_protocols(mut self, alpn_protocols: impl Into<Vec<u8>>) -> Self {
@@ -161,6 +218,22 @@ impl OpensslConfig {
         certs.try_for_each(|cert| builder.add_extra_chain_cert(cert))?;
         builder.set_private_key(PKey::private_key_from_pem(self.keycert.key()?)?.as_ref())?;
 
+        let (trust_anchor, mode) = match &self.client_auth {
+            TlsClientAuth::Off => (None, SslVerifyMode::NONE),
+            TlsClientAuth::Optional(trust_anchor) => (Some(trust_anchor), SslVerifyMode::PEER),
+            TlsClientAuth::Required(trust_anchor) => (
+                Some(trust_anchor),
+                SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT,
+            ),
+        };
+        if let Some(trust_anchor) = trust_anchor {
+            for cert in X509::stack_from_pem(trust_anchor)? {
+                builder.add_client_ca(&cert)?;
+                builder.cert_store_mut().add_cert(cert)?;
+            }
+        }
+        builder.set_verify(mode);
+
         // set ALPN protocols
         let alpn_protocols = self.alpn_protocols.clone();
         builder.set_alpn_protos(&self.alpn_protocols)?;
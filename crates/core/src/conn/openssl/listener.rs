@@ -7,13 +7,16 @@ use std::task::{Context, Poll};
 
 use futures_util::stream::{BoxStream, Stream, StreamExt};
 use futures_util::task::noop_waker_ref;
+use http::Extensions;
 use http::uri::Scheme;
 use openssl::ssl::{Ssl, SslAcceptor};
 use tokio_openssl::SslStream;
 
 use super::SslAcceptorBuilder;
 
-use crate::conn::{Accepted, Acceptor, HandshakeStream, Holding, IntoConfigStream, Listener};
+use crate::conn::{
+    Accepted, Acceptor, ClientCert, HandshakeStream, Holding, IntoConfigStream, Listener,
+};
 use crate::fuse::ArcFuseFactory;
 use crate::http::HttpConnection;
 
@@ -163,6 +166,7 @@ where
             conn,
             local_addr,
             remote_addr,
+            extensions,
             ..
         } = self.inner.accept(fuse_factory).await?;
         let fusewire = conn.fusewire();
@@ -177,10 +181,32 @@ where
         };
 
         Ok(Accepted {
-            conn: HandshakeStream::new(conn, fusewire),
+            conn: HandshakeStream::new(conn, fusewire).with_conn_info(conn_info::<T::Conn>),
             local_addr,
             remote_addr,
             http_scheme: Scheme::HTTPS,
+            extensions,
         })
     }
 }
+
+fn conn_info<IO>(stream: &SslStream<IO>) -> Extensions {
+    let mut extensions = Extensions::new();
+    let ssl = stream.ssl();
+    if let Some(leaf) = ssl.peer_certificate().and_then(|cert| cert.to_der().ok()) {
+        let mut chain = vec![leaf.clone()];
+        // The peer cert chain does not contain the leaf certificate on the server side.
+        if let Some(certs) = ssl.peer_cert_chain() {
+            chain.extend(
+                certs
+                    .iter()
+                    .filter_map(|cert| cert.to_der().ok())
+                    .filter(|der| *der != leaf),
+            );
+        }
+        if let Some(cert) = ClientCert::from_der_chain(chain) {
+            extensions.insert(cert);
+        }
+    }
+    extensions
+}
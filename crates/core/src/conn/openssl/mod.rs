@@ -1,6 +1,6 @@
 //! OpensslListener and utils.
 mod config;
-pub use config::{Keycert, OpensslConfig, SslAcceptorBuilder};
+pub use config::{Keycert, OpensslConfig, SslAcceptorBuilder, TlsClientAuth};
 
 mod listener;
 pub use listener::{OpensslAcceptor, OpensslListener};
@@ -22,17 +22,41 @@ type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>
 
 #[cfg(feature = "http2")]
 use crate::rt::tokio::TokioExecutor;
+#[cfg(any(feature = "http1", feature = "http2"))]
+use crate::rt::tokio::TokioTimer;
 #[cfg(feature = "http1")]
 use hyper::server::conn::http1;
 #[cfg(feature = "http2")]
 use hyper::server::conn::http2;
+#[cfg(any(feature = "http1", feature = "http2"))]
+use std::time::Duration;
 
 #[cfg(feature = "quinn")]
 use crate::conn::quinn;
 
 const H2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
 
-#[doc(hidden)]
+/// Builder to tune the HTTP protocols used to serve connections.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use salvo_core::conn::HttpBuilder;
+/// use salvo_core::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+///     let builder = HttpBuilder::new()
+///         .http1_header_read_timeout(Duration::from_secs(10))
+///         .http1_max_buf_size(64 * 1024)
+///         .http2_max_concurrent_streams(256)
+///         .http2_initial_stream_window_size(1024 * 1024);
+///     Server::with_http_builder(acceptor, builder).serve(Router::new()).await;
+/// }
+/// ```
 pub struct HttpBuilder {
     #[cfg(feature = "http1")]
     pub(crate) http1: http1::Builder,
@@ -48,17 +72,113 @@ impl Default for HttpBuilder {
 }
 
 impl HttpBuilder {
+    /// Create a new `HttpBuilder` with default options.
     pub fn new() -> Self {
+        #[cfg(feature = "http1")]
+        let mut http1 = http1::Builder::new();
+        #[cfg(feature = "http1")]
+        http1.timer(TokioTimer::new());
+        #[cfg(feature = "http2")]
+        let mut http2 = http2::Builder::new(TokioExecutor::new());
+        #[cfg(feature = "http2")]
+        http2.timer(TokioTimer::new());
         Self {
             #[cfg(feature = "http1")]
-            http1: http1::Builder::new(),
+            http1,
             #[cfg(feature = "http2")]
-            http2: http2::Builder::new(crate::rt::tokio::TokioExecutor::new()),
+            http2,
             #[cfg(feature = "quinn")]
             quinn: crate::conn::quinn::Builder::new(),
         }
     }
 
+    cfg_feature! {
+        #![feature = "http1"]
+        /// Sets whether HTTP/1 connections should support keep-alive. Defaults to `true`.
+        #[inline]
+        pub fn http1_keep_alive(mut self, enabled: bool) -> Self {
+            self.http1.keep_alive(enabled);
+            self
+        }
+        /// Sets whether HTTP/1 connections should support half-closures. Defaults to `false`.
+        ///
+        /// Clients can choose to shutdown their write-side while waiting for the server to respond. If it is
+        /// enabled, the read-side closing will not close the connection.
+        #[inline]
+        pub fn http1_half_close(mut self, enabled: bool) -> Self {
+            self.http1.half_close(enabled);
+            self
+        }
+        /// Sets the maximum buffer size for HTTP/1 connections, it limits the size of the request headers.
+        /// Defaults to about 400kb.
+        #[inline]
+        pub fn http1_max_buf_size(mut self, max: usize) -> Self {
+            self.http1.max_buf_size(max);
+            self
+        }
+        /// Sets a timeout for reading the request headers of HTTP/1 connections, the connection is closed if
+        /// the headers are not received in time. Defaults to 30 seconds.
+        #[inline]
+        pub fn http1_header_read_timeout(mut self, timeout: Duration) -> Self {
+            self.http1.header_read_timeout(timeout);
+            self
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "http2"]
+        /// Sets the maximum number of concurrent streams for HTTP/2 connections. Defaults to 200.
+        #[inline]
+        pub fn http2_max_concurrent_streams(mut self, max: u32) -> Self {
+            self.http2.max_concurrent_streams(max);
+            self
+        }
+        /// Sets the initial window size of HTTP/2 stream-level flow control. Defaults to 1MB.
+        #[inline]
+        pub fn http2_initial_stream_window_size(mut self, size: u32) -> Self {
+            self.http2.initial_stream_window_size(size);
+            self
+        }
+        /// Sets the initial window size of HTTP/2 connection-level flow control. Defaults to 1MB.
+        #[inline]
+        pub fn http2_initial_connection_window_size(mut self, size: u32) -> Self {
+            self.http2.initial_connection_window_size(size);
+            self
+        }
+        /// Sets whether to use an adaptive flow control for HTTP/2 connections, it overrides the initial
+        /// window sizes.
+        #[inline]
+        pub fn http2_adaptive_window(mut self, enabled: bool) -> Self {
+            self.http2.adaptive_window(enabled);
+            self
+        }
+        /// Sets the maximum frame size for HTTP/2 connections. Defaults to 16KB.
+        #[inline]
+        pub fn http2_max_frame_size(mut self, size: u32) -> Self {
+            self.http2.max_frame_size(size);
+            self
+        }
+        /// Sets the maximum size of received header list for HTTP/2 connections. Defaults to 16KB.
+        #[inline]
+        pub fn http2_max_header_list_size(mut self, size: u32) -> Self {
+            self.http2.max_header_list_size(size);
+            self
+        }
+        /// Sets the interval of HTTP/2 ping frames to keep the connection alive. Disabled by default.
+        #[inline]
+        pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+            self.http2.keep_alive_interval(interval);
+            self
+        }
+        /// Sets the timeout for receiving an acknowledgement of the HTTP/2 keep-alive ping, the connection is
+        /// closed if it is not received in time. Defaults to 20 seconds.
+        #[inline]
+        pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+            self.http2.keep_alive_timeout(timeout);
+            self
+        }
+    }
+
     /// Serve a connection with the given service.
     #[allow(unused_variables)]
     pub async fn serve_connection<I, S, B>(
@@ -0,0 +1,433 @@
+//! ProxyProtocolListener and it's implements.
+//!
+//! When the server is deployed behind a load balancer in TCP mode, such as AWS NLB or HAProxy, the load balancer
+//! can send the [PROXY protocol](https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt) header at the
+//! beginning of each connection. `ProxyProtocolListener` parses the header (both v1 and v2 are supported) and
+//! uses the real client address as the `remote_addr` of the connection. The parsed header is also inserted into
+//! the extensions of every request as [`ProxyHeader`].
+//!
+//! The header is required, connections without a valid header are closed. The headers are read concurrently,
+//! so a slow client does not block accepting other connections.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_core::conn::proxy_protocol::ProxyProtocolListener;
+//!
+//! #[handler]
+//! async fn hello(req: &mut Request) -> String {
+//!     format!("Hello {}", req.remote_addr())
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let acceptor = ProxyProtocolListener::new(TcpListener::new("0.0.0.0:5800")).bind().await;
+//!     Server::new(acceptor).serve(Router::new().get(hello)).await;
+//! }
+//! ```
+use std::io::{Error as IoError, ErrorKind, Result as IoResult};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr as StdSocketAddr};
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use futures_util::future::BoxFuture;
+use futures_util::stream::FuturesUnordered;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::conn::{Accepted, Acceptor, Holding, Listener};
+use crate::fuse::ArcFuseFactory;
+use crate::http::uri::Scheme;
+
+const V2_SIGNATURE: &[u8; 12] = b"\r\n\r\n\0\r\nQUIT\n";
+const V1_MAX_LENGTH: usize = 107;
+
+const PP2_TYPE_ALPN: u8 = 0x01;
+const PP2_TYPE_AUTHORITY: u8 = 0x02;
+const PP2_TYPE_SSL: u8 = 0x20;
+const PP2_SUBTYPE_SSL_VERSION: u8 = 0x21;
+const PP2_SUBTYPE_SSL_CN: u8 = 0x22;
+const PP2_SUBTYPE_SSL_CIPHER: u8 = 0x23;
+const PP2_CLIENT_SSL: u8 = 0x01;
+
+/// The TLS information of the client connection to the proxy, only available in PROXY protocol v2.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ProxyTlsInfo {
+    /// Whether the client certificate is verified.
+    pub verified: bool,
+    /// The TLS version, for example: `TLSv1.3`.
+    pub version: Option<String>,
+    /// The TLS cipher, for example: `ECDHE-RSA-AES128-GCM-SHA256`.
+    pub cipher: Option<String>,
+    /// The common name of the client certificate.
+    pub client_cn: Option<String>,
+}
+
+/// The parsed PROXY protocol header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ProxyHeader {
+    /// The PROXY protocol version, `1` or `2`.
+    pub version: u8,
+    /// The source address, it is the real client address. `None` if the address is unknown or the connection
+    /// is established by the proxy itself, like health checks.
+    pub source: Option<StdSocketAddr>,
+    /// The destination address.
+    pub destination: Option<StdSocketAddr>,
+    /// The TLS information if the client connected to the proxy over TLS.
+    pub tls: Option<ProxyTlsInfo>,
+    /// The application protocol negotiated by ALPN.
+    pub alpn: Option<Vec<u8>>,
+    /// The host name sent by the client through SNI.
+    pub authority: Option<String>,
+}
+
+/// `ProxyProtocolListener` is used to accept connections which start with PROXY protocol header.
+///
+/// View [module level documentation](index.html) for more details.
+pub struct ProxyProtocolListener<T> {
+    inner: T,
+    timeout: Duration,
+}
+impl<T> ProxyProtocolListener<T> {
+    /// Create new `ProxyProtocolListener` from inner listener, the default header read timeout is 5 seconds.
+    #[inline]
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Set the timeout of reading PROXY protocol header.
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl<T> Listener for ProxyProtocolListener<T>
+where
+    T: Listener + Send,
+    T::Acceptor: Send + 'static,
+{
+    type Acceptor = ProxyProtocolAcceptor<T::Acceptor>;
+
+    async fn try_bind(self) -> crate::Result<Self::Acceptor> {
+        Ok(ProxyProtocolAcceptor {
+            inner: self.inner.try_bind().await?,
+            timeout: self.timeout,
+            pending: FuturesUnordered::new(),
+        })
+    }
+}
+
+/// `ProxyProtocolAcceptor` is used to accept connections which start with PROXY protocol header.
+pub struct ProxyProtocolAcceptor<A: Acceptor> {
+    inner: A,
+    timeout: Duration,
+    pending: FuturesUnordered<BoxFuture<'static, IoResult<Accepted<A::Conn>>>>,
+}
+
+impl<A> Acceptor for ProxyProtocolAcceptor<A>
+where
+    A: Acceptor + Send + 'static,
+{
+    type Conn = A::Conn;
+
+    #[inline]
+    fn holdings(&self) -> &[Holding] {
+        self.inner.holdings()
+    }
+
+    async fn accept(
+        &mut self,
+        fuse_factory: Option<ArcFuseFactory>,
+    ) -> IoResult<Accepted<Self::Conn>> {
+        loop {
+            tokio::select! {
+                accepted = self.inner.accept(fuse_factory.clone()) => {
+                    let accepted = accepted?;
+                    let timeout = self.timeout;
+                    self.pending.push(Box::pin(async move {
+                        tokio::time::timeout(timeout, read_header(accepted))
+                            .await
+                            .map_err(|_| IoError::new(ErrorKind::TimedOut, "read proxy protocol header timeout"))?
+                    }));
+                }
+                Some(result) = self.pending.next(), if !self.pending.is_empty() => {
+                    match result {
+                        Ok(accepted) => return Ok(accepted),
+                        Err(e) => tracing::warn!(error = ?e, "proxy protocol header is invalid"),
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn read_header<C>(mut accepted: Accepted<C>) -> IoResult<Accepted<C>>
+where
+    C: crate::http::HttpConnection + AsyncRead + Unpin,
+{
+    let header = parse_header(&mut accepted.conn).await?;
+    if let Some(source) = header.source {
+        accepted.remote_addr = source.into();
+    }
+    if header.tls.is_some() {
+        accepted.http_scheme = Scheme::HTTPS;
+    }
+    accepted.extensions.insert(header);
+    Ok(accepted)
+}
+
+fn invalid(msg: &str) -> IoError {
+    IoError::new(ErrorKind::InvalidData, format!("proxy protocol: {msg}"))
+}
+
+pub(crate) async fn parse_header<R>(reader: &mut R) -> IoResult<ProxyHeader>
+where
+    R: AsyncRead + Unpin,
+{
+    // Both v1 header (at least `PROXY UNKNOWN\r\n`) and v2 header are longer than 12 bytes.
+    let mut buf = vec![0; V2_SIGNATURE.len()];
+    reader.read_exact(&mut buf).await?;
+    if buf == V2_SIGNATURE {
+        parse_v2(reader).await
+    } else if buf.starts_with(b"PROXY ") {
+        while !buf.ends_with(b"\r\n") {
+            if buf.len() >= V1_MAX_LENGTH {
+                return Err(invalid("v1 header is too long"));
+            }
+            buf.push(reader.read_u8().await?);
+        }
+        parse_v1(&buf[..buf.len() - 2])
+    } else {
+        Err(invalid("header is missing"))
+    }
+}
+
+fn parse_v1(line: &[u8]) -> IoResult<ProxyHeader> {
+    let line = std::str::from_utf8(line).map_err(|_| invalid("v1 header is not utf8"))?;
+    let parts = line.split(' ').collect::<Vec<_>>();
+    let mut header = ProxyHeader {
+        version: 1,
+        ..Default::default()
+    };
+    match parts.get(1).copied() {
+        Some("UNKNOWN") => Ok(header),
+        Some("TCP4") | Some("TCP6") if parts.len() == 6 => {
+            let parse_ip = |s: &str| {
+                s.parse::<IpAddr>()
+                    .map_err(|_| invalid("v1 address is invalid"))
+            };
+            let parse_port = |s: &str| s.parse::<u16>().map_err(|_| invalid("v1 port is invalid"));
+            header.source = Some(StdSocketAddr::new(
+                parse_ip(parts[2])?,
+                parse_port(parts[4])?,
+            ));
+            header.destination = Some(StdSocketAddr::new(
+                parse_ip(parts[3])?,
+                parse_port(parts[5])?,
+            ));
+            Ok(header)
+        }
+        _ => Err(invalid("v1 protocol is invalid")),
+    }
+}
+
+async fn parse_v2<R>(reader: &mut R) -> IoResult<ProxyHeader>
+where
+    R: AsyncRead + Unpin,
+{
+    let ver_cmd = reader.read_u8().await?;
+    let family = reader.read_u8().await?;
+    let len = reader.read_u16().await? as usize;
+    if ver_cmd >> 4 != 2 {
+        return Err(invalid("v2 version is invalid"));
+    }
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload).await?;
+
+    let mut header = ProxyHeader {
+        version: 2,
+        ..Default::default()
+    };
+    // The LOCAL command means the connection is established by the proxy itself, the addresses are ignored.
+    let is_proxy = ver_cmd & 0x0F == 1;
+    let addr_len = match family >> 4 {
+        1 => 12,
+        2 => 36,
+        3 => 216,
+        _ => 0,
+    };
+    if payload.len() < addr_len {
+        return Err(invalid("v2 address is truncated"));
+    }
+    if is_proxy {
+        match family >> 4 {
+            1 => {
+                let src = Ipv4Addr::new(payload[0], payload[1], payload[2], payload[3]);
+                let dst = Ipv4Addr::new(payload[4], payload[5], payload[6], payload[7]);
+                let src_port = u16::from_be_bytes([payload[8], payload[9]]);
+                let dst_port = u16::from_be_bytes([payload[10], payload[11]]);
+                header.source = Some(StdSocketAddr::new(src.into(), src_port));
+                header.destination = Some(StdSocketAddr::new(dst.into(), dst_port));
+            }
+            2 => {
+                let mut src = [0; 16];
+                src.copy_from_slice(&payload[0..16]);
+                let mut dst = [0; 16];
+                dst.copy_from_slice(&payload[16..32]);
+                let (src, dst) = (Ipv6Addr::from(src), Ipv6Addr::from(dst));
+                let src_port = u16::from_be_bytes([payload[32], payload[33]]);
+                let dst_port = u16::from_be_bytes([payload[34], payload[35]]);
+                header.source = Some(StdSocketAddr::new(src.into(), src_port));
+                header.destination = Some(StdSocketAddr::new(dst.into(), dst_port));
+            }
+            _ => {}
+        }
+    }
+
+    for (kind, value) in tlvs(&payload[addr_len..])? {
+        match kind {
+            PP2_TYPE_ALPN => header.alpn = Some(value.to_vec()),
+            PP2_TYPE_AUTHORITY => {
+                header.authority = Some(String::from_utf8_lossy(value).into_owned())
+            }
+            PP2_TYPE_SSL if value.len() >= 5 => {
+                if value[0] & PP2_CLIENT_SSL == 0 {
+                    continue;
+                }
+                let verify = u32::from_be_bytes([value[1], value[2], value[3], value[4]]);
+                let mut tls = ProxyTlsInfo {
+                    verified: verify == 0,
+                    ..Default::default()
+                };
+                for (kind, value) in tlvs(&value[5..])? {
+                    let value = Some(String::from_utf8_lossy(value).into_owned());
+                    match kind {
+                        PP2_SUBTYPE_SSL_VERSION => tls.version = value,
+                        PP2_SUBTYPE_SSL_CN => tls.client_cn = value,
+                        PP2_SUBTYPE_SSL_CIPHER => tls.cipher = value,
+                        _ => {}
+                    }
+                }
+                header.tls = Some(tls);
+            }
+            _ => {}
+        }
+    }
+    Ok(header)
+}
+
+fn tlvs(mut data: &[u8]) -> IoResult<Vec<(u8, &[u8])>> {
+    let mut tlvs = vec![];
+    while !data.is_empty() {
+        if data.len() < 3 {
+            return Err(invalid("v2 tlv is truncated"));
+        }
+        let len = u16::from_be_bytes([data[1], data[2]]) as usize;
+        if data.len() < 3 + len {
+            return Err(invalid("v2 tlv is truncated"));
+        }
+        tlvs.push((data[0], &data[3..3 + len]));
+        data = &data[3 + len..];
+    }
+    Ok(tlvs)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    use super::*;
+    use crate::conn::TcpListener;
+
+    #[tokio::test]
+    async fn test_parse_v1() {
+        let mut data = &b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\nGET /"[..];
+        let header = parse_header(&mut data).await.unwrap();
+        assert_eq!(header.version, 1);
+        assert_eq!(header.source, Some("192.168.0.1:56324".parse().unwrap()));
+        assert_eq!(
+            header.destination,
+            Some("192.168.0.11:443".parse().unwrap())
+        );
+        assert_eq!(data, b"GET /");
+
+        let mut data = &b"PROXY UNKNOWN\r\n"[..];
+        let header = parse_header(&mut data).await.unwrap();
+        assert_eq!(header.source, None);
+
+        let mut data = &b"GET / HTTP/1.1\r\n"[..];
+        assert!(parse_header(&mut data).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parse_v2() {
+        let mut data = V2_SIGNATURE.to_vec();
+        data.extend_from_slice(&[0x21, 0x11]);
+        let mut payload = vec![10, 0, 0, 1, 10, 0, 0, 2, 0x1F, 0x90, 0x01, 0xBB];
+        let mut ssl = vec![PP2_CLIENT_SSL, 0, 0, 0, 0];
+        ssl.extend_from_slice(&[PP2_SUBTYPE_SSL_VERSION, 0, 7]);
+        ssl.extend_from_slice(b"TLSv1.3");
+        payload.extend_from_slice(&[PP2_TYPE_SSL, 0, ssl.len() as u8]);
+        payload.extend_from_slice(&ssl);
+        payload.extend_from_slice(&[PP2_TYPE_AUTHORITY, 0, 11]);
+        payload.extend_from_slice(b"example.com");
+        data.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        data.extend_from_slice(&payload);
+        data.extend_from_slice(b"GET /");
+
+        let mut data = &data[..];
+        let header = parse_header(&mut data).await.unwrap();
+        assert_eq!(header.version, 2);
+        assert_eq!(header.source, Some("10.0.0.1:8080".parse().unwrap()));
+        assert_eq!(header.destination, Some("10.0.0.2:443".parse().unwrap()));
+        assert_eq!(header.authority.as_deref(), Some("example.com"));
+        let tls = header.tls.unwrap();
+        assert!(tls.verified);
+        assert_eq!(tls.version.as_deref(), Some("TLSv1.3"));
+        assert_eq!(data, b"GET /");
+    }
+
+    #[tokio::test]
+    async fn test_proxy_protocol_listener() {
+        let mut acceptor = ProxyProtocolListener::new(TcpListener::new("127.0.0.1:0"))
+            .bind()
+            .await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+        tokio::spawn(async move {
+            // The connection without header is dropped and does not block the next one.
+            let _stream = TcpStream::connect(addr).await.unwrap();
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\n")
+                .await
+                .unwrap();
+            stream.write_i32(150).await.unwrap();
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        });
+
+        let Accepted {
+            mut conn,
+            remote_addr,
+            extensions,
+            ..
+        } = acceptor.accept(None).await.unwrap();
+        assert_eq!(
+            remote_addr.into_std(),
+            Some("192.168.0.1:56324".parse().unwrap())
+        );
+        assert_eq!(extensions.get::<ProxyHeader>().unwrap().version, 1);
+        assert_eq!(conn.read_i32().await.unwrap(), 150);
+    }
+}
@@ -152,6 +152,7 @@ where
                         local_addr: self.holdings[0].local_addr.clone(),
                         remote_addr: remote_addr.into(),
                         http_scheme: self.holdings[0].http_scheme.clone(),
+                        extensions: Default::default(),
                     });
                 }
                 Err(e) => return Err(IoError::other(e.to_string())),
@@ -6,6 +6,7 @@ use std::io::{Error as IoError, Read, Result as IoResult};
 use std::path::Path;
 use std::sync::Arc;
 
+use futures_util::future::BoxFuture;
 use futures_util::stream::{once, Once, Stream};
 use tokio_rustls::rustls::crypto::ring::sign::any_supported_type;
 use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
@@ -82,7 +83,7 @@ impl Keycert {
         &self.ocsp_resp
     }
 
-    fn build_certified_key(&mut self) -> IoResult<CertifiedKey> {
+    pub(crate) fn build_certified_key(&mut self) -> IoResult<CertifiedKey> {
         let cert = rustls_pemfile::certs(&mut self.cert.as_ref())
             .flat_map(|certs| certs.into_iter().collect::<Vec<CertificateDer<'static>>>())
             .collect::<Vec<_>>();
@@ -160,6 +161,8 @@ pub struct RustlsConfig {
     pub keycerts: HashMap<String, Keycert>,
     /// Client auth.
     pub client_auth: TlsClientAuth,
+    /// PEM encoded certificate revocation lists used to check client certificates.
+    pub client_auth_crls: Vec<u8>,
     /// Protocols through ALPN (Application-Layer Protocol Negotiation).
     pub alpn_protocols: Vec<Vec<u8>>,
 }
@@ -173,6 +176,7 @@ impl RustlsConfig {
             fallback: fallback.into(),
             keycerts: HashMap::new(),
             client_auth: TlsClientAuth::Off,
+            client_auth_crls: vec![],
             alpn_protocols: alpn_protocols(),
         }
     }
@@ -220,7 +224,27 @@ impl RustlsConfig {
         self
     }
 
+    /// Sets the certificate revocation lists used to check client certificates via file path.
+    pub fn client_auth_crls_path(mut self, path: impl AsRef<Path>) -> IoResult<Self> {
+        let mut data = vec![];
+        let mut file = File::open(path)?;
+        file.read_to_end(&mut data)?;
+        self.client_auth_crls = data;
+        Ok(self)
+    }
+
+    /// Sets the PEM encoded certificate revocation lists used to check client certificates via bytes slice.
+    #[inline]
+    pub fn client_auth_crls(mut self, crls: impl Into<Vec<u8>>) -> Self {
+        self.client_auth_crls = crls.into();
+        self
+    }
+
     /// Add a new keycert to be used for the given SNI `name`.
+    ///
+    /// The `name` can be a wildcard name like `*.example.com`, which matches `www.example.com` but not
+    /// `example.com`. Exact names take precedence over wildcard names, and the fallback keycert is used if no
+    /// name matches.
     #[inline]
     pub fn keycert(mut self, name: impl Into<String>, keycert: Keycert) -> Self {
         self.keycerts.insert(name.into(), keycert);
@@ -248,16 +272,20 @@ impl RustlsConfig {
             certified_keys.insert(name.clone(), Arc::new(keycert.build_certified_key()?));
         }
 
+        let crls = rustls_pemfile::crls(&mut self.client_auth_crls.as_slice())
+            .collect::<IoResult<Vec<_>>>()?;
         let client_auth = match &self.client_auth {
             TlsClientAuth::Off => WebPkiClientVerifier::no_client_auth(),
             TlsClientAuth::Optional(trust_anchor) => {
                 WebPkiClientVerifier::builder(read_trust_anchor(trust_anchor)?.into())
+                    .with_crls(crls)
                     .allow_unauthenticated()
                     .build()
                     .map_err(|e| IoError::other(format!("failed to build server config: {}", e)))?
             }
             TlsClientAuth::Required(trust_anchor) => {
                 WebPkiClientVerifier::builder(read_trust_anchor(trust_anchor)?.into())
+                    .with_crls(crls)
                     .build()
                     .map_err(|e| IoError::other(format!("failed to build server config: {}", e)))?
             }
@@ -302,6 +330,21 @@ cfg_feature! {
     }
 }
 
+/// Resolve keycert by SNI server name asynchronously, it is used by [`RustlsListener::cert_resolver`].
+///
+/// [`RustlsListener::cert_resolver`]: super::RustlsListener::cert_resolver
+pub(crate) type ArcCertResolver =
+    Arc<dyn Fn(Option<String>) -> BoxFuture<'static, Option<Keycert>> + Send + Sync>;
+
+#[derive(Debug)]
+pub(crate) struct SingleCertResolver(pub(crate) Arc<CertifiedKey>);
+
+impl ResolvesServerCert for SingleCertResolver {
+    fn resolve(&self, _client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        Some(self.0.clone())
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct CertResolver {
     fallback: Option<Arc<CertifiedKey>>,
@@ -312,7 +355,14 @@ impl ResolvesServerCert for CertResolver {
     fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
         client_hello
             .server_name()
-            .and_then(|name| self.certified_keys.get(name).cloned())
+            .and_then(|name| {
+                self.certified_keys.get(name).or_else(|| {
+                    // Match wildcard certificate such as `*.example.com`.
+                    let (_, parent) = name.split_once('.')?;
+                    self.certified_keys.get(&format!("*.{parent}"))
+                })
+            })
+            .cloned()
             .or_else(|| self.fallback.clone())
     }
 }
@@ -6,22 +6,30 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use futures_util::FutureExt;
 use futures_util::stream::{BoxStream, Stream, StreamExt};
 use futures_util::task::noop_waker_ref;
+use http::Extensions;
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::LazyConfigAcceptor;
+use tokio_rustls::rustls::server::Acceptor as RustlsServerAcceptor;
 use tokio_rustls::server::TlsStream;
 
-use crate::conn::{Accepted, Acceptor, HandshakeStream, Holding, IntoConfigStream, Listener};
+use crate::conn::{
+    Accepted, Acceptor, ClientCert, HandshakeStream, Holding, IntoConfigStream, Listener,
+};
 use crate::fuse::ArcFuseFactory;
 use crate::http::HttpConnection;
 use crate::http::uri::Scheme;
 
-use super::ServerConfig;
+use super::config::{ArcCertResolver, SingleCertResolver};
+use super::{Keycert, ServerConfig};
 
 /// A wrapper of `Listener` with rustls.
 pub struct RustlsListener<S, C, T, E> {
     config_stream: S,
     inner: T,
+    cert_resolver: Option<ArcCertResolver>,
     _phantom: PhantomData<(C, E)>,
 }
 
@@ -38,9 +46,25 @@ where
         RustlsListener {
             config_stream,
             inner,
+            cert_resolver: None,
             _phantom: PhantomData,
         }
     }
+
+    /// Set an async resolver to look up the keycert by SNI server name during handshake, for example, from a
+    /// database.
+    ///
+    /// The resolver is called on every handshake, and the keycerts in config are used if it returns `None`.
+    /// The resolver should cache the keycerts by itself if the lookup is expensive.
+    #[inline]
+    pub fn cert_resolver<F, Fut>(mut self, resolver: F) -> Self
+    where
+        F: Fn(Option<String>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<Keycert>> + Send + 'static,
+    {
+        self.cert_resolver = Some(Arc::new(move |server_name| resolver(server_name).boxed()));
+        self
+    }
 }
 
 impl<S, C, T, E> Listener for RustlsListener<S, C, T, E>
@@ -54,10 +78,12 @@ where
     type Acceptor = RustlsAcceptor<BoxStream<'static, C>, C, T::Acceptor, E>;
 
     async fn try_bind(self) -> crate::Result<Self::Acceptor> {
-        Ok(RustlsAcceptor::new(
+        let mut acceptor = RustlsAcceptor::new(
             self.config_stream.into_stream().boxed(),
             self.inner.try_bind().await?,
-        ))
+        );
+        acceptor.cert_resolver = self.cert_resolver;
+        Ok(acceptor)
     }
 }
 
@@ -67,6 +93,7 @@ pub struct RustlsAcceptor<S, C, T, E> {
     inner: T,
     holdings: Vec<Holding>,
     tls_acceptor: Option<tokio_rustls::TlsAcceptor>,
+    cert_resolver: Option<ArcCertResolver>,
     _phantom: PhantomData<(C, E)>,
 }
 impl<S, C, T, E> RustlsAcceptor<S, C, T, E>
@@ -104,6 +131,7 @@ where
             inner,
             holdings,
             tls_acceptor: None,
+            cert_resolver: None,
             _phantom: PhantomData,
         }
     }
@@ -164,14 +192,137 @@ where
             conn,
             local_addr,
             remote_addr,
+            extensions,
             ..
         } = self.inner.accept(fuse_factory).await?;
         let fusewire = conn.fusewire();
+        let conn = match self.cert_resolver.clone() {
+            Some(resolver) => {
+                let config = tls_acceptor.config().clone();
+                HandshakeStream::new(
+                    async move {
+                        let start =
+                            LazyConfigAcceptor::new(RustlsServerAcceptor::default(), conn).await?;
+                        let server_name = start.client_hello().server_name().map(ToOwned::to_owned);
+                        let config = match resolver(server_name).await {
+                            Some(mut keycert) => {
+                                let certified_key = keycert.build_certified_key()?;
+                                let mut config = (*config).clone();
+                                config.cert_resolver =
+                                    Arc::new(SingleCertResolver(Arc::new(certified_key)));
+                                Arc::new(config)
+                            }
+                            None => config,
+                        };
+                        start.into_stream(config).await
+                    },
+                    fusewire,
+                )
+            }
+            None => HandshakeStream::new(tls_acceptor.accept(conn), fusewire),
+        };
         Ok(Accepted {
-            conn: HandshakeStream::new(tls_acceptor.accept(conn), fusewire),
+            conn: conn.with_conn_info(conn_info::<T::Conn>),
             local_addr,
             remote_addr,
             http_scheme: Scheme::HTTPS,
+            extensions,
         })
     }
 }
+
+fn conn_info<IO>(stream: &TlsStream<IO>) -> Extensions {
+    let mut extensions = Extensions::new();
+    let chain = stream
+        .get_ref()
+        .1
+        .peer_certificates()
+        .map(|certs| certs.iter().map(|cert| cert.to_vec()).collect());
+    if let Some(cert) = chain.and_then(ClientCert::from_der_chain) {
+        extensions.insert(cert);
+    }
+    extensions
+}
+
+cfg_feature! {
+    #![feature = "quinn"]
+
+    impl<S, A> RustlsListener<S, super::RustlsConfig, crate::conn::TcpListener<A>, IoError>
+    where
+        S: IntoConfigStream<super::RustlsConfig> + Send + 'static,
+        A: tokio::net::ToSocketAddrs + std::net::ToSocketAddrs + Clone + Send,
+    {
+        /// Creates a `JoinedListener` which also serves HTTP/3 by quinn on the same address, and the TLS config is
+        /// shared by both listeners.
+        ///
+        /// The `Alt-Svc` header advertising the HTTP/3 endpoint is added to responses automatically, see
+        /// [`Server::alt_svc_max_age`](crate::Server::alt_svc_max_age).
+        ///
+        /// **NOTE**: The address should have a specified port, since port `0` will be allocated twice.
+        #[allow(clippy::type_complexity)]
+        pub fn quinn(
+            self,
+        ) -> crate::conn::JoinedListener<
+            RustlsListener<ConfigTee<super::RustlsConfig>, super::RustlsConfig, crate::conn::TcpListener<A>, IoError>,
+            crate::conn::QuinnListener<ConfigTee<super::RustlsConfig>, super::RustlsConfig, A, IoError>,
+        > {
+            let Self {
+                config_stream,
+                inner,
+                cert_resolver,
+                ..
+            } = self;
+            let local_addr = inner.local_addr.clone();
+            let (a, b) = ConfigTee::pair(config_stream.into_stream().boxed());
+            let tcp = RustlsListener {
+                config_stream: a,
+                inner,
+                cert_resolver,
+                _phantom: PhantomData,
+            };
+            tcp.join(crate::conn::QuinnListener::new(b, local_addr))
+        }
+    }
+
+    /// A config stream shared by two listeners, every config is cloned to both of them.
+    pub struct ConfigTee<C> {
+        shared: Arc<parking_lot::Mutex<TeeShared<C>>>,
+        index: usize,
+    }
+    struct TeeShared<C> {
+        stream: BoxStream<'static, C>,
+        queues: [std::collections::VecDeque<C>; 2],
+    }
+    impl<C> ConfigTee<C> {
+        pub(crate) fn pair(stream: BoxStream<'static, C>) -> (Self, Self) {
+            let shared = Arc::new(parking_lot::Mutex::new(TeeShared {
+                stream,
+                queues: Default::default(),
+            }));
+            (
+                ConfigTee {
+                    shared: shared.clone(),
+                    index: 0,
+                },
+                ConfigTee { shared, index: 1 },
+            )
+        }
+    }
+    impl<C: Clone> Stream for ConfigTee<C> {
+        type Item = C;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<C>> {
+            let mut shared = self.shared.lock();
+            if let Some(item) = shared.queues[self.index].pop_front() {
+                return Poll::Ready(Some(item));
+            }
+            match shared.stream.poll_next_unpin(cx) {
+                Poll::Ready(Some(item)) => {
+                    shared.queues[1 - self.index].push_back(item.clone());
+                    Poll::Ready(Some(item))
+                }
+                poll => poll,
+            }
+        }
+    }
+}
@@ -4,10 +4,14 @@ use std::io::{Error as IoError,  Result as IoResult};
 use tokio_rustls::rustls::RootCertStore;
 
 pub(crate) mod config;
-pub use config::{Keycert, RustlsConfig, ServerConfig};
+pub use config::{Keycert, RustlsConfig, ServerConfig, TlsClientAuth};
 
 mod listener;
 pub use listener::{RustlsAcceptor, RustlsListener};
+cfg_feature! {
+    #![feature = "quinn"]
+    pub use listener::ConfigTee;
+}
 
 pub(crate) fn read_trust_anchor(mut trust_anchor: &[u8]) -> IoResult<RootCertStore> {
     let certs = rustls_pemfile::certs(&mut trust_anchor).collect::<IoResult<Vec<_>>>()?;
@@ -65,4 +69,76 @@ mod tests {
         let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
         assert_eq!(conn.read_i32().await.unwrap(), 518);
     }
+
+    async fn connect(addr: std::net::SocketAddr, server_name: &'static str) {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let trust_anchor = include_bytes!("../../../certs/chain.pem");
+        let client_config = ClientConfig::builder()
+            .with_root_certificates(read_trust_anchor(trust_anchor.as_slice()).unwrap())
+            .with_no_client_auth();
+        let connector = TlsConnector::from(Arc::new(client_config));
+        let mut tls_stream = connector
+            .connect(ServerName::try_from(server_name).unwrap(), stream)
+            .await
+            .unwrap();
+        tls_stream.write_i32(518).await.unwrap();
+    }
+
+    fn keycert() -> Keycert {
+        Keycert::new()
+            .key_from_path("certs/key.pem")
+            .unwrap()
+            .cert_from_path("certs/cert.pem")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rustls_wildcard_keycert() {
+        let mut acceptor = TcpListener::new("127.0.0.1:0")
+            .rustls(RustlsConfig::new(None).keycert("*.testserver.com", keycert()))
+            .bind()
+            .await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+        tokio::spawn(connect(addr, "second.testserver.com"));
+
+        let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
+        assert_eq!(conn.read_i32().await.unwrap(), 518);
+    }
+
+    #[tokio::test]
+    async fn test_rustls_cert_resolver() {
+        let mut acceptor = TcpListener::new("127.0.0.1:0")
+            .rustls(RustlsConfig::new(None))
+            .cert_resolver(|server_name| async move {
+                (server_name.as_deref() == Some("testserver.com")).then(keycert)
+            })
+            .bind()
+            .await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+        tokio::spawn(connect(addr, "testserver.com"));
+
+        let Accepted { mut conn, .. } = acceptor.accept(None).await.unwrap();
+        assert_eq!(conn.read_i32().await.unwrap(), 518);
+    }
+
+    #[cfg(feature = "quinn")]
+    #[tokio::test]
+    async fn test_config_tee() {
+        use futures_util::StreamExt;
+
+        let (mut a, mut b) = ConfigTee::pair(futures_util::stream::iter([1, 2]).boxed());
+        assert_eq!(a.next().await, Some(1));
+        assert_eq!(b.next().await, Some(1));
+        assert_eq!(b.next().await, Some(2));
+        assert_eq!(a.next().await, Some(2));
+        assert_eq!(a.next().await, None);
+    }
 }
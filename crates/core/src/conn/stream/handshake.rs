@@ -4,6 +4,7 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use futures_util::{future::BoxFuture, FutureExt};
+use http::Extensions;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf, Result};
 use tokio_util::sync::CancellationToken;
 
@@ -22,6 +23,7 @@ enum State<S> {
 pub struct HandshakeStream<S> {
     state: State<S>,
     fusewire: Option<ArcFusewire>,
+    conn_info: Option<fn(&S) -> Extensions>,
 }
 
 impl<S> HandshakeStream<S> {
@@ -35,9 +37,30 @@ impl<S> HandshakeStream<S> {
         Self {
             state: State::Handshaking(handshake.boxed()),
             fusewire,
+            conn_info: None,
         }
     }
 
+    /// Set a function to get connection level extensions from the stream after handshake, such as the client
+    /// certificate. If it is set, the handshake is completed before serving the connection.
+    pub(crate) fn with_conn_info(mut self, conn_info: fn(&S) -> Extensions) -> Self {
+        self.conn_info = Some(conn_info);
+        self
+    }
+
+    async fn handshake(&mut self) -> IoResult<()> {
+        if let State::Handshaking(fut) = &mut self.state {
+            match fut.await {
+                Ok(s) => self.set_state_ready(s),
+                Err(e) => {
+                    self.state = State::Error;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn set_state_ready(&mut self, stream: S) {
         self.state = State::Ready(stream);
         if let Some(fusewire) = &self.fusewire {
@@ -50,11 +73,17 @@ where
     S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
 {
     async fn serve(
-        self,
-        handler: HyperHandler,
+        mut self,
+        mut handler: HyperHandler,
         builder: Arc<HttpBuilder>,
         graceful_stop_token: Option<CancellationToken>,
     ) -> IoResult<()> {
+        if let Some(conn_info) = self.conn_info {
+            self.handshake().await?;
+            if let State::Ready(stream) = &self.state {
+                handler.conn_extensions.extend(conn_info(stream));
+            }
+        }
         let fusewire = self.fusewire.clone();
         if let Some(fusewire) = &fusewire {
             fusewire.event(FuseEvent::Alive);
@@ -29,7 +29,7 @@ use crate::conn::acme::AcmeListener;
 
 /// `TcpListener` is used to create a TCP connection listener.
 pub struct TcpListener<T> {
-    local_addr: T,
+    pub(crate) local_addr: T,
     ttl: Option<u32>,
     #[cfg(feature = "socket2")]
     backlog: Option<u32>,
@@ -201,6 +201,16 @@ impl TryFrom<TokioTcpListener> for TcpAcceptor {
     }
 }
 
+impl TryFrom<std::net::TcpListener> for TcpAcceptor {
+    type Error = IoError;
+    /// Creates a new `TcpAcceptor` from an inherited std listener, for example, created from a raw fd or socket
+    /// passed by the parent process.
+    fn try_from(inner: std::net::TcpListener) -> Result<Self, Self::Error> {
+        inner.set_nonblocking(true)?;
+        TokioTcpListener::from_std(inner)?.try_into()
+    }
+}
+
 impl Acceptor for TcpAcceptor {
     type Conn = StraightStream<TcpStream>;
 
@@ -230,6 +240,7 @@ impl Acceptor for TcpAcceptor {
                 remote_addr: remote_addr.into(),
                 local_addr,
                 http_scheme: Scheme::HTTP,
+                extensions: Default::default(),
             }
         })
     }
@@ -1,9 +1,34 @@
 //! UnixListener module
+//!
+//! The credentials of the peer process are inserted into the extensions of every request as [`PeerCred`],
+//! so that the handlers can enforce local-caller policies.
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_core::conn::unix::{PeerCred, UnixListener};
+//!
+//! #[handler]
+//! async fn hello(req: &mut Request) -> String {
+//!     match req.extensions().get::<PeerCred>() {
+//!         Some(cred) => format!("Hello uid {}", cred.uid),
+//!         None => "Hello".into(),
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let acceptor = UnixListener::new("/tmp/salvo.sock").mode(0o660).bind().await;
+//!     Server::new(acceptor).serve(Router::new().get(hello)).await;
+//! }
+//! ```
 use std::fs::{set_permissions, Permissions};
-use std::io::Result as IoResult;
+use std::io::{Error as IoError, Result as IoResult};
+use std::os::unix::net::UnixListener as StdUnixListener;
+use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::sync::Arc;
 
+use http::Extensions;
 use http::uri::Scheme;
 use nix::unistd::{chown, Gid, Uid};
 use tokio::net::{UnixListener as TokioUnixListener, UnixStream};
@@ -54,6 +79,12 @@ impl<T> UnixListener<T> {
         self
     }
 
+    /// Provides file mode to be set on actual bind, for example: `0o660`.
+    #[inline]
+    pub fn mode(self, mode: u32) -> Self {
+        self.permissions(Permissions::from_mode(mode))
+    }
+
     #[inline]
     /// Provides owner to be set on actual bind.
     pub fn owner(mut self, uid: Option<u32>, gid: Option<u32>) -> Self {
@@ -105,21 +136,22 @@ where
             socket.listen(backlog as _)?;
         }
 
-        let holdings = vec![Holding {
-            local_addr: inner.local_addr()?.into(),
-            #[cfg(not(feature = "http2-cleartext"))]
-            http_versions: vec![Version::HTTP_11],
-            #[cfg(feature = "http2-cleartext")]
-            http_versions: vec![Version::HTTP_11, Version::HTTP_2],
-            http_scheme: Scheme::HTTP,
-        }];
-        Ok(UnixAcceptor {
-            inner,
-            holdings,
-        })
+        Ok(inner.try_into()?)
     }
 }
 
+/// The credentials of the peer process of a Unix socket connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PeerCred {
+    /// The user id of the peer process.
+    pub uid: u32,
+    /// The group id of the peer process.
+    pub gid: u32,
+    /// The process id of the peer process, it is not available on some platforms.
+    pub pid: Option<i32>,
+}
+
 /// `UnixAcceptor` is used to accept a Unix socket connection.
 pub struct UnixAcceptor {
     inner: TokioUnixListener,
@@ -132,6 +164,30 @@ impl UnixAcceptor {
     }
 }
 
+impl TryFrom<TokioUnixListener> for UnixAcceptor {
+    type Error = IoError;
+    fn try_from(inner: TokioUnixListener) -> Result<Self, Self::Error> {
+        let holdings = vec![Holding {
+            local_addr: inner.local_addr()?.into(),
+            #[cfg(not(feature = "http2-cleartext"))]
+            http_versions: vec![Version::HTTP_11],
+            #[cfg(feature = "http2-cleartext")]
+            http_versions: vec![Version::HTTP_11, Version::HTTP_2],
+            http_scheme: Scheme::HTTP,
+        }];
+        Ok(UnixAcceptor { inner, holdings })
+    }
+}
+
+impl TryFrom<StdUnixListener> for UnixAcceptor {
+    type Error = IoError;
+    /// Creates a new `UnixAcceptor` from an inherited std listener, for example, passed by the parent process.
+    fn try_from(inner: StdUnixListener) -> Result<Self, Self::Error> {
+        inner.set_nonblocking(true)?;
+        TokioUnixListener::from_std(inner)?.try_into()
+    }
+}
+
 #[cfg(unix)]
 impl Acceptor for UnixAcceptor {
     type Conn = StraightStream<UnixStream>;
@@ -144,6 +200,17 @@ impl Acceptor for UnixAcceptor {
     #[inline]
     async fn accept(&mut self, fuse_factory: Option<ArcFuseFactory>) -> IoResult<Accepted<Self::Conn>> {
         self.inner.accept().await.map(move |(conn, remote_addr)|{
+            let mut extensions = Extensions::new();
+            match conn.peer_cred() {
+                Ok(cred) => {
+                    extensions.insert(PeerCred {
+                        uid: cred.uid(),
+                        gid: cred.gid(),
+                        pid: cred.pid(),
+                    });
+                }
+                Err(e) => tracing::warn!(error = ?e, "get unix socket peer credentials failed"),
+            }
             let remote_addr = Arc::new(remote_addr);
             let local_addr = self.holdings[0].local_addr.clone();
             Accepted {
@@ -155,6 +222,7 @@ impl Acceptor for UnixAcceptor {
                 local_addr: self.holdings[0].local_addr.clone(),
                 remote_addr: remote_addr.clone().into(),
                 http_scheme: Scheme::HTTP,
+                extensions,
             }
         })
     }
@@ -181,4 +249,25 @@ mod tests {
         assert_eq!(conn.read_i32().await.unwrap(), 518);
         std::fs::remove_file(sock_file).unwrap();
     }
+
+    #[tokio::test]
+    async fn test_unix_listener_peer_cred() {
+        use std::os::unix::fs::MetadataExt;
+
+        let sock_file = "/tmp/test-salvo-peer-cred.sock";
+        let _ = std::fs::remove_file(sock_file);
+        let mut acceptor = UnixListener::new(sock_file).mode(0o600).bind().await;
+        assert_eq!(std::fs::metadata(sock_file).unwrap().mode() & 0o777, 0o600);
+
+        tokio::spawn(async move {
+            let mut stream = tokio::net::UnixStream::connect(sock_file).await.unwrap();
+            stream.write_i32(518).await.unwrap();
+        });
+
+        let Accepted { extensions, .. } = acceptor.accept(None).await.unwrap();
+        let cred = extensions.get::<PeerCred>().unwrap();
+        assert_eq!(cred.uid, nix::unistd::getuid().as_raw());
+        assert_eq!(cred.gid, nix::unistd::getgid().as_raw());
+        std::fs::remove_file(sock_file).unwrap();
+    }
 }
@@ -0,0 +1,114 @@
+//! Watch files and reload TLS config when they are changed.
+//!
+//! [`watch_config`] creates a config stream which can be passed to `RustlsListener`, `NativeTlsListener` and
+//! `OpensslListener`, so the server certificate can be swapped without restarting the listener. It is useful for
+//! the short-lived certificates renewed by cert-manager or Let's Encrypt clients.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # #[cfg(feature = "rustls")]
+//! # async fn run() {
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_core::conn::rustls::{Keycert, RustlsConfig};
+//! use salvo_core::conn::watch::watch_config;
+//!
+//! let config_stream = watch_config(
+//!     ["certs/cert.pem", "certs/key.pem"],
+//!     Duration::from_secs(10),
+//!     || {
+//!         Ok(RustlsConfig::new(
+//!             Keycert::new()
+//!                 .cert_from_path("certs/cert.pem")?
+//!                 .key_from_path("certs/key.pem")?,
+//!         ))
+//!     },
+//! );
+//! let acceptor = TcpListener::new("0.0.0.0:5800").rustls(config_stream).bind().await;
+//! Server::new(acceptor).serve(Router::new()).await;
+//! # }
+//! ```
+use std::io::Result as IoResult;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use futures_util::stream::{self, BoxStream, StreamExt};
+
+/// Create a config stream which yields a config loaded by `loader` immediately, and yields a new one whenever
+/// the modified time of any file in `paths` is changed. The files are checked every `interval`.
+///
+/// If `loader` returns an error, the error is logged and the previous config is kept until the files are changed
+/// again.
+pub fn watch_config<C, F, P>(
+    paths: impl IntoIterator<Item = P>,
+    interval: Duration,
+    loader: F,
+) -> BoxStream<'static, C>
+where
+    C: Send + 'static,
+    F: Fn() -> IoResult<C> + Send + Sync + 'static,
+    P: Into<PathBuf>,
+{
+    let paths = Arc::new(paths.into_iter().map(Into::into).collect::<Vec<PathBuf>>());
+    let loader = Arc::new(loader);
+    stream::unfold(None, move |mut last: Option<Vec<Option<SystemTime>>>| {
+        let paths = paths.clone();
+        let loader = loader.clone();
+        async move {
+            loop {
+                if last.is_some() {
+                    tokio::time::sleep(interval).await;
+                }
+                let modified = paths
+                    .iter()
+                    .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+                    .collect::<Vec<_>>();
+                if last.as_ref() == Some(&modified) {
+                    continue;
+                }
+                match loader() {
+                    Ok(config) => return Some((config, Some(modified))),
+                    Err(e) => {
+                        tracing::error!(error = ?e, "load config from watched files failed");
+                        last = Some(modified);
+                    }
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::Write;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_watch_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cert.pem");
+        std::fs::write(&path, "first").unwrap();
+
+        let mut stream = watch_config([path.clone()], Duration::from_millis(10), {
+            let path = path.clone();
+            move || std::fs::read_to_string(&path)
+        });
+        assert_eq!(stream.next().await.unwrap(), "first");
+
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"second").unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        drop(file);
+        let config = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap();
+        assert_eq!(config.unwrap(), "second");
+    }
+}
@@ -1,6 +1,9 @@
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::Router;
 
 /// Store temp data for current request.
 ///
@@ -187,6 +190,23 @@ impl Depot {
     ) -> Result<T, Option<Box<dyn Any + Send + Sync>>> {
         self.remove(&type_key::<T>())
     }
+
+    /// Generate url for the router with the given name, the root router is injected by [`Service`].
+    ///
+    /// View [`Router::url_for`] for more details.
+    ///
+    /// [`Service`]: crate::Service
+    #[inline]
+    pub fn url_for<I, K, V>(&self, name: &str, params: I) -> Option<String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: ToString,
+    {
+        self.obtain::<Arc<Router>>()
+            .ok()
+            .and_then(|router| router.url_for(name, params))
+    }
 }
 
 impl Debug for Depot {
@@ -0,0 +1,131 @@
+use std::convert::Infallible;
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+use crate::Writer;
+use crate::extract::{Extractible, Metadata};
+use crate::http::Request;
+
+/// Defers the extraction of `T` until [`Lazy::extract`] is called in handler.
+///
+/// Nothing is read from the request before the handler runs, so the body is not consumed and the
+/// extraction error can be handled in handler instead of producing a `400 Bad Request` automatically.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::extract::Lazy;
+/// use salvo_core::prelude::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Extractible, Debug)]
+/// #[salvo(extract(default_source(from = "body")))]
+/// struct User {
+///     name: String,
+/// }
+///
+/// #[handler]
+/// async fn create(user: Lazy<User>, req: &mut Request) -> String {
+///     match user.extract(req).await {
+///         Ok(user) => format!("Hello {}", user.name),
+///         Err(_) => "Hello anonymous".into(),
+///     }
+/// }
+/// ```
+pub struct Lazy<T> {
+    arg: String,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T> Lazy<T> {
+    /// Create a new `Lazy` with the argument name used to extract `T`.
+    pub fn new(arg: impl Into<String>) -> Self {
+        Self {
+            arg: arg.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the argument name used to extract `T`.
+    pub fn arg(&self) -> &str {
+        &self.arg
+    }
+
+    /// Extracts `T` from request.
+    pub fn extract<'ex>(
+        &self,
+        req: &'ex mut Request,
+    ) -> impl Future<Output = Result<T, impl Writer + Send + Debug + 'static>> + Send
+    where
+        T: Extractible<'ex>,
+    {
+        T::extract_with_arg(req, &self.arg)
+    }
+}
+
+impl<T> Debug for Lazy<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("type", &std::any::type_name::<T>())
+            .field("arg", &self.arg)
+            .finish()
+    }
+}
+
+impl<'ex, T> Extractible<'ex> for Lazy<T> {
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    fn extract(
+        _req: &'ex mut Request,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        std::future::ready(Ok::<_, Infallible>(Self::new("")))
+    }
+    fn extract_with_arg(
+        _req: &'ex mut Request,
+        arg: &str,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        std::future::ready(Ok::<_, Infallible>(Self::new(arg)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::macros::Extractible;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_extract_lazy() {
+        #[derive(Deserialize, Extractible, Debug)]
+        #[salvo(extract(default_source(from = "body")))]
+        struct User {
+            name: String,
+        }
+
+        #[handler]
+        async fn hello(user: Lazy<User>, req: &mut Request) -> String {
+            assert_eq!(user.arg(), "user");
+            match user.extract(req).await {
+                Ok(user) => format!("Hello {}", user.name),
+                Err(_) => "Hello anonymous".into(),
+            }
+        }
+
+        let router = Router::with_path("hello").post(hello);
+        let service = Service::new(router);
+        let mut res = TestClient::post("http://127.0.0.1:5801/hello")
+            .json(&serde_json::json!({"name": "jobs"}))
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "Hello jobs");
+        let mut res = TestClient::post("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(res.take_string().await.unwrap(), "Hello anonymous");
+    }
+}
@@ -51,6 +51,8 @@ pub enum SourceParser {
     Json,
     /// Url or Header parser.
     Flat,
+    /// Bracket notation parser for url query and form, e.g. `user[name]=x&user[tags][]=a&user[tags][]=b`.
+    Nested,
     /// Smart parser.
     Smart,
 }
@@ -62,6 +64,7 @@ impl FromStr for SourceParser {
         match input {
             "multimap" => Ok(Self::MultiMap),
             "json" => Ok(Self::Json),
+            "nested" => Ok(Self::Nested),
             "smart" => Ok(Self::Smart),
             _ => Err(crate::Error::Other("invalid source format".into())),
         }
@@ -163,6 +166,9 @@ pub struct Field {
     pub serde_rename: Option<&'static str>,
     /// Field metadata, this is used for nested extractible types.
     pub metadata: Option<&'static Metadata>,
+    /// Field default value defined by `#[salvo(extract(default = ""))]`, it is parsed like a query value
+    /// when the field is not found in any source.
+    pub default_value: Option<&'static str>,
 }
 impl Field {
     /// Create a new field with the given name and kind.
@@ -180,6 +186,7 @@ impl Field {
             rename: None,
             serde_rename: None,
             metadata: None,
+            default_value: None,
         }
     }
 
@@ -225,6 +232,12 @@ impl Field {
         self
     }
 
+    /// Sets the default value to the given value.
+    pub fn default_value(mut self, default_value: &'static str) -> Self {
+        self.default_value = Some(default_value);
+        self
+    }
+
     /// Check is this field has body required.
     pub(crate) fn has_body_required(&self) -> bool {
         self.sources.iter().any(|s| s.from == SourceFrom::Body)
@@ -239,11 +252,23 @@ pub struct Source {
     pub from: SourceFrom,
     /// The parser used to parse data.
     pub parser: SourceParser,
+    /// The name used to find data in this source, it overrides the field name.
+    pub rename: Option<&'static str>,
 }
 impl Source {
     /// Create a new source from a string.
     pub fn new(from: SourceFrom, parser: SourceParser) -> Self {
-        Self { from, parser }
+        Self {
+            from,
+            parser,
+            rename: None,
+        }
+    }
+
+    /// Sets the rename to the given value.
+    pub fn rename(mut self, rename: &'static str) -> Self {
+        self.rename = Some(rename);
+        self
     }
 }
 
@@ -271,6 +296,7 @@ mod tests {
         for (key, value) in [
             ("multimap", SourceParser::MultiMap),
             ("json", SourceParser::Json),
+            ("nested", SourceParser::Nested),
         ] {
             assert_eq!(key.parse::<SourceParser>().unwrap(), value);
         }
@@ -61,12 +61,116 @@
 //! ```
 //!
 //! View [full source code](https://github.com/salvo-rs/salvo/blob/main/examples/extract-nested/src/main.rs)
+//!
+//! A field can be renamed for each source, and a default value can be given for a field that is missing from
+//! all sources, it is parsed in the same way as a query value. Repeated query params such as
+//! `?tag=a&tag=b` are collected into `Vec<T>` or `HashSet<T>`. `Option<T>` fields and fields with
+//! `#[serde(default)]` can also be missing.
+//!
+//! ```
+//! # use std::collections::HashSet;
+//! # use salvo_core::prelude::*;
+//! # use serde::Deserialize;
+//! #[derive(Deserialize, Extractible, Debug)]
+//! #[salvo(extract(default_source(from = "query")))]
+//! struct Search {
+//!     #[salvo(extract(
+//!         source(from = "header", rename = "x-request-id"),
+//!         source(from = "query", rename = "requestId")
+//!     ))]
+//!     request_id: String,
+//!     #[salvo(extract(rename = "tag"))]
+//!     tags: HashSet<String>,
+//!     #[salvo(extract(default = 20))]
+//!     size: u32,
+//!     #[salvo(extract(default = "[created_at, id]"))]
+//!     order_by: Vec<String>,
+//!     keyword: Option<String>,
+//! }
+//! ```
+//!
+//! The url query and form fields in bracket notation, such as `user[name]=x&user[tags][]=a&user[tags][]=b`, can
+//! be deserialized into nested structs with `parse = "nested"`:
+//!
+//! ```
+//! # use salvo_core::prelude::*;
+//! # use serde::Deserialize;
+//! #[derive(Deserialize, Debug)]
+//! struct User {
+//!     name: String,
+//!     tags: Vec<String>,
+//! }
+//!
+//! #[derive(Deserialize, Extractible, Debug)]
+//! struct SignUp {
+//!     #[salvo(extract(source(from = "body", parse = "nested")))]
+//!     user: User,
+//! }
+//! ```
+//!
+//! An extractible parameter of handler can be wrapped in `Option<T>` or `Result<T, ParseError>` to handle the
+//! extraction failure in handler, instead of responding `400 Bad Request` automatically. [`Lazy<T>`] defers the
+//! extraction until [`Lazy::extract`] is called.
+//!
+//! ```
+//! # use salvo_core::prelude::*;
+//! # use salvo_core::http::ParseError;
+//! # use serde::Deserialize;
+//! # #[derive(Deserialize, Extractible, Debug)]
+//! # #[salvo(extract(default_source(from = "query")))]
+//! # struct Search {
+//! #     keyword: String,
+//! # }
+//! #[handler]
+//! async fn find(search: Result<Search, ParseError>, req: &mut Request, res: &mut Response) {
+//!     match search {
+//!         Ok(search) => res.render(search.keyword),
+//!         Err(e) => e.render_problem(req, res),
+//!     }
+//! }
+//! ```
+//!
+//! The extraction error can be mapped to a custom response with `error = "path::to::fn"`, either on the struct or
+//! on a handler parameter. The function takes a [`ParseError`](crate::http::ParseError) and returns a [`Writer`].
+//!
+//! ```
+//! # use salvo_core::prelude::*;
+//! # use salvo_core::http::ParseError;
+//! # use serde::Deserialize;
+//! fn bad_request(e: ParseError) -> StatusError {
+//!     StatusError::unprocessable_entity().brief(e.to_string())
+//! }
+//!
+//! #[derive(Deserialize, Extractible, Debug)]
+//! #[salvo(extract(default_source(from = "query"), error = "bad_request"))]
+//! struct Pagination {
+//!     size: u32,
+//! }
+//!
+//! #[handler]
+//! async fn list(#[salvo(extract(error = "bad_request"))] page: Pagination) -> String {
+//!     page.size.to_string()
+//! }
+//! ```
 
 /// Metadata types.
 pub mod metadata;
 pub use metadata::Metadata;
 mod case;
 pub use case::RenameRule;
+mod lazy;
+pub use lazy::Lazy;
+pub(crate) mod optional;
+mod state;
+pub use state::State;
+mod typed_header;
+pub use typed_header::TypedHeader;
+cfg_feature! {
+    #![feature = "validator"]
+    mod valid;
+    pub use valid::{Valid, ValidError};
+}
+pub(crate) use state::StateHoop;
 
 use std::fmt::Debug;
 
@@ -0,0 +1,182 @@
+use std::any::Any;
+use std::convert::Infallible;
+use std::fmt::Debug;
+
+use crate::Writer;
+use crate::extract::{Extractible, Metadata};
+use crate::http::{ParseError, Request};
+
+/// Extracts `Some(T)` if `T` is extracted successfully, otherwise `None`.
+///
+/// The extraction error is discarded, use `Result<T, ParseError>` if it is needed.
+// The futures are returned explicitly, `async fn` futures are not proved to be `Send` in handlers
+// because of rust-lang/rust#100013.
+#[allow(clippy::manual_async_fn)]
+impl<'ex, T> Extractible<'ex> for Option<T>
+where
+    T: Extractible<'ex> + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        T::metadata()
+    }
+    fn extract(
+        req: &'ex mut Request,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        async move { Ok::<_, Infallible>(T::extract(req).await.ok()) }
+    }
+    fn extract_with_arg(
+        req: &'ex mut Request,
+        arg: &str,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        async move { Ok::<_, Infallible>(T::extract_with_arg(req, arg).await.ok()) }
+    }
+}
+
+/// Extracts the result of `T`, so the extraction error can be handled in handler.
+///
+/// If the error of `T` is not a [`ParseError`], it is converted to [`ParseError::Other`] with its debug
+/// message.
+// The futures are returned explicitly, `async fn` futures are not proved to be `Send` in handlers
+// because of rust-lang/rust#100013.
+#[allow(clippy::manual_async_fn)]
+impl<'ex, T> Extractible<'ex> for Result<T, ParseError>
+where
+    T: Extractible<'ex> + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        T::metadata()
+    }
+    fn extract(
+        req: &'ex mut Request,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        async move { Ok::<_, Infallible>(T::extract(req).await.map_err(into_parse_error)) }
+    }
+    fn extract_with_arg(
+        req: &'ex mut Request,
+        arg: &str,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        async move {
+            Ok::<_, Infallible>(
+                T::extract_with_arg(req, arg)
+                    .await
+                    .map_err(into_parse_error),
+            )
+        }
+    }
+}
+
+/// Converts the extraction error to [`ParseError`], the error which is not a [`ParseError`] is converted to
+/// [`ParseError::Other`] with its debug message. This function is used by macros internal.
+#[doc(hidden)]
+pub fn into_parse_error<E>(e: E) -> ParseError
+where
+    E: Debug + 'static,
+{
+    let mut e = Some(e);
+    let parse_error = (&mut e as &mut dyn Any)
+        .downcast_mut::<Option<ParseError>>()
+        .and_then(Option::take);
+    match parse_error {
+        Some(parse_error) => parse_error,
+        None => ParseError::other(format!("{:?}", e.expect("error should exist"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::http::ParseError;
+    use crate::macros::Extractible;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Deserialize, Extractible, Debug)]
+    #[salvo(extract(default_source(from = "query")))]
+    struct Pagination {
+        size: u32,
+    }
+
+    #[tokio::test]
+    async fn test_extract_option() {
+        #[handler]
+        async fn size(data: Option<Pagination>) -> String {
+            match data {
+                Some(data) => data.size.to_string(),
+                None => "none".into(),
+            }
+        }
+
+        let router = Router::with_path("size").get(size);
+        let service = Service::new(router);
+        let mut res = TestClient::get("http://127.0.0.1:5801/size?size=10")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "10");
+        let mut res = TestClient::get("http://127.0.0.1:5801/size?size=ten")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(res.take_string().await.unwrap(), "none");
+    }
+
+    #[tokio::test]
+    async fn test_extract_result() {
+        #[handler]
+        async fn size(data: Result<Pagination, ParseError>) -> String {
+            match data {
+                Ok(data) => data.size.to_string(),
+                Err(e) => e.field_error().unwrap().path.clone(),
+            }
+        }
+
+        let router = Router::with_path("size").get(size);
+        let service = Service::new(router);
+        let mut res = TestClient::get("http://127.0.0.1:5801/size?size=10")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "10");
+        let mut res = TestClient::get("http://127.0.0.1:5801/size?size=ten")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+        assert_eq!(res.take_string().await.unwrap(), "size");
+    }
+
+    #[tokio::test]
+    async fn test_extract_error_fn() {
+        fn bad_size(e: ParseError) -> StatusError {
+            StatusError::unprocessable_entity().brief(e.to_string())
+        }
+
+        #[derive(Deserialize, Extractible, Debug)]
+        #[salvo(extract(default_source(from = "query"), error = bad_size))]
+        struct Size {
+            size: u32,
+        }
+
+        #[handler]
+        async fn size(data: Size) -> String {
+            data.size.to_string()
+        }
+        #[handler]
+        async fn page(#[salvo(extract(error = "bad_size"))] data: Pagination) -> String {
+            data.size.to_string()
+        }
+
+        let router = Router::new()
+            .push(Router::with_path("size").get(size))
+            .push(Router::with_path("page").get(page));
+        let service = Service::new(router);
+        let mut res = TestClient::get("http://127.0.0.1:5801/size?size=10")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "10");
+        for path in ["size", "page"] {
+            let res = TestClient::get(format!("http://127.0.0.1:5801/{path}?size=ten"))
+                .send(&service)
+                .await;
+            assert_eq!(res.status_code.unwrap(), StatusCode::UNPROCESSABLE_ENTITY);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use crate::extract::{Extractible, Metadata};
+use crate::http::{Request, Response, StatusError};
+use crate::{Depot, FlowCtrl, Handler, async_trait};
+
+/// Extracts the typed state shared by [`Router::with_state`](crate::Router::with_state).
+///
+/// If no state of type `T` is found, a `500 Internal Server Error` is returned.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::extract::State;
+///
+/// #[derive(Clone)]
+/// struct AppState {
+///     name: String,
+/// }
+///
+/// #[handler]
+/// async fn hello(state: State<AppState>) -> String {
+///     format!("Hello {}", state.name)
+/// }
+///
+/// let router = Router::with_state(AppState { name: "salvo".into() })
+///     .push(Router::with_path("hello").get(hello));
+/// ```
+pub struct State<T>(pub T);
+impl<T> State<T> {
+    /// Consumes self and returns the value of the state.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for State<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for State<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Clone for State<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Debug for State<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'ex, T> Extractible<'ex> for State<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    #[allow(refining_impl_trait)]
+    async fn extract(req: &'ex mut Request) -> Result<Self, StatusError> {
+        req.extensions().get::<State<T>>().cloned().ok_or_else(|| {
+            tracing::error!(
+                state = std::any::type_name::<T>(),
+                "state not found, it should be added by `Router::with_state`"
+            );
+            StatusError::internal_server_error()
+        })
+    }
+}
+
+/// Handler that shares the state with the request extensions and depot.
+pub(crate) struct StateHoop<T>(pub(crate) T);
+#[async_trait]
+impl<T> Handler for StateHoop<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        _res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        req.extensions_mut().insert(State(self.0.clone()));
+        depot.inject(self.0.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_state() {
+        #[derive(Clone, Debug)]
+        struct AppState(&'static str);
+
+        #[handler]
+        async fn hello(state: State<AppState>, depot: &mut Depot) -> String {
+            assert_eq!(depot.obtain::<AppState>().unwrap().0, state.0.0);
+            format!("Hello {}", state.0.0)
+        }
+
+        let router = Router::new()
+            .push(
+                Router::with_path("outer")
+                    .state(AppState("outer"))
+                    .get(hello)
+                    .push(
+                        Router::with_path("inner")
+                            .state(AppState("inner"))
+                            .get(hello),
+                    ),
+            )
+            .push(Router::with_path("none").get(hello));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/outer")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "Hello outer");
+        let mut res = TestClient::get("http://127.0.0.1:5801/outer/inner")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "Hello inner");
+        let res = TestClient::get("http://127.0.0.1:5801/none")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use headers::Header;
+
+use crate::extract::{Extractible, Metadata};
+use crate::http::{Request, StatusError};
+
+/// Extracts a typed header from the request, such as [`headers::UserAgent`] or [`headers::CacheControl`].
+///
+/// If the header is not found or is invalid, a `400 Bad Request` is returned. Use `Option<TypedHeader<H>>`
+/// if the header is optional.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::extract::TypedHeader;
+/// use salvo_core::http::headers::UserAgent;
+///
+/// #[handler]
+/// async fn hello(user_agent: TypedHeader<UserAgent>) -> String {
+///     format!("Hello {}", user_agent.as_str())
+/// }
+/// ```
+pub struct TypedHeader<H>(pub H);
+impl<H> TypedHeader<H> {
+    /// Consumes self and returns the value of the header.
+    pub fn into_inner(self) -> H {
+        self.0
+    }
+}
+
+impl<H> Deref for TypedHeader<H> {
+    type Target = H;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<H> DerefMut for TypedHeader<H> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<H> Clone for TypedHeader<H>
+where
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<H> Debug for TypedHeader<H>
+where
+    H: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'ex, H> Extractible<'ex> for TypedHeader<H>
+where
+    H: Header + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    #[allow(refining_impl_trait)]
+    async fn extract(req: &'ex mut Request) -> Result<Self, StatusError> {
+        match req.try_typed_header::<H>() {
+            Ok(Some(header)) => Ok(Self(header)),
+            Ok(None) => Err(StatusError::bad_request()
+                .brief(format!("Missing request header `{}`.", H::name()))),
+            Err(e) => Err(StatusError::bad_request()
+                .brief(format!("Invalid request header `{}`.", H::name()))
+                .cause(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use headers::UserAgent;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_extract_typed_header() {
+        let mut req = Request::new();
+        assert!(TypedHeader::<UserAgent>::extract(&mut req).await.is_err());
+
+        req.set_typed_header(UserAgent::from_static("salvo"));
+        let user_agent = TypedHeader::<UserAgent>::extract(&mut req).await.unwrap();
+        assert_eq!(user_agent.as_str(), "salvo");
+    }
+}
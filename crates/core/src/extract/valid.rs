@@ -0,0 +1,227 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use serde_json::{Value, json};
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
+
+use crate::extract::{Extractible, Metadata};
+use crate::http::{Request, Response, StatusCode};
+use crate::writing::Problem;
+use crate::{Depot, Writer, async_trait};
+
+/// Extracts `T` from the request and validates it with [`validator::Validate`].
+///
+/// If the extraction fails, the error of `T` is returned. If the validation fails, a
+/// `422 Unprocessable Entity` problem details document is returned, its `errors` member lists the
+/// offending fields.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::extract::Valid;
+/// use salvo_core::prelude::*;
+/// use serde::Deserialize;
+/// use validator::Validate;
+///
+/// #[derive(Deserialize, Extractible, Validate, Debug)]
+/// #[salvo(extract(default_source(from = "body")))]
+/// struct SignUp {
+///     #[validate(length(min = 3, max = 20))]
+///     username: String,
+///     #[validate(email)]
+///     email: String,
+/// }
+///
+/// #[handler]
+/// async fn sign_up(data: Valid<SignUp>) -> String {
+///     format!("Welcome {}", data.username)
+/// }
+/// ```
+pub struct Valid<T>(pub T);
+impl<T> Valid<T> {
+    /// Consumes self and returns the validated value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Valid<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Valid<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Debug for Valid<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+// The futures are returned explicitly, `async fn` futures are not proved to be `Send` in handlers
+// because of rust-lang/rust#100013.
+#[allow(clippy::manual_async_fn)]
+impl<'ex, T> Extractible<'ex> for Valid<T>
+where
+    T: Extractible<'ex> + Validate + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        T::metadata()
+    }
+    fn extract(
+        req: &'ex mut Request,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        async move { validate(T::extract(req).await) }
+    }
+    fn extract_with_arg(
+        req: &'ex mut Request,
+        arg: &str,
+    ) -> impl Future<Output = Result<Self, impl Writer + Send + Debug + 'static>> + Send {
+        async move { validate(T::extract_with_arg(req, arg).await) }
+    }
+}
+
+fn validate<T, E>(extracted: Result<T, E>) -> Result<Valid<T>, ValidError<E>>
+where
+    T: Validate,
+{
+    match extracted {
+        Ok(value) => match value.validate() {
+            Ok(()) => Ok(Valid(value)),
+            Err(e) => Err(ValidError::Invalid(e)),
+        },
+        Err(e) => Err(ValidError::Extract(e)),
+    }
+}
+
+/// Error returned by [`Valid`] extractor.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValidError<E> {
+    /// The value can not be extracted from request.
+    Extract(E),
+    /// The extracted value is invalid.
+    Invalid(ValidationErrors),
+}
+
+#[async_trait]
+impl<E> Writer for ValidError<E>
+where
+    E: Writer + Send,
+{
+    async fn write(self, req: &mut Request, depot: &mut Depot, res: &mut Response) {
+        match self {
+            Self::Extract(e) => e.write(req, depot, res).await,
+            Self::Invalid(errors) => {
+                let mut fields = Vec::new();
+                collect_field_errors(&errors, "", &mut fields);
+                res.render(
+                    Problem::new(StatusCode::UNPROCESSABLE_ENTITY)
+                        .detail("request data validation failed.")
+                        .instance(req.uri().path())
+                        .extension("errors", fields),
+                );
+            }
+        }
+    }
+}
+
+/// Flatten the errors to a list of `{field, code, message, params}` objects, the field path of nested structs
+/// is separated by `.` and the index of list items is in brackets, e.g. `users[0].name`.
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, fields: &mut Vec<Value>) {
+    let mut errors = errors.errors().iter().collect::<Vec<_>>();
+    errors.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, kind) in errors {
+        let path = if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                for error in errors {
+                    fields.push(json!({
+                        "field": path,
+                        "code": error.code,
+                        "message": error.message,
+                        "params": error.params,
+                    }));
+                }
+            }
+            ValidationErrorsKind::Struct(errors) => collect_field_errors(errors, &path, fields),
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect_field_errors(errors, &format!("{path}[{index}]"), fields);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::macros::Extractible;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Deserialize, Extractible, Validate, Debug)]
+    #[salvo(extract(default_source(from = "query")))]
+    struct Pagination {
+        #[validate(range(min = 1, max = 100))]
+        size: u32,
+        #[validate(length(min = 2))]
+        keyword: String,
+    }
+
+    #[tokio::test]
+    async fn test_extract_valid() {
+        let mut req = TestClient::get("http://127.0.0.1:5800/users")
+            .query("size", "20")
+            .query("keyword", "jobs")
+            .build();
+        let data = Valid::<Pagination>::extract(&mut req).await.unwrap();
+        assert_eq!(data.size, 20);
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/users")
+            .query("size", "200")
+            .query("keyword", "j")
+            .build();
+        let err = Valid::<Pagination>::extract(&mut req).await.unwrap_err();
+        let mut res = Response::new();
+        err.write(&mut req, &mut Depot::new(), &mut res).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNPROCESSABLE_ENTITY));
+        let problem: Value = serde_json::from_slice(&res.take_bytes(None).await.unwrap()).unwrap();
+        let fields = problem["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| {
+                (
+                    error["field"].as_str().unwrap(),
+                    error["code"].as_str().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec![("keyword", "length"), ("size", "range")]);
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/users")
+            .query("size", "ten")
+            .build();
+        let err = Valid::<Pagination>::extract(&mut req).await.unwrap_err();
+        let mut res = Response::new();
+        err.write(&mut req, &mut Depot::new(), &mut res).await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+    }
+}
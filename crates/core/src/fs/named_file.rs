@@ -13,10 +13,8 @@ use headers::*;
 use tokio::fs::File;
 
 use super::{ChunkedFile, ChunkedState};
-use crate::http::header::{
-    CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_TYPE, IF_NONE_MATCH, RANGE,
-};
-use crate::http::{HttpRange, Mime, Request, Response, StatusCode, StatusError};
+use crate::http::header::{CONTENT_DISPOSITION, CONTENT_ENCODING, CONTENT_TYPE, RANGE};
+use crate::http::{HttpRange, Mime, Precondition, Request, Response, StatusCode, StatusError};
 use crate::{Depot, Error, Result, Writer, async_trait};
 
 const CHUNK_SIZE: u64 = 1024 * 1024;
@@ -420,29 +418,7 @@ impl NamedFile {
             None
         };
 
-        // check preconditions
-        let precondition_failed = if !any_match(etag.as_ref(), req_headers) {
-            true
-        } else if let (Some(last_modified), Some(since)) =
-            (&last_modified, req_headers.typed_get::<IfUnmodifiedSince>())
-        {
-            !since.precondition_passes(*last_modified)
-        } else {
-            false
-        };
-
-        // check last modified
-        let not_modified = if !none_match(etag.as_ref(), req_headers) {
-            true
-        } else if req_headers.contains_key(IF_NONE_MATCH) {
-            false
-        } else if let (Some(last_modified), Some(since)) =
-            (&last_modified, req_headers.typed_get::<IfModifiedSince>())
-        {
-            !since.is_modified(*last_modified)
-        } else {
-            false
-        };
+        let precondition = Precondition::evaluate(req_headers, etag.as_ref(), last_modified);
 
         if self.flags.contains(Flag::ContentDisposition) {
             if let Some(content_disposition) = self.content_disposition.take() {
@@ -499,12 +475,16 @@ impl NamedFile {
             };
         }
 
-        if precondition_failed {
-            res.status_code(StatusCode::PRECONDITION_FAILED);
-            return;
-        } else if not_modified {
-            res.status_code(StatusCode::NOT_MODIFIED);
-            return;
+        match precondition {
+            Precondition::Failed => {
+                res.status_code(StatusCode::PRECONDITION_FAILED);
+                return;
+            }
+            Precondition::NotModified => {
+                res.status_code(StatusCode::NOT_MODIFIED);
+                return;
+            }
+            Precondition::Passed => {}
         }
 
         if offset != 0 || length != self.metadata.len() || range.is_some() {
@@ -562,35 +542,3 @@ impl DerefMut for NamedFile {
         &mut self.file
     }
 }
-
-/// Returns true if `req_headers` has no `If-Match` header or one which matches `etag`.
-fn any_match(etag: Option<&ETag>, req_headers: &HeaderMap) -> bool {
-    match req_headers.typed_get::<IfMatch>() {
-        None => true,
-        Some(if_match) => {
-            if if_match == IfMatch::any() {
-                true
-            } else if let Some(etag) = etag {
-                if_match.precondition_passes(etag)
-            } else {
-                false
-            }
-        }
-    }
-}
-
-/// Returns true if `req_headers` doesn't have an `If-None-Match` header matching `req`.
-fn none_match(etag: Option<&ETag>, req_headers: &HeaderMap) -> bool {
-    match req_headers.typed_get::<IfNoneMatch>() {
-        None => true,
-        Some(if_none_match) => {
-            if if_none_match == IfNoneMatch::any() {
-                false
-            } else if let Some(etag) = etag {
-                if_none_match.precondition_passes(etag)
-            } else {
-                true
-            }
-        }
-    }
-}
@@ -135,6 +135,14 @@ pub trait Handler: Send + Sync + 'static {
     fn type_name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
+    /// Returns the HTTP authentication this handler enforces when it is used as a middleware.
+    ///
+    /// Authentication middlewares return their scheme here, so `salvo-oapi` can add the security
+    /// requirement to the endpoints under the router automatically.
+    #[inline]
+    fn auth_scheme(&self) -> Option<AuthScheme> {
+        None
+    }
     /// Handle http request.
     #[must_use = "handle future must be used"]
     async fn handle(
@@ -186,12 +194,26 @@ pub trait Handler: Send + Sync + 'static {
     }
 }
 
+/// HTTP authentication enforced by a middleware, returned by [`Handler::auth_scheme`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthScheme {
+    /// Basic authentication.
+    Basic,
+    /// Bearer authentication, with the format of the token such as `JWT`.
+    Bearer(Option<&'static str>),
+}
+
 /// A handler that wraps another [Handler] to enable it to be cloneable.
 #[derive(Clone)]
 pub struct ArcHandler(Arc<dyn Handler>);
 
 #[async_trait]
 impl Handler for ArcHandler {
+    #[inline]
+    fn auth_scheme(&self) -> Option<AuthScheme> {
+        self.0.auth_scheme()
+    }
     async fn handle(
         &self,
         req: &mut Request,
@@ -242,6 +264,10 @@ where
     H: Handler,
     F: Fn(&Request, &Depot) -> bool + Send + Sync + 'static,
 {
+    #[inline]
+    fn auth_scheme(&self) -> Option<AuthScheme> {
+        self.inner.auth_scheme()
+    }
     async fn handle(
         &self,
         req: &mut Request,
@@ -4,6 +4,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use futures_util::stream::Stream;
+use http_body_util::Limited;
 use hyper::body::{Body, Frame, Incoming, SizeHint};
 
 use bytes::Bytes;
@@ -79,6 +80,18 @@ impl ReqBody {
     pub fn take(&mut self) -> Self {
         std::mem::replace(self, Self::None)
     }
+
+    /// Limit the body size, reading the body returns an error when the size exceeds `max_size`.
+    pub(crate) fn limited(self, max_size: usize) -> Self {
+        match self {
+            Self::None => Self::None,
+            Self::Once(bytes) if bytes.len() <= max_size => Self::Once(bytes),
+            body => Self::Boxed {
+                inner: Box::pin(Limited::new(body, max_size)),
+                fusewire: None,
+            },
+        }
+    }
 }
 
 impl Body for ReqBody {
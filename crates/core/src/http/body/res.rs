@@ -5,7 +5,9 @@ use std::pin::Pin;
 use std::task::{self, Context, Poll, ready};
 
 use futures_channel::{mpsc, oneshot};
+use futures_util::future::BoxFuture;
 use futures_util::stream::{BoxStream, FusedStream, Stream, TryStreamExt};
+use hyper::HeaderMap;
 use hyper::body::{Body, Frame, Incoming, SizeHint};
 use sync_wrapper::SyncWrapper;
 
@@ -109,6 +111,21 @@ impl ResBody {
         (tx, rx)
     }
 
+    /// Wrap this body so that `trailers` are sent after all data of it.
+    ///
+    /// The `trailers` future is polled only after the data is terminated, so it can wait for the values
+    /// computed while streaming, such as checksums.
+    pub fn with_trailers<F>(self, trailers: F) -> Self
+    where
+        F: Future<Output = HeaderMap> + Send + 'static,
+    {
+        Self::Boxed(Box::pin(WithTrailers {
+            inner: self,
+            inner_done: false,
+            trailers: Some(SyncWrapper::new(Box::pin(trailers))),
+        }))
+    }
+
     /// Get body's size.
     #[inline]
     pub fn size(&self) -> Option<u64> {
@@ -217,6 +234,48 @@ impl Body for ResBody {
     }
 }
 
+struct WithTrailers {
+    inner: ResBody,
+    inner_done: bool,
+    trailers: Option<SyncWrapper<BoxFuture<'static, HeaderMap>>>,
+}
+impl Body for WithTrailers {
+    type Data = Bytes;
+    type Error = BoxedError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if !this.inner_done {
+            match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+                // The trailers of inner body are replaced.
+                Some(Ok(frame)) if frame.is_trailers() => this.inner_done = true,
+                Some(frame) => return Poll::Ready(Some(frame.map_err(Into::into))),
+                None => this.inner_done = true,
+            }
+        }
+        match &mut this.trailers {
+            Some(trailers) => {
+                let trailers = ready!(trailers.get_mut().as_mut().poll(cx));
+                this.trailers = None;
+                Poll::Ready(Some(Ok(Frame::trailers(trailers))))
+            }
+            None => Poll::Ready(None),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // An exact size makes HTTP/1.1 send `Content-Length`, and trailers are only sent with chunked encoding.
+        SizeHint::default()
+    }
+}
+
 impl Stream for ResBody {
     type Item = IoResult<Frame<Bytes>>;
 
@@ -0,0 +1,155 @@
+//! Conditional request evaluation.
+//!
+//! [`Precondition::evaluate`] evaluates `If-Match`, `If-Unmodified-Since`, `If-None-Match` and
+//! `If-Modified-Since` headers of request against the validators of the selected representation, in the
+//! order defined by [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#section-13.2.2). It is shared by
+//! [`NamedFile`](crate::fs::NamedFile), [`ReadSeeker`](crate::writing::ReadSeeker) and
+//! [`Response::check_preconditions`](crate::http::Response::check_preconditions).
+use std::time::SystemTime;
+
+use headers::{ETag, HeaderMapExt, IfMatch, IfModifiedSince, IfNoneMatch, IfUnmodifiedSince};
+use http::header::{HeaderMap, IF_MATCH, IF_NONE_MATCH};
+
+/// The result of evaluating the preconditions of a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Precondition {
+    /// All preconditions passed, the request should be processed normally.
+    Passed,
+    /// The representation is not modified, `304 Not Modified` should be responded for `GET` and `HEAD`
+    /// requests, and `412 Precondition Failed` for other methods.
+    NotModified,
+    /// A precondition failed, `412 Precondition Failed` should be responded.
+    Failed,
+}
+
+impl Precondition {
+    /// Evaluate the preconditions in `req_headers` against the current `etag` and `last_modified` of the
+    /// representation.
+    ///
+    /// `If-Match` is compared with strong comparison and `If-None-Match` with weak comparison,
+    /// `If-Unmodified-Since` and `If-Modified-Since` are ignored if the corresponding entity tag header is
+    /// present.
+    pub fn evaluate(
+        req_headers: &HeaderMap,
+        etag: Option<&ETag>,
+        last_modified: Option<SystemTime>,
+    ) -> Self {
+        if req_headers.contains_key(IF_MATCH) {
+            if !any_match(etag, req_headers) {
+                return Self::Failed;
+            }
+        } else if let (Some(last_modified), Some(since)) =
+            (last_modified, req_headers.typed_get::<IfUnmodifiedSince>())
+        {
+            if !since.precondition_passes(last_modified) {
+                return Self::Failed;
+            }
+        }
+
+        if req_headers.contains_key(IF_NONE_MATCH) {
+            if !none_match(etag, req_headers) {
+                return Self::NotModified;
+            }
+        } else if let (Some(last_modified), Some(since)) =
+            (last_modified, req_headers.typed_get::<IfModifiedSince>())
+        {
+            if !since.is_modified(last_modified) {
+                return Self::NotModified;
+            }
+        }
+        Self::Passed
+    }
+}
+
+/// Returns true if `req_headers` has no `If-Match` header or one which matches `etag`.
+fn any_match(etag: Option<&ETag>, req_headers: &HeaderMap) -> bool {
+    match req_headers.typed_get::<IfMatch>() {
+        None => true,
+        Some(if_match) => {
+            if if_match == IfMatch::any() {
+                true
+            } else if let Some(etag) = etag {
+                if_match.precondition_passes(etag)
+            } else {
+                false
+            }
+        }
+    }
+}
+
+/// Returns true if `req_headers` doesn't have an `If-None-Match` header matching `etag`.
+fn none_match(etag: Option<&ETag>, req_headers: &HeaderMap) -> bool {
+    match req_headers.typed_get::<IfNoneMatch>() {
+        None => true,
+        Some(if_none_match) => {
+            if if_none_match == IfNoneMatch::any() {
+                false
+            } else if let Some(etag) = etag {
+                if_none_match.precondition_passes(etag)
+            } else {
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use headers::LastModified;
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_evaluate_preconditions() {
+        let etag = "\"xyzzy\"".parse::<ETag>().unwrap();
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let evaluate = |name: &'static str, value: HeaderValue| {
+            let mut headers = HeaderMap::new();
+            headers.insert(name, value);
+            Precondition::evaluate(&headers, Some(&etag), Some(modified))
+        };
+        let since = |secs: u64| {
+            let mut headers = HeaderMap::new();
+            headers.typed_insert(LastModified::from(
+                SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            ));
+            headers.remove("last-modified").unwrap()
+        };
+
+        assert_eq!(
+            Precondition::evaluate(&HeaderMap::new(), Some(&etag), Some(modified)),
+            Precondition::Passed
+        );
+        assert_eq!(
+            evaluate("if-none-match", HeaderValue::from_static("W/\"xyzzy\"")),
+            Precondition::NotModified
+        );
+        assert_eq!(
+            evaluate("if-none-match", HeaderValue::from_static("\"other\"")),
+            Precondition::Passed
+        );
+        assert_eq!(
+            evaluate("if-match", HeaderValue::from_static("\"other\"")),
+            Precondition::Failed
+        );
+        assert_eq!(
+            evaluate("if-match", HeaderValue::from_static("*")),
+            Precondition::Passed
+        );
+        assert_eq!(
+            evaluate("if-modified-since", since(1_000_000)),
+            Precondition::NotModified
+        );
+        assert_eq!(
+            evaluate("if-modified-since", since(999_999)),
+            Precondition::Passed
+        );
+        assert_eq!(
+            evaluate("if-unmodified-since", since(999_999)),
+            Precondition::Failed
+        );
+    }
+}
@@ -2,5 +2,7 @@
 
 mod parse_error;
 mod status_error;
-pub use parse_error::{ParseError, ParseResult};
+pub use parse_error::{
+    FieldError, ParseError, ParseErrorRenderer, ParseResult, set_parse_error_renderer,
+};
 pub use status_error::{StatusError, StatusResult};
@@ -1,16 +1,115 @@
-use std::fmt::Debug;
+use std::error::Error as StdError;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::io::Error as IoError;
 use std::str::Utf8Error;
 
+use http_body_util::LengthLimitError;
+use parking_lot::RwLock;
 use serde::de::value::Error as DeError;
 use thiserror::Error;
 
-use crate::http::{Request, Response, StatusError};
+use crate::extract::metadata::SourceFrom;
+use crate::http::{Request, Response, StatusCode, StatusError};
+use crate::writing::Problem;
 use crate::{BoxedError, Depot, Writer, async_trait};
 
 /// Result type with `ParseError` has it's error type.
 pub type ParseResult<T> = Result<T, ParseError>;
 
+/// Function used to render [`ParseError`] to response, view [`set_parse_error_renderer`] for more details.
+pub type ParseErrorRenderer = fn(ParseError, &Request, &mut Response);
+
+static PARSE_ERROR_RENDERER: RwLock<Option<ParseErrorRenderer>> = RwLock::new(None);
+
+/// Set the renderer used to write [`ParseError`] to response globally.
+///
+/// By default, extraction failures are responded as `400 Bad Request` (or `413 Payload Too Large`) status
+/// errors. Use [`ParseError::render_problem`] to respond them as
+/// [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) `application/problem+json` documents instead:
+///
+/// ```
+/// use salvo_core::http::ParseError;
+/// use salvo_core::http::errors::set_parse_error_renderer;
+///
+/// set_parse_error_renderer(Some(ParseError::render_problem));
+/// ```
+///
+/// Pass `None` to restore the default renderer.
+pub fn set_parse_error_renderer(renderer: Option<ParseErrorRenderer>) {
+    *PARSE_ERROR_RENDERER.write() = renderer;
+}
+
+/// Details about a field which failed to be extracted from request.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct FieldError {
+    /// Path of the field, it is the name used in request.
+    pub path: String,
+    /// Where the field is extracted from, `None` if it is unknown.
+    pub source_from: Option<SourceFrom>,
+    /// The expected type of the field, e.g. `i64`, `None` if it is unknown.
+    pub expected: Option<String>,
+    /// The message describing the failure.
+    pub message: String,
+}
+impl FieldError {
+    /// Create a new `FieldError`.
+    ///
+    /// If `message` is a deserializer message like `invalid type: string "a", expected u32`, the expected
+    /// type is taken from it.
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        let message = message.into();
+        let expected = message.rsplit_once("expected ").map(|(_, expected)| {
+            expected
+                .split(" at line ")
+                .next()
+                .unwrap_or(expected)
+                .to_owned()
+        });
+        Self {
+            path: path.into(),
+            source_from: None,
+            expected,
+            message,
+        }
+    }
+
+    /// Sets where the field is extracted from and returns `Self`.
+    pub fn source_from(mut self, source_from: SourceFrom) -> Self {
+        self.source_from = Some(source_from);
+        self
+    }
+
+    /// Sets the expected type and returns `Self`.
+    pub fn expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+}
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid field `{}`", self.path)?;
+        if let Some(source_from) = self.source_from {
+            write!(f, " from {}", source_from_name(source_from))?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+impl StdError for FieldError {}
+
+#[allow(unreachable_patterns)]
+fn source_from_name(source_from: SourceFrom) -> &'static str {
+    match source_from {
+        SourceFrom::Param => "param",
+        SourceFrom::Query => "query",
+        SourceFrom::Header => "header",
+        #[cfg(feature = "cookie")]
+        SourceFrom::Cookie => "cookie",
+        SourceFrom::Body => "body",
+        _ => "unknown",
+    }
+}
+
 /// Errors happened when read data from http request.
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -53,10 +152,18 @@ pub enum ParseError {
     #[error("invalid header value: {0}")]
     InvalidHeaderValue(#[from] http::header::InvalidHeaderValue),
 
+    /// A typed header is invalid.
+    #[error("invalid typed header: {0}")]
+    InvalidTypedHeader(#[from] headers::Error),
+
     /// Deserialize error when parse from request.
     #[error("deserialize error: {0}")]
     Deserialize(#[from] DeError),
 
+    /// A field can not be extracted from request.
+    #[error("{0}")]
+    InvalidField(Box<FieldError>),
+
     /// DuplicateKey.
     #[error("duplicate key")]
     DuplicateKey,
@@ -69,6 +176,10 @@ pub enum ParseError {
     #[error("the Hyper request Content-Type sub-level Mime was not `FormData`.")]
     NotFormData,
 
+    /// The request body is larger than the limit.
+    #[error("the request body is too large")]
+    PayloadTooLarge,
+
     /// InvalidRange.
     #[error("invalid range")]
     InvalidRange,
@@ -93,6 +204,26 @@ pub enum ParseError {
     #[error("serde json error: {0}")]
     SerdeJson(#[from] serde_json::error::Error),
 
+    /// MessagePack decode error.
+    #[cfg(feature = "msgpack")]
+    #[error("msgpack error: {0}")]
+    MsgPack(#[from] rmp_serde::decode::Error),
+
+    /// CBOR decode error.
+    #[cfg(feature = "cbor")]
+    #[error("cbor error: {0}")]
+    Cbor(#[from] ciborium::de::Error<IoError>),
+
+    /// YAML decode error.
+    #[cfg(feature = "yaml")]
+    #[error("yaml error: {0}")]
+    Yaml(#[from] serde_norway::Error),
+
+    /// Protocol Buffers decode error.
+    #[cfg(feature = "protobuf")]
+    #[error("protobuf error: {0}")]
+    ProtoBuf(#[from] prost::DecodeError),
+
     /// Custom error that does not fall under any other error kind.
     #[error("other error: {0}")]
     Other(BoxedError),
@@ -103,23 +234,128 @@ impl ParseError {
     pub fn other(error: impl Into<BoxedError>) -> Self {
         Self::Other(error.into())
     }
+
+    /// Create an error for the field with `path` which can not be extracted from request.
+    pub fn invalid_field(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::InvalidField(Box::new(FieldError::new(path, message)))
+    }
+
+    /// Get the details of the field which failed to be extracted, if the error is caused by a field.
+    pub fn field_error(&self) -> Option<&FieldError> {
+        match self {
+            Self::InvalidField(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Render the error as an [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) `application/problem+json`
+    /// document, view [`Problem`] for more details.
+    ///
+    /// If the error is caused by a field, the `field`, `source` and `expected` members describe it. It can be
+    /// registered as the global renderer with [`set_parse_error_renderer`].
+    pub fn render_problem(self, req: &Request, res: &mut Response) {
+        let mut problem = Problem::new(self.status_code())
+            .detail(self.to_string())
+            .instance(req.uri().path());
+        if let Some(field) = self.field_error() {
+            problem = problem.extension("field", &field.path);
+            if let Some(source_from) = field.source_from {
+                problem = problem.extension("source", source_from_name(source_from));
+            }
+            if let Some(expected) = &field.expected {
+                problem = problem.extension("expected", expected);
+            }
+        }
+        res.render(problem);
+    }
+
+    fn status_code(&self) -> StatusCode {
+        if matches!(self, Self::PayloadTooLarge) {
+            StatusCode::PAYLOAD_TOO_LARGE
+        } else {
+            StatusCode::BAD_REQUEST
+        }
+    }
+
+    /// Create an error from the error happened when read request body, the error caused by exceeding the body
+    /// size limit is converted to [`ParseError::PayloadTooLarge`].
+    pub(crate) fn from_body_error(error: impl Into<BoxedError>) -> Self {
+        let error = error.into();
+        if is_length_limit_error(&*error) {
+            Self::PayloadTooLarge
+        } else {
+            Self::Other(error)
+        }
+    }
+
+    /// Convert the error caused by exceeding the body size limit to [`ParseError::PayloadTooLarge`].
+    pub(crate) fn check_payload_too_large(self) -> Self {
+        let too_large = match &self {
+            Self::Multer(multer::Error::StreamReadFailed(e)) => is_length_limit_error(&**e),
+            Self::Multer(e) => is_length_limit_error(e),
+            Self::Io(e) => is_length_limit_error(e),
+            Self::Hyper(e) => is_length_limit_error(e),
+            Self::Other(e) => is_length_limit_error(&**e),
+            _ => false,
+        };
+        if too_large {
+            Self::PayloadTooLarge
+        } else {
+            self
+        }
+    }
+}
+
+fn is_length_limit_error(mut error: &(dyn StdError + 'static)) -> bool {
+    loop {
+        if error.is::<LengthLimitError>() {
+            return true;
+        }
+        if let Some(inner) = error.downcast_ref::<IoError>().and_then(|e| e.get_ref()) {
+            error = inner;
+            continue;
+        }
+        match error.source() {
+            Some(source) => error = source,
+            None => return false,
+        }
+    }
+}
+
+impl serde::de::Error for ParseError {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Deserialize(DeError::custom(msg))
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self::invalid_field(field, "missing field")
+    }
 }
 
 #[async_trait]
 impl Writer for ParseError {
-    async fn write(self, _req: &mut Request, _depot: &mut Depot, res: &mut Response) {
-        res.render(
-            StatusError::bad_request()
-                .brief("parse http data failed.")
-                .cause(self),
-        );
+    async fn write(self, req: &mut Request, _depot: &mut Depot, res: &mut Response) {
+        let renderer = *PARSE_ERROR_RENDERER.read();
+        if let Some(renderer) = renderer {
+            renderer(self, req, res);
+        } else if matches!(self, Self::PayloadTooLarge) {
+            res.render(StatusError::payload_too_large().brief("the request body is too large."));
+        } else {
+            res.render(
+                StatusError::bad_request()
+                    .brief("parse http data failed.")
+                    .cause(self),
+            );
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::header::CONTENT_TYPE;
     use crate::prelude::*;
+    use crate::test::ResponseExt;
 
     #[tokio::test]
     async fn test_write_error() {
@@ -129,4 +365,50 @@ mod tests {
         let err = ParseError::EmptyBody;
         err.write(&mut req, &mut depot, &mut res).await;
     }
+
+    #[tokio::test]
+    async fn test_payload_too_large() {
+        use http_body_util::{BodyExt, Full, Limited};
+
+        let error = Limited::new(Full::new(bytes::Bytes::from("abc")), 1)
+            .collect()
+            .await
+            .unwrap_err();
+        let err = ParseError::from_body_error(IoError::other(error));
+        assert!(matches!(err, ParseError::PayloadTooLarge));
+        let err = ParseError::Io(IoError::other("other")).check_payload_too_large();
+        assert!(matches!(err, ParseError::Io(_)));
+
+        let mut res = Response::default();
+        ParseError::PayloadTooLarge
+            .write(&mut Request::default(), &mut Depot::new(), &mut res)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+    }
+
+    #[tokio::test]
+    async fn test_render_problem() {
+        let req = Request::default();
+        let mut res = Response::default();
+        ParseError::InvalidField(Box::new(
+            FieldError::new("age", "invalid type: string \"old\", expected u8")
+                .source_from(SourceFrom::Query),
+        ))
+        .render_problem(&req, &mut res);
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+        assert_eq!(
+            res.headers().get(CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+        let problem: serde_json::Value =
+            serde_json::from_slice(&res.take_bytes(None).await.unwrap()).unwrap();
+        assert_eq!(problem["status"], 400);
+        assert_eq!(problem["field"], "age");
+        assert_eq!(problem["source"], "query");
+        assert_eq!(problem["expected"], "u8");
+
+        let mut res = Response::default();
+        ParseError::PayloadTooLarge.render_problem(&req, &mut res);
+        assert_eq!(res.status_code, Some(StatusCode::PAYLOAD_TOO_LARGE));
+    }
 }
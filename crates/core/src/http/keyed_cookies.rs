@@ -0,0 +1,204 @@
+//! Signed and private (encrypted) cookies with key rotation.
+//!
+//! Signed cookies are authenticated with HMAC-SHA256, so the client can read but can not tamper with them.
+//! Private cookies are encrypted with AEAD (AES-256-GCM), so the client can neither read nor tamper with them.
+//!
+//! [`CookieKeys`] holds the primary key which is used to sign or encrypt new cookies, and the fallback keys
+//! which are still accepted when verifying or decrypting cookies, so keys can be rotated without invalidating
+//! the cookies issued with the previous keys.
+//!
+//! # Example
+//!
+//! ```
+//! use salvo_core::http::cookie::{Cookie, Key};
+//! use salvo_core::http::keyed_cookies::CookieKeys;
+//! use salvo_core::prelude::*;
+//!
+//! struct Visits {
+//!     keys: CookieKeys,
+//! }
+//! #[async_trait]
+//! impl Handler for Visits {
+//!     async fn handle(&self, req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+//!         let visits = req
+//!             .private_cookies(&self.keys)
+//!             .get("visits")
+//!             .and_then(|cookie| cookie.value().parse::<u64>().ok())
+//!             .unwrap_or_default()
+//!             + 1;
+//!         res.add_private_cookie(Cookie::new("visits", visits.to_string()), &self.keys);
+//!         res.render(format!("visits: {visits}"));
+//!     }
+//! }
+//!
+//! let visits = Visits {
+//!     keys: CookieKeys::new(Key::generate()).fallback(Key::generate()),
+//! };
+//! let router = Router::new().get(visits);
+//! ```
+use std::fmt::{self, Debug, Formatter};
+
+use cookie::{Cookie, CookieJar, Key};
+
+/// Keys used to sign and encrypt cookies.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct CookieKeys {
+    primary: Key,
+    fallbacks: Vec<Key>,
+}
+impl CookieKeys {
+    /// Create a new `CookieKeys` with the primary key.
+    #[inline]
+    pub fn new(primary: Key) -> Self {
+        Self {
+            primary,
+            fallbacks: Vec::new(),
+        }
+    }
+
+    /// Add a fallback key, it is used only to verify and decrypt cookies.
+    ///
+    /// Usually it is the previous primary key during key rotation.
+    #[inline]
+    pub fn fallback(mut self, key: Key) -> Self {
+        self.fallbacks.push(key);
+        self
+    }
+
+    /// Get the primary key, which is used to sign and encrypt cookies.
+    #[inline]
+    pub fn primary(&self) -> &Key {
+        &self.primary
+    }
+
+    /// Iterate over all keys, the primary key is the first.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &Key> {
+        std::iter::once(&self.primary).chain(self.fallbacks.iter())
+    }
+}
+impl From<Key> for CookieKeys {
+    #[inline]
+    fn from(key: Key) -> Self {
+        Self::new(key)
+    }
+}
+impl Debug for CookieKeys {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CookieKeys")
+            .field("fallbacks", &self.fallbacks.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A read-only view of the request cookies which verifies signed cookies, created by
+/// [`Request::signed_cookies`](crate::http::Request::signed_cookies).
+#[derive(Debug)]
+pub struct SignedCookies<'a> {
+    jar: &'a CookieJar,
+    keys: &'a CookieKeys,
+}
+impl<'a> SignedCookies<'a> {
+    pub(crate) fn new(jar: &'a CookieJar, keys: &'a CookieKeys) -> Self {
+        Self { jar, keys }
+    }
+
+    /// Get the cookie with `name` and verify its signature with any of the keys.
+    ///
+    /// Returns `None` if the cookie does not exist or its signature is invalid. The value of the returned
+    /// cookie is the original value without the signature.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.keys
+            .iter()
+            .find_map(|key| self.jar.signed(key).get(name))
+    }
+}
+
+/// A read-only view of the request cookies which decrypts private cookies, created by
+/// [`Request::private_cookies`](crate::http::Request::private_cookies).
+#[derive(Debug)]
+pub struct PrivateCookies<'a> {
+    jar: &'a CookieJar,
+    keys: &'a CookieKeys,
+}
+impl<'a> PrivateCookies<'a> {
+    pub(crate) fn new(jar: &'a CookieJar, keys: &'a CookieKeys) -> Self {
+        Self { jar, keys }
+    }
+
+    /// Get the cookie with `name` and decrypt it with any of the keys.
+    ///
+    /// Returns `None` if the cookie does not exist or it can not be decrypted and authenticated.
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.keys
+            .iter()
+            .find_map(|key| self.jar.private(key).get(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{Request, Response};
+
+    fn transfer(res: &Response) -> Request {
+        let mut req = Request::new();
+        for cookie in res.cookies().delta() {
+            req.cookies_mut().add_original(cookie.clone());
+        }
+        req
+    }
+
+    #[test]
+    fn test_signed_cookies() {
+        let old = Key::generate();
+        let keys = CookieKeys::new(Key::generate()).fallback(old.clone());
+
+        let mut res = Response::new();
+        res.add_signed_cookie(Cookie::new("user", "jobs"), &keys);
+        assert_ne!(res.cookie("user").unwrap().value(), "jobs");
+        let req = transfer(&res);
+        assert_eq!(
+            req.signed_cookies(&keys).get("user").unwrap().value(),
+            "jobs"
+        );
+
+        let mut res = Response::new();
+        res.add_signed_cookie(Cookie::new("user", "jobs"), &CookieKeys::new(old));
+        let req = transfer(&res);
+        assert_eq!(
+            req.signed_cookies(&keys).get("user").unwrap().value(),
+            "jobs"
+        );
+        assert!(
+            req.signed_cookies(&CookieKeys::new(Key::generate()))
+                .get("user")
+                .is_none()
+        );
+
+        let mut req = Request::new();
+        req.cookies_mut().add_original(Cookie::new("user", "gates"));
+        assert!(req.signed_cookies(&keys).get("user").is_none());
+    }
+
+    #[test]
+    fn test_private_cookies() {
+        let keys = CookieKeys::new(Key::generate());
+
+        let mut res = Response::new();
+        res.add_private_cookie(Cookie::new("token", "secret"), &keys);
+        assert!(!res.cookie("token").unwrap().value().contains("secret"));
+        let req = transfer(&res);
+        assert_eq!(
+            req.private_cookies(&keys).get("token").unwrap().value(),
+            "secret"
+        );
+        assert!(
+            req.private_cookies(&CookieKeys::new(Key::generate()))
+                .get("token")
+                .is_none()
+        );
+    }
+}
@@ -1,20 +1,30 @@
 //! The HTTP related types and functions.
 
+pub mod conditional;
 pub mod errors;
 pub mod form;
+pub mod multipart;
+pub mod ndjson;
 mod range;
 pub mod request;
 pub mod response;
 cfg_feature! {
     #![feature = "cookie"]
     pub use cookie;
+    pub mod keyed_cookies;
 }
+pub use conditional::Precondition;
 pub use errors::{ParseError, ParseResult, StatusError, StatusResult};
 pub use headers;
 pub use http::method::Method;
 pub use http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header, method, uri};
 pub use mime::{self, Mime};
 pub use range::HttpRange;
+cfg_feature! {
+    #![feature = "real-ip"]
+    pub mod real_ip;
+    pub use real_ip::{RealIp, RealIpHeader};
+}
 pub use request::Request;
 pub mod body;
 pub use body::{Body, ReqBody, ResBody};
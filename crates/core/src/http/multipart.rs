@@ -0,0 +1,273 @@
+//! Streaming `multipart/form-data` parse module.
+//!
+//! Unlike [`FormData`](crate::http::form::FormData), which writes uploaded files into temporary files, the
+//! [`Multipart`] yields fields one by one, and the content of each field is a stream of [`Bytes`], so large
+//! uploads can be piped to disk or object storage without buffering the entire file in memory.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use salvo_core::http::ParseError;
+//! use salvo_core::http::multipart::MultipartLimits;
+//! use salvo_core::prelude::*;
+//! use tokio::io::AsyncWriteExt;
+//!
+//! #[handler]
+//! async fn upload(req: &mut Request) -> Result<&'static str, ParseError> {
+//!     let limits = MultipartLimits::new().max_fields(8).max_field_size(1024 * 1024 * 1024);
+//!     let mut multipart = req.multipart_with_limits(limits)?;
+//!     while let Some(mut field) = multipart.next_field().await? {
+//!         if let Some(file_name) = field.file_name() {
+//!             let mut file = tokio::fs::File::create(format!("temp/{file_name}")).await?;
+//!             while let Some(chunk) = field.next().await {
+//!                 file.write_all(&chunk?).await?;
+//!             }
+//!         }
+//!     }
+//!     Ok("uploaded")
+//! }
+//! ```
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_util::stream::{Stream, StreamExt};
+use mime::Mime;
+use multer::{Constraints, SizeLimit};
+
+use crate::http::body::ReqBody;
+use crate::http::header::HeaderMap;
+use crate::http::{ParseError, ParseResult};
+
+/// Limits of the streaming [`Multipart`], there is no limit by default.
+///
+/// The whole body is always limited by [`Request::max_body_size`](crate::http::Request::max_body_size).
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct MultipartLimits {
+    /// The maximum number of fields.
+    pub max_fields: Option<usize>,
+    /// The maximum size of each field, in bytes.
+    pub max_field_size: Option<u64>,
+    /// The maximum size of all fields, in bytes.
+    pub max_total_size: Option<u64>,
+}
+impl MultipartLimits {
+    /// Create a new `MultipartLimits` without any limit.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set the maximum number of fields.
+    #[inline]
+    pub fn max_fields(mut self, max: usize) -> Self {
+        self.max_fields = Some(max);
+        self
+    }
+    /// Set the maximum size of each field, in bytes.
+    #[inline]
+    pub fn max_field_size(mut self, max: u64) -> Self {
+        self.max_field_size = Some(max);
+        self
+    }
+    /// Set the maximum size of all fields, in bytes.
+    #[inline]
+    pub fn max_total_size(mut self, max: u64) -> Self {
+        self.max_total_size = Some(max);
+        self
+    }
+}
+
+/// A streaming `multipart/form-data` parser, created by
+/// [`Request::multipart`](crate::http::Request::multipart).
+///
+/// View [module level documentation](index.html) for more details.
+pub struct Multipart {
+    inner: multer::Multipart<'static>,
+    max_fields: Option<usize>,
+    count: usize,
+}
+impl Multipart {
+    pub(crate) fn new(body: ReqBody, boundary: String, limits: MultipartLimits) -> Self {
+        let mut size_limit = SizeLimit::new();
+        if let Some(max) = limits.max_field_size {
+            size_limit = size_limit.per_field(max);
+        }
+        if let Some(max) = limits.max_total_size {
+            size_limit = size_limit.whole_stream(max);
+        }
+        let body = body.map(|f| f.map(|f| f.into_data().unwrap_or_default()));
+        Self {
+            inner: multer::Multipart::with_constraints(
+                body,
+                boundary,
+                Constraints::new().size_limit(size_limit),
+            ),
+            max_fields: limits.max_fields,
+            count: 0,
+        }
+    }
+
+    /// Yields the next field, returns `None` if there is no more field.
+    ///
+    /// The previous field should be dropped before calling this method.
+    pub async fn next_field(&mut self) -> ParseResult<Option<Field>> {
+        let field = self.inner.next_field().await.map_err(map_multer_error)?;
+        if field.is_some() {
+            self.count += 1;
+            if self.max_fields.is_some_and(|max| self.count > max) {
+                return Err(ParseError::PayloadTooLarge);
+            }
+        }
+        Ok(field.map(|inner| Field { inner }))
+    }
+}
+
+/// A field of the streaming [`Multipart`], it is a stream of [`Bytes`] chunks.
+pub struct Field {
+    inner: multer::Field<'static>,
+}
+impl Field {
+    /// The field name found in the `Content-Disposition` header.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+    /// The file name found in the `Content-Disposition` header, it is `None` if the field is not a file.
+    #[inline]
+    pub fn file_name(&self) -> Option<&str> {
+        self.inner.file_name()
+    }
+    /// The content type of the field.
+    #[inline]
+    pub fn content_type(&self) -> Option<&Mime> {
+        self.inner.content_type()
+    }
+    /// The headers of the field.
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        self.inner.headers()
+    }
+    /// The index of the field, starts from `0`.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.inner.index()
+    }
+
+    /// Get the next chunk of the field content, returns `None` when the field is ended.
+    pub async fn chunk(&mut self) -> ParseResult<Option<Bytes>> {
+        self.inner.chunk().await.map_err(map_multer_error)
+    }
+    /// Read the whole field content into memory.
+    pub async fn bytes(self) -> ParseResult<Bytes> {
+        self.inner.bytes().await.map_err(map_multer_error)
+    }
+    /// Read the whole field content into memory as text.
+    pub async fn text(self) -> ParseResult<String> {
+        self.inner.text().await.map_err(map_multer_error)
+    }
+}
+impl Stream for Field {
+    type Item = ParseResult<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|item| item.map(|chunk| chunk.map_err(map_multer_error)))
+    }
+}
+
+fn map_multer_error(e: multer::Error) -> ParseError {
+    match e {
+        multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. } => {
+            ParseError::PayloadTooLarge
+        }
+        e => ParseError::Multer(e).check_payload_too_large(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::Request;
+    use crate::http::header::CONTENT_TYPE;
+
+    fn request(body: &'static str) -> Request {
+        let mut req = Request::new();
+        req.headers_mut().insert(
+            CONTENT_TYPE,
+            "multipart/form-data; boundary=X-BOUNDARY".parse().unwrap(),
+        );
+        *req.body_mut() = ReqBody::Once(Bytes::from(body.replace('\n', "\r\n")));
+        req
+    }
+
+    const BODY: &str = "--X-BOUNDARY
+Content-Disposition: form-data; name=\"name\"
+
+salvo
+--X-BOUNDARY
+Content-Disposition: form-data; name=\"file\"; filename=\"hello.txt\"
+Content-Type: text/plain
+
+hello world
+--X-BOUNDARY--
+";
+
+    #[tokio::test]
+    async fn test_multipart() {
+        let mut req = request(BODY);
+        let mut multipart = req.multipart().unwrap();
+        let field = multipart.next_field().await.unwrap().unwrap();
+        assert_eq!(field.name(), Some("name"));
+        assert!(field.file_name().is_none());
+        assert_eq!(field.text().await.unwrap(), "salvo");
+
+        let mut field = multipart.next_field().await.unwrap().unwrap();
+        assert_eq!(field.file_name(), Some("hello.txt"));
+        assert_eq!(field.content_type(), Some(&mime::TEXT_PLAIN));
+        let mut content = Vec::new();
+        while let Some(chunk) = field.next().await {
+            content.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(content, b"hello world");
+        drop(field);
+        assert!(multipart.next_field().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_multipart_limits() {
+        let mut req = request(BODY);
+        let mut multipart = req
+            .multipart_with_limits(MultipartLimits::new().max_fields(1))
+            .unwrap();
+        drop(multipart.next_field().await.unwrap());
+        assert!(matches!(
+            multipart.next_field().await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+
+        let mut req = request(BODY);
+        let mut multipart = req
+            .multipart_with_limits(MultipartLimits::new().max_field_size(4))
+            .unwrap();
+        let field = multipart.next_field().await.unwrap().unwrap();
+        assert!(matches!(
+            field.text().await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+
+        let mut req = request(BODY);
+        req.set_max_body_size(16);
+        let mut multipart = req.multipart().unwrap();
+        let result = async {
+            while let Some(field) = multipart.next_field().await? {
+                field.bytes().await?;
+            }
+            Ok::<_, ParseError>(())
+        }
+        .await;
+        assert!(matches!(result, Err(ParseError::PayloadTooLarge)));
+    }
+}
@@ -0,0 +1,185 @@
+//! Streaming [JSON Lines](https://jsonlines.org) / NDJSON request body parse module.
+//!
+//! [`NdJsonStream`] decodes the request body line by line while it is received, every non-empty line is
+//! deserialized as a value, so large ingestion payloads are handled without buffering the entire body.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use futures_util::StreamExt;
+//! use salvo_core::http::ParseError;
+//! use salvo_core::prelude::*;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Log {
+//!     level: String,
+//!     message: String,
+//! }
+//!
+//! #[handler]
+//! async fn ingest(req: &mut Request) -> Result<String, ParseError> {
+//!     let mut logs = req.ndjson::<Log>()?;
+//!     let mut count = 0;
+//!     while let Some(log) = logs.next().await {
+//!         let log = log?;
+//!         tracing::info!(level = log.level, "{}", log.message);
+//!         count += 1;
+//!     }
+//!     Ok(format!("{count} logs ingested"))
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::BytesMut;
+use futures_util::stream::Stream;
+use hyper::body::Body;
+use serde::de::DeserializeOwned;
+
+use crate::extract::{Extractible, Metadata};
+use crate::http::body::ReqBody;
+use crate::http::{ParseError, ParseResult, Request};
+
+/// A stream of values decoded from a newline-delimited JSON request body, created by
+/// [`Request::ndjson`](crate::http::Request::ndjson).
+///
+/// Empty lines are skipped, and the last line is decoded even if it is not terminated by a newline.
+///
+/// View [module level documentation](index.html) for more details.
+pub struct NdJsonStream<T> {
+    body: ReqBody,
+    buffer: BytesMut,
+    finished: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T> NdJsonStream<T> {
+    pub(crate) fn new(body: ReqBody) -> Self {
+        Self {
+            body,
+            buffer: BytesMut::new(),
+            finished: false,
+            _marker: PhantomData,
+        }
+    }
+
+    fn next_line(&mut self) -> Option<BytesMut> {
+        loop {
+            let line = if let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+                let mut line = self.buffer.split_to(pos + 1);
+                line.truncate(pos);
+                line
+            } else if self.finished && !self.buffer.is_empty() {
+                self.buffer.split()
+            } else {
+                return None;
+            };
+            if !line.trim_ascii().is_empty() {
+                return Some(line);
+            }
+        }
+    }
+}
+
+impl<T> Stream for NdJsonStream<T>
+where
+    T: DeserializeOwned,
+{
+    type Item = ParseResult<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(line) = this.next_line() {
+                return Poll::Ready(Some(
+                    serde_json::from_slice(&line).map_err(ParseError::SerdeJson),
+                ));
+            }
+            if this.finished {
+                return Poll::Ready(None);
+            }
+            match Pin::new(&mut this.body).poll_frame(cx) {
+                Poll::Ready(Some(Ok(frame))) => {
+                    if let Ok(data) = frame.into_data() {
+                        this.buffer.extend_from_slice(&data);
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    this.finished = true;
+                    this.buffer.clear();
+                    return Poll::Ready(Some(Err(ParseError::from_body_error(e))));
+                }
+                Poll::Ready(None) => this.finished = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<T> Debug for NdJsonStream<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NdJsonStream")
+            .field("buffered", &self.buffer.len())
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl<'ex, T> Extractible<'ex> for NdJsonStream<T>
+where
+    T: DeserializeOwned + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    #[allow(refining_impl_trait)]
+    async fn extract(req: &'ex mut Request) -> Result<Self, ParseError> {
+        req.ndjson()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::StreamExt;
+    use http_body_util::StreamBody;
+    use hyper::body::Frame;
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::BoxedError;
+    use crate::http::header::CONTENT_TYPE;
+
+    #[derive(Deserialize, PartialEq, Debug)]
+    struct Log {
+        id: u32,
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_stream() {
+        let mut req = Request::new();
+        req.headers_mut()
+            .insert(CONTENT_TYPE, "application/x-ndjson".parse().unwrap());
+        let chunks = ["{\"id\":1}\n{\"i", "d\":2}\r\n\n", "{\"id\":3}"]
+            .into_iter()
+            .map(|chunk| Ok::<_, BoxedError>(Frame::data(Bytes::from_static(chunk.as_bytes()))));
+        *req.body_mut() = ReqBody::Boxed {
+            inner: Box::pin(StreamBody::new(futures_util::stream::iter(chunks))),
+            fusewire: None,
+        };
+        let logs = NdJsonStream::<Log>::extract(&mut req).await.unwrap();
+        let logs = logs.map(|log| log.unwrap()).collect::<Vec<_>>().await;
+        assert_eq!(logs, vec![Log { id: 1 }, Log { id: 2 }, Log { id: 3 }]);
+
+        let mut req = Request::new();
+        req.headers_mut()
+            .insert(CONTENT_TYPE, "application/json".parse().unwrap());
+        assert!(matches!(
+            req.ndjson::<Log>(),
+            Err(ParseError::InvalidContentType)
+        ));
+    }
+}
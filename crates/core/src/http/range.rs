@@ -1,3 +1,10 @@
+use std::collections::VecDeque;
+use std::io::{Error as IoError, ErrorKind, SeekFrom};
+
+use bytes::Bytes;
+use futures_util::stream::{self, Stream};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
 use crate::http::ParseError;
 
 /// HTTP Range header representation.
@@ -99,6 +106,66 @@ impl HttpRange {
     }
 }
 
+const CHUNK_SIZE: u64 = 64 * 1024;
+
+/// A segment of the body sent by [`Response::send_seekable`](crate::http::Response::send_seekable).
+#[derive(Debug)]
+pub(crate) enum Segment {
+    /// Bytes sent as is, such as the headers of a `multipart/byteranges` part.
+    Bytes(Bytes),
+    /// A range read from the reader.
+    Range(HttpRange),
+}
+impl Segment {
+    pub(crate) fn len(&self) -> u64 {
+        match self {
+            Self::Bytes(bytes) => bytes.len() as u64,
+            Self::Range(range) => range.length,
+        }
+    }
+}
+
+/// Read the segments from a seekable reader as a stream.
+pub(crate) fn seekable_stream<R>(
+    reader: R,
+    segments: Vec<Segment>,
+) -> impl Stream<Item = Result<Bytes, IoError>> + Send + 'static
+where
+    R: AsyncRead + AsyncSeek + Send + Unpin + 'static,
+{
+    let state = (reader, VecDeque::from(segments), None::<u64>);
+    stream::try_unfold(state, |(mut reader, mut segments, mut pos)| async move {
+        loop {
+            match segments.front_mut() {
+                None => return Ok(None),
+                Some(Segment::Bytes(_)) => {
+                    if let Some(Segment::Bytes(bytes)) = segments.pop_front() {
+                        return Ok(Some((bytes, (reader, segments, pos))));
+                    }
+                }
+                Some(Segment::Range(range)) if range.length == 0 => {
+                    segments.pop_front();
+                }
+                Some(Segment::Range(range)) => {
+                    if pos != Some(range.start) {
+                        reader.seek(SeekFrom::Start(range.start)).await?;
+                    }
+                    let mut buf = vec![0; range.length.min(CHUNK_SIZE) as usize];
+                    let size = reader.read(&mut buf).await?;
+                    if size == 0 {
+                        return Err(IoError::from(ErrorKind::UnexpectedEof));
+                    }
+                    buf.truncate(size);
+                    range.start += size as u64;
+                    range.length -= size as u64;
+                    pos = Some(range.start);
+                    return Ok(Some((Bytes::from(buf), (reader, segments, pos))));
+                }
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
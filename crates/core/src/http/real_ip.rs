@@ -0,0 +1,392 @@
+//! Resolve the real client address of requests forwarded by trusted proxies.
+//!
+//! When the server runs behind load balancers or reverse proxies, the peer address of the
+//! connection is the address of the nearest proxy. [`RealIp`] resolves the client address from
+//! the forwarding headers, but only when the peer is a trusted proxy, because the headers can be
+//! forged by anyone else.
+//!
+//! Set it to the [`Service`](crate::Service) and the resolved address is returned by
+//! [`Request::client_ip`], which is used by the middlewares identifying clients, such as rate
+//! limiting, logging and IP filtering.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::http::RealIp;
+//! use salvo_core::prelude::*;
+//!
+//! #[handler]
+//! async fn hello(req: &mut Request) -> String {
+//!     format!("Hello {:?}", req.client_ip())
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let real_ip = RealIp::new().trusted_proxy("10.0.0.0/8");
+//!     let service = Service::new(Router::new().get(hello)).real_ip(real_ip);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(service).await;
+//! }
+//! ```
+use std::net::IpAddr;
+
+use http::HeaderName;
+use http::header::FORWARDED;
+use http::uri::Scheme;
+use ipnet::IpNet;
+
+use crate::http::Request;
+
+/// The headers which carry the client address, see [`RealIp::headers`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RealIpHeader {
+    /// The `for` parameters of the standard `Forwarded` header, a list of hops.
+    Forwarded,
+    /// The `X-Forwarded-For` header, a list of hops.
+    XForwardedFor,
+    /// The `X-Real-IP` header set by nginx, a single address.
+    XRealIp,
+    /// The `CF-Connecting-IP` header set by Cloudflare, a single address.
+    CfConnectingIp,
+    /// Any other header with a single address.
+    Custom(HeaderName),
+}
+impl RealIpHeader {
+    fn name(&self) -> HeaderName {
+        match self {
+            Self::Forwarded => FORWARDED,
+            Self::XForwardedFor => HeaderName::from_static("x-forwarded-for"),
+            Self::XRealIp => HeaderName::from_static("x-real-ip"),
+            Self::CfConnectingIp => HeaderName::from_static("cf-connecting-ip"),
+            Self::Custom(name) => name.clone(),
+        }
+    }
+}
+
+/// Parse a value of the `for` parameter or of the `X-Forwarded-For` header, which may be quoted,
+/// bracketed or with port.
+fn parse_addr(value: &str) -> Option<IpAddr> {
+    let value = value.trim().trim_matches('"');
+    if let Ok(ip) = value.parse() {
+        return Some(ip);
+    }
+    if let Some(rest) = value.strip_prefix('[') {
+        return rest.split(']').next()?.parse().ok();
+    }
+    value.rsplit_once(':')?.0.parse().ok()
+}
+
+/// Resolve the real client address from the forwarding headers sent by trusted proxies.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Debug)]
+pub struct RealIp {
+    trusted_proxies: Vec<IpNet>,
+    headers: Vec<RealIpHeader>,
+    forwarded_scheme: bool,
+}
+impl Default for RealIp {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl RealIp {
+    /// Create new `RealIp` which trusts no proxy.
+    ///
+    /// The headers are checked in order `Forwarded`, `X-Forwarded-For`, `X-Real-IP` and
+    /// `CF-Connecting-IP`, the first present one is used.
+    pub fn new() -> Self {
+        Self {
+            trusted_proxies: Vec::new(),
+            headers: vec![
+                RealIpHeader::Forwarded,
+                RealIpHeader::XForwardedFor,
+                RealIpHeader::XRealIp,
+                RealIpHeader::CfConnectingIp,
+            ],
+            forwarded_scheme: true,
+        }
+    }
+
+    /// Trust the proxies in the range, e.g. `10.0.0.0/8`, `fd00::/8` or a single IP.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `net` is not a valid IP address or CIDR range.
+    pub fn trusted_proxy(mut self, net: &str) -> Self {
+        let net = net
+            .parse::<IpNet>()
+            .or_else(|_| net.parse::<IpAddr>().map(IpNet::from))
+            .unwrap_or_else(|_| panic!("invalid IP address or CIDR range: {net}"));
+        self.trusted_proxies.push(net);
+        self
+    }
+
+    /// Trust the proxies in the loopback and private networks.
+    pub fn trust_private_networks(mut self) -> Self {
+        self.trusted_proxies.extend(
+            [
+                "127.0.0.0/8",
+                "10.0.0.0/8",
+                "172.16.0.0/12",
+                "192.168.0.0/16",
+                "::1/128",
+                "fc00::/7",
+            ]
+            .map(|net| net.parse::<IpNet>().expect("valid CIDR range")),
+        );
+        self
+    }
+
+    /// Set the headers which carry the client address, in order of precedence.
+    pub fn headers(mut self, headers: impl IntoIterator<Item = RealIpHeader>) -> Self {
+        self.headers = headers.into_iter().collect();
+        self
+    }
+
+    /// Take the request scheme from the `proto` parameter of the `Forwarded` header or from the
+    /// `X-Forwarded-Proto` header sent by trusted proxies. Default is `true`.
+    pub fn forwarded_scheme(mut self, enabled: bool) -> Self {
+        self.forwarded_scheme = enabled;
+        self
+    }
+
+    /// Returns `true` if the IP is a trusted proxy.
+    pub fn is_trusted(&self, ip: &IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        self.trusted_proxies.iter().any(|net| net.contains(&ip))
+    }
+
+    fn forwarded_elements(req: &Request) -> impl Iterator<Item = &str> {
+        req.headers()
+            .get_all(FORWARDED)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+    }
+
+    fn forwarded_param<'a>(element: &'a str, param: &str) -> Option<&'a str> {
+        element.split(';').find_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            key.trim().eq_ignore_ascii_case(param).then_some(value)
+        })
+    }
+
+    fn forwarded_params<'a>(req: &'a Request, param: &'a str) -> impl Iterator<Item = &'a str> {
+        Self::forwarded_elements(req).filter_map(move |element| Self::forwarded_param(element, param))
+    }
+
+    /// Resolve the client IP of the request, `None` if the peer address is not an IP address,
+    /// e.g. unix socket.
+    ///
+    /// If the peer is a trusted proxy, the hops of the first present header are walked from the
+    /// nearest one and the first address which is not a trusted proxy is the client.
+    pub fn resolve(&self, req: &Request) -> Option<IpAddr> {
+        let peer = req.remote_addr().clone().into_std()?.ip().to_canonical();
+        if !self.is_trusted(&peer) {
+            return Some(peer);
+        }
+        for header in &self.headers {
+            let hops: Vec<&str> = match header {
+                RealIpHeader::Forwarded => Self::forwarded_params(req, "for").collect(),
+                RealIpHeader::XForwardedFor => req
+                    .headers()
+                    .get_all(header.name())
+                    .iter()
+                    .filter_map(|v| v.to_str().ok())
+                    .flat_map(|v| v.split(','))
+                    .collect(),
+                _ => req
+                    .headers()
+                    .get(header.name())
+                    .and_then(|v| v.to_str().ok())
+                    .into_iter()
+                    .collect(),
+            };
+            if hops.is_empty() {
+                continue;
+            }
+            let mut client = peer;
+            for hop in hops.iter().rev() {
+                // Obfuscated or unknown addresses can not be walked through.
+                let Some(ip) = parse_addr(hop) else {
+                    break;
+                };
+                client = ip.to_canonical();
+                if !self.is_trusted(&client) {
+                    break;
+                }
+            }
+            return Some(client);
+        }
+        Some(peer)
+    }
+
+    /// Resolve the scheme which the client used, `None` if it is not forwarded by a trusted proxy.
+    ///
+    /// The elements of the `Forwarded` header are walked from the nearest one like [`RealIp::resolve`],
+    /// and the `proto` of the element where the client is found is used. Otherwise the last value of
+    /// the `X-Forwarded-Proto` header is used, which is set by the nearest proxy. The values before them
+    /// may be forged by the client.
+    pub fn resolve_scheme(&self, req: &Request) -> Option<Scheme> {
+        let peer = req.remote_addr().clone().into_std()?.ip().to_canonical();
+        if !self.forwarded_scheme || !self.is_trusted(&peer) {
+            return None;
+        }
+        let elements: Vec<&str> = Self::forwarded_elements(req).collect();
+        let mut proto = None;
+        for element in elements.iter().rev() {
+            proto = Self::forwarded_param(element, "proto").or(proto);
+            let trusted = Self::forwarded_param(element, "for")
+                .and_then(parse_addr)
+                .is_some_and(|ip| self.is_trusted(&ip));
+            if !trusted {
+                break;
+            }
+        }
+        let proto = proto.or_else(|| {
+            req.headers()
+                .get_all("x-forwarded-proto")
+                .iter()
+                .filter_map(|v| v.to_str().ok())
+                .flat_map(|v| v.split(','))
+                .next_back()
+        })?;
+        proto.trim().trim_matches('"').parse().ok()
+    }
+
+    /// Resolve the client IP and scheme, and set them to the request.
+    pub fn apply(&self, req: &mut Request) {
+        req.client_ip = self.resolve(req);
+        if let Some(scheme) = self.resolve_scheme(req) {
+            req.scheme = scheme;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conn::SocketAddr;
+    use crate::prelude::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    fn request(peer: &str, headers: &[(&'static str, &'static str)]) -> Request {
+        let mut builder = TestClient::get("http://127.0.0.1:5801/");
+        for (name, value) in headers {
+            builder = builder.add_header(*name, *value, false);
+        }
+        let mut req = builder.build();
+        *req.remote_addr_mut() = SocketAddr::from(peer.parse::<std::net::SocketAddr>().unwrap());
+        req
+    }
+
+    #[test]
+    fn test_resolve() {
+        let real_ip = RealIp::new().trusted_proxy("192.168.0.0/16");
+        let ip = |peer: &str, headers: &[(&'static str, &'static str)]| {
+            real_ip
+                .resolve(&request(peer, headers))
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            ip("1.2.3.4:80", &[("x-forwarded-for", "5.6.7.8")]),
+            "1.2.3.4"
+        );
+        assert_eq!(
+            ip(
+                "192.168.0.1:80",
+                &[("x-forwarded-for", "9.9.9.9, 5.6.7.8, 192.168.0.2")]
+            ),
+            "5.6.7.8"
+        );
+        assert_eq!(
+            ip(
+                "192.168.0.1:80",
+                &[
+                    ("x-forwarded-for", "5.6.7.8"),
+                    (
+                        "forwarded",
+                        r#"for=5.6.7.8, for="[2001:db8::1]:4711";proto=https"#
+                    )
+                ]
+            ),
+            "2001:db8::1"
+        );
+        assert_eq!(
+            ip("192.168.0.1:80", &[("forwarded", "for=unknown")]),
+            "192.168.0.1"
+        );
+        assert_eq!(
+            ip("[::ffff:192.168.0.1]:80", &[("x-real-ip", "5.6.7.8")]),
+            "5.6.7.8"
+        );
+        assert_eq!(
+            ip("192.168.0.1:80", &[("cf-connecting-ip", "5.6.7.8")]),
+            "5.6.7.8"
+        );
+    }
+
+    #[test]
+    fn test_resolve_scheme() {
+        let real_ip = RealIp::new().trust_private_networks();
+        let mut req = request("10.0.0.1:80", &[("x-forwarded-proto", "https")]);
+        real_ip.apply(&mut req);
+        assert_eq!(req.scheme(), &Scheme::HTTPS);
+
+        let mut req = request("[::ffff:10.0.0.1]:80", &[("x-forwarded-proto", "https")]);
+        real_ip.apply(&mut req);
+        assert_eq!(req.scheme(), &Scheme::HTTPS);
+
+        let mut req = request("1.2.3.4:80", &[("forwarded", "for=5.6.7.8;proto=https")]);
+        real_ip.apply(&mut req);
+        assert_eq!(req.scheme(), &Scheme::HTTP);
+        assert_eq!(req.client_ip(), Some("1.2.3.4".parse().unwrap()));
+        // The leading values are sent by the client and can not be trusted.
+        let mut req = request(
+            "10.0.0.1:80",
+            &[("forwarded", "for=1.1.1.1;proto=https, for=5.6.7.8;proto=http")],
+        );
+        real_ip.apply(&mut req);
+        assert_eq!(req.scheme(), &Scheme::HTTP);
+        assert_eq!(req.client_ip(), Some("5.6.7.8".parse().unwrap()));
+
+        let mut req = request(
+            "10.0.0.1:80",
+            &[(
+                "forwarded",
+                "for=5.6.7.8;proto=https, for=10.0.0.2;proto=http",
+            )],
+        );
+        real_ip.apply(&mut req);
+        assert_eq!(req.scheme(), &Scheme::HTTPS);
+
+        let mut req = request("10.0.0.1:80", &[("x-forwarded-proto", "https, http")]);
+        real_ip.apply(&mut req);
+        assert_eq!(req.scheme(), &Scheme::HTTP);
+    }
+
+    #[tokio::test]
+    async fn test_service_real_ip() {
+        #[handler]
+        async fn client_ip(req: &mut Request) -> String {
+            format!("{:?} {}", req.client_ip(), req.scheme())
+        }
+        let service = Service::new(Router::new().goal(client_ip))
+            .real_ip(RealIp::new().trusted_proxy("10.0.0.1"));
+        let req = request(
+            "10.0.0.1:80",
+            &[
+                ("x-forwarded-for", "5.6.7.8"),
+                ("x-forwarded-proto", "https"),
+            ],
+        );
+        let body = service.handle(req).await.take_string().await.unwrap();
+        assert_eq!(body, "Some(5.6.7.8) https");
+    }
+}
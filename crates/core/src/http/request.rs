@@ -1,15 +1,19 @@
 //! HTTP request.
+use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt::{self, Debug, Formatter};
+use std::net::IpAddr;
 #[cfg(feature = "quinn")]
 use std::sync::Arc;
 use std::sync::OnceLock;
+use std::time::Instant;
 
 use bytes::Bytes;
 #[cfg(feature = "cookie")]
 use cookie::{Cookie, CookieJar};
+use headers::{Header, HeaderMapExt};
 use http::Extensions;
-use http::header::{AsHeaderName, CONTENT_TYPE, HeaderMap, HeaderValue, IntoHeaderName};
+use http::header::{ACCEPT, AsHeaderName, CONTENT_TYPE, HeaderMap, HeaderValue, IntoHeaderName};
 use http::method::Method;
 use http::uri::{Scheme, Uri};
 
@@ -18,15 +22,21 @@ pub use http::request::Parts;
 use http_body_util::{BodyExt, Limited};
 use multimap::MultiMap;
 use parking_lot::RwLock;
+use percent_encoding::{percent_decode_str, utf8_percent_encode};
 use serde::de::Deserialize;
+use serde::de::DeserializeOwned;
 
 use crate::conn::SocketAddr;
 use crate::extract::{Extractible, Metadata};
 use crate::fuse::TransProto;
 use crate::http::body::ReqBody;
 use crate::http::form::{FilePart, FormData};
+#[cfg(feature = "cookie")]
+use crate::http::keyed_cookies::{CookieKeys, PrivateCookies, SignedCookies};
+use crate::http::multipart::{Multipart, MultipartLimits};
+use crate::http::ndjson::NdJsonStream;
 use crate::http::{Mime, ParseError, ParseResult, Response, Version};
-use crate::routing::PathParams;
+use crate::routing::{PATH_SEGMENT, PathParams};
 use crate::serde::{
     from_request, from_str_map, from_str_multi_map, from_str_multi_val, from_str_val,
 };
@@ -56,6 +66,26 @@ pub fn set_global_secure_max_size(size: usize) {
     *lock = size;
 }
 
+static GLOBAL_MAX_BODY_SIZE: RwLock<usize> = RwLock::new(256 * 1024 * 1024);
+
+/// Get global maximum body size, default value is 256MB.
+///
+/// Unlike the secure maximum size, it limits all reading of the request body, including uploaded files and
+/// the body taken by [`Request::take_body`]. Reading a body larger than it fails with
+/// [`ParseError::PayloadTooLarge`], which is responded as `413 Payload Too Large`.
+pub fn global_max_body_size() -> usize {
+    *GLOBAL_MAX_BODY_SIZE.read()
+}
+
+/// Set maximum body size globally.
+///
+/// Use [`Router::max_body_size`](crate::Router::max_body_size) or [`Request::set_max_body_size`] to override
+/// it for specific routes or handlers.
+pub fn set_global_max_body_size(size: usize) {
+    let mut lock = GLOBAL_MAX_BODY_SIZE.write();
+    *lock = size;
+}
+
 /// Middleware for set the secure maximum size of request body.
 ///
 /// **Note**: The security maximum value is only effective when directly obtaining data
@@ -112,10 +142,13 @@ pub struct Request {
     pub(crate) scheme: Scheme,
     pub(crate) local_addr: SocketAddr,
     pub(crate) remote_addr: SocketAddr,
+    pub(crate) client_ip: Option<IpAddr>,
 
     pub(crate) secure_max_size: Option<usize>,
+    pub(crate) max_body_size: Option<usize>,
+    pub(crate) deadline: Option<Instant>,
     #[cfg(feature = "matched-path")]
-    pub(crate) matched_path: String,
+    pub(crate) matched_path: Option<String>,
 }
 
 impl Debug for Request {
@@ -161,9 +194,12 @@ impl Request {
             scheme: Scheme::HTTP,
             local_addr: SocketAddr::Unknown,
             remote_addr: SocketAddr::Unknown,
+            client_ip: None,
             secure_max_size: None,
+            max_body_size: None,
+            deadline: None,
             #[cfg(feature = "matched-path")]
-            matched_path: Default::default(),
+            matched_path: None,
         }
     }
     #[doc(hidden)]
@@ -225,11 +261,14 @@ impl Request {
             // multipart: OnceLock::new(),
             local_addr: SocketAddr::Unknown,
             remote_addr: SocketAddr::Unknown,
+            client_ip: None,
             version,
             scheme,
             secure_max_size: None,
+            max_body_size: None,
+            deadline: None,
             #[cfg(feature = "matched-path")]
-            matched_path: Default::default(),
+            matched_path: None,
         }
     }
 
@@ -382,6 +421,21 @@ impl Request {
         &mut self.remote_addr
     }
 
+    /// Get the IP address of the client.
+    ///
+    /// It is resolved by the [`RealIp`](crate::http::RealIp) of the service if the request is
+    /// forwarded by trusted proxies, otherwise it is the IP of the remote address.
+    #[inline]
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+            .or_else(|| self.remote_addr.clone().into_std().map(|addr| addr.ip()))
+    }
+    /// Set the IP address of the client.
+    #[inline]
+    pub fn set_client_ip(&mut self, ip: impl Into<Option<IpAddr>>) {
+        self.client_ip = ip.into();
+    }
+
     /// Get request local address reference.
     #[inline]
     pub fn local_addr(&self) -> &SocketAddr {
@@ -397,14 +451,29 @@ impl Request {
         #![feature = "matched-path"]
 
         /// Get matched path.
+        ///
+        /// It is the route template of the matched router chain without leading slash, such as
+        /// `users/{id}/posts/{post_id}`, the parameters are not replaced by the real values. So it is suitable
+        /// to be used as label in logging, metrics and tracing instead of the raw uri, which may cause
+        /// unbounded cardinality.
+        ///
+        /// It is empty before routing is done, or no router is matched.
         #[inline]
         pub fn matched_path(&self) -> &str {
-            &self.matched_path
+            self.matched_path.as_deref().unwrap_or_default()
         }
         /// Get mutable matched path.
         #[inline]
         pub fn matched_path_mut(&mut self) -> &mut String {
-            &mut self.matched_path
+            self.matched_path.get_or_insert_with(String::new)
+        }
+        /// Get matched route, it is the matched path with leading slash, such as `/users/{id}`.
+        ///
+        /// It is `None` before routing is done, or no router is matched, such as the request is handled
+        /// by the fallback or catcher, so unmatched requests are not mixed with the root route `/`.
+        #[inline]
+        pub fn matched_route(&self) -> Option<String> {
+            self.matched_path.as_ref().map(|path| format!("/{path}"))
         }
     }
 
@@ -464,6 +533,36 @@ impl Request {
         from_str_multi_val(values).map_err(Into::into)
     }
 
+    /// Get typed header, such as [`headers::CacheControl`].
+    ///
+    /// Returns `None` if failed or not found.
+    #[inline]
+    pub fn typed_header<H>(&self) -> Option<H>
+    where
+        H: Header,
+    {
+        self.headers.typed_get()
+    }
+
+    /// Try to get typed header, returns `Ok(None)` if not found.
+    #[inline]
+    pub fn try_typed_header<H>(&self) -> ParseResult<Option<H>>
+    where
+        H: Header,
+    {
+        self.headers.typed_try_get().map_err(Into::into)
+    }
+
+    /// Set typed header for this request, the existing values with the same name are replaced.
+    #[inline]
+    pub fn set_typed_header<H>(&mut self, header: H) -> &mut Self
+    where
+        H: Header,
+    {
+        self.headers.typed_insert(header);
+        self
+    }
+
     /// Modify a header for this request.
     ///
     /// When `overwrite` is set to `true`, If the header is already present, the value will be replaced.
@@ -507,9 +606,13 @@ impl Request {
     }
 
     /// Take body form the request, and set the body to None in the request.
+    ///
+    /// The returned body is limited by [`Request::max_body_size`], reading it returns an error when the size
+    /// exceeds the limit.
     #[inline]
     pub fn take_body(&mut self) -> ReqBody {
-        self.replace_body(ReqBody::None)
+        let max_size = self.max_body_size();
+        self.replace_body(ReqBody::None).limited(max_size)
     }
 
     /// Returns a reference to the associated extensions.
@@ -536,6 +639,26 @@ impl Request {
         self.secure_max_size.unwrap_or_else(global_secure_max_size)
     }
 
+    /// Set max body size, it overrides the global max body size and the max body size of matched router.
+    pub fn set_max_body_size(&mut self, size: usize) {
+        self.max_body_size = Some(size);
+    }
+
+    /// Get max body size, default value is 256MB.
+    ///
+    /// View [`global_max_body_size`] for more details.
+    pub fn max_body_size(&self) -> usize {
+        self.max_body_size.unwrap_or_else(global_max_body_size)
+    }
+
+    /// Get the deadline of current request, it is set when the matched router has a timeout.
+    ///
+    /// View [`Router::timeout`](crate::Router::timeout) for more details.
+    #[inline]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
     cfg_feature! {
         #![feature = "quinn"]
 
@@ -639,6 +762,55 @@ impl Request {
         }
     }
 
+    /// Choose the best one of the offered media types according to the `Accept` header, per the q-values and
+    /// precedence rules of [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#field.accept).
+    ///
+    /// If the `Accept` header is absent, the first offer is returned. If no offer is acceptable, `None` is
+    /// returned, and `406 Not Acceptable` is usually responded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// let mut req = Request::new();
+    /// req.add_header("accept", "text/html;q=0.9, application/*", true).unwrap();
+    /// assert_eq!(req.accepts(&["text/html", "application/json"]), Some("application/json"));
+    /// ```
+    pub fn accepts<'a>(&self, offers: &[&'a str]) -> Option<&'a str> {
+        let ranges = self
+            .headers
+            .get_all(ACCEPT)
+            .iter()
+            .filter_map(|h| h.to_str().ok())
+            .flat_map(|h| h.split(','))
+            .filter_map(|range| range.trim().parse::<Mime>().ok())
+            .collect::<Vec<_>>();
+        if ranges.is_empty() {
+            return offers.first().copied();
+        }
+        let mut best: Option<(&str, f32)> = None;
+        for offer in offers {
+            let Ok(mime) = offer.parse::<Mime>() else {
+                continue;
+            };
+            let quality = ranges
+                .iter()
+                .filter_map(|range| media_range_precedence(range, &mime).map(|p| (p, range)))
+                .max_by_key(|(precedence, _)| *precedence)
+                .map(|(_, range)| {
+                    range
+                        .get_param("q")
+                        .and_then(|q| q.as_str().parse::<f32>().ok())
+                        .unwrap_or(1.0)
+                })
+                .unwrap_or(0.0);
+            if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+                best = Some((offer, quality));
+            }
+        }
+        best.map(|(offer, _)| offer)
+    }
+
     /// Get content type.
     #[inline]
     pub fn content_type(&self) -> Option<Mime> {
@@ -668,6 +840,16 @@ impl Request {
         {
             self.cookies.get(name.as_ref())
         }
+        /// Get a view of the cookies which verifies signed cookies with `keys`.
+        #[inline]
+        pub fn signed_cookies<'a>(&'a self, keys: &'a CookieKeys) -> SignedCookies<'a> {
+            SignedCookies::new(&self.cookies, keys)
+        }
+        /// Get a view of the cookies which decrypts private cookies with `keys`.
+        #[inline]
+        pub fn private_cookies<'a>(&'a self, keys: &'a CookieKeys) -> PrivateCookies<'a> {
+            PrivateCookies::new(&self.cookies, keys)
+        }
     }
     /// Get params reference.
     #[inline]
@@ -680,6 +862,42 @@ impl Request {
         &mut self.params
     }
 
+    /// Get the raw (percent-encoded) remainder of the path captured by the wildcard param, for example:
+    /// <**rest>, <*?rest>.
+    ///
+    /// Unlike [`PathParams::tail`], the encoded characters are kept as they are in the request uri. The decoded
+    /// segments can be got by [`PathParams::tail_segments`].
+    pub fn raw_tail(&self) -> Option<String> {
+        if !self.params.greedy() {
+            return None;
+        }
+        let segments = self.params.tail_segments();
+        let raw_segments = self
+            .uri
+            .path()
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>();
+        if raw_segments.len() < segments.len() {
+            return None;
+        }
+        let mut tail = raw_segments[raw_segments.len() - segments.len()..]
+            .iter()
+            .map(|s| Cow::Borrowed(*s))
+            .collect::<Vec<_>>();
+        if let (Some(raw_first), Some(first)) = (tail.first_mut(), segments.first()) {
+            // The first segment may be partially captured, like `/files/prefix_{**rest}`.
+            if percent_decode_str(raw_first).decode_utf8_lossy() != first.as_str() {
+                *raw_first = Cow::Owned(utf8_percent_encode(first, PATH_SEGMENT).to_string());
+            }
+        }
+        let mut tail = tail.join("/");
+        if !segments.is_empty() && self.uri.path().ends_with('/') {
+            tail.push('/');
+        }
+        Some(tail)
+    }
+
     /// Get param value from params.
     #[inline]
     pub fn param<'de, T>(&'de self, key: &str) -> Option<T>
@@ -875,15 +1093,59 @@ impl Request {
                 Ok(Limited::new(body, max_size)
                     .collect()
                     .await
-                    .map_err(ParseError::other)?
+                    .map_err(ParseError::from_body_error)?
                     .to_bytes())
             })
             .await
     }
 
+    /// Read the whole body into memory with size limit, and replace the body with the buffered one.
+    ///
+    /// Middleware can use it to inspect the payload, for example to verify signatures or to write audit logs,
+    /// without starving the handlers and extractors which read the body later. Calling it again returns the
+    /// same buffered bytes.
+    ///
+    /// If the body exceeds `limit`, [`ParseError::PayloadTooLarge`] is returned, and the body may be
+    /// partially consumed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::http::ParseError;
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn audit(req: &mut Request) -> Result<(), ParseError> {
+    ///     let body = req.buffer_body(1024 * 1024).await?;
+    ///     tracing::info!(size = body.len(), "request body");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn buffer_body(&mut self, limit: usize) -> ParseResult<Bytes> {
+        let bytes = if let Some(payload) = self.payload.get() {
+            payload.clone()
+        } else {
+            match self.take_body() {
+                ReqBody::Once(bytes) if bytes.len() > limit => {
+                    self.body = ReqBody::Once(bytes);
+                    return Err(ParseError::PayloadTooLarge);
+                }
+                ReqBody::Once(bytes) => bytes,
+                body => Limited::new(body, limit)
+                    .collect()
+                    .await
+                    .map_err(ParseError::from_body_error)?
+                    .to_bytes(),
+            }
+        };
+        self.body = ReqBody::Once(bytes.clone());
+        Ok(bytes)
+    }
+
     /// Get `FormData` reference from request.
     ///
-    /// *Notice: This method takes body and body's size is not limited.
+    /// *Notice: This method takes body, and body's size is limited by [`Request::max_body_size`], reading a
+    /// larger body returns [`ParseError::PayloadTooLarge`].
     #[inline]
     pub async fn form_data(&mut self) -> ParseResult<&FormData> {
         if let Some(ctype) = self.content_type() {
@@ -891,7 +1153,11 @@ impl Request {
                 let body = self.take_body();
                 let headers = self.headers();
                 self.form_data
-                    .get_or_try_init(|| async { FormData::read(headers, body).await })
+                    .get_or_try_init(|| async {
+                        FormData::read(headers, body)
+                            .await
+                            .map_err(ParseError::check_payload_too_large)
+                    })
                     .await
             } else {
                 Err(ParseError::NotFormData)
@@ -901,6 +1167,49 @@ impl Request {
         }
     }
 
+    /// Get a streaming [`Multipart`] parser of the request body without limits except the max body size.
+    ///
+    /// *Notice: This method takes body.
+    #[inline]
+    pub fn multipart(&mut self) -> ParseResult<Multipart> {
+        self.multipart_with_limits(MultipartLimits::default())
+    }
+
+    /// Get a streaming [`Multipart`] parser of the request body with limits.
+    ///
+    /// *Notice: This method takes body.
+    pub fn multipart_with_limits(&mut self, limits: MultipartLimits) -> ParseResult<Multipart> {
+        let boundary = self
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|ct| ct.to_str().ok())
+            .and_then(|ct| multer::parse_boundary(ct).ok())
+            .ok_or(ParseError::NotMultipart)?;
+        Ok(Multipart::new(self.take_body(), boundary, limits))
+    }
+
+    /// Get a [`NdJsonStream`] which decodes the newline-delimited JSON request body as values of type `T`.
+    ///
+    /// The content type should be `application/x-ndjson`, `application/ndjson`, `application/jsonl` or
+    /// `application/x-jsonlines`.
+    ///
+    /// *Notice: This method takes body.
+    pub fn ndjson<T>(&mut self) -> ParseResult<NdJsonStream<T>>
+    where
+        T: DeserializeOwned,
+    {
+        if self.content_type().is_some_and(|ctype| {
+            matches!(
+                ctype.subtype().as_str(),
+                "x-ndjson" | "ndjson" | "jsonl" | "x-jsonlines"
+            )
+        }) {
+            Ok(NdJsonStream::new(self.take_body()))
+        } else {
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
     /// Extract request as type `T` from request's different parts.
     #[inline]
     pub async fn extract<'de, T>(&'de mut self) -> ParseResult<T>
@@ -1020,6 +1329,123 @@ impl Request {
         Err(ParseError::InvalidContentType)
     }
 
+    cfg_feature! {
+        #![feature = "msgpack"]
+        /// Parse MessagePack body as type `T` from request with default max size limit.
+        ///
+        /// The content type should be `application/msgpack`, `application/x-msgpack` or `application/vnd.msgpack`.
+        #[inline]
+        pub async fn parse_msgpack<'de, T>(&'de mut self) -> ParseResult<T>
+        where
+            T: Deserialize<'de>,
+        {
+            self.parse_msgpack_with_max_size(self.secure_max_size()).await
+        }
+        /// Parse MessagePack body as type `T` from request with max size limit.
+        pub async fn parse_msgpack_with_max_size<'de, T>(
+            &'de mut self,
+            max_size: usize,
+        ) -> ParseResult<T>
+        where
+            T: Deserialize<'de>,
+        {
+            if self.content_type().is_some_and(|ctype| {
+                matches!(ctype.subtype().as_str(), "msgpack" | "x-msgpack" | "vnd.msgpack")
+            }) {
+                let payload = self.payload_with_max_size(max_size).await?;
+                return rmp_serde::from_slice::<T>(payload).map_err(ParseError::MsgPack);
+            }
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "cbor"]
+        /// Parse CBOR body as type `T` from request with default max size limit.
+        ///
+        /// The content type should be `application/cbor`.
+        #[inline]
+        pub async fn parse_cbor<T>(&mut self) -> ParseResult<T>
+        where
+            T: DeserializeOwned,
+        {
+            self.parse_cbor_with_max_size(self.secure_max_size()).await
+        }
+        /// Parse CBOR body as type `T` from request with max size limit.
+        pub async fn parse_cbor_with_max_size<T>(&mut self, max_size: usize) -> ParseResult<T>
+        where
+            T: DeserializeOwned,
+        {
+            if self.content_type().is_some_and(|ctype| ctype.subtype() == "cbor") {
+                let payload = self.payload_with_max_size(max_size).await?;
+                return ciborium::from_reader::<T, _>(payload.as_ref()).map_err(ParseError::Cbor);
+            }
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "yaml"]
+        /// Parse YAML body as type `T` from request with default max size limit.
+        ///
+        /// The content type should be `application/yaml`, `application/x-yaml` or `text/yaml`.
+        #[inline]
+        pub async fn parse_yaml<'de, T>(&'de mut self) -> ParseResult<T>
+        where
+            T: Deserialize<'de>,
+        {
+            self.parse_yaml_with_max_size(self.secure_max_size()).await
+        }
+        /// Parse YAML body as type `T` from request with max size limit.
+        pub async fn parse_yaml_with_max_size<'de, T>(
+            &'de mut self,
+            max_size: usize,
+        ) -> ParseResult<T>
+        where
+            T: Deserialize<'de>,
+        {
+            if self
+                .content_type()
+                .is_some_and(|ctype| matches!(ctype.subtype().as_str(), "yaml" | "x-yaml"))
+            {
+                let payload = self.payload_with_max_size(max_size).await?;
+                return serde_norway::from_slice::<T>(payload).map_err(ParseError::Yaml);
+            }
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "protobuf"]
+        /// Parse Protocol Buffers body as message `T` from request with default max size limit.
+        ///
+        /// The content type should be `application/x-protobuf`, `application/protobuf` or
+        /// `application/vnd.google.protobuf`.
+        #[inline]
+        pub async fn parse_protobuf<T>(&mut self) -> ParseResult<T>
+        where
+            T: prost::Message + Default,
+        {
+            self.parse_protobuf_with_max_size(self.secure_max_size()).await
+        }
+        /// Parse Protocol Buffers body as message `T` from request with max size limit.
+        pub async fn parse_protobuf_with_max_size<T>(&mut self, max_size: usize) -> ParseResult<T>
+        where
+            T: prost::Message + Default,
+        {
+            if self.content_type().is_some_and(|ctype| {
+                matches!(
+                    ctype.subtype().as_str(),
+                    "x-protobuf" | "protobuf" | "vnd.google.protobuf"
+                )
+            }) {
+                let payload = self.payload_with_max_size(max_size).await?;
+                return T::decode(payload.as_ref()).map_err(ParseError::ProtoBuf);
+            }
+            Err(ParseError::InvalidContentType)
+        }
+    }
+
     /// Parse json body or form body as type `T` from request with default max size.
     #[inline]
     pub async fn parse_body<'de, T>(&'de mut self) -> ParseResult<T>
@@ -1048,6 +1474,33 @@ impl Request {
     }
 }
 
+/// Returns the precedence of the media range if it matches the media type, more specific range has higher precedence.
+fn media_range_precedence(range: &Mime, mime: &Mime) -> Option<usize> {
+    if range.type_() == mime::STAR {
+        return Some(0);
+    }
+    if range.type_() != mime.type_() {
+        return None;
+    }
+    if range.subtype() == mime::STAR {
+        return Some(1);
+    }
+    if range.subtype() != mime.subtype() {
+        return None;
+    }
+    let mut params = 0;
+    for (name, value) in range.params() {
+        if name == "q" {
+            continue;
+        }
+        if mime.get_param(name) != Some(value) {
+            return None;
+        }
+        params += 1;
+    }
+    Some(2 + params)
+}
+
 #[cfg(test)]
 mod tests {
     use serde::{Deserialize, Serialize};
@@ -1106,6 +1559,28 @@ mod tests {
         );
     }
     #[tokio::test]
+    async fn test_buffer_body() {
+        #[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+        struct User {
+            name: String,
+        }
+        let user = User {
+            name: "jobs".into(),
+        };
+        let mut req = TestClient::post("http://127.0.0.1:5800/hello")
+            .json(&user)
+            .build();
+        assert!(matches!(
+            req.buffer_body(4).await,
+            Err(ParseError::PayloadTooLarge)
+        ));
+        let body = req.buffer_body(1024).await.unwrap();
+        assert_eq!(body, r#"{"name":"jobs"}"#);
+        assert_eq!(req.buffer_body(1024).await.unwrap(), body);
+        assert_eq!(req.parse_json::<User>().await.unwrap(), user);
+        assert_eq!(req.buffer_body(1024).await.unwrap(), body);
+    }
+    #[tokio::test]
     async fn test_query() {
         let req = TestClient::get(
             "http://127.0.0.1:5801/hello?name=rust&name=25&name=a&name=2&weapons=98&weapons=gun",
@@ -1153,4 +1628,33 @@ file content\r\n\
         let files = req.files("file1").await.unwrap();
         assert_eq!(files[0].name().unwrap(), "err.txt");
     }
+
+    #[test]
+    fn test_accepts() {
+        let mut req = Request::new();
+        assert_eq!(
+            req.accepts(&["text/html", "application/json"]),
+            Some("text/html")
+        );
+
+        req.add_header("accept", "text/*;q=0.5, text/plain, */*;q=0.1", true)
+            .unwrap();
+        assert_eq!(
+            req.accepts(&["application/json", "text/html", "text/plain"]),
+            Some("text/plain")
+        );
+        assert_eq!(
+            req.accepts(&["application/json", "text/html"]),
+            Some("text/html")
+        );
+        assert_eq!(req.accepts(&["application/json"]), Some("application/json"));
+
+        req.add_header("accept", "application/json, text/html;q=0", true)
+            .unwrap();
+        assert_eq!(req.accepts(&["text/html"]), None);
+        assert_eq!(
+            req.accepts(&["text/html", "application/json"]),
+            Some("application/json")
+        );
+    }
 }
@@ -1,24 +1,44 @@
 //! HTTP response.
+use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 #[cfg(feature = "cookie")]
 use cookie::{Cookie, CookieJar};
 use futures_util::stream::Stream;
-use http::header::{HeaderMap, HeaderValue, IntoHeaderName};
+use headers::{
+    AcceptRanges, ContentLength, ContentRange, ETag, Header, HeaderMapExt, IfRange, LastModified,
+};
+use http::header::{
+    CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, IntoHeaderName, RANGE, TRAILER,
+};
 pub use http::response::Parts;
 use http::{Extensions, version::Version};
 use mime::Mime;
+use rand::TryRngCore;
+use rand::rngs::OsRng;
+use tokio::io::{AsyncRead, AsyncSeek};
 
 use crate::fs::NamedFile;
 use crate::fuse::TransProto;
-use crate::http::{StatusCode, StatusError};
+#[cfg(feature = "cookie")]
+use crate::http::keyed_cookies::CookieKeys;
+use crate::http::range::{Segment, seekable_stream};
+use crate::http::{HttpRange, Method, ParseError, Precondition, Request, StatusCode, StatusError};
 use crate::{BoxedError, Error, Scribe};
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 
 pub use crate::http::body::{BodySender, BytesFrame, ResBody};
 
+const WRITE_BUFFER_CAPACITY: usize = 8 * 1024;
+const WRITE_BUFFER_LOW_WATERMARK: usize = 1024;
+
+thread_local! {
+    static WRITE_BUFFER: RefCell<BytesMut> = RefCell::new(BytesMut::new());
+}
+
 /// Represents an HTTP response.
 #[non_exhaustive]
 pub struct Response {
@@ -131,6 +151,41 @@ impl Response {
         self.headers = headers
     }
 
+    /// Get typed header, such as [`headers::ContentType`].
+    ///
+    /// Returns `None` if failed or not found.
+    #[inline]
+    pub fn typed_header<H>(&self) -> Option<H>
+    where
+        H: Header,
+    {
+        self.headers.typed_get()
+    }
+
+    /// Set typed header for this response, the existing values with the same name are replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::prelude::*;
+    /// use salvo_core::http::headers::{CacheControl, ContentDisposition};
+    ///
+    /// #[handler]
+    /// async fn hello(res: &mut Response) {
+    ///     res.set_typed_header(CacheControl::new().with_no_store())
+    ///         .set_typed_header(ContentDisposition::inline());
+    ///     res.render("hello");
+    /// }
+    /// ```
+    #[inline]
+    pub fn set_typed_header<H>(&mut self, header: H) -> &mut Self
+    where
+        H: Header,
+    {
+        self.headers.typed_insert(header);
+        self
+    }
+
     /// Modify a header for this response.
     ///
     /// When `overwrite` is set to `true`, If the header is already present, the value will be replaced.
@@ -156,6 +211,97 @@ impl Response {
         Ok(self)
     }
 
+    /// Declare trailer fields with `names` in `Trailer` header, and send `trailers` after the body.
+    ///
+    /// Trailers are sent as the last chunk of HTTP/1.1 chunked responses, and as the trailing `HEADERS` frame
+    /// of HTTP/2 and HTTP/3 responses. HTTP/1.1 clients receive trailers only if they send `TE: trailers`.
+    ///
+    /// This method wraps the current body, so it should be called after the body is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures_util::{StreamExt, stream};
+    /// use salvo_core::http::header::{HeaderMap, HeaderName, HeaderValue};
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn hello(res: &mut Response) {
+    ///     let start = std::time::Instant::now();
+    ///     res.stream(stream::iter(["hello", " world"]).map(Ok::<_, std::io::Error>));
+    ///     res.trailers(&[HeaderName::from_static("server-timing")], async move {
+    ///         let mut trailers = HeaderMap::new();
+    ///         let timing = format!("total;dur={}", start.elapsed().as_millis());
+    ///         trailers.insert("server-timing", HeaderValue::from_str(&timing).unwrap());
+    ///         trailers
+    ///     });
+    /// }
+    /// ```
+    pub fn trailers<F>(&mut self, names: &[HeaderName], trailers: F) -> &mut Self
+    where
+        F: Future<Output = HeaderMap> + Send + 'static,
+    {
+        for name in names {
+            self.headers
+                .append(TRAILER, HeaderValue::from_name(name.clone()));
+        }
+        self.body = self.body.take().with_trailers(trailers);
+        self
+    }
+
+    /// Set `ETag` header for this response.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::http::headers::ETag;
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn article(req: &mut Request, res: &mut Response) {
+    ///     res.etag("\"v1\"".parse::<ETag>().unwrap());
+    ///     if res.check_preconditions(req) {
+    ///         res.render("article content");
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn etag(&mut self, etag: ETag) -> &mut Self {
+        self.headers.typed_insert(etag);
+        self
+    }
+    /// Set `Last-Modified` header for this response.
+    #[inline]
+    pub fn last_modified(&mut self, time: SystemTime) -> &mut Self {
+        self.headers.typed_insert(LastModified::from(time));
+        self
+    }
+
+    /// Evaluate the conditional headers of request against `ETag` and `Last-Modified` headers of this
+    /// response, see [`Precondition::evaluate`].
+    ///
+    /// Returns `true` if all preconditions passed. Otherwise the status code is set to
+    /// `304 Not Modified` for `GET` and `HEAD` requests, or `412 Precondition Failed` for others, the body is
+    /// cleared and `false` is returned, so the handler should skip producing the content.
+    pub fn check_preconditions(&mut self, req: &Request) -> bool {
+        let etag = self.headers.typed_get::<ETag>();
+        let last_modified = self
+            .headers
+            .typed_get::<LastModified>()
+            .map(SystemTime::from);
+        match Precondition::evaluate(req.headers(), etag.as_ref(), last_modified) {
+            Precondition::Passed => return true,
+            Precondition::NotModified if matches!(*req.method(), Method::GET | Method::HEAD) => {
+                self.status_code(StatusCode::NOT_MODIFIED);
+            }
+            _ => {
+                self.status_code(StatusCode::PRECONDITION_FAILED);
+            }
+        }
+        self.body = ResBody::None;
+        false
+    }
+
     /// Get version.
     #[inline]
     pub fn version(&self) -> Version {
@@ -313,6 +459,30 @@ impl Response {
             self.cookies.add(cookie);
             self
         }
+        /// Add a cookie which is signed with the primary key of `keys`.
+        ///
+        /// The signed cookie can be verified by [`Request::signed_cookies`](crate::http::Request::signed_cookies).
+        #[inline]
+        pub fn add_signed_cookie(
+            &mut self,
+            cookie: Cookie<'static>,
+            keys: &CookieKeys,
+        ) -> &mut Self {
+            self.cookies.signed_mut(keys.primary()).add(cookie);
+            self
+        }
+        /// Add a cookie which is encrypted with the primary key of `keys`.
+        ///
+        /// The private cookie can be decrypted by [`Request::private_cookies`](crate::http::Request::private_cookies).
+        #[inline]
+        pub fn add_private_cookie(
+            &mut self,
+            cookie: Cookie<'static>,
+            keys: &CookieKeys,
+        ) -> &mut Self {
+            self.cookies.private_mut(keys.primary()).add(cookie);
+            self
+        }
 
         /// Helper function for remove cookie.
         ///
@@ -419,15 +589,122 @@ impl Response {
         }
     }
 
+    /// Send a seekable reader of `len` bytes, such as a blob in database or object storage, with the support
+    /// of `Range` and `If-Range` requests.
+    ///
+    /// A single range is responded as `206 Partial Content`, multiple ranges are responded as
+    /// `multipart/byteranges`, and an unsatisfiable range is responded as `416 Range Not Satisfiable`.
+    ///
+    /// The `Content-Type`, `ETag` and `Last-Modified` headers should be set before calling it, they are used
+    /// to check the `If-Range` header and as the content type of the `multipart/byteranges` parts.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn blob(req: &mut Request, res: &mut Response) {
+    ///     let data = b"hello world".to_vec();
+    ///     let len = data.len() as u64;
+    ///     res.add_header("content-type", "text/plain", true).unwrap();
+    ///     res.send_seekable(req.headers(), Cursor::new(data), len);
+    /// }
+    /// ```
+    pub fn send_seekable<R>(&mut self, req_headers: &HeaderMap, reader: R, len: u64)
+    where
+        R: AsyncRead + AsyncSeek + Send + Unpin + 'static,
+    {
+        self.headers.typed_insert(AcceptRanges::bytes());
+        let range = req_headers.get(RANGE).filter(|_| {
+            req_headers.typed_get::<IfRange>().is_none_or(|if_range| {
+                !if_range.is_modified(
+                    self.headers.typed_get::<ETag>().as_ref(),
+                    self.headers.typed_get::<LastModified>().as_ref(),
+                )
+            })
+        });
+        let ranges = match range.map(|range| {
+            range
+                .to_str()
+                .map_err(|_| ParseError::InvalidRange)
+                .and_then(|range| HttpRange::parse(range, len))
+        }) {
+            None => Vec::new(),
+            Some(Ok(ranges)) => ranges,
+            Some(Err(_)) => {
+                self.headers
+                    .typed_insert(ContentRange::unsatisfied_bytes(len));
+                self.status_code(StatusCode::RANGE_NOT_SATISFIABLE);
+                return;
+            }
+        };
+
+        let segments = match &ranges[..] {
+            [] => {
+                self.status_code(StatusCode::OK);
+                vec![Segment::Range(HttpRange {
+                    start: 0,
+                    length: len,
+                })]
+            }
+            [range] => {
+                self.status_code(StatusCode::PARTIAL_CONTENT);
+                if let Ok(content_range) =
+                    ContentRange::bytes(range.start..range.start + range.length, len)
+                {
+                    self.headers.typed_insert(content_range);
+                }
+                vec![Segment::Range(*range)]
+            }
+            ranges => {
+                self.status_code(StatusCode::PARTIAL_CONTENT);
+                let boundary = format!("{:016x}", OsRng.try_next_u64().unwrap_or_default());
+                let content_type = self
+                    .headers
+                    .get(CONTENT_TYPE)
+                    .and_then(|ct| ct.to_str().ok())
+                    .map(|ct| format!("content-type: {ct}\r\n"))
+                    .unwrap_or_default();
+                let mut segments = Vec::with_capacity(ranges.len() * 2 + 1);
+                for (index, range) in ranges.iter().enumerate() {
+                    let part = format!(
+                        "{}--{boundary}\r\n{content_type}content-range: bytes {}-{}/{len}\r\n\r\n",
+                        if index == 0 { "" } else { "\r\n" },
+                        range.start,
+                        (range.start + range.length).saturating_sub(1),
+                    );
+                    segments.push(Segment::Bytes(part.into()));
+                    segments.push(Segment::Range(*range));
+                }
+                segments.push(Segment::Bytes(format!("\r\n--{boundary}--\r\n").into()));
+                if let Ok(content_type) =
+                    HeaderValue::from_str(&format!("multipart/byteranges; boundary={boundary}"))
+                {
+                    self.headers.insert(CONTENT_TYPE, content_type);
+                }
+                segments
+            }
+        };
+        self.headers
+            .typed_insert(ContentLength(segments.iter().map(Segment::len).sum()));
+        self.stream(seekable_stream(reader, segments));
+    }
+
     /// Write bytes data to body. If body is none, a new `ResBody` will created.
     pub fn write_body(&mut self, data: impl Into<Bytes>) -> crate::Result<()> {
         match self.body_mut() {
             ResBody::None => {
                 self.body = ResBody::Once(data.into());
             }
+            ResBody::Once(bytes) if bytes.is_empty() => {
+                *bytes = data.into();
+            }
             ResBody::Once(bytes) => {
-                let mut chunks = VecDeque::new();
-                chunks.push_back(bytes.clone());
+                let mut chunks = VecDeque::with_capacity(2);
+                chunks.push_back(std::mem::take(bytes));
                 chunks.push_back(data.into());
                 self.body = ResBody::Chunks(chunks);
             }
@@ -473,6 +750,54 @@ impl Response {
         Ok(())
     }
 
+    /// Write data to body by appending it to a reusable buffer, instead of allocating a new buffer.
+    ///
+    /// The buffer is shared by the renders on the same thread, the written data is split off from it
+    /// without copying, so small bodies rendered one after another share the same allocation. If `write`
+    /// returns an error, the written data is discarded and the body is not changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::BufMut;
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn hello(res: &mut Response) {
+    ///     res.write_body_with(|buf| {
+    ///         buf.put_slice(b"hello ");
+    ///         buf.put_slice(b"world");
+    ///         Ok::<_, salvo_core::Error>(())
+    ///     })
+    ///     .unwrap();
+    /// }
+    /// ```
+    pub fn write_body_with<F, E>(&mut self, write: F) -> crate::Result<()>
+    where
+        F: FnOnce(&mut BytesMut) -> Result<(), E>,
+        E: Into<Error>,
+    {
+        let bytes = WRITE_BUFFER.with(|buffer| {
+            let Ok(mut buf) = buffer.try_borrow_mut() else {
+                // Called recursively in `write`, uses a new buffer.
+                let mut buf = BytesMut::new();
+                write(&mut buf).map_err(Into::into)?;
+                return Ok(buf.freeze());
+            };
+            if buf.capacity() < WRITE_BUFFER_LOW_WATERMARK {
+                buf.reserve(WRITE_BUFFER_CAPACITY);
+            }
+            match write(&mut buf) {
+                Ok(()) => Ok(buf.split().freeze()),
+                Err(e) => {
+                    buf.clear();
+                    Err(e.into())
+                }
+            }
+        })?;
+        self.write_body(bytes)
+    }
+
     /// Set response's body to stream.
     #[inline]
     pub fn stream<S, O, E>(&mut self, stream: S)
@@ -570,4 +895,163 @@ mod test {
 
         assert_eq!("Hello World", &result)
     }
+
+    async fn send_seekable(
+        range: Option<&'static str>,
+        if_range: Option<&'static str>,
+    ) -> (Response, String) {
+        let mut req_headers = HeaderMap::new();
+        if let Some(range) = range {
+            req_headers.insert(RANGE, HeaderValue::from_static(range));
+        }
+        if let Some(if_range) = if_range {
+            req_headers.insert("if-range", HeaderValue::from_static(if_range));
+        }
+        let mut res = Response::new();
+        res.headers
+            .insert(CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+        res.headers
+            .insert("etag", HeaderValue::from_static("\"v1\""));
+        res.send_seekable(
+            &req_headers,
+            std::io::Cursor::new(b"hello world".to_vec()),
+            11,
+        );
+        let mut body = String::new();
+        while let Some(Ok(data)) = res.body.next().await {
+            body.push_str(std::str::from_utf8(&data.into_data().unwrap_or_default()).unwrap());
+        }
+        (res, body)
+    }
+
+    #[tokio::test]
+    async fn test_send_seekable() {
+        let (res, body) = send_seekable(None, None).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(body, "hello world");
+
+        let (res, body) = send_seekable(Some("bytes=6-"), None).await;
+        assert_eq!(res.status_code, Some(StatusCode::PARTIAL_CONTENT));
+        assert_eq!(res.headers["content-range"], "bytes 6-10/11");
+        assert_eq!(res.headers["content-length"], "5");
+        assert_eq!(body, "world");
+
+        let (res, body) = send_seekable(Some("bytes=0-4,6-"), Some("\"v1\"")).await;
+        assert_eq!(res.status_code, Some(StatusCode::PARTIAL_CONTENT));
+        let content_type = res.headers[CONTENT_TYPE].to_str().unwrap();
+        let boundary = content_type
+            .strip_prefix("multipart/byteranges; boundary=")
+            .unwrap();
+        assert_eq!(
+            body,
+            format!(
+                "--{boundary}\r\ncontent-type: text/plain\r\ncontent-range: bytes 0-4/11\r\n\r\nhello\
+                 \r\n--{boundary}\r\ncontent-type: text/plain\r\ncontent-range: bytes 6-10/11\r\n\r\nworld\
+                 \r\n--{boundary}--\r\n"
+            )
+        );
+        assert_eq!(res.headers["content-length"], body.len().to_string());
+
+        let (res, body) = send_seekable(Some("bytes=0-4"), Some("\"v2\"")).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(body, "hello world");
+
+        let (res, _) = send_seekable(Some("bytes=20-"), None).await;
+        assert_eq!(res.status_code, Some(StatusCode::RANGE_NOT_SATISFIABLE));
+        assert_eq!(res.headers["content-range"], "bytes */11");
+    }
+
+    #[test]
+    fn test_check_preconditions() {
+        let mut req = Request::new();
+        req.headers_mut()
+            .insert("if-none-match", HeaderValue::from_static("\"v1\""));
+        let mut res = Response::new();
+        res.etag("\"v1\"".parse().unwrap()).render("hello");
+        assert!(!res.check_preconditions(&req));
+        assert_eq!(res.status_code, Some(StatusCode::NOT_MODIFIED));
+        assert!(res.body.is_none());
+
+        *req.method_mut() = Method::PUT;
+        let mut res = Response::new();
+        res.etag("\"v1\"".parse().unwrap());
+        assert!(!res.check_preconditions(&req));
+        assert_eq!(res.status_code, Some(StatusCode::PRECONDITION_FAILED));
+
+        let mut res = Response::new();
+        res.etag("\"v2\"".parse().unwrap());
+        assert!(res.check_preconditions(&req));
+        assert_eq!(res.status_code, None);
+    }
+
+    #[tokio::test]
+    async fn test_trailers() {
+        use crate::test::ResponseExt;
+
+        let mut res = Response::new();
+        res.stream(iter(vec![
+            Ok::<_, Box<dyn Error + Send + Sync>>("Hello"),
+            Ok(" World"),
+        ]));
+        res.trailers(&[HeaderName::from_static("x-checksum")], async {
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+            trailers
+        });
+        assert_eq!(res.headers["trailer"], "x-checksum");
+
+        let (bytes, trailers) = res.take_bytes_with_trailers().await.unwrap();
+        assert_eq!(bytes, "Hello World");
+        assert_eq!(trailers.unwrap()["x-checksum"], "abc");
+    }
+
+    #[tokio::test]
+    async fn test_trailers_after_render() {
+        use hyper::body::Body;
+
+        use crate::prelude::Text;
+        use crate::test::ResponseExt;
+
+        let mut res = Response::new();
+        res.render(Text::Plain("Hello World"));
+        res.trailers(&[HeaderName::from_static("x-checksum")], async {
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+            trailers
+        });
+        assert_eq!(res.body.size(), None);
+        assert_eq!(Body::size_hint(&res.body).exact(), None);
+
+        let (bytes, trailers) = res.take_bytes_with_trailers().await.unwrap();
+        assert_eq!(bytes, "Hello World");
+        assert_eq!(trailers.unwrap()["x-checksum"], "abc");
+    }
+
+    #[tokio::test]
+    async fn test_write_body_with() {
+        use bytes::BufMut;
+
+        use crate::test::ResponseExt;
+
+        let mut res = Response::new();
+        res.write_body_with(|buf| {
+            buf.put_slice(b"Hello");
+            Ok::<_, std::io::Error>(())
+        })
+        .unwrap();
+        assert!(
+            res.write_body_with(|buf| {
+                buf.put_slice(b"discarded");
+                Err(std::io::Error::other("failed"))
+            })
+            .is_err()
+        );
+        res.write_body_with(|buf| {
+            buf.put_slice(b" World");
+            Ok::<_, std::io::Error>(())
+        })
+        .unwrap();
+        assert!(res.body.is_chunks());
+        assert_eq!(res.take_string().await.unwrap(), "Hello World");
+    }
 }
@@ -74,7 +74,9 @@ pub use self::extract::Extractible;
 pub use self::handler::Handler;
 pub use self::http::{Request, Response};
 pub use self::routing::{FlowCtrl, Router};
-pub use self::service::Service;
+#[cfg(feature = "router-swap")]
+pub use self::service::RouterSwapper;
+pub use self::service::{Service, ShutdownSignal};
 pub use self::writing::{Scribe, Writer};
 /// Result type which has `salvo::Error` as its error type.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -82,7 +84,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// A list of things that automatically imports into application use salvo_core.
 pub mod prelude {
     pub use async_trait::async_trait;
-    pub use salvo_macros::{Extractible, handler};
+    pub use salvo_macros::{Extractible, TypedPath, handler};
 
     pub use crate::depot::Depot;
     pub use crate::http::{Request, Response, StatusCode, StatusError};
@@ -124,6 +126,8 @@ pub mod prelude {
 #[doc(hidden)]
 pub mod __private {
     pub use tracing;
+
+    pub use crate::extract::optional::into_parse_error;
 }
 
 #[doc(hidden)]
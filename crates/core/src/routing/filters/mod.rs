@@ -7,6 +7,7 @@ mod opts;
 mod others;
 mod path;
 
+use std::any::Any;
 use std::fmt::{self, Debug, Formatter};
 
 use self::opts::*;
@@ -32,6 +33,12 @@ pub trait Filter: Debug + Send + Sync + 'static {
     fn type_name(&self) -> &'static str {
         std::any::type_name::<Self>()
     }
+    /// Returns the filter as `Any` so that it can be downcast by [`downcast_ref`](dyn Filter::downcast_ref),
+    /// returns `None` by default.
+    #[inline]
+    fn as_any(&self) -> Option<&dyn Any> {
+        None
+    }
     /// Create a new filter use `And` filter.
     #[inline]
     fn and<F>(self, other: F) -> And<Self, F>
@@ -84,10 +91,30 @@ pub trait Filter: Debug + Send + Sync + 'static {
         }
     }
 
+    /// Create a new filter use `Not` filter, it passes only if the current filter fails.
+    ///
+    /// The path state is not restored, so it should not be used to negate a filter which consumes
+    /// the path, like `PathFilter`.
+    #[inline]
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not { filter: self }
+    }
+
     /// Filter `Request` and returns false or true.
     async fn filter(&self, req: &mut Request, path: &mut PathState) -> bool;
 }
 
+impl dyn Filter {
+    /// Returns the concrete filter if it is of type `F`, e.g. [`PathFilter`] or [`MethodFilter`].
+    #[inline]
+    pub fn downcast_ref<F: Filter>(&self) -> Option<&F> {
+        self.as_any().and_then(|any| any.downcast_ref())
+    }
+}
+
 /// `FnFilter` accepts a function as its parameter, using this function to filter requests.
 #[derive(Copy, Clone)]
 #[allow(missing_debug_implementations)]
@@ -111,13 +138,39 @@ impl<F> fmt::Debug for FnFilter<F> {
     }
 }
 
+/// Create a `FnFilter` from a function which only reads the `PathState`.
+///
+/// Use `FnFilter` directly if the function needs to modify the `PathState`.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::routing::{Filter, filters};
+///
+/// // Passes if the request has `x-api-key` header or the query has `public` param.
+/// let filter = filters::filter_fn(|req, _| req.headers().contains_key("x-api-key"))
+///     .or(filters::filter_fn(|req, _| req.queries().contains_key("public")));
+/// let router = Router::with_filter(filters::get().or(filters::head()).and(filter));
+/// ```
+#[inline]
+pub fn filter_fn<F>(
+    func: F,
+) -> FnFilter<impl Fn(&mut Request, &mut PathState) -> bool + Send + Sync + 'static>
+where
+    F: Fn(&mut Request, &PathState) -> bool + Send + Sync + 'static,
+{
+    FnFilter(move |req: &mut Request, state: &mut PathState| func(req, state))
+}
+
 /// Filter request by uri scheme.
 #[inline]
 pub fn scheme(scheme: Scheme) -> SchemeFilter {
     SchemeFilter::new(scheme)
 }
 
-/// Filter request by uri hostname.
+/// Filter request by uri hostname, wildcard subdomain like `*.example.com` and
+/// captured subdomain like `{tenant}.example.com` are supported.
 #[inline]
 pub fn host(host: impl Into<String>) -> HostFilter {
     HostFilter::new(host)
@@ -129,6 +182,14 @@ pub fn port(port: u16) -> PortFilter {
     PortFilter::new(port)
 }
 
+/// Filter request by API version.
+///
+/// View [`VersionFilter`] for more details.
+#[inline]
+pub fn version(version: impl Into<String>) -> VersionFilter {
+    VersionFilter::new(version)
+}
+
 /// Filter request use `PathFilter`.
 #[inline]
 pub fn path(path: impl Into<String>) -> PathFilter {
@@ -186,6 +247,24 @@ mod tests {
         assert!(delete() == MethodFilter(Method::DELETE));
     }
 
+    #[test]
+    fn test_downcast_ref() {
+        let filter: Box<dyn Filter> = Box::new(path("users/{id:num}"));
+        assert_eq!(
+            filter.downcast_ref::<PathFilter>().map(|f| f.raw_value()),
+            Some("users/{id:num}")
+        );
+        assert!(filter.downcast_ref::<MethodFilter>().is_none());
+
+        let filter: Box<dyn Filter> = Box::new(get());
+        assert_eq!(
+            filter.downcast_ref::<MethodFilter>().map(|f| f.method()),
+            Some(&Method::GET)
+        );
+        let filter: Box<dyn Filter> = Box::new(get().and(path("users")));
+        assert!(filter.downcast_ref::<MethodFilter>().is_none());
+    }
+
     #[tokio::test]
     async fn test_opts() {
         fn has_one(_req: &mut Request, path: &mut PathState) -> bool {
@@ -283,4 +362,145 @@ mod tests {
                 .await
         );
     }
+
+    #[tokio::test]
+    async fn test_not_and_filter_fn() {
+        fn has_key(req: &mut Request, _path: &PathState) -> bool {
+            req.headers().contains_key("x-api-key")
+        }
+        fn is_public(req: &mut Request, _path: &PathState) -> bool {
+            req.queries().contains_key("public")
+        }
+
+        let mut req = Request::default();
+        let mut path_state = PathState::new(req.uri().path());
+        assert!(get().or(head()).filter(&mut req, &mut path_state).await);
+        assert!(!filter_fn(has_key).filter(&mut req, &mut path_state).await);
+        assert!(
+            filter_fn(has_key)
+                .not()
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(post().not().filter(&mut req, &mut path_state).await);
+        assert!(!get().not().filter(&mut req, &mut path_state).await);
+
+        *req.method_mut() = Method::HEAD;
+        req.headers_mut()
+            .insert("x-api-key", "key".parse().unwrap());
+        assert!(get().or(head()).filter(&mut req, &mut path_state).await);
+        assert!(
+            filter_fn(has_key)
+                .and(filter_fn(is_public).not())
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+
+        *req.method_mut() = Method::POST;
+        req.set_uri("http://127.0.0.1:5800/?public=1".parse().unwrap());
+        assert!(!get().or(head()).filter(&mut req, &mut path_state).await);
+        assert!(
+            !filter_fn(has_key)
+                .and(filter_fn(is_public).not())
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_host() {
+        let mut req = Request::default();
+        *req.uri_mut() = "http://acme.example.com:5800/".parse().unwrap();
+        let mut path_state = PathState::new(req.uri().path());
+        assert!(
+            host("acme.example.com")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(!host("example.com").filter(&mut req, &mut path_state).await);
+        assert!(
+            host("*.example.com")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(
+            !host("*.example.org")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(
+            host("{tenant}.example.com")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert_eq!(path_state.params["tenant"], "acme");
+
+        *req.uri_mut() = "http://example.com/".parse().unwrap();
+        let mut path_state = PathState::new(req.uri().path());
+        assert!(
+            !host("*.example.com")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(
+            !host("{tenant}.example.com")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_version() {
+        let mut req = Request::default();
+        *req.uri_mut() = "http://127.0.0.1:5800/v2/users".parse().unwrap();
+        let mut path_state = PathState::new(req.uri().path());
+        assert!(!version("2").filter(&mut req, &mut path_state).await);
+        assert!(
+            !version("1")
+                .path_prefix("v")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(
+            version("2")
+                .path_prefix("v")
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert_eq!(path_state.pick(), Some("users"));
+
+        let mut req = Request::default();
+        req.headers_mut()
+            .insert("accept-version", "2".parse().unwrap());
+        let mut path_state = PathState::new(req.uri().path());
+        assert!(version("2").filter(&mut req, &mut path_state).await);
+        assert!(
+            !version("1")
+                .lack(true)
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+
+        let mut req = Request::default();
+        req.headers_mut().insert(
+            "accept",
+            "application/vnd.api+json;version=3".parse().unwrap(),
+        );
+        let mut path_state = PathState::new(req.uri().path());
+        assert!(version("3").filter(&mut req, &mut path_state).await);
+        assert!(
+            !version("3")
+                .media_type_param(None)
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+        assert!(
+            version("1")
+                .lack(true)
+                .header(None)
+                .media_type_param(None)
+                .filter(&mut req, &mut path_state)
+                .await
+        );
+    }
 }
@@ -103,3 +103,19 @@ impl<T, F> fmt::Debug for AndThen<T, F> {
         write!(f, "opt:and_then")
     }
 }
+
+#[derive(Clone, Copy, Debug)]
+pub struct Not<T> {
+    pub(super) filter: T,
+}
+
+#[async_trait]
+impl<T> Filter for Not<T>
+where
+    T: Filter,
+{
+    #[inline]
+    async fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
+        !self.filter.filter(req, state).await
+    }
+}
@@ -1,6 +1,8 @@
+use std::any::Any;
 use std::fmt::{self, Debug, Formatter};
 
 use crate::async_trait;
+use crate::http::header::HeaderName;
 use crate::http::uri::Scheme;
 use crate::http::{Method, Request};
 use crate::routing::{Filter, PathState};
@@ -13,10 +15,19 @@ impl MethodFilter {
     pub fn new(method: Method) -> Self {
         Self(method)
     }
+    /// Get the method to filter.
+    #[inline]
+    pub fn method(&self) -> &Method {
+        &self.0
+    }
 }
 
 #[async_trait]
 impl Filter for MethodFilter {
+    #[inline]
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
     #[inline]
     async fn filter(&self, req: &mut Request, _state: &mut PathState) -> bool {
         req.method() == self.0
@@ -71,6 +82,11 @@ impl Debug for SchemeFilter {
 }
 
 /// Filter by request uri host.
+///
+/// Besides exact hostname, the host can also be:
+/// - `*.example.com`, matches any subdomain of `example.com`;
+/// - `{tenant}.example.com`, matches any subdomain of `example.com` and captures it as param `tenant`,
+///   which can be get by `req.param::<String>("tenant")`.
 #[derive(Clone, PartialEq, Eq)]
 pub struct HostFilter {
     /// Host to filter.
@@ -96,7 +112,7 @@ impl HostFilter {
 #[async_trait]
 impl Filter for HostFilter {
     #[inline]
-    async fn filter(&self, req: &mut Request, _state: &mut PathState) -> bool {
+    async fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
         // Http1, if `fix-http1-request-uri` feature is disabled, host is lack. so use header host instead.
         // https://github.com/hyperium/hyper/issues/1310
         #[cfg(feature = "fix-http1-request-uri")]
@@ -116,10 +132,31 @@ impl Filter for HostFilter {
                 h
             }
         })
-        .map(|h| h == self.host)
+        .map(|h| self.detect(h, state))
         .unwrap_or(self.lack)
     }
 }
+impl HostFilter {
+    fn detect(&self, host: &str, state: &mut PathState) -> bool {
+        if let Some(suffix) = self.host.strip_prefix('*') {
+            host.len() > suffix.len() && host.ends_with(suffix)
+        } else if let Some((name, suffix)) = self
+            .host
+            .strip_prefix('{')
+            .and_then(|rest| rest.split_once('}'))
+        {
+            if host.len() > suffix.len() && host.ends_with(suffix) {
+                let sub = &host[..host.len() - suffix.len()];
+                state.params.insert(name, sub.to_owned());
+                true
+            } else {
+                false
+            }
+        } else {
+            host == self.host
+        }
+    }
+}
 impl Debug for HostFilter {
     #[inline]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -183,3 +220,132 @@ impl Debug for PortFilter {
         write!(f, "port:{:?}", self.port)
     }
 }
+
+/// The API version selected by [`VersionFilter`].
+///
+/// It is injected into [`Depot`](crate::Depot) by [`Router::version`](crate::Router::version), and can be
+/// extracted by [`State<ApiVersion>`](crate::extract::State).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ApiVersion(pub String);
+impl ApiVersion {
+    /// Get the version string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Filter by API version.
+///
+/// The version of the request is detected in the following order, the first found one is used:
+///
+/// - Path prefix, only if [`VersionFilter::path_prefix`] is set, for example `/v2/users` with prefix `v`, the
+///   matched segment is consumed;
+/// - Header, `Accept-Version` by default;
+/// - Media type parameter in `Accept` header, for example `application/vnd.api+json;version=2`.
+///
+/// If no version is found in the request, [`VersionFilter::lack`] is used.
+#[derive(Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct VersionFilter {
+    /// Version to filter.
+    pub version: String,
+    /// The prefix of the path segment which contains the version, `None` means path is not used.
+    pub path_prefix: Option<String>,
+    /// The header which contains the version, `None` means header is not used.
+    pub header: Option<HeaderName>,
+    /// The parameter name of media type in `Accept` header, `None` means media type is not used.
+    pub media_type_param: Option<String>,
+    /// When version is lack in request, use this value.
+    pub lack: bool,
+}
+impl VersionFilter {
+    /// Create a new `VersionFilter`.
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            path_prefix: None,
+            header: Some(HeaderName::from_static("accept-version")),
+            media_type_param: Some("version".into()),
+            lack: false,
+        }
+    }
+    /// Set path prefix and return `Self`.
+    pub fn path_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.path_prefix = Some(prefix.into());
+        self
+    }
+    /// Set header and return `Self`.
+    pub fn header(mut self, header: Option<HeaderName>) -> Self {
+        self.header = header;
+        self
+    }
+    /// Set media type parameter name and return `Self`.
+    pub fn media_type_param(mut self, param: Option<String>) -> Self {
+        self.media_type_param = param;
+        self
+    }
+    /// Set lack value and return `Self`.
+    pub fn lack(mut self, lack: bool) -> Self {
+        self.lack = lack;
+        self
+    }
+}
+
+impl From<&str> for VersionFilter {
+    #[inline]
+    fn from(version: &str) -> Self {
+        Self::new(version)
+    }
+}
+impl From<String> for VersionFilter {
+    #[inline]
+    fn from(version: String) -> Self {
+        Self::new(version)
+    }
+}
+
+#[async_trait]
+impl Filter for VersionFilter {
+    #[inline]
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+    #[inline]
+    async fn filter(&self, req: &mut Request, state: &mut PathState) -> bool {
+        if let Some(prefix) = &self.path_prefix {
+            if let Some(picked) = state.pick() {
+                if picked.strip_prefix(&**prefix) == Some(&*self.version) {
+                    let len = picked.len();
+                    #[cfg(feature = "matched-path")]
+                    state.matched_parts.push(picked.to_owned());
+                    state.forward(len);
+                    return true;
+                }
+            }
+        }
+        if let Some(header) = &self.header {
+            if let Some(version) = req.headers().get(header).and_then(|v| v.to_str().ok()) {
+                return version.trim() == self.version;
+            }
+        }
+        if let Some(param) = &self.media_type_param {
+            for mime in req.accept() {
+                if let Some(version) = mime.get_param(&**param) {
+                    return version.as_str() == self.version;
+                }
+            }
+        }
+        self.lack
+    }
+}
+impl Debug for VersionFilter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(prefix) = &self.path_prefix {
+            write!(f, "version:{};prefix:{}", self.version, prefix)
+        } else {
+            write!(f, "version:{}", self.version)
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Path filter implementation.
 
+use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::{Arc, LazyLock};
@@ -33,6 +34,12 @@ pub trait PathWisp: Send + Sync + fmt::Debug + 'static {
 pub trait WispBuilder: Send + Sync {
     /// Build `PathWisp`.
     fn build(&self, name: String, sign: String, args: Vec<String>) -> Result<WispKind, String>;
+    /// The regex pattern of the wisps built by this builder, if it can be described by a regex.
+    ///
+    /// It is used to describe the path parameter, for example in OpenAPI document.
+    fn pattern(&self) -> Option<&str> {
+        None
+    }
 }
 
 type WispBuilderMap = RwLock<HashMap<String, Arc<Box<dyn WispBuilder>>>>;
@@ -152,6 +159,9 @@ impl WispBuilder for RegexWispBuilder {
         }
         .into())
     }
+    fn pattern(&self) -> Option<&str> {
+        Some(self.0.as_str())
+    }
 }
 
 /// CharsWispBuilder
@@ -300,13 +310,24 @@ impl PathWisp for CharsWisp {
 }
 
 /// Comb wisp is a group of other kind of wisps in the same url segment.
-#[derive(Debug)]
 pub struct CombWisp {
     names: Vec<String>,
+    patterns: Vec<(String, String)>,
     comb_regex: Regex,
     wild_regex: Option<Regex>,
     wild_start: Option<String>,
 }
+impl Debug for CombWisp {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CombWisp")
+            .field("names", &self.names)
+            .field("comb_regex", &self.comb_regex)
+            .field("wild_regex", &self.wild_regex)
+            .field("wild_start", &self.wild_start)
+            .finish()
+    }
+}
 impl CombWisp {
     /// Create new `CombWisp`.
     ///
@@ -315,6 +336,7 @@ impl CombWisp {
     pub fn new(wisps: Vec<WispKind>) -> Result<Self, String> {
         let mut comb_regex = "^".to_owned();
         let mut names = Vec::with_capacity(wisps.len());
+        let mut patterns = vec![];
         let mut is_prev_named = false;
         let mut is_greedy = false;
         let mut wild_start = None;
@@ -378,6 +400,7 @@ impl CombWisp {
                             .trim_start_matches('^')
                             .trim_end_matches('$');
                         comb_regex.push_str(&format!("(?<{}>{})", wisp.name, regex));
+                        patterns.push((wisp.name.clone(), wisp.regex.as_str().to_owned()));
                         names.push(wisp.name);
                     }
                 }
@@ -398,6 +421,7 @@ impl CombWisp {
         Regex::new(&comb_regex)
             .map(|comb_regex| Self {
                 names,
+                patterns,
                 comb_regex,
                 wild_regex,
                 wild_start,
@@ -480,8 +504,14 @@ impl PathWisp for CombWisp {
                 let cap = wild_regex.captures(&wild_path).and_then(|caps| caps.get(0));
                 if let Some(cap) = cap {
                     let cap = cap.as_str().to_owned();
+                    // The wild param captures the rest after the matched part of this segment.
+                    let rest = state.all_rest().unwrap_or_default().to_string();
+                    state.params.tail_segments = state.rest_segments();
                     state.forward(cap.len());
-                    state.params.insert(wild_name, cap);
+                    // Insert with the star mark, so that the params are marked as greedy.
+                    state
+                        .params
+                        .insert(&format!("{}{}", wild_start, wild_name), rest);
                     #[cfg(feature = "matched-path")]
                     state.matched_parts.push(format!("{{{}}}", wild_name));
                     true
@@ -515,6 +545,7 @@ impl PathWisp for NamedWisp {
             }
             if !rest.is_empty() || !self.0.starts_with("*+") {
                 let rest = rest.to_string();
+                state.params.tail_segments = state.rest_segments();
                 state.params.insert(&self.0, rest);
                 state.cursor.0 = state.parts.len();
                 #[cfg(feature = "matched-path")]
@@ -590,6 +621,7 @@ impl PathWisp for RegexWisp {
 
                 if let Some(cap) = cap {
                     let cap = cap.as_str().to_owned();
+                    state.params.tail_segments = state.rest_segments();
                     state.forward(cap.len());
                     state.params.insert(&self.name, cap);
                     #[cfg(feature = "matched-path")]
@@ -634,6 +666,19 @@ impl PathWisp for ConstWisp {
             #[cfg(feature = "matched-path")]
             state.matched_parts.push(self.0.clone());
             true
+        } else if state.case_insensitive
+            && picked
+                .get(..self.0.len())
+                .is_some_and(|p| p.eq_ignore_ascii_case(&self.0))
+        {
+            if state.case_redirect {
+                let (row, col) = state.cursor;
+                state.case_corrections.push((row, col, self.0.clone()));
+            }
+            state.forward(self.0.len());
+            #[cfg(feature = "matched-path")]
+            state.matched_parts.push(self.0.clone());
+            true
         } else {
             false
         }
@@ -741,6 +786,36 @@ impl PathParser {
             Ok(regex)
         }
     }
+    /// Scan regex wrapped by slashes, `\/` is used to escape slash in the regex.
+    #[inline]
+    fn scan_slashed_regex(&mut self) -> Result<String, String> {
+        let mut regex = "".to_owned();
+        let mut escaping = false;
+        loop {
+            let ch = self
+                .next(false)
+                .ok_or_else(|| "ended unexpectedly, should end with: /".to_owned())?;
+            if escaping {
+                if ch != '/' {
+                    regex.push('\\');
+                }
+                regex.push(ch);
+                escaping = false;
+            } else if ch == '\\' {
+                escaping = true;
+            } else if ch == '/' {
+                break;
+            } else {
+                regex.push(ch);
+            }
+        }
+        self.next(false);
+        if regex.is_empty() {
+            Err("regex segment is empty".to_owned())
+        } else {
+            Ok(regex)
+        }
+    }
     #[inline]
     fn scan_const(&mut self) -> Result<String, String> {
         let mut cnst = "".to_owned();
@@ -819,7 +894,12 @@ impl PathParser {
                 ch = self
                     .curr()
                     .ok_or_else(|| "current position is out of index".to_owned())?;
-                if ch == ':' {
+                if ch == ':' && self.peek(false) == Some('/') {
+                    // start to scan inline regex part, like `{id:/\d+/}`
+                    self.next(false);
+                    let regex = &self.scan_slashed_regex()?;
+                    wisps.push(RegexWisp::new(name, regex)?.into());
+                } else if ch == ':' {
                     //start to scan fn part
                     self.next(false);
                     let sign = self.scan_ident()?;
@@ -1016,6 +1096,10 @@ impl Debug for PathFilter {
 }
 #[async_trait]
 impl Filter for PathFilter {
+    #[inline]
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
     #[inline]
     async fn filter(&self, _req: &mut Request, state: &mut PathState) -> bool {
         self.detect(state)
@@ -1043,6 +1127,11 @@ impl PathFilter {
             path_wisps,
         }
     }
+    /// Get the raw path value, e.g. `users/{id:num}`.
+    #[inline]
+    pub fn raw_value(&self) -> &str {
+        &self.raw_value
+    }
     /// Register new path wisp builder.
     #[inline]
     pub fn register_wisp_builder<B>(name: impl Into<String>, builder: B)
@@ -1061,6 +1150,25 @@ impl PathFilter {
             Arc::new(Box::new(RegexWispBuilder::new(regex))),
         );
     }
+    /// Get the regex patterns of the constrained path parameters, wildcard parameters are excluded.
+    ///
+    /// Parameters constrained by regex like `{id|\d+}`, `{id:/\d+/}` or registered regex like `{id:my_regex}`
+    /// are included.
+    pub fn param_patterns(&self) -> Vec<(&str, &str)> {
+        let mut patterns = vec![];
+        for wisp in &self.path_wisps {
+            match wisp {
+                WispKind::Regex(wisp) if !wisp.name.starts_with('*') => {
+                    patterns.push((&*wisp.name, wisp.regex.as_str()));
+                }
+                WispKind::Comb(wisp) => {
+                    patterns.extend(wisp.patterns.iter().map(|(n, p)| (&**n, &**p)));
+                }
+                _ => {}
+            }
+        }
+        patterns
+    }
     /// Detect is that path is match.
     pub fn detect(&self, state: &mut PathState) -> bool {
         let original_cursor = state.cursor;
@@ -1118,6 +1226,29 @@ mod tests {
         );
     }
     #[test]
+    fn test_parse_slashed_regex() {
+        let segments = PathParser::new(r"/{abc:/\d+/}").parse().unwrap();
+        assert_eq!(
+            format!("{:?}", segments),
+            r#"[RegexWisp { name: "abc", regex: Regex("^\\d+$") }]"#
+        );
+        let segments = PathParser::new(r"/{abc:/[a-z\/]{2,3}/}/def")
+            .parse()
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", segments),
+            r#"[RegexWisp { name: "abc", regex: Regex("^[a-z/]{2,3}$") }, ConstWisp("def")]"#
+        );
+    }
+    #[test]
+    fn test_param_patterns() {
+        let filter = PathFilter::new(r"/{id|\d+}/prefix_{name:/[a-z]+/}/{page}/{**rest}");
+        assert_eq!(
+            filter.param_patterns(),
+            vec![("id", r"^\d+$"), ("name", "^[a-z]+$")]
+        );
+    }
+    #[test]
     fn test_parse_wildcard_regex() {
         let segments = PathParser::new(r"/{abc|\d+\.+}").parse().unwrap();
         assert_eq!(
@@ -1353,6 +1484,16 @@ mod tests {
         assert!(filter.detect(&mut state));
     }
     #[test]
+    fn test_detect_slashed_regex() {
+        let filter = PathFilter::new(r"/users/{id:/\d+/}");
+        let mut state = PathState::new("/users/abc");
+        assert!(!filter.detect(&mut state));
+
+        let mut state = PathState::new("/users/29");
+        assert!(filter.detect(&mut state));
+        assert_eq!(state.params["id"], "29");
+    }
+    #[test]
     fn test_detect_named_regex() {
         PathFilter::register_wisp_regex(
             "guid",
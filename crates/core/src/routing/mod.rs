@@ -137,7 +137,9 @@
 //! ```
 //!
 //! `{id}` matches a fragment in the path, under normal circumstances, the article `id` is just a number, which we can
-//! use regular expressions to restrict `id` matching rules, `r"{id|\d+}"`.
+//! use regular expressions to restrict `id` matching rules, `r"{id|\d+}"`. The regex can also be wrapped by slashes,
+//! `r"{id:/\d+/}"`, use `\/` if the regex contains slash. If the segment does not match the regex, the router is
+//! skipped and the sibling routers will be tried.
 //!
 //! For numeric characters there is an easier way to use `{id:num}`, the specific writing is:
 //!
@@ -372,7 +374,8 @@
 pub mod filters;
 pub use filters::*;
 mod router;
-pub use router::Router;
+pub(crate) use router::PATH_SEGMENT;
+pub use router::{RouteInfo, Router};
 
 mod path_params;
 pub use path_params::PathParams;
@@ -380,16 +383,54 @@ mod path_state;
 pub use path_state::PathState;
 mod flow_ctrl;
 pub use flow_ctrl::FlowCtrl;
+mod typed_path;
+pub use typed_path::{TypedPath, build_typed_path};
 
+use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::Handler;
+use crate::http::StatusCode;
 
 #[doc(hidden)]
+#[derive(Clone)]
 pub struct DetectMatched {
     pub hoops: Vec<Arc<dyn Handler>>,
     pub goal: Arc<dyn Handler>,
+    pub timeout: Option<Duration>,
+    pub timeout_status: Option<StatusCode>,
+    pub max_body_size: Option<usize>,
 }
+impl fmt::Debug for DetectMatched {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DetectMatched")
+            .field(
+                "hoops",
+                &self.hoops.iter().map(|h| h.type_name()).collect::<Vec<_>>(),
+            )
+            .field("goal", &self.goal.type_name())
+            .field("timeout", &self.timeout)
+            .field("timeout_status", &self.timeout_status)
+            .field("max_body_size", &self.max_body_size)
+            .finish()
+    }
+}
+impl PartialEq for DetectMatched {
+    fn eq(&self, other: &Self) -> bool {
+        self.hoops.len() == other.hoops.len()
+            && self
+                .hoops
+                .iter()
+                .zip(other.hoops.iter())
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+            && Arc::ptr_eq(&self.goal, &other.goal)
+            && self.timeout == other.timeout
+            && self.timeout_status == other.timeout_status
+            && self.max_body_size == other.max_body_size
+    }
+}
+impl Eq for DetectMatched {}
 
 pub(crate) fn split_wild_name(name: &str) -> (&str, &str) {
     if name.starts_with("*+") || name.starts_with("*?") || name.starts_with("**") {
@@ -508,4 +549,76 @@ mod tests {
         access(&service, "/alice3").await;
         access(&service, "/alice1/bob3").await;
     }
+
+    #[tokio::test]
+    async fn test_matched_route() {
+        #[handler]
+        async fn route(
+            req: &mut Request,
+            res: &mut Response,
+            depot: &mut Depot,
+            ctrl: &mut FlowCtrl,
+        ) {
+            ctrl.call_next(req, depot, res).await;
+            res.render(format!("{:?}", req.matched_route()));
+        }
+        #[handler]
+        async fn hello() {}
+
+        let router = Router::new()
+            .get(hello)
+            .push(Router::with_path("users/{id}").get(hello));
+        let service = Service::new(router).hoop(route);
+
+        async fn access(service: &Service, path: &str) -> String {
+            TestClient::get(format!("http://127.0.0.1{}", path))
+                .send(service)
+                .await
+                .take_string()
+                .await
+                .unwrap()
+        }
+        assert_eq!(access(&service, "/").await, r#"Some("/")"#);
+        assert_eq!(access(&service, "/users/1").await, r#"Some("/users/{id}")"#);
+        assert_eq!(access(&service, "/unknown").await, "None");
+    }
+
+    #[tokio::test]
+    async fn test_tail_segments() {
+        #[handler]
+        async fn files(req: &mut Request, res: &mut Response) {
+            res.render(format!(
+                "{:?} {:?} {:?}",
+                req.params().tail(),
+                req.params().tail_segments(),
+                req.raw_tail()
+            ));
+        }
+        let router = Router::new()
+            .push(Router::with_path("files/{**rest}").get(files))
+            .push(Router::with_path("assets/v_{**rest}").get(files));
+        let service = Service::new(router);
+
+        let content = TestClient::get("http://127.0.0.1:5801/files/a%2Fb/c%20d/e.txt")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(
+            content,
+            r#"Some("a/b/c d/e.txt") ["a/b", "c d", "e.txt"] Some("a%2Fb/c%20d/e.txt")"#
+        );
+
+        let content = TestClient::get("http://127.0.0.1:5801/assets/v_1%202/app.js")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(
+            content,
+            r#"Some("1 2/app.js") ["1 2", "app.js"] Some("1%202/app.js")"#
+        );
+    }
 }
@@ -9,6 +9,7 @@ use super::split_wild_name;
 pub struct PathParams {
     inner: IndexMap<String, String>,
     greedy: bool,
+    pub(crate) tail_segments: Vec<String>,
 }
 impl Deref for PathParams {
     type Target = IndexMap<String, String>;
@@ -35,6 +36,14 @@ impl PathParams {
         }
     }
 
+    /// Get the decoded segments of the last param starts with '*', for example: <**rest>, <*?rest>.
+    ///
+    /// Unlike splitting [`PathParams::tail`] by `/`, the encoded slash (`%2F`) in a segment does not split it.
+    /// It is empty if there is no wildcard param.
+    pub fn tail_segments(&self) -> &[String] {
+        &self.tail_segments
+    }
+
     /// Insert new param.
     pub fn insert(&mut self, name: &str, value: String) {
         #[cfg(debug_assertions)]
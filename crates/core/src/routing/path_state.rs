@@ -1,6 +1,11 @@
 use std::borrow::Cow;
 
-use super::{PathParams, decode_url_path_safely};
+use percent_encoding::utf8_percent_encode;
+
+use crate::http::Method;
+
+use super::router::PATH_SEGMENT;
+use super::{DetectMatched, PathParams, decode_url_path_safely};
 
 #[doc(hidden)]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -13,6 +18,13 @@ pub struct PathState {
     pub(crate) matched_parts: Vec<String>,
     pub(crate) end_slash: bool, // For rest match, we want include the last slash.
     pub(crate) once_ended: bool, // Once it has ended, used to determine whether the error code returned is 404 or 405.
+    pub(crate) method_not_allowed: bool, // Whether current detecting router chain wants `Allow` header for 405.
+    pub(crate) allow_header: bool, // Once it has ended in a router chain which wants `Allow` header for 405.
+    pub(crate) allowed_methods: Vec<Method>, // Methods of the routes which match all filters except the method, for `Allow` header.
+    pub(crate) fallback: Option<DetectMatched>, // The innermost fallback of the routers whose filters are passed.
+    pub(crate) case_insensitive: bool, // Whether current detecting router chain matches const segments case-insensitively.
+    pub(crate) case_redirect: bool, // Whether current detecting router chain redirects to the canonical casing.
+    pub(crate) case_corrections: Vec<(usize, usize, String)>, // (row, col, const) of the case-insensitive matched parts.
 }
 impl PathState {
     /// Creates a new `PathState`.
@@ -37,6 +49,13 @@ impl PathState {
             params: PathParams::new(),
             end_slash,
             once_ended: false,
+            method_not_allowed: false,
+            allow_header: false,
+            allowed_methods: vec![],
+            fallback: None,
+            case_insensitive: false,
+            case_redirect: false,
+            case_corrections: vec![],
             #[cfg(feature = "matched-path")]
             matched_parts: vec![],
         }
@@ -79,6 +98,20 @@ impl PathState {
         }
     }
 
+    /// Get the decoded segments from current cursor to the end.
+    #[inline]
+    pub(crate) fn rest_segments(&self) -> Vec<String> {
+        let Some(picked) = self.pick() else {
+            return vec![];
+        };
+        let mut segments = Vec::with_capacity(self.parts.len() - self.cursor.0);
+        segments.push(picked.to_owned());
+        if self.cursor.0 + 1 < self.parts.len() {
+            segments.extend(self.parts[self.cursor.0 + 1..].iter().cloned());
+        }
+        segments
+    }
+
     #[inline]
     pub fn forward(&mut self, steps: usize) {
         let mut steps = steps + self.cursor.1;
@@ -97,4 +130,27 @@ impl PathState {
     pub fn is_ended(&self) -> bool {
         self.cursor.0 >= self.parts.len()
     }
+
+    /// Get the path with canonical casing, returns `None` if no case correction is needed.
+    pub(crate) fn canonical_path(&self) -> Option<String> {
+        if self.case_corrections.is_empty() {
+            return None;
+        }
+        let mut parts = self.parts.clone();
+        for (row, col, cnst) in &self.case_corrections {
+            if let Some(part) = parts.get_mut(*row) {
+                part.replace_range(*col..*col + cnst.len(), cnst);
+            }
+        }
+        let mut path = parts
+            .iter()
+            .map(|part| utf8_percent_encode(part, PATH_SEGMENT).to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        path.insert(0, '/');
+        if self.end_slash && path.len() > 1 {
+            path.push('/');
+        }
+        Some(path)
+    }
 }
@@ -1,11 +1,17 @@
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
-use super::filters::{self, FnFilter, PathFilter};
-use super::{DetectMatched, Filter, PathState};
+use indexmap::IndexMap;
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+
+use super::filters::{self, ApiVersion, FnFilter, MethodFilter, PathFilter, VersionFilter};
+use super::{DetectMatched, Filter, PathState, TypedPath, split_wild_name};
+use crate::extract::StateHoop;
 use crate::handler::{Handler, WhenHoop};
 use crate::http::uri::Scheme;
+use crate::http::{Method, StatusCode};
 use crate::{Depot, Request};
 
 /// Route request to different handlers.
@@ -23,6 +29,24 @@ pub struct Router {
     pub hoops: Vec<Arc<dyn Handler>>,
     /// The final handler to handle request of current router.
     pub goal: Option<Arc<dyn Handler>>,
+    /// The name of current router, used to generate url by [`Router::url_for`].
+    pub name: Option<String>,
+    /// Whether to respond `405 Method Not Allowed` with `Allow` header for current router and it's descendants.
+    pub method_not_allowed: bool,
+    /// The timeout of current router and it's descendants, the innermost one wins.
+    pub timeout: Option<Duration>,
+    /// The status code responded when timeout, the innermost one wins.
+    pub timeout_status: Option<StatusCode>,
+    /// The max body size of current router and it's descendants, the innermost one wins.
+    pub max_body_size: Option<usize>,
+    /// The handler used when no route is found in current router's subtree.
+    pub fallback: Option<Arc<dyn Handler>>,
+    /// Whether match the const path segments case-insensitively, `None` means inherit from parent.
+    pub case_insensitive: Option<bool>,
+    /// Whether redirect to the canonical casing when case-insensitive matched, `None` means inherit from parent.
+    pub case_redirect: Option<bool>,
+    /// The priority of current router among its siblings, higher one is tested first, default is `0`.
+    pub priority: i32,
 }
 
 impl Default for Router {
@@ -43,6 +67,15 @@ impl Router {
             filters: Vec::new(),
             hoops: Vec::new(),
             goal: None,
+            name: None,
+            method_not_allowed: false,
+            timeout: None,
+            timeout_status: None,
+            max_body_size: None,
+            fallback: None,
+            case_insensitive: None,
+            case_redirect: None,
+            priority: 0,
         }
     }
 
@@ -52,6 +85,9 @@ impl Router {
         &self.routers
     }
     /// Get current router's children mutable reference.
+    ///
+    /// The children are kept sorted by [`Router::priority`] in descending order, keep the order when
+    /// modifying them, since they are tested in the order of this `Vec`.
     #[inline]
     pub fn routers_mut(&mut self) -> &mut Vec<Router> {
         &mut self.routers
@@ -86,36 +122,87 @@ impl Router {
         path_state: &mut PathState,
     ) -> Option<DetectMatched> {
         Box::pin(async move {
-            for filter in &self.filters {
+            let original_case_insensitive = path_state.case_insensitive;
+            let original_case_redirect = path_state.case_redirect;
+            if let Some(enabled) = self.case_insensitive {
+                path_state.case_insensitive = enabled;
+            }
+            if let Some(enabled) = self.case_redirect {
+                path_state.case_redirect = enabled;
+            }
+            for (index, filter) in self.filters.iter().enumerate() {
                 if !filter.filter(req, path_state).await {
+                    if path_state.method_not_allowed || self.method_not_allowed {
+                        self.collect_allowed_method(&**filter, index, req, path_state)
+                            .await;
+                    }
+                    path_state.case_insensitive = original_case_insensitive;
+                    path_state.case_redirect = original_case_redirect;
                     return None;
                 }
             }
+            let original_fallback_found = path_state.fallback.is_some();
+            let original_method_not_allowed = path_state.method_not_allowed;
+            if self.method_not_allowed {
+                path_state.method_not_allowed = true;
+            }
             if !self.routers.is_empty() {
                 let original_cursor = path_state.cursor;
                 #[cfg(feature = "matched-path")]
                 let original_matched_parts_len = path_state.matched_parts.len();
+                let original_case_corrections_len = path_state.case_corrections.len();
                 for child in &self.routers {
                     if let Some(dm) = child.detect(req, path_state).await {
                         return Some(DetectMatched {
                             hoops: [&self.hoops[..], &dm.hoops[..]].concat(),
                             goal: dm.goal.clone(),
+                            timeout: dm.timeout.or(self.timeout),
+                            timeout_status: dm.timeout_status.or(self.timeout_status),
+                            max_body_size: dm.max_body_size.or(self.max_body_size),
                         });
                     } else {
                         #[cfg(feature = "matched-path")]
                         path_state
                             .matched_parts
                             .truncate(original_matched_parts_len);
+                        path_state
+                            .case_corrections
+                            .truncate(original_case_corrections_len);
                         path_state.cursor = original_cursor;
                     }
                 }
             }
             if path_state.is_ended() {
                 path_state.once_ended = true;
+                if path_state.method_not_allowed {
+                    path_state.allow_header = true;
+                }
                 if let Some(goal) = &self.goal {
                     return Some(DetectMatched {
                         hoops: self.hoops.clone(),
                         goal: goal.clone(),
+                        timeout: self.timeout,
+                        timeout_status: self.timeout_status,
+                        max_body_size: self.max_body_size,
+                    });
+                }
+            }
+            path_state.method_not_allowed = original_method_not_allowed;
+            path_state.case_insensitive = original_case_insensitive;
+            path_state.case_redirect = original_case_redirect;
+            if !original_fallback_found {
+                if let Some(dm) = &mut path_state.fallback {
+                    dm.hoops.splice(0..0, self.hoops.iter().cloned());
+                    dm.timeout = dm.timeout.or(self.timeout);
+                    dm.timeout_status = dm.timeout_status.or(self.timeout_status);
+                    dm.max_body_size = dm.max_body_size.or(self.max_body_size);
+                } else if let Some(fallback) = &self.fallback {
+                    path_state.fallback = Some(DetectMatched {
+                        hoops: self.hoops.clone(),
+                        goal: fallback.clone(),
+                        timeout: self.timeout,
+                        timeout_status: self.timeout_status,
+                        max_body_size: self.max_body_size,
                     });
                 }
             }
@@ -124,29 +211,74 @@ impl Router {
         .await
     }
 
+    /// Record the method of the failed method filter if the other filters pass and current router is a route
+    /// for the path, so the `Allow` header can be set without detecting the router again for every method.
+    async fn collect_allowed_method(
+        &self,
+        failed: &dyn Filter,
+        index: usize,
+        req: &mut Request,
+        path_state: &mut PathState,
+    ) {
+        let Some(method) = failed.downcast_ref::<MethodFilter>().map(|f| f.method()) else {
+            return;
+        };
+        if self.goal.is_none() || path_state.allowed_methods.contains(method) {
+            return;
+        }
+        for filter in &self.filters[index + 1..] {
+            if !filter.filter(req, path_state).await {
+                return;
+            }
+        }
+        if path_state.is_ended() {
+            path_state.allowed_methods.push(method.clone());
+        }
+    }
+
     /// Insert a router at the beginning of current router, shifting all routers after it to the right.
+    ///
+    /// The router is placed before the routers with the same priority, but after the ones with higher priority.
     #[inline]
     pub fn unshift(mut self, router: Router) -> Self {
-        self.routers.insert(0, router);
+        let index = self
+            .routers
+            .partition_point(|child| child.priority > router.priority);
+        self.routers.insert(index, router);
         self
     }
     /// Insert a router at position `index` within current router, shifting all routers after it to the right.
+    ///
+    /// The `index` is clamped to the range of the routers with the same priority.
     #[inline]
     pub fn insert(mut self, index: usize, router: Router) -> Self {
-        self.routers.insert(index, router);
+        let start = self
+            .routers
+            .partition_point(|child| child.priority > router.priority);
+        let end = self
+            .routers
+            .partition_point(|child| child.priority >= router.priority);
+        self.routers.insert(index.clamp(start, end), router);
         self
     }
 
     /// Push a router as child of current router.
+    ///
+    /// The router is placed after the routers with the same or higher priority.
     #[inline]
     pub fn push(mut self, router: Router) -> Self {
-        self.routers.push(router);
+        let index = self
+            .routers
+            .partition_point(|child| child.priority >= router.priority);
+        self.routers.insert(index, router);
         self
     }
     /// Append all routers in a Vec as children of current router.
     #[inline]
     pub fn append(mut self, others: &mut Vec<Router>) -> Self {
-        self.routers.append(others);
+        for router in others.drain(..) {
+            self = self.push(router);
+        }
         self
     }
 
@@ -201,6 +333,22 @@ impl Router {
         Router::with_filter(PathFilter::new(path))
     }
 
+    /// Create a new router and set path filter by [`TypedPath`].
+    ///
+    /// [`TypedPath`]: super::TypedPath
+    #[inline]
+    pub fn with_typed_path<T: TypedPath>() -> Self {
+        Router::with_path(T::PATH)
+    }
+
+    /// Create a new path filter by [`TypedPath`] for current router.
+    ///
+    /// [`TypedPath`]: super::TypedPath
+    #[inline]
+    pub fn typed_path<T: TypedPath>(self) -> Self {
+        self.path(T::PATH)
+    }
+
     /// Create a new path filter for current router.
     ///
     /// # Panics
@@ -247,6 +395,366 @@ impl Router {
         self
     }
 
+    /// Create a new router and share the `state` to it and it's descendants.
+    ///
+    /// View [`Router::state`] for more details.
+    #[inline]
+    pub fn with_state<T>(state: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        Router::new().state(state)
+    }
+
+    /// Share the `state` to current router and it's descendants.
+    ///
+    /// The state can be extracted by [`State`](crate::extract::State) in handlers, or be obtained from
+    /// [`Depot`] by it's type. If multiple states of the same type are added in the router chain, the innermost
+    /// one wins.
+    #[inline]
+    pub fn state<T>(self, state: T) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        self.hoop(StateHoop(state))
+    }
+
+    /// Create a new router and only allow requests of the API version.
+    ///
+    /// View [`Router::version`] for more details.
+    #[inline]
+    pub fn with_version(version: impl Into<VersionFilter>) -> Self {
+        Router::new().version(version)
+    }
+
+    /// Only allow requests of the API version to be handled by current router, and inject the selected
+    /// [`ApiVersion`] into [`Depot`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// use salvo_core::routing::filters::VersionFilter;
+    /// # #[handler] async fn list_users_v1() {}
+    /// # #[handler] async fn list_users_v2() {}
+    ///
+    /// // `/v1/users`, or `/users` with header `Accept-Version: 1` or no version provided.
+    /// let v1 = Router::with_version(VersionFilter::new("1").path_prefix("v").lack(true))
+    ///     .push(Router::with_path("users").get(list_users_v1));
+    /// // `/v2/users`, or `/users` with header `Accept-Version: 2`, or media type like
+    /// // `application/vnd.api+json;version=2`.
+    /// let v2 = Router::with_version(VersionFilter::new("2").path_prefix("v"))
+    ///     .push(Router::with_path("users").get(list_users_v2));
+    /// Router::new().push(v2).push(v1);
+    /// ```
+    #[inline]
+    pub fn version(self, version: impl Into<VersionFilter>) -> Self {
+        let filter = version.into();
+        let version = ApiVersion(filter.version.clone());
+        self.filter(filter).state(version)
+    }
+
+    /// Sets current router's fallback handler, it is used when the filters of current router are passed,
+    /// but no route is found in it's subtree.
+    ///
+    /// The innermost fallback wins, and the middlewares of the routers on the way are executed before it. The
+    /// response status code is set to `404 Not Found` before the fallback is called, if the fallback does not
+    /// write body, the [`Catcher`](crate::catcher::Catcher) will handle it as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// #[handler]
+    /// async fn api_not_found(res: &mut Response) {
+    ///     res.render(Json(serde_json::json!({"error": "not found"})));
+    /// }
+    /// # #[handler] async fn list_users() {}
+    ///
+    /// Router::new().push(
+    ///     Router::with_path("api")
+    ///         .fallback(api_not_found)
+    ///         .push(Router::with_path("users").get(list_users)),
+    /// );
+    /// ```
+    #[inline]
+    pub fn fallback<H: Handler>(mut self, fallback: H) -> Self {
+        self.fallback = Some(Arc::new(fallback));
+        self
+    }
+
+    /// Sets whether current router and it's descendants match the const path segments case-insensitively,
+    /// descendants can override it.
+    ///
+    /// Only ASCII characters are compared case-insensitively, and the const parts combined with parameters in
+    /// the same segment, like `article_{id}`, are still case-sensitive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] async fn legacy() {}
+    /// // Matches `/Default.aspx`, `/default.ASPX` ...
+    /// Router::new().case_insensitive(true).push(Router::with_path("Default.aspx").get(legacy));
+    /// ```
+    #[inline]
+    pub fn case_insensitive(mut self, enabled: bool) -> Self {
+        self.case_insensitive = Some(enabled);
+        self
+    }
+
+    /// Sets whether redirect to the canonical casing with `308 Permanent Redirect` when the path is matched
+    /// case-insensitively with different casing, descendants can override it.
+    ///
+    /// View [`Router::case_insensitive`] for more details.
+    #[inline]
+    pub fn case_redirect(mut self, enabled: bool) -> Self {
+        self.case_redirect = Some(enabled);
+        self
+    }
+
+    /// Sets current router's priority among its siblings, it should be set before the router is added to
+    /// its parent.
+    ///
+    /// By default, sibling routers are tested in the order they were added. Routers with higher priority are
+    /// tested before the ones with lower priority, and the ones with the same priority keep the order they
+    /// were added. This is useful when routes are contributed by different libraries, for example, to ensure
+    /// static routes are tested before the wildcard routes regardless of the registration order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::prelude::*;
+    ///
+    /// #[handler]
+    /// async fn show_file() {}
+    /// #[handler]
+    /// async fn show_about() {}
+    ///
+    /// let router = Router::new()
+    ///     .push(Router::with_path("{**path}").get(show_file))
+    ///     .push(Router::with_path("about").priority(10).get(show_about));
+    /// ```
+    #[inline]
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets current router's name, so that the url of this router can be generated by [`Router::url_for`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] fn show_user() {}
+    /// let router = Router::new().push(Router::with_path("users/{id}").name("user_detail").get(show_user));
+    /// assert_eq!(router.url_for("user_detail", [("id", "12")]).unwrap(), "/users/12");
+    /// ```
+    #[inline]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Generate url for the router with the given name.
+    ///
+    /// Path params in the router's path template are replaced by the values in `params`,
+    /// the remaining values in `params` are appended to the url as query string.
+    ///
+    /// Returns `None` if no router has this name or a required path param is missing.
+    pub fn url_for<I, K, V>(&self, name: &str, params: I) -> Option<String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: ToString,
+    {
+        let templates = self.find_named(name)?;
+        let mut params = params
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.to_string()))
+            .collect::<IndexMap<String, String>>();
+        let mut segments = Vec::with_capacity(templates.len());
+        for template in &templates {
+            let segment = fill_path_template(template, &mut params)?;
+            if !segment.is_empty() {
+                segments.push(segment);
+            }
+        }
+        let mut url = format!("/{}", segments.join("/"));
+        if !params.is_empty() {
+            let query = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(params.iter())
+                .finish();
+            url.push('?');
+            url.push_str(&query);
+        }
+        Some(url)
+    }
+
+    fn find_named(&self, name: &str) -> Option<Vec<String>> {
+        let path = self.path_template();
+        if self.name.as_deref() == Some(name) {
+            return Some(path.into_iter().collect());
+        }
+        for child in &self.routers {
+            if let Some(mut templates) = child.find_named(name) {
+                if let Some(path) = path {
+                    templates.insert(0, path);
+                }
+                return Some(templates);
+            }
+        }
+        None
+    }
+
+    /// Get all routes registered in current router and it's descendants, each router which has a goal
+    /// handler is a route.
+    ///
+    /// It is useful to print a route table at startup, build admin dashboards, or assert expected routes in tests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] fn list_users() {}
+    /// # #[handler] fn show_user() {}
+    /// let router = Router::with_path("users")
+    ///     .get(list_users)
+    ///     .push(Router::with_path("{id}").get(show_user));
+    /// for route in router.routes() {
+    ///     println!("{:?} /{} -> {}", route.methods, route.path, route.goal);
+    /// }
+    /// ```
+    pub fn routes(&self) -> impl Iterator<Item = RouteInfo> {
+        fn collect(router: &Router, parent: &RouteInfo, routes: &mut Vec<RouteInfo>) {
+            let mut info = parent.clone();
+            if let Some(path) = router.path_template() {
+                // Name is only inherited by the descendants which have the same path.
+                info.name = None;
+                if info.path.is_empty() {
+                    info.path = path;
+                } else {
+                    info.path = format!("{}/{}", info.path, path);
+                }
+            }
+            for filter in &router.filters {
+                if filter.downcast_ref::<PathFilter>().is_some() {
+                    continue;
+                }
+                if let Some(filter) = filter.downcast_ref::<MethodFilter>() {
+                    info.methods.push(filter.method().clone());
+                } else {
+                    info.filters.push(format!("{filter:?}"));
+                }
+            }
+            info.hoops
+                .extend(router.hoops.iter().map(|hoop| hoop.type_name()));
+            if router.name.is_some() {
+                info.name.clone_from(&router.name);
+            }
+            for child in &router.routers {
+                collect(child, &info, routes);
+            }
+            if let Some(goal) = &router.goal {
+                info.goal = goal.type_name();
+                routes.push(info);
+            }
+        }
+        let mut routes = Vec::new();
+        collect(self, &RouteInfo::default(), &mut routes);
+        routes.into_iter()
+    }
+
+    /// Get the path template of current router, it is combined by all path filters in current router.
+    pub(crate) fn path_template(&self) -> Option<String> {
+        let paths = self
+            .filters
+            .iter()
+            .filter_map(|filter| filter.downcast_ref::<PathFilter>())
+            .map(|filter| filter.raw_value().trim_matches('/').to_owned())
+            .filter(|path| !path.is_empty())
+            .collect::<Vec<_>>();
+        if paths.is_empty() {
+            None
+        } else {
+            Some(paths.join("/"))
+        }
+    }
+
+    /// Sets whether to respond `405 Method Not Allowed` with a correct `Allow` header when the path is matched
+    /// but the method is not, it takes effect on current router and it's descendants.
+    ///
+    /// When it is disabled (default), the response is still `405 Method Not Allowed`, but without `Allow` header.
+    ///
+    /// The allowed methods are those of the routers which have a goal and a [`MethodFilter`], such as the
+    /// ones added by [`Router::get`] or [`Router::post`].
+    #[inline]
+    pub fn method_not_allowed(mut self, enabled: bool) -> Self {
+        self.method_not_allowed = enabled;
+        self
+    }
+
+    /// Sets the timeout of current router and it's descendants.
+    ///
+    /// Timeouts compose, the innermost router's timeout wins. When the timeout elapsed, the handlers
+    /// (including middlewares) are cancelled, and a `503 Service Unavailable` error is responded by default,
+    /// use [`Router::timeout_status`] to respond other status code, such as `408 Request Timeout` or
+    /// `504 Gateway Timeout`.
+    ///
+    /// The deadline can be get by [`Request::deadline`], so that handlers like proxy can respect it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] fn report() {}
+    /// # #[handler] fn hello() {}
+    /// Router::new()
+    ///     .timeout(Duration::from_secs(5))
+    ///     .push(Router::with_path("report").timeout(Duration::from_secs(60)).get(report))
+    ///     .push(Router::with_path("hello").get(hello));
+    /// ```
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the status code responded when timeout of current router and it's descendants elapsed.
+    ///
+    /// View [`Router::timeout`] for more details.
+    #[inline]
+    pub fn timeout_status(mut self, status: StatusCode) -> Self {
+        self.timeout_status = Some(status);
+        self
+    }
+
+    /// Sets the max body size of current router and it's descendants, the innermost router's one wins.
+    ///
+    /// It overrides the [global max body size](crate::http::request::global_max_body_size), and can be
+    /// overridden by handlers using [`Request::set_max_body_size`]. Reading a body larger than it responds
+    /// `413 Payload Too Large`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # #[handler] fn upload() {}
+    /// # #[handler] fn hello() {}
+    /// Router::new()
+    ///     .max_body_size(1024 * 1024)
+    ///     .push(Router::with_path("upload").max_body_size(1024 * 1024 * 1024).post(upload))
+    ///     .push(Router::with_path("hello").post(hello));
+    /// ```
+    #[inline]
+    pub fn max_body_size(mut self, size: usize) -> Self {
+        self.max_body_size = Some(size);
+        self
+    }
+
     /// When you want write router chain, this function will be useful,
     /// You can write your custom logic in FnOnce.
     #[inline]
@@ -354,6 +862,89 @@ impl Router {
     }
 }
 
+/// Information of a route, it is returned by [`Router::routes`].
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct RouteInfo {
+    /// The full path template of the route, such as `users/{id}/posts`.
+    pub path: String,
+    /// The methods allowed by the route, empty means all methods are allowed.
+    pub methods: Vec<Method>,
+    /// The descriptions of other filters, such as `host:"example.com"`.
+    pub filters: Vec<String>,
+    /// The type names of the middlewares attached to the route, outer first.
+    pub hoops: Vec<&'static str>,
+    /// The type name of the goal handler.
+    pub goal: &'static str,
+    /// The name of the route, view [`Router::name`] for more details.
+    pub name: Option<String>,
+}
+
+pub(crate) const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Replace the params in path template with the values in `params`, used params are removed from `params`.
+pub(crate) fn fill_path_template(
+    template: &str,
+    params: &mut IndexMap<String, String>,
+) -> Option<String> {
+    let mut filled = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' | '}' if chars.peek() == Some(&ch) => {
+                chars.next();
+                filled.push(ch);
+            }
+            '{' => {
+                let mut depth = 1;
+                let mut spec = String::new();
+                for c in chars.by_ref() {
+                    if c == '{' {
+                        depth += 1;
+                    } else if c == '}' {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    spec.push(c);
+                }
+                let full_name = spec.split([':', '|']).next().unwrap_or_default().trim();
+                let (sign, name) = split_wild_name(full_name);
+                match params.shift_remove(name) {
+                    Some(value) if sign.is_empty() => {
+                        filled.push_str(&utf8_percent_encode(&value, PATH_SEGMENT).to_string());
+                    }
+                    Some(value) => {
+                        let value = value
+                            .trim_matches('/')
+                            .split('/')
+                            .map(|s| utf8_percent_encode(s, PATH_SEGMENT).to_string())
+                            .collect::<Vec<_>>()
+                            .join("/");
+                        filled.push_str(&value);
+                    }
+                    None if sign == "**" || sign == "*?" || sign == "*" => {}
+                    None => return None,
+                }
+            }
+            _ => filled.push(ch),
+        }
+    }
+    Some(filled.trim_end_matches('/').to_owned())
+}
+
 const SYMBOL_DOWN: &str = "│";
 const SYMBOL_TEE: &str = "├";
 const SYMBOL_ELL: &str = "└";
@@ -676,6 +1267,47 @@ mod tests {
         assert!(matched.is_none());
     }
 
+    #[tokio::test]
+    async fn test_router_detect_priority() {
+        let router = Router::new()
+            .push(Router::with_path("{**path}").get(fake_handler))
+            .push(Router::with_path("about").get(fake_handler));
+        let mut req = TestClient::get("http://local.host/about").build();
+        let mut path_state = PathState::new(req.uri().path());
+        let matched = router.detect(&mut req, &mut path_state).await;
+        assert!(matched.is_some());
+        assert_eq!(path_state.params.get("path").map(|s| &**s), Some("about"));
+
+        let router = Router::new()
+            .push(Router::with_path("{**path}").get(fake_handler))
+            .push(Router::with_path("about").priority(10).get(fake_handler))
+            .push(Router::with_path("{**low}").priority(-1).get(fake_handler));
+        let mut req = TestClient::get("http://local.host/about").build();
+        let mut path_state = PathState::new(req.uri().path());
+        let matched = router.detect(&mut req, &mut path_state).await;
+        assert!(matched.is_some());
+        assert!(path_state.params.is_empty());
+
+        let mut req = TestClient::get("http://local.host/contact").build();
+        let mut path_state = PathState::new(req.uri().path());
+        let matched = router.detect(&mut req, &mut path_state).await;
+        assert!(matched.is_some());
+        assert_eq!(path_state.params.get("path").map(|s| &**s), Some("contact"));
+        assert!(path_state.params.get("low").is_none());
+
+        let router = Router::new()
+            .push(Router::new().priority(1))
+            .unshift(Router::new().priority(-1))
+            .insert(0, Router::new())
+            .append(&mut vec![Router::new().priority(2)]);
+        let priorities = router
+            .routers()
+            .iter()
+            .map(|router| router.priority)
+            .collect::<Vec<_>>();
+        assert_eq!(priorities, [2, 1, 0, -1]);
+    }
+
     #[tokio::test]
     async fn test_router_detect_path_encoded() {
         let router = Router::new().path("api/{p}").get(fake_handler);
@@ -685,4 +1317,87 @@ mod tests {
         assert!(matched.is_some());
         assert_eq!(path_state.params["p"], "a/b/c");
     }
+
+    #[test]
+    fn test_router_url_for() {
+        let router = Router::new()
+            .push(
+                Router::with_path("users")
+                    .name("users")
+                    .get(fake_handler)
+                    .push(
+                        Router::with_path(r"{id|\d+}")
+                            .name("user_detail")
+                            .get(fake_handler)
+                            .push(
+                                Router::with_path("posts/{post_id:num}")
+                                    .name("user_post")
+                                    .get(fake_handler),
+                            ),
+                    ),
+            )
+            .push(
+                Router::with_path("files/{**path}")
+                    .name("files")
+                    .get(fake_handler),
+            );
+        assert_eq!(
+            router.url_for("users", [("page", "2")]).unwrap(),
+            "/users?page=2"
+        );
+        assert_eq!(
+            router.url_for("user_detail", [("id", 12)]).unwrap(),
+            "/users/12"
+        );
+        assert_eq!(
+            router
+                .url_for("user_post", [("id", "12"), ("post_id", "3"), ("q", "a b")])
+                .unwrap(),
+            "/users/12/posts/3?q=a+b"
+        );
+        assert_eq!(
+            router.url_for("files", [("path", "a b/c.txt")]).unwrap(),
+            "/files/a%20b/c.txt"
+        );
+        assert_eq!(
+            router.url_for("files", Vec::<(&str, &str)>::new()).unwrap(),
+            "/files"
+        );
+        assert!(router.url_for("user_detail", [("page", "2")]).is_none());
+        assert!(router.url_for("unknown", [("id", "2")]).is_none());
+    }
+
+    #[test]
+    fn test_router_routes() {
+        let router = Router::new()
+            .hoop(fake_handler)
+            .push(
+                Router::with_path("users")
+                    .get(fake_handler)
+                    .push(Router::with_path("{id}").name("user").delete(fake_handler)),
+            )
+            .push(
+                Router::with_path("static/{**path}")
+                    .host("example.com")
+                    .goal(fake_handler),
+            );
+        let routes = router.routes().collect::<Vec<_>>();
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].path, "users");
+        assert_eq!(routes[0].methods, vec![crate::http::Method::GET]);
+        assert_eq!(
+            routes[0].hoops,
+            vec!["salvo_core::routing::router::tests::fake_handler"]
+        );
+        assert_eq!(
+            routes[0].goal,
+            "salvo_core::routing::router::tests::fake_handler"
+        );
+        assert_eq!(routes[1].path, "users/{id}");
+        assert_eq!(routes[1].methods, vec![crate::http::Method::DELETE]);
+        assert_eq!(routes[1].name.as_deref(), Some("user"));
+        assert_eq!(routes[2].path, "static/{**path}");
+        assert!(routes[2].methods.is_empty());
+        assert_eq!(routes[2].filters, vec![r#"host:"example.com""#]);
+    }
 }
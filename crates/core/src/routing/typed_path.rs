@@ -0,0 +1,51 @@
+use indexmap::IndexMap;
+
+use super::router::fill_path_template;
+
+/// A type which defines a path template, its fields are the params in the path.
+///
+/// It is usually implemented by `#[derive(TypedPath)]`, the derived type can be used to register router by
+/// [`Router::with_typed_path`] and be used as an extractor in handler, so path definitions and handler signatures
+/// can't drift apart.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::routing::TypedPath;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, TypedPath)]
+/// #[salvo(typed_path(path = "users/{user_id}/posts/{post_id}"))]
+/// struct UserPost {
+///     user_id: u64,
+///     post_id: u64,
+/// }
+///
+/// #[handler]
+/// async fn show_post(post: UserPost) -> String {
+///     format!("{}", post.to_path())
+/// }
+///
+/// let router = Router::with_typed_path::<UserPost>().get(show_post);
+/// assert_eq!(UserPost { user_id: 1, post_id: 2 }.to_path(), "/users/1/posts/2");
+/// ```
+///
+/// [`Router::with_typed_path`]: crate::Router::with_typed_path
+pub trait TypedPath {
+    /// The path template, such as `users/{user_id}/posts/{post_id}`.
+    const PATH: &'static str;
+
+    /// Generate the url path by filling the path template with current value.
+    fn to_path(&self) -> String;
+}
+
+#[doc(hidden)]
+pub fn build_typed_path<const N: usize>(template: &str, params: [(&str, String); N]) -> String {
+    let mut params = params
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect::<IndexMap<_, _>>();
+    let path = fill_path_template(template, &mut params).unwrap_or_default();
+    format!("/{}", path.trim_start_matches('/'))
+}
@@ -14,7 +14,9 @@ macro_rules! forward_cow_parsed_value {
             {
                 match self.0.parse::<$ty>() {
                     Ok(val) => val.into_deserializer().$method(visitor),
-                    Err(e) => Err(DeError::custom(e))
+                    Err(e) => Err(DeError::custom(
+                        format_args!("{e}, expected {}", stringify!($ty))
+                    ))
                 }
             }
         )*
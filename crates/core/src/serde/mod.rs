@@ -15,6 +15,8 @@ mod vec_value;
 use vec_value::VecValue;
 mod flat_value;
 use flat_value::FlatValue;
+mod nested_value;
+use nested_value::NestedValue;
 
 #[inline]
 pub fn from_str_map<'de, I, T, K, V>(input: I) -> Result<T, ValError>
@@ -0,0 +1,304 @@
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+use serde::de::value::{Error as ValError, MapDeserializer, SeqDeserializer};
+use serde::de::{Deserializer, Error as DeError, IntoDeserializer, Visitor};
+
+use super::{CowValue, FlatValue};
+
+macro_rules! forward_nested_value {
+    ($($method:ident,)*) => {
+        $(
+            #[inline]
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self {
+                    Self::Leaf(items) => FlatValue(items).$method(visitor),
+                    map => map.deserialize_any(visitor),
+                }
+            }
+        )*
+    }
+}
+
+/// Value collected from the keys in bracket notation, e.g. `user[name]=x&user[tags][]=a&user[tags][]=b`.
+///
+/// Empty brackets append the value to the list, a map whose keys are all numbers can be deserialized as
+/// sequence, e.g. `items[0][id]=1&items[1][id]=2`.
+#[derive(Debug)]
+pub(super) enum NestedValue<'de> {
+    Leaf(Vec<CowValue<'de>>),
+    Map(IndexMap<Cow<'de, str>, NestedValue<'de>>),
+}
+
+impl<'de> NestedValue<'de> {
+    /// Collect the values of key `name` and the keys like `name[a][b]` from `pairs`.
+    pub(super) fn collect<I>(name: &str, pairs: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (&'de String, &'de Vec<String>)>,
+    {
+        let mut value = None::<Self>;
+        for (key, values) in pairs {
+            if let Some(path) = key_path(name, key) {
+                value
+                    .get_or_insert_with(|| Self::Leaf(Vec::new()))
+                    .insert(&path, values);
+            }
+        }
+        value
+    }
+
+    fn insert(&mut self, path: &[&'de str], values: &'de [String]) {
+        match path.split_first() {
+            None => {
+                // Plain values are ignored if the same key has nested values.
+                if let Self::Leaf(items) = self {
+                    items.extend(values.iter().map(|v| CowValue(Cow::Borrowed(v.as_str()))));
+                }
+            }
+            Some((segment, rest)) => {
+                if let Self::Leaf(_) = self {
+                    *self = Self::Map(IndexMap::new());
+                }
+                if let Self::Map(map) = self {
+                    map.entry(Cow::Borrowed(*segment))
+                        .or_insert_with(|| Self::Leaf(Vec::new()))
+                        .insert(rest, values);
+                }
+            }
+        }
+    }
+}
+
+/// Split `key` to the segments in brackets if it is `name` or starts with `name[`, empty segments are skipped.
+fn key_path<'a>(name: &str, key: &'a str) -> Option<Vec<&'a str>> {
+    let mut rest = key.strip_prefix(name)?;
+    let mut path = Vec::new();
+    while !rest.is_empty() {
+        let inner = rest.strip_prefix('[')?;
+        let end = inner.find(']')?;
+        if end > 0 {
+            path.push(&inner[..end]);
+        }
+        rest = &inner[end + 1..];
+    }
+    Some(path)
+}
+
+impl<'de> IntoDeserializer<'de> for NestedValue<'de> {
+    type Deserializer = Self;
+
+    #[inline]
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for NestedValue<'de> {
+    type Error = ValError;
+
+    #[inline]
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Leaf(items) => FlatValue(items).deserialize_any(visitor),
+            Self::Map(map) => visitor.visit_map(MapDeserializer::new(
+                map.into_iter().map(|(k, v)| (CowValue(k), v)),
+            )),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Leaf(items) => FlatValue(items).deserialize_seq(visitor),
+            Self::Map(map) => {
+                let mut items = Vec::with_capacity(map.len());
+                for (key, value) in map {
+                    match key.parse::<usize>() {
+                        Ok(index) => items.push((index, value)),
+                        Err(_) => {
+                            return Err(DeError::custom(format_args!(
+                                "invalid index `{key}`, expected sequence"
+                            )));
+                        }
+                    }
+                }
+                items.sort_by_key(|(index, _)| *index);
+                visitor.visit_seq(SeqDeserializer::new(
+                    items.into_iter().map(|(_, value)| value),
+                ))
+            }
+        }
+    }
+
+    #[inline]
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    #[inline]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Self::Leaf(items) => FlatValue(items).deserialize_enum(name, variants, visitor),
+            Self::Map(_) => Err(DeError::custom("expected unit variant")),
+        }
+    }
+
+    forward_nested_value! {
+        deserialize_bool,
+        deserialize_i8,
+        deserialize_i16,
+        deserialize_i32,
+        deserialize_i64,
+        deserialize_u8,
+        deserialize_u16,
+        deserialize_u32,
+        deserialize_u64,
+        deserialize_f32,
+        deserialize_f64,
+        deserialize_char,
+        deserialize_str,
+        deserialize_string,
+        deserialize_bytes,
+        deserialize_byte_buf,
+        deserialize_unit,
+        deserialize_map,
+        deserialize_identifier,
+        deserialize_ignored_any,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use multimap::MultiMap;
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[test]
+    fn test_key_path() {
+        assert_eq!(key_path("user", "user"), Some(vec![]));
+        assert_eq!(key_path("user", "user[tags][]"), Some(vec!["tags"]));
+        assert_eq!(
+            key_path("user", "user[items][0][id]"),
+            Some(vec!["items", "0", "id"])
+        );
+        assert_eq!(key_path("user", "username"), None);
+        assert_eq!(key_path("user", "user[name"), None);
+    }
+
+    #[test]
+    fn test_de_nested_value() {
+        #[derive(Deserialize, Eq, PartialEq, Debug)]
+        struct Item {
+            id: u32,
+        }
+        #[derive(Deserialize, Eq, PartialEq, Debug)]
+        struct User {
+            name: String,
+            age: u8,
+            tags: Vec<String>,
+            items: Vec<Item>,
+            nickname: Option<String>,
+        }
+
+        let mut pairs = MultiMap::new();
+        pairs.insert("user[name]".to_owned(), "jobs".to_owned());
+        pairs.insert("user[age]".to_owned(), "56".to_owned());
+        pairs.insert("user[tags][]".to_owned(), "a".to_owned());
+        pairs.insert("user[tags][]".to_owned(), "b".to_owned());
+        pairs.insert("user[items][1][id]".to_owned(), "2".to_owned());
+        pairs.insert("user[items][0][id]".to_owned(), "1".to_owned());
+        pairs.insert("username".to_owned(), "steve".to_owned());
+        let value = NestedValue::collect("user", pairs.iter_all()).unwrap();
+        let user = User::deserialize(value).unwrap();
+        assert_eq!(
+            user,
+            User {
+                name: "jobs".into(),
+                age: 56,
+                tags: vec!["a".into(), "b".into()],
+                items: vec![Item { id: 1 }, Item { id: 2 }],
+                nickname: None,
+            }
+        );
+        assert!(NestedValue::collect("order", pairs.iter_all()).is_none());
+    }
+}
@@ -3,8 +3,7 @@ use std::collections::HashMap;
 
 use indexmap::IndexMap;
 use multimap::MultiMap;
-use serde::de::value::Error as ValError;
-use serde::de::{self, Deserialize, Error as DeError, IntoDeserializer};
+use serde::de::{self, Deserialize, IntoDeserializer};
 use serde::forward_to_deserialize_any;
 use serde_json::value::RawValue;
 
@@ -12,10 +11,11 @@ use crate::Request;
 use crate::extract::Metadata;
 use crate::extract::metadata::{Field, Source, SourceFrom, SourceParser};
 use crate::http::ParseError;
+use crate::http::errors::FieldError;
 use crate::http::form::FormData;
 use crate::http::header::HeaderMap;
 
-use super::{CowValue, FlatValue, VecValue};
+use super::{CowValue, FlatValue, NestedValue, VecValue};
 
 pub async fn from_request<'de, T>(
     req: &'de mut Request,
@@ -40,7 +40,7 @@ where
             _ => {}
         }
     }
-    Ok(T::deserialize(RequestDeserializer::new(req, metadata)?)?)
+    T::deserialize(RequestDeserializer::new(req, metadata)?)
 }
 
 #[derive(Clone, Debug)]
@@ -76,6 +76,8 @@ pub(crate) struct RequestDeserializer<'de> {
     field_source: Option<&'de Source>,
     field_str_value: Option<&'de str>,
     field_vec_value: Option<Vec<CowValue<'de>>>,
+    field_nested_value: Option<NestedValue<'de>>,
+    field_default_value: Option<&'de str>,
 }
 
 impl<'de> RequestDeserializer<'de> {
@@ -125,6 +127,8 @@ impl<'de> RequestDeserializer<'de> {
             field_source: None,
             field_str_value: None,
             field_vec_value: None,
+            field_nested_value: None,
+            field_default_value: None,
         })
     }
 
@@ -150,7 +154,7 @@ impl<'de> RequestDeserializer<'de> {
         parser
     }
 
-    fn deserialize_value<T>(&mut self, seed: T) -> Result<T::Value, ValError>
+    fn deserialize_value<T>(&mut self, seed: T) -> Result<T::Value, ParseError>
     where
         T: de::DeserializeSeed<'de>,
     {
@@ -174,7 +178,18 @@ impl<'de> RequestDeserializer<'de> {
                 field_source: None,
                 field_str_value: None,
                 field_vec_value: None,
+                field_nested_value: None,
+                field_default_value: None,
             })
+        } else if let Some(value) = self.field_default_value.take() {
+            seed.deserialize(FlatValue(vec![CowValue(value.into())]))
+                .map_err(|e| {
+                    let field = &self.metadata.fields[self.field_index as usize];
+                    ParseError::InvalidField(Box::new(FieldError::new(
+                        self.field_name(field),
+                        e.to_string(),
+                    )))
+                })
         } else {
             let source = self
                 .field_source
@@ -182,31 +197,80 @@ impl<'de> RequestDeserializer<'de> {
                 .expect("`MapAccess::next_value` called before next_key");
 
             let parser = self.real_parser(source);
-            if source.from == SourceFrom::Body && parser == SourceParser::Json {
+            let result = if source.from == SourceFrom::Body && parser == SourceParser::Json {
                 // panic because this indicates a bug in the program rather than an expected failure.
                 let value = self
                     .field_str_value
                     .expect("MapAccess::next_value called before next_key");
                 let mut value = serde_json::Deserializer::new(serde_json::de::StrRead::new(value));
 
-                seed.deserialize(&mut value)
-                    .map_err(|_| ValError::custom("parse value error"))
+                seed.deserialize(&mut value).map_err(|e| e.to_string())
             } else if let Some(value) = self.field_str_value.take() {
                 seed.deserialize(CowValue(value.into()))
+                    .map_err(|e| e.to_string())
+            } else if let Some(value) = self.field_nested_value.take() {
+                seed.deserialize(value).map_err(|e| e.to_string())
             } else if let Some(value) = self.field_vec_value.take() {
-                if source.from == SourceFrom::Query || source.from == SourceFrom::Header {
-                    seed.deserialize(FlatValue(value))
-                } else {
-                    seed.deserialize(VecValue(value.into_iter()))
-                }
+                let result =
+                    if source.from == SourceFrom::Query || source.from == SourceFrom::Header {
+                        seed.deserialize(FlatValue(value))
+                    } else {
+                        seed.deserialize(VecValue(value.into_iter()))
+                    };
+                result.map_err(|e| e.to_string())
             } else {
-                Err(ValError::custom("parse value error"))
-            }
+                Err("parse value error".to_owned())
+            };
+            result.map_err(|message| {
+                let field = &self.metadata.fields[self.field_index as usize];
+                ParseError::InvalidField(Box::new(
+                    FieldError::new(self.field_name(field), message).source_from(source.from),
+                ))
+            })
+        }
+    }
+
+    fn field_name(&self, field: &'de Field) -> Cow<'de, str> {
+        if let Some(rename) = field.rename {
+            Cow::Borrowed(rename)
+        } else if let Some(serde_rename) = field.serde_rename {
+            Cow::Borrowed(serde_rename)
+        } else if let Some(rename_all) = self.metadata.rename_all {
+            Cow::Owned(rename_all.apply_to_field(field.decl_name))
+        } else if let Some(serde_rename_all) = self.metadata.serde_rename_all {
+            Cow::Owned(serde_rename_all.apply_to_field(field.decl_name))
+        } else {
+            Cow::Borrowed(field.decl_name)
         }
     }
 
-    #[allow(unreachable_patterns)]
     fn fill_value(&mut self, field: &'de Field) -> bool {
+        if self.fill_source_value(field) {
+            true
+        } else if let Some(default_value) = field.default_value {
+            self.field_default_value = Some(default_value);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn nested_value(
+        &self,
+        pairs: &'de MultiMap<String, String>,
+        field_name: &str,
+        field: &'de Field,
+    ) -> Option<NestedValue<'de>> {
+        NestedValue::collect(field_name, pairs.iter_all()).or_else(|| {
+            field
+                .aliases
+                .iter()
+                .find_map(|alias| NestedValue::collect(alias, pairs.iter_all()))
+        })
+    }
+
+    #[allow(unreachable_patterns)]
+    fn fill_source_value(&mut self, field: &'de Field) -> bool {
         if field.flatten {
             self.field_flatten = true;
             return true;
@@ -220,19 +284,11 @@ impl<'de> RequestDeserializer<'de> {
             return false;
         };
 
-        let field_name = if let Some(rename) = field.rename {
-            rename
-        } else if let Some(serde_rename) = field.serde_rename {
-            serde_rename
-        } else if let Some(rename_all) = self.metadata.rename_all {
-            &*rename_all.apply_to_field(field.decl_name)
-        } else if let Some(serde_rename_all) = self.metadata.serde_rename_all {
-            &*serde_rename_all.apply_to_field(field.decl_name)
-        } else {
-            field.decl_name
-        };
+        let field_name = self.field_name(field);
+        let field_name = &*field_name;
 
         for source in sources {
+            let field_name = source.rename.unwrap_or(field_name);
             match source.from {
                 SourceFrom::Param => {
                     let mut value = self.params.get(field_name);
@@ -251,6 +307,14 @@ impl<'de> RequestDeserializer<'de> {
                     }
                 }
                 SourceFrom::Query => {
+                    if source.parser == SourceParser::Nested {
+                        if let Some(value) = self.nested_value(self.queries, field_name, field) {
+                            self.field_nested_value = Some(value);
+                            self.field_source = Some(source);
+                            return true;
+                        }
+                        continue;
+                    }
                     let mut value = self.queries.get_vec(field_name);
                     if value.is_none() {
                         for alias in &field.aliases {
@@ -380,6 +444,18 @@ impl<'de> RequestDeserializer<'de> {
                             }
                             return false;
                         }
+                        SourceParser::Nested => {
+                            if let Some(Payload::FormData(form_data)) = self.payload {
+                                if let Some(value) =
+                                    self.nested_value(&form_data.fields, field_name, field)
+                                {
+                                    self.field_nested_value = Some(value);
+                                    self.field_source = Some(source);
+                                    return true;
+                                }
+                            }
+                            return false;
+                        }
                         _ => {
                             panic!("unsupported source parser: {:?}", parser);
                         }
@@ -396,6 +472,8 @@ impl<'de> RequestDeserializer<'de> {
             self.field_flatten = field.flatten;
             self.field_str_value = None;
             self.field_vec_value = None;
+            self.field_nested_value = None;
+            self.field_default_value = None;
 
             if self.fill_value(field) {
                 return field.serde_rename.map(Cow::from).or_else(|| {
@@ -412,7 +490,7 @@ impl<'de> RequestDeserializer<'de> {
 }
 
 impl<'de> de::Deserializer<'de> for RequestDeserializer<'de> {
-    type Error = ValError;
+    type Error = ParseError;
 
     #[inline]
     fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
@@ -438,7 +516,7 @@ impl<'de> de::Deserializer<'de> for RequestDeserializer<'de> {
 }
 
 impl<'de> de::MapAccess<'de> for RequestDeserializer<'de> {
-    type Error = ValError;
+    type Error = ParseError;
 
     #[inline]
     fn next_key_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
@@ -471,7 +549,8 @@ impl<'de> de::MapAccess<'de> for RequestDeserializer<'de> {
     {
         match self.next() {
             Some(key) => {
-                let key = kseed.deserialize(key.into_deserializer())?;
+                let key = kseed
+                    .deserialize(IntoDeserializer::<'de, ParseError>::into_deserializer(key))?;
                 let value = self.deserialize_value(vseed)?;
                 Ok(Some((key, value)))
             }
@@ -852,4 +931,158 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn test_de_request_repeated_query() {
+        use std::collections::HashSet;
+
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[salvo(extract(default_source(from = "query")))]
+        struct RequestData {
+            #[salvo(extract(rename = "tag"))]
+            tags: Vec<String>,
+            #[salvo(extract(rename = "id"))]
+            ids: HashSet<i64>,
+        }
+        let mut req = TestClient::get("http://127.0.0.1:5800/test")
+            .query("tag", "a")
+            .query("tag", "b")
+            .query("id", "1")
+            .query("id", "2")
+            .query("id", "1")
+            .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            RequestData {
+                tags: vec!["a".to_string(), "b".to_string()],
+                ids: HashSet::from([1, 2]),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_de_request_default_value() {
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[salvo(extract(default_source(from = "query")))]
+        struct RequestData {
+            #[salvo(extract(default = 20))]
+            size: u32,
+            #[salvo(extract(default = "name"))]
+            order_by: String,
+            #[salvo(extract(default = "[a, b]"))]
+            tags: Vec<String>,
+            #[serde(default)]
+            keyword: String,
+            page: Option<u32>,
+        }
+        let mut req = TestClient::get("http://127.0.0.1:5800/test").build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            RequestData {
+                size: 20,
+                order_by: "name".to_string(),
+                tags: vec!["a".to_string(), "b".to_string()],
+                keyword: "".to_string(),
+                page: None,
+            }
+        );
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/test")
+            .query("size", "50")
+            .query("tags", "c")
+            .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(data.size, 50);
+        assert_eq!(data.tags, vec!["c".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_de_request_source_rename() {
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        struct RequestData {
+            #[salvo(extract(
+                source(from = "header", rename = "x-request-id"),
+                source(from = "query", rename = "requestId")
+            ))]
+            request_id: String,
+        }
+        let mut req = TestClient::get("http://127.0.0.1:5800/test")
+            .add_header("x-request-id", "abc", true)
+            .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(data.request_id, "abc");
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/test")
+            .query("requestId", "def")
+            .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(data.request_id, "def");
+    }
+
+    #[tokio::test]
+    async fn test_de_request_nested() {
+        #[derive(Deserialize, Eq, PartialEq, Debug)]
+        struct User {
+            name: String,
+            tags: Vec<String>,
+        }
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        struct RequestData {
+            #[salvo(extract(source(from = "query", parse = "nested")))]
+            filter: User,
+            #[salvo(extract(source(from = "body", parse = "nested")))]
+            user: User,
+        }
+
+        let mut req = TestClient::post(
+            "http://127.0.0.1:5800/test?filter[name]=jobs&filter[tags][]=a&filter[tags][]=b",
+        )
+        .raw_form("user%5Bname%5D=steve&user%5Btags%5D%5B%5D=c")
+        .build();
+        let data: RequestData = req.extract().await.unwrap();
+        assert_eq!(
+            data,
+            RequestData {
+                filter: User {
+                    name: "jobs".into(),
+                    tags: vec!["a".into(), "b".into()],
+                },
+                user: User {
+                    name: "steve".into(),
+                    tags: vec!["c".into()],
+                },
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_de_request_field_error() {
+        use crate::extract::metadata::SourceFrom;
+        use crate::http::ParseError;
+
+        #[derive(Deserialize, Extractible, Eq, PartialEq, Debug)]
+        #[salvo(extract(default_source(from = "query")))]
+        struct RequestData {
+            name: String,
+            age: u8,
+        }
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/test")
+            .query("name", "jobs")
+            .query("age", "old")
+            .build();
+        let err = req.extract::<RequestData>().await.unwrap_err();
+        let field = err.field_error().unwrap();
+        assert_eq!(field.path, "age");
+        assert_eq!(field.source_from, Some(SourceFrom::Query));
+        assert_eq!(field.expected.as_deref(), Some("u8"));
+
+        let mut req = TestClient::get("http://127.0.0.1:5800/test")
+            .query("age", "18")
+            .build();
+        let err = req.extract::<RequestData>().await.unwrap_err();
+        assert!(matches!(&err, ParseError::InvalidField(field) if field.path == "name"));
+    }
 }
@@ -13,7 +13,9 @@ macro_rules! forward_vec_parsed_value {
                 if let Some(item) = self.0.into_iter().next() {
                     match item.0.parse::<$ty>() {
                         Ok(val) => val.into_deserializer().$method(visitor),
-                        Err(e) => Err(DeError::custom(e))
+                        Err(e) => Err(DeError::custom(
+                            format_args!("{e}, expected {}", stringify!($ty))
+                        ))
                     }
                 } else {
                     Err(DeError::custom("expected vec not empty"))
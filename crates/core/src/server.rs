@@ -3,6 +3,7 @@ use std::io::Result as IoResult;
 #[cfg(feature = "server-handle")]
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[cfg(not(any(feature = "http1", feature = "http2", feature = "quinn")))]
 compile_error!(
@@ -15,7 +16,7 @@ use hyper::server::conn::http1;
 use hyper::server::conn::http2;
 #[cfg(feature = "server-handle")]
 use tokio::{
-    time::Duration,sync::{
+    sync::{
     Notify,
     mpsc::{UnboundedReceiver, UnboundedSender}
 }};
@@ -82,8 +83,21 @@ impl ServerHandle {
     pub fn stop_graceful(&self, timeout: impl Into<Option<Duration>>) {
         let _ = self.tx_cmd.send(ServerCommand::StopGraceful(timeout.into()));
     }
+
+    /// Graceful shutdown server with a deadline.
+    ///
+    /// The hooks registered by [`Server::on_shutdown`] are called first, then the server stops accepting new
+    /// connections and waits for in-flight requests. The responses of the in-flight requests are sent with
+    /// `Connection: close` header. If there are still alive connections when `timeout` elapsed, they will be
+    /// aborted.
+    pub fn graceful_shutdown(&self, timeout: Duration) {
+        self.stop_graceful(timeout);
+    }
 }
 
+#[cfg(feature = "server-handle")]
+type ShutdownHook = Box<dyn Fn() + Send + Sync + 'static>;
+
 #[cfg(feature = "server-handle")]
 enum ServerCommand {
     StopForcible,
@@ -97,10 +111,14 @@ pub struct Server<A> {
     acceptor: A,
     builder: HttpBuilder,
     fuse_factory: Option<ArcFuseFactory>,
+    alt_svc_max_age: Duration,
+    alt_svc_port: Option<u16>,
     #[cfg(feature = "server-handle")]
     tx_cmd: UnboundedSender<ServerCommand>,
     #[cfg(feature = "server-handle")]
     rx_cmd: UnboundedReceiver<ServerCommand>,
+    #[cfg(feature = "server-handle")]
+    shutdown_hooks: Vec<ShutdownHook>,
 }
 
 impl<A: Acceptor + Send> Server<A> {
@@ -129,13 +147,56 @@ impl<A: Acceptor + Send> Server<A> {
             acceptor,
             builder,
             fuse_factory: None,
+            alt_svc_max_age: Duration::from_secs(2592000),
+            alt_svc_port: None,
             #[cfg(feature = "server-handle")]
             tx_cmd,
             #[cfg(feature = "server-handle")]
             rx_cmd,
+            #[cfg(feature = "server-handle")]
+            shutdown_hooks: Vec::new(),
+        }
+    }
+
+    cfg_feature! {
+        #![feature = "quinn"]
+        /// Sets the `ma` (max-age) of the `Alt-Svc` header which advertises the HTTP/3 endpoint.
+        ///
+        /// The `Alt-Svc` header is added to responses automatically if the acceptor holds an HTTP/3 endpoint.
+        /// Defaults to 30 days.
+        #[inline]
+        pub fn alt_svc_max_age(mut self, max_age: Duration) -> Self {
+            self.alt_svc_max_age = max_age;
+            self
+        }
+        /// Sets the port advertised in the `Alt-Svc` header, it is useful if the HTTP/3 endpoint is exposed on
+        /// a different port by a load balancer. Defaults to the port of the HTTP/3 endpoint.
+        #[inline]
+        pub fn alt_svc_port(mut self, port: u16) -> Self {
+            self.alt_svc_port = Some(port);
+            self
         }
     }
 
+    fn alt_svc_h3(&self) -> Option<HeaderValue> {
+        let mut alt_svc_h3 = None;
+        for holding in self.acceptor.holdings() {
+            tracing::info!("listening {}", holding);
+            if holding.http_versions.contains(&Version::HTTP_3) {
+                if let Some(addr) = holding.local_addr.clone().into_std() {
+                    let port = self.alt_svc_port.unwrap_or_else(|| addr.port());
+                    let max_age = self.alt_svc_max_age.as_secs();
+                    alt_svc_h3 = Some(
+                        format!(r#"h3=":{port}"; ma={max_age},h3-29=":{port}"; ma={max_age}"#)
+                            .parse::<HeaderValue>()
+                            .expect("Parse alt-svc header should not failed."),
+                    );
+                }
+            }
+        }
+        alt_svc_h3
+    }
+
     /// Set the fuse factory.
     pub fn fuse_factory<F>(mut self, factory: F) -> Self
     where
@@ -169,6 +230,15 @@ impl<A: Acceptor + Send> Server<A> {
         pub fn stop_graceful(&self, timeout: impl Into<Option<Duration>>) {
             let _ = self.tx_cmd.send(ServerCommand::StopGraceful(timeout.into()));
         }
+
+        /// Add a hook which is called when the server is going to stop, before it stops accepting new
+        /// connections and starts draining the alive connections.
+        ///
+        /// It can be used to flip the readiness probes, so that the load balancer stops sending new traffic.
+        pub fn on_shutdown(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+            self.shutdown_hooks.push(Box::new(hook));
+            self
+        }
     }
     
     /// Get holding information of this server.
@@ -235,11 +305,13 @@ impl<A: Acceptor + Send> Server<A> {
         S: Into<Service> + Send,
     {
         async{
+            let alt_svc_h3 = self.alt_svc_h3();
             let Self {
                 mut acceptor,
                 builder,
                 fuse_factory,
                 mut rx_cmd,
+                shutdown_hooks,
                 ..
             } = self;
             let alive_connections = Arc::new(AtomicUsize::new(0));
@@ -247,34 +319,21 @@ impl<A: Acceptor + Send> Server<A> {
             let force_stop_token = CancellationToken::new();
             let graceful_stop_token = CancellationToken::new();
 
-            let mut alt_svc_h3 = None;
-            for holding in acceptor.holdings() {
-                tracing::info!("listening {}", holding);
-                if holding.http_versions.contains(&Version::HTTP_3) {
-                    if let Some(addr) = holding.local_addr.clone().into_std() {
-                        let port = addr.port();
-                        alt_svc_h3 = Some(
-                            format!(r#"h3=":{port}"; ma=2592000,h3-29=":{port}"; ma=2592000"#)
-                                .parse::<HeaderValue>()
-                                .expect("Parse alt-svc header should not failed."),
-                        );
-                    }
-                }
-            }
-
             let service: Arc<Service> = Arc::new(service.into());
             let builder = Arc::new(builder);
             loop {
                 tokio::select! {
                     accepted = acceptor.accept(fuse_factory.clone()) => {
                         match accepted {
-                            Ok(Accepted { conn, local_addr, remote_addr, http_scheme, ..}) => {
+                            Ok(Accepted { conn, local_addr, remote_addr, http_scheme, extensions, ..}) => {
                                 alive_connections.fetch_add(1, Ordering::Release);
 
                                 let service = service.clone();
                                 let alive_connections = alive_connections.clone();
                                 let notify = notify.clone();
-                                let handler = service.hyper_handler(local_addr, remote_addr, http_scheme, conn.fusewire(), alt_svc_h3.clone());
+                                let mut handler = service.hyper_handler(local_addr, remote_addr, http_scheme, conn.fusewire(), alt_svc_h3.clone());
+                                handler.draining = Some(graceful_stop_token.clone());
+                                handler.conn_extensions = extensions;
                                 let builder = builder.clone();
 
                                 let force_stop_token = force_stop_token.clone();
@@ -304,6 +363,9 @@ impl<A: Acceptor + Send> Server<A> {
                         }
                     }
                     Some(cmd) = rx_cmd.recv() => {
+                        for hook in &shutdown_hooks {
+                            hook();
+                        }
                         match cmd {
                             ServerCommand::StopGraceful(timeout) => {
                                 let graceful_stop_token = graceful_stop_token.clone();
@@ -348,35 +410,23 @@ impl<A: Acceptor + Send> Server<A> {
         where
             S: Into<Service> + Send,
     {
+        let alt_svc_h3 = self.alt_svc_h3();
         let Self {
             mut acceptor,
             builder,
             fuse_factory,
             ..
         } = self;
-        let mut alt_svc_h3 = None;
-        for holding in acceptor.holdings() {
-            tracing::info!("listening {}", holding);
-            if holding.http_versions.contains(&Version::HTTP_3) {
-                if let Some(addr) = holding.local_addr.clone().into_std() {
-                    let port = addr.port();
-                    alt_svc_h3 = Some(
-                        format!(r#"h3=":{port}"; ma=2592000,h3-29=":{port}"; ma=2592000"#)
-                            .parse::<HeaderValue>()
-                            .expect("Parse alt-svc header should not failed."),
-                    );
-                }
-            }
-        }
 
         let service: Arc<Service> = Arc::new(service.into());
         let builder = Arc::new(builder);
         loop {
             match acceptor.accept(fuse_factory.clone()).await {
-                Ok(Accepted { conn, local_addr, remote_addr, http_scheme, ..}) => {
+                Ok(Accepted { conn, local_addr, remote_addr, http_scheme, extensions, ..}) => {
 
                     let service = service.clone();
-                    let handler = service.hyper_handler(local_addr, remote_addr, http_scheme, conn.fusewire(), alt_svc_h3.clone());
+                    let mut handler = service.hyper_handler(local_addr, remote_addr, http_scheme, conn.fusewire(), alt_svc_h3.clone());
+                    handler.conn_extensions = extensions;
                     let builder = builder.clone();
 
                     tokio::spawn(async move {
@@ -465,6 +515,27 @@ mod tests {
         assert!(result.contains("<code>404</code>"));
     }
 
+    #[cfg(feature = "server-handle")]
+    #[tokio::test]
+    async fn test_graceful_shutdown_hooks() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        let ready = Arc::new(AtomicBool::new(true));
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let server = Server::new(acceptor).on_shutdown({
+            let ready = ready.clone();
+            move || ready.store(false, Ordering::Release)
+        });
+        let handle = server.handle();
+        handle.graceful_shutdown(Duration::from_secs(1));
+        tokio::time::timeout(Duration::from_secs(5), server.serve(Router::new()))
+            .await
+            .unwrap();
+        assert!(!ready.load(Ordering::Acquire));
+    }
+
     #[test]
     fn test_regression_209() {
         #[cfg(feature = "acme")]
@@ -550,4 +621,35 @@ mod tests {
             Server::new(acceptor).serve(Router::new()).await;
         };
     }
+
+    #[cfg(feature = "http1")]
+    #[tokio::test]
+    async fn test_http_builder() {
+        use std::time::Duration;
+
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        use crate::conn::{Acceptor, HttpBuilder};
+
+        #[handler]
+        async fn hello() -> &'static str {
+            "Hello World"
+        }
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0].local_addr.clone().into_std().unwrap();
+        let builder = HttpBuilder::new()
+            .http1_keep_alive(false)
+            .http1_header_read_timeout(Duration::from_secs(5));
+        tokio::spawn(Server::with_http_builder(acceptor, builder).serve(Router::new().get(hello)));
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.contains("connection: close"));
+        assert!(response.ends_with("Hello World"));
+    }
 }
@@ -1,32 +1,83 @@
+#[cfg(feature = "router-swap")]
+use std::fmt::{self, Debug, Formatter};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Instant;
 
-use headers::HeaderValue;
-use http::header::{ALT_SVC, CONTENT_TYPE};
+#[cfg(feature = "router-swap")]
+use arc_swap::ArcSwap;
+use headers::{Connection, HeaderMapExt, HeaderValue};
+use http::header::{ALLOW, ALT_SVC, CONTENT_TYPE};
 use http::uri::Scheme;
 use hyper::service::Service as HyperService;
 use hyper::{Method, Request as HyperRequest, Response as HyperResponse};
+use tokio_util::sync::CancellationToken;
 
 use crate::catcher::{Catcher, write_error_default};
 use crate::conn::SocketAddr;
 use crate::fuse::ArcFusewire;
 use crate::handler::{Handler, WhenHoop};
+#[cfg(feature = "real-ip")]
+use crate::http::RealIp;
 use crate::http::body::{ReqBody, ResBody};
-use crate::http::{Mime, Request, Response, StatusCode};
-use crate::routing::{FlowCtrl, PathState, Router};
+use crate::http::{Mime, Request, Response, StatusCode, StatusError, Version};
+use crate::routing::{DetectMatched, FlowCtrl, PathState, Router};
+use crate::writing::Redirect;
 use crate::{Depot, async_trait};
 
+/// Signal of the server graceful shutdown.
+///
+/// The server inserts it into the extensions of every request. Handlers of long-lived connections,
+/// such as websocket, can wait for it to close the connection cleanly instead of being aborted when
+/// the graceful shutdown timeout elapsed.
+#[derive(Clone, Default, Debug)]
+pub struct ShutdownSignal(CancellationToken);
+impl ShutdownSignal {
+    /// Create a new `ShutdownSignal` which is not triggered.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Get the `ShutdownSignal` of the request, returns `None` if the request is not served by a
+    /// server.
+    #[inline]
+    pub fn of(req: &Request) -> Option<&Self> {
+        req.extensions().get::<Self>()
+    }
+    /// Trigger the signal, it is called by the server when graceful shutdown started.
+    #[inline]
+    pub fn trigger(&self) {
+        self.0.cancel();
+    }
+    /// Returns `true` if the server is shutting down.
+    #[inline]
+    pub fn is_triggered(&self) -> bool {
+        self.0.is_cancelled()
+    }
+    /// Wait until the server starts graceful shutdown.
+    #[inline]
+    pub fn triggered(&self) -> impl Future<Output = ()> + Send + Sync + 'static {
+        self.0.clone().cancelled_owned()
+    }
+}
+
 /// Service http request.
 #[non_exhaustive]
 pub struct Service {
     /// The router of this service.
     pub router: Arc<Router>,
+    /// The handle which replaces the router at runtime, see [`Service::with_swapper`].
+    #[cfg(feature = "router-swap")]
+    pub swapper: Option<RouterSwapper>,
     /// The catcher of this service.
     pub catcher: Option<Arc<Catcher>>,
     /// These hoops will always be called when request received.
     pub hoops: Vec<Arc<dyn Handler>>,
     /// The allowed media types of this service.
     pub allowed_media_types: Arc<Vec<Mime>>,
+    /// The resolver of the client address of requests forwarded by trusted proxies.
+    #[cfg(feature = "real-ip")]
+    pub real_ip: Option<Arc<RealIp>>,
 }
 
 impl Service {
@@ -38,15 +89,53 @@ impl Service {
     {
         Service {
             router: router.into(),
+            #[cfg(feature = "router-swap")]
+            swapper: None,
             catcher: None,
             hoops: vec![],
             allowed_media_types: Arc::new(vec![]),
+            #[cfg(feature = "real-ip")]
+            real_ip: None,
         }
     }
 
-    /// Get router in this `Service`.
+    /// Create a new Service whose router can be replaced at runtime by the [`RouterSwapper`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use salvo_core::prelude::*;
+    /// use salvo_core::RouterSwapper;
+    ///
+    /// # #[handler] async fn hello() {}
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let swapper = RouterSwapper::new(Router::new().get(hello));
+    ///     let service = Service::with_swapper(swapper.clone());
+    ///     tokio::spawn(async move {
+    ///         // Reload config or plugins, then replace the router.
+    ///         swapper.swap(Router::with_path("hello").get(hello));
+    ///     });
+    ///
+    ///     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+    ///     Server::new(acceptor).serve(service).await;
+    /// }
+    /// ```
+    #[cfg(feature = "router-swap")]
+    #[inline]
+    pub fn with_swapper(swapper: RouterSwapper) -> Service {
+        let mut service = Service::new(swapper.load());
+        service.swapper = Some(swapper);
+        service
+    }
+
+    /// Get the current router in this `Service`.
     #[inline]
     pub fn router(&self) -> Arc<Router> {
+        #[cfg(feature = "router-swap")]
+        if let Some(swapper) = &self.swapper {
+            return swapper.load();
+        }
         self.router.clone()
     }
 
@@ -122,6 +211,24 @@ impl Service {
         self
     }
 
+    /// Sets the [`RealIp`] which resolves the client address of requests forwarded by trusted
+    /// proxies, it is returned by [`Request::client_ip`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::http::RealIp;
+    /// use salvo_core::prelude::*;
+    ///
+    /// let service = Service::new(Router::new()).real_ip(RealIp::new().trust_private_networks());
+    /// ```
+    #[cfg(feature = "real-ip")]
+    #[inline]
+    pub fn real_ip(mut self, real_ip: RealIp) -> Self {
+        self.real_ip = Some(Arc::new(real_ip));
+        self
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn hyper_handler(
@@ -137,11 +244,17 @@ impl Service {
             remote_addr,
             http_scheme,
             router: self.router.clone(),
+            #[cfg(feature = "router-swap")]
+            swapper: self.swapper.clone(),
             catcher: self.catcher.clone(),
             hoops: self.hoops.clone(),
             allowed_media_types: self.allowed_media_types.clone(),
             fusewire,
             alt_svc_h3,
+            draining: None,
+            #[cfg(feature = "real-ip")]
+            real_ip: self.real_ip.clone(),
+            conn_extensions: Default::default(),
         }
     }
     /// Handle new request, this function only used for test.
@@ -171,6 +284,38 @@ where
     }
 }
 
+/// A handle to replace the router of a running [`Service`] atomically.
+///
+/// The requests received after swapping are handled by the new router, while the in-flight requests are
+/// still handled by the old router, so no request is dropped and the listeners are not restarted.
+#[cfg(feature = "router-swap")]
+#[derive(Clone)]
+pub struct RouterSwapper(Arc<ArcSwap<Router>>);
+#[cfg(feature = "router-swap")]
+impl RouterSwapper {
+    /// Create a new `RouterSwapper` with the initial router.
+    #[inline]
+    pub fn new(router: impl Into<Arc<Router>>) -> Self {
+        Self(Arc::new(ArcSwap::new(router.into())))
+    }
+    /// Get the current router.
+    #[inline]
+    pub fn load(&self) -> Arc<Router> {
+        self.0.load_full()
+    }
+    /// Replace the current router with the new one, returns the old router.
+    #[inline]
+    pub fn swap(&self, router: impl Into<Arc<Router>>) -> Arc<Router> {
+        self.0.swap(router.into())
+    }
+}
+#[cfg(feature = "router-swap")]
+impl Debug for RouterSwapper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RouterSwapper").finish_non_exhaustive()
+    }
+}
+
 struct DefaultStatusOK;
 #[async_trait]
 impl Handler for DefaultStatusOK {
@@ -188,6 +333,47 @@ impl Handler for DefaultStatusOK {
     }
 }
 
+async fn call_with_timeout(
+    ctrl: &mut FlowCtrl,
+    dm: &DetectMatched,
+    req: &mut Request,
+    depot: &mut Depot,
+    res: &mut Response,
+) {
+    if let Some(size) = dm.max_body_size {
+        req.max_body_size = Some(size);
+    }
+    if let Some(timeout) = dm.timeout {
+        req.deadline = Some(Instant::now() + timeout);
+        if tokio::time::timeout(timeout, ctrl.call_next(req, depot, res))
+            .await
+            .is_err()
+        {
+            res.headers_mut().typed_insert(Connection::close());
+            let error = dm
+                .timeout_status
+                .and_then(StatusError::from_code)
+                .unwrap_or_else(StatusError::service_unavailable);
+            res.render(error.brief("Server process the request timeout."));
+        }
+    } else {
+        ctrl.call_next(req, depot, res).await;
+    }
+}
+
+/// Set the methods which the router accepts for current request path to `Allow` header, they are collected
+/// while detecting the router.
+fn insert_allow_header(path_state: &PathState, res: &mut Response) {
+    let allowed = path_state
+        .allowed_methods
+        .iter()
+        .map(|m| m.as_str())
+        .collect::<Vec<_>>();
+    if let Ok(value) = HeaderValue::from_str(&allowed.join(", ")) {
+        res.headers_mut().insert(ALLOW, value);
+    }
+}
+
 #[doc(hidden)]
 #[derive(Clone)]
 pub struct HyperHandler {
@@ -195,11 +381,17 @@ pub struct HyperHandler {
     pub(crate) remote_addr: SocketAddr,
     pub(crate) http_scheme: Scheme,
     pub(crate) router: Arc<Router>,
+    #[cfg(feature = "router-swap")]
+    pub(crate) swapper: Option<RouterSwapper>,
     pub(crate) catcher: Option<Arc<Catcher>>,
     pub(crate) hoops: Vec<Arc<dyn Handler>>,
     pub(crate) allowed_media_types: Arc<Vec<Mime>>,
     pub(crate) fusewire: Option<ArcFusewire>,
     pub(crate) alt_svc_h3: Option<HeaderValue>,
+    pub(crate) draining: Option<CancellationToken>, // Cancelled when the server starts graceful shutdown.
+    #[cfg(feature = "real-ip")]
+    pub(crate) real_ip: Option<Arc<RealIp>>,
+    pub(crate) conn_extensions: http::Extensions, // Connection level extensions, cloned into every request.
 }
 impl HyperHandler {
     /// Handle [`Request`] and returns [`Response`].
@@ -208,6 +400,16 @@ impl HyperHandler {
         let allowed_media_types = self.allowed_media_types.clone();
         req.local_addr = self.local_addr.clone();
         req.remote_addr = self.remote_addr.clone();
+        #[cfg(feature = "real-ip")]
+        if let Some(real_ip) = &self.real_ip {
+            real_ip.apply(&mut req);
+        }
+        if !self.conn_extensions.is_empty() {
+            req.extensions.extend(self.conn_extensions.clone());
+        }
+        if let Some(draining) = &self.draining {
+            req.extensions.insert(ShutdownSignal(draining.clone()));
+        }
         #[cfg(not(feature = "cookie"))]
         let mut res = Response::new();
         #[cfg(feature = "cookie")]
@@ -219,15 +421,41 @@ impl HyperHandler {
         }
         let mut depot = Depot::new();
         let mut path_state = PathState::new(req.uri().path());
+        // In-flight requests keep the router they started with even if it is swapped.
+        #[cfg(feature = "router-swap")]
+        let router = self
+            .swapper
+            .as_ref()
+            .map_or_else(|| self.router.clone(), RouterSwapper::load);
+        #[cfg(not(feature = "router-swap"))]
         let router = self.router.clone();
 
         let hoops = self.hoops.clone();
+        let draining = self.draining.clone();
         async move {
-            if let Some(dm) = router.detect(&mut req, &mut path_state).await {
+            depot.inject(router.clone());
+            #[cfg(any(feature = "rustls", feature = "openssl"))]
+            if let Some(cert) = req.extensions().get::<crate::conn::ClientCert>() {
+                depot.inject(cert.clone());
+            }
+            let detected = router.detect(&mut req, &mut path_state).await;
+            if let (Some(_), Some(canonical_path)) = (&detected, path_state.canonical_path()) {
+                let location = match req.uri().query() {
+                    Some(query) => format!("{canonical_path}?{query}"),
+                    None => canonical_path,
+                };
+                match Redirect::with_status_code(StatusCode::PERMANENT_REDIRECT, &*location) {
+                    Ok(redirect) => res.render(redirect),
+                    Err(e) => {
+                        tracing::error!(error = ?e, %location, "redirect to canonical path failed");
+                        res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+                    }
+                }
+            } else if let Some(dm) = detected {
                 req.params = path_state.params;
                 #[cfg(feature = "matched-path")]
                 {
-                    req.matched_path = path_state.matched_parts.join("/");
+                    req.matched_path = Some(path_state.matched_parts.join("/"));
                 }
                 // Set default status code before service hoops executed.
                 // We hope all hoops in service can get the correct status code.
@@ -236,16 +464,29 @@ impl HyperHandler {
                         &hoops[..],
                         &dm.hoops[..],
                         &[Arc::new(DefaultStatusOK)],
-                        &[dm.goal],
+                        std::slice::from_ref(&dm.goal),
                     ]
                     .concat(),
                 );
-                ctrl.call_next(&mut req, &mut depot, &mut res).await;
+                call_with_timeout(&mut ctrl, &dm, &mut req, &mut depot, &mut res).await;
                 // Set it to default status code again if any hoop set status code to None.
                 if res.status_code.is_none() {
                     res.status_code = Some(StatusCode::OK);
                 }
+            } else if let (false, Some(dm)) = (path_state.once_ended, path_state.fallback.take()) {
+                req.params = path_state.params;
+                res.status_code = Some(StatusCode::NOT_FOUND);
+                let mut ctrl = FlowCtrl::new(
+                    [&hoops[..], &dm.hoops[..], std::slice::from_ref(&dm.goal)].concat(),
+                );
+                call_with_timeout(&mut ctrl, &dm, &mut req, &mut depot, &mut res).await;
+                if res.status_code.is_none() {
+                    res.status_code = Some(StatusCode::NOT_FOUND);
+                }
             } else if !hoops.is_empty() {
+                if path_state.once_ended && path_state.allow_header {
+                    insert_allow_header(&path_state, &mut res);
+                }
                 req.params = path_state.params;
                 // Set default status code before service hoops executed.
                 // We hope all hoops in service can get the correct status code.
@@ -261,6 +502,9 @@ impl HyperHandler {
                     res.status_code = Some(StatusCode::METHOD_NOT_ALLOWED);
                 }
             } else if path_state.once_ended {
+                if path_state.allow_header {
+                    insert_allow_header(&path_state, &mut res);
+                }
                 res.status_code = Some(StatusCode::METHOD_NOT_ALLOWED);
             }
 
@@ -320,6 +564,12 @@ impl HyperHandler {
                     "request with head method should not have body: https://developer.mozilla.org/en-US/docs/Web/HTTP/Methods/HEAD"
                 );
             }
+            // Ask the client not to reuse the connection while the server is draining.
+            if draining.is_some_and(|token| token.is_cancelled())
+                && req.version() <= Version::HTTP_11
+            {
+                res.headers_mut().typed_insert(Connection::close());
+            }
             #[cfg(feature = "quinn")]
             {
                 use bytes::Bytes;
@@ -395,6 +645,10 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::ShutdownSignal;
     use crate::prelude::*;
     use crate::test::{ResponseExt, TestClient};
 
@@ -529,4 +783,327 @@ mod tests {
             .await;
         assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
     }
+
+    #[tokio::test]
+    async fn test_service_405_allow_header() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+        let router = Router::new().method_not_allowed(true).push(
+            Router::with_path("hello")
+                .get(hello)
+                .post(hello)
+                .push(Router::with_path("world").delete(hello)),
+        );
+        let service = Service::new(router);
+
+        let res = TestClient::put("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.headers().get("allow").unwrap(), "GET, POST");
+
+        let res = TestClient::get("http://127.0.0.1:5801/hello/world")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(res.headers().get("allow").unwrap(), "DELETE");
+
+        let service = Service::new(Router::with_path("hello").get(hello));
+        let res = TestClient::put("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::METHOD_NOT_ALLOWED);
+        assert!(res.headers().get("allow").is_none());
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let router = Router::new().method_not_allowed(true).push(
+            Router::with_path("hello")
+                .filter_fn(move |_, _| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    true
+                })
+                .get(hello)
+                .head(hello),
+        );
+        let res = TestClient::put("http://127.0.0.1:5801/hello")
+            .send(&Service::new(router))
+            .await;
+        assert_eq!(res.headers().get("allow").unwrap(), "GET, HEAD");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_service_router_timeout() {
+        #[handler]
+        async fn slow(req: &mut Request) -> &'static str {
+            assert!(req.deadline().is_some());
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            "slow"
+        }
+        #[handler]
+        async fn fast(req: &mut Request) -> &'static str {
+            assert!(req.deadline().is_none());
+            "fast"
+        }
+        let router = Router::new()
+            .push(
+                Router::with_path("outer")
+                    .timeout(std::time::Duration::from_secs(10))
+                    .timeout_status(StatusCode::GATEWAY_TIMEOUT)
+                    .push(
+                        Router::with_path("inner")
+                            .timeout(std::time::Duration::from_millis(50))
+                            .get(slow),
+                    ),
+            )
+            .push(Router::with_path("fast").get(fast));
+        let service = Service::new(router);
+
+        let res = TestClient::get("http://127.0.0.1:5801/outer/inner")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(res.headers().get("connection").unwrap(), "close");
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/fast")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "fast");
+    }
+
+    #[tokio::test]
+    async fn test_service_router_max_body_size() {
+        use http_body_util::BodyExt;
+
+        use crate::http::ParseError;
+
+        #[handler]
+        async fn payload(req: &mut Request) -> Result<String, ParseError> {
+            req.payload().await.map(|p| format!("{}", p.len()))
+        }
+        #[handler]
+        async fn stream(req: &mut Request) -> Result<String, ParseError> {
+            let body = req
+                .take_body()
+                .collect()
+                .await
+                .map_err(ParseError::from_body_error)?;
+            Ok(format!("{}", body.to_bytes().len()))
+        }
+        #[handler]
+        async fn enlarge(req: &mut Request) {
+            req.set_max_body_size(64);
+        }
+        let router = Router::new()
+            .max_body_size(16)
+            .push(Router::with_path("payload").post(payload))
+            .push(Router::with_path("stream").post(stream))
+            .push(Router::with_path("enlarge").hoop(enlarge).post(stream))
+            .push(Router::with_path("shrink").max_body_size(4).post(payload));
+        let service = Service::new(router);
+
+        let body = "a".repeat(32);
+        for (path, status) in [
+            ("payload", StatusCode::PAYLOAD_TOO_LARGE),
+            ("stream", StatusCode::PAYLOAD_TOO_LARGE),
+            ("enlarge", StatusCode::OK),
+        ] {
+            let res = TestClient::post(format!("http://127.0.0.1:5801/{path}"))
+                .text(body.clone())
+                .send(&service)
+                .await;
+            assert_eq!(res.status_code.unwrap(), status);
+        }
+        let res = TestClient::post("http://127.0.0.1:5801/shrink")
+            .text("abcdef")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PAYLOAD_TOO_LARGE);
+        let res = TestClient::post("http://127.0.0.1:5801/payload")
+            .text("abcdef")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_service_router_fallback() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+        #[handler]
+        async fn api_fallback(res: &mut Response) {
+            res.render(Json(serde_json::json!({"error": "api not found"})));
+        }
+        #[handler]
+        async fn v2_fallback() -> &'static str {
+            "v2 not found"
+        }
+        let router = Router::new()
+            .push(
+                Router::with_path("api")
+                    .fallback(api_fallback)
+                    .push(Router::with_path("hello").get(hello))
+                    .push(Router::with_path("v2").fallback(v2_fallback)),
+            )
+            .push(Router::with_path("hello").get(hello));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/api/world")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
+        assert!(res.take_string().await.unwrap().contains("api not found"));
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/api/v2/world")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
+        assert_eq!(res.take_string().await.unwrap(), "v2 not found");
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/api/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+
+        let res = TestClient::get("http://127.0.0.1:5801/world")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_service_case_insensitive() {
+        #[handler]
+        async fn legacy() -> &'static str {
+            "legacy"
+        }
+        let router = Router::new()
+            .push(
+                Router::with_path("Legacy")
+                    .case_insensitive(true)
+                    .push(Router::with_path("Default.aspx").get(legacy))
+                    .push(
+                        Router::with_path("Redirect/{id}/Page.aspx")
+                            .case_redirect(true)
+                            .get(legacy),
+                    ),
+            )
+            .push(Router::with_path("Strict").get(legacy));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/legacy/DEFAULT.aspx")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "legacy");
+
+        let res = TestClient::get("http://127.0.0.1:5801/Legacy/redirect/Ab/page.ASPX?a=1")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            res.headers().get("location").unwrap(),
+            "/Legacy/Redirect/Ab/Page.aspx?a=1"
+        );
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/Legacy/Redirect/ab/Page.aspx")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "legacy");
+
+        let res = TestClient::get("http://127.0.0.1:5801/strict")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_service_draining() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+        let service = Service::new(Router::new().get(hello));
+        let req: Request = TestClient::get("http://127.0.0.1:5801").build();
+        let mut handler = service.hyper_handler(
+            req.local_addr.clone(),
+            req.remote_addr.clone(),
+            req.scheme.clone(),
+            None,
+            None,
+        );
+        let token = tokio_util::sync::CancellationToken::new();
+        handler.draining = Some(token.clone());
+        let res = handler.handle(req).await;
+        assert!(res.headers().get("connection").is_none());
+
+        token.cancel();
+        let req: Request = TestClient::get("http://127.0.0.1:5801").build();
+        let res = handler.handle(req).await;
+        assert_eq!(res.headers().get("connection").unwrap(), "close");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_signal() {
+        #[handler]
+        async fn signal(req: &mut Request) -> String {
+            let shutdown = ShutdownSignal::of(req).unwrap();
+            format!("{}", shutdown.is_triggered())
+        }
+        let service = Service::new(Router::new().get(signal));
+        let req: Request = TestClient::get("http://127.0.0.1:5801").build();
+        let mut handler = service.hyper_handler(
+            req.local_addr.clone(),
+            req.remote_addr.clone(),
+            req.scheme.clone(),
+            None,
+            None,
+        );
+        let token = tokio_util::sync::CancellationToken::new();
+        handler.draining = Some(token.clone());
+        let mut res = handler.handle(req).await;
+        assert_eq!(res.take_string().await.unwrap(), "false");
+
+        let waiter = ShutdownSignal(token.clone()).triggered();
+        token.cancel();
+        waiter.await;
+        let req: Request = TestClient::get("http://127.0.0.1:5801").build();
+        let mut res = handler.handle(req).await;
+        assert_eq!(res.take_string().await.unwrap(), "true");
+    }
+
+    #[cfg(feature = "router-swap")]
+    #[tokio::test]
+    async fn test_service_router_swapper() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+        #[handler]
+        async fn world() -> &'static str {
+            "world"
+        }
+        let swapper = super::RouterSwapper::new(Router::with_path("hello").get(hello));
+        let service = Service::with_swapper(swapper.clone());
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+
+        let old = swapper.swap(Router::with_path("world").get(world));
+        assert_eq!(old.routes().count(), 1);
+        let res = TestClient::get("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_FOUND);
+        let mut res = TestClient::get("http://127.0.0.1:5801/world")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "world");
+    }
 }
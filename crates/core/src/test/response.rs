@@ -12,7 +12,7 @@ use zstd::stream::write::Decoder as ZstdDecoder;
 
 use crate::Error;
 use crate::catcher::status_error_bytes;
-use crate::http::header::{self, CONTENT_ENCODING};
+use crate::http::header::{self, CONTENT_ENCODING, HeaderMap};
 use crate::http::response::{ResBody, Response};
 
 struct Writer {
@@ -60,6 +60,10 @@ pub trait ResponseExt {
         &mut self,
         content_type: Option<&Mime>,
     ) -> impl Future<Output = crate::Result<Bytes>> + Send;
+    /// Take all body bytes and the trailers sent after the body.
+    fn take_bytes_with_trailers(
+        &mut self,
+    ) -> impl Future<Output = crate::Result<(Bytes, Option<HeaderMap>)>> + Send;
 }
 
 impl ResponseExt for Response {
@@ -147,4 +151,9 @@ impl ResponseExt for Response {
         };
         Ok(bytes)
     }
+    async fn take_bytes_with_trailers(&mut self) -> crate::Result<(Bytes, Option<HeaderMap>)> {
+        let collected = BodyExt::collect(self.take_body()).await?;
+        let trailers = collected.trailers().cloned();
+        Ok((collected.to_bytes(), trailers))
+    }
 }
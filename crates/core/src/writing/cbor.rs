@@ -0,0 +1,119 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use serde::Serialize;
+
+use super::{Scribe, try_set_header};
+use crate::http::header::{CONTENT_TYPE, HeaderValue};
+use crate::http::{Response, StatusError};
+
+/// Write serializable content to response as CBOR content.
+///
+/// It will set `content-type` to `application/cbor`.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Cbor;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///    name: String,
+/// }
+/// #[handler]
+/// async fn hello(res: &mut Response) -> Cbor<User> {
+///     Cbor(User { name: "jobs".into() })
+/// }
+/// ```
+pub struct Cbor<T>(pub T);
+
+impl<T> Scribe for Cbor<T>
+where
+    T: Serialize + Send,
+{
+    fn render(self, res: &mut Response) {
+        let mut bytes = Vec::new();
+        match ciborium::into_writer(&self.0, &mut bytes) {
+            Ok(()) => {
+                try_set_header(
+                    &mut res.headers,
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/cbor"),
+                );
+                let _ = res.write_body(bytes);
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "CborContent write error");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+impl<T: Debug> Debug for Cbor<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Cbor").field(&self.0).finish()
+    }
+}
+impl<T: Display> Display for Cbor<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct User {
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_write_cbor_content() {
+        #[handler]
+        async fn test() -> Cbor<User> {
+            Cbor(User {
+                name: "jobs".into(),
+            })
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/cbor"
+        );
+        let bytes = res.take_bytes(None).await.unwrap();
+        assert_eq!(
+            ciborium::from_reader::<User, _>(&bytes[..]).unwrap(),
+            User {
+                name: "jobs".into()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_cbor() {
+        let user = User {
+            name: "jobs".into(),
+        };
+        let mut req = TestClient::post("http://127.0.0.1:5800/test")
+            .add_header("content-type", "application/cbor", true)
+            .bytes({
+                let mut bytes = Vec::new();
+                ciborium::into_writer(&user, &mut bytes).unwrap();
+                bytes
+            })
+            .build();
+        assert_eq!(req.parse_cbor::<User>().await.unwrap(), user);
+    }
+}
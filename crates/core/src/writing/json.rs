@@ -1,9 +1,11 @@
 use std::fmt::{self, Debug, Display, Formatter};
 
 use async_trait::async_trait;
+use bytes::BufMut;
 use serde::Serialize;
 
 use super::{Scribe, try_set_header};
+use crate::Error;
 use crate::http::header::{CONTENT_TYPE, HeaderValue};
 use crate::http::{Response, StatusError};
 
@@ -34,19 +36,19 @@ where
     T: Serialize + Send,
 {
     fn render(self, res: &mut Response) {
-        match serde_json::to_vec(&self.0) {
-            Ok(bytes) => {
+        match res.write_body_with(|buf| serde_json::to_writer(buf.writer(), &self.0)) {
+            Ok(()) => {
                 try_set_header(
                     &mut res.headers,
                     CONTENT_TYPE,
                     HeaderValue::from_static("application/json; charset=utf-8"),
                 );
-                let _ = res.write_body(bytes);
             }
-            Err(e) => {
+            Err(Error::SerdeJson(e)) => {
                 tracing::error!(error = ?e, "JsonContent write error");
                 res.render(StatusError::internal_server_error());
             }
+            Err(_) => {}
         }
     }
 }
@@ -1,17 +1,45 @@
 //! Writer trait and it's implements.
 
 mod json;
+mod ndjson;
+mod negotiate;
+mod problem;
 mod redirect;
 mod seek;
 mod text;
 
+use bytes::Bytes;
 use http::header::{AsHeaderName, IntoHeaderName};
 use http::{HeaderMap, StatusCode};
 pub use json::Json;
+pub use ndjson::NdJson;
+pub use negotiate::Negotiate;
+pub use problem::Problem;
 pub use redirect::Redirect;
 pub use seek::ReadSeeker;
 pub use text::Text;
 
+cfg_feature! {
+    #![feature = "msgpack"]
+    mod msgpack;
+    pub use msgpack::MsgPack;
+}
+cfg_feature! {
+    #![feature = "cbor"]
+    mod cbor;
+    pub use cbor::Cbor;
+}
+cfg_feature! {
+    #![feature = "yaml"]
+    mod yaml;
+    pub use yaml::Yaml;
+}
+cfg_feature! {
+    #![feature = "protobuf"]
+    mod protobuf;
+    pub use protobuf::ProtoBuf;
+}
+
 use crate::http::header::{CONTENT_TYPE, HeaderValue};
 use crate::{Depot, Request, Response, async_trait};
 
@@ -128,6 +156,28 @@ impl Scribe for String {
         let _ = res.write_body(self);
     }
 }
+impl Scribe for Bytes {
+    #[inline]
+    fn render(self, res: &mut Response) {
+        try_set_header(
+            &mut res.headers,
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/octet-stream"),
+        );
+        let _ = res.write_body(self);
+    }
+}
+impl Scribe for Vec<u8> {
+    #[inline]
+    fn render(self, res: &mut Response) {
+        try_set_header(
+            &mut res.headers,
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/octet-stream"),
+        );
+        let _ = res.write_body(self);
+    }
+}
 impl Scribe for std::convert::Infallible {
     #[inline]
     fn render(self, _res: &mut Response) {}
@@ -166,6 +216,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
+
     use crate::prelude::*;
 
     use crate::test::{ResponseExt, TestClient};
@@ -206,4 +258,32 @@ mod tests {
             "text/plain; charset=utf-8"
         );
     }
+
+    #[tokio::test]
+    async fn test_write_bytes() {
+        #[handler]
+        async fn hello_bytes() -> Bytes {
+            Bytes::from_static(b"hello")
+        }
+        #[handler]
+        async fn hello_vec() -> Vec<u8> {
+            b"hello".to_vec()
+        }
+
+        let router = Router::new()
+            .push(Router::with_path("bytes").get(hello_bytes))
+            .push(Router::with_path("vec").get(hello_vec));
+        let service = Service::new(router);
+        for path in ["bytes", "vec"] {
+            let mut res = TestClient::get(format!("http://127.0.0.1:5800/{path}"))
+                .send(&service)
+                .await;
+            assert!(res.body.is_once());
+            assert_eq!(res.take_string().await.unwrap(), "hello");
+            assert_eq!(
+                res.headers().get("content-type").unwrap(),
+                "application/octet-stream"
+            );
+        }
+    }
 }
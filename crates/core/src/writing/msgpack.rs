@@ -0,0 +1,116 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use serde::Serialize;
+
+use super::{Scribe, try_set_header};
+use crate::http::header::{CONTENT_TYPE, HeaderValue};
+use crate::http::{Response, StatusError};
+
+/// Write serializable content to response as MessagePack content.
+///
+/// It will set `content-type` to `application/msgpack`.
+///
+/// Struct fields are encoded as maps with names, so the data can be decoded by clients without schemas.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::MsgPack;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///    name: String,
+/// }
+/// #[handler]
+/// async fn hello(res: &mut Response) -> MsgPack<User> {
+///     MsgPack(User { name: "jobs".into() })
+/// }
+/// ```
+pub struct MsgPack<T>(pub T);
+
+impl<T> Scribe for MsgPack<T>
+where
+    T: Serialize + Send,
+{
+    fn render(self, res: &mut Response) {
+        match rmp_serde::to_vec_named(&self.0) {
+            Ok(bytes) => {
+                try_set_header(
+                    &mut res.headers,
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/msgpack"),
+                );
+                let _ = res.write_body(bytes);
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "MsgPackContent write error");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+impl<T: Debug> Debug for MsgPack<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("MsgPack").field(&self.0).finish()
+    }
+}
+impl<T: Display> Display for MsgPack<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct User {
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_write_msgpack_content() {
+        #[handler]
+        async fn test() -> MsgPack<User> {
+            MsgPack(User {
+                name: "jobs".into(),
+            })
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/msgpack"
+        );
+        let bytes = res.take_bytes(None).await.unwrap();
+        assert_eq!(
+            rmp_serde::from_slice::<User>(&bytes).unwrap(),
+            User {
+                name: "jobs".into()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_msgpack() {
+        let user = User {
+            name: "jobs".into(),
+        };
+        let mut req = TestClient::post("http://127.0.0.1:5800/test")
+            .add_header("content-type", "application/msgpack", true)
+            .bytes(rmp_serde::to_vec_named(&user).unwrap())
+            .build();
+        assert_eq!(req.parse_msgpack::<User>().await.unwrap(), user);
+    }
+}
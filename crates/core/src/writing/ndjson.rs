@@ -0,0 +1,132 @@
+use std::fmt::{self, Debug, Formatter};
+use std::io::Error as IoError;
+
+use bytes::Bytes;
+use futures_util::stream::{Stream, StreamExt};
+use serde::Serialize;
+
+use super::{Scribe, try_set_header};
+use crate::http::Response;
+use crate::http::header::{CONTENT_TYPE, HeaderValue};
+
+/// Write a stream of serializable items to response as newline-delimited JSON.
+///
+/// It will set `content-type` to `application/x-ndjson`.
+///
+/// Items are pulled from the stream only when the client is ready to receive more data, so slow clients
+/// apply backpressure to the producer. By default, every item is flushed as a separate chunk, use
+/// [`NdJson::batch_size`] to write the items which are ready at the same time in fewer chunks.
+///
+/// If an item fails to serialize, the error is logged and the response body is terminated.
+///
+/// # Example
+///
+/// ```
+/// use futures_util::{Stream, stream};
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::NdJson;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+/// }
+/// #[handler]
+/// async fn export() -> NdJson<impl Stream<Item = User>> {
+///     let users = (0..1000).map(|i| User { name: format!("user{i}") });
+///     NdJson::new(stream::iter(users)).batch_size(100)
+/// }
+/// ```
+pub struct NdJson<S> {
+    stream: S,
+    batch_size: usize,
+}
+impl<S> NdJson<S> {
+    /// Create a new `NdJson` from a stream of serializable items.
+    #[inline]
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            batch_size: 1,
+        }
+    }
+
+    /// Set the maximum number of items written in one chunk, defaults to `1`.
+    ///
+    /// A chunk is flushed as soon as the stream has no item ready, so a larger batch size never delays items.
+    #[inline]
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self
+    }
+}
+
+impl<S, T> Scribe for NdJson<S>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: Serialize + Send + 'static,
+{
+    fn render(self, res: &mut Response) {
+        try_set_header(
+            &mut res.headers,
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-ndjson"),
+        );
+        let stream = self.stream.ready_chunks(self.batch_size).map(|items| {
+            let mut buf = Vec::new();
+            for item in items {
+                serde_json::to_writer(&mut buf, &item).map_err(|e| {
+                    tracing::error!(error = ?e, "NdJson write error");
+                    IoError::other(e)
+                })?;
+                buf.push(b'\n');
+            }
+            Ok::<_, IoError>(Bytes::from(buf))
+        });
+        res.stream(stream);
+    }
+}
+impl<S> Debug for NdJson<S> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("NdJson")
+            .field("batch_size", &self.batch_size)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Serialize, Debug)]
+    struct User {
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_write_ndjson_content() {
+        #[handler]
+        async fn test() -> NdJson<impl Stream<Item = User>> {
+            let users = ["jobs", "gates"].map(|name| User { name: name.into() });
+            NdJson::new(stream::iter(users)).batch_size(8)
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/x-ndjson"
+        );
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            "{\"name\":\"jobs\"}\n{\"name\":\"gates\"}\n"
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::{self, Debug, Formatter};
+
+use serde::Serialize;
+
+use super::{Json, Text, Writer};
+#[cfg(feature = "protobuf")]
+use crate::http::header::CONTENT_TYPE;
+use crate::http::header::{HeaderValue, VARY};
+use crate::http::{Request, Response, StatusError};
+use crate::{Depot, async_trait};
+
+const APPLICATION_JSON: &str = "application/json";
+const TEXT_HTML: &str = "text/html";
+#[cfg(feature = "msgpack")]
+const APPLICATION_MSGPACK: &str = "application/msgpack";
+#[cfg(feature = "cbor")]
+const APPLICATION_CBOR: &str = "application/cbor";
+#[cfg(feature = "yaml")]
+const APPLICATION_YAML: &str = "application/yaml";
+#[cfg(feature = "protobuf")]
+const APPLICATION_PROTOBUF: &str = "application/x-protobuf";
+
+type HtmlRender<T> = Box<dyn FnOnce(&T) -> String + Send>;
+
+/// Write serializable content to response in the format chosen by the `Accept` header of the request.
+///
+/// JSON is always offered and it is used if the `Accept` header is absent, MessagePack, CBOR and YAML are
+/// offered if the `msgpack`, `cbor` and `yaml` features are enabled, HTML is offered only if a render
+/// function is set by [`Negotiate::html`], Protocol Buffers is offered only if [`Negotiate::protobuf`] is
+/// called. If none of the offered formats is acceptable, a
+/// `406 Not Acceptable` error is responded.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Negotiate;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String,
+/// }
+/// #[handler]
+/// async fn hello() -> Negotiate<User> {
+///     Negotiate::new(User { name: "jobs".into() })
+///         .html(|user| format!("<h1>Hello {}</h1>", user.name))
+/// }
+/// ```
+pub struct Negotiate<T> {
+    data: T,
+    html: Option<HtmlRender<T>>,
+    #[cfg(feature = "protobuf")]
+    protobuf: Option<fn(&T) -> Vec<u8>>,
+}
+impl<T> Negotiate<T> {
+    /// Create a new `Negotiate` with data.
+    #[inline]
+    pub fn new(data: T) -> Self {
+        Self {
+            data,
+            html: None,
+            #[cfg(feature = "protobuf")]
+            protobuf: None,
+        }
+    }
+
+    /// Set the function to render data as HTML, `text/html` is offered only if it is set.
+    #[inline]
+    pub fn html<F>(mut self, render: F) -> Self
+    where
+        F: FnOnce(&T) -> String + Send + 'static,
+    {
+        self.html = Some(Box::new(render));
+        self
+    }
+
+    fn offers(&self) -> Vec<&'static str> {
+        let mut offers = vec![APPLICATION_JSON];
+        #[cfg(feature = "msgpack")]
+        offers.push(APPLICATION_MSGPACK);
+        #[cfg(feature = "cbor")]
+        offers.push(APPLICATION_CBOR);
+        #[cfg(feature = "yaml")]
+        offers.push(APPLICATION_YAML);
+        if self.html.is_some() {
+            offers.push(TEXT_HTML);
+        }
+        #[cfg(feature = "protobuf")]
+        if self.protobuf.is_some() {
+            offers.push(APPLICATION_PROTOBUF);
+        }
+        offers
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl<T> Negotiate<T>
+where
+    T: prost::Message,
+{
+    /// Offer `application/x-protobuf`, the data is encoded as [`prost`] message if it is chosen,
+    /// so the JSON is served as fallback for the clients that do not accept Protocol Buffers.
+    #[inline]
+    pub fn protobuf(mut self) -> Self {
+        self.protobuf = Some(|data| data.encode_to_vec());
+        self
+    }
+}
+
+#[async_trait]
+impl<T> Writer for Negotiate<T>
+where
+    T: Serialize + Send,
+{
+    async fn write(self, req: &mut Request, _depot: &mut Depot, res: &mut Response) {
+        res.headers_mut()
+            .append(VARY, HeaderValue::from_static("accept"));
+        match req.accepts(&self.offers()) {
+            Some(APPLICATION_JSON) => res.render(Json(self.data)),
+            #[cfg(feature = "msgpack")]
+            Some(APPLICATION_MSGPACK) => res.render(super::MsgPack(self.data)),
+            #[cfg(feature = "cbor")]
+            Some(APPLICATION_CBOR) => res.render(super::Cbor(self.data)),
+            #[cfg(feature = "yaml")]
+            Some(APPLICATION_YAML) => res.render(super::Yaml(self.data)),
+            Some(TEXT_HTML) => {
+                if let Some(html) = self.html {
+                    res.render(Text::Html(html(&self.data)));
+                }
+            }
+            #[cfg(feature = "protobuf")]
+            Some(APPLICATION_PROTOBUF) => {
+                if let Some(encode) = self.protobuf {
+                    res.headers_mut()
+                        .insert(CONTENT_TYPE, HeaderValue::from_static(APPLICATION_PROTOBUF));
+                    let _ = res.write_body(encode(&self.data));
+                }
+            }
+            _ => res.render(StatusError::not_acceptable()),
+        }
+    }
+}
+
+impl<T: Debug> Debug for Negotiate<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Negotiate").field(&self.data).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Serialize, Debug)]
+    struct User {
+        name: String,
+    }
+
+    #[handler]
+    async fn user() -> Negotiate<User> {
+        Negotiate::new(User {
+            name: "jobs".into(),
+        })
+        .html(|data| format!("<h1>{}</h1>", data.name))
+    }
+
+    #[tokio::test]
+    async fn test_negotiate() {
+        let service = Service::new(Router::new().get(user));
+
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), r#"{"name":"jobs"}"#);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("accept", "application/json;q=0.5, text/*", true)
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(res.take_string().await.unwrap(), "<h1>jobs</h1>");
+
+        let res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("accept", "image/png", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code.unwrap(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[tokio::test]
+    async fn test_negotiate_protobuf() {
+        use prost::Message;
+
+        #[derive(Serialize, Clone, PartialEq, Message)]
+        struct Ping {
+            #[prost(uint32, tag = "1")]
+            seq: u32,
+        }
+        #[handler]
+        async fn ping() -> Negotiate<Ping> {
+            Negotiate::new(Ping { seq: 7 }).protobuf()
+        }
+        let service = Service::new(Router::new().get(ping));
+
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("accept", "application/x-protobuf", true)
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/x-protobuf"
+        );
+        let bytes = res.take_bytes(None).await.unwrap();
+        assert_eq!(Ping::decode(bytes).unwrap(), Ping { seq: 7 });
+
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("accept", "application/json", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), r#"{"seq":7}"#);
+    }
+}
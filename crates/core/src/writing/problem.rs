@@ -0,0 +1,204 @@
+use std::fmt::{self, Display, Formatter};
+
+use bytes::BufMut;
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+
+use super::Scribe;
+use crate::Error;
+use crate::http::header::{CONTENT_TYPE, HeaderValue};
+use crate::http::{Response, StatusCode, StatusError};
+
+/// Write [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457) (which obsoletes RFC 7807) problem details to
+/// response.
+///
+/// It will set the status code of response to [`Problem::status`] and `content-type` to
+/// `application/problem+json`.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Problem;
+///
+/// #[handler]
+/// async fn withdraw() -> Problem {
+///     Problem::new(StatusCode::FORBIDDEN)
+///         .r#type("https://example.com/probs/out-of-credit")
+///         .title("You do not have enough credit.")
+///         .detail("Your current balance is 30, but that costs 50.")
+///         .instance("/account/12345/msgs/abc")
+///         .extension("balance", 30)
+/// }
+/// ```
+#[derive(Serialize, Clone, Debug)]
+#[non_exhaustive]
+pub struct Problem {
+    /// A URI reference that identifies the problem type, defaults to `about:blank`.
+    pub r#type: String,
+    /// A short, human-readable summary of the problem type.
+    pub title: String,
+    /// The HTTP status code.
+    #[serde(serialize_with = "serialize_status")]
+    pub status: StatusCode,
+    /// A human-readable explanation specific to this occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// A URI reference that identifies the specific occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Extension members, they are serialized as the top-level members of the problem details object.
+    #[serde(flatten)]
+    pub extensions: Map<String, Value>,
+}
+
+fn serialize_status<S: Serializer>(status: &StatusCode, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u16(status.as_u16())
+}
+
+impl Problem {
+    /// Create a new `Problem` with `status`, the title is the canonical reason of `status`.
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            r#type: "about:blank".into(),
+            title: status.canonical_reason().unwrap_or_default().into(),
+            status,
+            detail: None,
+            instance: None,
+            extensions: Map::new(),
+        }
+    }
+
+    /// Sets type field and returns `Self`.
+    pub fn r#type(mut self, r#type: impl Into<String>) -> Self {
+        self.r#type = r#type.into();
+        self
+    }
+    /// Sets title field and returns `Self`.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+    /// Sets status field and returns `Self`.
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+    /// Sets detail field and returns `Self`.
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+    /// Sets instance field and returns `Self`.
+    pub fn instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+    /// Adds an extension member and returns `Self`.
+    ///
+    /// The value is ignored if it can not be serialized to json, extensions named as the standard members
+    /// are ignored too.
+    pub fn extension(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
+        let name = name.into();
+        if matches!(&*name, "type" | "title" | "status" | "detail" | "instance") {
+            tracing::warn!(name, "problem extension conflicts with standard member");
+            return self;
+        }
+        match serde_json::to_value(value) {
+            Ok(value) => {
+                self.extensions.insert(name, value);
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, name, "problem extension serialize error");
+            }
+        }
+        self
+    }
+}
+
+impl From<StatusError> for Problem {
+    fn from(e: StatusError) -> Self {
+        Self::new(e.code)
+            .title(e.name)
+            .detail(e.detail.unwrap_or(e.brief))
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.status.as_u16(), self.title)?;
+        if let Some(detail) = &self.detail {
+            write!(f, ": {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Scribe for Problem {
+    fn render(self, res: &mut Response) {
+        match res.write_body_with(|buf| serde_json::to_writer(buf.writer(), &self)) {
+            Ok(()) => {
+                res.status_code(self.status);
+                res.headers.insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/problem+json"),
+                );
+            }
+            Err(Error::SerdeJson(e)) => {
+                tracing::error!(error = ?e, "Problem write error");
+                res.render(StatusError::internal_server_error());
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[tokio::test]
+    async fn test_write_problem() {
+        #[handler]
+        async fn test() -> Problem {
+            Problem::new(StatusCode::FORBIDDEN)
+                .r#type("https://example.com/probs/out-of-credit")
+                .detail("Your current balance is 30, but that costs 50.")
+                .extension("balance", 30)
+                .extension("status", 200)
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/problem+json"
+        );
+        let problem: Value = serde_json::from_slice(&res.take_bytes(None).await.unwrap()).unwrap();
+        assert_eq!(
+            problem,
+            serde_json::json!({
+                "type": "https://example.com/probs/out-of-credit",
+                "title": "Forbidden",
+                "status": 403,
+                "detail": "Your current balance is 30, but that costs 50.",
+                "balance": 30,
+            })
+        );
+    }
+
+    #[test]
+    fn test_problem_from_status_error() {
+        let problem = Problem::from(StatusError::not_found().brief("user not found"));
+        assert_eq!(problem.status, StatusCode::NOT_FOUND);
+        assert_eq!(problem.title, "Not Found");
+        assert_eq!(problem.detail.as_deref(), Some("user not found"));
+        assert_eq!(problem.to_string(), "404 Not Found: user not found");
+    }
+}
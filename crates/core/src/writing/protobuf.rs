@@ -0,0 +1,103 @@
+use std::fmt::{self, Debug, Formatter};
+
+use prost::Message;
+
+use super::{Scribe, try_set_header};
+use crate::http::Response;
+use crate::http::header::{CONTENT_TYPE, HeaderValue};
+
+/// Write [`prost`] message to response as Protocol Buffers content.
+///
+/// It will set `content-type` to `application/x-protobuf`.
+///
+/// If the message should also be served as JSON to the clients that do not accept Protocol Buffers, use
+/// [`Negotiate::protobuf`](super::Negotiate::protobuf) instead.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::ProtoBuf;
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct User {
+///     #[prost(string, tag = "1")]
+///     name: String,
+/// }
+/// #[handler]
+/// async fn hello(res: &mut Response) -> ProtoBuf<User> {
+///     ProtoBuf(User { name: "jobs".into() })
+/// }
+/// ```
+pub struct ProtoBuf<T>(pub T);
+
+impl<T> Scribe for ProtoBuf<T>
+where
+    T: Message,
+{
+    fn render(self, res: &mut Response) {
+        try_set_header(
+            &mut res.headers,
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-protobuf"),
+        );
+        let _ = res.write_body(self.0.encode_to_vec());
+    }
+}
+impl<T: Debug> Debug for ProtoBuf<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ProtoBuf").field(&self.0).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Clone, PartialEq, Message)]
+    struct User {
+        #[prost(string, tag = "1")]
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_write_protobuf_content() {
+        #[handler]
+        async fn test() -> ProtoBuf<User> {
+            ProtoBuf(User {
+                name: "jobs".into(),
+            })
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/x-protobuf"
+        );
+        let bytes = res.take_bytes(None).await.unwrap();
+        assert_eq!(
+            User::decode(bytes).unwrap(),
+            User {
+                name: "jobs".into()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_protobuf() {
+        let user = User {
+            name: "jobs".into(),
+        };
+        let mut req = TestClient::post("http://127.0.0.1:5800/test")
+            .add_header("content-type", "application/x-protobuf", true)
+            .bytes(user.encode_to_vec())
+            .build();
+        assert_eq!(req.parse_protobuf::<User>().await.unwrap(), user);
+    }
+}
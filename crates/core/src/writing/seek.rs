@@ -6,8 +6,8 @@ use headers::*;
 use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt};
 use tokio_util::io::ReaderStream;
 
-use crate::http::header::{IF_NONE_MATCH, RANGE};
-use crate::http::{HttpRange, Request, Response, StatusCode, StatusError};
+use crate::http::header::RANGE;
+use crate::http::{HttpRange, Precondition, Request, Response, StatusCode, StatusError};
 use crate::{Depot, Writer, async_trait};
 
 /// `ReadSeeker` is used to write data to [`Response`] from a reader which implements [`AsyncRead`] and [`AsyncSeek`].
@@ -61,31 +61,8 @@ where
 
     ///Consume self and send content to [`Response`].
     pub async fn send(mut self, req_headers: &HeaderMap, res: &mut Response) {
-        // check preconditions
-        let precondition_failed = if !any_match(self.etag.as_ref(), req_headers) {
-            true
-        } else if let (Some(last_modified), Some(since)) = (
-            &self.last_modified,
-            req_headers.typed_get::<IfUnmodifiedSince>(),
-        ) {
-            !since.precondition_passes(*last_modified)
-        } else {
-            false
-        };
-
-        // check last modified
-        let not_modified = if !none_match(self.etag.as_ref(), req_headers) {
-            true
-        } else if req_headers.contains_key(IF_NONE_MATCH) {
-            false
-        } else if let (Some(last_modified), Some(since)) = (
-            &self.last_modified,
-            req_headers.typed_get::<IfModifiedSince>(),
-        ) {
-            !since.is_modified(*last_modified)
-        } else {
-            false
-        };
+        let precondition =
+            Precondition::evaluate(req_headers, self.etag.as_ref(), self.last_modified);
 
         if let Some(lm) = self.last_modified {
             res.headers_mut().typed_insert(LastModified::from(lm));
@@ -116,12 +93,16 @@ where
             };
         }
 
-        if precondition_failed {
-            res.status_code(StatusCode::PRECONDITION_FAILED);
-            return;
-        } else if not_modified {
-            res.status_code(StatusCode::NOT_MODIFIED);
-            return;
+        match precondition {
+            Precondition::Failed => {
+                res.status_code(StatusCode::PRECONDITION_FAILED);
+                return;
+            }
+            Precondition::NotModified => {
+                res.status_code(StatusCode::NOT_MODIFIED);
+                return;
+            }
+            Precondition::Passed => {}
         }
 
         if offset != 0 || length != self.length || range.is_some() {
@@ -160,35 +141,3 @@ where
         self.send(req.headers(), res).await;
     }
 }
-
-/// Returns true if `req_headers` has no `If-Match` header or one which matches `etag`.
-fn any_match(etag: Option<&ETag>, req_headers: &HeaderMap) -> bool {
-    match req_headers.typed_get::<IfMatch>() {
-        None => true,
-        Some(if_match) => {
-            if if_match == IfMatch::any() {
-                true
-            } else if let Some(etag) = etag {
-                if_match.precondition_passes(etag)
-            } else {
-                false
-            }
-        }
-    }
-}
-
-/// Returns true if `req_headers` doesn't have an `If-None-Match` header matching `req`.
-fn none_match(etag: Option<&ETag>, req_headers: &HeaderMap) -> bool {
-    match req_headers.typed_get::<IfNoneMatch>() {
-        None => true,
-        Some(if_none_match) => {
-            if if_none_match == IfNoneMatch::any() {
-                false
-            } else if let Some(etag) = etag {
-                if_none_match.precondition_passes(etag)
-            } else {
-                true
-            }
-        }
-    }
-}
@@ -24,6 +24,8 @@ pub enum Text<C> {
     Json(C),
     /// It will set `content-type` to `application/xml; charset=utf-8`.
     Xml(C),
+    /// It will set `content-type` to `application/yaml; charset=utf-8`.
+    Yaml(C),
     /// It will set `content-type` to `text/html; charset=utf-8`.
     Html(C),
     /// It will set `content-type` to `text/javascript; charset=utf-8`.
@@ -58,6 +60,10 @@ where
                 HeaderValue::from_static("application/xml; charset=utf-8"),
                 content,
             ),
+            Self::Yaml(content) => (
+                HeaderValue::from_static("application/yaml; charset=utf-8"),
+                content,
+            ),
             Self::Html(content) => (
                 HeaderValue::from_static("text/html; charset=utf-8"),
                 content,
@@ -112,6 +118,7 @@ impl<C: Debug> Debug for Text<C> {
             Text::Plain(content) => f.debug_tuple("Text::Plain").field(content).finish(),
             Text::Json(content) => f.debug_tuple("Text::Json").field(content).finish(),
             Text::Xml(content) => f.debug_tuple("Text::Xml").field(content).finish(),
+            Text::Yaml(content) => f.debug_tuple("Text::Yaml").field(content).finish(),
             Text::Html(content) => f.debug_tuple("Text::Html").field(content).finish(),
             Text::Js(content) => f.debug_tuple("Text::Js").field(content).finish(),
             Text::Css(content) => f.debug_tuple("Text::Css").field(content).finish(),
@@ -128,6 +135,7 @@ impl<C: Display> Display for Text<C> {
             Text::Plain(content) => Display::fmt(content, f),
             Text::Json(content) => Display::fmt(content, f),
             Text::Xml(content) => Display::fmt(content, f),
+            Text::Yaml(content) => Display::fmt(content, f),
             Text::Html(content) => Display::fmt(content, f),
             Text::Js(content) => Display::fmt(content, f),
             Text::Css(content) => Display::fmt(content, f),
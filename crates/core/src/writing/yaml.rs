@@ -0,0 +1,114 @@
+use std::fmt::{self, Debug, Display, Formatter};
+
+use serde::Serialize;
+
+use super::{Scribe, try_set_header};
+use crate::http::header::{CONTENT_TYPE, HeaderValue};
+use crate::http::{Response, StatusError};
+
+/// Write serializable content to response as YAML content.
+///
+/// It will set `content-type` to `application/yaml; charset=utf-8`.
+///
+/// # Example
+///
+/// ```
+/// use salvo_core::prelude::*;
+/// use salvo_core::writing::Yaml;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///    name: String,
+/// }
+/// #[handler]
+/// async fn hello(res: &mut Response) -> Yaml<User> {
+///     Yaml(User { name: "jobs".into() })
+/// }
+/// ```
+pub struct Yaml<T>(pub T);
+
+impl<T> Scribe for Yaml<T>
+where
+    T: Serialize + Send,
+{
+    fn render(self, res: &mut Response) {
+        match serde_norway::to_string(&self.0) {
+            Ok(bytes) => {
+                try_set_header(
+                    &mut res.headers,
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/yaml; charset=utf-8"),
+                );
+                let _ = res.write_body(bytes);
+            }
+            Err(e) => {
+                tracing::error!(error = ?e, "YamlContent write error");
+                res.render(StatusError::internal_server_error());
+            }
+        }
+    }
+}
+impl<T: Debug> Debug for Yaml<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Yaml").field(&self.0).finish()
+    }
+}
+impl<T: Display> Display for Yaml<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::prelude::*;
+
+    use super::*;
+    use crate::test::{ResponseExt, TestClient};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct User {
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_write_yaml_content() {
+        #[handler]
+        async fn test() -> Yaml<User> {
+            Yaml(User {
+                name: "jobs".into(),
+            })
+        }
+
+        let router = Router::new().push(Router::with_path("test").get(test));
+        let mut res = TestClient::get("http://127.0.0.1:5800/test")
+            .send(router)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/yaml; charset=utf-8"
+        );
+        let bytes = res.take_bytes(None).await.unwrap();
+        assert_eq!(
+            serde_norway::from_slice::<User>(&bytes).unwrap(),
+            User {
+                name: "jobs".into()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_yaml() {
+        let user = User {
+            name: "jobs".into(),
+        };
+        let mut req = TestClient::post("http://127.0.0.1:5800/test")
+            .add_header("content-type", "application/yaml; charset=utf-8", true)
+            .bytes(serde_norway::to_string(&user).unwrap().into_bytes())
+            .build();
+        assert_eq!(req.parse_yaml::<User>().await.unwrap(), user);
+    }
+}
@@ -0,0 +1,654 @@
+//! Structured access logging middleware.
+//!
+//! [`AccessLog`] writes one line for every request in the [Common Log Format], the
+//! [Combined Log Format] or as a JSON object. The lines are written to an [`AccessLogSink`],
+//! which can be [`tracing`](TracingSink) (default), [`stdout`](StdoutSink), a
+//! [rotated file](FileSink) or any closure.
+//!
+//! [Common Log Format]: https://httpd.apache.org/docs/current/logs.html#common
+//! [Combined Log Format]: https://httpd.apache.org/docs/current/logs.html#combined
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::access_log::{AccessLog, FileSink, LogField, LogFormat};
+//!
+//! #[handler]
+//! async fn hello() -> &'static str {
+//!     "Hello World"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let access_log = AccessLog::new()
+//!         .format(LogFormat::Json)
+//!         .fields([LogField::Time, LogField::Method, LogField::Path, LogField::Status])
+//!         .sample_rate(0.1)
+//!         .sink(FileSink::new("access.log").unwrap().max_size(10 * 1024 * 1024));
+//!     let router = Router::new().get(hello);
+//!     let service = Service::new(router).hoop(access_log);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(service).await;
+//! }
+//! ```
+use std::ffi::OsString;
+use std::fmt::{self, Debug, Formatter, Write as _};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use salvo_core::http::header::{CONTENT_LENGTH, REFERER, USER_AGENT};
+use salvo_core::http::{Method, Request, ResBody, Response, StatusCode, Version};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+use serde_json::Value;
+use time::OffsetDateTime;
+
+/// The format of the access log lines.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[non_exhaustive]
+pub enum LogFormat {
+    /// Common Log Format: `127.0.0.1 - - [10/Oct/2000:13:55:36 +0000] "GET / HTTP/1.1" 200 2326`.
+    Common,
+    /// Combined Log Format, the Common Log Format with the referer and the user agent.
+    #[default]
+    Combined,
+    /// JSON lines with the fields selected by [`AccessLog::fields`].
+    Json,
+}
+
+/// The fields written by [`LogFormat::Json`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum LogField {
+    /// The time the request received, in RFC 3339 format.
+    Time,
+    /// The IP address of the client.
+    RemoteAddr,
+    /// The request method.
+    Method,
+    /// The request path with query.
+    Path,
+    /// The HTTP version.
+    Version,
+    /// The response status code.
+    Status,
+    /// The size of the response body, it is `null` if the size is unknown, e.g. streaming body.
+    Bytes,
+    /// The time elapsed handling the request, in milliseconds.
+    Latency,
+    /// The matched route pattern, it requires the `matched-path` feature.
+    Route,
+    /// The request id, from the [`RequestId`](crate::request_id::RequestId) middleware or the
+    /// `x-request-id` header.
+    RequestId,
+    /// The `user-agent` header.
+    UserAgent,
+    /// The `referer` header.
+    Referer,
+}
+impl LogField {
+    /// All the fields, they are written by default.
+    pub const ALL: [LogField; 12] = [
+        LogField::Time,
+        LogField::RemoteAddr,
+        LogField::Method,
+        LogField::Path,
+        LogField::Version,
+        LogField::Status,
+        LogField::Bytes,
+        LogField::Latency,
+        LogField::Route,
+        LogField::RequestId,
+        LogField::UserAgent,
+        LogField::Referer,
+    ];
+
+    /// The key of the field in JSON lines.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogField::Time => "time",
+            LogField::RemoteAddr => "remote_addr",
+            LogField::Method => "method",
+            LogField::Path => "path",
+            LogField::Version => "version",
+            LogField::Status => "status",
+            LogField::Bytes => "bytes",
+            LogField::Latency => "latency_ms",
+            LogField::Route => "route",
+            LogField::RequestId => "request_id",
+            LogField::UserAgent => "user_agent",
+            LogField::Referer => "referer",
+        }
+    }
+}
+
+/// The destination of the access log lines.
+pub trait AccessLogSink: Send + Sync + 'static {
+    /// Write a line, the line does not end with a line break.
+    fn write(&self, line: &str);
+}
+impl<F> AccessLogSink for F
+where
+    F: Fn(&str) + Send + Sync + 'static,
+{
+    #[inline]
+    fn write(&self, line: &str) {
+        self(line)
+    }
+}
+
+/// Writes the access log lines as `INFO` events with target `salvo::access_log`.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct TracingSink;
+impl AccessLogSink for TracingSink {
+    #[inline]
+    fn write(&self, line: &str) {
+        tracing::info!(target: "salvo::access_log", "{line}");
+    }
+}
+
+/// Writes the access log lines to stdout.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct StdoutSink;
+impl AccessLogSink for StdoutSink {
+    fn write(&self, line: &str) {
+        let mut stdout = io::stdout().lock();
+        if let Err(e) = writeln!(stdout, "{line}") {
+            tracing::error!(error = ?e, "write access log to stdout failed");
+        }
+    }
+}
+
+/// Writes the access log lines to a file, the file is rotated when it reaches
+/// [`max_size`](FileSink::max_size).
+///
+/// When rotated, `access.log` is renamed to `access.log.1`, `access.log.1` to `access.log.2` and
+/// so on, the files older than [`max_files`](FileSink::max_files) are removed.
+///
+/// The lines are written with blocking IO.
+pub struct FileSink {
+    path: PathBuf,
+    max_size: Option<u64>,
+    max_files: usize,
+    state: Mutex<FileState>,
+}
+struct FileState {
+    file: File,
+    size: u64,
+}
+impl FileSink {
+    /// Create new `FileSink` which appends to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_size: None,
+            max_files: 5,
+            state: Mutex::new(FileState { file, size }),
+        })
+    }
+
+    /// Rotate the file when its size would exceed `max_size` bytes. Default is no rotation.
+    #[inline]
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// The number of rotated files to keep. Default is 5.
+    #[inline]
+    pub fn max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = OsString::from(self.path.as_os_str());
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+        state.file.flush()?;
+        if self.max_files == 0 {
+            std::fs::remove_file(&self.path)?;
+        } else {
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    std::fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        state.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        state.size = 0;
+        Ok(())
+    }
+
+    fn write_line(&self, line: &str) -> io::Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let len = line.len() as u64 + 1;
+        if let Some(max_size) = self.max_size {
+            if state.size > 0 && state.size + len > max_size {
+                self.rotate(&mut state)?;
+            }
+        }
+        writeln!(state.file, "{line}")?;
+        state.size += len;
+        Ok(())
+    }
+
+    /// Returns the path of the file.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+impl AccessLogSink for FileSink {
+    fn write(&self, line: &str) {
+        if let Err(e) = self.write_line(line) {
+            tracing::error!(error = ?e, path = ?self.path, "write access log to file failed");
+        }
+    }
+}
+impl Debug for FileSink {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("FileSink")
+            .field("path", &self.path)
+            .field("max_size", &self.max_size)
+            .field("max_files", &self.max_files)
+            .finish()
+    }
+}
+
+struct Record {
+    time: OffsetDateTime,
+    remote_addr: Option<String>,
+    method: Method,
+    path: String,
+    version: Version,
+    status: StatusCode,
+    bytes: Option<u64>,
+    latency: Duration,
+    route: Option<String>,
+    request_id: Option<String>,
+    user_agent: Option<String>,
+    referer: Option<String>,
+}
+impl Record {
+    fn clf(&self, out: &mut String, combined: bool) {
+        let _ = write!(
+            out,
+            "{} - - [{:02}/{}/{}:{:02}:{:02}:{:02} +0000] \"{} {} {:?}\" {} ",
+            self.remote_addr.as_deref().unwrap_or("-"),
+            self.time.day(),
+            MONTHS[u8::from(self.time.month()) as usize - 1],
+            self.time.year(),
+            self.time.hour(),
+            self.time.minute(),
+            self.time.second(),
+            self.method,
+            escape_clf(&self.path),
+            self.version,
+            self.status.as_u16(),
+        );
+        match self.bytes {
+            Some(bytes) if bytes > 0 => {
+                let _ = write!(out, "{bytes}");
+            }
+            _ => out.push('-'),
+        }
+        if combined {
+            let _ = write!(
+                out,
+                " \"{}\" \"{}\"",
+                escape_clf(self.referer.as_deref().unwrap_or("-")),
+                escape_clf(self.user_agent.as_deref().unwrap_or("-")),
+            );
+        }
+    }
+
+    fn json(&self, fields: &[LogField]) -> String {
+        // Written by hand instead of a `serde_json::Map`, which sorts the keys, to keep the
+        // configured order of the fields.
+        let mut line = String::from("{");
+        for (i, field) in fields.iter().enumerate() {
+            let value = match field {
+                LogField::Time => Value::from(format!(
+                    "{}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+                    self.time.year(),
+                    u8::from(self.time.month()),
+                    self.time.day(),
+                    self.time.hour(),
+                    self.time.minute(),
+                    self.time.second(),
+                    self.time.millisecond(),
+                )),
+                LogField::RemoteAddr => Value::from(self.remote_addr.clone()),
+                LogField::Method => Value::from(self.method.as_str()),
+                LogField::Path => Value::from(self.path.as_str()),
+                LogField::Version => Value::from(format!("{:?}", self.version)),
+                LogField::Status => Value::from(self.status.as_u16()),
+                LogField::Bytes => Value::from(self.bytes),
+                LogField::Latency => Value::from(self.latency.as_secs_f64() * 1000.0),
+                LogField::Route => Value::from(self.route.clone()),
+                LogField::RequestId => Value::from(self.request_id.clone()),
+                LogField::UserAgent => Value::from(self.user_agent.clone()),
+                LogField::Referer => Value::from(self.referer.clone()),
+            };
+            if i > 0 {
+                line.push(',');
+            }
+            let _ = write!(line, "\"{}\":{value}", field.name());
+        }
+        line.push('}');
+        line
+    }
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn escape_clf(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Structured access logging middleware.
+///
+/// View [module level documentation](index.html) for more details.
+pub struct AccessLog {
+    format: LogFormat,
+    fields: Vec<LogField>,
+    sample_rate: f64,
+    always_log_errors: bool,
+    sink: Arc<dyn AccessLogSink>,
+    counter: AtomicU64,
+}
+impl Default for AccessLog {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl AccessLog {
+    /// Create new `AccessLog` middleware which writes all requests in [`LogFormat::Combined`]
+    /// to [`TracingSink`].
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            format: LogFormat::default(),
+            fields: LogField::ALL.to_vec(),
+            sample_rate: 1.0,
+            always_log_errors: true,
+            sink: Arc::new(TracingSink),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Set the format of the lines.
+    #[inline]
+    pub fn format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the fields written by [`LogFormat::Json`], in the given order.
+    #[inline]
+    pub fn fields(mut self, fields: impl IntoIterator<Item = LogField>) -> Self {
+        self.fields = fields.into_iter().collect();
+        self
+    }
+
+    /// Only log the given fraction of the requests, between `0.0` and `1.0`. Default is `1.0`.
+    ///
+    /// The sampling is deterministic, e.g. `0.25` logs every fourth request.
+    #[inline]
+    pub fn sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Always log the requests whose response status is a client or server error, regardless
+    /// of [`sample_rate`](Self::sample_rate). Default is `true`.
+    #[inline]
+    pub fn always_log_errors(mut self, always: bool) -> Self {
+        self.always_log_errors = always;
+        self
+    }
+
+    /// Set the sink which the lines are written to.
+    #[inline]
+    pub fn sink(mut self, sink: impl AccessLogSink) -> Self {
+        self.sink = Arc::new(sink);
+        self
+    }
+
+    fn sampled(&self) -> bool {
+        if self.sample_rate >= 1.0 {
+            return true;
+        }
+        let n = self.counter.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * self.sample_rate).floor() > (n * self.sample_rate).floor()
+    }
+
+    fn format_record(&self, record: &Record) -> String {
+        match self.format {
+            LogFormat::Common | LogFormat::Combined => {
+                let mut line = String::with_capacity(128);
+                record.clf(&mut line, self.format == LogFormat::Combined);
+                line
+            }
+            LogFormat::Json => record.json(&self.fields),
+        }
+    }
+}
+impl Debug for AccessLog {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("AccessLog")
+            .field("format", &self.format)
+            .field("fields", &self.fields)
+            .field("sample_rate", &self.sample_rate)
+            .field("always_log_errors", &self.always_log_errors)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Handler for AccessLog {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let time = OffsetDateTime::now_utc();
+        let started = Instant::now();
+        ctrl.call_next(req, depot, res).await;
+        let latency = started.elapsed();
+
+        let status = res.status_code.unwrap_or(match &res.body {
+            ResBody::None => StatusCode::NOT_FOUND,
+            ResBody::Error(e) => e.code,
+            _ => StatusCode::OK,
+        });
+        let forced = self.always_log_errors && (status.is_client_error() || status.is_server_error());
+        if !forced && !self.sampled() {
+            return;
+        }
+
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(ToOwned::to_owned)
+        };
+        #[cfg(feature = "request-id")]
+        let request_id = crate::request_id::RequestIdDepotExt::request_id(depot)
+            .map(ToOwned::to_owned)
+            .or_else(|| header("x-request-id"));
+        #[cfg(not(feature = "request-id"))]
+        let request_id = header("x-request-id");
+        #[cfg(feature = "matched-path")]
+        let route = req.matched_route();
+        #[cfg(not(feature = "matched-path"))]
+        let route = None;
+        let record = Record {
+            time,
+            remote_addr: req.client_ip().map(|ip| ip.to_string()),
+            method: req.method().clone(),
+            path: req
+                .uri()
+                .path_and_query()
+                .map(|p| p.as_str())
+                .unwrap_or("/")
+                .to_owned(),
+            version: req.version(),
+            status,
+            bytes: res.body.size().or_else(|| {
+                res.headers()
+                    .get(CONTENT_LENGTH)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok())
+            }),
+            latency,
+            route,
+            request_id,
+            user_agent: header(USER_AGENT.as_str()),
+            referer: header(REFERER.as_str()),
+        };
+        self.sink.write(&self.format_record(&record));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn hello() -> &'static str {
+        "hello"
+    }
+    #[handler]
+    async fn fail(res: &mut Response) {
+        res.status_code(StatusCode::BAD_REQUEST);
+    }
+
+    fn collect() -> (Arc<Mutex<Vec<String>>>, impl AccessLogSink) {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let lines = lines.clone();
+            move |line: &str| lines.lock().unwrap().push(line.to_owned())
+        };
+        (lines, sink)
+    }
+
+    #[tokio::test]
+    async fn test_access_log_combined() {
+        let (lines, sink) = collect();
+        let router = Router::new()
+            .hoop(AccessLog::new().sink(sink))
+            .push(Router::with_path("hello").get(hello));
+
+        TestClient::get("http://127.0.0.1:5801/hello?name=world")
+            .add_header("user-agent", "test \"agent\"", true)
+            .send(router)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        let lines = lines.lock().unwrap();
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].starts_with("- - - ["), "{}", lines[0]);
+        assert!(
+            lines[0].ends_with(
+                "] \"GET /hello?name=world HTTP/1.1\" 200 5 \"-\" \"test \\\"agent\\\"\""
+            ),
+            "{}",
+            lines[0]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_access_log_json_fields() {
+        let (lines, sink) = collect();
+        let router = Router::new()
+            .hoop(
+                AccessLog::new()
+                    .format(LogFormat::Json)
+                    .fields([LogField::Method, LogField::Status, LogField::RequestId])
+                    .sink(sink),
+            )
+            .push(Router::with_path("hello").get(hello));
+
+        TestClient::get("http://127.0.0.1:5801/hello")
+            .add_header("x-request-id", "abc123", true)
+            .send(router)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(
+            lines.lock().unwrap()[0],
+            r#"{"method":"GET","status":200,"request_id":"abc123"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_access_log_sampling() {
+        let (lines, sink) = collect();
+        let router = Arc::new(
+            Router::new()
+                .hoop(AccessLog::new().sample_rate(0.5).sink(sink))
+                .push(Router::with_path("hello").get(hello))
+                .push(Router::with_path("fail").get(fail)),
+        );
+
+        for _ in 0..4 {
+            TestClient::get("http://127.0.0.1:5801/hello")
+                .send(router.clone())
+                .await;
+        }
+        assert_eq!(lines.lock().unwrap().len(), 2);
+        for _ in 0..3 {
+            TestClient::get("http://127.0.0.1:5801/fail")
+                .send(router.clone())
+                .await;
+        }
+        assert_eq!(lines.lock().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_file_sink_rotation() {
+        let dir = std::env::temp_dir().join(format!("salvo-access-log-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let sink = FileSink::new(dir.join("access.log"))
+            .unwrap()
+            .max_size(8)
+            .max_files(2);
+        for line in ["line1", "line2", "line3", "line4"] {
+            sink.write(line);
+        }
+        let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("access.log"), "line4\n");
+        assert_eq!(read("access.log.1"), "line3\n");
+        assert_eq!(read("access.log.2"), "line2\n");
+        assert!(!dir.join("access.log.3").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -3,6 +3,13 @@
 //! This middleware implements the standard HTTP Basic Authentication scheme as described in RFC 7617.
 //! It extracts credentials from the Authorization header and validates them against your custom validator.
 //!
+//! The validator is async, so the credentials can be checked against a database. The users of an
+//! htpasswd file can also be validated by [`Htpasswd`], which supports the bcrypt hashes when the
+//! `basic-auth-bcrypt` feature is enabled.
+//!
+//! Once authenticated, the [`BasicAuthPrincipal`] is stored in the [`Depot`], see
+//! [`BasicAuthDepotExt`].
+//!
 //! # Example
 //!
 //! ```no_run
@@ -23,14 +30,18 @@
 //!
 //! #[tokio::main]
 //! async fn main() {
-//!     let auth_handler = BasicAuth::new(Validator);
+//!     let auth_handler = BasicAuth::new(Validator).realm("admin");
 //!     let router = Router::with_hoop(auth_handler).goal(hello);
 //!
 //!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
 //!     Server::new(acceptor).serve(router).await;
 //! }
 //! ```
+use std::collections::HashMap;
+use std::path::Path;
+
 use base64::engine::{general_purpose, Engine};
+use salvo_core::handler::AuthScheme;
 use salvo_core::http::header::{HeaderName, AUTHORIZATION, PROXY_AUTHORIZATION};
 use salvo_core::http::{Request, Response, StatusCode};
 use salvo_core::{async_trait, Depot, Error, FlowCtrl, Handler};
@@ -38,6 +49,26 @@ use salvo_core::{async_trait, Depot, Error, FlowCtrl, Handler};
 /// key used when insert into depot.
 pub const USERNAME_KEY: &str = "::salvo::basic_auth::username";
 
+/// The authenticated principal, stored in the [`Depot`] after a successful authentication.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BasicAuthPrincipal {
+    /// The authenticated username.
+    pub username: String,
+    /// The realm of the [`BasicAuth`] which authenticated the user.
+    pub realm: String,
+}
+
+/// Compares two byte slices in constant time relative to their content, to not leak the
+/// position of the first difference through timing. The length is not hidden.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    std::hint::black_box(diff) == 0
+}
+
 /// Validator for Basic Authentication credentials.
 pub trait BasicAuthValidator: Send + Sync {
     /// Validates whether the provided username and password are correct.
@@ -51,12 +82,100 @@ pub trait BasicAuthValidator: Send + Sync {
 pub trait BasicAuthDepotExt {
     /// Returns the authenticated username if authentication was successful.
     fn basic_auth_username(&self) -> Option<&str>;
+    /// Returns the authenticated principal if authentication was successful.
+    fn basic_auth_principal(&self) -> Option<&BasicAuthPrincipal>;
 }
 
 impl BasicAuthDepotExt for Depot {
     fn basic_auth_username(&self) -> Option<&str> {
         self.get::<String>(USERNAME_KEY).map(|v|&**v).ok()
     }
+    fn basic_auth_principal(&self) -> Option<&BasicAuthPrincipal> {
+        self.obtain::<BasicAuthPrincipal>().ok()
+    }
+}
+
+/// Validator of the users of an htpasswd file, with lines `username:hash`.
+///
+/// The hashes can be bcrypt (`$2y$`, `$2b$` or `$2a$`, requires the `basic-auth-bcrypt` feature)
+/// or plain text, which is compared in constant time. The other formats, such as `$apr1$` and
+/// `{SHA}`, are not supported and their users are rejected.
+#[derive(Clone, Default)]
+pub struct Htpasswd {
+    users: HashMap<String, String>,
+}
+impl Htpasswd {
+    /// Parse the content of an htpasswd file, the empty lines and comments are ignored.
+    pub fn parse(content: &str) -> Self {
+        let users = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once(':'))
+            .map(|(username, hash)| (username.to_owned(), hash.to_owned()))
+            .collect();
+        Self { users }
+    }
+
+    /// Load an htpasswd file.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::read_to_string(path).map(|content| Self::parse(&content))
+    }
+
+    /// Add a user with its hash, replacing the previous one.
+    pub fn user(mut self, username: impl Into<String>, hash: impl Into<String>) -> Self {
+        self.users.insert(username.into(), hash.into());
+        self
+    }
+
+    /// Returns `true` if the user exists in the file.
+    pub fn contains(&self, username: &str) -> bool {
+        self.users.contains_key(username)
+    }
+
+    /// Verify the password of the user.
+    pub async fn verify(&self, username: &str, password: &str) -> bool {
+        let Some(hash) = self.users.get(username) else {
+            return false;
+        };
+        if hash.starts_with("$2y$") || hash.starts_with("$2b$") || hash.starts_with("$2a$") {
+            #[cfg(feature = "basic-auth-bcrypt")]
+            {
+                // bcrypt is slow by design, so it must not block the runtime.
+                let (hash, password) = (hash.clone(), password.to_owned());
+                return tokio::task::spawn_blocking(move || {
+                    bcrypt::verify(password, &hash).unwrap_or(false)
+                })
+                .await
+                .unwrap_or(false);
+            }
+            #[cfg(not(feature = "basic-auth-bcrypt"))]
+            {
+                tracing::warn!(
+                    username,
+                    "bcrypt hash requires the `basic-auth-bcrypt` feature"
+                );
+                return false;
+            }
+        }
+        if hash.starts_with('$') || hash.starts_with("{SHA}") {
+            tracing::warn!(username, "unsupported htpasswd hash format");
+            return false;
+        }
+        constant_time_eq(hash.as_bytes(), password.as_bytes())
+    }
+}
+impl std::fmt::Debug for Htpasswd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Htpasswd")
+            .field("users", &self.users.keys())
+            .finish()
+    }
+}
+impl BasicAuthValidator for Htpasswd {
+    async fn validate(&self, username: &str, password: &str, _depot: &mut Depot) -> bool {
+        self.verify(username, password).await
+    }
 }
 
 /// BasicAuth
@@ -80,6 +199,13 @@ where
         }
     }
 
+    /// Set the realm sent in the `WWW-Authenticate` header, default is `realm`.
+    #[inline]
+    pub fn realm(mut self, realm: impl Into<String>) -> Self {
+        self.realm = realm.into();
+        self
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn set_header_names(mut self, header_names: impl Into<Vec<HeaderName>>) -> Self {
@@ -154,9 +280,17 @@ impl<V> Handler for BasicAuth<V>
 where
     V: BasicAuthValidator + 'static,
 {
+    #[inline]
+    fn auth_scheme(&self) -> Option<AuthScheme> {
+        Some(AuthScheme::Basic)
+    }
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
         if let Ok((username, password)) = self.parse_credentials(req) {
             if self.validator.validate(&username, &password, depot).await {
+                depot.inject(BasicAuthPrincipal {
+                    username: username.clone(),
+                    realm: self.realm.clone(),
+                });
                 depot.insert(USERNAME_KEY, username);
                 ctrl.call_next(req, depot, res).await;
                 return;
@@ -210,4 +344,46 @@ mod tests {
             .unwrap();
         assert!(content.contains("Unauthorized"));
     }
+
+    #[handler]
+    async fn principal(depot: &mut Depot) -> String {
+        let current = depot.basic_auth_principal().unwrap();
+        format!("{}@{}", current.username, current.realm)
+    }
+
+    #[tokio::test]
+    async fn test_htpasswd() {
+        let htpasswd = Htpasswd::parse("# users\nroot:pwd\n\nadmin:$apr1$salt$hash\n");
+        assert!(htpasswd.contains("admin"));
+        assert!(htpasswd.verify("root", "pwd").await);
+        assert!(!htpasswd.verify("root", "pwd2").await);
+        assert!(!htpasswd.verify("admin", "pwd").await);
+        assert!(!htpasswd.verify("nobody", "pwd").await);
+
+        let router = Router::with_hoop(BasicAuth::new(htpasswd).realm("admin")).goal(principal);
+        let service = Service::new(router);
+        let content = TestClient::get("http://127.0.0.1:5800/")
+            .basic_auth("root", Some("pwd"))
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert_eq!(content, "root@admin");
+
+        let res = TestClient::get("http://127.0.0.1:5800/")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers().get("www-authenticate").unwrap(),
+            "Basic realm=\"admin\""
+        );
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
 }
@@ -7,7 +7,7 @@
 use etag::EntityTag;
 use salvo_core::http::header::{ETAG, IF_NONE_MATCH};
 use salvo_core::http::headers::{self, HeaderMapExt};
-use salvo_core::http::{ResBody, StatusCode};
+use salvo_core::http::{Method, ResBody, StatusCode};
 use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
 
 /// Etag and If-None-Match header handler
@@ -20,6 +20,10 @@ use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
 /// [`304 not modified`](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status/304) status,
 /// omitting the response body.
 ///
+/// The etag is computed from the buffered body of successful `GET` and `HEAD`
+/// responses, unless the handlers set it already. The bodies larger than
+/// [`max_body_size`](ETag::max_body_size) are not hashed.
+///
 /// ## Streamed bodies
 ///
 /// **Note**: This handler does not currently provide an etag trailer for
@@ -35,6 +39,8 @@ use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response};
 #[derive(Default, Clone, Copy, Debug)]
 pub struct ETag {
     strong: bool,
+    weak_tags: bool,
+    max_body_size: Option<usize>,
 }
 
 impl ETag {
@@ -51,6 +57,57 @@ impl ETag {
         self.strong = true;
         self
     }
+
+    /// Configures this handler to generate weak etags (`W/"..."`), e.g. when the
+    /// responses are compressed afterwards, so that the bytes sent differ from
+    /// the hashed body.
+    pub fn weak_tags(mut self) -> Self {
+        self.weak_tags = true;
+        self
+    }
+
+    /// Skip hashing the bodies larger than `size` bytes.
+    pub fn max_body_size(mut self, size: usize) -> Self {
+        self.max_body_size = Some(size);
+        self
+    }
+
+    fn compute(&self, body: &ResBody) -> Option<EntityTag> {
+        let size = match body {
+            ResBody::Once(bytes) => bytes.len(),
+            ResBody::Chunks(chunks) => chunks.iter().map(|chunk| chunk.len()).sum(),
+            ResBody::Stream(_) => {
+                tracing::debug!("etag not supported for streaming body");
+                return None;
+            }
+            ResBody::None => {
+                tracing::debug!("etag not supported for empty body");
+                return None;
+            }
+            _ => return None,
+        };
+        if self.max_body_size.is_some_and(|max| size > max) {
+            tracing::debug!(size, "etag skipped for large body");
+            return None;
+        }
+        let etag = match body {
+            ResBody::Once(bytes) => EntityTag::from_data(bytes),
+            ResBody::Chunks(chunks) => {
+                let tags = chunks
+                    .iter()
+                    .map(|item| EntityTag::from_data(item).tag().to_owned())
+                    .collect::<Vec<_>>()
+                    .concat();
+                EntityTag::from_data(tags.as_bytes())
+            }
+            _ => return None,
+        };
+        if self.weak_tags {
+            Some(EntityTag::weak(etag.tag()))
+        } else {
+            Some(etag)
+        }
+    }
 }
 
 #[async_trait]
@@ -61,39 +118,21 @@ impl Handler for ETag {
             return;
         }
 
-        let if_none_match = req
-            .headers()
-            .get(IF_NONE_MATCH)
-            .and_then(|etag| etag.to_str().ok())
-            .and_then(|etag| etag.parse::<EntityTag>().ok());
+        if !matches!(*req.method(), Method::GET | Method::HEAD)
+            || res
+                .status_code
+                .is_some_and(|status| status != StatusCode::OK)
+        {
+            return;
+        }
 
-        let etag = req
+        let etag = res
             .headers()
             .get(ETAG)
             .and_then(|etag| etag.to_str().ok())
-            .and_then(|etag| etag.parse().ok())
+            .and_then(|etag| etag.parse::<EntityTag>().ok())
             .or_else(|| {
-                let etag = match &res.body {
-                    ResBody::Once(bytes) => Some(EntityTag::from_data(bytes)),
-                    ResBody::Chunks(bytes) => {
-                        let tags = bytes
-                            .iter()
-                            .map(|item| EntityTag::from_data(item).tag().to_owned())
-                            .collect::<Vec<_>>()
-                            .concat();
-                        Some(EntityTag::from_data(tags.as_bytes()))
-                    }
-                    ResBody::Stream(_) => {
-                        tracing::debug!("etag not supported for streaming body");
-                        None
-                    }
-                    ResBody::None => {
-                        tracing::debug!("etag not supported for empty body");
-                        None
-                    }
-                    _ => None,
-                };
-
+                let etag = self.compute(&res.body);
                 if let Some(etag) = &etag {
                     match etag.to_string().parse::<headers::ETag>() {
                         Ok(etag) => res.headers_mut().typed_insert(etag),
@@ -104,18 +143,30 @@ impl Handler for ETag {
                 }
                 etag
             });
+        let Some(etag) = etag else {
+            return;
+        };
 
-        if let (Some(etag), Some(if_none_match)) = (etag, if_none_match) {
-            let eq = if self.strong {
-                etag.strong_eq(&if_none_match)
-            } else {
-                etag.weak_eq(&if_none_match)
-            };
-
-            if eq {
-                res.body(ResBody::None);
-                res.status_code(StatusCode::NOT_MODIFIED);
-            }
+        let matched = req
+            .headers()
+            .get_all(IF_NONE_MATCH)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|value| {
+                let value = value.trim();
+                value == "*"
+                    || value.parse::<EntityTag>().is_ok_and(|if_none_match| {
+                        if self.strong {
+                            etag.strong_eq(&if_none_match)
+                        } else {
+                            etag.weak_eq(&if_none_match)
+                        }
+                    })
+            });
+        if matched {
+            res.body(ResBody::None);
+            res.status_code(StatusCode::NOT_MODIFIED);
         }
     }
 }
@@ -212,4 +263,52 @@ mod tests {
         assert_eq!(response.status_code, Some(StatusCode::NOT_MODIFIED));
         assert!(response.body.is_none());
     }
+
+    #[tokio::test]
+    async fn test_etag_weak_and_max_body_size() {
+        let router = Router::new()
+            .push(
+                Router::with_path("weak")
+                    .hoop(ETag::new().weak_tags())
+                    .goal(hello),
+            )
+            .push(
+                Router::with_path("large")
+                    .hoop(ETag::new().max_body_size(4))
+                    .get(hello),
+            );
+        let service = Service::new(router);
+
+        let response = TestClient::get("http://127.0.0.1:5800/weak")
+            .send(&service)
+            .await;
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_owned();
+        assert!(etag.starts_with("W/"));
+
+        let response = TestClient::get("http://127.0.0.1:5800/weak")
+            .add_header(IF_NONE_MATCH, format!("\"other\", {etag}"), true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code, Some(StatusCode::NOT_MODIFIED));
+        assert_eq!(response.headers().get(ETAG).unwrap(), etag.as_str());
+
+        let response = TestClient::post("http://127.0.0.1:5800/weak")
+            .add_header(IF_NONE_MATCH, "*", true)
+            .send(&service)
+            .await;
+        assert!(response.headers().get(ETAG).is_none());
+
+        let response = TestClient::get("http://127.0.0.1:5800/large")
+            .add_header(IF_NONE_MATCH, "*", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code, Some(StatusCode::OK));
+        assert!(response.headers().get(ETAG).is_none());
+    }
 }
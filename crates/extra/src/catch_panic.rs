@@ -3,6 +3,9 @@
 //! This middleware catches panics and write `500 Internal Server Error` into response.
 //! This middleware should be used as the first middleware.
 //!
+//! The response can be customized by [`CatchPanic::on_panic`], and the panics can be reported to
+//! an error tracking service by [`CatchPanic::report`].
+//!
 //! # Example
 //!
 //! ```no_run
@@ -22,13 +25,88 @@
 //! }
 //! ```
 
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Formatter};
 use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Once};
 
 use futures_util::FutureExt;
 
-use salvo_core::http::{Request, Response, StatusError};
+use salvo_core::http::{Method, Request, Response, StatusCode, StatusError};
 use salvo_core::{async_trait, Depot, FlowCtrl, Error, Handler};
 
+thread_local! {
+    static LAST_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+static BACKTRACE_HOOK: Once = Once::new();
+
+/// Installs a panic hook which captures the backtrace of the panic, the previous hook is still
+/// called.
+fn install_backtrace_hook() {
+    BACKTRACE_HOOK.call_once(|| {
+        let prev = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            LAST_BACKTRACE.with(|bt| *bt.borrow_mut() = Some(Backtrace::force_capture()));
+            prev(info);
+        }));
+    });
+}
+
+/// The information of a panic caught by [`CatchPanic`].
+pub struct CaughtPanic {
+    payload: Box<dyn Any + Send>,
+    backtrace: Option<Backtrace>,
+    method: Method,
+    path: String,
+}
+impl CaughtPanic {
+    /// The panic message, it is empty if the payload is neither `&str` nor `String`.
+    pub fn message(&self) -> &str {
+        if let Some(msg) = self.payload.downcast_ref::<&'static str>() {
+            msg
+        } else if let Some(msg) = self.payload.downcast_ref::<String>() {
+            msg
+        } else {
+            ""
+        }
+    }
+    /// The panic payload, the value passed to `panic!` or `std::panic::panic_any`.
+    #[inline]
+    pub fn payload(&self) -> &(dyn Any + Send) {
+        &*self.payload
+    }
+    /// The backtrace of the panic, it is only available when
+    /// [`CatchPanic::capture_backtrace`] is enabled.
+    #[inline]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_ref()
+    }
+    /// The method of the request.
+    #[inline]
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+    /// The path of the request.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+impl Debug for CaughtPanic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CaughtPanic")
+            .field("message", &self.message())
+            .field("backtrace", &self.backtrace)
+            .field("method", &self.method)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+type PanicRender = dyn Fn(&CaughtPanic, &Request, &mut Response) + Send + Sync;
+type PanicReport = dyn Fn(&CaughtPanic) + Send + Sync;
 
 /// Middleware that catches panics in handlers and converts them to HTTP 500 responses.
 /// 
@@ -36,26 +114,117 @@ use salvo_core::{async_trait, Depot, FlowCtrl, Error, Handler};
 /// to ensure it catches panics from all subsequent handlers and middlewares.
 /// 
 /// View [module level documentation](index.html) for more details.
-#[derive(Default, Debug)]
-pub struct CatchPanic {}
+#[derive(Default)]
+pub struct CatchPanic {
+    render: Option<Arc<PanicRender>>,
+    report: Option<Arc<PanicReport>>,
+    capture_backtrace: bool,
+}
 impl CatchPanic {
     /// Create new `CatchPanic` middleware.
     #[inline]
     pub fn new() -> Self {
-        CatchPanic {}
+        Self::default()
+    }
+
+    /// Render the response of the caught panic by `render` instead of the default
+    /// `500 Internal Server Error`, e.g. a problem details JSON with the correlation id of the
+    /// request.
+    ///
+    /// The status code is set to `500 Internal Server Error` before `render` is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use salvo_core::prelude::*;
+    /// use salvo_core::writing::Text;
+    /// use salvo_extra::catch_panic::CatchPanic;
+    ///
+    /// let catch_panic = CatchPanic::new().on_panic(|panic, req, res| {
+    ///     let request_id = req.header::<String>("x-request-id").unwrap_or_default();
+    ///     res.render(Text::Json(format!(
+    ///         r#"{{"title":"Internal Server Error","status":500,"instance":{:?},"request_id":{:?}}}"#,
+    ///         panic.path(),
+    ///         request_id,
+    ///     )));
+    /// });
+    /// ```
+    #[inline]
+    pub fn on_panic<F>(mut self, render: F) -> Self
+    where
+        F: Fn(&CaughtPanic, &Request, &mut Response) + Send + Sync + 'static,
+    {
+        self.render = Some(Arc::new(render));
+        self
+    }
+
+    /// Call `report` with every caught panic, e.g. to send it to an error tracking service.
+    ///
+    /// It is called before the response is rendered.
+    #[inline]
+    pub fn report<F>(mut self, report: F) -> Self
+    where
+        F: Fn(&CaughtPanic) + Send + Sync + 'static,
+    {
+        self.report = Some(Arc::new(report));
+        self
+    }
+
+    /// Capture the backtraces of the panics, it is available from [`CaughtPanic::backtrace`].
+    ///
+    /// This installs a process wide panic hook which captures the backtrace of every panic,
+    /// the previously installed hook is still called. The backtrace is captured regardless of
+    /// `RUST_BACKTRACE`.
+    #[inline]
+    pub fn capture_backtrace(mut self, capture: bool) -> Self {
+        if capture {
+            install_backtrace_hook();
+        }
+        self.capture_backtrace = capture;
+        self
+    }
+}
+impl Debug for CatchPanic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CatchPanic")
+            .field("render", &self.render.is_some())
+            .field("report", &self.report.is_some())
+            .field("capture_backtrace", &self.capture_backtrace)
+            .finish()
     }
 }
 
 #[async_trait]
 impl Handler for CatchPanic {
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let method = req.method().clone();
+        let path = req.uri().path().to_owned();
         if let Err(e) = AssertUnwindSafe(ctrl.call_next(req, depot, res)).catch_unwind().await {
-            tracing::error!(error = ?e, "panic occurred");
-            res.render(
-                StatusError::internal_server_error()
-                    .brief("Panic occurred on server.")
-                    .cause(Error::other(format!("{e:#?}"))),
-            );
+            tracing::error!(error = ?e, %method, %path, "panic occurred");
+            let backtrace = if self.capture_backtrace {
+                LAST_BACKTRACE.with(|bt| bt.borrow_mut().take())
+            } else {
+                None
+            };
+            let panic = CaughtPanic {
+                payload: e,
+                backtrace,
+                method,
+                path,
+            };
+            if let Some(report) = &self.report {
+                report(&panic);
+            }
+            if let Some(render) = &self.render {
+                res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+                render(&panic, req, res);
+            } else {
+                res.render(
+                    StatusError::internal_server_error()
+                        .brief("Panic occurred on server.")
+                        .cause(Error::other(format!("{:#?}", panic.payload))),
+                );
+            }
         }
     }
 }
@@ -88,4 +257,44 @@ mod tests {
             .unwrap();
         assert!(logs_contain("panic occurred"));
     }
+
+    #[tokio::test]
+    async fn test_catch_panic_hooks() {
+        #[handler]
+        async fn hello() -> &'static str {
+            panic!("panic error!");
+        }
+
+        static REPORTED: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+        let router = Router::new()
+            .hoop(
+                CatchPanic::new()
+                    .capture_backtrace(true)
+                    .report(|panic| {
+                        assert!(panic.backtrace().is_some());
+                        *REPORTED.lock().unwrap() = Some(panic.message().to_owned());
+                    })
+                    .on_panic(|panic, req, res| {
+                        let request_id = req.header::<String>("x-request-id").unwrap_or_default();
+                        res.render(format!(
+                            "{} {} {} {request_id}",
+                            panic.method(),
+                            panic.path(),
+                            panic.message()
+                        ));
+                    }),
+            )
+            .push(Router::with_path("hello").get(hello));
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/hello")
+            .add_header("x-request-id", "abc", true)
+            .send(router)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(
+            res.take_string().await.unwrap(),
+            "GET /hello panic error! abc"
+        );
+        assert_eq!(REPORTED.lock().unwrap().as_deref(), Some("panic error!"));
+    }
 }
@@ -0,0 +1,508 @@
+//! Circuit breaker middleware.
+//!
+//! [`CircuitBreaker`] watches the responses of the following handlers, usually the handlers
+//! calling flaky upstreams such as the proxy. When the failure rate or the slow call rate in the
+//! current window exceeds the threshold, the circuit opens and the requests are rejected with
+//! `503 Service Unavailable` and a `Retry-After` header without calling the handlers. After
+//! [`open_duration`](CircuitBreaker::open_duration), the circuit is half-open and a few trial
+//! requests are let through: the circuit closes if they all succeed, otherwise it opens again.
+//!
+//! By default a response with server error status is a failure, e.g. `502 Bad Gateway` or
+//! `504 Gateway Timeout` written by the proxy, this can be changed by
+//! [`failure_when`](CircuitBreaker::failure_when).
+//!
+//! The requests share one circuit by default, [`key`](CircuitBreaker::key) splits them into
+//! circuits, e.g. by upstream host.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::circuit_breaker::CircuitBreaker;
+//!
+//! #[handler]
+//! async fn upstream() -> &'static str {
+//!     "Hello World"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let breaker = CircuitBreaker::new()
+//!         .failure_rate_threshold(0.5)
+//!         .slow_call(Duration::from_secs(2), 0.8)
+//!         .open_duration(Duration::from_secs(30))
+//!         .key(|req, _depot| req.param::<String>("service").unwrap_or_default());
+//!     let router = Router::with_path("{service}").hoop(breaker).get(upstream);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use salvo_core::http::header::RETRY_AFTER;
+use salvo_core::http::{HeaderValue, Request, ResBody, Response, StatusError};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+
+/// The state of a circuit.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CircuitState {
+    /// Requests are let through and their results are recorded.
+    Closed,
+    /// Requests are rejected.
+    Open,
+    /// A limited number of trial requests are let through.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Circuit {
+    state: CircuitState,
+    opened_at: Instant,
+    window_start: Instant,
+    calls: u32,
+    failures: u32,
+    slow_calls: u32,
+    trial_calls: u32,
+    trial_successes: u32,
+}
+impl Circuit {
+    fn new(now: Instant) -> Self {
+        Self {
+            state: CircuitState::Closed,
+            opened_at: now,
+            window_start: now,
+            calls: 0,
+            failures: 0,
+            slow_calls: 0,
+            trial_calls: 0,
+            trial_successes: 0,
+        }
+    }
+
+    fn reset_window(&mut self, now: Instant) {
+        self.window_start = now;
+        self.calls = 0;
+        self.failures = 0;
+        self.slow_calls = 0;
+    }
+
+    fn open(&mut self, now: Instant) {
+        self.state = CircuitState::Open;
+        self.opened_at = now;
+    }
+
+    /// A closed circuit without calls in the current window, it can be dropped without losing
+    /// any state.
+    fn is_idle(&self, window: Duration, now: Instant) -> bool {
+        self.state == CircuitState::Closed && now.duration_since(self.window_start) >= window
+    }
+}
+
+type KeyGetter = dyn Fn(&Request, &Depot) -> String + Send + Sync;
+type FailureChecker = dyn Fn(&Response) -> bool + Send + Sync;
+
+/// Circuit breaker middleware.
+///
+/// Cloned `CircuitBreaker`s share the same circuits.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    failure_rate_threshold: f64,
+    slow_call: Option<(Duration, f64)>,
+    minimum_calls: u32,
+    window: Duration,
+    open_duration: Duration,
+    half_open_calls: u32,
+    capacity: usize,
+    key: Option<Arc<KeyGetter>>,
+    failure_when: Arc<FailureChecker>,
+    circuits: Arc<Mutex<HashMap<String, Circuit>>>,
+}
+impl Default for CircuitBreaker {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl CircuitBreaker {
+    /// Create new `CircuitBreaker` which opens when half of at least 20 requests within 10
+    /// seconds failed, and stays open for 30 seconds.
+    pub fn new() -> Self {
+        Self {
+            failure_rate_threshold: 0.5,
+            slow_call: None,
+            minimum_calls: 20,
+            window: Duration::from_secs(10),
+            open_duration: Duration::from_secs(30),
+            half_open_calls: 1,
+            capacity: 10_000,
+            key: None,
+            failure_when: Arc::new(|res| {
+                let status = res.status_code.or(match &res.body {
+                    ResBody::Error(e) => Some(e.code),
+                    _ => None,
+                });
+                status.is_some_and(|status| status.is_server_error())
+            }),
+            circuits: Arc::default(),
+        }
+    }
+
+    /// Open the circuit when the rate of failed requests reaches `threshold`, between `0.0` and
+    /// `1.0`. Default is `0.5`.
+    #[inline]
+    pub fn failure_rate_threshold(mut self, threshold: f64) -> Self {
+        self.failure_rate_threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Treat the requests taking longer than `duration` as slow, and open the circuit when the
+    /// rate of slow requests reaches `rate_threshold`. Default is disabled.
+    #[inline]
+    pub fn slow_call(mut self, duration: Duration, rate_threshold: f64) -> Self {
+        self.slow_call = Some((duration, rate_threshold.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// The minimum number of requests in a window before the rates are evaluated. Default is 20.
+    #[inline]
+    pub fn minimum_calls(mut self, calls: u32) -> Self {
+        self.minimum_calls = calls.max(1);
+        self
+    }
+
+    /// The duration of the window in which the requests are counted. Default is 10 seconds.
+    #[inline]
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// How long the circuit stays open before trial requests are let through.
+    /// Default is 30 seconds.
+    #[inline]
+    pub fn open_duration(mut self, duration: Duration) -> Self {
+        self.open_duration = duration;
+        self
+    }
+
+    /// The number of trial requests let through when the circuit is half-open, the circuit
+    /// closes when all of them succeed. Default is 1.
+    #[inline]
+    pub fn half_open_calls(mut self, calls: u32) -> Self {
+        self.half_open_calls = calls.max(1);
+        self
+    }
+
+    /// When there are more than `capacity` circuits, the closed circuits without requests in the
+    /// current window are removed. Default is 10000.
+    #[inline]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Split the requests into separate circuits by the returned key.
+    #[inline]
+    pub fn key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&Request, &Depot) -> String + Send + Sync + 'static,
+    {
+        self.key = Some(Arc::new(key));
+        self
+    }
+
+    /// Decide whether the response is a failure. Default is the responses with server error
+    /// status.
+    #[inline]
+    pub fn failure_when<F>(mut self, failure_when: F) -> Self
+    where
+        F: Fn(&Response) -> bool + Send + Sync + 'static,
+    {
+        self.failure_when = Arc::new(failure_when);
+        self
+    }
+
+    /// Returns the state of the circuit of `key`, the key is empty if [`key`](Self::key) is
+    /// not set.
+    pub fn state(&self, key: &str) -> CircuitState {
+        self.circuits
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .map(|circuit| circuit.state)
+            .unwrap_or(CircuitState::Closed)
+    }
+
+    /// Asks to let a request through, returns whether it is a trial request of the half-open
+    /// circuit, or the duration to retry after if it is rejected.
+    fn acquire(&self, key: &str, now: Instant) -> Result<bool, Duration> {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        if circuits.len() >= self.capacity && !circuits.contains_key(key) {
+            circuits.retain(|_, circuit| !circuit.is_idle(self.window, now));
+        }
+        let circuit = circuits
+            .entry(key.to_owned())
+            .or_insert_with(|| Circuit::new(now));
+        if circuit.state == CircuitState::Open {
+            let elapsed = now.duration_since(circuit.opened_at);
+            if elapsed < self.open_duration {
+                return Err(self.open_duration - elapsed);
+            }
+            tracing::debug!(key, "circuit half-open");
+            circuit.state = CircuitState::HalfOpen;
+            circuit.trial_calls = 0;
+            circuit.trial_successes = 0;
+        }
+        if circuit.state == CircuitState::HalfOpen {
+            if circuit.trial_calls >= self.half_open_calls {
+                return Err(Duration::from_secs(1));
+            }
+            circuit.trial_calls += 1;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Gives the slot of a trial request back when it is canceled before its result is recorded.
+    fn release_trial(&self, key: &str) {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(circuit) = circuits.get_mut(key) {
+            if circuit.state == CircuitState::HalfOpen {
+                circuit.trial_calls = circuit.trial_calls.saturating_sub(1);
+            }
+        }
+    }
+
+    fn record(&self, key: &str, failed: bool, slow: bool, now: Instant) {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(circuit) = circuits.get_mut(key) else {
+            return;
+        };
+        match circuit.state {
+            CircuitState::Closed => {
+                if now.duration_since(circuit.window_start) >= self.window {
+                    circuit.reset_window(now);
+                }
+                circuit.calls += 1;
+                circuit.failures += failed as u32;
+                circuit.slow_calls += slow as u32;
+                if circuit.calls < self.minimum_calls {
+                    return;
+                }
+                let calls = circuit.calls as f64;
+                let failure_rate = circuit.failures as f64 / calls;
+                let slow_rate = circuit.slow_calls as f64 / calls;
+                if (circuit.failures > 0 && failure_rate >= self.failure_rate_threshold)
+                    || self.slow_call.is_some_and(|(_, threshold)| {
+                        circuit.slow_calls > 0 && slow_rate >= threshold
+                    })
+                {
+                    tracing::warn!(key, failure_rate, slow_rate, "circuit opened");
+                    circuit.open(now);
+                }
+            }
+            CircuitState::HalfOpen => {
+                if failed || slow {
+                    tracing::warn!(key, "trial request failed, circuit opened");
+                    circuit.open(now);
+                } else {
+                    circuit.trial_successes += 1;
+                    if circuit.trial_successes >= self.half_open_calls {
+                        tracing::info!(key, "circuit closed");
+                        circuit.state = CircuitState::Closed;
+                        circuit.reset_window(now);
+                    }
+                }
+            }
+            // The request was let through before the circuit opened.
+            CircuitState::Open => {}
+        }
+    }
+}
+impl Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("failure_rate_threshold", &self.failure_rate_threshold)
+            .field("slow_call", &self.slow_call)
+            .field("minimum_calls", &self.minimum_calls)
+            .field("window", &self.window)
+            .field("open_duration", &self.open_duration)
+            .field("half_open_calls", &self.half_open_calls)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+/// Releases the trial slot if the request is canceled before its result is recorded.
+struct TrialGuard<'a> {
+    breaker: &'a CircuitBreaker,
+    key: &'a str,
+    pending: bool,
+}
+impl Drop for TrialGuard<'_> {
+    fn drop(&mut self) {
+        if self.pending {
+            self.breaker.release_trial(self.key);
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for CircuitBreaker {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let key = match &self.key {
+            Some(key) => key(req, depot),
+            None => String::new(),
+        };
+        let trial = match self.acquire(&key, Instant::now()) {
+            Ok(trial) => trial,
+            Err(retry_after) => {
+                let seconds = retry_after.as_secs() + (retry_after.subsec_nanos() > 0) as u64;
+                res.headers_mut()
+                    .insert(RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+                res.render(StatusError::service_unavailable().brief("Circuit breaker is open."));
+                ctrl.skip_rest();
+                return;
+            }
+        };
+        let mut guard = TrialGuard {
+            breaker: self,
+            key: &key,
+            pending: trial,
+        };
+        let started = Instant::now();
+        ctrl.call_next(req, depot, res).await;
+        let now = Instant::now();
+        let failed = (self.failure_when)(res);
+        let slow = self
+            .slow_call
+            .is_some_and(|(duration, _)| now.duration_since(started) > duration);
+        guard.pending = false;
+        self.record(&key, failed, slow, now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::TestClient;
+
+    use super::*;
+
+    static FAIL: AtomicBool = AtomicBool::new(true);
+
+    #[handler]
+    async fn upstream(res: &mut Response) {
+        if FAIL.load(Ordering::SeqCst) {
+            res.status_code(StatusCode::BAD_GATEWAY);
+        } else {
+            res.render("ok");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker() {
+        let breaker = CircuitBreaker::new()
+            .minimum_calls(2)
+            .open_duration(Duration::from_millis(200));
+        let router = Arc::new(
+            Router::with_path("upstream")
+                .hoop(breaker.clone())
+                .get(upstream),
+        );
+        let send = || async {
+            TestClient::get("http://127.0.0.1:5801/upstream")
+                .send(router.clone())
+                .await
+        };
+
+        assert_eq!(send().await.status_code, Some(StatusCode::BAD_GATEWAY));
+        assert_eq!(breaker.state(""), CircuitState::Closed);
+        assert_eq!(send().await.status_code, Some(StatusCode::BAD_GATEWAY));
+        assert_eq!(breaker.state(""), CircuitState::Open);
+
+        let res = send().await;
+        assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(res.headers().get(RETRY_AFTER).unwrap(), "1");
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        FAIL.store(false, Ordering::SeqCst);
+        assert_eq!(send().await.status_code, Some(StatusCode::OK));
+        assert_eq!(breaker.state(""), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_trial_failed() {
+        let breaker = CircuitBreaker::new()
+            .minimum_calls(1)
+            .slow_call(Duration::from_millis(100), 1.0)
+            .open_duration(Duration::ZERO);
+        let now = Instant::now();
+        breaker.acquire("a", now).unwrap();
+        breaker.record("a", false, true, now);
+        assert_eq!(breaker.state("a"), CircuitState::Open);
+        assert_eq!(breaker.state("b"), CircuitState::Closed);
+
+        breaker.acquire("a", now).unwrap();
+        assert_eq!(breaker.state("a"), CircuitState::HalfOpen);
+        assert!(breaker.acquire("a", now).is_err());
+        breaker.record("a", true, false, now);
+        assert_eq!(breaker.state("a"), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_half_open_trial_canceled() {
+        let breaker = CircuitBreaker::new()
+            .minimum_calls(1)
+            .open_duration(Duration::ZERO);
+        let now = Instant::now();
+        breaker.acquire("a", now).unwrap();
+        breaker.record("a", true, false, now);
+        assert_eq!(breaker.state("a"), CircuitState::Open);
+
+        assert_eq!(breaker.acquire("a", now), Ok(true));
+        drop(TrialGuard {
+            breaker: &breaker,
+            key: "a",
+            pending: true,
+        });
+        assert_eq!(breaker.acquire("a", now), Ok(true));
+        breaker.record("a", false, false, now);
+        assert_eq!(breaker.state("a"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_idle_circuits_removed() {
+        let breaker = CircuitBreaker::new()
+            .minimum_calls(1)
+            .window(Duration::from_secs(10))
+            .capacity(2);
+        let now = Instant::now();
+        breaker.acquire("a", now).unwrap();
+        breaker.record("a", true, false, now);
+        breaker.acquire("b", now).unwrap();
+
+        let later = now + Duration::from_secs(10);
+        breaker.acquire("c", later).unwrap();
+        let circuits = breaker.circuits.lock().unwrap();
+        assert!(circuits.contains_key("a"));
+        assert!(!circuits.contains_key("b"));
+        assert!(circuits.contains_key("c"));
+    }
+}
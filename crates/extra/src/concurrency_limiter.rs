@@ -3,6 +3,10 @@
 //! This middleware limits the maximum number of requests being processed concurrently,
 //! which helps prevent server overload during traffic spikes.
 //!
+//! [`max_concurrency`] makes the requests wait until they can be processed. [`ConcurrencyLimiter`]
+//! supports per-key limits, e.g. by client IP or user id, bounded wait queues with timeout, and
+//! reports its utilization for metrics.
+//!
 //! # Example
 //! 
 //! ```no_run
@@ -69,7 +73,12 @@
 //! "#;
 //! ```
 
-use tokio::sync::Semaphore;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 use salvo_core::http::StatusError;
 use salvo_core::http::{Request, Response};
@@ -104,3 +113,347 @@ pub fn max_concurrency(size: usize) -> MaxConcurrency {
         semaphore: Semaphore::new(size),
     }
 }
+
+type KeyFn = dyn Fn(&Request, &Depot) -> Option<String> + Send + Sync;
+
+#[derive(Debug)]
+struct Slot {
+    semaphore: Arc<Semaphore>,
+    // Requests holding or waiting for a permit, the slot is removed when it drops to zero.
+    active: usize,
+    waiting: usize,
+}
+
+/// Utilization of a [`ConcurrencyLimiter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConcurrencyStats {
+    /// Number of keys with requests in flight or queued.
+    pub keys: usize,
+    /// Number of requests in flight.
+    pub in_flight: usize,
+    /// Number of requests waiting in the queues.
+    pub queued: usize,
+}
+
+/// Concurrency limiter with per-key limits and bounded wait queues.
+///
+/// Every key, such as a client IP, a user id or a route, has its own limit of requests in flight.
+/// When it is reached, the requests wait in a queue of bounded length for at most the queue
+/// timeout, the others are rejected with `503 Service Unavailable`. By default all the requests
+/// share one key and are rejected immediately when the limit is reached.
+///
+/// The limiter is cheap to clone, the clones share the state, so that a clone can be kept to
+/// report the utilization with [`stats`](Self::stats).
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use salvo_core::prelude::*;
+/// use salvo_extra::concurrency_limiter::ConcurrencyLimiter;
+///
+/// let limiter = ConcurrencyLimiter::new(4)
+///     .by_ip()
+///     .queue(16, Duration::from_secs(5));
+/// let router = Router::new().hoop(limiter.clone());
+/// println!("in flight: {}", limiter.stats().in_flight);
+/// ```
+#[derive(Clone)]
+pub struct ConcurrencyLimiter {
+    max: usize,
+    queue_len: usize,
+    queue_timeout: Duration,
+    key: Option<Arc<KeyFn>>,
+    slots: Arc<Mutex<HashMap<String, Slot>>>,
+}
+impl ConcurrencyLimiter {
+    /// Create new `ConcurrencyLimiter` which allows `max` requests in flight per key.
+    #[inline]
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            queue_len: 0,
+            queue_timeout: Duration::ZERO,
+            key: None,
+            slots: Arc::default(),
+        }
+    }
+
+    /// Let at most `len` requests per key wait for `timeout` when the limit is reached.
+    #[inline]
+    pub fn queue(mut self, len: usize, timeout: Duration) -> Self {
+        self.queue_len = len;
+        self.queue_timeout = timeout;
+        self
+    }
+
+    /// Limit the requests per key returned by `key`, the requests without key are not limited.
+    #[inline]
+    pub fn key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&Request, &Depot) -> Option<String> + Send + Sync + 'static,
+    {
+        self.key = Some(Arc::new(key));
+        self
+    }
+
+    /// Limit the requests per client IP, see [`Request::client_ip`].
+    #[inline]
+    pub fn by_ip(self) -> Self {
+        self.key(|req, _| req.client_ip().map(|ip| ip.to_string()))
+    }
+
+    /// Limit the requests per route, which is the matched route pattern such as `/users/{id}`, see
+    /// [`Request::matched_route`]. The requests which no router is matched share one limit.
+    ///
+    /// It requires the `matched-path` feature, the raw request path is never used as key, otherwise
+    /// every distinct url, e.g. `/users/1` and `/users/2`, would get its own limit.
+    #[cfg(feature = "matched-path")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "matched-path")))]
+    #[inline]
+    pub fn by_route(self) -> Self {
+        self.key(|req, _| Some(req.matched_route().unwrap_or_default()))
+    }
+
+    /// Limit the requests per the `String` value stored in the depot under `depot_key`, e.g. the
+    /// user id set by an authentication middleware.
+    #[inline]
+    pub fn by_depot(self, depot_key: impl Into<String>) -> Self {
+        let depot_key = depot_key.into();
+        self.key(move |_, depot| depot.get::<String>(&depot_key).ok().cloned())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Slot>> {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn stats_of(&self, slot: &Slot) -> ConcurrencyStats {
+        ConcurrencyStats {
+            keys: 1,
+            in_flight: self.max.saturating_sub(slot.semaphore.available_permits()),
+            queued: slot.waiting,
+        }
+    }
+
+    /// Returns the utilization of all the keys.
+    pub fn stats(&self) -> ConcurrencyStats {
+        self.lock()
+            .values()
+            .fold(ConcurrencyStats::default(), |total, slot| {
+                let stats = self.stats_of(slot);
+                ConcurrencyStats {
+                    keys: total.keys + 1,
+                    in_flight: total.in_flight + stats.in_flight,
+                    queued: total.queued + stats.queued,
+                }
+            })
+    }
+
+    /// Returns the utilization of a key, `None` if it has no request in flight or queued.
+    pub fn key_stats(&self, key: &str) -> Option<ConcurrencyStats> {
+        self.lock().get(key).map(|slot| self.stats_of(slot))
+    }
+
+    async fn acquire(&self, key: &str) -> Option<OwnedSemaphorePermit> {
+        let semaphore = {
+            let mut slots = self.lock();
+            let slot = slots.entry(key.to_owned()).or_insert_with(|| Slot {
+                semaphore: Arc::new(Semaphore::new(self.max)),
+                active: 0,
+                waiting: 0,
+            });
+            slot.active += 1;
+            slot.semaphore.clone()
+        };
+        if let Ok(permit) = semaphore.clone().try_acquire_owned() {
+            return Some(permit);
+        }
+        {
+            let mut slots = self.lock();
+            let slot = slots.get_mut(key).expect("slot should exist while active");
+            if slot.waiting >= self.queue_len {
+                return None;
+            }
+            slot.waiting += 1;
+        }
+        let _guard = WaitGuard { limiter: self, key };
+        let permit = tokio::time::timeout(self.queue_timeout, semaphore.acquire_owned()).await;
+        permit.ok()?.ok()
+    }
+
+    fn release(&self, key: &str) {
+        let mut slots = self.lock();
+        if let Some(slot) = slots.get_mut(key) {
+            slot.active -= 1;
+            if slot.active == 0 {
+                slots.remove(key);
+            }
+        }
+    }
+}
+impl Debug for ConcurrencyLimiter {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ConcurrencyLimiter")
+            .field("max", &self.max)
+            .field("queue_len", &self.queue_len)
+            .field("queue_timeout", &self.queue_timeout)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// Releases the slot of the key when the request finishes or is canceled.
+struct SlotGuard<'a> {
+    limiter: &'a ConcurrencyLimiter,
+    key: &'a str,
+}
+impl Drop for SlotGuard<'_> {
+    fn drop(&mut self) {
+        self.limiter.release(self.key);
+    }
+}
+
+/// Leaves the wait queue of the key when the request gets a permit, times out or is canceled.
+struct WaitGuard<'a> {
+    limiter: &'a ConcurrencyLimiter,
+    key: &'a str,
+}
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.limiter.lock().get_mut(self.key) {
+            slot.waiting -= 1;
+        }
+    }
+}
+
+#[async_trait]
+impl Handler for ConcurrencyLimiter {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let key = match &self.key {
+            Some(key) => match key(req, depot) {
+                Some(key) => key,
+                None => return,
+            },
+            None => String::new(),
+        };
+        let _guard = SlotGuard {
+            limiter: self,
+            key: &key,
+        };
+        match self.acquire(&key).await {
+            Some(_permit) => {
+                ctrl.call_next(req, depot, res).await;
+            }
+            None => {
+                tracing::debug!(key, "concurrency limit reached");
+                res.render(StatusError::service_unavailable().brief("Max concurrency reached."));
+                ctrl.skip_rest();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::TestClient;
+
+    use super::*;
+
+    #[handler]
+    async fn slow() -> &'static str {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        "ok"
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter() {
+        let limiter = ConcurrencyLimiter::new(1)
+            .key(|req, _| req.header::<String>("x-user"))
+            .queue(1, Duration::from_secs(5));
+        let service = Service::new(Router::new().hoop(limiter.clone()).get(slow));
+        let get = |user: &'static str| {
+            let service = &service;
+            async move {
+                TestClient::get("http://127.0.0.1:5801/")
+                    .add_header("x-user", user, true)
+                    .send(service)
+                    .await
+                    .status_code
+            }
+        };
+
+        let (a, b, c, d, ()) = tokio::join!(get("a"), get("a"), get("a"), get("b"), async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            assert_eq!(
+                limiter.key_stats("a"),
+                Some(ConcurrencyStats {
+                    keys: 1,
+                    in_flight: 1,
+                    queued: 1,
+                })
+            );
+            assert_eq!(limiter.stats().in_flight, 2);
+        });
+        assert_eq!(a, Some(StatusCode::OK));
+        assert_eq!(b, Some(StatusCode::OK));
+        assert_eq!(c, Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(d, Some(StatusCode::OK));
+        assert_eq!(limiter.stats(), ConcurrencyStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter_queue_timeout() {
+        let limiter = ConcurrencyLimiter::new(1).queue(1, Duration::from_millis(10));
+        let service = Service::new(Router::new().hoop(limiter).get(slow));
+        let get = || TestClient::get("http://127.0.0.1:5801/").send(&service);
+        let (a, b) = tokio::join!(get(), get());
+        assert_eq!(a.status_code, Some(StatusCode::OK));
+        assert_eq!(b.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limiter_queue_cancel() {
+        let limiter = ConcurrencyLimiter::new(1).queue(1, Duration::from_secs(5));
+        let service = Service::new(Router::new().hoop(limiter.clone()).get(slow));
+        let get = || TestClient::get("http://127.0.0.1:5801/").send(&service);
+
+        let (a, canceled, c) = tokio::join!(
+            get(),
+            tokio::time::timeout(Duration::from_millis(10), get()),
+            async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                get().await
+            }
+        );
+        assert_eq!(a.status_code, Some(StatusCode::OK));
+        assert!(canceled.is_err());
+        assert_eq!(c.status_code, Some(StatusCode::OK));
+        assert_eq!(limiter.stats(), ConcurrencyStats::default());
+    }
+
+    #[cfg(feature = "matched-path")]
+    #[tokio::test]
+    async fn test_concurrency_limiter_by_route() {
+        let limiter = ConcurrencyLimiter::new(1).by_route();
+        let service = Service::new(
+            Router::new()
+                .hoop(limiter)
+                .push(Router::with_path("users/{id}").get(slow)),
+        );
+        let get = |path: &'static str| {
+            TestClient::get(format!("http://127.0.0.1:5801{path}")).send(&service)
+        };
+        let (a, b) = tokio::join!(get("/users/1"), get("/users/2"));
+        assert_eq!(a.status_code, Some(StatusCode::OK));
+        assert_eq!(b.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+    }
+}
@@ -12,12 +12,18 @@
 //! Regardless of whether the request is successfully matched by the route,
 //! the middleware added to the Service will always be executed.
 //!
+//! The middleware can also send the `Strict-Transport-Security` header with the HTTPS responses,
+//! see [`Hsts`], and exempt some paths from the redirect, such as health checks probed over
+//! plain HTTP and the ACME HTTP-01 challenges.
+//!
 //! Example:
 //!
 //! ```no_run
+//! use std::time::Duration;
+//!
 //! use salvo_core::prelude::*;
 //! use salvo_core::conn::rustls::{Keycert, RustlsConfig};
-//! use salvo_extra::force_https::ForceHttps;
+//! use salvo_extra::force_https::{ForceHttps, Hsts};
 //!
 //! #[handler]
 //! async fn hello() -> &'static str {
@@ -27,7 +33,12 @@
 //! #[tokio::main]
 //! async fn main() {
 //!     let router = Router::new().get(hello);
-//!     let service = Service::new(router).hoop(ForceHttps::new().https_port(5443));
+//!     let force_https = ForceHttps::new()
+//!         .https_port(5443)
+//!         .hsts(Hsts::new(Duration::from_secs(31536000)).include_subdomains(true))
+//!         .exempt_acme_challenge()
+//!         .exempt_path("/health");
+//!     let service = Service::new(router).hoop(force_https);
 //!
 //!     let config = RustlsConfig::new(
 //!         Keycert::new()
@@ -43,20 +54,84 @@
 //! }
 //! ```
 use std::borrow::Cow;
+use std::time::Duration;
 
 use salvo_core::handler::Skipper;
-use salvo_core::http::header;
+use salvo_core::http::header::{self, HeaderValue};
 use salvo_core::http::uri::{Scheme, Uri};
-use salvo_core::http::{Request, ResBody, Response};
+use salvo_core::http::{Request, ResBody, Response, StatusCode};
 use salvo_core::writing::Redirect;
 use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
 
+/// The path prefix of the ACME HTTP-01 challenges.
+pub const ACME_CHALLENGE_PATH: &str = "/.well-known/acme-challenge";
+
+/// `Strict-Transport-Security` header configuration.
+///
+/// The header is only sent with the HTTPS responses, browsers ignore it over plain HTTP.
+#[derive(Clone, Debug)]
+pub struct Hsts {
+    max_age: Duration,
+    include_subdomains: bool,
+    preload: bool,
+}
+impl Hsts {
+    /// Create new `Hsts` with the `max-age` directive.
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            include_subdomains: false,
+            preload: false,
+        }
+    }
+
+    /// Add the `includeSubDomains` directive.
+    pub fn include_subdomains(self, include_subdomains: bool) -> Self {
+        Self {
+            include_subdomains,
+            ..self
+        }
+    }
+
+    /// Add the `preload` directive, required to submit the domain to the browsers preload list
+    /// together with `includeSubDomains` and a `max-age` of at least one year.
+    pub fn preload(self, preload: bool) -> Self {
+        Self { preload, ..self }
+    }
+
+    fn header_value(&self) -> HeaderValue {
+        let mut value = format!("max-age={}", self.max_age.as_secs());
+        if self.include_subdomains {
+            value.push_str("; includeSubDomains");
+        }
+        if self.preload {
+            value.push_str("; preload");
+        }
+        HeaderValue::from_str(&value).expect("valid header value")
+    }
+}
+
 /// Middleware for force redirect to http uri.
-#[derive(Default)]
 pub struct ForceHttps {
     https_port: Option<u16>,
+    port_map: Vec<(u16, u16)>,
+    redirect_status: StatusCode,
+    hsts: Option<HeaderValue>,
+    exempt_paths: Vec<String>,
     skipper: Option<Box<dyn Skipper>>,
 }
+impl Default for ForceHttps {
+    fn default() -> Self {
+        Self {
+            https_port: None,
+            port_map: Vec::new(),
+            redirect_status: StatusCode::PERMANENT_REDIRECT,
+            hsts: None,
+            exempt_paths: Vec::new(),
+            skipper: None,
+        }
+    }
+}
 impl ForceHttps {
     /// Create new `ForceHttps` middleware.
     pub fn new() -> Self {
@@ -71,6 +146,47 @@ impl ForceHttps {
         }
     }
 
+    /// Redirect the requests on `http_port` to `https_port`, e.g. `8080` to `8443`. It takes
+    /// precedence over [`https_port`](Self::https_port), the port of a `Host` header without
+    /// port is `80`.
+    pub fn port_map(mut self, http_port: u16, https_port: u16) -> Self {
+        self.port_map.push((http_port, https_port));
+        self
+    }
+
+    /// Specify the redirect status, `308 Permanent Redirect` by default. Use
+    /// `301 Moved Permanently` for the old clients which do not support `308`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `status` is not a redirection status.
+    pub fn redirect_status(self, status: StatusCode) -> Self {
+        assert!(status.is_redirection(), "invalid redirect status: {status}");
+        Self {
+            redirect_status: status,
+            ..self
+        }
+    }
+
+    /// Send the `Strict-Transport-Security` header with the HTTPS responses.
+    pub fn hsts(self, hsts: Hsts) -> Self {
+        Self {
+            hsts: Some(hsts.header_value()),
+            ..self
+        }
+    }
+
+    /// Do not redirect the requests whose path starts with `prefix`, e.g. `/health`.
+    pub fn exempt_path(mut self, prefix: impl Into<String>) -> Self {
+        self.exempt_paths.push(prefix.into());
+        self
+    }
+
+    /// Do not redirect the ACME HTTP-01 challenges, which are under [`ACME_CHALLENGE_PATH`].
+    pub fn exempt_acme_challenge(self) -> Self {
+        self.exempt_path(ACME_CHALLENGE_PATH)
+    }
+
     /// Uses a closure to determine if a request should be redirect.
     pub fn skipper(self, skipper: impl Skipper) -> Self {
         Self {
@@ -78,12 +194,46 @@ impl ForceHttps {
             ..self
         }
     }
+
+    fn is_exempt(&self, path: &str) -> bool {
+        self.exempt_paths.iter().any(|prefix| {
+            path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+            })
+        })
+    }
+
+    fn https_port_for(&self, host: &str) -> Option<u16> {
+        if !self.port_map.is_empty() {
+            let port = match host.rsplit_once(':') {
+                Some((_, port)) if !port.ends_with(']') => port.parse().ok(),
+                _ => Some(80),
+            };
+            if let Some(port) = port {
+                if let Some((_, https_port)) = self
+                    .port_map
+                    .iter()
+                    .find(|(http_port, _)| *http_port == port)
+                {
+                    return Some(*https_port);
+                }
+            }
+        }
+        self.https_port
+    }
 }
 
 #[async_trait]
 impl Handler for ForceHttps {
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
-        if req.uri().scheme() == Some(&Scheme::HTTPS)
+        if req.uri().scheme() == Some(&Scheme::HTTPS) || req.scheme() == &Scheme::HTTPS {
+            if let Some(hsts) = &self.hsts {
+                res.headers_mut()
+                    .insert(header::STRICT_TRANSPORT_SECURITY, hsts.clone());
+            }
+            return;
+        }
+        if self.is_exempt(req.uri().path())
             || self
                 .skipper
                 .as_ref()
@@ -93,15 +243,19 @@ impl Handler for ForceHttps {
             return;
         }
         if let Some(host) = req.header::<String>(header::HOST) {
-            let host = redirect_host(&host, self.https_port);
+            let host = redirect_host(&host, self.https_port_for(&host));
             let uri_parts = std::mem::take(req.uri_mut()).into_parts();
             let mut builder = Uri::builder().scheme(Scheme::HTTPS).authority(&*host);
             if let Some(path_and_query) = uri_parts.path_and_query {
                 builder = builder.path_and_query(path_and_query);
             }
-            if let Ok(uri) = builder.build() {
+            if let Some(redirect) = builder
+                .build()
+                .ok()
+                .and_then(|uri| Redirect::with_status_code(self.redirect_status, uri).ok())
+            {
                 res.body(ResBody::None);
-                res.render(Redirect::permanent(uri));
+                res.render(redirect);
                 ctrl.skip_rest();
             }
         }
@@ -118,7 +272,7 @@ fn redirect_host(host: &str, https_port: Option<u16>) -> Cow<'_, str> {
 
 #[cfg(test)]
 mod tests {
-    use salvo_core::http::header::{HOST, LOCATION};
+    use salvo_core::http::header::{HOST, LOCATION, STRICT_TRANSPORT_SECURITY};
     use salvo_core::prelude::*;
     use salvo_core::test::TestClient;
 
@@ -150,4 +304,49 @@ mod tests {
             Some(&"https://127.0.0.1:1234/".parse().unwrap())
         );
     }
+
+    #[tokio::test]
+    async fn test_force_https_options() {
+        let force_https = ForceHttps::new()
+            .port_map(8080, 8443)
+            .redirect_status(StatusCode::MOVED_PERMANENTLY)
+            .hsts(
+                Hsts::new(Duration::from_secs(31536000))
+                    .include_subdomains(true)
+                    .preload(true),
+            )
+            .exempt_acme_challenge()
+            .exempt_path("/health");
+        let service = Service::new(Router::new().push(Router::with_path("{**rest}").goal(hello)))
+            .hoop(force_https);
+
+        let response = TestClient::get("http://example.com:8080/index.html?a=1")
+            .add_header(HOST, "example.com:8080", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code, Some(StatusCode::MOVED_PERMANENTLY));
+        assert_eq!(
+            response.headers().get(LOCATION),
+            Some(&"https://example.com:8443/index.html?a=1".parse().unwrap())
+        );
+        assert!(response.headers().get(STRICT_TRANSPORT_SECURITY).is_none());
+
+        for path in ["/health", "/.well-known/acme-challenge/token"] {
+            let response = TestClient::get(format!("http://example.com:8080{path}"))
+                .add_header(HOST, "example.com:8080", true)
+                .send(&service)
+                .await;
+            assert_eq!(response.status_code, Some(StatusCode::OK));
+        }
+
+        let response = TestClient::get("https://example.com/")
+            .add_header(HOST, "example.com", true)
+            .send(&service)
+            .await;
+        assert_eq!(response.status_code, Some(StatusCode::OK));
+        assert_eq!(
+            response.headers().get(STRICT_TRANSPORT_SECURITY).unwrap(),
+            "max-age=31536000; includeSubDomains; preload"
+        );
+    }
 }
@@ -0,0 +1,325 @@
+//! Health check and readiness endpoints.
+//!
+//! [`HealthCheck`] holds a registry of async check functions, such as database ping or upstream reachability.
+//! The `/healthz` endpoint runs all checks and the `/readyz` endpoint also takes the readiness flag into account,
+//! which can be flipped by [`Readiness`] during graceful shutdown.
+//!
+//! Both endpoints respond `200 OK` if everything is fine, otherwise `503 Service Unavailable`, with the
+//! aggregated JSON output like this:
+//!
+//! ```json
+//! {"status":"down","checks":{"db":{"status":"up","duration_ms":2},"cache":{"status":"down","duration_ms":1000,"error":"check timeout"}}}
+//! ```
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::health::HealthCheck;
+//!
+//! async fn ping_db() -> Result<(), std::io::Error> {
+//!     Ok(())
+//! }
+//! async fn shutdown_signal() {}
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let health = HealthCheck::new()
+//!         .check("db", ping_db)
+//!         .check_with_timeout("upstream", Duration::from_millis(500), || async {
+//!             Ok::<_, String>(())
+//!         });
+//!     let readiness = health.readiness();
+//!     let router = Router::new().push(health.router());
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     let server = Server::new(acceptor);
+//!     let handle = server.handle();
+//!     tokio::spawn(async move {
+//!         shutdown_signal().await;
+//!         // Stop receiving new traffic from the load balancer before shutting down.
+//!         readiness.set_ready(false);
+//!         tokio::time::sleep(Duration::from_secs(5)).await;
+//!         handle.stop_graceful(None);
+//!     });
+//!     server.serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Display, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use futures_util::future::{BoxFuture, join_all};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+use salvo_core::http::{Request, Response, StatusCode};
+use salvo_core::writing::Json;
+use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Router};
+
+type CheckFn = dyn Fn() -> BoxFuture<'static, Result<(), String>> + Send + Sync;
+
+struct Check {
+    name: String,
+    timeout: Option<Duration>,
+    func: Box<CheckFn>,
+}
+
+/// The readiness flag, it is shared by the [`HealthCheck`] and the handlers created from it.
+///
+/// The service is ready by default, set it to not ready to let the load balancer stop sending new traffic,
+/// for example, at the beginning of graceful shutdown.
+#[derive(Clone, Debug)]
+pub struct Readiness(Arc<AtomicBool>);
+impl Readiness {
+    /// Set whether the service is ready.
+    #[inline]
+    pub fn set_ready(&self, ready: bool) {
+        self.0.store(ready, Ordering::Release);
+    }
+    /// Returns `true` if the service is ready.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct CheckReport {
+    status: &'static str,
+    duration_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Registry of the health checks.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct HealthCheck {
+    checks: Vec<Arc<Check>>,
+    timeout: Duration,
+    readiness: Readiness,
+}
+impl Debug for HealthCheck {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthCheck")
+            .field(
+                "checks",
+                &self.checks.iter().map(|c| &c.name).collect::<Vec<_>>(),
+            )
+            .field("timeout", &self.timeout)
+            .field("readiness", &self.readiness)
+            .finish()
+    }
+}
+impl Default for HealthCheck {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl HealthCheck {
+    /// Create new `HealthCheck` without any check, the default timeout of each check is 5 seconds.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            checks: vec![],
+            timeout: Duration::from_secs(5),
+            readiness: Readiness(Arc::new(AtomicBool::new(true))),
+        }
+    }
+
+    /// Set the default timeout of each check.
+    #[inline]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Register a check with the default timeout.
+    #[inline]
+    pub fn check<F, Fut, E>(self, name: impl Into<String>, func: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        self.add_check(name.into(), None, func)
+    }
+
+    /// Register a check with its own timeout.
+    #[inline]
+    pub fn check_with_timeout<F, Fut, E>(self, name: impl Into<String>, timeout: Duration, func: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        self.add_check(name.into(), Some(timeout), func)
+    }
+
+    fn add_check<F, Fut, E>(mut self, name: String, timeout: Option<Duration>, func: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: Display,
+    {
+        let func = Box::new(move || -> BoxFuture<'static, Result<(), String>> {
+            let fut = func();
+            Box::pin(async move { fut.await.map_err(|e| e.to_string()) })
+        });
+        self.checks.push(Arc::new(Check { name, timeout, func }));
+        self
+    }
+
+    /// Get the readiness flag, it can be used to flip readiness during graceful shutdown.
+    #[inline]
+    pub fn readiness(&self) -> Readiness {
+        self.readiness.clone()
+    }
+
+    /// Create the handler for `/healthz`, it runs all checks.
+    #[inline]
+    pub fn healthz(&self) -> HealthHandler {
+        HealthHandler {
+            health: self.clone(),
+            readiness: false,
+        }
+    }
+
+    /// Create the handler for `/readyz`, it runs all checks and responds `503 Service Unavailable` if
+    /// the service is not ready.
+    #[inline]
+    pub fn readyz(&self) -> HealthHandler {
+        HealthHandler {
+            health: self.clone(),
+            readiness: true,
+        }
+    }
+
+    /// Create a router which contains `healthz` and `readyz` endpoints.
+    #[inline]
+    pub fn router(&self) -> Router {
+        Router::new()
+            .push(Router::with_path("healthz").get(self.healthz()))
+            .push(Router::with_path("readyz").get(self.readyz()))
+    }
+
+    /// Run all checks concurrently, returns whether all checks passed and the aggregated report.
+    pub async fn run(&self) -> (bool, Value) {
+        let reports = join_all(self.checks.iter().map(|check| async move {
+            let now = Instant::now();
+            let timeout = check.timeout.unwrap_or(self.timeout);
+            let result = match tokio::time::timeout(timeout, (check.func)()).await {
+                Ok(result) => result,
+                Err(_) => Err("check timeout".to_owned()),
+            };
+            let report = CheckReport {
+                status: if result.is_ok() { "up" } else { "down" },
+                duration_ms: now.elapsed().as_millis(),
+                error: result.err(),
+            };
+            (check.name.clone(), report)
+        }))
+        .await;
+        let healthy = reports.iter().all(|(_, report)| report.error.is_none());
+        let mut checks = Map::with_capacity(reports.len());
+        for (name, report) in reports {
+            checks.insert(name, json!(report));
+        }
+        (
+            healthy,
+            json!({
+                "status": if healthy { "up" } else { "down" },
+                "checks": checks,
+            }),
+        )
+    }
+}
+
+/// Handler for health check endpoints, created by [`HealthCheck::healthz`] or [`HealthCheck::readyz`].
+#[derive(Clone, Debug)]
+pub struct HealthHandler {
+    health: HealthCheck,
+    readiness: bool,
+}
+
+#[async_trait]
+impl Handler for HealthHandler {
+    async fn handle(&self, _req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+        let (mut healthy, mut report) = self.health.run().await;
+        if self.readiness {
+            let ready = self.health.readiness.is_ready();
+            if !ready {
+                healthy = false;
+                report["status"] = json!("down");
+            }
+            report["ready"] = json!(ready);
+        }
+        res.status_code(if healthy {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        });
+        res.render(Json(report));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_health() {
+        let health = HealthCheck::new()
+            .check("db", || async { Ok::<_, String>(()) })
+            .check_with_timeout("slow", Duration::from_millis(50), || async {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                Ok::<_, String>(())
+            });
+        let service = Service::new(health.router());
+        let mut res = TestClient::get("http://127.0.0.1:5801/healthz")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+        let report: Value = res.take_json().await.unwrap();
+        assert_eq!(report["status"], "down");
+        assert_eq!(report["checks"]["db"]["status"], "up");
+        assert_eq!(report["checks"]["slow"]["error"], "check timeout");
+
+        let health = HealthCheck::new().check("db", || async { Ok::<_, String>(()) });
+        let readiness = health.readiness();
+        let service = Service::new(health.router());
+        let res = TestClient::get("http://127.0.0.1:5801/healthz")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let mut res = TestClient::get("http://127.0.0.1:5801/readyz")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let report: Value = res.take_json().await.unwrap();
+        assert_eq!(report["ready"], true);
+
+        readiness.set_ready(false);
+        let mut res = TestClient::get("http://127.0.0.1:5801/readyz")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+        let report: Value = res.take_json().await.unwrap();
+        assert_eq!(report["status"], "down");
+        assert_eq!(report["ready"], false);
+        let res = TestClient::get("http://127.0.0.1:5801/healthz")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+    }
+}
@@ -0,0 +1,336 @@
+//! IP allow/deny list middleware.
+//!
+//! [`IpFilter`] rejects requests with `403 Forbidden` according to the client IP:
+//!
+//! 1. IPs matching a [`deny`](IpFilter::deny) range are rejected.
+//! 2. IPs matching an [`allow`](IpFilter::allow) range are accepted.
+//! 3. The [`IpLookup`] is asked, if any, e.g. a block list stored in Redis. Its answers are
+//!    cached for [`lookup_cache`](IpFilter::lookup_cache) TTL.
+//! 4. Otherwise, the request is rejected if any allow range is set, and accepted if not.
+//!
+//! The client IP is [`Request::client_ip`], which is resolved by the
+//! [`RealIp`](salvo_core::http::RealIp) of the service. Trusted proxies can also be set to the
+//! middleware by [`trusted_proxy`](IpFilter::trusted_proxy), then the client IP is taken from the
+//! forwarding headers sent by them.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::ip_filter::IpFilter;
+//!
+//! #[handler]
+//! async fn hello() -> &'static str {
+//!     "Hello World"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let filter = IpFilter::new()
+//!         .allow("10.0.0.0/8")
+//!         .allow("2001:db8::/32")
+//!         .deny("10.0.0.13")
+//!         .trusted_proxy("192.168.0.0/16");
+//!     let router = Router::new().hoop(filter).get(hello);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ipnet::IpNet;
+use salvo_core::http::{RealIp, Request, Response, StatusError};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+
+/// Dynamic lookup of the IPs which are not in the static allow and deny lists.
+#[async_trait]
+pub trait IpLookup: Send + Sync + 'static {
+    /// Returns `Some(true)` to accept the IP, `Some(false)` to reject it and `None` to let the
+    /// default rule decide.
+    async fn lookup(&self, ip: IpAddr) -> Option<bool>;
+}
+
+fn parse_net(net: &str) -> IpNet {
+    net.parse::<IpNet>()
+        .or_else(|_| net.parse::<IpAddr>().map(IpNet::from))
+        .unwrap_or_else(|_| panic!("invalid IP address or CIDR range: {net}"))
+}
+
+type LookupCache = HashMap<IpAddr, (Option<bool>, Instant)>;
+
+/// IP allow/deny list middleware.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct IpFilter {
+    allow: Vec<IpNet>,
+    deny: Vec<IpNet>,
+    real_ip: Option<RealIp>,
+    lookup: Option<Arc<dyn IpLookup>>,
+    cache_ttl: Duration,
+    cache_capacity: usize,
+    cache: Arc<Mutex<LookupCache>>,
+}
+impl Default for IpFilter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl IpFilter {
+    /// Create new `IpFilter` which accepts all requests.
+    pub fn new() -> Self {
+        Self {
+            allow: Vec::new(),
+            deny: Vec::new(),
+            real_ip: None,
+            lookup: None,
+            cache_ttl: Duration::from_secs(60),
+            cache_capacity: 10_000,
+            cache: Arc::default(),
+        }
+    }
+
+    /// Accept the IPs in the range, e.g. `10.0.0.0/8`, `2001:db8::/32` or a single IP.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `net` is not a valid IP address or CIDR range.
+    #[inline]
+    pub fn allow(mut self, net: &str) -> Self {
+        self.allow.push(parse_net(net));
+        self
+    }
+
+    /// Reject the IPs in the range, it takes precedence over [`allow`](Self::allow).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `net` is not a valid IP address or CIDR range.
+    #[inline]
+    pub fn deny(mut self, net: &str) -> Self {
+        self.deny.push(parse_net(net));
+        self
+    }
+
+    /// Trust the forwarding headers sent by the proxies in the range, see [`RealIp`].
+    ///
+    /// It is not needed if the [`RealIp`] of the service trusts the proxies already.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `net` is not a valid IP address or CIDR range.
+    #[inline]
+    pub fn trusted_proxy(mut self, net: &str) -> Self {
+        self.real_ip = Some(self.real_ip.take().unwrap_or_default().trusted_proxy(net));
+        self
+    }
+
+    /// Ask `lookup` for the IPs which are not in the static lists.
+    #[inline]
+    pub fn lookup(mut self, lookup: impl IpLookup) -> Self {
+        self.lookup = Some(Arc::new(lookup));
+        self
+    }
+
+    /// Cache the answers of the [`IpLookup`] for `ttl`, at most `capacity` IPs are cached.
+    /// Default is 60 seconds and 10000 IPs.
+    #[inline]
+    pub fn lookup_cache(mut self, ttl: Duration, capacity: usize) -> Self {
+        self.cache_ttl = ttl;
+        self.cache_capacity = capacity;
+        self
+    }
+
+    /// Returns the client IP of the request, `None` if the peer address is not an IP address,
+    /// e.g. unix socket.
+    ///
+    /// It is resolved with the trusted proxies of this middleware if any is set, otherwise it is
+    /// [`Request::client_ip`].
+    pub fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        match &self.real_ip {
+            Some(real_ip) => real_ip.resolve(req),
+            None => req.client_ip(),
+        }
+    }
+
+    async fn lookup_cached(&self, lookup: &dyn IpLookup, ip: IpAddr) -> Option<bool> {
+        let now = Instant::now();
+        let cached = self
+            .cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&ip)
+            .copied();
+        if let Some((allowed, expires)) = cached {
+            if expires > now {
+                return allowed;
+            }
+        }
+        let allowed = lookup.lookup(ip).await;
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if cache.len() >= self.cache_capacity {
+            cache.retain(|_, (_, expires)| *expires > now);
+            if cache.len() >= self.cache_capacity {
+                cache.clear();
+            }
+        }
+        if self.cache_capacity > 0 {
+            cache.insert(ip, (allowed, now + self.cache_ttl));
+        }
+        allowed
+    }
+
+    /// Returns whether the IP is accepted.
+    pub async fn is_allowed(&self, ip: IpAddr) -> bool {
+        let ip = ip.to_canonical();
+        if self.deny.iter().any(|net| net.contains(&ip)) {
+            return false;
+        }
+        if self.allow.iter().any(|net| net.contains(&ip)) {
+            return true;
+        }
+        if let Some(lookup) = &self.lookup {
+            if let Some(allowed) = self.lookup_cached(&**lookup, ip).await {
+                return allowed;
+            }
+        }
+        self.allow.is_empty()
+    }
+}
+impl Debug for IpFilter {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("IpFilter")
+            .field("allow", &self.allow)
+            .field("deny", &self.deny)
+            .field("real_ip", &self.real_ip)
+            .field("lookup", &self.lookup.is_some())
+            .field("cache_ttl", &self.cache_ttl)
+            .field("cache_capacity", &self.cache_capacity)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Handler for IpFilter {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        _depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let allowed = match self.client_ip(req) {
+            Some(ip) => self.is_allowed(ip).await,
+            None => self.allow.is_empty(),
+        };
+        if !allowed {
+            tracing::debug!(remote_addr = %req.remote_addr(), "ip is not allowed");
+            res.render(StatusError::forbidden());
+            ctrl.skip_rest();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use salvo_core::conn::SocketAddr;
+    use salvo_core::prelude::*;
+    use salvo_core::test::TestClient;
+
+    use super::*;
+
+    fn request(peer: &str, headers: &[(&'static str, &'static str)]) -> Request {
+        let mut builder = TestClient::get("http://127.0.0.1:5801/");
+        for (name, value) in headers {
+            builder = builder.add_header(*name, *value, false);
+        }
+        let mut req = builder.build();
+        *req.remote_addr_mut() = SocketAddr::from(peer.parse::<std::net::SocketAddr>().unwrap());
+        req
+    }
+
+    #[tokio::test]
+    async fn test_allow_deny() {
+        let filter = IpFilter::new()
+            .allow("10.0.0.0/8")
+            .allow("2001:db8::/32")
+            .deny("10.0.0.13");
+        assert!(filter.is_allowed("10.1.2.3".parse().unwrap()).await);
+        assert!(!filter.is_allowed("10.0.0.13".parse().unwrap()).await);
+        assert!(!filter.is_allowed("11.0.0.1".parse().unwrap()).await);
+        assert!(filter.is_allowed("2001:db8::1".parse().unwrap()).await);
+        assert!(filter.is_allowed("::ffff:10.0.0.1".parse().unwrap()).await);
+
+        #[handler]
+        async fn ok() -> &'static str {
+            "ok"
+        }
+        let service = Service::new(Router::new().hoop(filter).goal(ok));
+        let res = service.handle(request("11.0.0.1:1234", &[])).await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+        let res = service.handle(request("10.0.0.1:1234", &[])).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let filter = IpFilter::new().trusted_proxy("192.168.0.0/16");
+        let ip = |peer: &str, headers: &[(&'static str, &'static str)]| {
+            filter
+                .client_ip(&request(peer, headers))
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            ip("1.2.3.4:80", &[("x-forwarded-for", "5.6.7.8")]),
+            "1.2.3.4"
+        );
+        assert_eq!(
+            ip(
+                "192.168.0.1:80",
+                &[("x-forwarded-for", "9.9.9.9, 5.6.7.8, 192.168.0.2")]
+            ),
+            "5.6.7.8"
+        );
+
+        let mut req = request("192.168.0.1:80", &[("x-forwarded-for", "5.6.7.8")]);
+        assert_eq!(IpFilter::new().client_ip(&req), "192.168.0.1".parse().ok());
+        req.set_client_ip("5.6.7.8".parse::<IpAddr>().unwrap());
+        assert_eq!(IpFilter::new().client_ip(&req), "5.6.7.8".parse().ok());
+    }
+
+    struct BlockList(AtomicUsize);
+    #[async_trait]
+    impl IpLookup for BlockList {
+        async fn lookup(&self, ip: IpAddr) -> Option<bool> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            (ip == IpAddr::from([6, 6, 6, 6])).then_some(false)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_cache() {
+        let lookup = Arc::new(BlockList(AtomicUsize::new(0)));
+        struct Shared(Arc<BlockList>);
+        #[async_trait]
+        impl IpLookup for Shared {
+            async fn lookup(&self, ip: IpAddr) -> Option<bool> {
+                self.0.lookup(ip).await
+            }
+        }
+        let filter = IpFilter::new().lookup(Shared(lookup.clone()));
+        assert!(!filter.is_allowed(IpAddr::from([6, 6, 6, 6])).await);
+        assert!(!filter.is_allowed(IpAddr::from([6, 6, 6, 6])).await);
+        assert!(filter.is_allowed(IpAddr::from([1, 1, 1, 1])).await);
+        assert_eq!(lookup.0.load(Ordering::SeqCst), 2);
+    }
+}
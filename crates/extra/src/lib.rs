@@ -9,19 +9,31 @@
 //!
 //! | Feature | Description |
 //! | --- | --- |
+//! | [`access-log`](access_log) | Middleware for structured access logging |
 //! | [`affix-state`](affix_state) | Middleware for adding prefix and suffix to the request path |
 //! | [`basic-auth`](basic_auth) | Middleware for basic authentication |
 //! | [`caching-headers`](caching_headers) | Middleware for setting caching headers |
 //! | [`catch-panic`](catch_panic) | Middleware for catching panics |
+//! | [`circuit-breaker`](circuit_breaker) | Circuit breaker middleware |
 //! | [`concurrency-limiter`](concurrency_limiter) | Middleware for limiting concurrency |
 //! | [`force-https`](force_https) | Middleware for forcing HTTPS |
+//! | [`health`] | Health check and readiness endpoints |
+//! | [`ip-filter`](ip_filter) | IP allow/deny list middleware |
 //! | [`logging`] | Middleware for logging requests and responses |
+//! | [`maintenance`] | Maintenance mode middleware with bypass rules |
+//! | [`metrics`] | Prometheus metrics middleware and exposition endpoint |
 //! | [`request-id`](request_id) | Middleware for setting a request ID |
+//! | [`retry`] | Retry middleware for idempotent requests |
+//! | [`secure-headers`](secure_headers) | Middleware for setting security headers |
+//! | [`singleflight`] | Middleware for coalescing concurrent identical requests |
 //! | [`size-limiter`](size_limiter) | Middleware for limiting request size |
+//! | [`slow-request`](slow_request) | Slow request detection and `Server-Timing` header |
 //! | [`sse`] | Server-Sent Events (SSE) middleware |
 //! | [`timeout`] | Middleware for setting a timeout |
+//! | [`token-auth`](token_auth) | Middleware for opaque token and API key authentication |
 //! | [`trailing-slash`](trailing_slash) | Middleware for handling trailing slashes |
 //! | [`tower-compat`](tower_compat) | Adapters for `tower::Layer` and `tower::Service` |
+//! | [`webhook`] | Webhook signature verification middleware |
 //! | [`websocket`] | WebSocket implementation |
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
 #![doc(html_logo_url = "https://salvo.rs/images/logo.svg")]
@@ -30,6 +42,11 @@
 #[macro_use]
 mod cfg;
 
+cfg_feature! {
+    #![feature = "access-log"]
+    pub mod access_log;
+}
+
 cfg_feature! {
     #![feature = "basic-auth"]
     pub mod basic_auth;
@@ -48,11 +65,19 @@ cfg_feature! {
     #![feature = "catch-panic"]
     pub mod catch_panic;
 }
+cfg_feature! {
+    #![feature = "circuit-breaker"]
+    pub mod circuit_breaker;
+}
 
 cfg_feature! {
     #![feature = "logging"]
     pub mod logging;
 }
+cfg_feature! {
+    #![feature = "metrics"]
+    pub mod metrics;
+}
 cfg_feature! {
     #![feature = "sse"]
     pub mod sse;
@@ -85,6 +110,42 @@ cfg_feature! {
     #![feature = "request-id"]
     pub mod request_id;
 }
+cfg_feature! {
+    #![feature = "ip-filter"]
+    pub mod ip_filter;
+}
+cfg_feature! {
+    #![feature = "maintenance"]
+    pub mod maintenance;
+}
+cfg_feature! {
+    #![feature = "retry"]
+    pub mod retry;
+}
+cfg_feature! {
+    #![feature = "secure-headers"]
+    pub mod secure_headers;
+}
+cfg_feature! {
+    #![feature = "token-auth"]
+    pub mod token_auth;
+}
+cfg_feature! {
+    #![feature = "webhook"]
+    pub mod webhook;
+}
+cfg_feature! {
+    #![feature = "singleflight"]
+    pub mod singleflight;
+}
+cfg_feature! {
+    #![feature = "slow-request"]
+    pub mod slow_request;
+}
+cfg_feature! {
+    #![feature = "health"]
+    pub mod health;
+}
 cfg_feature! {
     #![feature ="tower-compat"]
     pub mod tower_compat;
@@ -29,6 +29,10 @@ use salvo_core::http::{Request, ResBody, Response, StatusCode};
 use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
 
 /// A simple logger middleware.
+///
+/// If the `request-id` feature is enabled, the id set by [`RequestId`](crate::request_id::RequestId) is
+/// recorded in the span. Add `RequestId` before `Logger` so that the events logged by the handlers carry
+/// the id, otherwise it is only recorded when the response is logged.
 #[derive(Default, Debug)]
 pub struct Logger {}
 impl Logger {
@@ -46,15 +50,38 @@ impl Handler for Logger {
             Level::INFO,
             "Request",
             remote_addr = %req.remote_addr().to_string(),
+            client_ip = ?req.client_ip(),
             version = ?req.version(),
             method = %req.method(),
             path = %req.uri(),
+            route = tracing::field::Empty,
+            request_id = tracing::field::Empty,
         );
+        // Unmatched requests are not recorded under any route, so they are not mixed with the root route.
+        #[cfg(feature = "matched-path")]
+        if let Some(route) = req.matched_route() {
+            span.record("route", tracing::field::display(route));
+        }
+
+        #[cfg(feature = "request-id")]
+        let recorded = match crate::request_id::RequestIdDepotExt::request_id(depot) {
+            Some(id) => {
+                span.record("request_id", tracing::field::display(id));
+                true
+            }
+            None => false,
+        };
 
         async move {
             let now = Instant::now();
             ctrl.call_next(req, depot, res).await;
             let duration = now.elapsed();
+            #[cfg(feature = "request-id")]
+            if !recorded {
+                if let Some(id) = crate::request_id::RequestIdDepotExt::request_id(depot) {
+                    tracing::Span::current().record("request_id", tracing::field::display(id));
+                }
+            }
 
             let status = res.status_code.unwrap_or(match &res.body {
                 ResBody::None => StatusCode::NOT_FOUND,
@@ -100,4 +127,65 @@ mod tests {
             .unwrap();
         assert!(logs_contain("duration"));
     }
+
+    #[cfg(feature = "request-id")]
+    #[tokio::test]
+    #[traced_test]
+    async fn test_log_request_id() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+
+        let router = Router::new()
+            .hoop(crate::request_id::RequestId::new().overwrite(false))
+            .hoop(Logger::new())
+            .push(Router::with_path("hello").get(hello));
+
+        TestClient::get("http://127.0.0.1:5801/hello")
+            .add_header("x-request-id", "abc123", true)
+            .send(router)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert!(logs_contain("request_id=abc123"));
+
+        // Logger added before RequestId records the id after the handlers.
+        let router = Router::new()
+            .hoop(Logger::new())
+            .hoop(crate::request_id::RequestId::new().overwrite(false))
+            .push(Router::with_path("hello").get(hello));
+
+        TestClient::get("http://127.0.0.1:5801/hello")
+            .add_header("x-request-id", "def456", true)
+            .send(router)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert!(logs_contain("request_id=def456"));
+    }
+
+    #[cfg(feature = "matched-path")]
+    #[tokio::test]
+    #[traced_test]
+    async fn test_log_route() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+
+        let router = Router::new()
+            .hoop(Logger::new())
+            .push(Router::with_path("hello/{name}").get(hello));
+
+        TestClient::get("http://127.0.0.1:5801/hello/world")
+            .send(router)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert!(logs_contain("route=/hello/{name}"));
+    }
 }
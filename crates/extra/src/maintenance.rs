@@ -0,0 +1,346 @@
+//! Maintenance mode middleware.
+//!
+//! While the maintenance mode is enabled, [`Maintenance`] responds `503 Service Unavailable` with
+//! a `Retry-After` header and a templated body, except for the requests of operators which match
+//! the bypass rules: client IP ranges, path prefixes, header values or a custom [`Skipper`].
+//!
+//! The mode is toggled at runtime through the [`MaintenanceHandle`] shared by the middleware, for
+//! example from an admin endpoint or a signal handler, without restarting the server.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::maintenance::{Maintenance, MaintenanceHandle};
+//!
+//! #[handler]
+//! async fn hello() -> &'static str {
+//!     "Hello World"
+//! }
+//!
+//! struct Toggle(MaintenanceHandle);
+//! #[handler]
+//! impl Toggle {
+//!     async fn handle(&self) -> &'static str {
+//!         if self.0.is_enabled() {
+//!             self.0.disable();
+//!             "maintenance disabled"
+//!         } else {
+//!             self.0.enable();
+//!             "maintenance enabled"
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let maintenance = Maintenance::new()
+//!         .retry_after(Duration::from_secs(600))
+//!         .allow_ip("10.0.0.0/8")
+//!         .allow_path("/admin");
+//!     let toggle = Toggle(maintenance.handle());
+//!     let router = Router::new()
+//!         .hoop(maintenance)
+//!         .get(hello)
+//!         .push(Router::with_path("admin/maintenance").post(toggle));
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use ipnet::IpNet;
+use salvo_core::handler::Skipper;
+use salvo_core::http::header::RETRY_AFTER;
+use salvo_core::http::{HeaderName, HeaderValue, Request, Response, StatusCode};
+use salvo_core::writing::Text;
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+
+/// The default body template.
+pub const DEFAULT_TEMPLATE: &str = "<!DOCTYPE html>
+<html>
+<head><meta charset=\"utf-8\"><title>Service Unavailable</title></head>
+<body>
+<h1>Service Unavailable</h1>
+<p>The service is under maintenance, please retry in {retry_after} seconds.</p>
+</body>
+</html>
+";
+
+#[derive(Debug)]
+struct State {
+    enabled: AtomicBool,
+    retry_after: AtomicU64,
+}
+
+/// Handle to toggle the maintenance mode of [`Maintenance`] at runtime.
+#[derive(Clone, Debug)]
+pub struct MaintenanceHandle(Arc<State>);
+impl MaintenanceHandle {
+    /// Enable the maintenance mode.
+    #[inline]
+    pub fn enable(&self) {
+        self.set_enabled(true);
+    }
+    /// Disable the maintenance mode.
+    #[inline]
+    pub fn disable(&self) {
+        self.set_enabled(false);
+    }
+    /// Enable or disable the maintenance mode.
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        self.0.enabled.store(enabled, Ordering::Relaxed);
+    }
+    /// Returns `true` if the maintenance mode is enabled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.0.enabled.load(Ordering::Relaxed)
+    }
+    /// Set the delay sent in the `Retry-After` header, rounded up to seconds.
+    #[inline]
+    pub fn set_retry_after(&self, retry_after: Duration) {
+        let seconds = retry_after.as_secs() + (retry_after.subsec_nanos() > 0) as u64;
+        self.0.retry_after.store(seconds, Ordering::Relaxed);
+    }
+    /// Returns the delay sent in the `Retry-After` header.
+    #[inline]
+    pub fn retry_after(&self) -> Duration {
+        Duration::from_secs(self.0.retry_after.load(Ordering::Relaxed))
+    }
+}
+
+/// Maintenance mode middleware.
+///
+/// View [module level documentation](index.html) for more details.
+pub struct Maintenance {
+    handle: MaintenanceHandle,
+    template: String,
+    allowed_ips: Vec<IpNet>,
+    allowed_paths: Vec<String>,
+    allowed_headers: Vec<(HeaderName, HeaderValue)>,
+    skipper: Option<Box<dyn Skipper>>,
+}
+impl Default for Maintenance {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Maintenance {
+    /// Create new `Maintenance` which is disabled, with `Retry-After` of 300 seconds and
+    /// [`DEFAULT_TEMPLATE`].
+    pub fn new() -> Self {
+        Self {
+            handle: MaintenanceHandle(Arc::new(State {
+                enabled: AtomicBool::new(false),
+                retry_after: AtomicU64::new(300),
+            })),
+            template: DEFAULT_TEMPLATE.into(),
+            allowed_ips: Vec::new(),
+            allowed_paths: Vec::new(),
+            allowed_headers: Vec::new(),
+            skipper: None,
+        }
+    }
+
+    /// Get the handle to toggle the maintenance mode at runtime.
+    #[inline]
+    pub fn handle(&self) -> MaintenanceHandle {
+        self.handle.clone()
+    }
+
+    /// Set whether the maintenance mode is enabled initially.
+    #[inline]
+    pub fn enabled(self, enabled: bool) -> Self {
+        self.handle.set_enabled(enabled);
+        self
+    }
+
+    /// Set the delay sent in the `Retry-After` header, rounded up to seconds.
+    #[inline]
+    pub fn retry_after(self, retry_after: Duration) -> Self {
+        self.handle.set_retry_after(retry_after);
+        self
+    }
+
+    /// Set the body template, `{retry_after}` is replaced with the delay in seconds and `{path}`
+    /// with the request path. It is sent as HTML if it starts with `<`, otherwise as plain text.
+    #[inline]
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Bypass the requests from the client IPs in the range, e.g. `10.0.0.0/8` or a single IP.
+    /// The client IP is [`Request::client_ip`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `net` is not a valid IP address or CIDR range.
+    pub fn allow_ip(mut self, net: &str) -> Self {
+        let net = net
+            .parse::<IpNet>()
+            .or_else(|_| net.parse::<std::net::IpAddr>().map(IpNet::from))
+            .unwrap_or_else(|_| panic!("invalid IP address or CIDR range: {net}"));
+        self.allowed_ips.push(net);
+        self
+    }
+
+    /// Bypass the requests whose path starts with `prefix`, e.g. `/admin` or `/health`.
+    #[inline]
+    pub fn allow_path(mut self, prefix: impl Into<String>) -> Self {
+        self.allowed_paths.push(prefix.into());
+        self
+    }
+
+    /// Bypass the requests with the header value, e.g. a secret token of operators.
+    #[inline]
+    pub fn allow_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.allowed_headers.push((name, value));
+        self
+    }
+
+    /// Uses a closure to determine if a request bypasses the maintenance mode.
+    #[inline]
+    pub fn skipper(mut self, skipper: impl Skipper) -> Self {
+        self.skipper = Some(Box::new(skipper));
+        self
+    }
+
+    fn bypassed(&self, req: &mut Request, depot: &Depot) -> bool {
+        if let Some(ip) = req.client_ip() {
+            let ip = ip.to_canonical();
+            if self.allowed_ips.iter().any(|net| net.contains(&ip)) {
+                return true;
+            }
+        }
+        let path = req.uri().path();
+        if self.allowed_paths.iter().any(|prefix| {
+            path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+            })
+        }) {
+            return true;
+        }
+        if self
+            .allowed_headers
+            .iter()
+            .any(|(name, value)| req.headers().get_all(name).iter().any(|v| v == value))
+        {
+            return true;
+        }
+        self.skipper
+            .as_ref()
+            .is_some_and(|skipper| skipper.skipped(req, depot))
+    }
+}
+impl Debug for Maintenance {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Maintenance")
+            .field("handle", &self.handle)
+            .field("allowed_ips", &self.allowed_ips)
+            .field("allowed_paths", &self.allowed_paths)
+            .field("allowed_headers", &self.allowed_headers)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Handler for Maintenance {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        if !self.handle.is_enabled() || self.bypassed(req, depot) {
+            return;
+        }
+        let retry_after = self.handle.0.retry_after.load(Ordering::Relaxed);
+        let body = self
+            .template
+            .replace("{retry_after}", &retry_after.to_string())
+            .replace("{path}", req.uri().path());
+        res.status_code(StatusCode::SERVICE_UNAVAILABLE);
+        res.headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        if body.trim_start().starts_with('<') {
+            res.render(Text::Html(body));
+        } else {
+            res.render(Text::Plain(body));
+        }
+        ctrl.skip_rest();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn hello() -> &'static str {
+        "hello"
+    }
+
+    #[tokio::test]
+    async fn test_maintenance() {
+        let maintenance = Maintenance::new()
+            .retry_after(Duration::from_secs(60))
+            .template("down for {retry_after}s: {path}")
+            .allow_path("/admin")
+            .allow_header(
+                HeaderName::from_static("x-operator"),
+                HeaderValue::from_static("secret"),
+            );
+        let handle = maintenance.handle();
+        let service = Service::new(
+            Router::new()
+                .hoop(maintenance)
+                .push(Router::with_path("{**rest}").get(hello)),
+        );
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "hello");
+
+        handle.enable();
+        let mut res = TestClient::get("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(res.headers().get(RETRY_AFTER).unwrap(), "60");
+        assert_eq!(res.take_string().await.unwrap(), "down for 60s: /hello");
+
+        let res = TestClient::get("http://127.0.0.1:5801/admin/users")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let res = TestClient::get("http://127.0.0.1:5801/administrator")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+        let res = TestClient::get("http://127.0.0.1:5801/hello")
+            .add_header("x-operator", "secret", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+
+        handle.disable();
+        let res = TestClient::get("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+    }
+}
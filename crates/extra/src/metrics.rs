@@ -0,0 +1,671 @@
+//! Prometheus metrics middleware and exposition endpoint.
+//!
+//! [`Metrics`] records these metrics for every request, labeled by `method`, `route` and
+//! `status`:
+//!
+//! - `http_requests_total`: counter of the handled requests.
+//! - `http_request_duration_seconds`: histogram of the request durations.
+//! - `http_response_size_bytes`: histogram of the response body sizes, the responses with
+//!   unknown size, e.g. streaming body, are not observed.
+//! - `http_requests_in_flight`: gauge of the requests being handled, labeled by `method` only.
+//!
+//! The `route` label is the matched route pattern, e.g. `/users/{id}`, which requires the
+//! `matched-path` feature, otherwise it is empty. The requests which no router is matched, e.g. 404
+//! scans, are labeled by [`UNMATCHED_ROUTE`]. The raw path is never used as label to keep the
+//! cardinality bounded.
+//!
+//! The metrics are stored in a [`Registry`], which is rendered in the Prometheus text
+//! exposition format by the handler created with [`Metrics::exporter`]. Applications can
+//! register their own [`Counter`], [`Gauge`], [`Histogram`] or any [`Collector`] into the same
+//! registry.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::metrics::{Counter, Metrics};
+//!
+//! #[handler]
+//! async fn hello() -> &'static str {
+//!     "Hello World"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let metrics = Metrics::new();
+//!     let greetings = Counter::new("greetings_total", "Number of greetings.", &["lang"]);
+//!     metrics.registry().register(greetings.clone());
+//!     greetings.inc(&["en"]);
+//!
+//!     let router = Router::new()
+//!         .push(Router::with_path("metrics").get(metrics.exporter()))
+//!         .push(Router::new().hoop(metrics).get(hello));
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::BTreeMap;
+use std::fmt::{self, Debug, Formatter, Write};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+
+use salvo_core::http::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use salvo_core::http::{HeaderValue, Request, ResBody, Response, StatusCode};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+
+/// The content type of the Prometheus text exposition format.
+pub const TEXT_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The `route` label of the requests which no router is matched.
+pub const UNMATCHED_ROUTE: &str = "<unmatched>";
+
+/// The default buckets of the request duration histogram, in seconds.
+pub const DEFAULT_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// The default buckets of the response size histogram, in bytes.
+pub const DEFAULT_SIZE_BUCKETS: &[f64] = &[100.0, 1000.0, 10000.0, 100000.0, 1000000.0, 10000000.0];
+
+/// A metric which can be rendered in the Prometheus text exposition format.
+pub trait Collector: Send + Sync + 'static {
+    /// Write the `# HELP`, `# TYPE` and sample lines of the metric to `out`.
+    fn encode(&self, out: &mut String);
+}
+
+/// A registry of [`Collector`]s, cloned registries share the same collectors.
+#[derive(Clone, Default)]
+pub struct Registry {
+    collectors: Arc<RwLock<Vec<Arc<dyn Collector>>>>,
+}
+impl Registry {
+    /// Create new empty `Registry`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the collector, it is rendered after the previously registered collectors.
+    pub fn register(&self, collector: impl Collector) {
+        self.collectors
+            .write()
+            .expect("metrics registry lock poisoned")
+            .push(Arc::new(collector));
+    }
+
+    /// Render all collectors in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for collector in self
+            .collectors
+            .read()
+            .expect("metrics registry lock poisoned")
+            .iter()
+        {
+            collector.encode(&mut out);
+        }
+        out
+    }
+}
+impl Debug for Registry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field(
+                "collectors",
+                &self.collectors.read().map(|c| c.len()).unwrap_or_default(),
+            )
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+struct Desc {
+    name: String,
+    help: String,
+    label_names: Vec<String>,
+}
+impl Desc {
+    fn new(name: &str, help: &str, label_names: &[&str]) -> Self {
+        Self {
+            name: name.to_owned(),
+            help: help.to_owned(),
+            label_names: label_names.iter().map(|l| (*l).to_owned()).collect(),
+        }
+    }
+
+    fn key(&self, label_values: &[&str]) -> Vec<String> {
+        assert_eq!(
+            label_values.len(),
+            self.label_names.len(),
+            "metric `{}` expects {} label values",
+            self.name,
+            self.label_names.len()
+        );
+        label_values.iter().map(|v| (*v).to_owned()).collect()
+    }
+
+    fn write_header(&self, out: &mut String, kind: &str) {
+        let help = self.help.replace('\\', "\\\\").replace('\n', "\\n");
+        let _ = writeln!(out, "# HELP {} {help}", self.name);
+        let _ = writeln!(out, "# TYPE {} {kind}", self.name);
+    }
+
+    fn write_sample(
+        &self,
+        out: &mut String,
+        suffix: &str,
+        label_values: &[String],
+        extra: Option<(&str, &str)>,
+        value: f64,
+    ) {
+        out.push_str(&self.name);
+        out.push_str(suffix);
+        let labels = self
+            .label_names
+            .iter()
+            .map(|n| n.as_str())
+            .zip(label_values.iter().map(|v| v.as_str()))
+            .chain(extra);
+        let mut first = true;
+        for (name, value) in labels {
+            out.push(if first { '{' } else { ',' });
+            first = false;
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            let _ = write!(out, "{name}=\"{value}\"");
+        }
+        if !first {
+            out.push('}');
+        }
+        out.push(' ');
+        write_value(out, value);
+        out.push('\n');
+    }
+}
+
+fn write_value(out: &mut String, value: f64) {
+    if value == f64::INFINITY {
+        out.push_str("+Inf");
+    } else if value == f64::NEG_INFINITY {
+        out.push_str("-Inf");
+    } else {
+        let _ = write!(out, "{value}");
+    }
+}
+
+/// A counter which only goes up, with a value for every combination of label values.
+#[derive(Clone, Debug)]
+pub struct Counter {
+    desc: Arc<Desc>,
+    values: Arc<Mutex<BTreeMap<Vec<String>, f64>>>,
+}
+impl Counter {
+    /// Create new `Counter`.
+    pub fn new(name: &str, help: &str, label_names: &[&str]) -> Self {
+        Self {
+            desc: Arc::new(Desc::new(name, help, label_names)),
+            values: Arc::default(),
+        }
+    }
+
+    /// Increase the counter of the label values by 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of label values does not match the label names.
+    #[inline]
+    pub fn inc(&self, label_values: &[&str]) {
+        self.inc_by(label_values, 1.0);
+    }
+
+    /// Increase the counter of the label values by `value`, negative values are ignored.
+    pub fn inc_by(&self, label_values: &[&str], value: f64) {
+        if value < 0.0 {
+            return;
+        }
+        let key = self.desc.key(label_values);
+        *self
+            .values
+            .lock()
+            .expect("metric lock poisoned")
+            .entry(key)
+            .or_default() += value;
+    }
+
+    /// Get the value of the label values.
+    pub fn get(&self, label_values: &[&str]) -> f64 {
+        let key = self.desc.key(label_values);
+        self.values
+            .lock()
+            .expect("metric lock poisoned")
+            .get(&key)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+impl Collector for Counter {
+    fn encode(&self, out: &mut String) {
+        self.desc.write_header(out, "counter");
+        for (labels, value) in self.values.lock().expect("metric lock poisoned").iter() {
+            self.desc.write_sample(out, "", labels, None, *value);
+        }
+    }
+}
+
+/// A gauge which can go up and down, with a value for every combination of label values.
+#[derive(Clone, Debug)]
+pub struct Gauge {
+    desc: Arc<Desc>,
+    values: Arc<Mutex<BTreeMap<Vec<String>, f64>>>,
+}
+impl Gauge {
+    /// Create new `Gauge`.
+    pub fn new(name: &str, help: &str, label_names: &[&str]) -> Self {
+        Self {
+            desc: Arc::new(Desc::new(name, help, label_names)),
+            values: Arc::default(),
+        }
+    }
+
+    /// Set the gauge of the label values.
+    pub fn set(&self, label_values: &[&str], value: f64) {
+        let key = self.desc.key(label_values);
+        self.values
+            .lock()
+            .expect("metric lock poisoned")
+            .insert(key, value);
+    }
+
+    /// Add `value` to the gauge of the label values, it can be negative.
+    pub fn add(&self, label_values: &[&str], value: f64) {
+        let key = self.desc.key(label_values);
+        *self
+            .values
+            .lock()
+            .expect("metric lock poisoned")
+            .entry(key)
+            .or_default() += value;
+    }
+
+    /// Increase the gauge of the label values by 1.
+    #[inline]
+    pub fn inc(&self, label_values: &[&str]) {
+        self.add(label_values, 1.0);
+    }
+
+    /// Decrease the gauge of the label values by 1.
+    #[inline]
+    pub fn dec(&self, label_values: &[&str]) {
+        self.add(label_values, -1.0);
+    }
+
+    /// Get the value of the label values.
+    pub fn get(&self, label_values: &[&str]) -> f64 {
+        let key = self.desc.key(label_values);
+        self.values
+            .lock()
+            .expect("metric lock poisoned")
+            .get(&key)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+impl Collector for Gauge {
+    fn encode(&self, out: &mut String) {
+        self.desc.write_header(out, "gauge");
+        for (labels, value) in self.values.lock().expect("metric lock poisoned").iter() {
+            self.desc.write_sample(out, "", labels, None, *value);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct HistogramState {
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+/// A histogram which counts the observed values in buckets, with a histogram for every
+/// combination of label values.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    desc: Arc<Desc>,
+    bounds: Arc<[f64]>,
+    values: Arc<Mutex<BTreeMap<Vec<String>, HistogramState>>>,
+}
+impl Histogram {
+    /// Create new `Histogram` with the upper bounds of the buckets, the `+Inf` bucket is always
+    /// added.
+    pub fn new(name: &str, help: &str, label_names: &[&str], buckets: &[f64]) -> Self {
+        let mut bounds: Vec<f64> = buckets.iter().copied().filter(|b| b.is_finite()).collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+        Self {
+            desc: Arc::new(Desc::new(name, help, label_names)),
+            bounds: bounds.into(),
+            values: Arc::default(),
+        }
+    }
+
+    /// Observe the value for the label values.
+    pub fn observe(&self, label_values: &[&str], value: f64) {
+        let key = self.desc.key(label_values);
+        let mut values = self.values.lock().expect("metric lock poisoned");
+        let state = values.entry(key).or_insert_with(|| HistogramState {
+            buckets: vec![0; self.bounds.len()],
+            sum: 0.0,
+            count: 0,
+        });
+        if let Some(index) = self.bounds.iter().position(|bound| value <= *bound) {
+            state.buckets[index] += 1;
+        }
+        state.sum += value;
+        state.count += 1;
+    }
+
+    /// Get the number of observed values of the label values.
+    pub fn count(&self, label_values: &[&str]) -> u64 {
+        let key = self.desc.key(label_values);
+        self.values
+            .lock()
+            .expect("metric lock poisoned")
+            .get(&key)
+            .map(|state| state.count)
+            .unwrap_or_default()
+    }
+}
+impl Collector for Histogram {
+    fn encode(&self, out: &mut String) {
+        self.desc.write_header(out, "histogram");
+        for (labels, state) in self.values.lock().expect("metric lock poisoned").iter() {
+            let mut cumulative = 0;
+            for (bound, count) in self.bounds.iter().zip(&state.buckets) {
+                cumulative += count;
+                let mut le = String::new();
+                write_value(&mut le, *bound);
+                let le = Some(("le", le.as_str()));
+                self.desc
+                    .write_sample(out, "_bucket", labels, le, cumulative as f64);
+            }
+            let inf = Some(("le", "+Inf"));
+            self.desc
+                .write_sample(out, "_bucket", labels, inf, state.count as f64);
+            self.desc.write_sample(out, "_sum", labels, None, state.sum);
+            self.desc
+                .write_sample(out, "_count", labels, None, state.count as f64);
+        }
+    }
+}
+
+/// Middleware which records the HTTP metrics of the requests.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    registry: Registry,
+    requests: Counter,
+    duration: Histogram,
+    response_size: Histogram,
+    in_flight: Gauge,
+}
+impl Default for Metrics {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Metrics {
+    /// Create new `Metrics` middleware with a new [`Registry`].
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_registry(Registry::new())
+    }
+
+    /// Create new `Metrics` middleware which registers the HTTP metrics into `registry`.
+    pub fn with_registry(registry: Registry) -> Self {
+        Self::with_options(registry, "", DEFAULT_DURATION_BUCKETS, DEFAULT_SIZE_BUCKETS)
+    }
+
+    /// Create new `Metrics` middleware with the prefix of the metric names, e.g. `myapp_`, and
+    /// the buckets of the duration and response size histograms.
+    pub fn with_options(
+        registry: Registry,
+        prefix: &str,
+        duration_buckets: &[f64],
+        size_buckets: &[f64],
+    ) -> Self {
+        let labels = &["method", "route", "status"];
+        let requests = Counter::new(
+            &format!("{prefix}http_requests_total"),
+            "Total number of HTTP requests.",
+            labels,
+        );
+        let duration = Histogram::new(
+            &format!("{prefix}http_request_duration_seconds"),
+            "HTTP request duration in seconds.",
+            labels,
+            duration_buckets,
+        );
+        let response_size = Histogram::new(
+            &format!("{prefix}http_response_size_bytes"),
+            "HTTP response body size in bytes.",
+            labels,
+            size_buckets,
+        );
+        let in_flight = Gauge::new(
+            &format!("{prefix}http_requests_in_flight"),
+            "Number of HTTP requests being handled.",
+            &["method"],
+        );
+        registry.register(requests.clone());
+        registry.register(duration.clone());
+        registry.register(response_size.clone());
+        registry.register(in_flight.clone());
+        Self {
+            registry,
+            requests,
+            duration,
+            response_size,
+            in_flight,
+        }
+    }
+
+    /// Returns the registry of the metrics, it can be used to register application metrics.
+    #[inline]
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Create a handler which serves the metrics of the registry in the Prometheus text
+    /// exposition format.
+    #[inline]
+    pub fn exporter(&self) -> MetricsExporter {
+        MetricsExporter::new(self.registry.clone())
+    }
+}
+
+struct InFlightGuard<'a> {
+    gauge: &'a Gauge,
+    method: &'a str,
+}
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.gauge.dec(&[self.method]);
+    }
+}
+
+#[async_trait]
+impl Handler for Metrics {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let method = req.method().as_str().to_owned();
+        self.in_flight.inc(&[&method]);
+        let guard = InFlightGuard {
+            gauge: &self.in_flight,
+            method: &method,
+        };
+        let started = Instant::now();
+        ctrl.call_next(req, depot, res).await;
+        let duration = started.elapsed();
+        drop(guard);
+
+        let status = res.status_code.unwrap_or(match &res.body {
+            ResBody::None => StatusCode::NOT_FOUND,
+            ResBody::Error(e) => e.code,
+            _ => StatusCode::OK,
+        });
+        #[cfg(feature = "matched-path")]
+        let route = req
+            .matched_route()
+            .unwrap_or_else(|| UNMATCHED_ROUTE.to_owned());
+        #[cfg(not(feature = "matched-path"))]
+        let route = String::new();
+        let labels = [method.as_str(), route.as_str(), status.as_str()];
+        self.requests.inc(&labels);
+        self.duration.observe(&labels, duration.as_secs_f64());
+        let size = res.body.size().or_else(|| {
+            res.headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+        });
+        if let Some(size) = size {
+            self.response_size.observe(&labels, size as f64);
+        }
+    }
+}
+
+/// Handler which serves the metrics of a [`Registry`] in the Prometheus text exposition format.
+#[derive(Clone, Debug)]
+pub struct MetricsExporter {
+    registry: Registry,
+}
+impl MetricsExporter {
+    /// Create new `MetricsExporter`.
+    #[inline]
+    pub fn new(registry: Registry) -> Self {
+        Self { registry }
+    }
+}
+#[async_trait]
+impl Handler for MetricsExporter {
+    async fn handle(
+        &self,
+        _req: &mut Request,
+        _depot: &mut Depot,
+        res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        res.headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(TEXT_CONTENT_TYPE));
+        let _ = res.write_body(self.registry.render());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let registry = Registry::new();
+        let counter = Counter::new("jobs_total", "Jobs.", &["kind"]);
+        let gauge = Gauge::new("queue_size", "Queue size.", &[]);
+        let histogram = Histogram::new("job_seconds", "Job duration.", &[], &[1.0, 0.5]);
+        registry.register(counter.clone());
+        registry.register(gauge.clone());
+        registry.register(histogram.clone());
+        counter.inc(&["a\"b"]);
+        counter.inc_by(&["a\"b"], 2.0);
+        gauge.set(&[], 3.0);
+        gauge.dec(&[]);
+        histogram.observe(&[], 0.25);
+        histogram.observe(&[], 0.75);
+        histogram.observe(&[], 3.0);
+        assert_eq!(
+            registry.render(),
+            r#"# HELP jobs_total Jobs.
+# TYPE jobs_total counter
+jobs_total{kind="a\"b"} 3
+# HELP queue_size Queue size.
+# TYPE queue_size gauge
+queue_size 2
+# HELP job_seconds Job duration.
+# TYPE job_seconds histogram
+job_seconds_bucket{le="0.5"} 1
+job_seconds_bucket{le="1"} 2
+job_seconds_bucket{le="+Inf"} 3
+job_seconds_sum 4
+job_seconds_count 3
+"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+
+        let metrics = Metrics::new();
+        let router = Router::new()
+            .push(Router::with_path("metrics").get(metrics.exporter()))
+            .push(Router::with_path("hello").hoop(metrics.clone()).get(hello));
+        let service = Service::new(router);
+
+        TestClient::get("http://127.0.0.1:5801/hello")
+            .send(&service)
+            .await;
+        let mut res = TestClient::get("http://127.0.0.1:5801/metrics")
+            .send(&service)
+            .await;
+        assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), TEXT_CONTENT_TYPE);
+        let body = res.take_string().await.unwrap();
+        let route = if cfg!(feature = "matched-path") {
+            "/hello"
+        } else {
+            ""
+        };
+        let labels = format!(r#"method="GET",route="{route}",status="200""#);
+        assert!(body.contains(&format!("http_requests_total{{{labels}}} 1\n")));
+        assert!(body.contains(&format!(
+            "http_request_duration_seconds_count{{{labels}}} 1\n"
+        )));
+        assert!(body.contains(&format!("http_response_size_bytes_sum{{{labels}}} 5\n")));
+        assert!(body.contains("http_requests_in_flight{method=\"GET\"} 0\n"));
+        assert_eq!(metrics.requests.get(&["GET", route, "200"]), 1.0);
+    }
+
+    #[cfg(feature = "matched-path")]
+    #[tokio::test]
+    async fn test_metrics_unmatched() {
+        #[handler]
+        async fn hello() -> &'static str {
+            "hello"
+        }
+
+        let metrics = Metrics::new();
+        let service = Service::new(Router::new().get(hello)).hoop(metrics.clone());
+
+        TestClient::get("http://127.0.0.1:5801/").send(&service).await;
+        let res = TestClient::get("http://127.0.0.1:5801/unknown")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+        assert_eq!(metrics.requests.get(&["GET", "/", "200"]), 1.0);
+        assert_eq!(metrics.requests.get(&["GET", "/", "404"]), 0.0);
+        assert_eq!(metrics.requests.get(&["GET", UNMATCHED_ROUTE, "404"]), 1.0);
+    }
+}
@@ -1,61 +1,107 @@
 //! Request id middleware.
 //!
+//! The middleware reads the request id from the incoming request header if it is allowed and valid, otherwise
+//! generates a new one. The request id is stored in the request header, the depot, the response header and a
+//! tracing span, so that it can be used by the handlers and the logging middleware.
+//!
 //! # Example
 //!
 //! ```no_run
 //! use salvo_core::prelude::*;
-//! use salvo_extra::request_id::RequestId;
+//! use salvo_extra::request_id::{RequestId, RequestIdDepotExt, UuidV7Generator};
 //!
 //! #[handler]
-//! async fn hello(req: &mut Request) -> String {
-//!     format!("Request id: {:?}", req.header::<String>("x-request-id"))
+//! async fn hello(depot: &mut Depot) -> String {
+//!     format!("Request id: {:?}", depot.request_id())
 //! }
 //!
 //! #[tokio::main]
 //! async fn main() {
 //!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
-//!     let router = Router::new().hoop(RequestId::new()).get(hello);
+//!     let router = Router::new()
+//!         .hoop(RequestId::new().overwrite(false).generator(UuidV7Generator::new()))
+//!         .get(hello);
 //!     Server::new(acceptor).serve(router).await;
 //! }
 //! ```
+use std::fmt::{self, Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::Instrument;
 use ulid::Ulid;
+use uuid::Uuid;
 
 use salvo_core::http::{header::HeaderName, Request, Response};
 use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
 
-/// Key for incoming flash messages in depot.
+/// Key for request id in depot.
 pub const REQUEST_ID_KEY: &str = "::salvo::request_id";
 
 /// Extension for Depot.
 pub trait RequestIdDepotExt {
     /// Get request id reference from depot.
-    fn csrf_token(&self) -> Option<&str>;
+    fn request_id(&self) -> Option<&str>;
+
+    /// Get request id reference from depot.
+    #[deprecated(note = "use `request_id` instead")]
+    fn csrf_token(&self) -> Option<&str> {
+        self.request_id()
+    }
 }
 
 impl RequestIdDepotExt for Depot {
     #[inline]
-    fn csrf_token(&self) -> Option<&str> {
-        self.get::<String>(REQUEST_ID_KEY).map(|v|&**v).ok()
+    fn request_id(&self) -> Option<&str> {
+        self.get::<String>(REQUEST_ID_KEY).map(|v| &**v).ok()
     }
 }
 
+/// The default validator for the incoming request id.
+///
+/// The request id is valid if it is not empty, its length is not greater than 128 and it only contains ascii
+/// alphanumeric characters and `-`, `_`, `.`, `:`, `+`, `/`, `=`.
+pub fn default_validator(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"-_.:+/=".contains(&b))
+}
+
 /// A middleware for generate request id.
 #[non_exhaustive]
 pub struct RequestId {
     /// The header name for request id.
     pub header_name: HeaderName,
-    /// Whether overwrite exists request id. Default is `true`
+    /// Whether overwrite exists request id. Default is `true`.
     pub overwrite: bool,
+    /// The validator for the incoming request id, it is used when `overwrite` is `false`.
+    pub validator: Box<dyn Fn(&str) -> bool + Send + Sync>,
+    /// Whether set the request id to the response header. Default is `false`.
+    pub echo: bool,
     /// The generator for request id.
     pub generator: Box<dyn IdGenerator + Send + Sync>,
 }
 
+impl Debug for RequestId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestId")
+            .field("header_name", &self.header_name)
+            .field("overwrite", &self.overwrite)
+            .field("echo", &self.echo)
+            .finish()
+    }
+}
+
 impl RequestId {
-    /// Create new `CatchPanic` middleware.
+    /// Create new `RequestId` middleware.
     pub fn new() -> Self {
         Self {
             header_name: HeaderName::from_static("x-request-id"),
             overwrite: true,
+            validator: Box::new(default_validator),
+            echo: false,
             generator: Box::new(UlidGenerator::new()),
         }
     }
@@ -67,11 +113,25 @@ impl RequestId {
     }
 
     /// Set whether overwrite exists request id. Default is `true`.
+    ///
+    /// If it is `false`, the incoming request id is reused if it passes the validator.
     pub fn overwrite(mut self, overwrite: bool) -> Self {
         self.overwrite = overwrite;
         self
     }
 
+    /// Set the validator for the incoming request id. Default is [`default_validator`].
+    pub fn validator(mut self, validator: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.validator = Box::new(validator);
+        self
+    }
+
+    /// Set whether set the request id to the response header. Default is `false`.
+    pub fn echo(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+
     /// Set the generator for request id.
     pub fn generator(mut self, generator: impl IdGenerator + Send + Sync + 'static) -> Self {
         self.generator = Box::new(generator);
@@ -102,8 +162,8 @@ where
 
 /// A generator for generate request id with ulid.
 #[derive(Default, Debug)]
-pub struct UlidGenerator{}
-impl UlidGenerator{
+pub struct UlidGenerator {}
+impl UlidGenerator {
     /// Create new `UlidGenerator`.
     pub fn new() -> Self {
         Self {}
@@ -115,14 +175,173 @@ impl IdGenerator for UlidGenerator {
     }
 }
 
+/// A generator for generate request id with UUID version 7.
+#[derive(Default, Debug)]
+pub struct UuidV7Generator {}
+impl UuidV7Generator {
+    /// Create new `UuidV7Generator`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+impl IdGenerator for UuidV7Generator {
+    fn generate(&self, _req: &mut Request, _depot: &mut Depot) -> String {
+        Uuid::now_v7().to_string()
+    }
+}
+
+/// A generator for generate request id with snowflake algorithm.
+///
+/// The id is composed of 41 bits milliseconds since the epoch, 10 bits node id and 12 bits sequence number.
+/// The default epoch is `2020-01-01T00:00:00Z`.
+#[derive(Debug)]
+pub struct SnowflakeGenerator {
+    node_id: u64,
+    epoch: SystemTime,
+    last: AtomicU64,
+}
+impl SnowflakeGenerator {
+    const NODE_BITS: u64 = 10;
+    const SEQUENCE_BITS: u64 = 12;
+    const SEQUENCE_MASK: u64 = (1 << Self::SEQUENCE_BITS) - 1;
+
+    /// Create new `SnowflakeGenerator`, only the lowest 10 bits of `node_id` are used.
+    pub fn new(node_id: u16) -> Self {
+        Self {
+            node_id: node_id as u64 & ((1 << Self::NODE_BITS) - 1),
+            epoch: UNIX_EPOCH + Duration::from_millis(1_577_836_800_000),
+            last: AtomicU64::new(0),
+        }
+    }
+
+    /// Set the epoch of the generator.
+    pub fn epoch(mut self, epoch: SystemTime) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Generate the next id.
+    pub fn next_id(&self) -> u64 {
+        loop {
+            let now = SystemTime::now()
+                .duration_since(self.epoch)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default();
+            let last = self.last.load(Ordering::Acquire);
+            let last_millis = last >> Self::SEQUENCE_BITS;
+            let next = if now > last_millis {
+                now << Self::SEQUENCE_BITS
+            } else if last & Self::SEQUENCE_MASK < Self::SEQUENCE_MASK {
+                last + 1
+            } else {
+                // The sequence is exhausted in current millisecond, wait for the next one.
+                std::thread::yield_now();
+                continue;
+            };
+            if self
+                .last
+                .compare_exchange_weak(last, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return (next >> Self::SEQUENCE_BITS) << (Self::NODE_BITS + Self::SEQUENCE_BITS)
+                    | self.node_id << Self::SEQUENCE_BITS
+                    | next & Self::SEQUENCE_MASK;
+            }
+        }
+    }
+}
+impl IdGenerator for SnowflakeGenerator {
+    fn generate(&self, _req: &mut Request, _depot: &mut Depot) -> String {
+        self.next_id().to_string()
+    }
+}
+
 #[async_trait]
 impl Handler for RequestId {
-    async fn handle(&self, req: &mut Request, depot: &mut Depot, _res: &mut Response, _ctrl: &mut FlowCtrl) {
-        if !self.overwrite && req.headers().contains_key(&self.header_name) {
-            return;
-        }
-        let id = self.generator.generate(req, depot);
+    async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let incoming = if self.overwrite {
+            None
+        } else {
+            req.headers()
+                .get(&self.header_name)
+                .and_then(|v| v.to_str().ok())
+                .filter(|id| (self.validator)(id))
+                .map(ToOwned::to_owned)
+        };
+        let id = match incoming {
+            Some(id) => id,
+            None => self.generator.generate(req, depot),
+        };
         let _ = req.add_header(self.header_name.clone(), &id, true);
-        depot.insert(REQUEST_ID_KEY, id);
+        depot.insert(REQUEST_ID_KEY, id.clone());
+
+        let span = tracing::info_span!("RequestId", request_id = %id);
+        ctrl.call_next(req, depot, res).instrument(span).await;
+        if self.echo {
+            let _ = res.add_header(self.header_name.clone(), &id, true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn hello(depot: &mut Depot) -> String {
+        depot.request_id().unwrap_or_default().to_owned()
+    }
+
+    #[tokio::test]
+    async fn test_request_id() {
+        let router = Router::new().hoop(RequestId::new().echo(true)).get(hello);
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("x-request-id", "incoming", true)
+            .send(router)
+            .await;
+        let header = res.headers().get("x-request-id").unwrap().to_str().unwrap().to_owned();
+        assert_ne!(header, "incoming");
+        assert_eq!(res.take_string().await.unwrap(), header);
+
+        let router = Router::new()
+            .hoop(RequestId::new().overwrite(false).echo(true))
+            .get(hello);
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("x-request-id", "incoming", true)
+            .send(router)
+            .await;
+        assert_eq!(res.headers().get("x-request-id").unwrap(), "incoming");
+        assert_eq!(res.take_string().await.unwrap(), "incoming");
+
+        let router = Router::new()
+            .hoop(RequestId::new().overwrite(false))
+            .get(hello);
+        let mut res = TestClient::get("http://127.0.0.1:5801")
+            .add_header("x-request-id", "in valid", true)
+            .send(router)
+            .await;
+        assert!(res.headers().get("x-request-id").is_none());
+        assert_ne!(res.take_string().await.unwrap(), "in valid");
+    }
+
+    #[test]
+    fn test_generators() {
+        let mut req = Request::default();
+        let mut depot = Depot::new();
+        let id = UuidV7Generator::new().generate(&mut req, &mut depot);
+        assert_eq!(Uuid::parse_str(&id).unwrap().get_version_num(), 7);
+
+        let generator = SnowflakeGenerator::new(3);
+        let ids = (0..10_000).map(|_| generator.next_id()).collect::<Vec<_>>();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+        assert!(ids.iter().all(|id| (id >> 12) & 0x3FF == 3));
+
+        assert!(default_validator("01HZX-abc_1.2:3"));
+        assert!(!default_validator(""));
+        assert!(!default_validator("in valid"));
+        assert!(!default_validator(&"a".repeat(129)));
     }
 }
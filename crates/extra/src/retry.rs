@@ -0,0 +1,363 @@
+//! Retry middleware for idempotent requests.
+//!
+//! [`Retry`] wraps a handler, usually the proxy handler, and calls it again when the response
+//! is retryable, by default a response with server error status, which the proxy writes when
+//! the upstream is unreachable, times out or fails.
+//!
+//! Only idempotent requests are retried: `GET`, `HEAD`, `OPTIONS` and `TRACE` by default, or the
+//! requests with an `Idempotency-Key` header. The request body is buffered up to
+//! [`body_limit`](Retry::body_limit) by [`Request::buffer_body`] so that it can be replayed,
+//! larger bodies are not retried.
+//!
+//! The delay between attempts grows exponentially with full jitter. The retries are limited by
+//! a [`RetryBudget`] shared by the clones of the middleware, so that retries do not multiply
+//! the load of an upstream which is already failing.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::retry::Retry;
+//!
+//! #[handler]
+//! async fn upstream() -> &'static str {
+//!     "Hello World"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let retry = Retry::new(upstream)
+//!         .max_attempts(3)
+//!         .backoff(Duration::from_millis(50), Duration::from_secs(1));
+//!     let router = Router::new().path("{**rest}").goal(retry);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use salvo_core::http::{HeaderMap, Method, ReqBody, Request, ResBody, Response};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+
+/// The header which marks a request as idempotent.
+pub const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// Limits the number of retries relative to the number of requests.
+///
+/// Every request deposits `ratio` tokens and every retry withdraws one token, the balance is
+/// capped at `max_tokens` and starts full.
+#[derive(Debug)]
+pub struct RetryBudget {
+    ratio: f64,
+    max_tokens: f64,
+    tokens: Mutex<f64>,
+}
+impl Default for RetryBudget {
+    /// Allows retries of 20% of the requests, with a reserve of 10 retries.
+    #[inline]
+    fn default() -> Self {
+        Self::new(0.2, 10)
+    }
+}
+impl RetryBudget {
+    /// Create new `RetryBudget`.
+    pub fn new(ratio: f64, max_tokens: u32) -> Self {
+        Self {
+            ratio: ratio.max(0.0),
+            max_tokens: max_tokens as f64,
+            tokens: Mutex::new(max_tokens as f64),
+        }
+    }
+
+    fn deposit(&self) {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        *tokens = (*tokens + self.ratio).min(self.max_tokens);
+    }
+
+    fn withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        if *tokens >= 1.0 {
+            *tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+type RetryWhen = dyn Fn(&Response) -> bool + Send + Sync;
+
+/// Retry middleware, it wraps a handler and calls it again for retryable responses.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct Retry<H> {
+    inner: H,
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    methods: Vec<Method>,
+    body_limit: usize,
+    retry_when: Arc<RetryWhen>,
+    budget: Arc<RetryBudget>,
+}
+impl<H: Handler> Retry<H> {
+    /// Create new `Retry` which makes at most 3 attempts with delays starting from 100
+    /// milliseconds.
+    pub fn new(inner: H) -> Self {
+        Self {
+            inner,
+            max_attempts: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(2),
+            methods: vec![Method::GET, Method::HEAD, Method::OPTIONS, Method::TRACE],
+            body_limit: 64 * 1024,
+            retry_when: Arc::new(|res| {
+                let status = res.status_code.or(match &res.body {
+                    ResBody::Error(e) => Some(e.code),
+                    _ => None,
+                });
+                status.is_some_and(|status| status.is_server_error())
+            }),
+            budget: Arc::default(),
+        }
+    }
+
+    /// The maximum number of attempts including the first one. Default is 3.
+    #[inline]
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry, doubled for every following retry up to `max_delay`.
+    /// The actual delay is a random duration between zero and it.
+    #[inline]
+    pub fn backoff(mut self, base_delay: Duration, max_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self.max_delay = max_delay.max(base_delay);
+        self
+    }
+
+    /// The methods of the requests which are retried. Default is `GET`, `HEAD`, `OPTIONS` and
+    /// `TRACE`. The requests with an `Idempotency-Key` header are always retried.
+    #[inline]
+    pub fn methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// The maximum size of the request body which is buffered for replay, the requests with
+    /// larger bodies are not retried. Default is 64 KiB.
+    #[inline]
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = limit;
+        self
+    }
+
+    /// Decide whether the response is retryable. Default is the responses with server error
+    /// status.
+    #[inline]
+    pub fn retry_when<F>(mut self, retry_when: F) -> Self
+    where
+        F: Fn(&Response) -> bool + Send + Sync + 'static,
+    {
+        self.retry_when = Arc::new(retry_when);
+        self
+    }
+
+    /// Set the budget which limits the retries, it can be shared by several middlewares.
+    #[inline]
+    pub fn budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    fn is_idempotent(&self, req: &Request) -> bool {
+        self.methods.contains(req.method()) || req.headers().contains_key(IDEMPOTENCY_KEY)
+    }
+
+    fn delay(&self, retry: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << retry.min(16))
+            .min(self.max_delay);
+        Duration::from_nanos(fastrand::u64(..=delay.as_nanos() as u64))
+    }
+}
+impl<H> Debug for Retry<H> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Retry")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("methods", &self.methods)
+            .field("body_limit", &self.body_limit)
+            .field("budget", &self.budget)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<H: Handler> Handler for Retry<H> {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        self.budget.deposit();
+        let mut body = None;
+        let retryable = self.max_attempts > 1
+            && self.is_idempotent(req)
+            && match req.body() {
+                ReqBody::None => true,
+                _ => match req.buffer_body(self.body_limit).await {
+                    Ok(bytes) => {
+                        body = Some(bytes);
+                        true
+                    }
+                    Err(e) => {
+                        tracing::debug!(error = ?e, "request body is not buffered, no retry");
+                        false
+                    }
+                },
+            };
+        if !retryable {
+            self.inner.handle(req, depot, res, ctrl).await;
+            return;
+        }
+
+        let status_code = res.status_code;
+        let headers: HeaderMap = res.headers().clone();
+        let mut attempt = 1;
+        loop {
+            self.inner.handle(req, depot, res, ctrl).await;
+            if attempt >= self.max_attempts || !(self.retry_when)(res) {
+                return;
+            }
+            let delay = self.delay(attempt - 1);
+            if req
+                .deadline()
+                .is_some_and(|deadline| Instant::now() + delay >= deadline)
+            {
+                tracing::debug!("request deadline is near, no retry");
+                return;
+            }
+            if !self.budget.withdraw() {
+                tracing::debug!("retry budget exhausted");
+                return;
+            }
+            tracing::debug!(attempt, ?delay, status = ?res.status_code, "retry request");
+            tokio::time::sleep(delay).await;
+
+            res.status_code = status_code;
+            res.body = ResBody::None;
+            *res.headers_mut() = headers.clone();
+            if let Some(body) = &body {
+                req.replace_body(ReqBody::Once(body.clone()));
+            }
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    struct Flaky {
+        failures: u32,
+        attempts: Arc<AtomicU32>,
+    }
+    #[async_trait]
+    impl Handler for Flaky {
+        async fn handle(
+            &self,
+            req: &mut Request,
+            _depot: &mut Depot,
+            res: &mut Response,
+            _ctrl: &mut FlowCtrl,
+        ) {
+            let body = req.payload().await.unwrap().clone();
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            if attempt <= self.failures {
+                res.status_code(StatusCode::BAD_GATEWAY);
+            } else {
+                res.render(format!("{attempt} {}", String::from_utf8_lossy(&body)));
+            }
+        }
+    }
+
+    fn router(failures: u32, max_attempts: u32) -> (Router, Arc<AtomicU32>) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let flaky = Flaky {
+            failures,
+            attempts: attempts.clone(),
+        };
+        let retry = Retry::new(flaky)
+            .max_attempts(max_attempts)
+            .backoff(Duration::from_millis(1), Duration::from_millis(5));
+        (Router::with_path("flaky").goal(retry), attempts)
+    }
+
+    #[tokio::test]
+    async fn test_retry_get() {
+        let (flaky, attempts) = router(2, 3);
+        let mut res = TestClient::get("http://127.0.0.1:5801/flaky")
+            .send(flaky)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.take_string().await.unwrap(), "3 ");
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        let (flaky, attempts) = router(5, 3);
+        let res = TestClient::get("http://127.0.0.1:5801/flaky")
+            .send(flaky)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_GATEWAY));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_post() {
+        let (flaky, attempts) = router(1, 3);
+        let res = TestClient::post("http://127.0.0.1:5801/flaky")
+            .text("hello")
+            .send(flaky)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_GATEWAY));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let (flaky, attempts) = router(1, 3);
+        let mut res = TestClient::post("http://127.0.0.1:5801/flaky")
+            .add_header(IDEMPOTENCY_KEY, "key1", true)
+            .text("hello")
+            .send(flaky)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "2 hello");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_retry_budget() {
+        let budget = RetryBudget::new(0.5, 1);
+        assert!(budget.withdraw());
+        assert!(!budget.withdraw());
+        budget.deposit();
+        assert!(!budget.withdraw());
+        budget.deposit();
+        assert!(budget.withdraw());
+    }
+}
@@ -0,0 +1,631 @@
+//! Security headers middleware.
+//!
+//! [`SecureHeaders`] sets the response headers which enable the security features of browsers:
+//! `Content-Security-Policy`, `X-Frame-Options`, `Referrer-Policy`, `X-Content-Type-Options`,
+//! `Permissions-Policy` and `Cross-Origin-*`. The defaults are strict, relax them for the pages
+//! which need it.
+//!
+//! The headers are set after the handlers run, only if they are not set already, so a handler
+//! can override them for a specific response.
+//!
+//! If the [`ContentSecurityPolicy`] uses a nonce, a new one is generated for every request and
+//! stored in the [`Depot`] as [`CspNonce`], to be rendered in the `nonce` attribute of the inline
+//! scripts and styles of templates.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::secure_headers::{ContentSecurityPolicy, CspNonce, SecureHeaders};
+//!
+//! #[handler]
+//! async fn hello(depot: &mut Depot) -> Text<String> {
+//!     let nonce = depot.obtain::<CspNonce>().map(|nonce| nonce.as_str()).unwrap_or_default();
+//!     Text::Html(format!(r#"<script nonce="{nonce}">console.log("hello")</script>"#))
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let csp = ContentSecurityPolicy::strict()
+//!         .img_src(["'self'", "https://images.example.com"])
+//!         .nonce(true);
+//!     let router = Router::new().hoop(SecureHeaders::new().csp(csp)).get(hello);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Display, Formatter};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use salvo_core::http::header::{
+    CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY_REPORT_ONLY, REFERRER_POLICY,
+    X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS,
+};
+use salvo_core::http::{HeaderName, HeaderValue, Request, Response};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+
+/// The `Permissions-Policy` header name.
+pub const PERMISSIONS_POLICY: HeaderName = HeaderName::from_static("permissions-policy");
+/// The `Cross-Origin-Opener-Policy` header name.
+pub const CROSS_ORIGIN_OPENER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-opener-policy");
+/// The `Cross-Origin-Embedder-Policy` header name.
+pub const CROSS_ORIGIN_EMBEDDER_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-embedder-policy");
+/// The `Cross-Origin-Resource-Policy` header name.
+pub const CROSS_ORIGIN_RESOURCE_POLICY: HeaderName =
+    HeaderName::from_static("cross-origin-resource-policy");
+
+/// The CSP nonce of the current request, stored in the [`Depot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CspNonce(String);
+impl CspNonce {
+    fn generate() -> Self {
+        Self(STANDARD.encode(rand::random::<[u8; 16]>()))
+    }
+    /// Get the nonce value, to be rendered in the `nonce` attribute.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+impl Display for CspNonce {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// `Content-Security-Policy` header builder.
+#[derive(Clone, Debug, Default)]
+pub struct ContentSecurityPolicy {
+    directives: Vec<(String, Vec<String>)>,
+    nonce: bool,
+    report_only: bool,
+}
+impl ContentSecurityPolicy {
+    /// Create new empty `ContentSecurityPolicy`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create new `ContentSecurityPolicy` which only allows the resources of the same origin, with
+    /// `default-src 'self'`, `base-uri 'self'`, `object-src 'none'`, `frame-ancestors 'none'` and
+    /// `form-action 'self'`.
+    pub fn strict() -> Self {
+        Self::new()
+            .default_src(["'self'"])
+            .base_uri(["'self'"])
+            .object_src(["'none'"])
+            .frame_ancestors(["'none'"])
+            .form_action(["'self'"])
+    }
+
+    /// Set the sources of a directive, replacing the previous ones. An empty list sets a
+    /// directive without value, e.g. `upgrade-insecure-requests`.
+    pub fn directive<I, S>(mut self, name: impl Into<String>, sources: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let name = name.into();
+        let sources = sources.into_iter().map(Into::into).collect();
+        match self.directives.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, s)) => *s = sources,
+            None => self.directives.push((name, sources)),
+        }
+        self
+    }
+
+    /// Remove a directive.
+    pub fn remove_directive(mut self, name: &str) -> Self {
+        self.directives.retain(|(n, _)| n != name);
+        self
+    }
+
+    /// Set the `default-src` directive.
+    #[inline]
+    pub fn default_src<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("default-src", sources)
+    }
+    /// Set the `script-src` directive.
+    #[inline]
+    pub fn script_src<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("script-src", sources)
+    }
+    /// Set the `style-src` directive.
+    #[inline]
+    pub fn style_src<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("style-src", sources)
+    }
+    /// Set the `img-src` directive.
+    #[inline]
+    pub fn img_src<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("img-src", sources)
+    }
+    /// Set the `connect-src` directive.
+    #[inline]
+    pub fn connect_src<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("connect-src", sources)
+    }
+    /// Set the `font-src` directive.
+    #[inline]
+    pub fn font_src<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("font-src", sources)
+    }
+    /// Set the `object-src` directive.
+    #[inline]
+    pub fn object_src<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("object-src", sources)
+    }
+    /// Set the `base-uri` directive.
+    #[inline]
+    pub fn base_uri<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("base-uri", sources)
+    }
+    /// Set the `form-action` directive.
+    #[inline]
+    pub fn form_action<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("form-action", sources)
+    }
+    /// Set the `frame-ancestors` directive.
+    #[inline]
+    pub fn frame_ancestors<I: IntoIterator<Item = S>, S: Into<String>>(self, sources: I) -> Self {
+        self.directive("frame-ancestors", sources)
+    }
+    /// Set the `report-uri` directive.
+    #[inline]
+    pub fn report_uri(self, uri: impl Into<String>) -> Self {
+        self.directive("report-uri", [uri])
+    }
+
+    /// Add a nonce source generated for every request to the `script-src` and `style-src`
+    /// directives, which are added with `'self'` if they are not set. The nonce is stored in the
+    /// [`Depot`] as [`CspNonce`].
+    #[inline]
+    pub fn nonce(mut self, enabled: bool) -> Self {
+        self.nonce = enabled;
+        self
+    }
+
+    /// Send the policy in the `Content-Security-Policy-Report-Only` header, so the violations
+    /// are reported but not enforced.
+    #[inline]
+    pub fn report_only(mut self, enabled: bool) -> Self {
+        self.report_only = enabled;
+        self
+    }
+
+    fn header_name(&self) -> HeaderName {
+        if self.report_only {
+            CONTENT_SECURITY_POLICY_REPORT_ONLY
+        } else {
+            CONTENT_SECURITY_POLICY
+        }
+    }
+
+    fn header_value(&self, nonce: Option<&CspNonce>) -> String {
+        let mut directives = self.directives.clone();
+        if let Some(nonce) = nonce {
+            for name in ["script-src", "style-src"] {
+                let source = format!("'nonce-{nonce}'");
+                match directives.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, sources)) => sources.push(source),
+                    None => directives.push((name.into(), vec!["'self'".into(), source])),
+                }
+            }
+        }
+        directives
+            .iter()
+            .map(|(name, sources)| {
+                if sources.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{name} {}", sources.join(" "))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// `X-Frame-Options` header value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XFrameOptions {
+    /// The page can not be displayed in a frame.
+    Deny,
+    /// The page can only be displayed in a frame of the same origin.
+    SameOrigin,
+}
+impl XFrameOptions {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deny => "DENY",
+            Self::SameOrigin => "SAMEORIGIN",
+        }
+    }
+}
+
+/// `Referrer-Policy` header value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferrerPolicy {
+    /// `no-referrer`
+    NoReferrer,
+    /// `no-referrer-when-downgrade`
+    NoReferrerWhenDowngrade,
+    /// `origin`
+    Origin,
+    /// `origin-when-cross-origin`
+    OriginWhenCrossOrigin,
+    /// `same-origin`
+    SameOrigin,
+    /// `strict-origin`
+    StrictOrigin,
+    /// `strict-origin-when-cross-origin`
+    StrictOriginWhenCrossOrigin,
+    /// `unsafe-url`
+    UnsafeUrl,
+}
+impl ReferrerPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoReferrer => "no-referrer",
+            Self::NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
+            Self::Origin => "origin",
+            Self::OriginWhenCrossOrigin => "origin-when-cross-origin",
+            Self::SameOrigin => "same-origin",
+            Self::StrictOrigin => "strict-origin",
+            Self::StrictOriginWhenCrossOrigin => "strict-origin-when-cross-origin",
+            Self::UnsafeUrl => "unsafe-url",
+        }
+    }
+}
+
+/// `Cross-Origin-Opener-Policy` header value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrossOriginOpenerPolicy {
+    /// `same-origin`
+    SameOrigin,
+    /// `same-origin-allow-popups`
+    SameOriginAllowPopups,
+    /// `unsafe-none`
+    UnsafeNone,
+}
+impl CrossOriginOpenerPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::SameOrigin => "same-origin",
+            Self::SameOriginAllowPopups => "same-origin-allow-popups",
+            Self::UnsafeNone => "unsafe-none",
+        }
+    }
+}
+
+/// `Cross-Origin-Embedder-Policy` header value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrossOriginEmbedderPolicy {
+    /// `require-corp`
+    RequireCorp,
+    /// `credentialless`
+    Credentialless,
+    /// `unsafe-none`
+    UnsafeNone,
+}
+impl CrossOriginEmbedderPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::RequireCorp => "require-corp",
+            Self::Credentialless => "credentialless",
+            Self::UnsafeNone => "unsafe-none",
+        }
+    }
+}
+
+/// `Cross-Origin-Resource-Policy` header value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrossOriginResourcePolicy {
+    /// `same-origin`
+    SameOrigin,
+    /// `same-site`
+    SameSite,
+    /// `cross-origin`
+    CrossOrigin,
+}
+impl CrossOriginResourcePolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::SameOrigin => "same-origin",
+            Self::SameSite => "same-site",
+            Self::CrossOrigin => "cross-origin",
+        }
+    }
+}
+
+/// `Permissions-Policy` header builder.
+#[derive(Clone, Debug, Default)]
+pub struct PermissionsPolicy {
+    features: Vec<(String, Vec<String>)>,
+}
+impl PermissionsPolicy {
+    /// Create new empty `PermissionsPolicy`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow the feature for the origins, `self` and `*` are kept as is and the other origins
+    /// are quoted, e.g. `geolocation=(self "https://maps.example.com")`.
+    pub fn allow<I, S>(mut self, feature: impl Into<String>, origins: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let origins = origins
+            .into_iter()
+            .map(|origin| {
+                let origin = origin.into();
+                if origin == "self" || origin == "*" {
+                    origin
+                } else {
+                    format!("\"{origin}\"")
+                }
+            })
+            .collect();
+        self.features.push((feature.into(), origins));
+        self
+    }
+
+    /// Disable the feature, e.g. `camera=()`.
+    #[inline]
+    pub fn deny(self, feature: impl Into<String>) -> Self {
+        self.allow(feature, Vec::<String>::new())
+    }
+
+    fn header_value(&self) -> String {
+        self.features
+            .iter()
+            .map(|(feature, origins)| {
+                if origins.len() == 1 && origins[0] == "*" {
+                    format!("{feature}=*")
+                } else {
+                    format!("{feature}=({})", origins.join(" "))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Security headers middleware.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone, Debug)]
+pub struct SecureHeaders {
+    csp: Option<ContentSecurityPolicy>,
+    x_frame_options: Option<XFrameOptions>,
+    referrer_policy: Option<ReferrerPolicy>,
+    nosniff: bool,
+    permissions_policy: Option<PermissionsPolicy>,
+    cross_origin_opener_policy: Option<CrossOriginOpenerPolicy>,
+    cross_origin_embedder_policy: Option<CrossOriginEmbedderPolicy>,
+    cross_origin_resource_policy: Option<CrossOriginResourcePolicy>,
+}
+impl Default for SecureHeaders {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl SecureHeaders {
+    /// Create new `SecureHeaders` with the strict defaults:
+    ///
+    /// - `Content-Security-Policy`: [`ContentSecurityPolicy::strict`]
+    /// - `X-Frame-Options: DENY`
+    /// - `Referrer-Policy: no-referrer`
+    /// - `X-Content-Type-Options: nosniff`
+    /// - `Cross-Origin-Opener-Policy: same-origin`
+    /// - `Cross-Origin-Resource-Policy: same-origin`
+    ///
+    /// `Permissions-Policy` and `Cross-Origin-Embedder-Policy` are not set by default.
+    pub fn new() -> Self {
+        Self {
+            csp: Some(ContentSecurityPolicy::strict()),
+            x_frame_options: Some(XFrameOptions::Deny),
+            referrer_policy: Some(ReferrerPolicy::NoReferrer),
+            nosniff: true,
+            permissions_policy: None,
+            cross_origin_opener_policy: Some(CrossOriginOpenerPolicy::SameOrigin),
+            cross_origin_embedder_policy: None,
+            cross_origin_resource_policy: Some(CrossOriginResourcePolicy::SameOrigin),
+        }
+    }
+
+    /// Set the `Content-Security-Policy` header, `None` to not send it.
+    #[inline]
+    pub fn csp(mut self, csp: impl Into<Option<ContentSecurityPolicy>>) -> Self {
+        self.csp = csp.into();
+        self
+    }
+
+    /// Set the `X-Frame-Options` header, `None` to not send it.
+    #[inline]
+    pub fn x_frame_options(mut self, value: impl Into<Option<XFrameOptions>>) -> Self {
+        self.x_frame_options = value.into();
+        self
+    }
+
+    /// Set the `Referrer-Policy` header, `None` to not send it.
+    #[inline]
+    pub fn referrer_policy(mut self, value: impl Into<Option<ReferrerPolicy>>) -> Self {
+        self.referrer_policy = value.into();
+        self
+    }
+
+    /// Send the `X-Content-Type-Options: nosniff` header. Default is `true`.
+    #[inline]
+    pub fn nosniff(mut self, enabled: bool) -> Self {
+        self.nosniff = enabled;
+        self
+    }
+
+    /// Set the `Permissions-Policy` header, `None` to not send it.
+    #[inline]
+    pub fn permissions_policy(mut self, value: impl Into<Option<PermissionsPolicy>>) -> Self {
+        self.permissions_policy = value.into();
+        self
+    }
+
+    /// Set the `Cross-Origin-Opener-Policy` header, `None` to not send it.
+    #[inline]
+    pub fn cross_origin_opener_policy(
+        mut self,
+        value: impl Into<Option<CrossOriginOpenerPolicy>>,
+    ) -> Self {
+        self.cross_origin_opener_policy = value.into();
+        self
+    }
+
+    /// Set the `Cross-Origin-Embedder-Policy` header, `None` to not send it.
+    #[inline]
+    pub fn cross_origin_embedder_policy(
+        mut self,
+        value: impl Into<Option<CrossOriginEmbedderPolicy>>,
+    ) -> Self {
+        self.cross_origin_embedder_policy = value.into();
+        self
+    }
+
+    /// Set the `Cross-Origin-Resource-Policy` header, `None` to not send it.
+    #[inline]
+    pub fn cross_origin_resource_policy(
+        mut self,
+        value: impl Into<Option<CrossOriginResourcePolicy>>,
+    ) -> Self {
+        self.cross_origin_resource_policy = value.into();
+        self
+    }
+}
+
+fn insert_default(res: &mut Response, name: HeaderName, value: &str) {
+    if res.headers().contains_key(&name) {
+        return;
+    }
+    match HeaderValue::from_str(value) {
+        Ok(value) => {
+            res.headers_mut().insert(name, value);
+        }
+        Err(_) => tracing::error!(header = %name, value, "invalid security header value"),
+    }
+}
+
+#[async_trait]
+impl Handler for SecureHeaders {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let nonce = match &self.csp {
+            Some(csp) if csp.nonce => {
+                let nonce = CspNonce::generate();
+                depot.inject(nonce.clone());
+                Some(nonce)
+            }
+            _ => None,
+        };
+        ctrl.call_next(req, depot, res).await;
+
+        if let Some(csp) = &self.csp {
+            insert_default(res, csp.header_name(), &csp.header_value(nonce.as_ref()));
+        }
+        if let Some(value) = self.x_frame_options {
+            insert_default(res, X_FRAME_OPTIONS, value.as_str());
+        }
+        if let Some(value) = self.referrer_policy {
+            insert_default(res, REFERRER_POLICY, value.as_str());
+        }
+        if self.nosniff {
+            insert_default(res, X_CONTENT_TYPE_OPTIONS, "nosniff");
+        }
+        if let Some(value) = &self.permissions_policy {
+            insert_default(res, PERMISSIONS_POLICY, &value.header_value());
+        }
+        if let Some(value) = self.cross_origin_opener_policy {
+            insert_default(res, CROSS_ORIGIN_OPENER_POLICY, value.as_str());
+        }
+        if let Some(value) = self.cross_origin_embedder_policy {
+            insert_default(res, CROSS_ORIGIN_EMBEDDER_POLICY, value.as_str());
+        }
+        if let Some(value) = self.cross_origin_resource_policy {
+            insert_default(res, CROSS_ORIGIN_RESOURCE_POLICY, value.as_str());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn page(depot: &mut Depot, res: &mut Response) {
+        let nonce = depot.obtain::<CspNonce>().unwrap();
+        res.headers_mut()
+            .insert(X_FRAME_OPTIONS, HeaderValue::from_static("SAMEORIGIN"));
+        res.render(nonce.as_str().to_owned());
+    }
+
+    #[tokio::test]
+    async fn test_secure_headers() {
+        let secure_headers = SecureHeaders::new()
+            .csp(
+                ContentSecurityPolicy::strict()
+                    .img_src(["'self'", "data:"])
+                    .nonce(true),
+            )
+            .referrer_policy(ReferrerPolicy::StrictOriginWhenCrossOrigin)
+            .cross_origin_resource_policy(None)
+            .permissions_policy(
+                PermissionsPolicy::new()
+                    .deny("camera")
+                    .allow("geolocation", ["self", "https://maps.example.com"]),
+            );
+        let router = Router::new().hoop(secure_headers).get(page);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/").send(router).await;
+        let nonce = res.take_string().await.unwrap();
+        assert_eq!(nonce.len(), 24);
+        let header = |name: &str| {
+            res.headers()
+                .get(name)
+                .map(|v| v.to_str().unwrap().to_owned())
+        };
+        assert_eq!(
+            header("content-security-policy").unwrap(),
+            format!(
+                "default-src 'self'; base-uri 'self'; object-src 'none'; \
+                 frame-ancestors 'none'; form-action 'self'; img-src 'self' data:; \
+                 script-src 'self' 'nonce-{nonce}'; style-src 'self' 'nonce-{nonce}'"
+            )
+        );
+        assert_eq!(header("x-frame-options").unwrap(), "SAMEORIGIN");
+        assert_eq!(
+            header("referrer-policy").unwrap(),
+            "strict-origin-when-cross-origin"
+        );
+        assert_eq!(header("x-content-type-options").unwrap(), "nosniff");
+        assert_eq!(
+            header("permissions-policy").unwrap(),
+            r#"camera=(), geolocation=(self "https://maps.example.com")"#
+        );
+        assert_eq!(header("cross-origin-opener-policy").unwrap(), "same-origin");
+        assert!(header("cross-origin-resource-policy").is_none());
+        assert!(header("cross-origin-embedder-policy").is_none());
+    }
+}
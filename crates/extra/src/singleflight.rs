@@ -0,0 +1,345 @@
+//! Request coalescing middleware.
+//!
+//! [`SingleFlight`] coalesces the concurrent identical requests, so that the handler runs only
+//! once and all the waiting requests share its buffered response. It prevents stampedes of
+//! expensive requests, e.g. when a cache entry expires under heavy load.
+//!
+//! The requests are identical if they have the same method, path, query and values of the
+//! [`vary`](SingleFlight::vary) headers. Only `GET` and `HEAD` requests are coalesced by default,
+//! since the others may not be idempotent.
+//!
+//! The responses with a streaming or error body are not shared, the waiting requests then run the
+//! handler by themselves, and so does it if the first request is canceled. The shared responses
+//! include all the headers, so this middleware should not be used for personalized responses.
+//!
+//! The requests with credentials, i.e. `Authorization`, `Proxy-Authorization` or `Cookie` headers,
+//! are not coalesced unless [`credentialed`](SingleFlight::credentialed) is enabled, otherwise two
+//! users requesting the same url at the same time could get the response of each other.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::singleflight::SingleFlight;
+//!
+//! #[handler]
+//! async fn report() -> &'static str {
+//!     // An expensive computation.
+//!     "report"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let router = Router::with_path("report").hoop(SingleFlight::new()).get(report);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Debug, Formatter, Write};
+use std::sync::{Arc, Mutex};
+
+use salvo_core::http::header::{ACCEPT, ACCEPT_ENCODING, AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION};
+use salvo_core::http::{HeaderMap, HeaderName, Method, Request, ResBody, Response, StatusCode};
+use salvo_core::hyper::body::Bytes;
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+use tokio::sync::watch;
+
+#[derive(Debug)]
+enum SharedBody {
+    None,
+    Once(Bytes),
+    Chunks(VecDeque<Bytes>),
+}
+
+/// The response shared with the waiting requests.
+#[derive(Debug)]
+struct SharedResponse {
+    status_code: Option<StatusCode>,
+    headers: HeaderMap,
+    body: SharedBody,
+}
+impl SharedResponse {
+    fn of(res: &Response) -> Option<Self> {
+        let body = match &res.body {
+            ResBody::None => SharedBody::None,
+            ResBody::Once(bytes) => SharedBody::Once(bytes.clone()),
+            ResBody::Chunks(chunks) => SharedBody::Chunks(chunks.clone()),
+            _ => return None,
+        };
+        Some(Self {
+            status_code: res.status_code,
+            headers: res.headers().clone(),
+            body,
+        })
+    }
+
+    fn write(&self, res: &mut Response) {
+        res.status_code = self.status_code;
+        *res.headers_mut() = self.headers.clone();
+        res.body = match &self.body {
+            SharedBody::None => ResBody::None,
+            SharedBody::Once(bytes) => ResBody::Once(bytes.clone()),
+            SharedBody::Chunks(chunks) => ResBody::Chunks(chunks.clone()),
+        };
+    }
+}
+
+/// `None` while the first request is running, then `Some(None)` if the response is not
+/// shareable.
+type Flight = Option<Option<Arc<SharedResponse>>>;
+type Flights = Mutex<HashMap<String, watch::Receiver<Flight>>>;
+
+/// Removes the flight when the first request finishes or is canceled.
+struct FlightGuard<'a> {
+    flights: &'a Flights,
+    key: &'a str,
+}
+impl Drop for FlightGuard<'_> {
+    fn drop(&mut self) {
+        self.flights
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(self.key);
+    }
+}
+
+/// Request coalescing middleware.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct SingleFlight {
+    methods: Vec<Method>,
+    vary: Vec<HeaderName>,
+    credentialed: bool,
+    flights: Arc<Flights>,
+}
+impl Default for SingleFlight {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl SingleFlight {
+    /// Create new `SingleFlight` which coalesces `GET` and `HEAD` requests, varying on `Accept`
+    /// and `Accept-Encoding` headers.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            methods: vec![Method::GET, Method::HEAD],
+            vary: vec![ACCEPT, ACCEPT_ENCODING],
+            credentialed: false,
+            flights: Arc::default(),
+        }
+    }
+
+    /// Set the methods of the requests which are coalesced.
+    #[inline]
+    pub fn methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Set the headers whose values are part of the request identity, e.g. `Accept-Language`.
+    #[inline]
+    pub fn vary(mut self, headers: impl IntoIterator<Item = HeaderName>) -> Self {
+        self.vary = headers.into_iter().collect();
+        self
+    }
+
+    /// Coalesce the requests with credentials too, they are not coalesced by default.
+    ///
+    /// Enable it only if the responses do not depend on the user, or add the credential headers to
+    /// [`vary`](Self::vary), e.g. `Authorization`, so only the requests of the same user are coalesced.
+    #[inline]
+    pub fn credentialed(mut self, enabled: bool) -> Self {
+        self.credentialed = enabled;
+        self
+    }
+
+    fn key(&self, req: &Request) -> String {
+        let mut key = format!("{} {}", req.method(), req.uri().path());
+        if let Some(query) = req.uri().query() {
+            let _ = write!(key, "?{query}");
+        }
+        for name in &self.vary {
+            for value in req.headers().get_all(name) {
+                let _ = write!(
+                    key,
+                    "\n{name}: {}",
+                    String::from_utf8_lossy(value.as_bytes())
+                );
+            }
+        }
+        key
+    }
+}
+impl Debug for SingleFlight {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SingleFlight")
+            .field("methods", &self.methods)
+            .field("vary", &self.vary)
+            .field("credentialed", &self.credentialed)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Handler for SingleFlight {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        if !self.methods.contains(req.method()) {
+            return;
+        }
+        if !self.credentialed
+            && [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE]
+                .iter()
+                .any(|name| req.headers().contains_key(name))
+        {
+            return;
+        }
+        let key = self.key(req);
+        let waiting = {
+            let mut flights = self.flights.lock().unwrap_or_else(|e| e.into_inner());
+            match flights.get(&key) {
+                Some(rx) => Err(rx.clone()),
+                None => {
+                    let (tx, rx) = watch::channel(None);
+                    flights.insert(key.clone(), rx);
+                    Ok(tx)
+                }
+            }
+        };
+        match waiting {
+            Ok(tx) => {
+                let _guard = FlightGuard {
+                    flights: &self.flights,
+                    key: &key,
+                };
+                ctrl.call_next(req, depot, res).await;
+                let _ = tx.send(Some(SharedResponse::of(res).map(Arc::new)));
+            }
+            Err(mut rx) => {
+                let shared = match rx.wait_for(|flight| flight.is_some()).await {
+                    Ok(flight) => flight.clone().flatten(),
+                    Err(_) => None,
+                };
+                match shared {
+                    Some(shared) => {
+                        tracing::debug!(key, "response shared with coalesced request");
+                        shared.write(res);
+                        ctrl.skip_rest();
+                    }
+                    None => {
+                        ctrl.call_next(req, depot, res).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    struct Slow(Arc<AtomicUsize>);
+    #[async_trait]
+    impl Handler for Slow {
+        async fn handle(
+            &self,
+            req: &mut Request,
+            _depot: &mut Depot,
+            res: &mut Response,
+            _ctrl: &mut FlowCtrl,
+        ) {
+            let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            res.render(format!("{} {count}", req.uri().path()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_singleflight() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let service = Service::new(
+            Router::new()
+                .hoop(SingleFlight::new())
+                .push(Router::with_path("{name}").get(Slow(count.clone()))),
+        );
+        let get = |path: &'static str| {
+            let service = &service;
+            async move {
+                TestClient::get(format!("http://127.0.0.1:5801/{path}"))
+                    .send(service)
+                    .await
+                    .take_string()
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let (a, b, c) = tokio::join!(get("a"), get("a"), get("a"));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(a, "/a 1");
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+
+        let (a, b) = tokio::join!(get("a"), get("b"));
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+        assert_ne!(a, b);
+
+        get("a").await;
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_singleflight_credentialed() {
+        async fn get(service: &Service, token: &str) -> String {
+            TestClient::get("http://127.0.0.1:5801/me")
+                .add_header(AUTHORIZATION, token, true)
+                .send(service)
+                .await
+                .take_string()
+                .await
+                .unwrap()
+        }
+        let count = Arc::new(AtomicUsize::new(0));
+        let router = |single_flight: SingleFlight| {
+            Router::new()
+                .hoop(single_flight)
+                .push(Router::with_path("{name}").get(Slow(count.clone())))
+        };
+
+        let service = Service::new(router(SingleFlight::new()));
+        let (alice, bob) = tokio::join!(get(&service, "Bearer alice"), get(&service, "Bearer bob"));
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert_ne!(alice, bob);
+
+        let service = Service::new(router(
+            SingleFlight::new()
+                .credentialed(true)
+                .vary([AUTHORIZATION]),
+        ));
+        let (alice1, alice2, bob) = tokio::join!(
+            get(&service, "Bearer alice"),
+            get(&service, "Bearer alice"),
+            get(&service, "Bearer bob")
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+        assert_eq!(alice1, alice2);
+        assert_ne!(alice1, bob);
+    }
+}
@@ -0,0 +1,312 @@
+//! Slow request detection middleware.
+//!
+//! [`SlowRequest`] logs a warning for the requests whose handling takes longer than a
+//! threshold, with the route pattern, the status and the phase timings recorded by the
+//! handlers, and calls an optional hook, e.g. to count them in a metric.
+//!
+//! The handlers record the duration of their phases, such as database queries or upstream
+//! calls, in the [`Depot`] with [`TimingDepotExt`]. The middleware can also send them to the
+//! client in the `Server-Timing` header, which is displayed by the browser developer tools.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::slow_request::{SlowRequest, TimingDepotExt};
+//!
+//! #[handler]
+//! async fn hello(depot: &mut Depot) -> &'static str {
+//!     depot.start_timer("db");
+//!     tokio::time::sleep(Duration::from_millis(300)).await;
+//!     depot.stop_timer("db");
+//!     "Hello World"
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let slow_request = SlowRequest::new(Duration::from_millis(200)).server_timing(true);
+//!     let router = Router::new().hoop(slow_request).get(hello);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::fmt::{self, Debug, Formatter, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use salvo_core::http::header::HeaderName;
+use salvo_core::http::{HeaderValue, Method, Request, ResBody, Response, StatusCode};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+
+/// The `Server-Timing` header name.
+pub const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+/// Phase timings of a request, stored in the [`Depot`].
+#[derive(Clone, Debug, Default)]
+pub struct ServerTimings {
+    entries: Vec<(String, Duration)>,
+    running: Vec<(String, Instant)>,
+}
+impl ServerTimings {
+    /// Record the duration of a phase.
+    #[inline]
+    pub fn record(&mut self, name: impl Into<String>, duration: Duration) {
+        self.entries.push((name.into(), duration));
+    }
+    /// Start the timer of a phase.
+    #[inline]
+    pub fn start(&mut self, name: impl Into<String>) {
+        self.running.push((name.into(), Instant::now()));
+    }
+    /// Stop the timer of a phase and record its duration, returns `None` if it is not started.
+    pub fn stop(&mut self, name: &str) -> Option<Duration> {
+        let index = self.running.iter().rposition(|(n, _)| n == name)?;
+        let (name, started) = self.running.remove(index);
+        let duration = started.elapsed();
+        self.entries.push((name, duration));
+        Some(duration)
+    }
+    /// Get the recorded phases.
+    #[inline]
+    pub fn entries(&self) -> &[(String, Duration)] {
+        &self.entries
+    }
+
+    fn header_value(&self, total: Duration) -> String {
+        let mut value = String::new();
+        for (name, duration) in &self.entries {
+            let _ = write!(value, "{name};dur={:.1}, ", duration.as_secs_f64() * 1000.0);
+        }
+        let _ = write!(value, "total;dur={:.1}", total.as_secs_f64() * 1000.0);
+        value
+    }
+}
+
+/// Record phase timings in the [`Depot`].
+pub trait TimingDepotExt {
+    /// Record the duration of a phase.
+    fn record_timing(&mut self, name: impl Into<String>, duration: Duration);
+    /// Start the timer of a phase.
+    fn start_timer(&mut self, name: impl Into<String>);
+    /// Stop the timer of a phase and record its duration, returns `None` if it is not started.
+    fn stop_timer(&mut self, name: &str) -> Option<Duration>;
+    /// Get the recorded timings.
+    fn server_timings(&self) -> Option<&ServerTimings>;
+}
+fn timings_mut(depot: &mut Depot) -> &mut ServerTimings {
+    if !depot.contains::<ServerTimings>() {
+        depot.inject(ServerTimings::default());
+    }
+    depot
+        .obtain_mut::<ServerTimings>()
+        .expect("server timings should be injected")
+}
+impl TimingDepotExt for Depot {
+    #[inline]
+    fn record_timing(&mut self, name: impl Into<String>, duration: Duration) {
+        timings_mut(self).record(name, duration);
+    }
+    #[inline]
+    fn start_timer(&mut self, name: impl Into<String>) {
+        timings_mut(self).start(name);
+    }
+    #[inline]
+    fn stop_timer(&mut self, name: &str) -> Option<Duration> {
+        timings_mut(self).stop(name)
+    }
+    #[inline]
+    fn server_timings(&self) -> Option<&ServerTimings> {
+        self.obtain::<ServerTimings>().ok()
+    }
+}
+
+/// A request which exceeded the threshold, passed to [`SlowRequest::on_slow`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SlowRequestEvent {
+    /// The request method.
+    pub method: Method,
+    /// The request path.
+    pub path: String,
+    /// The matched route pattern, `None` if no router is matched or the `matched-path` feature is disabled.
+    pub route: Option<String>,
+    /// The response status.
+    pub status: StatusCode,
+    /// The handling duration.
+    pub duration: Duration,
+    /// The phase timings recorded in the depot.
+    pub timings: Vec<(String, Duration)>,
+}
+
+type OnSlow = dyn Fn(&SlowRequestEvent) + Send + Sync;
+
+/// Slow request detection middleware.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct SlowRequest {
+    threshold: Duration,
+    server_timing: bool,
+    on_slow: Option<Arc<OnSlow>>,
+}
+impl SlowRequest {
+    /// Create new `SlowRequest` which logs the requests taking longer than `threshold`.
+    #[inline]
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            server_timing: false,
+            on_slow: None,
+        }
+    }
+
+    /// Send the phase timings and the total duration in the `Server-Timing` header of every
+    /// response. Default is `false`, since it exposes the internals to the clients.
+    #[inline]
+    pub fn server_timing(mut self, enabled: bool) -> Self {
+        self.server_timing = enabled;
+        self
+    }
+
+    /// Call `on_slow` for every slow request, e.g. to increase a metric.
+    #[inline]
+    pub fn on_slow<F>(mut self, on_slow: F) -> Self
+    where
+        F: Fn(&SlowRequestEvent) + Send + Sync + 'static,
+    {
+        self.on_slow = Some(Arc::new(on_slow));
+        self
+    }
+}
+impl Debug for SlowRequest {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SlowRequest")
+            .field("threshold", &self.threshold)
+            .field("server_timing", &self.server_timing)
+            .field("on_slow", &self.on_slow.is_some())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Handler for SlowRequest {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let started = Instant::now();
+        ctrl.call_next(req, depot, res).await;
+        let duration = started.elapsed();
+
+        let timings = depot.server_timings();
+        if self.server_timing {
+            let value = timings
+                .map(|timings| timings.header_value(duration))
+                .unwrap_or_else(|| ServerTimings::default().header_value(duration));
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                res.headers_mut().append(SERVER_TIMING, value);
+            }
+        }
+        if duration < self.threshold {
+            return;
+        }
+
+        let status = res.status_code.unwrap_or(match &res.body {
+            ResBody::None => StatusCode::NOT_FOUND,
+            ResBody::Error(e) => e.code,
+            _ => StatusCode::OK,
+        });
+        #[cfg(feature = "matched-path")]
+        let route = req.matched_route();
+        #[cfg(not(feature = "matched-path"))]
+        let route = None;
+        let event = SlowRequestEvent {
+            method: req.method().clone(),
+            path: req.uri().path().to_owned(),
+            route,
+            status,
+            duration,
+            timings: timings
+                .map(|timings| timings.entries().to_vec())
+                .unwrap_or_default(),
+        };
+        tracing::warn!(
+            method = %event.method,
+            path = %event.path,
+            route = event.route.as_deref(),
+            status = event.status.as_u16(),
+            duration = ?event.duration,
+            timings = ?event.timings,
+            "slow request"
+        );
+        if let Some(on_slow) = &self.on_slow {
+            on_slow(&event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use salvo_core::prelude::*;
+    use salvo_core::test::TestClient;
+
+    use super::*;
+
+    #[handler]
+    async fn slow(depot: &mut Depot) -> &'static str {
+        depot.start_timer("db");
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        depot.stop_timer("db");
+        depot.record_timing("cache", Duration::from_micros(1500));
+        "slow"
+    }
+
+    #[handler]
+    async fn fast() -> &'static str {
+        "fast"
+    }
+
+    #[tokio::test]
+    async fn test_slow_request() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let slow_request = SlowRequest::new(Duration::from_millis(20))
+            .server_timing(true)
+            .on_slow({
+                let events = events.clone();
+                move |event| events.lock().unwrap().push(event.clone())
+            });
+        let router = Router::new()
+            .hoop(slow_request)
+            .push(Router::with_path("slow").get(slow))
+            .push(Router::with_path("fast").get(fast));
+        let service = Service::new(router);
+
+        let res = TestClient::get("http://127.0.0.1:5801/slow")
+            .send(&service)
+            .await;
+        let header = res.headers().get(SERVER_TIMING).unwrap().to_str().unwrap();
+        assert!(header.starts_with("db;dur="));
+        assert!(header.contains(", cache;dur=1.5, total;dur="));
+
+        let res = TestClient::get("http://127.0.0.1:5801/fast")
+            .send(&service)
+            .await;
+        let header = res.headers().get(SERVER_TIMING).unwrap().to_str().unwrap();
+        assert!(header.starts_with("total;dur="));
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path, "/slow");
+        assert_eq!(events[0].status, StatusCode::OK);
+        assert_eq!(events[0].timings.len(), 2);
+        assert!(events[0].duration >= Duration::from_millis(30));
+    }
+}
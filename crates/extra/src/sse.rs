@@ -44,24 +44,72 @@
 //! Each field already is event which can be sent to client.
 //! The events with multiple fields can be created by combining fields using tuples.
 //!
+//! Use [`channel`] to produce events from another task, sending fails once the client is
+//! disconnected, so the producer can stop. The id of the last event received by a reconnecting
+//! client can be read by [`last_event_id`] to resume the stream.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use salvo_core::prelude::*;
+//! use salvo_extra::sse::{self, SseEvent, SseKeepAlive};
+//!
+//! #[handler]
+//! async fn handle(req: &mut Request, res: &mut Response) {
+//!     let mut id = sse::last_event_id(req)
+//!         .and_then(|id| id.parse::<u64>().ok())
+//!         .unwrap_or_default();
+//!     let (tx, rx) = sse::channel(16);
+//!     tokio::spawn(async move {
+//!         loop {
+//!             id += 1;
+//!             let event = SseEvent::default().id(id.to_string()).text("tick");
+//!             if tx.send(event).await.is_err() {
+//!                 // client is disconnected.
+//!                 break;
+//!             }
+//!             tokio::time::sleep(Duration::from_secs(1)).await;
+//!         }
+//!     });
+//!     SseKeepAlive::new(rx).stream(res);
+//! }
+//! ```
+//!
 //! See also the [EventSource](https://developer.mozilla.org/en-US/docs/Web/API/EventSource) API,
 //! which specifies the expected behavior of Server Sent Events.
 
 use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::Infallible;
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter, Write};
 use std::pin::Pin;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
 use std::time::Duration;
 
 use futures_util::future;
 use futures_util::stream::{Stream, TryStream, TryStreamExt};
 use pin_project::pin_project;
-use salvo_core::http::header::{HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
+use salvo_core::http::header::{HeaderName, HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
+use tokio::sync::{broadcast, mpsc};
 use tokio::time::{self, Sleep};
 
-use salvo_core::http::Response;
+use salvo_core::http::{Request, Response};
+
+/// The header sent by a reconnecting client with the id of the last event it received.
+pub const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+/// Returns the id of the last event received by the client from the `Last-Event-ID` header.
+///
+/// The client sends it when reconnecting, so the stream can be resumed after that event.
+#[inline]
+pub fn last_event_id(req: &Request) -> Option<&str> {
+    req.headers()
+        .get(LAST_EVENT_ID)
+        .and_then(|value| value.to_str().ok())
+}
 
 /// Server-sent event data type
 #[derive(Clone, Debug)]
@@ -114,9 +162,11 @@ impl SseEvent {
     }
 
     /// Sets Server-sent event event.
+    ///
+    /// Line breaks are removed, since they would end the field.
     #[inline]
     pub fn name<T: Into<String>>(mut self, event: T) -> SseEvent {
-        self.name = Some(event.into());
+        self.name = Some(strip_line_breaks(event.into()));
         self
     }
 
@@ -128,13 +178,24 @@ impl SseEvent {
     }
 
     /// Sets Server-sent event id.
+    ///
+    /// Line breaks and null characters are removed, since they are not allowed in the id.
     #[inline]
     pub fn id<T: Into<String>>(mut self, id: T) -> SseEvent {
-        self.id = Some(id.into());
+        self.id = Some(strip_line_breaks(id.into()).replace('\0', ""));
         self
     }
 }
 
+#[inline]
+fn strip_line_breaks(value: String) -> String {
+    if value.contains(['\r', '\n']) {
+        value.replace(['\r', '\n'], "")
+    } else {
+        value
+    }
+}
+
 impl Display for SseEvent {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         if let Some(comment) = &self.comment {
@@ -238,6 +299,7 @@ where
     #[inline]
     pub fn max_interval(mut self, time: Duration) -> Self {
         self.max_interval = time;
+        self.alive_timer = time::sleep(time);
         self
     }
 
@@ -257,6 +319,223 @@ where
     }
 }
 
+/// Creates a channel to send events from another task, the [`SseReceiver`] is sent to the client as
+/// event stream by [`stream`] or [`SseKeepAlive`].
+///
+/// `buffer` is the max number of events waiting to be sent, [`SseSender::send`] waits when the
+/// buffer is full.
+#[inline]
+pub fn channel(buffer: usize) -> (SseSender, SseReceiver) {
+    let (tx, rx) = mpsc::channel(buffer);
+    (SseSender(tx), SseReceiver(rx))
+}
+
+/// Sending half of the event channel created by [`channel`].
+#[derive(Clone, Debug)]
+pub struct SseSender(mpsc::Sender<SseEvent>);
+impl SseSender {
+    /// Sends an event to the client.
+    ///
+    /// Returns [`SseError`] if the client is disconnected.
+    #[inline]
+    pub async fn send(&self, event: SseEvent) -> Result<(), SseError> {
+        self.0.send(event).await.map_err(|_| SseError)
+    }
+
+    /// Returns `true` if the client is disconnected.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    /// Completes when the client is disconnected.
+    #[inline]
+    pub async fn closed(&self) {
+        self.0.closed().await
+    }
+}
+
+/// Receiving half of the event channel created by [`channel`].
+///
+/// It is dropped when the client is disconnected, which is surfaced to the [`SseSender`].
+#[derive(Debug)]
+pub struct SseReceiver(mpsc::Receiver<SseEvent>);
+impl Stream for SseReceiver {
+    type Item = Result<SseEvent, Infallible>;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.get_mut().0.poll_recv(cx).map(|event| event.map(Ok))
+    }
+}
+
+/// Policy applied when a subscriber of [`Broadcaster`] can not keep up with the published events.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[non_exhaustive]
+pub enum LagPolicy {
+    /// Drop the oldest buffered events and continue with the newer ones.
+    #[default]
+    DropOldest,
+    /// Disconnect the lagging client, it can reconnect and resume with [`last_event_id`].
+    Disconnect,
+}
+
+/// A hub which publishes events to many SSE clients subscribed to topics.
+///
+/// Each topic buffers at most `capacity` events for every subscriber, what happens when a
+/// subscriber lags behind is decided by the [`LagPolicy`]. Cloned broadcasters share the same
+/// topics, so it can be injected into handlers by `affix_state`.
+///
+/// # Example
+///
+/// ```no_run
+/// use salvo_core::prelude::*;
+/// use salvo_extra::sse::{Broadcaster, SseEvent, SseKeepAlive};
+///
+/// #[handler]
+/// async fn subscribe(depot: &mut Depot, res: &mut Response) {
+///     let broadcaster = depot.obtain::<Broadcaster>().unwrap();
+///     SseKeepAlive::new(broadcaster.subscribe("dashboard")).stream(res);
+/// }
+/// #[handler]
+/// async fn publish(depot: &mut Depot) -> String {
+///     let broadcaster = depot.obtain::<Broadcaster>().unwrap();
+///     let count = broadcaster.publish("dashboard", SseEvent::default().text("updated"));
+///     format!("sent to {count} clients")
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Broadcaster {
+    topics: Arc<Mutex<HashMap<String, broadcast::Sender<SseEvent>>>>,
+    capacity: usize,
+    lag_policy: LagPolicy,
+}
+impl Default for Broadcaster {
+    #[inline]
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+impl Broadcaster {
+    /// Create new `Broadcaster` buffering at most `capacity` events for every subscriber.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is `0`.
+    #[inline]
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "broadcaster capacity must be greater than 0");
+        Self {
+            topics: Default::default(),
+            capacity,
+            lag_policy: LagPolicy::default(),
+        }
+    }
+
+    /// Sets the [`LagPolicy`] of subscribers.
+    ///
+    /// Default is [`LagPolicy::DropOldest`].
+    #[inline]
+    pub fn lag_policy(mut self, lag_policy: LagPolicy) -> Self {
+        self.lag_policy = lag_policy;
+        self
+    }
+
+    /// Subscribe to the topic, the returned stream is sent to the client by [`stream`] or
+    /// [`SseKeepAlive`].
+    pub fn subscribe(&self, topic: impl Into<String>) -> SseSubscription {
+        let receiver = self
+            .topics
+            .lock()
+            .expect("broadcaster topics lock poisoned")
+            .entry(topic.into())
+            .or_insert_with(|| broadcast::channel(self.capacity).0)
+            .subscribe();
+        let lag_policy = self.lag_policy;
+        let inner = futures_util::stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((Ok(event), receiver)),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "sse subscriber lagged behind");
+                        if lag_policy == LagPolicy::Disconnect {
+                            return None;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+        SseSubscription(Box::pin(inner))
+    }
+
+    /// Publish the event to the subscribers of the topic, returns the number of subscribers.
+    ///
+    /// The topic is removed once it has no subscriber.
+    pub fn publish(&self, topic: &str, event: SseEvent) -> usize {
+        let mut topics = self.topics.lock().expect("broadcaster topics lock poisoned");
+        let Some(sender) = topics.get(topic) else {
+            return 0;
+        };
+        match sender.send(event) {
+            Ok(count) => count,
+            Err(_) => {
+                topics.remove(topic);
+                0
+            }
+        }
+    }
+
+    /// Publish the event to the subscribers of all topics, returns the number of subscribers.
+    pub fn publish_all(&self, event: SseEvent) -> usize {
+        let mut topics = self.topics.lock().expect("broadcaster topics lock poisoned");
+        let mut total = 0;
+        topics.retain(|_, sender| match sender.send(event.clone()) {
+            Ok(count) => {
+                total += count;
+                true
+            }
+            Err(_) => false,
+        });
+        total
+    }
+
+    /// Returns the number of subscribers of the topic.
+    pub fn subscriber_count(&self, topic: &str) -> usize {
+        self.topics
+            .lock()
+            .expect("broadcaster topics lock poisoned")
+            .get(topic)
+            .map(|sender| sender.receiver_count())
+            .unwrap_or_default()
+    }
+
+    /// Returns the topics which have subscribers.
+    pub fn topics(&self) -> Vec<String> {
+        self.topics
+            .lock()
+            .expect("broadcaster topics lock poisoned")
+            .iter()
+            .filter(|(_, sender)| sender.receiver_count() > 0)
+            .map(|(topic, _)| topic.clone())
+            .collect()
+    }
+}
+
+/// Event stream of a topic subscribed by [`Broadcaster::subscribe`].
+///
+/// It ends when the client is disconnected by [`LagPolicy::Disconnect`].
+#[allow(missing_debug_implementations)]
+pub struct SseSubscription(Pin<Box<dyn Stream<Item = Result<SseEvent, Infallible>> + Send>>);
+impl Stream for SseSubscription {
+    type Item = Result<SseEvent, Infallible>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
+
 #[inline]
 fn write_response_headers(res: &mut Response) {
     res.headers_mut()
@@ -322,6 +601,7 @@ mod tests {
     use std::convert::Infallible;
     use std::time::Duration;
 
+    use futures_util::StreamExt;
     use salvo_core::prelude::*;
     use salvo_core::test::ResponseExt;
     use tokio_stream;
@@ -405,6 +685,86 @@ mod tests {
         assert!(text.contains("retry:1001"));
     }
 
+    #[tokio::test]
+    async fn test_sse_keep_alive_comment() {
+        let event_stream = futures_util::stream::pending::<Result<SseEvent, Infallible>>();
+        let keep_alive = SseKeepAlive::new(event_stream)
+            .comment("ping")
+            .max_interval(Duration::from_millis(10));
+        tokio::pin!(keep_alive);
+        let event = keep_alive.next().await.unwrap().unwrap();
+        assert_eq!(event.to_string(), ":ping\n\n");
+    }
+
+    #[tokio::test]
+    async fn test_sse_channel() {
+        let (tx, rx) = super::channel(2);
+        tx.send(SseEvent::default().text("1")).await.unwrap();
+        drop(tx);
+        let mut res = Response::new();
+        super::stream(&mut res, rx);
+        let text = res.take_string().await.unwrap();
+        assert!(text.contains("data:1"));
+
+        let (tx, rx) = super::channel(2);
+        drop(rx);
+        assert!(tx.is_closed());
+        assert!(tx.send(SseEvent::default().text("1")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sse_broadcaster() {
+        let broadcaster = Broadcaster::new(4);
+        let mut news = broadcaster.subscribe("news");
+        let mut all = broadcaster.clone().subscribe("sports");
+        assert_eq!(broadcaster.subscriber_count("news"), 1);
+        assert_eq!(broadcaster.publish("news", SseEvent::default().text("1")), 1);
+        assert_eq!(broadcaster.publish("weather", SseEvent::default().text("2")), 0);
+        assert_eq!(broadcaster.publish_all(SseEvent::default().text("3")), 2);
+
+        assert_eq!(news.next().await.unwrap().unwrap().to_string(), "data:1\n\n");
+        assert_eq!(news.next().await.unwrap().unwrap().to_string(), "data:3\n\n");
+        assert_eq!(all.next().await.unwrap().unwrap().to_string(), "data:3\n\n");
+
+        drop(news);
+        assert_eq!(broadcaster.subscriber_count("news"), 0);
+        assert_eq!(broadcaster.publish("news", SseEvent::default().text("4")), 0);
+        assert_eq!(broadcaster.topics(), vec!["sports".to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_sse_broadcaster_lag_policy() {
+        let broadcaster = Broadcaster::new(1);
+        let mut subscription = broadcaster.subscribe("news");
+        broadcaster.publish("news", SseEvent::default().text("1"));
+        broadcaster.publish("news", SseEvent::default().text("2"));
+        assert_eq!(
+            subscription.next().await.unwrap().unwrap().to_string(),
+            "data:2\n\n"
+        );
+
+        let broadcaster = Broadcaster::new(1).lag_policy(LagPolicy::Disconnect);
+        let mut subscription = broadcaster.subscribe("news");
+        broadcaster.publish("news", SseEvent::default().text("1"));
+        broadcaster.publish("news", SseEvent::default().text("2"));
+        assert!(subscription.next().await.is_none());
+    }
+
+    #[test]
+    fn test_sse_last_event_id() {
+        let mut req = Request::new();
+        assert!(last_event_id(&req).is_none());
+        req.headers_mut()
+            .insert(LAST_EVENT_ID, HeaderValue::from_static("13"));
+        assert_eq!(last_event_id(&req), Some("13"));
+    }
+
+    #[test]
+    fn test_sse_strip_line_breaks() {
+        let event = SseEvent::default().name("chat\nid:1").id("2\r\n");
+        assert_eq!(event.to_string(), "event:chatid:1\nid:2\n\n");
+    }
+
     #[tokio::test]
     async fn test_sse_id() {
         let event_stream = tokio_stream::iter(vec![Ok::<_, Infallible>(SseEvent::default().id("jobs"))]);
@@ -1,10 +1,23 @@
 //! Middleware for controlling requests timeout.
 //!
-//! If the request does not complete within the specified timeout it will be aborted and a `503 Service Unavailable`
+//! If the request does not complete within the specified timeout it will be aborted and a `504 Gateway Timeout`
 //! response will be sent.
 //!
 //! This middleware can be used to deal with slow network attacks.
 //!
+//! Besides the budget of the handlers execution, separate budgets can be set for:
+//!
+//! - reading the request body, with [`Timeout::body_read`]. When it elapsed, a
+//!   `408 Request Timeout` response is sent, since the client is too slow.
+//! - writing the streaming response body, with [`Timeout::response_write`]. When it elapsed, the
+//!   connection is aborted.
+//!
+//! The budget of reading the request headers is set on the server, with
+//! `HttpBuilder::http1_header_read_timeout`, since the headers are read before any middleware runs.
+//!
+//! Trusted internal callers can override the handlers budget with the `X-Request-Timeout` header,
+//! in seconds, when allowed by [`Timeout::allow_override`].
+//!
 //! # Example
 //!
 //! ```no_run
@@ -35,11 +48,69 @@
 //! }
 //! ```
 
+use std::future::Future;
+use std::io::{Error as IoError, ErrorKind};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
+use salvo_core::http::body::{ReqBody, ResBody};
 use salvo_core::http::headers::{Connection, HeaderMapExt};
 use salvo_core::http::{Request, Response, StatusError};
-use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
+use salvo_core::hyper::body::{Body, Bytes, Frame, SizeHint};
+use salvo_core::{async_trait, BoxedError, Depot, FlowCtrl, Handler};
+use tokio::time::Sleep;
+
+/// The header used by trusted callers to override the handlers budget, in seconds.
+pub const REQUEST_TIMEOUT_HEADER: &str = "x-request-timeout";
+
+/// Body which fails when the deadline elapsed before it is fully read.
+struct DeadlineBody<B> {
+    inner: Pin<Box<B>>,
+    sleep: Pin<Box<Sleep>>,
+    expired: Arc<AtomicBool>,
+}
+impl<B> DeadlineBody<B> {
+    fn new(inner: B, budget: Duration, expired: Arc<AtomicBool>) -> Self {
+        Self {
+            inner: Box::pin(inner),
+            sleep: Box::pin(tokio::time::sleep(budget)),
+            expired,
+        }
+    }
+}
+impl<B> Body for DeadlineBody<B>
+where
+    B: Body<Data = Bytes>,
+    B::Error: Into<BoxedError>,
+{
+    type Data = Bytes;
+    type Error = BoxedError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxedError>>> {
+        if let Poll::Ready(frame) = self.inner.as_mut().poll_frame(cx) {
+            return Poll::Ready(frame.map(|frame| frame.map_err(Into::into)));
+        }
+        if self.sleep.as_mut().poll(cx).is_ready() {
+            self.expired.store(true, Ordering::Relaxed);
+            return Poll::Ready(Some(Err(IoError::new(ErrorKind::TimedOut, "body timeout").into())));
+        }
+        Poll::Pending
+    }
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+type AllowOverride = dyn Fn(&Request, &Depot) -> bool + Send + Sync;
 
 /// Middleware for controlling request timeout.
 /// 
@@ -47,40 +118,116 @@ use salvo_core::{async_trait, Depot, FlowCtrl, Handler};
 pub struct Timeout {
     value: Duration,
     error: Box<dyn Fn() -> StatusError + Send + Sync + 'static>,
+    body_read: Option<Duration>,
+    response_write: Option<Duration>,
+    allow_override: Option<Box<AllowOverride>>,
 }
 impl Timeout {
     /// Create a new `Timeout`.
     #[inline]
     pub fn new(value: Duration) -> Self {
-        // If a 408 error code is returned, the browser may resend the request multiple times. In most cases,
-        // this behavior is undesirable.
+        // A 408 error code means the client is too slow, and the browser may resend the request multiple times.
         // https://github.com/tower-rs/tower-http/issues/300
         Timeout {
             value,
-            error: Box::new(|| StatusError::service_unavailable().brief("Server process the request timeout.")),
+            error: Box::new(|| StatusError::gateway_timeout().brief("Server process the request timeout.")),
+            body_read: None,
+            response_write: None,
+            allow_override: None,
         }
     }
 
     /// Custom error returned when timeout.
     ///
-    /// By default, a `503 Service Unavailable` error is returned. You can set this function to other error types,
-    /// such as `403 Request Timeout`, but the 403 error code may cause the browser to automatically resend the
-    /// request multiple times.
+    /// By default, a `504 Gateway Timeout` error is returned. You can set this function to other error types,
+    /// such as `503 Service Unavailable`. Avoid `408 Request Timeout`, it means the client is too slow and may
+    /// cause the browser to automatically resend the request multiple times.
     pub fn error(mut self, error: impl Fn() -> StatusError + Send + Sync + 'static) -> Self {
         self.error = Box::new(error);
         self
     }
+
+    /// Set the budget of reading the request body, counted from the start of this middleware.
+    ///
+    /// When it elapsed, reading the body fails and a `408 Request Timeout` response is sent.
+    pub fn body_read(mut self, budget: Duration) -> Self {
+        self.body_read = Some(budget);
+        self
+    }
+
+    /// Set the budget of writing the response body, counted from the end of the handlers.
+    ///
+    /// It only applies to streaming bodies, the connection is aborted when it elapsed.
+    pub fn response_write(mut self, budget: Duration) -> Self {
+        self.response_write = Some(budget);
+        self
+    }
+
+    /// Allow the requests for which `allow` returns `true` to override the handlers budget with the
+    /// `X-Request-Timeout` header, in seconds, e.g. the requests from internal services.
+    pub fn allow_override<F>(mut self, allow: F) -> Self
+    where
+        F: Fn(&Request, &Depot) -> bool + Send + Sync + 'static,
+    {
+        self.allow_override = Some(Box::new(allow));
+        self
+    }
+
+    fn handler_budget(&self, req: &Request, depot: &Depot) -> Duration {
+        if let Some(allow) = &self.allow_override {
+            if let Some(budget) = req
+                .header::<String>(REQUEST_TIMEOUT_HEADER)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            {
+                if allow(req, depot) {
+                    return budget;
+                }
+            }
+        }
+        self.value
+    }
 }
 #[async_trait]
 impl Handler for Timeout {
     #[inline]
     async fn handle(&self, req: &mut Request, depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+        let body_expired = Arc::new(AtomicBool::new(false));
+        if let Some(budget) = self.body_read {
+            let body = req.take_body();
+            if matches!(body, ReqBody::None | ReqBody::Once(_)) {
+                req.replace_body(body);
+            } else {
+                let body = DeadlineBody::new(body, budget, body_expired.clone());
+                req.replace_body(ReqBody::Boxed {
+                    inner: Box::pin(body),
+                    fusewire: None,
+                });
+            }
+        }
+        let budget = self.handler_budget(req, depot);
         tokio::select! {
             _ = ctrl.call_next(req, depot, res) => {},
-            _ = tokio::time::sleep(self.value) => {
-                res.headers_mut().typed_insert(Connection::close());
-                res.render((self.error)());
-                ctrl.skip_rest();
+            _ = tokio::time::sleep(budget) => {
+                if !body_expired.load(Ordering::Relaxed) {
+                    res.headers_mut().typed_insert(Connection::close());
+                    res.render((self.error)());
+                    ctrl.skip_rest();
+                    return;
+                }
+            }
+        }
+        if body_expired.load(Ordering::Relaxed) {
+            res.headers_mut().typed_insert(Connection::close());
+            res.render(StatusError::request_timeout().brief("Client sent the request body too slowly."));
+            ctrl.skip_rest();
+            return;
+        }
+        if let Some(budget) = self.response_write {
+            let body = &res.body;
+            if body.is_hyper() || body.is_boxed() || body.is_stream() || body.is_channel() {
+                let body = DeadlineBody::new(res.take_body(), budget, Arc::default());
+                res.body(ResBody::Boxed(Box::pin(body)));
             }
         }
     }
@@ -127,4 +274,88 @@ mod tests {
             .unwrap();
         assert!(content.contains("hello"));
     }
+
+    #[tokio::test]
+    async fn test_timeout_status() {
+        #[handler]
+        async fn slow() -> &'static str {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            "hello"
+        }
+
+        let service = Service::new(Router::new().hoop(Timeout::new(Duration::from_millis(10))).get(slow));
+        let res = TestClient::get("http://127.0.0.1:5801/").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::GATEWAY_TIMEOUT));
+
+        let service = Service::new(
+            Router::new()
+                .hoop(Timeout::new(Duration::from_millis(10)).error(StatusError::service_unavailable))
+                .get(slow),
+        );
+        let res = TestClient::get("http://127.0.0.1:5801/").send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    struct Stalled;
+    impl Body for Stalled {
+        type Data = Bytes;
+        type Error = BoxedError;
+        fn poll_frame(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, BoxedError>>> {
+            Poll::Pending
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_body_read() {
+        #[handler]
+        async fn upload(req: &mut Request) -> &'static str {
+            if req.payload().await.is_ok() { "uploaded" } else { "failed" }
+        }
+
+        let router = Router::new()
+            .hoop(Timeout::new(Duration::from_secs(5)).body_read(Duration::from_millis(50)))
+            .post(upload);
+        let service = Service::new(router);
+
+        let mut req = TestClient::post("http://127.0.0.1:5801/").build();
+        req.replace_body(ReqBody::Boxed {
+            inner: Box::pin(Stalled),
+            fusewire: None,
+        });
+        let res = service.handle(req).await;
+        assert_eq!(res.status_code, Some(StatusCode::REQUEST_TIMEOUT));
+
+        let mut res = TestClient::post("http://127.0.0.1:5801/").text("data").send(&service).await;
+        assert_eq!(res.take_string().await.unwrap(), "uploaded");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_override() {
+        #[handler]
+        async fn slow() -> &'static str {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            "hello"
+        }
+
+        let router = Router::new()
+            .hoop(
+                Timeout::new(Duration::from_millis(10))
+                    .allow_override(|req, _| req.header::<String>("x-internal").is_some()),
+            )
+            .get(slow);
+        let service = Service::new(router);
+
+        let res = TestClient::get("http://127.0.0.1:5801/")
+            .add_header(REQUEST_TIMEOUT_HEADER, "5", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::GATEWAY_TIMEOUT));
+
+        let res = TestClient::get("http://127.0.0.1:5801/")
+            .add_header(REQUEST_TIMEOUT_HEADER, "5", true)
+            .add_header("x-internal", "1", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+    }
 }
@@ -0,0 +1,402 @@
+//! Middleware for opaque token authentication, such as API keys.
+//!
+//! [`TokenAuth`] extracts a token from the `Authorization: Bearer` header, another header, a
+//! query parameter or a cookie, and validates it with a [`TokenValidator`]. It is meant for the
+//! services which use opaque API keys rather than JWTs, see `salvo_jwt_auth` for the latter.
+//!
+//! The validator is async, so the tokens can be checked against a database. [`StaticTokens`]
+//! validates a fixed list of tokens, looked up by their SHA-256 hash.
+//!
+//! Once authenticated, the [`TokenIdentity`] with its scopes is stored in the [`Depot`], see
+//! [`TokenAuthDepotExt`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::token_auth::{
+//!     StaticTokens, TokenAuth, TokenAuthDepotExt, TokenIdentity, TokenSource,
+//! };
+//!
+//! #[handler]
+//! async fn hello(depot: &mut Depot) -> String {
+//!     let identity = depot.token_identity().unwrap();
+//!     format!("Hello {}", identity.subject)
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let tokens = StaticTokens::new()
+//!         .token("secret-key", TokenIdentity::new("ci").scopes(["deploy"]));
+//!     let auth = TokenAuth::new(tokens)
+//!         .sources([TokenSource::Bearer, TokenSource::header("x-api-key")])
+//!         .required_scopes(["deploy"]);
+//!     let router = Router::with_hoop(auth).get(hello);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter, Write};
+
+use salvo_core::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use salvo_core::http::{HeaderName, HeaderValue, Request, Response, StatusError};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+use sha2::{Digest, Sha256};
+
+/// The identity of an authenticated token, stored in the [`Depot`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TokenIdentity {
+    /// The subject of the token, e.g. a user or service name.
+    pub subject: String,
+    /// The scopes granted to the token.
+    pub scopes: Vec<String>,
+}
+impl TokenIdentity {
+    /// Create new `TokenIdentity` without scope.
+    #[inline]
+    pub fn new(subject: impl Into<String>) -> Self {
+        Self {
+            subject: subject.into(),
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Set the scopes granted to the token.
+    #[inline]
+    pub fn scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns `true` if the scope is granted to the token.
+    #[inline]
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Validator of the tokens.
+pub trait TokenValidator: Send + Sync {
+    /// Returns the identity of the token, `None` if it is not valid.
+    fn validate(
+        &self,
+        token: &str,
+        depot: &mut Depot,
+    ) -> impl Future<Output = Option<TokenIdentity>> + Send;
+}
+
+/// Returns the hex encoded SHA-256 hash of the token, as used by [`StaticTokens::hashed`].
+pub fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hash, byte| {
+            let _ = write!(hash, "{byte:02x}");
+            hash
+        })
+}
+
+/// Validator of a fixed list of tokens.
+///
+/// The tokens are stored and looked up by their SHA-256 hash, so the lookup does not leak the
+/// token content through timing, and the tokens can be configured by their hash only.
+#[derive(Clone, Default)]
+pub struct StaticTokens {
+    tokens: HashMap<String, TokenIdentity>,
+}
+impl StaticTokens {
+    /// Create new empty `StaticTokens`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a token with its identity.
+    #[inline]
+    pub fn token(self, token: &str, identity: TokenIdentity) -> Self {
+        self.hashed(hash_token(token), identity)
+    }
+
+    /// Add a token by its hex encoded SHA-256 hash, see [`hash_token`].
+    #[inline]
+    pub fn hashed(mut self, hash: impl Into<String>, identity: TokenIdentity) -> Self {
+        self.tokens
+            .insert(hash.into().to_ascii_lowercase(), identity);
+        self
+    }
+}
+impl Debug for StaticTokens {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("StaticTokens")
+            .field("tokens", &self.tokens.len())
+            .finish()
+    }
+}
+impl TokenValidator for StaticTokens {
+    async fn validate(&self, token: &str, _depot: &mut Depot) -> Option<TokenIdentity> {
+        self.tokens.get(&hash_token(token)).cloned()
+    }
+}
+
+/// Where the token is extracted from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TokenSource {
+    /// The `Authorization` header with the `Bearer` scheme.
+    Bearer,
+    /// The whole value of a header, e.g. `X-API-Key`.
+    Header(HeaderName),
+    /// A query parameter.
+    Query(String),
+    /// A cookie.
+    Cookie(String),
+}
+impl TokenSource {
+    /// Create new `TokenSource::Header`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid header name.
+    #[inline]
+    pub fn header(name: &str) -> Self {
+        Self::Header(HeaderName::try_from(name).expect("invalid header name"))
+    }
+    /// Create new `TokenSource::Query`.
+    #[inline]
+    pub fn query(name: impl Into<String>) -> Self {
+        Self::Query(name.into())
+    }
+    /// Create new `TokenSource::Cookie`.
+    #[inline]
+    pub fn cookie(name: impl Into<String>) -> Self {
+        Self::Cookie(name.into())
+    }
+
+    fn find(&self, req: &Request) -> Option<String> {
+        let token = match self {
+            Self::Bearer => {
+                let value = req.headers().get(AUTHORIZATION)?.to_str().ok()?;
+                let (scheme, token) = value.split_once(' ')?;
+                if !scheme.eq_ignore_ascii_case("bearer") {
+                    return None;
+                }
+                token.trim().to_owned()
+            }
+            Self::Header(name) => req.headers().get(name)?.to_str().ok()?.trim().to_owned(),
+            Self::Query(name) => req.query::<String>(name)?,
+            Self::Cookie(name) => req.cookie(name)?.value().to_owned(),
+        };
+        (!token.is_empty()).then_some(token)
+    }
+}
+
+/// Extension trait for retrieving the authenticated identity from a Depot.
+pub trait TokenAuthDepotExt {
+    /// Returns the identity of the token if authentication was successful.
+    fn token_identity(&self) -> Option<&TokenIdentity>;
+}
+impl TokenAuthDepotExt for Depot {
+    #[inline]
+    fn token_identity(&self) -> Option<&TokenIdentity> {
+        self.obtain::<TokenIdentity>().ok()
+    }
+}
+
+/// Middleware for opaque token authentication.
+///
+/// View [module level documentation](index.html) for more details.
+pub struct TokenAuth<V> {
+    validator: V,
+    sources: Vec<TokenSource>,
+    required_scopes: Vec<String>,
+    optional: bool,
+}
+impl<V: TokenValidator> TokenAuth<V> {
+    /// Create new `TokenAuth` which extracts the token from the `Authorization: Bearer` header.
+    #[inline]
+    pub fn new(validator: V) -> Self {
+        Self {
+            validator,
+            sources: vec![TokenSource::Bearer],
+            required_scopes: Vec::new(),
+            optional: false,
+        }
+    }
+
+    /// Set where the token is extracted from, the first found token is validated.
+    #[inline]
+    pub fn sources(mut self, sources: impl IntoIterator<Item = TokenSource>) -> Self {
+        self.sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Reject the tokens which are not granted all these scopes with `403 Forbidden`.
+    #[inline]
+    pub fn required_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.required_scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Let the requests without token pass without identity, the invalid tokens are still
+    /// rejected. Default is `false`.
+    #[inline]
+    pub fn optional(mut self, optional: bool) -> Self {
+        self.optional = optional;
+        self
+    }
+}
+impl<V> Debug for TokenAuth<V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("TokenAuth")
+            .field("sources", &self.sources)
+            .field("required_scopes", &self.required_scopes)
+            .field("optional", &self.optional)
+            .finish()
+    }
+}
+
+fn unauthorized(res: &mut Response, error: Option<&'static str>) {
+    let challenge = match error {
+        Some(error) => format!("Bearer error=\"{error}\""),
+        None => "Bearer".to_owned(),
+    };
+    if let Ok(challenge) = HeaderValue::from_str(&challenge) {
+        res.headers_mut().insert(WWW_AUTHENTICATE, challenge);
+    }
+    res.render(StatusError::unauthorized());
+}
+
+#[async_trait]
+impl<V> Handler for TokenAuth<V>
+where
+    V: TokenValidator + 'static,
+{
+    async fn handle(
+        &self,
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        let Some(token) = self.sources.iter().find_map(|source| source.find(req)) else {
+            if !self.optional {
+                unauthorized(res, None);
+                ctrl.skip_rest();
+            }
+            return;
+        };
+        let Some(identity) = self.validator.validate(&token, depot).await else {
+            unauthorized(res, Some("invalid_token"));
+            ctrl.skip_rest();
+            return;
+        };
+        if !self
+            .required_scopes
+            .iter()
+            .all(|scope| identity.has_scope(scope))
+        {
+            res.render(
+                StatusError::forbidden().brief("The token is not granted the required scopes."),
+            );
+            ctrl.skip_rest();
+            return;
+        }
+        depot.inject(identity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[handler]
+    async fn whoami(depot: &mut Depot) -> String {
+        depot
+            .token_identity()
+            .map(|identity| identity.subject.clone())
+            .unwrap_or_else(|| "anonymous".into())
+    }
+
+    #[tokio::test]
+    async fn test_token_auth() {
+        let tokens = StaticTokens::new()
+            .token(
+                "admin-key",
+                TokenIdentity::new("admin").scopes(["read", "write"]),
+            )
+            .hashed(
+                hash_token("reader-key"),
+                TokenIdentity::new("reader").scopes(["read"]),
+            );
+        let auth = TokenAuth::new(tokens)
+            .sources([
+                TokenSource::Bearer,
+                TokenSource::header("x-api-key"),
+                TokenSource::query("api_key"),
+            ])
+            .required_scopes(["read"]);
+        let service = Service::new(Router::with_hoop(auth).goal(whoami));
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/")
+            .add_header("authorization", "Bearer admin-key", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "admin");
+        let mut res = TestClient::get("http://127.0.0.1:5801/")
+            .add_header("x-api-key", "reader-key", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "reader");
+        let mut res = TestClient::get("http://127.0.0.1:5801/?api_key=reader-key")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "reader");
+
+        let res = TestClient::get("http://127.0.0.1:5801/")
+            .add_header("authorization", "Bearer wrong-key", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+        assert_eq!(
+            res.headers().get(WWW_AUTHENTICATE).unwrap(),
+            "Bearer error=\"invalid_token\""
+        );
+        let res = TestClient::get("http://127.0.0.1:5801/")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn test_token_auth_scopes_and_optional() {
+        let tokens =
+            StaticTokens::new().token("reader-key", TokenIdentity::new("reader").scopes(["read"]));
+        let auth = TokenAuth::new(tokens)
+            .required_scopes(["write"])
+            .optional(true);
+        let service = Service::new(Router::with_hoop(auth).goal(whoami));
+
+        let res = TestClient::get("http://127.0.0.1:5801/")
+            .add_header("authorization", "Bearer reader-key", true)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+        let mut res = TestClient::get("http://127.0.0.1:5801/")
+            .send(&service)
+            .await;
+        assert_eq!(res.take_string().await.unwrap(), "anonymous");
+    }
+}
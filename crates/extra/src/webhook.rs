@@ -0,0 +1,438 @@
+//! Webhook signature verification middleware.
+//!
+//! [`WebhookVerifier`] verifies the HMAC-SHA256 signature of the request body sent by webhook
+//! providers, and rejects the requests with an invalid signature with `401 Unauthorized`. The
+//! body is buffered by [`Request::buffer_body`], so the handler still gets the payload.
+//!
+//! The [`SignatureScheme`] presets cover GitHub, Stripe and Slack, and
+//! [`SignatureScheme::custom`] covers the other providers which send a hex signature in a
+//! header. For the schemes with a timestamp, the requests older than the
+//! [`tolerance`](WebhookVerifier::tolerance) are rejected, and the signatures already seen in
+//! this window are rejected as replays.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use salvo_core::prelude::*;
+//! use salvo_extra::webhook::{SignatureScheme, WebhookVerifier};
+//!
+//! #[handler]
+//! async fn github(req: &mut Request) -> StatusCode {
+//!     let event = req.payload().await.unwrap();
+//!     tracing::info!(size = event.len(), "webhook received");
+//!     StatusCode::NO_CONTENT
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let verifier = WebhookVerifier::new(SignatureScheme::github(), "webhook-secret");
+//!     let router = Router::with_path("webhooks/github").hoop(verifier).post(github);
+//!
+//!     let acceptor = TcpListener::new("0.0.0.0:5800").bind().await;
+//!     Server::new(acceptor).serve(router).await;
+//! }
+//! ```
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use salvo_core::http::{HeaderName, ParseError, Request, Response, StatusError};
+use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Clone, Debug)]
+enum SchemeKind {
+    /// `t=<timestamp>,v1=<signature>` in a single header, signed payload `<timestamp>.<body>`.
+    Stripe,
+    /// A signature header with an optional timestamp header.
+    Header {
+        signature_header: HeaderName,
+        signature_prefix: String,
+        timestamp_header: Option<HeaderName>,
+        payload_prefix: String,
+        payload_separator: String,
+    },
+}
+
+/// The signature scheme of a webhook provider.
+#[derive(Clone, Debug)]
+pub struct SignatureScheme {
+    kind: SchemeKind,
+}
+impl SignatureScheme {
+    /// GitHub scheme: `X-Hub-Signature-256: sha256=<hex>` of the body, without timestamp.
+    pub fn github() -> Self {
+        Self::custom(HeaderName::from_static("x-hub-signature-256")).signature_prefix("sha256=")
+    }
+
+    /// Stripe scheme: `Stripe-Signature: t=<timestamp>,v1=<hex>` of `<timestamp>.<body>`. The
+    /// secret is the whole `whsec_` string.
+    pub fn stripe() -> Self {
+        Self {
+            kind: SchemeKind::Stripe,
+        }
+    }
+
+    /// Slack scheme: `X-Slack-Signature: v0=<hex>` of `v0:<timestamp>:<body>`, with the
+    /// timestamp in `X-Slack-Request-Timestamp`.
+    pub fn slack() -> Self {
+        Self::custom(HeaderName::from_static("x-slack-signature"))
+            .signature_prefix("v0=")
+            .timestamp_header(HeaderName::from_static("x-slack-request-timestamp"))
+            .payload_format("v0:", ":")
+    }
+
+    /// Custom scheme with the hex signature of the body in `signature_header`.
+    pub fn custom(signature_header: HeaderName) -> Self {
+        Self {
+            kind: SchemeKind::Header {
+                signature_header,
+                signature_prefix: String::new(),
+                timestamp_header: None,
+                payload_prefix: String::new(),
+                payload_separator: ".".into(),
+            },
+        }
+    }
+
+    /// Set the prefix of the signature header value, e.g. `sha256=`. It has no effect on the
+    /// Stripe scheme.
+    pub fn signature_prefix(mut self, prefix: impl Into<String>) -> Self {
+        if let SchemeKind::Header {
+            signature_prefix, ..
+        } = &mut self.kind
+        {
+            *signature_prefix = prefix.into();
+        }
+        self
+    }
+
+    /// Set the header of the Unix timestamp, then the signed payload is
+    /// `<timestamp>.<body>` by default. It has no effect on the Stripe scheme.
+    pub fn timestamp_header(mut self, name: HeaderName) -> Self {
+        if let SchemeKind::Header {
+            timestamp_header, ..
+        } = &mut self.kind
+        {
+            *timestamp_header = Some(name);
+        }
+        self
+    }
+
+    /// Set the format of the signed payload with a timestamp, which is
+    /// `<prefix><timestamp><separator><body>`. It has no effect on the Stripe scheme.
+    pub fn payload_format(
+        mut self,
+        prefix: impl Into<String>,
+        separator: impl Into<String>,
+    ) -> Self {
+        if let SchemeKind::Header {
+            payload_prefix,
+            payload_separator,
+            ..
+        } = &mut self.kind
+        {
+            *payload_prefix = prefix.into();
+            *payload_separator = separator.into();
+        }
+        self
+    }
+
+    /// Extract the timestamp, the signatures and the prefix of the signed payload.
+    fn extract(&self, req: &Request) -> Option<(Option<u64>, Vec<Vec<u8>>, String)> {
+        match &self.kind {
+            SchemeKind::Stripe => {
+                let value = req.headers().get("stripe-signature")?.to_str().ok()?;
+                let mut timestamp = None;
+                let mut signatures = Vec::new();
+                for pair in value.split(',') {
+                    match pair.trim().split_once('=') {
+                        Some(("t", t)) => timestamp = t.parse::<u64>().ok(),
+                        Some(("v1", signature)) => {
+                            signatures.extend(hex::decode(signature).ok());
+                        }
+                        _ => {}
+                    }
+                }
+                let timestamp = timestamp?;
+                Some((Some(timestamp), signatures, format!("{timestamp}.")))
+            }
+            SchemeKind::Header {
+                signature_header,
+                signature_prefix,
+                timestamp_header,
+                payload_prefix,
+                payload_separator,
+            } => {
+                let value = req.headers().get(signature_header)?.to_str().ok()?;
+                let signature =
+                    hex::decode(value.trim().strip_prefix(signature_prefix.as_str())?).ok()?;
+                match timestamp_header {
+                    Some(name) => {
+                        let timestamp = req
+                            .headers()
+                            .get(name)?
+                            .to_str()
+                            .ok()?
+                            .trim()
+                            .parse::<u64>()
+                            .ok()?;
+                        let prefix = format!("{payload_prefix}{timestamp}{payload_separator}");
+                        Some((Some(timestamp), vec![signature], prefix))
+                    }
+                    None => Some((None, vec![signature], String::new())),
+                }
+            }
+        }
+    }
+}
+
+/// Webhook signature verification middleware.
+///
+/// View [module level documentation](index.html) for more details.
+#[derive(Clone)]
+pub struct WebhookVerifier {
+    scheme: SignatureScheme,
+    secrets: Vec<Vec<u8>>,
+    tolerance: Duration,
+    replay_protection: bool,
+    body_limit: usize,
+    seen: Arc<Mutex<HashMap<Vec<u8>, Instant>>>,
+}
+impl WebhookVerifier {
+    /// Create new `WebhookVerifier` with the tolerance of 5 minutes, the replay protection
+    /// enabled and the body limit of 1 MiB.
+    pub fn new(scheme: SignatureScheme, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            scheme,
+            secrets: vec![secret.as_ref().to_vec()],
+            tolerance: Duration::from_secs(300),
+            replay_protection: true,
+            body_limit: 1024 * 1024,
+            seen: Arc::default(),
+        }
+    }
+
+    /// Accept the signatures made with another secret too, e.g. while rotating the secret.
+    pub fn secret(mut self, secret: impl AsRef<[u8]>) -> Self {
+        self.secrets.push(secret.as_ref().to_vec());
+        self
+    }
+
+    /// Set the maximum age of the timestamp, for the schemes with a timestamp.
+    pub fn tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Reject the signatures already seen within the tolerance, for the schemes with a
+    /// timestamp. The seen signatures are kept in memory, so they are not shared between the
+    /// server instances. Default is `true`.
+    pub fn replay_protection(mut self, enabled: bool) -> Self {
+        self.replay_protection = enabled;
+        self
+    }
+
+    /// Set the maximum size of the body, the larger bodies are rejected with
+    /// `413 Payload Too Large`.
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = limit;
+        self
+    }
+
+    /// Returns the matched signature if the body is signed by any secret.
+    fn verify_signature(
+        &self,
+        prefix: &str,
+        body: &[u8],
+        signatures: &[Vec<u8>],
+    ) -> Option<Vec<u8>> {
+        for secret in &self.secrets {
+            let Ok(mut mac) = HmacSha256::new_from_slice(secret) else {
+                continue;
+            };
+            mac.update(prefix.as_bytes());
+            mac.update(body);
+            for signature in signatures {
+                if mac.clone().verify_slice(signature).is_ok() {
+                    return Some(signature.clone());
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns `false` if the signature is already seen, and remembers it.
+    fn check_replay(&self, signature: Vec<u8>) -> bool {
+        let now = Instant::now();
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.retain(|_, expires| *expires > now);
+        seen.insert(signature, now + self.tolerance * 2).is_none()
+    }
+
+    async fn verify(&self, req: &mut Request) -> Result<(), StatusError> {
+        let Some((timestamp, signatures, prefix)) = self.scheme.extract(req) else {
+            return Err(
+                StatusError::unauthorized().brief("Missing or malformed webhook signature.")
+            );
+        };
+        if let Some(timestamp) = timestamp {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            if now.abs_diff(timestamp) > self.tolerance.as_secs() {
+                return Err(
+                    StatusError::unauthorized().brief("Webhook timestamp is out of tolerance.")
+                );
+            }
+        }
+        let body = match req.buffer_body(self.body_limit).await {
+            Ok(body) => body,
+            Err(ParseError::PayloadTooLarge) => return Err(StatusError::payload_too_large()),
+            Err(e) => {
+                return Err(StatusError::bad_request()
+                    .brief("Failed to read webhook body.")
+                    .cause(e));
+            }
+        };
+        let Some(signature) = self.verify_signature(&prefix, &body, &signatures) else {
+            return Err(StatusError::unauthorized().brief("Invalid webhook signature."));
+        };
+        if timestamp.is_some() && self.replay_protection && !self.check_replay(signature) {
+            return Err(StatusError::unauthorized().brief("Webhook is already received."));
+        }
+        Ok(())
+    }
+}
+impl Debug for WebhookVerifier {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("WebhookVerifier")
+            .field("scheme", &self.scheme)
+            .field("tolerance", &self.tolerance)
+            .field("replay_protection", &self.replay_protection)
+            .field("body_limit", &self.body_limit)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Handler for WebhookVerifier {
+    async fn handle(
+        &self,
+        req: &mut Request,
+        _depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        if let Err(e) = self.verify(req).await {
+            tracing::debug!(error = ?e, "webhook verification failed");
+            res.render(e);
+            ctrl.skip_rest();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    fn sign(secret: &str, payload: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(payload.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[handler]
+    async fn echo(req: &mut Request) -> String {
+        String::from_utf8(req.payload().await.unwrap().to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_github() {
+        let verifier = WebhookVerifier::new(SignatureScheme::github(), "old").secret("secret");
+        let service = Service::new(Router::new().hoop(verifier).post(echo));
+
+        let body = r#"{"action":"opened"}"#;
+        let mut res = TestClient::post("http://127.0.0.1:5801/")
+            .add_header(
+                "x-hub-signature-256",
+                format!("sha256={}", sign("secret", body)),
+                true,
+            )
+            .body(body)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.take_string().await.unwrap(), body);
+
+        let res = TestClient::post("http://127.0.0.1:5801/")
+            .add_header(
+                "x-hub-signature-256",
+                format!("sha256={}", sign("wrong", body)),
+                true,
+            )
+            .body(body)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn test_slack_and_stripe() {
+        let slack = WebhookVerifier::new(SignatureScheme::slack(), "secret");
+        let stripe = WebhookVerifier::new(SignatureScheme::stripe(), "whsec_secret");
+        let service = Service::new(
+            Router::new()
+                .push(Router::with_path("slack").hoop(slack).post(echo))
+                .push(Router::with_path("stripe").hoop(stripe).post(echo)),
+        );
+        let body = "token=abc&command=/deploy";
+
+        let slack = |timestamp: u64| {
+            TestClient::post("http://127.0.0.1:5801/slack")
+                .add_header("x-slack-request-timestamp", timestamp.to_string(), true)
+                .add_header(
+                    "x-slack-signature",
+                    format!("v0={}", sign("secret", &format!("v0:{timestamp}:{body}"))),
+                    true,
+                )
+                .body(body)
+        };
+        let timestamp = now();
+        let res = slack(timestamp).send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let res = slack(timestamp).send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+        let res = slack(timestamp - 600).send(&service).await;
+        assert_eq!(res.status_code, Some(StatusCode::UNAUTHORIZED));
+
+        let timestamp = now();
+        let signature = sign("whsec_secret", &format!("{timestamp}.{body}"));
+        let mut res = TestClient::post("http://127.0.0.1:5801/stripe")
+            .add_header(
+                "stripe-signature",
+                format!("t={timestamp},v1={signature},v0=ignored"),
+                true,
+            )
+            .body(body)
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(res.take_string().await.unwrap(), body);
+    }
+}
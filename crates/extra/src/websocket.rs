@@ -77,9 +77,17 @@
 //! "#;
 //!```
 
-use std::fmt::{self, Debug, Formatter};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::error::Error as StdError;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
+use std::time::Duration;
 
 use futures_util::sink::{Sink, SinkExt};
 use futures_util::stream::{Stream, StreamExt};
@@ -91,11 +99,15 @@ use salvo_core::http::headers::{
 };
 use salvo_core::http::{StatusCode, StatusError};
 use salvo_core::rt::tokio::TokioIo;
-use salvo_core::{Error, Request, Response};
+use salvo_core::{Error, Request, Response, ShutdownSignal};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
 use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 use tokio_tungstenite::tungstenite::protocol::frame::{CloseFrame, Utf8Bytes};
 use tokio_tungstenite::tungstenite::protocol::{self, WebSocketConfig};
-use tokio_tungstenite::tungstenite::Bytes;
+use tokio_tungstenite::tungstenite::{self, Bytes};
+use tokio::sync::mpsc;
+use tokio::time::{Instant, Sleep};
 use tokio_tungstenite::WebSocketStream;
 
 /// Creates a WebSocket Handler.
@@ -111,9 +123,32 @@ use tokio_tungstenite::WebSocketStream;
 /// - Header `connection: upgrade`
 /// - Header `upgrade: websocket`
 /// - Header `sec-websocket-accept` with the hash value of the received key.
+///
+/// Dead connections, e.g. behind NATs which dropped the mapping silently, can be reaped by
+/// [`ping_interval`](Self::ping_interval) and [`idle_timeout`](Self::idle_timeout).
+///
+/// When the server starts graceful shutdown, the [`WebSocket`] sends a close frame with the
+/// "going away" code (1001) and keeps yielding messages until the client completes the close
+/// handshake, see [`close_on_shutdown`](Self::close_on_shutdown).
 #[allow(missing_debug_implementations)]
 pub struct WebSocketUpgrade {
     config: Option<WebSocketConfig>,
+    keep_alive: KeepAlive,
+    size_limit_policy: SizeLimitPolicy,
+    close_on_shutdown: bool,
+}
+
+/// What happens when a message or frame received from the peer exceeds
+/// [`max_message_size`](WebSocketUpgrade::max_message_size) or
+/// [`max_frame_size`](WebSocketUpgrade::max_frame_size).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[non_exhaustive]
+pub enum SizeLimitPolicy {
+    /// Return the error, then close the connection with code `1009` (message too big).
+    #[default]
+    Close,
+    /// Only return the error, the connection is left open.
+    Error,
 }
 
 impl Default for WebSocketUpgrade {
@@ -127,7 +162,12 @@ impl WebSocketUpgrade {
     /// Create new `WebSocketUpgrade`.
     #[inline]
     pub fn new() -> Self {
-        WebSocketUpgrade { config: None }
+        WebSocketUpgrade {
+            config: None,
+            keep_alive: KeepAlive::default(),
+            size_limit_policy: SizeLimitPolicy::default(),
+            close_on_shutdown: true,
+        }
     }
 
     /// Create new `WebSocketUpgrade` with config.
@@ -135,9 +175,37 @@ impl WebSocketUpgrade {
     pub fn with_config(config: WebSocketConfig) -> Self {
         WebSocketUpgrade {
             config: Some(config),
+            keep_alive: KeepAlive::default(),
+            size_limit_policy: SizeLimitPolicy::default(),
+            close_on_shutdown: true,
         }
     }
 
+    /// Send a ping to the client every `interval`.
+    ///
+    /// The connection is closed when [`max_missed_pongs`](Self::max_missed_pongs) pings in a row
+    /// are not answered. Pings are sent while the [`WebSocket`] is polled for messages.
+    #[inline]
+    pub fn ping_interval(mut self, interval: Duration) -> Self {
+        self.keep_alive.ping_interval = Some(interval);
+        self
+    }
+
+    /// The number of unanswered pings after which the connection is closed.
+    /// The default value is 2.
+    #[inline]
+    pub fn max_missed_pongs(mut self, max: u32) -> Self {
+        self.keep_alive.max_missed_pongs = max;
+        self
+    }
+
+    /// Close the connection if nothing is received from the client within `timeout`.
+    #[inline]
+    pub fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive.idle_timeout = Some(timeout);
+        self
+    }
+
     /// The target minimum size of the write buffer to reach before writing the data
     /// to the underlying stream.
     /// The default value is 128 KiB.
@@ -193,6 +261,26 @@ impl WebSocketUpgrade {
         self
     }
 
+    /// Set what happens when a message or frame received from the peer exceeds the size limits.
+    ///
+    /// Default is [`SizeLimitPolicy::Close`].
+    #[inline]
+    pub fn size_limit_policy(mut self, policy: SizeLimitPolicy) -> Self {
+        self.size_limit_policy = policy;
+        self
+    }
+
+    /// Close the connection with the "going away" code (1001) when the server starts graceful
+    /// shutdown. The default value is `true`.
+    ///
+    /// The close frame is sent while the [`WebSocket`] is polled for messages. Handlers which
+    /// split the socket or wait on other events can use [`WebSocket::shutdown_signal`] instead.
+    #[inline]
+    pub fn close_on_shutdown(mut self, close: bool) -> Self {
+        self.close_on_shutdown = close;
+        self
+    }
+
     /// When set to `true`, the server will accept and handle unmasked frames
     /// from the client. According to the RFC 6455, the server must close the
     /// connection to the client in such cases, however it seems like there are
@@ -262,6 +350,10 @@ impl WebSocketUpgrade {
 
         if let Some(on_upgrade) = req.extensions_mut().remove::<OnUpgrade>() {
             let config = self.config;
+            let keep_alive = self.keep_alive;
+            let size_limit_policy = self.size_limit_policy;
+            let shutdown = ShutdownSignal::of(req).cloned();
+            let close_on_shutdown = self.close_on_shutdown;
             tokio::spawn(async move {
                 let socket = on_upgrade
                     .and_then(move |upgraded| {
@@ -270,6 +362,11 @@ impl WebSocketUpgrade {
                     })
                     .await
                     .expect("connection upgrade failed");
+                let mut socket = socket.keep_alive(keep_alive);
+                socket.size_limit_policy = size_limit_policy;
+                if let Some(shutdown) = shutdown {
+                    socket = socket.shutdown_signal_with(shutdown, close_on_shutdown);
+                }
                 callback(socket).await;
             });
             Ok(())
@@ -288,6 +385,30 @@ impl WebSocketUpgrade {
 /// `WebSocket`.
 pub struct WebSocket {
     inner: WebSocketStream<TokioIo<hyper::upgrade::Upgraded>>,
+    keep_alive: KeepAlive,
+    ping_timer: Option<Pin<Box<Sleep>>>,
+    idle_timer: Option<Pin<Box<Sleep>>>,
+    missed_pongs: u32,
+    size_limit_policy: SizeLimitPolicy,
+    shutdown: Option<ShutdownSignal>,
+    shutdown_waiter: Option<Pin<Box<dyn Future<Output = ()> + Send + Sync>>>,
+    dead: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct KeepAlive {
+    ping_interval: Option<Duration>,
+    max_missed_pongs: u32,
+    idle_timeout: Option<Duration>,
+}
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self {
+            ping_interval: None,
+            max_missed_pongs: 2,
+            idle_timeout: None,
+        }
+    }
 }
 
 impl WebSocket {
@@ -298,10 +419,96 @@ impl WebSocket {
         config: Option<protocol::WebSocketConfig>,
     ) -> Self {
         WebSocketStream::from_raw_socket(TokioIo::new(upgraded), role, config)
-            .map(|inner| WebSocket { inner })
+            .map(|inner| WebSocket {
+                inner,
+                keep_alive: KeepAlive::default(),
+                ping_timer: None,
+                idle_timer: None,
+                missed_pongs: 0,
+                size_limit_policy: SizeLimitPolicy::default(),
+                shutdown: None,
+                shutdown_waiter: None,
+                dead: false,
+            })
             .await
     }
 
+    fn keep_alive(mut self, keep_alive: KeepAlive) -> Self {
+        self.ping_timer = keep_alive
+            .ping_interval
+            .map(|interval| Box::pin(tokio::time::sleep(interval)));
+        self.idle_timer = keep_alive
+            .idle_timeout
+            .map(|timeout| Box::pin(tokio::time::sleep(timeout)));
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    fn shutdown_signal_with(mut self, shutdown: ShutdownSignal, close: bool) -> Self {
+        if close {
+            self.shutdown_waiter = Some(Box::pin(shutdown.triggered()));
+        }
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    /// Returns the graceful shutdown signal of the server, it can be used to close the
+    /// connection when the socket is split or not polled for messages.
+    ///
+    /// Returns `None` if the request is not served by a server.
+    #[inline]
+    pub fn shutdown_signal(&self) -> Option<&ShutdownSignal> {
+        self.shutdown.as_ref()
+    }
+
+    /// Starts closing the connection with the code, the close frame is flushed on next polls.
+    fn start_close(&mut self, cx: &mut Context, code: CloseCode, reason: &'static str) {
+        let mut inner = Pin::new(&mut self.inner);
+        if let Poll::Ready(Ok(())) = inner.as_mut().poll_ready(cx) {
+            let frame = CloseFrame {
+                code,
+                reason: reason.into(),
+            };
+            if let Err(e) = inner.as_mut().start_send(protocol::Message::Close(Some(frame))) {
+                tracing::debug!("websocket send close error: {}", e);
+                return;
+            }
+            let _ = inner.poll_flush(cx);
+        }
+    }
+
+    /// Sends pings and checks the timers, returns `true` if the connection is dead.
+    fn poll_keep_alive(&mut self, cx: &mut Context) -> bool {
+        if let Some(idle_timer) = &mut self.idle_timer {
+            if idle_timer.as_mut().poll(cx).is_ready() {
+                tracing::debug!("websocket idle timeout");
+                return true;
+            }
+        }
+        if let (Some(ping_timer), Some(interval)) =
+            (&mut self.ping_timer, self.keep_alive.ping_interval)
+        {
+            while ping_timer.as_mut().poll(cx).is_ready() {
+                if self.missed_pongs >= self.keep_alive.max_missed_pongs {
+                    tracing::debug!(missed_pongs = self.missed_pongs, "websocket pong missed");
+                    return true;
+                }
+                self.missed_pongs += 1;
+                ping_timer.as_mut().reset(Instant::now() + interval);
+                let mut inner = Pin::new(&mut self.inner);
+                if let Poll::Ready(Ok(())) = inner.as_mut().poll_ready(cx) {
+                    let ping = protocol::Message::Ping(Bytes::new());
+                    if let Err(e) = inner.as_mut().start_send(ping) {
+                        tracing::debug!("websocket send ping error: {}", e);
+                        return true;
+                    }
+                    let _ = inner.poll_flush(cx);
+                }
+            }
+        }
+        false
+    }
+
     /// Receive another message.
     ///
     /// Returns `None` if the stream has closed.
@@ -326,10 +533,45 @@ impl Stream for WebSocket {
 
     #[inline]
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
-        match ready!(Pin::new(&mut self.inner).poll_next(cx)) {
-            Some(Ok(item)) => Poll::Ready(Some(Ok(Message { inner: item }))),
+        let this = &mut *self;
+        if this.dead {
+            return Poll::Ready(None);
+        }
+        let polled = Pin::new(&mut this.inner).poll_next(cx);
+        if polled.is_pending() {
+            if let Some(waiter) = &mut this.shutdown_waiter {
+                if waiter.as_mut().poll(cx).is_ready() {
+                    tracing::debug!("server is shutting down, closing websocket");
+                    this.shutdown_waiter = None;
+                    this.start_close(cx, CloseCode::Away, "server shutting down");
+                }
+            }
+            if this.poll_keep_alive(cx) {
+                this.dead = true;
+                return Poll::Ready(None);
+            }
+            return Poll::Pending;
+        }
+        match ready!(polled) {
+            Some(Ok(item)) => {
+                if let (Some(idle_timer), Some(timeout)) =
+                    (&mut this.idle_timer, this.keep_alive.idle_timeout)
+                {
+                    idle_timer.as_mut().reset(Instant::now() + timeout);
+                }
+                if item.is_pong() {
+                    this.missed_pongs = 0;
+                }
+                Poll::Ready(Some(Ok(Message { inner: item })))
+            }
             Some(Err(e)) => {
                 tracing::debug!("websocket poll error: {}", e);
+                if matches!(e, tungstenite::Error::Capacity(_))
+                    && this.size_limit_policy == SizeLimitPolicy::Close
+                {
+                    this.start_close(cx, CloseCode::Size, "message too big");
+                    this.dead = true;
+                }
                 Poll::Ready(Some(Err(Error::other(e))))
             }
             None => {
@@ -510,6 +752,451 @@ impl Debug for Message {
     }
 }
 
+/// Format of the messages sent and received by [`WebSocketTyped`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[non_exhaustive]
+pub enum Codec {
+    /// JSON, sent as text messages. Both text and binary messages are accepted.
+    #[default]
+    Json,
+    /// MessagePack, sent as binary messages. Both text and binary messages are accepted.
+    #[cfg(feature = "websocket-msgpack")]
+    MsgPack,
+}
+impl Codec {
+    fn encode<T: Serialize>(self, value: &T) -> Result<Message, Error> {
+        match self {
+            Codec::Json => serde_json::to_string(value)
+                .map(Message::text)
+                .map_err(Error::other),
+            #[cfg(feature = "websocket-msgpack")]
+            Codec::MsgPack => rmp_serde::to_vec_named(value)
+                .map(Message::binary)
+                .map_err(Error::other),
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(self, msg: &Message) -> Result<T, String> {
+        match self {
+            Codec::Json => serde_json::from_slice(msg.as_bytes()).map_err(|e| e.to_string()),
+            #[cfg(feature = "websocket-msgpack")]
+            Codec::MsgPack => rmp_serde::from_slice(msg.as_bytes()).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Error returned by [`WebSocketTyped::recv`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TypedMessageError {
+    /// The message can not be decoded, the connection is still usable.
+    Malformed {
+        /// The received message.
+        message: Message,
+        /// Why the message can not be decoded.
+        reason: String,
+    },
+    /// The connection failed.
+    Connection(Error),
+}
+impl Display for TypedMessageError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Malformed { reason, .. } => write!(f, "malformed websocket message: {reason}"),
+            Self::Connection(e) => write!(f, "websocket connection error: {e}"),
+        }
+    }
+}
+impl StdError for TypedMessageError {}
+
+/// A [`WebSocket`] wrapper which decodes received messages into `In` and encodes `Out` to sent
+/// messages by the [`Codec`].
+///
+/// Ping and pong messages are skipped, and a close message ends the receiving.
+///
+/// # Example
+///
+/// ```no_run
+/// use salvo_core::prelude::*;
+/// use salvo_extra::websocket::{TypedMessageError, WebSocketTyped, WebSocketUpgrade};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize)]
+/// struct Question {
+///     text: String,
+/// }
+/// #[derive(Serialize)]
+/// enum Answer {
+///     Echo(String),
+///     Invalid(String),
+/// }
+///
+/// #[handler]
+/// async fn connect(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+///     WebSocketUpgrade::new()
+///         .upgrade(req, res, |ws| async move {
+///             let mut ws = WebSocketTyped::<Question, Answer>::new(ws);
+///             while let Some(question) = ws.recv().await {
+///                 let answer = match question {
+///                     Ok(question) => Answer::Echo(question.text),
+///                     Err(TypedMessageError::Malformed { reason, .. }) => Answer::Invalid(reason),
+///                     Err(_) => return,
+///                 };
+///                 if ws.send(&answer).await.is_err() {
+///                     return;
+///                 }
+///             }
+///         })
+///         .await
+/// }
+/// ```
+pub struct WebSocketTyped<In, Out> {
+    inner: WebSocket,
+    codec: Codec,
+    _marker: PhantomData<fn(Out) -> In>,
+}
+impl<In, Out> WebSocketTyped<In, Out>
+where
+    In: DeserializeOwned,
+    Out: Serialize,
+{
+    /// Create new `WebSocketTyped` using [`Codec::Json`].
+    #[inline]
+    pub fn new(inner: WebSocket) -> Self {
+        Self::with_codec(inner, Codec::Json)
+    }
+
+    /// Create new `WebSocketTyped` using the given [`Codec`].
+    #[inline]
+    pub fn with_codec(inner: WebSocket, codec: Codec) -> Self {
+        Self {
+            inner,
+            codec,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Receive and decode another message.
+    ///
+    /// Returns `None` if the stream has closed.
+    pub async fn recv(&mut self) -> Option<Result<In, TypedMessageError>> {
+        loop {
+            let message = match self.inner.recv().await? {
+                Ok(message) => message,
+                Err(e) => return Some(Err(TypedMessageError::Connection(e))),
+            };
+            if message.is_close() {
+                return None;
+            }
+            if !message.is_text() && !message.is_binary() {
+                continue;
+            }
+            return Some(
+                self.codec
+                    .decode(&message)
+                    .map_err(|reason| TypedMessageError::Malformed { message, reason }),
+            );
+        }
+    }
+
+    /// Encode and send a message.
+    ///
+    /// The value is encoded before the returned future is polled, so it does not need to be `Sync`.
+    pub fn send(&mut self, value: &Out) -> impl Future<Output = Result<(), Error>> + Send + '_ {
+        let message = self.codec.encode(value);
+        async move { self.inner.send(message?).await }
+    }
+
+    /// Gracefully close the websocket.
+    #[inline]
+    pub async fn close(self) -> Result<(), Error> {
+        self.inner.close().await
+    }
+
+    /// Consumes self and returns the inner [`WebSocket`].
+    #[inline]
+    pub fn into_inner(self) -> WebSocket {
+        self.inner
+    }
+}
+impl<In, Out> Debug for WebSocketTyped<In, Out> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("WebSocketTyped")
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
+
+/// Id of a connection registered in [`Rooms`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub struct ConnectionId(u64);
+impl Display for ConnectionId {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// A registry of websocket connections grouped in named rooms.
+///
+/// Messages can be broadcast to a room or to all connections, or sent to a single connection by
+/// [`ConnectionId`]. Connections and rooms are stored in sharded maps, so connections in
+/// different shards do not contend for the same lock. Cloned `Rooms` share the same registry.
+///
+/// Messages are queued without limit for every connection, they are sent to the client when
+/// the handler forwards them from [`RoomMember::recv`].
+///
+/// # Example
+///
+/// ```no_run
+/// use futures_util::{SinkExt, StreamExt};
+/// use salvo_core::prelude::*;
+/// use salvo_extra::websocket::{Rooms, WebSocketUpgrade};
+///
+/// #[handler]
+/// async fn connect(
+///     req: &mut Request,
+///     res: &mut Response,
+///     depot: &mut Depot,
+/// ) -> Result<(), StatusError> {
+///     let rooms = depot.obtain::<Rooms>().unwrap().clone();
+///     WebSocketUpgrade::new()
+///         .upgrade(req, res, move |ws| async move {
+///             let mut member = rooms.connect();
+///             member.join("lobby");
+///             let (mut tx, mut rx) = ws.split();
+///             loop {
+///                 tokio::select! {
+///                     msg = rx.next() => match msg {
+///                         Some(Ok(msg)) if msg.is_text() => {
+///                             rooms.broadcast("lobby", msg);
+///                         }
+///                         Some(Ok(_)) => {}
+///                         _ => break,
+///                     },
+///                     Some(msg) = member.recv() => {
+///                         if tx.send(msg).await.is_err() {
+///                             break;
+///                         }
+///                     }
+///                 }
+///             }
+///         })
+///         .await
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rooms {
+    inner: Arc<RoomsInner>,
+}
+type Shards<K, V> = Box<[RwLock<HashMap<K, V>>]>;
+#[derive(Debug)]
+struct RoomsInner {
+    next_id: AtomicU64,
+    connections: Shards<ConnectionId, mpsc::UnboundedSender<Message>>,
+    rooms: Shards<String, HashSet<ConnectionId>>,
+}
+impl Default for Rooms {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Rooms {
+    /// Create new `Rooms` with 16 shards.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_shards(16)
+    }
+
+    /// Create new `Rooms` with the given number of shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is `0`.
+    pub fn with_shards(shards: usize) -> Self {
+        assert!(shards > 0, "rooms shards must be greater than 0");
+        Self {
+            inner: Arc::new(RoomsInner {
+                next_id: AtomicU64::new(1),
+                connections: (0..shards).map(|_| RwLock::default()).collect(),
+                rooms: (0..shards).map(|_| RwLock::default()).collect(),
+            }),
+        }
+    }
+
+    /// Register a new connection, it is removed from the registry and all rooms when the
+    /// returned [`RoomMember`] is dropped.
+    pub fn connect(&self) -> RoomMember {
+        let id = ConnectionId(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.connection_shard(id)
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, tx);
+        RoomMember {
+            id,
+            rooms: self.clone(),
+            joined: Mutex::default(),
+            rx,
+        }
+    }
+
+    /// Send the message to the connection, returns `false` if the connection is not found.
+    pub fn send_to(&self, id: ConnectionId, msg: Message) -> bool {
+        self.connection_shard(id)
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+            .is_some_and(|tx| tx.send(msg).is_ok())
+    }
+
+    /// Broadcast the message to the members of the room, returns the number of receivers.
+    pub fn broadcast(&self, room: &str, msg: Message) -> usize {
+        self.broadcast_except(room, msg, None)
+    }
+
+    /// Broadcast the message to the members of the room except `except`, e.g. the sender of the
+    /// message, returns the number of receivers.
+    pub fn broadcast_except(
+        &self,
+        room: &str,
+        msg: Message,
+        except: impl Into<Option<ConnectionId>>,
+    ) -> usize {
+        let except = except.into();
+        self.members(room)
+            .into_iter()
+            .filter(|id| Some(*id) != except)
+            .filter(|id| self.send_to(*id, msg.clone()))
+            .count()
+    }
+
+    /// Broadcast the message to all connections, returns the number of receivers.
+    pub fn broadcast_all(&self, msg: Message) -> usize {
+        self.inner
+            .connections
+            .iter()
+            .map(|shard| {
+                shard
+                    .read()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .values()
+                    .filter(|tx| tx.send(msg.clone()).is_ok())
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Returns the connections in the room.
+    pub fn members(&self, room: &str) -> Vec<ConnectionId> {
+        self.room_shard(room)
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(room)
+            .map(|members| members.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of connections.
+    pub fn connection_count(&self) -> usize {
+        self.inner
+            .connections
+            .iter()
+            .map(|shard| shard.read().unwrap_or_else(|e| e.into_inner()).len())
+            .sum()
+    }
+
+    fn join(&self, room: &str, id: ConnectionId) {
+        self.room_shard(room)
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(room.to_owned())
+            .or_default()
+            .insert(id);
+    }
+
+    fn leave(&self, room: &str, id: ConnectionId) {
+        let mut rooms = self.room_shard(room).write().unwrap_or_else(|e| e.into_inner());
+        if let Some(members) = rooms.get_mut(room) {
+            members.remove(&id);
+            if members.is_empty() {
+                rooms.remove(room);
+            }
+        }
+    }
+
+    fn connection_shard(
+        &self,
+        id: ConnectionId,
+    ) -> &RwLock<HashMap<ConnectionId, mpsc::UnboundedSender<Message>>> {
+        &self.inner.connections[(id.0 % self.inner.connections.len() as u64) as usize]
+    }
+
+    fn room_shard(&self, room: &str) -> &RwLock<HashMap<String, HashSet<ConnectionId>>> {
+        let mut hasher = DefaultHasher::new();
+        room.hash(&mut hasher);
+        &self.inner.rooms[(hasher.finish() % self.inner.rooms.len() as u64) as usize]
+    }
+}
+
+/// A connection registered in [`Rooms`] by [`Rooms::connect`].
+#[derive(Debug)]
+pub struct RoomMember {
+    id: ConnectionId,
+    rooms: Rooms,
+    joined: Mutex<HashSet<String>>,
+    rx: mpsc::UnboundedReceiver<Message>,
+}
+impl RoomMember {
+    /// Returns the id of the connection.
+    #[inline]
+    pub fn id(&self) -> ConnectionId {
+        self.id
+    }
+
+    /// Join the room.
+    pub fn join(&self, room: impl Into<String>) {
+        let room = room.into();
+        self.rooms.join(&room, self.id);
+        self.joined.lock().unwrap_or_else(|e| e.into_inner()).insert(room);
+    }
+
+    /// Leave the room.
+    pub fn leave(&self, room: &str) {
+        self.rooms.leave(room, self.id);
+        self.joined.lock().unwrap_or_else(|e| e.into_inner()).remove(room);
+    }
+
+    /// Returns the rooms joined by the connection.
+    pub fn joined(&self) -> Vec<String> {
+        self.joined
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Receive the next message sent to the connection by [`Rooms`].
+    #[inline]
+    pub async fn recv(&mut self) -> Option<Message> {
+        self.rx.recv().await
+    }
+}
+impl Drop for RoomMember {
+    fn drop(&mut self) {
+        let joined = std::mem::take(&mut *self.joined.lock().unwrap_or_else(|e| e.into_inner()));
+        for room in joined {
+            self.rooms.leave(&room, self.id);
+        }
+        if let Ok(mut connections) = self.rooms.connection_shard(self.id).write() {
+            connections.remove(&self.id);
+        }
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<Vec<u8>> for Message {
     #[inline]
@@ -585,4 +1272,242 @@ mod tests {
 
         assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
     }
+
+    #[test]
+    fn test_codec() {
+        #[derive(serde::Deserialize, Serialize, PartialEq, Debug)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let msg = Codec::Json.encode(&Point { x: 1, y: 2 }).unwrap();
+        assert!(msg.is_text());
+        assert_eq!(msg.as_str().unwrap(), r#"{"x":1,"y":2}"#);
+        assert_eq!(
+            Codec::Json.decode::<Point>(&msg).unwrap(),
+            Point { x: 1, y: 2 }
+        );
+        assert!(Codec::Json.decode::<Point>(&Message::text("{")).is_err());
+
+        #[cfg(feature = "websocket-msgpack")]
+        {
+            let msg = Codec::MsgPack.encode(&Point { x: 1, y: 2 }).unwrap();
+            assert!(msg.is_binary());
+            assert_eq!(
+                Codec::MsgPack.decode::<Point>(&msg).unwrap(),
+                Point { x: 1, y: 2 }
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rooms() {
+        let rooms = Rooms::with_shards(2);
+        let mut alice = rooms.connect();
+        let mut bob = rooms.connect();
+        alice.join("lobby");
+        bob.join("lobby");
+        bob.join("games");
+        assert_eq!(rooms.connection_count(), 2);
+        assert_eq!(rooms.members("lobby").len(), 2);
+
+        assert_eq!(rooms.broadcast("lobby", Message::text("hi")), 2);
+        assert_eq!(alice.recv().await.unwrap().as_str().unwrap(), "hi");
+        assert_eq!(bob.recv().await.unwrap().as_str().unwrap(), "hi");
+        assert_eq!(
+            rooms.broadcast_except("lobby", Message::text("from alice"), alice.id()),
+            1
+        );
+        assert_eq!(bob.recv().await.unwrap().as_str().unwrap(), "from alice");
+        assert!(rooms.send_to(alice.id(), Message::text("direct")));
+        assert_eq!(alice.recv().await.unwrap().as_str().unwrap(), "direct");
+        assert_eq!(rooms.broadcast_all(Message::text("all")), 2);
+
+        alice.leave("lobby");
+        assert_eq!(rooms.members("lobby"), vec![bob.id()]);
+        let bob_id = bob.id();
+        drop(bob);
+        assert!(rooms.members("lobby").is_empty());
+        assert!(rooms.members("games").is_empty());
+        assert!(!rooms.send_to(bob_id, Message::text("gone")));
+        assert_eq!(rooms.connection_count(), 1);
+    }
+
+    static CLOSED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+    #[handler]
+    async fn connect_keep_alive(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+        WebSocketUpgrade::new()
+            .ping_interval(Duration::from_millis(50))
+            .max_missed_pongs(1)
+            .idle_timeout(Duration::from_millis(200))
+            .upgrade(req, res, |mut ws| async move {
+                while let Some(Ok(_)) = ws.recv().await {}
+                CLOSED.store(true, std::sync::atomic::Ordering::SeqCst);
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_websocket_keep_alive() {
+        let router = Router::new().goal(connect_keep_alive);
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+
+        tokio::spawn(async move {
+            Server::new(acceptor).serve(router).await;
+        });
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::task::spawn(async move {
+            let _ = conn.with_upgrades().await;
+        });
+        let req = hyper::Request::builder()
+            .uri(format!("http://{}", addr))
+            .header(UPGRADE, "websocket")
+            .header(CONNECTION, "Upgrade")
+            .header(SEC_WEBSOCKET_KEY, "6D69KGBOr4Re+Nj6zx9aQA==")
+            .header(SEC_WEBSOCKET_VERSION, "13")
+            .body(http_body_util::Empty::<hyper::body::Bytes>::new())
+            .unwrap();
+        let res = sender.send_request(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+        // Keep the connection open without answering the pings.
+        let _upgraded = hyper::upgrade::on(res).await.unwrap();
+
+        for _ in 0..50 {
+            if CLOSED.load(std::sync::atomic::Ordering::SeqCst) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("dead websocket connection is not closed");
+    }
+
+    #[handler]
+    async fn connect_echo(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+        WebSocketUpgrade::new()
+            .upgrade(req, res, |mut ws| async move {
+                while let Some(Ok(msg)) = ws.recv().await {
+                    if ws.send(msg).await.is_err() {
+                        return;
+                    }
+                }
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_websocket_close_on_shutdown() {
+        let router = Router::new().goal(connect_echo);
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+        let server = Server::new(acceptor);
+        let handle = server.handle();
+        tokio::spawn(async move {
+            server.serve(router).await;
+        });
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::task::spawn(async move {
+            let _ = conn.with_upgrades().await;
+        });
+        let req = hyper::Request::builder()
+            .uri(format!("http://{}", addr))
+            .header(UPGRADE, "websocket")
+            .header(CONNECTION, "Upgrade")
+            .header(SEC_WEBSOCKET_KEY, "6D69KGBOr4Re+Nj6zx9aQA==")
+            .header(SEC_WEBSOCKET_VERSION, "13")
+            .body(http_body_util::Empty::<hyper::body::Bytes>::new())
+            .unwrap();
+        let res = sender.send_request(req).await.unwrap();
+        let upgraded = hyper::upgrade::on(res).await.unwrap();
+        let mut client =
+            WebSocketStream::from_raw_socket(TokioIo::new(upgraded), protocol::Role::Client, None)
+                .await;
+        client.send(protocol::Message::text("hello")).await.unwrap();
+        assert_eq!(client.next().await.unwrap().unwrap(), protocol::Message::text("hello"));
+
+        handle.stop_graceful(Duration::from_secs(5));
+        let msg = client.next().await.unwrap().unwrap();
+        match msg {
+            protocol::Message::Close(Some(frame)) => assert_eq!(frame.code, CloseCode::Away),
+            msg => panic!("unexpected message: {msg:?}"),
+        }
+    }
+
+    #[handler]
+    async fn connect_limited(req: &mut Request, res: &mut Response) -> Result<(), StatusError> {
+        WebSocketUpgrade::new()
+            .max_message_size(8)
+            .upgrade(req, res, |mut ws| async move {
+                while let Some(Ok(msg)) = ws.recv().await {
+                    if ws.send(msg).await.is_err() {
+                        return;
+                    }
+                }
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_websocket_message_too_big() {
+        let router = Router::new().goal(connect_limited);
+        let acceptor = TcpListener::new("127.0.0.1:0").bind().await;
+        let addr = acceptor.holdings()[0]
+            .local_addr
+            .clone()
+            .into_std()
+            .unwrap();
+
+        tokio::spawn(async move {
+            Server::new(acceptor).serve(router).await;
+        });
+
+        let stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (mut sender, conn) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .unwrap();
+        tokio::task::spawn(async move {
+            let _ = conn.with_upgrades().await;
+        });
+        let req = hyper::Request::builder()
+            .uri(format!("http://{}", addr))
+            .header(UPGRADE, "websocket")
+            .header(CONNECTION, "Upgrade")
+            .header(SEC_WEBSOCKET_KEY, "6D69KGBOr4Re+Nj6zx9aQA==")
+            .header(SEC_WEBSOCKET_VERSION, "13")
+            .body(http_body_util::Empty::<hyper::body::Bytes>::new())
+            .unwrap();
+        let res = sender.send_request(req).await.unwrap();
+        let upgraded = hyper::upgrade::on(res).await.unwrap();
+        let mut client =
+            WebSocketStream::from_raw_socket(TokioIo::new(upgraded), protocol::Role::Client, None)
+                .await;
+
+        client
+            .send(protocol::Message::text("a message longer than the limit"))
+            .await
+            .unwrap();
+        let msg = client.next().await.unwrap().unwrap();
+        match msg {
+            protocol::Message::Close(Some(frame)) => assert_eq!(frame.code, CloseCode::Size),
+            msg => panic!("unexpected message: {msg:?}"),
+        }
+    }
 }
@@ -125,6 +125,7 @@ pub use jsonwebtoken::{
 use serde::de::DeserializeOwned;
 use thiserror::Error;
 
+use salvo_core::handler::AuthScheme;
 use salvo_core::http::{Method, Request, Response, StatusError};
 use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
 
@@ -360,6 +361,10 @@ where
     C: DeserializeOwned + Send + Sync + 'static,
     D: JwtAuthDecoder + Send + Sync + 'static,
 {
+    #[inline]
+    fn auth_scheme(&self) -> Option<AuthScheme> {
+        Some(AuthScheme::Bearer(Some("JWT")))
+    }
     async fn handle(
         &self,
         req: &mut Request,
@@ -5,12 +5,12 @@ use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
-    DeriveInput, Error, Expr, ExprLit, ExprPath, Field, Generics, Lit, Meta, MetaNameValue, Token,
-    Type,
+    DeriveInput, Error, Expr, ExprLit, ExprPath, ExprUnary, Field, Generics, Lit, Meta,
+    MetaNameValue, Path, Token, Type, UnOp,
 };
 
 use crate::{
-    attribute, omit_type_path_lifetimes, salvo_crate,
+    attribute, omit_type_path_lifetimes, parse_fn_path, salvo_crate,
     serde_util::{self, RenameRule, SerdeValue},
 };
 
@@ -22,6 +22,7 @@ struct FieldInfo {
     rename: Option<String>,
     serde_rename: Option<String>,
     flatten: bool,
+    default_value: Option<String>,
 }
 impl TryFrom<&Field> for FieldInfo {
     type Error = Error;
@@ -33,6 +34,7 @@ impl TryFrom<&Field> for FieldInfo {
         let mut aliases = Vec::with_capacity(field.attrs.len());
         let mut rename = None;
         let mut flatten = None;
+        let mut default_value = None;
         for attr in attrs {
             if attr.path().is_ident("salvo") {
                 if let Ok(Some(metas)) = attribute::find_nested_list(&attr, "extract") {
@@ -46,6 +48,9 @@ impl TryFrom<&Field> for FieldInfo {
                     if info.flatten.is_some() {
                         flatten = info.flatten;
                     }
+                    if info.default_value.is_some() {
+                        default_value = info.default_value;
+                    }
                 }
             }
         }
@@ -74,6 +79,12 @@ impl TryFrom<&Field> for FieldInfo {
                     "flatten field should not define aliases.",
                 ));
             }
+            if default_value.is_some() {
+                return Err(Error::new_spanned(
+                    ident,
+                    "flatten field should not define default value.",
+                ));
+            }
         }
 
         Ok(Self {
@@ -84,6 +95,7 @@ impl TryFrom<&Field> for FieldInfo {
             rename,
             serde_rename,
             flatten,
+            default_value,
         })
     }
 }
@@ -94,6 +106,7 @@ struct ExtractFieldInfo {
     aliases: Vec<String>,
     rename: Option<String>,
     flatten: Option<bool>,
+    default_value: Option<String>,
 }
 impl Parse for ExtractFieldInfo {
     fn parse(input: ParseStream) -> syn::Result<Self> {
@@ -119,6 +132,11 @@ impl Parse for ExtractFieldInfo {
                 "flatten" => {
                     extract.flatten = Some(true);
                 }
+                "default" => {
+                    input.parse::<Token![=]>()?;
+                    let expr = input.parse::<Expr>()?;
+                    extract.default_value = Some(parse_lit_value(&expr)?);
+                }
                 _ => {
                     return Err(input.error("unexpected attribute"));
                 }
@@ -133,6 +151,7 @@ impl Parse for ExtractFieldInfo {
 struct SourceInfo {
     from: String,
     parser: String,
+    rename: Option<String>,
 }
 
 impl Parse for SourceInfo {
@@ -140,6 +159,7 @@ impl Parse for SourceInfo {
         let mut source = SourceInfo {
             from: "body".to_owned(),
             parser: "smart".to_owned(),
+            rename: None,
         };
         let fields: Punctuated<MetaNameValue, Token![,]> = Punctuated::parse_terminated(input)?;
         for field in fields {
@@ -147,6 +167,8 @@ impl Parse for SourceInfo {
                 source.from = parse_path_or_lit_str(&field.value)?.to_lowercase();
             } else if field.path.is_ident("parse") {
                 source.parser = parse_path_or_lit_str(&field.value)?.to_lowercase();
+            } else if field.path.is_ident("rename") {
+                source.rename = Some(parse_path_or_lit_str(&field.value)?);
             } else {
                 return Err(input.error("unexpected attribute"));
             }
@@ -160,12 +182,18 @@ impl Parse for SourceInfo {
                 format!("source from is invalid: {}", source.from),
             ));
         }
-        if !["multimap", "json", "smart"].contains(&source.parser.as_str()) {
+        if !["multimap", "json", "nested", "smart"].contains(&source.parser.as_str()) {
             return Err(Error::new(
                 input.span(),
                 format!("source parser is invalid: {}", source.parser),
             ));
         }
+        if source.parser == "nested" && !["query", "body"].contains(&source.from.as_str()) {
+            return Err(Error::new(
+                input.span(),
+                "source parser nested is only supported for query and body",
+            ));
+        }
         Ok(source)
     }
 }
@@ -178,6 +206,7 @@ struct ExtractibleArgs {
     default_sources: Vec<SourceInfo>,
     rename_all: Option<RenameRule>,
     serde_rename_all: Option<RenameRule>,
+    error: Option<Path>,
 }
 
 impl ExtractibleArgs {
@@ -200,6 +229,7 @@ impl ExtractibleArgs {
         }
         let mut default_sources = Vec::new();
         let mut rename_all = None;
+        let mut error = None;
         for attr in &attrs {
             if attr.path().is_ident("salvo") {
                 if let Ok(Some(metas)) = attribute::find_nested_list(attr, "extract") {
@@ -218,6 +248,8 @@ impl ExtractibleArgs {
                                         parse_path_or_lit_str(&meta.value)?
                                             .parse::<RenameRule>()?,
                                     );
+                                } else if meta.path.is_ident("error") {
+                                    error = Some(parse_fn_path(&meta.value)?);
                                 }
                             }
                             _ => {}
@@ -235,6 +267,7 @@ impl ExtractibleArgs {
             default_sources,
             rename_all,
             serde_rename_all,
+            error,
         })
     }
 }
@@ -258,8 +291,13 @@ fn metadata_source(salvo: &Ident, source: &SourceInfo) -> TokenStream {
     let parser = quote! {
         #salvo::extract::metadata::SourceParser::#parser
     };
+    let rename = source.rename.as_ref().map(|rename| {
+        quote! {
+            .rename(#rename)
+        }
+    });
     quote! {
-        #salvo::extract::metadata::Source::new(#from, #parser)
+        #salvo::extract::metadata::Source::new(#from, #parser)#rename
     }
 }
 
@@ -358,6 +396,11 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
                 field = field.serde_rename(#serde_rename);
             }
         });
+        let default_value = field.default_value.as_ref().map(|default_value| {
+            quote! {
+                field = field.default_value(#default_value);
+            }
+        });
         fields.push(quote! {
             let mut field = #salvo::extract::metadata::Field::new(#field_ident);
             #nested_metadata
@@ -365,6 +408,7 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
             #(#aliases)*
             #rename
             #serde_rename
+            #default_value
             metadata = metadata.add_field(field);
         });
     }
@@ -387,6 +431,24 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
             })
         }
     };
+    let extract = if let Some(error) = &args.error {
+        quote! {
+            async fn extract(req: &'__macro_gen_ex mut #salvo::http::Request) -> Result<Self, impl #salvo::Writer + Send + ::std::fmt::Debug + 'static>
+            where
+                Self: Sized {
+                #salvo::serde::from_request(req, Self::metadata()).await.map_err(#error)
+            }
+        }
+    } else {
+        quote! {
+            #[allow(refining_impl_trait)]
+            async fn extract(req: &'__macro_gen_ex mut #salvo::http::Request) -> Result<Self, #salvo::http::ParseError>
+            where
+                Self: Sized {
+                #salvo::serde::from_request(req, Self::metadata()).await
+            }
+        }
+    };
     let life_param = args.generics.lifetimes().next();
     let code = if let Some(life_param) = life_param {
         let ex_life_def = syn::parse_str(&format!("'__macro_gen_ex:{}", life_param.lifetime))
@@ -398,12 +460,7 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
             impl #impl_generics_de #salvo::extract::Extractible<'__macro_gen_ex> for #name #ty_generics #where_clause {
                 #metadata
 
-                #[allow(refining_impl_trait)]
-                async fn extract(req: &'__macro_gen_ex mut #salvo::http::Request) -> Result<Self, #salvo::http::ParseError>
-                where
-                    Self: Sized {
-                    #salvo::serde::from_request(req, Self::metadata()).await
-                }
+                #extract
             }
         }
     } else {
@@ -415,12 +472,7 @@ pub(crate) fn generate(args: DeriveInput) -> Result<TokenStream, Error> {
             impl #impl_generics_de #salvo::extract::Extractible<'__macro_gen_ex> for #name #ty_generics #where_clause {
                 #metadata
 
-                #[allow(refining_impl_trait)]
-                async fn extract(req: &'__macro_gen_ex mut #salvo::http::Request) -> Result<Self, #salvo::http::ParseError>
-                where
-                    Self: Sized {
-                    #salvo::serde::from_request(req, Self::metadata()).await
-                }
+                #extract
             }
         }
     };
@@ -436,3 +488,21 @@ fn parse_path_or_lit_str(expr: &Expr) -> syn::Result<String> {
         _ => Err(Error::new_spanned(expr, "invalid indent or lit str")),
     }
 }
+
+fn parse_lit_value(expr: &Expr) -> syn::Result<String> {
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => match lit {
+            Lit::Str(s) => Ok(s.value()),
+            Lit::Int(i) => Ok(i.base10_digits().to_owned()),
+            Lit::Float(f) => Ok(f.base10_digits().to_owned()),
+            Lit::Bool(b) => Ok(b.value.to_string()),
+            _ => Err(Error::new_spanned(expr, "invalid default value")),
+        },
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => Ok(format!("-{}", parse_lit_value(expr)?)),
+        _ => Err(Error::new_spanned(expr, "invalid default value")),
+    }
+}
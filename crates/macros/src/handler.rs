@@ -8,6 +8,8 @@ pub(crate) fn generate(input: Item) -> syn::Result<TokenStream> {
     let salvo = salvo_crate();
     match input {
         Item::Fn(mut item_fn) => {
+            let hfn = handle_fn(&salvo, &item_fn.sig)?;
+            remove_param_attrs(&mut item_fn.sig);
             let attrs = item_fn
                 .attrs
                 .iter()
@@ -37,7 +39,6 @@ pub(crate) fn generate(input: Item) -> syn::Result<TokenStream> {
                 }
             };
 
-            let hfn = handle_fn(&salvo, sig)?;
             Ok(quote! {
                 #sdef
                 #[#salvo::async_trait]
@@ -46,7 +47,7 @@ pub(crate) fn generate(input: Item) -> syn::Result<TokenStream> {
                 }
             })
         }
-        Item::Impl(item_impl) => {
+        Item::Impl(mut item_impl) => {
             let mut hmtd = None;
             for item in &item_impl.items {
                 if let ImplItem::Fn(method) = item {
@@ -62,6 +63,13 @@ pub(crate) fn generate(input: Item) -> syn::Result<TokenStream> {
                 ));
             };
             let hfn = handle_fn(&salvo, &hmtd.sig)?;
+            for item in &mut item_impl.items {
+                if let ImplItem::Fn(method) = item {
+                    if method.sig.ident == "handle" {
+                        remove_param_attrs(&mut method.sig);
+                    }
+                }
+            }
             let ty = &item_impl.self_ty;
             let (impl_generics, _, where_clause) = &item_impl.generics.split_for_impl();
 
@@ -115,12 +123,21 @@ fn handle_fn(salvo: &Ident, sig: &Signature) -> syn::Result<TokenStream> {
                         .unwrap_or(idv);
                     let id = Ident::new(&idv, Span::call_site());
                     let idv = idv.trim_start_matches('_');
+                    let write_error = if let Some(error) = parse_param_extract_error(pat)? {
+                        quote! {
+                            #salvo::Writer::write(#error(#salvo::__private::into_parse_error(e)), __macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                        }
+                    } else {
+                        quote! {
+                            e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                        }
+                    };
 
                     extract_ts.push(quote!{
                         let #id: #ty = match <#ty as #salvo::Extractible>::extract_with_arg(__macro_gen_req, #idv).await {
                             Ok(data) => data,
                             Err(e) => {
-                                e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                                #write_error
                                 // If status code is not set or is not error, set it to 400.
                                 let status_code = __macro_gen_res.status_code.unwrap_or_default();
                                 if !status_code.is_client_error() && !status_code.is_server_error() {
@@ -12,6 +12,7 @@ mod attribute;
 mod extract;
 mod handler;
 mod shared;
+mod typed_path;
 
 pub(crate) use salvo_serde_util as serde_util;
 use shared::*;
@@ -41,6 +42,30 @@ pub fn derive_extractible(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Generate code for typed path type.
+///
+/// The struct's fields must be the same as the params in path template, so that path definitions and handler
+/// signatures can't drift apart. It implements `TypedPath` and `Extractible` (extract from path params) for the
+/// struct. It can be derived together with `ToParameters` in `salvo-oapi` to document the path params, which
+/// leaves `Extractible` to this derive.
+///
+/// ```ignore
+/// #[derive(Deserialize, TypedPath)]
+/// #[salvo(typed_path(path = "users/{user_id}/posts/{post_id}"))]
+/// struct UserPost {
+///     user_id: u64,
+///     post_id: u64,
+/// }
+/// ```
+#[proc_macro_derive(TypedPath, attributes(salvo))]
+pub fn derive_typed_path(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as DeriveInput);
+    match typed_path::generate(args) {
+        Ok(stream) => stream.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use quote::quote;
@@ -269,4 +294,61 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    fn test_typed_path() {
+        let input = quote! {
+            #[salvo(typed_path(path = "users/{user_id|\\d+}/posts/{post_id}"))]
+            struct UserPost {
+                user_id: u64,
+                post_id: u64,
+            }
+        };
+        let item = parse2(input).unwrap();
+        assert_eq!(
+            typed_path::generate(item).unwrap().to_string(),
+            quote! {
+                impl salvo::routing::TypedPath for UserPost {
+                    const PATH: &'static str = "users/{user_id|\\d+}/posts/{post_id}";
+                    fn to_path(&self) -> String {
+                        salvo::routing::build_typed_path(Self::PATH, [("user_id", self.user_id.to_string()), ("post_id", self.post_id.to_string())])
+                    }
+                }
+                impl<'__macro_gen_ex> salvo::extract::Extractible<'__macro_gen_ex> for UserPost {
+                    fn metadata() -> &'static salvo::extract::Metadata {
+                        static METADATA: ::std::sync::OnceLock<salvo::extract::Metadata> = ::std::sync::OnceLock::new();
+                        METADATA.get_or_init(|| {
+                            let mut metadata = salvo::extract::Metadata::new("UserPost");
+                            metadata = metadata.add_default_source(salvo::extract::metadata::Source::new(
+                                salvo::extract::metadata::SourceFrom::Param,
+                                salvo::extract::metadata::SourceParser::Smart
+                            ));
+                            metadata = metadata.add_field(salvo::extract::metadata::Field::new("user_id"));
+                            metadata = metadata.add_field(salvo::extract::metadata::Field::new("post_id"));
+                            metadata
+                        })
+                    }
+                    #[allow(refining_impl_trait)]
+                    async fn extract(req: &'__macro_gen_ex mut salvo::http::Request) -> Result<Self, salvo::http::ParseError>
+                    where
+                        Self: Sized {
+                        salvo::serde::from_request(req, Self::metadata()).await
+                    }
+                }
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_typed_path_mismatch() {
+        let input = quote! {
+            #[salvo(typed_path(path = "users/{id}"))]
+            struct User {
+                user_id: u64,
+            }
+        };
+        let item = parse2(input).unwrap();
+        assert!(typed_path::generate(item).is_err());
+    }
 }
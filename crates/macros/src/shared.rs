@@ -2,7 +2,14 @@ use proc_macro_crate::{FoundCrate, crate_name};
 use proc_macro2::Span;
 use quote::ToTokens;
 use regex::Regex;
-use syn::{FnArg, Ident, PatType, Receiver, Type, TypePath};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{
+    Expr, ExprLit, ExprPath, FnArg, Ident, Lit, Meta, PatType, Path, Receiver, Signature, Type,
+    TypePath,
+};
+
+use crate::attribute;
 
 #[allow(dead_code)]
 pub(crate) enum InputType<'a> {
@@ -70,6 +77,48 @@ pub(crate) fn parse_input_type(input: &FnArg) -> InputType {
     }
 }
 
+/// Parse the function from `#[salvo(extract(error = "..."))]` of a handler parameter, it converts the
+/// extraction error to a custom writer.
+pub(crate) fn parse_param_extract_error(pat: &PatType) -> syn::Result<Option<Path>> {
+    let mut error = None;
+    for attr in &pat.attrs {
+        if attr.path().is_ident("salvo") {
+            if let Some(metas) = attribute::find_nested_list(attr, "extract")? {
+                let nested = metas.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?;
+                for meta in nested {
+                    match meta {
+                        Meta::NameValue(meta) if meta.path.is_ident("error") => {
+                            error = Some(parse_fn_path(&meta.value)?);
+                        }
+                        _ => return Err(syn::Error::new_spanned(meta, "unexpected attribute")),
+                    }
+                }
+            }
+        }
+    }
+    Ok(error)
+}
+
+/// Parse a function path from a path or a string literal.
+pub(crate) fn parse_fn_path(expr: &Expr) -> syn::Result<Path> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => s.parse(),
+        Expr::Path(ExprPath { path, .. }) => Ok(path.clone()),
+        _ => Err(syn::Error::new_spanned(expr, "invalid function path")),
+    }
+}
+
+/// Remove the `#[salvo(...)]` attributes of the parameters, they are only used by macros.
+pub(crate) fn remove_param_attrs(sig: &mut Signature) {
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(pat) = input {
+            pat.attrs.retain(|attr| !attr.path().is_ident("salvo"));
+        }
+    }
+}
+
 pub(crate) fn omit_type_path_lifetimes(ty_path: &TypePath) -> TypePath {
     let reg = Regex::new(r"'\w+").expect("invalid regex");
     let ty_path = ty_path.into_token_stream().to_string();
@@ -0,0 +1,181 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{Data, DeriveInput, Error, Fields, Meta};
+
+use crate::{attribute, salvo_crate};
+
+struct TypedPathArgs {
+    path: String,
+}
+
+impl TypedPathArgs {
+    fn from_derive_input(input: &DeriveInput) -> syn::Result<Self> {
+        let mut path = None;
+        for attr in &input.attrs {
+            if attr.path().is_ident("salvo") {
+                if let Some(metas) = attribute::find_nested_list(attr, "typed_path")? {
+                    let nested =
+                        metas.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?;
+                    for meta in nested {
+                        match meta {
+                            Meta::NameValue(meta) if meta.path.is_ident("path") => {
+                                if let syn::Expr::Lit(syn::ExprLit {
+                                    lit: syn::Lit::Str(s),
+                                    ..
+                                }) = &meta.value
+                                {
+                                    path = Some(s.value());
+                                } else {
+                                    return Err(Error::new_spanned(
+                                        &meta.value,
+                                        "path should be a string literal.",
+                                    ));
+                                }
+                            }
+                            _ => return Err(Error::new_spanned(meta, "unexpected attribute")),
+                        }
+                    }
+                }
+            }
+        }
+        let path = path.ok_or_else(|| {
+            Error::new_spanned(
+                &input.ident,
+                r#"typed path requires attribute `#[salvo(typed_path(path = "..."))]`."#,
+            )
+        })?;
+        Ok(Self { path })
+    }
+}
+
+/// Get all param names in the path template, wildcard prefix is removed.
+fn parse_param_names(path: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut chars = path.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+        let mut depth = 1;
+        let mut spec = String::new();
+        for c in chars.by_ref() {
+            if c == '{' {
+                depth += 1;
+            } else if c == '}' {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            spec.push(c);
+        }
+        let name = spec
+            .split([':', '|'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_start_matches(['*', '+', '?']);
+        if !name.is_empty() {
+            names.push(name.to_owned());
+        }
+    }
+    names
+}
+
+pub(crate) fn generate(input: DeriveInput) -> Result<TokenStream, Error> {
+    let args = TypedPathArgs::from_derive_input(&input)?;
+    let salvo = salvo_crate();
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "typed path does not support generics.",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    name,
+                    "typed path can only be applied to a struct with named fields.",
+                ));
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "typed path can only be applied to a struct.",
+            ));
+        }
+    };
+
+    let param_names = parse_param_names(&args.path);
+    let mut idents = Vec::with_capacity(fields.len());
+    let mut field_names = Vec::with_capacity(fields.len());
+    for field in fields {
+        let ident = field.ident.clone().expect("named field should have ident");
+        let field_name = ident.to_string();
+        if !param_names.contains(&field_name) {
+            return Err(Error::new_spanned(
+                ident,
+                format!(
+                    "field `{}` is not found in path `{}`.",
+                    field_name, args.path
+                ),
+            ));
+        }
+        idents.push(ident);
+        field_names.push(field_name);
+    }
+    for param_name in &param_names {
+        if !field_names.contains(param_name) {
+            return Err(Error::new_spanned(
+                name,
+                format!(
+                    "param `{}` in path `{}` is not a field.",
+                    param_name, args.path
+                ),
+            ));
+        }
+    }
+
+    let path = &args.path;
+    let mt = name.to_string();
+    Ok(quote! {
+        impl #salvo::routing::TypedPath for #name {
+            const PATH: &'static str = #path;
+            fn to_path(&self) -> String {
+                #salvo::routing::build_typed_path(Self::PATH, [#((#field_names, self.#idents.to_string())),*])
+            }
+        }
+        impl<'__macro_gen_ex> #salvo::extract::Extractible<'__macro_gen_ex> for #name {
+            fn metadata() -> &'static #salvo::extract::Metadata {
+                static METADATA: ::std::sync::OnceLock<#salvo::extract::Metadata> = ::std::sync::OnceLock::new();
+                METADATA.get_or_init(|| {
+                    let mut metadata = #salvo::extract::Metadata::new(#mt);
+                    metadata = metadata.add_default_source(#salvo::extract::metadata::Source::new(
+                        #salvo::extract::metadata::SourceFrom::Param,
+                        #salvo::extract::metadata::SourceParser::Smart
+                    ));
+                    #(
+                        metadata = metadata.add_field(#salvo::extract::metadata::Field::new(#field_names));
+                    )*
+                    metadata
+                })
+            }
+            #[allow(refining_impl_trait)]
+            async fn extract(req: &'__macro_gen_ex mut #salvo::http::Request) -> Result<Self, #salvo::http::ParseError>
+            where
+                Self: Sized {
+                #salvo::serde::from_request(req, Self::metadata()).await
+            }
+        }
+    })
+}
@@ -421,16 +421,30 @@ impl ComponentSchema {
                     } else {
                         None
                     };
+                    // annotations of the field can not be added to the schema of the type, so the schema is
+                    // wrapped by `AllOf` if there is any.
+                    let annotations = [
+                        pop_feature!(features => Feature::Title(_)),
+                        pop_feature!(features => Feature::Example(_)),
+                        pop_feature!(features => Feature::Examples(_)),
+                        pop_feature!(features => Feature::ReadOnly(_)),
+                        pop_feature!(features => Feature::WriteOnly(_)),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .try_to_token_stream()?;
                     if is_inline {
                         let default = pop_feature!(features => Feature::Default(_))
                             .map(|feature| feature.try_to_token_stream())
                             .transpose()?;
-                        let schema = if default.is_some() || nullable {
+                        let schema = if default.is_some() || nullable || !annotations.is_empty() {
                             quote_spanned! {type_path.span()=>
                                 #oapi::oapi::schema::AllOf::new()
                                     #nullable_item
                                     .item(<#type_path as #oapi::oapi::ToSchema>::to_schema(components))
                                 #default
+                                #annotations
                             }
                         } else {
                             quote_spanned! {type_path.span() =>
@@ -450,12 +464,13 @@ impl ComponentSchema {
                         // TODO: refs support `summary` field but currently there is no such field
                         // on schemas more over there is no way to distinct the `summary` from
                         // `description` of the ref. Should we consider supporting the summary?
-                        let schema = if default.is_some() || nullable {
+                        let schema = if default.is_some() || nullable || !annotations.is_empty() {
                             quote! {
                                 #oapi::oapi::schema::AllOf::new()
                                     #nullable_item
                                     .item(#schema)
                                     #default
+                                    #annotations
                             }
                         } else {
                             quote! {
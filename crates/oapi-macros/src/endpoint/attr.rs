@@ -5,7 +5,8 @@ use syn::{parenthesized, parse::Parse};
 
 use crate::operation::request_body::RequestBodyAttr;
 use crate::{
-    Array, Parameter, Response, Token, parse_utils, security_requirement::SecurityRequirementsAttr,
+    AnyValue, Array, Parameter, Response, Token, parse_utils,
+    security_requirement::SecurityRequirementsAttr,
 };
 
 #[derive(Default, Debug)]
@@ -17,6 +18,8 @@ pub(crate) struct EndpointAttr<'p> {
     pub(crate) tags: Option<Vec<String>>,
     pub(crate) parameters: Vec<Parameter<'p>>,
     pub(crate) security: Option<Array<'p, SecurityRequirementsAttr>>,
+    pub(crate) visibility: Option<LitStr>,
+    pub(crate) extensions: Vec<Extension>,
 
     pub(crate) doc_comments: Option<Vec<String>>,
     pub(crate) deprecated: Option<bool>,
@@ -26,7 +29,7 @@ pub(crate) struct EndpointAttr<'p> {
 
 impl Parse for EndpointAttr<'_> {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        const EXPECTED_ATTRIBUTE_MESSAGE: &str = "unexpected identifier, expected any of: operation_id, path, get, post, put, delete, options, head, patch, trace, connect, request_body, responses, params, tag, security, context_path, description, summary";
+        const EXPECTED_ATTRIBUTE_MESSAGE: &str = "unexpected identifier, expected any of: operation_id, path, get, post, put, delete, options, head, patch, trace, connect, request_body, responses, params, tag, security, visibility, extensions, context_path, description, summary";
         let mut attr = EndpointAttr::default();
 
         while !input.is_empty() {
@@ -84,6 +87,16 @@ impl Parse for EndpointAttr<'_> {
                     parenthesized!(security in input);
                     attr.security = Some(parse_utils::parse_groups(&security)?)
                 }
+                "extensions" => {
+                    let extensions;
+                    parenthesized!(extensions in input);
+                    attr.extensions =
+                        Punctuated::<Extension, Token![,]>::parse_terminated(&extensions)
+                            .map(|punctuated| punctuated.into_iter().collect::<Vec<_>>())?;
+                }
+                "visibility" => {
+                    attr.visibility = Some(parse_utils::parse_next(input, || input.parse())?);
+                }
                 "description" => {
                     attr.description = Some(parse_utils::parse_next_lit_str_or_expr(input)?)
                 }
@@ -101,3 +114,25 @@ impl Parse for EndpointAttr<'_> {
         Ok(attr)
     }
 }
+
+/// Openapi extension (`x-something`) parsed from `"x-something" = value`.
+#[derive(Debug)]
+pub(crate) struct Extension {
+    pub(crate) key: LitStr,
+    pub(crate) value: AnyValue,
+}
+
+impl Parse for Extension {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let key = input.parse::<LitStr>()?;
+        if !key.value().starts_with("x-") {
+            return Err(syn::Error::new(
+                key.span(),
+                "extension name must start with `x-`",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        let value = AnyValue::parse_any(input)?;
+        Ok(Self { key, value })
+    }
+}
@@ -3,10 +3,13 @@ use quote::{ToTokens, quote};
 use syn::{Expr, Ident, ImplItem, Item, Pat, ReturnType, Signature, Type};
 
 use crate::doc_comment::CommentAttributes;
-use crate::{Array, DiagResult, InputType, Operation, omit_type_path_lifetimes, parse_input_type};
+use crate::{
+    Array, DiagResult, InputType, Operation, omit_type_path_lifetimes, parse_input_type,
+    parse_param_extract_error, remove_param_attrs,
+};
 
 mod attr;
-pub(crate) use attr::EndpointAttr;
+pub(crate) use attr::{EndpointAttr, Extension};
 
 fn metadata(
     salvo: &Ident,
@@ -84,6 +87,8 @@ pub(crate) fn generate(mut attr: EndpointAttr, input: Item) -> syn::Result<Token
     let oapi = crate::oapi_crate();
     match input {
         Item::Fn(mut item_fn) => {
+            let (hfn, modifiers) = handle_fn(&salvo, &oapi, &item_fn.sig)?;
+            remove_param_attrs(&mut item_fn.sig);
             let attrs = item_fn
                 .attrs
                 .iter()
@@ -120,7 +125,6 @@ pub(crate) fn generate(mut attr: EndpointAttr, input: Item) -> syn::Result<Token
                 None
             };
 
-            let (hfn, modifiers) = handle_fn(&salvo, &oapi, sig)?;
             let meta = metadata(&salvo, &oapi, attr, name, modifiers)?;
             Ok(quote! {
                 #sdef
@@ -131,7 +135,7 @@ pub(crate) fn generate(mut attr: EndpointAttr, input: Item) -> syn::Result<Token
                 #meta
             })
         }
-        Item::Impl(item_impl) => {
+        Item::Impl(mut item_impl) => {
             let attrs = &item_impl.attrs;
 
             attr.doc_comments = Some(CommentAttributes::from_attributes(attrs).0);
@@ -156,6 +160,13 @@ pub(crate) fn generate(mut attr: EndpointAttr, input: Item) -> syn::Result<Token
                 ));
             };
             let (hfn, modifiers) = handle_fn(&salvo, &oapi, &hmtd.sig)?;
+            for item in &mut item_impl.items {
+                if let ImplItem::Fn(method) = item {
+                    if method.sig.ident == "handle" {
+                        remove_param_attrs(&mut method.sig);
+                    }
+                }
+            }
             let ty = &item_impl.self_ty;
             let (impl_generics, _, where_clause) = &item_impl.generics.split_for_impl();
             let name = Ident::new(&ty.to_token_stream().to_string(), Span::call_site());
@@ -218,13 +229,22 @@ fn handle_fn(
                         .unwrap_or(idv);
                     let id = Ident::new(&idv, Span::call_site());
                     let idv = idv.trim_start_matches('_');
+                    let write_error = if let Some(error) = parse_param_extract_error(pat)? {
+                        quote! {
+                            #salvo::Writer::write(#error(#salvo::__private::into_parse_error(e)), __macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                        }
+                    } else {
+                        quote! {
+                            e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                        }
+                    };
                     extract_ts.push(quote!{
                         let #id: #ty = match <#ty as #salvo::Extractible>::extract_with_arg(__macro_gen_req, #idv).await {
                             Ok(data) => {
                                 data
                             },
                             Err(e) => {
-                                e.write(__macro_gen_req, __macro_gen_depot, __macro_gen_res).await;
+                                #write_error
                                 // If status code is not set or is not error, set it to 400.
                                 let status_code = __macro_gen_res.status_code.unwrap_or_default();
                                 if !status_code.is_client_error() && !status_code.is_server_error() {
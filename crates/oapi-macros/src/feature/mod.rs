@@ -11,6 +11,7 @@ pub(crate) use macros::*;
 
 pub(crate) mod attributes;
 pub(crate) mod validation;
+pub(crate) mod validator_attrs;
 pub(crate) mod validators;
 
 use crate::feature::attributes::*;
@@ -0,0 +1,230 @@
+//! Convert the constraints declared with `#[validate(...)]` attributes of the `validator` crate to schema
+//! validation features, so the documented schema matches what is validated at runtime.
+//!
+//! Only the constraints with literal values can be converted: `length(min, max, equal)` becomes
+//! `min_length`/`max_length` for strings or `min_items`/`max_items` for vectors, and
+//! `range(min, max, exclusive_min, exclusive_max)` becomes `minimum`/`maximum`/`exclusive_minimum`/
+//! `exclusive_maximum` for numbers. `regex(path = ...)` refers to a value only known at runtime, so it can
+//! not be converted, use `#[salvo(schema(pattern = "..."))]` to document it.
+use proc_macro2::Ident;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Attribute, Expr, Lit, Token, UnOp};
+
+use super::Feature;
+use super::validation::{
+    ExclusiveMaximum, ExclusiveMinimum, MaxItems, MaxLength, Maximum, MinItems, MinLength, Minimum,
+};
+use crate::schema_type::SchemaType;
+use crate::type_tree::{GenericType, TypeTree};
+
+/// Parse the `#[validate(...)]` attributes of a field with type `type_tree` to features.
+pub(crate) fn parse_validator_features(
+    attributes: &[Attribute],
+    type_tree: &TypeTree,
+) -> Vec<Feature> {
+    let type_tree = if type_tree.generic_type == Some(GenericType::Option) {
+        match type_tree
+            .children
+            .as_ref()
+            .and_then(|children| children.first())
+        {
+            Some(child) => child,
+            None => return Vec::new(),
+        }
+    } else {
+        type_tree
+    };
+    let is_vec = type_tree.generic_type == Some(GenericType::Vec);
+    let schema_type = type_tree
+        .path
+        .as_ref()
+        .filter(|_| type_tree.generic_type.is_none())
+        .map(|path| SchemaType {
+            path,
+            nullable: false,
+        });
+    let is_string = schema_type.as_ref().is_some_and(|ty| ty.is_string());
+    let is_number = schema_type.as_ref().is_some_and(|ty| ty.is_number());
+
+    let mut features = Vec::new();
+    for attribute in attributes
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("validate"))
+    {
+        // Invalid attributes are reported by the `Validate` derive, ignore them here.
+        let _ = attribute.parse_nested_meta(|meta| {
+            let span = meta.path.span();
+            let ident = |name: &str| Ident::new(name, span);
+            if meta.path.is_ident("length") && (is_string || is_vec) {
+                let (mut min, mut max) = (None, None);
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("min") {
+                        min = parse_usize(&meta)?;
+                    } else if meta.path.is_ident("max") {
+                        max = parse_usize(&meta)?;
+                    } else if meta.path.is_ident("equal") {
+                        min = parse_usize(&meta)?;
+                        max = min;
+                    } else {
+                        skip_meta(&meta)?;
+                    }
+                    Ok(())
+                })?;
+                // Zero length constraints are meaningless and rejected by the features.
+                if let Some(min) = min.filter(|min| *min > 0) {
+                    features.push(if is_vec {
+                        Feature::MinItems(MinItems(min, ident("min_items")))
+                    } else {
+                        Feature::MinLength(MinLength(min, ident("min_length")))
+                    });
+                }
+                if let Some(max) = max.filter(|max| *max > 0) {
+                    features.push(if is_vec {
+                        Feature::MaxItems(MaxItems(max, ident("max_items")))
+                    } else {
+                        Feature::MaxLength(MaxLength(max, ident("max_length")))
+                    });
+                }
+            } else if meta.path.is_ident("range") && is_number {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("min") {
+                        if let Some(value) = parse_f64(&meta)? {
+                            features.push(Feature::Minimum(Minimum(value, ident("minimum"))));
+                        }
+                    } else if meta.path.is_ident("max") {
+                        if let Some(value) = parse_f64(&meta)? {
+                            features.push(Feature::Maximum(Maximum(value, ident("maximum"))));
+                        }
+                    } else if meta.path.is_ident("exclusive_min") {
+                        if let Some(value) = parse_f64(&meta)? {
+                            features.push(Feature::ExclusiveMinimum(ExclusiveMinimum(
+                                value,
+                                ident("exclusive_minimum"),
+                            )));
+                        }
+                    } else if meta.path.is_ident("exclusive_max") {
+                        if let Some(value) = parse_f64(&meta)? {
+                            features.push(Feature::ExclusiveMaximum(ExclusiveMaximum(
+                                value,
+                                ident("exclusive_maximum"),
+                            )));
+                        }
+                    } else {
+                        skip_meta(&meta)?;
+                    }
+                    Ok(())
+                })?;
+            } else {
+                skip_meta(&meta)?;
+            }
+            Ok(())
+        });
+    }
+    features
+}
+
+/// Append the features parsed from the `#[validate(...)]` attributes to `features`, the features which are
+/// already declared explicitly take precedence.
+pub(crate) fn merge_validator_features(
+    features: &mut Vec<Feature>,
+    attributes: &[Attribute],
+    type_tree: &TypeTree,
+) {
+    for feature in parse_validator_features(attributes, type_tree) {
+        if !features
+            .iter()
+            .any(|f| std::mem::discriminant(f) == std::mem::discriminant(&feature))
+        {
+            features.push(feature);
+        }
+    }
+}
+
+/// Consume the value or the nested list of a meta item which is not converted.
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|meta| skip_meta(&meta))?;
+    }
+    Ok(())
+}
+
+/// Parse the value of a meta item, returns `None` if it is not a literal integer.
+fn parse_usize(meta: &ParseNestedMeta) -> syn::Result<Option<usize>> {
+    match meta.value()?.parse::<Expr>()? {
+        Expr::Lit(expr) => match expr.lit {
+            Lit::Int(lit) => Ok(lit.base10_parse().ok()),
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Parse the value of a meta item, returns `None` if it is not a literal number.
+fn parse_f64(meta: &ParseNestedMeta) -> syn::Result<Option<f64>> {
+    fn lit_to_f64(expr: &Expr) -> Option<f64> {
+        match expr {
+            Expr::Lit(expr) => match &expr.lit {
+                Lit::Int(lit) => lit.base10_parse().ok(),
+                Lit::Float(lit) => lit.base10_parse().ok(),
+                _ => None,
+            },
+            Expr::Unary(expr) if matches!(expr.op, UnOp::Neg(_)) => {
+                lit_to_f64(&expr.expr).map(|value| -value)
+            }
+            _ => None,
+        }
+    }
+    Ok(lit_to_f64(&meta.value()?.parse::<Expr>()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{Field, parse_quote};
+
+    use super::*;
+
+    fn validator_features(field: Field) -> Vec<String> {
+        let type_tree = TypeTree::from_type(&field.ty).unwrap();
+        parse_validator_features(&field.attrs, &type_tree)
+            .iter()
+            .map(|feature| format!("{feature:?}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_validator_features() {
+        let field: Field = parse_quote! {
+            #[validate(email, length(min = 3, max = 20, message = "bad name"))]
+            name: Option<String>
+        };
+        let features = validator_features(field);
+        assert_eq!(features.len(), 2);
+        assert!(features[0].starts_with("MinLength(MinLength(3"));
+        assert!(features[1].starts_with("MaxLength(MaxLength(20"));
+
+        let field: Field = parse_quote! {
+            #[validate(length(min = 1), custom(function = "check_tags"))]
+            tags: Vec<String>
+        };
+        let features = validator_features(field);
+        assert_eq!(features.len(), 1);
+        assert!(features[0].starts_with("MinItems(MinItems(1"));
+
+        let field: Field = parse_quote! {
+            #[validate(range(min = -1.5, max = MAX_AGE))]
+            age: f32
+        };
+        let features = validator_features(field);
+        assert_eq!(features.len(), 1);
+        assert!(features[0].starts_with("Minimum(Minimum(-1.5"));
+
+        let field: Field = parse_quote! {
+            #[validate(range(min = 1))]
+            name: String
+        };
+        assert!(validator_features(field).is_empty());
+    }
+}
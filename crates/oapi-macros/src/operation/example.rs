@@ -5,7 +5,7 @@ use syn::{Error, LitStr, Token, parenthesized};
 
 use crate::{AnyValue, parse_utils};
 
-// (name = (summary = "...", description = "...", value = "..", external_value = "..."))
+// (name = (summary = "...", description = "...", value = "..", value_file = "..", external_value = "..."))
 #[derive(Default, Debug)]
 pub(crate) struct Example {
     pub(crate) name: String,
@@ -47,6 +47,11 @@ impl Parse for Example {
                         AnyValue::parse_json(&content)
                     })?)
                 }
+                "value_file" => {
+                    example.value = Some(parse_utils::parse_next(&content, || {
+                        AnyValue::parse_file(&content)
+                    })?)
+                }
                 "external_value" => {
                     example.external_value = Some(
                         parse_utils::parse_next(&content, || content.parse::<LitStr>())?.value(),
@@ -56,7 +61,7 @@ impl Parse for Example {
                     return Err(Error::new(
                         ident.span(),
                         format!(
-                            "unexpected attribute: {attr_name}, expected one of: summary, description, value, external_value"
+                            "unexpected attribute: {attr_name}, expected one of: summary, description, value, value_file, external_value"
                         ),
                     ));
                 }
@@ -71,6 +76,24 @@ impl Parse for Example {
     }
 }
 
+impl Example {
+    pub(crate) fn is_file(&self) -> bool {
+        self.value.as_ref().is_some_and(AnyValue::is_file)
+    }
+}
+
+/// Wraps `content` tokens to check the examples loaded from files against the schema in debug
+/// builds, `components` must be in scope.
+pub(crate) fn check_file_examples(content: TokenStream) -> TokenStream {
+    let oapi = crate::oapi_crate();
+    quote! {{
+        let content = #content;
+        #[cfg(debug_assertions)]
+        #oapi::oapi::__private::assert_content_examples(&content, components);
+        content
+    }}
+}
+
 impl ToTokens for Example {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let oapi = crate::oapi_crate();
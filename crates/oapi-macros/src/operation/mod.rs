@@ -3,9 +3,9 @@ use std::ops::Deref;
 
 use proc_macro2::{Ident, TokenStream};
 use quote::{ToTokens, quote};
-use syn::{Expr, ExprPath, Path, Token, Type, parenthesized, parse::Parse, token::Paren};
+use syn::{Expr, ExprPath, LitStr, Path, Token, Type, parenthesized, parse::Parse, token::Paren};
 
-use crate::endpoint::EndpointAttr;
+use crate::endpoint::{EndpointAttr, Extension};
 use crate::parse_utils::LitStrOrExpr;
 use crate::schema_type::SchemaType;
 use crate::security_requirement::SecurityRequirementsAttr;
@@ -27,6 +27,8 @@ pub(crate) struct Operation<'a> {
     request_body: Option<&'a RequestBodyAttr<'a>>,
     responses: &'a Vec<Response<'a>>,
     security: Option<&'a Array<'a, SecurityRequirementsAttr>>,
+    visibility: Option<&'a LitStr>,
+    extensions: &'a [Extension],
     summary: Option<Summary<'a>>,
     description: Option<Description<'a>>,
 }
@@ -78,6 +80,8 @@ impl<'a> Operation<'a> {
             request_body: attr.request_body.as_ref(),
             responses: attr.responses.as_ref(),
             security: attr.security.as_ref(),
+            visibility: attr.visibility.as_ref(),
+            extensions: &attr.extensions,
             summary,
             description,
         }
@@ -123,6 +127,18 @@ impl<'a> Operation<'a> {
             })
         }
 
+        if let Some(visibility) = self.visibility {
+            modifiers.push(quote! {
+                operation.extensions.insert(#oapi::oapi::VISIBILITY_EXTENSION.into(), #visibility.into());
+            })
+        }
+
+        for Extension { key, value } in self.extensions {
+            modifiers.push(quote! {
+                operation.extensions.insert(#key.into(), #value);
+            })
+        }
+
         if let Some(tags) = self.tags {
             let tags = tags.iter().collect::<Array<_>>();
             modifiers.push(quote! {
@@ -166,20 +182,9 @@ impl<'a> Operation<'a> {
                 }
                 Response::Tuple(tuple) => {
                     let code = &tuple.status_code;
-                    if let Some(inner) = &tuple.inner {
-                        match inner {
-                            ResponseTupleInner::Ref(inline) => {
-                                let ty = &inline.ty;
-                                modifiers.push(quote! {
-                                    let _= <#ty as #oapi::oapi::ToSchema>::to_schema(components);
-                                });
-                            }
-                            ResponseTupleInner::Value(value) => {
-                                if let Some(content) = &value.response_type {
-                                    modifiers
-                                        .append(&mut generate_register_schemas(&oapi, content));
-                                }
-                            }
+                    if let Some(ResponseTupleInner::Value(value)) = &tuple.inner {
+                        if let Some(content) = &value.response_type {
+                            modifiers.append(&mut generate_register_schemas(&oapi, content));
                         }
                     }
                     let tuple = tuple.try_to_token_stream()?;
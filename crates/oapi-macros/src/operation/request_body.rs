@@ -7,7 +7,7 @@ use crate::component::ComponentSchema;
 use crate::feature::attributes::Inline;
 use crate::{AnyValue, Array, DiagResult, Required, TryToTokens, parse_utils};
 
-use super::example::Example;
+use super::example::{Example, check_file_examples};
 use super::{PathType, PathTypeTree};
 
 /// Parsed information related to request body of path.
@@ -58,8 +58,7 @@ pub(crate) struct RequestBodyAttr<'r> {
 
 impl Parse for RequestBodyAttr<'_> {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        const EXPECTED_ATTRIBUTE_MESSAGE: &str =
-            "unexpected attribute, expected any of: content, content_type, description, examples";
+        const EXPECTED_ATTRIBUTE_MESSAGE: &str = "unexpected attribute, expected any of: content, content_type, description, example, example_file, examples";
         let lookahead = input.lookahead1();
 
         if lookahead.peek(Paren) {
@@ -99,6 +98,11 @@ impl Parse for RequestBodyAttr<'_> {
                             AnyValue::parse_json(&group)
                         })?)
                     }
+                    "example_file" => {
+                        request_body_attr.example = Some(parse_utils::parse_next(&group, || {
+                            AnyValue::parse_file(&group)
+                        })?)
+                    }
                     "examples" => {
                         request_body_attr.examples =
                             Some(parse_utils::parse_punctuated_within_parenthesis(&group)?)
@@ -172,6 +176,14 @@ impl TryToTokens for RequestBodyAttr<'_> {
                     .extend_examples(#examples)
                 ))
             }
+            if self.example.as_ref().is_some_and(AnyValue::is_file)
+                || self
+                    .examples
+                    .as_ref()
+                    .is_some_and(|examples| examples.iter().any(Example::is_file))
+            {
+                content = check_file_examples(content);
+            }
 
             match body_type {
                 PathType::RefPath(_) => {
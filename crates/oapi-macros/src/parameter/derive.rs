@@ -19,6 +19,7 @@ use crate::feature::validation::{
     ExclusiveMaximum, ExclusiveMinimum, MaxItems, MaxLength, Maximum, MinItems, MinLength, Minimum,
     MultipleOf, Pattern,
 };
+use crate::feature::validator_attrs::merge_validator_features;
 use crate::feature::{
     Feature, FeaturesExt, Merge, TryToTokensExt, impl_into_inner, impl_merge, parse_features,
     pop_feature,
@@ -92,6 +93,14 @@ impl TryToTokens for ToParameters {
             .into_iter()
             .reduce(|acc, item| acc.merge(item));
         let serde_container = serde_util::parse_container(&self.attrs);
+        // `#[derive(TypedPath)]` implements `Extractible` for the struct with this attribute.
+        let is_typed_path = self.attrs.iter().any(|attr| {
+            attr.path().is_ident("salvo")
+                && attribute::find_nested_list(attr, "typed_path")
+                    .ok()
+                    .flatten()
+                    .is_some()
+        });
 
         // #[param] is only supported over fields
         if self.attrs.iter().any(|attr| {
@@ -117,7 +126,12 @@ impl TryToTokens for ToParameters {
 
         let default_style = pop_feature!(parameters_features => Feature::DefaultStyle(_));
         let default_parameter_in =
-            pop_feature!(parameters_features => Feature::DefaultParameterIn(_));
+            pop_feature!(parameters_features => Feature::DefaultParameterIn(_)).or_else(|| {
+                // Params of typed path are all in path.
+                is_typed_path.then_some(Feature::DefaultParameterIn(DefaultParameterIn(
+                    ParameterIn::Path,
+                )))
+            });
         let rename_all = pop_feature!(parameters_features => Feature::RenameAll(_));
         let default_source_from =
             if let Some(Feature::DefaultParameterIn(DefaultParameterIn(default_parameter_in))) =
@@ -240,25 +254,29 @@ impl TryToTokens for ToParameters {
                     }
                 }
             }
-            impl #ex_impl_generics #salvo::Extractible<'__macro_gen_ex> for #ident #ty_generics #where_clause {
-                fn metadata() -> &'__macro_gen_ex #salvo::extract::Metadata {
-                    static METADATA: ::std::sync::OnceLock<#salvo::extract::Metadata> = ::std::sync::OnceLock::new();
-                    METADATA.get_or_init(||
-                        #salvo::extract::Metadata::new(#name)
-                            .default_sources(vec![#default_source])
-                            .fields(vec![#(#extract_fields),*])
-                            #rename_all
-                            #serde_rename_all
-                    )
-                }
-                async fn extract(req: &'__macro_gen_ex mut #salvo::Request) -> Result<Self, impl #salvo::Writer + Send + std::fmt::Debug + 'static> {
-                    #salvo::serde::from_request(req, Self::metadata()).await
-                }
-                async fn extract_with_arg(req: &'__macro_gen_ex mut #salvo::Request, _arg: &str) -> Result<Self, impl #salvo::Writer + Send + std::fmt::Debug + 'static> {
-                    Self::extract(req).await
-                }
-            }
         });
+        if !is_typed_path {
+            tokens.extend(quote! {
+                impl #ex_impl_generics #salvo::Extractible<'__macro_gen_ex> for #ident #ty_generics #where_clause {
+                    fn metadata() -> &'__macro_gen_ex #salvo::extract::Metadata {
+                        static METADATA: ::std::sync::OnceLock<#salvo::extract::Metadata> = ::std::sync::OnceLock::new();
+                        METADATA.get_or_init(||
+                            #salvo::extract::Metadata::new(#name)
+                                .default_sources(vec![#default_source])
+                                .fields(vec![#(#extract_fields),*])
+                                #rename_all
+                                #serde_rename_all
+                        )
+                    }
+                    async fn extract(req: &'__macro_gen_ex mut #salvo::Request) -> Result<Self, impl #salvo::Writer + Send + std::fmt::Debug + 'static> {
+                        #salvo::serde::from_request(req, Self::metadata()).await
+                    }
+                    async fn extract_with_arg(req: &'__macro_gen_ex mut #salvo::Request, _arg: &str) -> Result<Self, impl #salvo::Writer + Send + std::fmt::Debug + 'static> {
+                        Self::extract(req).await
+                    }
+                }
+            });
+        }
         Ok(())
     }
 }
@@ -540,7 +558,7 @@ impl TryToTokens for Parameter<'_> {
             name = &name[2..];
         }
 
-        let (schema_features, mut param_features) = self.resolve_field_features()?;
+        let (mut schema_features, mut param_features) = self.resolve_field_features()?;
 
         let rename = param_features
             .pop_rename_feature()
@@ -616,6 +634,7 @@ impl TryToTokens for Parameter<'_> {
             });
             tokens.extend(param_features.try_to_token_stream()?);
 
+            merge_validator_features(&mut schema_features, &field.attrs, &component);
             let schema = ComponentSchema::new(component::ComponentSchemaProps {
                 type_tree: &component,
                 features: Some(schema_features),
@@ -107,6 +107,12 @@ impl TryToTokens for ToResponses<'_> {
         let oapi = crate::oapi_crate();
         let responses = match &self.data {
             Data::Struct(struct_value) => match &struct_value.fields {
+                _ if is_problem_response(self.attributes)? => {
+                    let response = UnitStructResponse::new(self.attributes)?.0;
+                    let status_code = &response.status_code;
+                    let response = response.try_to_token_stream()?;
+                    Array::from_iter(iter::once(quote!((#status_code, #response))))
+                }
                 Fields::Named(fields) => {
                     let response =
                         NamedStructResponse::new(self.attributes, self.ident, &fields.named)?.0;
@@ -138,6 +144,9 @@ impl TryToTokens for ToResponses<'_> {
                 .variants
                 .iter()
                 .map(|variant| match &variant.fields {
+                    _ if is_problem_response(&variant.attrs)? => {
+                        Ok(UnitStructResponse::new(&variant.attrs)?.0)
+                    }
                     Fields::Named(fields) => Ok(NamedStructResponse::new(
                         &variant.attrs,
                         &variant.ident,
@@ -195,6 +204,12 @@ impl TryToTokens for ToResponses<'_> {
     }
 }
 
+/// Returns whether the response is documented as problem details instead of its fields, which
+/// is set by the `problem` flag of `#[salvo(response(...))]`.
+fn is_problem_response(attributes: &[Attribute]) -> DiagResult<bool> {
+    Ok(DeriveToResponsesValue::from_attributes(attributes)?.is_some_and(|value| value.problem))
+}
+
 trait Response {
     fn to_type(ident: &Ident) -> Type {
         let path = Path::from(ident.clone());
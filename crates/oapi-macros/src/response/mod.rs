@@ -15,7 +15,9 @@ use syn::{Attribute, DeriveInput, Error, ExprPath, LitInt, LitStr, Token, parent
 use crate::component::ComponentSchema;
 use crate::feature::attributes::Inline;
 use crate::operation::{
-    InlineType, PathType, PathTypeTree, example::Example, status::STATUS_CODES,
+    InlineType, PathType, PathTypeTree,
+    example::{Example, check_file_examples},
+    status::STATUS_CODES,
 };
 use crate::type_tree::TypeTree;
 use crate::{AnyValue, Array, DiagResult, Diagnostic, TryToTokens, attribute, parse_utils};
@@ -121,7 +123,7 @@ pub(crate) enum ResponseTupleInner<'r> {
 
 impl Parse for ResponseTuple<'_> {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        const EXPECTED_ATTRIBUTE_MESSAGE: &str = "unexpected attribute, expected any of: status_code, description, body, content_type, headers, example, examples, response";
+        const EXPECTED_ATTRIBUTE_MESSAGE: &str = "unexpected attribute, expected any of: status_code, description, body, content_type, headers, example, example_file, examples, response";
 
         let mut response = ResponseTuple::default();
 
@@ -156,6 +158,9 @@ impl Parse for ResponseTuple<'_> {
                 "example" => {
                     response.as_value(input.span())?.example = Some(parse::example(input)?);
                 }
+                "example_file" => {
+                    response.as_value(input.span())?.example = Some(parse::example_file(input)?);
+                }
                 "examples" => {
                     response.as_value(input.span())?.examples = Some(parse::examples(input)?);
                 }
@@ -273,6 +278,23 @@ impl<'r> ResponseValue<'r> {
         response_value: DeriveToResponsesValue,
         description: parse_utils::LitStrOrExpr,
     ) -> Self {
+        let (response_type, content_type) = if response_value.problem {
+            let oapi = crate::oapi_crate();
+            let content_type = response_value.content_type.unwrap_or_else(|| {
+                vec![parse_utils::LitStrOrExpr::LitStr(LitStr::new(
+                    "application/problem+json",
+                    Span::call_site(),
+                ))]
+            });
+            (
+                Some(PathType::RefPath(
+                    syn::parse_quote!(#oapi::oapi::__private::Problem),
+                )),
+                Some(content_type),
+            )
+        } else {
+            (None, response_value.content_type)
+        };
         ResponseValue {
             description: if response_value.description.is_empty() && !description.is_empty() {
                 description
@@ -282,7 +304,8 @@ impl<'r> ResponseValue<'r> {
             headers: response_value.headers,
             example: response_value.example.map(|(example, _)| example),
             examples: response_value.examples.map(|(examples, _)| examples),
-            content_type: response_value.content_type,
+            content_type,
+            response_type,
             ..Default::default()
         }
     }
@@ -303,9 +326,22 @@ impl TryToTokens for ResponseTuple<'_> {
         {
             ResponseTupleInner::Ref(res) => {
                 let path = &res.ty;
-                tokens.extend(quote_spanned! {path.span()=>
-                    <#path as #oapi::oapi::ToResponse>::to_response(components)
-                });
+                if res.is_inline {
+                    tokens.extend(quote_spanned! {path.span()=>
+                        match <#path as #oapi::oapi::ToResponse>::to_response(components) {
+                            #oapi::oapi::RefOr::Ref(reference) => components
+                                .responses
+                                .get(reference.ref_location.trim_start_matches("#/components/responses/"))
+                                .cloned()
+                                .unwrap_or(#oapi::oapi::RefOr::Ref(reference)),
+                            response => response,
+                        }
+                    });
+                } else {
+                    tokens.extend(quote_spanned! {path.span()=>
+                        <#path as #oapi::oapi::ToResponse>::to_response(components)
+                    });
+                }
             }
             ResponseTupleInner::Value(val) => {
                 let description = &val.description;
@@ -354,6 +390,13 @@ impl TryToTokens for ResponseTuple<'_> {
                             .collect::<Array<TokenStream>>();
                         content.extend(quote!( .extend_examples(#examples)))
                     }
+                    if example.as_ref().is_some_and(AnyValue::is_file)
+                        || examples
+                            .as_ref()
+                            .is_some_and(|examples| examples.iter().any(Example::is_file))
+                    {
+                        content = check_file_examples(content);
+                    }
                     Ok(quote! {
                         #content
                     })
@@ -533,6 +576,7 @@ struct DeriveToResponsesValue {
     description: parse_utils::LitStrOrExpr,
     example: Option<(AnyValue, Ident)>,
     examples: Option<(Punctuated<Example, Token![,]>, Ident)>,
+    problem: bool,
 }
 
 impl DeriveResponseValue for DeriveToResponsesValue {
@@ -554,6 +598,7 @@ impl DeriveResponseValue for DeriveToResponsesValue {
         if other.examples.is_some() {
             self.examples = other.examples;
         }
+        self.problem |= other.problem;
 
         self
     }
@@ -604,11 +649,14 @@ impl Parse for DeriveToResponsesValue {
                 "examples" => {
                     response.examples = Some((parse::examples(input)?, ident));
                 }
+                "problem" => {
+                    response.problem = true;
+                }
                 _ => {
                     return Err(Error::new(
                         ident.span(),
                         format!(
-                            "unexpected attribute: {attr_name}, expected any of: description, content_type, headers, example, examples"
+                            "unexpected attribute: {attr_name}, expected any of: description, content_type, headers, example, examples, problem"
                         ),
                     ));
                 }
@@ -734,6 +782,11 @@ impl Parse for Content<'_> {
                         AnyValue::parse_json(&content)
                     })?)
                 }
+                "example_file" => {
+                    example = Some(parse_utils::parse_next(&content, || {
+                        AnyValue::parse_file(&content)
+                    })?)
+                }
                 "examples" => {
                     examples = Some(parse_utils::parse_punctuated_within_parenthesis(&content)?)
                 }
@@ -741,7 +794,7 @@ impl Parse for Content<'_> {
                     return Err(Error::new(
                         ident.span(),
                         format!(
-                            "unexpected attribute: {ident}, expected one of: example, examples"
+                            "unexpected attribute: {ident}, expected one of: example, example_file, examples"
                         ),
                     ));
                 }
@@ -44,6 +44,11 @@ pub(super) fn example(input: ParseStream) -> Result<AnyValue> {
     parse_utils::parse_next(input, || AnyValue::parse_lit_str_or_json(input))
 }
 
+#[inline]
+pub(super) fn example_file(input: ParseStream) -> Result<AnyValue> {
+    parse_utils::parse_next(input, || AnyValue::parse_file(input))
+}
+
 #[inline]
 pub(super) fn examples(input: ParseStream) -> Result<Punctuated<Example, Token![,]>> {
     parse_utils::parse_punctuated_within_parenthesis(input)
@@ -22,8 +22,7 @@ use crate::type_tree::{TypeTree, ValueType};
 use crate::{DiagLevel, DiagResult, Diagnostic, IntoInner, TryToTokens};
 
 use super::enum_variant::{
-    self, AdjacentlyTaggedEnum, CustomEnum, Enum, ObjectVariant, SimpleEnumVariant, TaggedEnum,
-    UntaggedEnum,
+    self, CustomEnum, Enum, ObjectVariant, SimpleEnumVariant, TaggedEnum, UntaggedEnum,
 };
 use super::feature::{
     self, ComplexEnumFeatures, EnumFeatures, EnumNamedFieldVariantFeatures,
@@ -424,26 +423,25 @@ fn regular_enum_to_tokens<T: self::enum_variant::Variant>(
     tokens.extend(match container_rules {
         Some(serde_container) => match &serde_container.enum_repr {
             SerdeEnumRepr::ExternallyTagged => Enum::new(enum_values).to_token_stream(),
-            SerdeEnumRepr::InternallyTagged { tag } => TaggedEnum::new(
-                enum_values
-                    .into_iter()
-                    .map(|variant| (Cow::Borrowed(tag.as_str()), variant)),
-            )
-            .to_token_stream(),
+            // Unit variants of adjacently tagged enum are serialized without content, so they are same as
+            // internally tagged.
+            SerdeEnumRepr::InternallyTagged { tag }
+            | SerdeEnumRepr::AdjacentlyTagged { tag, .. } => {
+                let oapi = crate::oapi_crate();
+                let tagged_enum = TaggedEnum::new(
+                    enum_values
+                        .into_iter()
+                        .map(|variant| (Cow::Borrowed(tag.as_str()), variant)),
+                );
+                quote! {
+                    #tagged_enum
+                        .discriminator(#oapi::oapi::schema::Discriminator::new(#tag))
+                }
+            }
             SerdeEnumRepr::Untagged => match UntaggedEnum::new().try_to_token_stream() {
                 Ok(tokens) => tokens,
                 Err(diag) => diag.emit_as_item_tokens(),
             },
-            SerdeEnumRepr::AdjacentlyTagged { tag, content } => {
-                AdjacentlyTaggedEnum::new(enum_values.into_iter().map(|variant| {
-                    (
-                        Cow::Borrowed(tag.as_str()),
-                        Cow::Borrowed(content.as_str()),
-                        variant,
-                    )
-                }))
-                .to_token_stream()
-            }
             // This should not be possible as serde should not let that happen
             SerdeEnumRepr::UnfinishedAdjacentlyTagged { .. } => panic!("Invalid serde enum repr"),
         },
@@ -825,7 +823,7 @@ impl ComplexEnum<'_> {
                     } else {
                         Ok(Some(quote! {
                             #unnamed_enum
-                                #name
+                                #title
                                 .schema_type(#oapi::oapi::schema::BasicType::Object)
                                 .property(#tag, #variant_name_tokens)
                                 .required(#tag)
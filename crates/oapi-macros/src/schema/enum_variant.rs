@@ -286,79 +286,6 @@ impl TryToTokens for UntaggedEnum {
     }
 }
 
-pub(crate) struct AdjacentlyTaggedEnum<T: Variant> {
-    items: TokenStream,
-    len: usize,
-    _p: PhantomData<T>,
-}
-
-impl<V: Variant> AdjacentlyTaggedEnum<V> {
-    pub(crate) fn new<'t, I: IntoIterator<Item = (Cow<'t, str>, Cow<'t, str>, V)>>(
-        items: I,
-    ) -> Self {
-        items.into_iter().collect()
-    }
-}
-
-impl<T> ToTokens for AdjacentlyTaggedEnum<T>
-where
-    T: Variant,
-{
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let oapi = crate::oapi_crate();
-        let len = &self.len;
-        let items = &self.items;
-
-        tokens.extend(quote! {
-            Into::<#oapi::oapi::schema::OneOf>::into(#oapi::oapi::schema::OneOf::with_capacity(#len))
-                #items
-        })
-    }
-}
-
-impl<'t, V: Variant> FromIterator<(Cow<'t, str>, Cow<'t, str>, V)> for AdjacentlyTaggedEnum<V> {
-    fn from_iter<T: IntoIterator<Item = (Cow<'t, str>, Cow<'t, str>, V)>>(iter: T) -> Self {
-        let oapi = crate::oapi_crate();
-        let mut len = 0;
-
-        let items = iter
-            .into_iter()
-            .enumerate()
-            .map(|(index, (tag, content, variant))| {
-                len = index + 1;
-
-                let (schema_type, enum_type) = variant.get_type();
-                let item = variant.to_tokens();
-                quote! {
-                    .item(
-                        #oapi::oapi::schema::Object::new()
-                            .property(
-                                #tag,
-                                #oapi::oapi::schema::Object::new()
-                                    .schema_type(#oapi::oapi::schema::BasicType::String)
-                                    .enum_values::<[#enum_type; 1], #enum_type>([#content])
-                            )
-                            .required(#tag)
-                            .property(
-                                #content,
-                                #oapi::oapi::schema::Object::new()
-                                    .schema_type(#schema_type)
-                                    .enum_values::<[#enum_type; 1], #enum_type>([#item])
-                            )
-                            .required(#content)
-                    )
-                }
-            })
-            .collect::<TokenStream>();
-
-        Self {
-            items,
-            len,
-            _p: PhantomData,
-        }
-    }
-}
-
 /// Used to create complex enums with varying Object types.
 ///
 /// Will create `oneOf` object with discriminator field for referenced schemas.
@@ -34,7 +34,9 @@ impl Parse for NamedFieldStructFeatures {
             Description,
             Skip,
             Bound,
-            SkipBound
+            SkipBound,
+            ReadOnly,
+            WriteOnly
         )))
     }
 }
@@ -145,7 +147,8 @@ impl Parse for NamedFieldFeatures {
             Deprecated,
             Skip,
             ContentEncoding,
-            ContentMediaType
+            ContentMediaType,
+            Title
         )))
     }
 }
@@ -10,6 +10,7 @@ use crate::doc_comment::CommentAttributes;
 use crate::feature::attributes::{
     self, Alias, Bound, Default, Name, RenameAll, Required, SkipBound,
 };
+use crate::feature::validator_attrs::merge_validator_features;
 use crate::feature::{
     Feature, FeaturesExt, IsSkipped, TryToTokensExt, parse_features, pop_feature,
     pop_feature_as_inner,
@@ -125,6 +126,11 @@ impl NamedStructSchema<'_> {
         let required = pop_feature_as_inner!(field_features => Feature::Required(_v));
         let type_tree = override_type_tree.as_ref().unwrap_or(type_tree);
         let is_option = type_tree.is_option();
+        merge_validator_features(
+            field_features.get_or_insert_with(Vec::new),
+            &field.attrs,
+            type_tree,
+        );
 
         Ok(NamedStructFieldOptions {
             property: if let Some(with_schema) = with_schema {
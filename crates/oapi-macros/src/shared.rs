@@ -8,11 +8,13 @@ use quote::{ToTokens, TokenStreamExt, quote};
 use regex::Regex;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
+use syn::token::Comma;
 use syn::{
-    Attribute, ExprPath, FnArg, Ident, Lit, LitStr, Member, PatType, Receiver, Token, Type,
-    TypePath,
+    Attribute, Expr, ExprLit, ExprPath, FnArg, Ident, Lit, LitStr, Member, Meta, PatType, Path,
+    Receiver, Signature, Token, Type, TypePath,
 };
 
+use crate::attribute;
 use crate::feature::attributes;
 use crate::{RenameRule, SerdeContainer, SerdeValue, parse_utils};
 
@@ -93,6 +95,48 @@ pub(crate) fn parse_input_type(input: &FnArg) -> InputType {
     }
 }
 
+/// Parse the function from `#[salvo(extract(error = "..."))]` of a handler parameter, it converts the
+/// extraction error to a custom writer.
+pub(crate) fn parse_param_extract_error(pat: &PatType) -> syn::Result<Option<Path>> {
+    let mut error = None;
+    for attr in &pat.attrs {
+        if attr.path().is_ident("salvo") {
+            if let Some(metas) = attribute::find_nested_list(attr, "extract")? {
+                let nested = metas.parse_args_with(Punctuated::<Meta, Comma>::parse_terminated)?;
+                for meta in nested {
+                    match meta {
+                        Meta::NameValue(meta) if meta.path.is_ident("error") => {
+                            error = Some(parse_fn_path(&meta.value)?);
+                        }
+                        _ => return Err(syn::Error::new_spanned(meta, "unexpected attribute")),
+                    }
+                }
+            }
+        }
+    }
+    Ok(error)
+}
+
+/// Parse a function path from a path or a string literal.
+pub(crate) fn parse_fn_path(expr: &Expr) -> syn::Result<Path> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(s), ..
+        }) => s.parse(),
+        Expr::Path(ExprPath { path, .. }) => Ok(path.clone()),
+        _ => Err(syn::Error::new_spanned(expr, "invalid function path")),
+    }
+}
+
+/// Remove the `#[salvo(...)]` attributes of the parameters, they are only used by macros.
+pub(crate) fn remove_param_attrs(sig: &mut Signature) {
+    for input in &mut sig.inputs {
+        if let FnArg::Typed(pat) = input {
+            pat.attrs.retain(|attr| !attr.path().is_ident("salvo"));
+        }
+    }
+}
+
 pub(crate) fn omit_type_path_lifetimes(ty_path: &TypePath) -> TypePath {
     let reg = Regex::new(r"'\w+").expect("invalid regex");
     let ty_path = ty_path.into_token_stream().to_string();
@@ -333,6 +377,8 @@ pub(crate) enum AnyValue {
         struct_ident: Ident,
         field_ident: Member,
     },
+    /// Json file path relative to the `CARGO_MANIFEST_DIR`, embedded at compile time.
+    File(LitStr),
 }
 
 impl AnyValue {
@@ -373,6 +419,15 @@ impl AnyValue {
         }
     }
 
+    /// Parse json file path as [`AnyValue::File`]
+    pub(crate) fn parse_file(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<LitStr>().map(AnyValue::File)
+    }
+
+    pub(crate) fn is_file(&self) -> bool {
+        matches!(self, Self::File(_))
+    }
+
     pub(crate) fn parse_lit_str_or_json(input: ParseStream) -> syn::Result<Self> {
         if input.peek(LitStr) {
             Ok(AnyValue::String(
@@ -408,6 +463,12 @@ impl ToTokens for AnyValue {
             } => tokens.extend(quote! {
                 #oapi::oapi::__private::serde_json::to_value(#struct_ident::default().#field_ident).unwrap()
             }),
+            Self::File(path) => tokens.extend(quote! {
+                #oapi::oapi::__private::serde_json::from_str::<#oapi::oapi::__private::serde_json::Value>(
+                    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path))
+                )
+                .expect(concat!("example file `", #path, "` is not valid json"))
+            }),
         }
     }
 }
@@ -250,3 +250,181 @@ fn test_derive_to_schema_enum() {
         })
     );
 }
+
+#[test]
+fn test_derive_to_schema_annotations() {
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    #[salvo(schema(title = "Pet owner", read_only))]
+    struct Owner {
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    struct Pet {
+        #[salvo(schema(title = "Pet id", read_only, example = 1))]
+        id: u64,
+        #[salvo(schema(write_only, format = Password))]
+        password: String,
+        #[salvo(schema(deprecated))]
+        nickname: String,
+        #[salvo(schema(title = "Owner of pet", read_only, example = json!({"name": "jobs"})))]
+        owner: Owner,
+    }
+
+    #[endpoint]
+    async fn create_pet(body: JsonBody<Pet>) -> String {
+        format!("{:?}", body)
+    }
+
+    salvo::oapi::naming::set_namer(
+        salvo::oapi::naming::FlexNamer::new()
+            .short_mode(true)
+            .generic_delimiter('_', '_'),
+    );
+
+    let router = Router::new().push(Router::with_path("pets").post(create_pet));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let value = serde_json::to_value(&doc).unwrap();
+    let owner = value.pointer("/components/schemas/Owner").unwrap();
+    assert_eq!(owner["title"], "Pet owner");
+    assert_eq!(owner["readOnly"], true);
+
+    let properties = value.pointer("/components/schemas/Pet/properties").unwrap();
+    assert_eq!(properties["id"]["title"], "Pet id");
+    assert_eq!(properties["id"]["readOnly"], true);
+    assert_eq!(properties["id"]["examples"], json!([1]));
+    assert_eq!(properties["password"]["writeOnly"], true);
+    assert_eq!(properties["password"]["format"], "password");
+    assert_eq!(properties["nickname"]["deprecated"], true);
+    assert_json_eq!(
+        properties["owner"],
+        json!({
+            "allOf": [{
+                "$ref": "#/components/schemas/Owner"
+            }],
+            "title": "Owner of pet",
+            "readOnly": true,
+            "examples": [{
+                "name": "jobs"
+            }]
+        })
+    );
+}
+
+#[test]
+fn test_derive_to_schema_recursive_generics() {
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    struct Tree<T: ToSchema + std::fmt::Debug + 'static> {
+        value: T,
+        children: Vec<Tree<T>>,
+    }
+
+    #[endpoint]
+    async fn create_tree(body: JsonBody<Tree<i32>>) -> String {
+        format!("{:?}", body)
+    }
+
+    salvo::oapi::naming::set_namer(
+        salvo::oapi::naming::FlexNamer::new()
+            .short_mode(true)
+            .generic_delimiter('_', '_'),
+    );
+
+    let router = Router::new().push(Router::with_path("trees").post(create_tree));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let value = serde_json::to_value(&doc).unwrap();
+    let schemas = value.pointer("/components/schemas").unwrap();
+    assert_json_eq!(
+        schemas,
+        json!({
+            "Tree_i32_": {
+                "type": "object",
+                "required": [
+                    "value",
+                    "children"
+                ],
+                "properties": {
+                    "value": {
+                        "type": "integer",
+                        "format": "int32"
+                    },
+                    "children": {
+                        "type": "array",
+                        "items": {
+                            "$ref": "#/components/schemas/Tree_i32_"
+                        }
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_derive_to_schema_tagged_unit_enum() {
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    #[serde(tag = "kind")]
+    enum Shape {
+        Circle,
+        Square,
+    }
+
+    #[derive(Serialize, Deserialize, ToSchema, Debug)]
+    #[serde(tag = "kind", content = "data")]
+    enum Color {
+        Red,
+        Blue,
+    }
+
+    #[endpoint]
+    async fn paint(shape: JsonBody<Shape>, color: JsonBody<Color>) -> String {
+        format!("{:?} {:?}", shape, color)
+    }
+
+    salvo::oapi::naming::set_namer(
+        salvo::oapi::naming::FlexNamer::new()
+            .short_mode(true)
+            .generic_delimiter('_', '_'),
+    );
+
+    let router = Router::new().push(Router::with_path("paint").post(paint));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let value = serde_json::to_value(&doc).unwrap();
+    let schemas = value.pointer("/components/schemas").unwrap();
+    let variant = |name: &str| {
+        json!({
+            "type": "object",
+            "required": ["kind"],
+            "properties": {
+                "kind": {
+                    "type": "string",
+                    "enum": [name]
+                }
+            }
+        })
+    };
+    assert_json_eq!(
+        schemas,
+        json!({
+            "Shape": {
+                "oneOf": [variant("Circle"), variant("Square")],
+                "discriminator": {
+                    "propertyName": "kind"
+                }
+            },
+            "Color": {
+                "oneOf": [variant("Red"), variant("Blue")],
+                "discriminator": {
+                    "propertyName": "kind"
+                }
+            }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Color::Red).unwrap(),
+        json!({"kind": "Red"})
+    );
+}
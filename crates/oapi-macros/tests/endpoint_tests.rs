@@ -44,3 +44,237 @@ fn test_endpoint_hello() {
         })
     );
 }
+
+#[test]
+fn test_endpoint_response_headers_and_reusable_responses() {
+    #[derive(salvo::oapi::ToResponse)]
+    #[salvo(response(description = "Pet not found"))]
+    struct NotFound;
+
+    #[endpoint(responses(
+        (status_code = 201, description = "Pet created", body = String,
+            content_type = ["application/json", "text/plain"],
+            headers(
+                ("Location" = String, description = "Url of the created pet"),
+                ("X-RateLimit-Remaining", description = "Remaining requests")
+            )
+        ),
+        (status_code = 404, response = NotFound),
+        (status_code = 410, response = inline(NotFound)),
+    ))]
+    async fn create_pet(res: &mut Response) {
+        res.status_code(StatusCode::CREATED);
+    }
+
+    let router = Router::new().push(Router::with_path("pets").post(create_pet));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    assert_json_eq!(
+        doc,
+        json!({
+            "openapi":"3.1.0",
+            "info":{
+                "title":"test api",
+                "version":"0.0.1"
+            },
+            "paths":{
+                "/pets":{
+                    "post":{
+                        "operationId":"endpoint_tests.test_endpoint_response_headers_and_reusable_responses.create_pet",
+                        "responses":{
+                            "201":{
+                                "description":"Pet created",
+                                "headers":{
+                                    "Location":{
+                                        "schema":{"type":"string"},
+                                        "description":"Url of the created pet"
+                                    },
+                                    "X-RateLimit-Remaining":{
+                                        "schema":{"type":"string"},
+                                        "description":"Remaining requests"
+                                    }
+                                },
+                                "content":{
+                                    "application/json":{"schema":{"type":"string"}},
+                                    "text/plain":{"schema":{"type":"string"}}
+                                }
+                            },
+                            "404":{"$ref":"#/components/responses/NotFound"},
+                            "410":{"description":"Pet not found"}
+                        }
+                    }
+                }
+            },
+            "components":{
+                "responses":{
+                    "NotFound":{"description":"Pet not found"}
+                }
+            }
+        })
+    );
+}
+
+#[test]
+fn test_endpoint_visibility() {
+    #[endpoint(visibility = "internal")]
+    async fn reindex() {}
+
+    #[endpoint]
+    async fn hello() -> &'static str {
+        "Hello"
+    }
+
+    let router = Router::new()
+        .push(Router::with_path("hello").get(hello))
+        .push(Router::with_path("reindex").post(reindex));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let value = serde_json::to_value(&doc).unwrap();
+    assert_eq!(
+        value["paths"]["/reindex"]["post"]["x-visibility"],
+        "internal"
+    );
+
+    let public = doc.filter_visibility(["public"]);
+    assert_eq!(public.paths.keys().collect::<Vec<_>>(), vec!["/hello"]);
+    let internal = doc.filter_visibility(["public", "internal"]);
+    assert_eq!(internal.paths.len(), 2);
+}
+
+#[test]
+fn test_endpoint_typed_path_parameters() {
+    #[derive(serde::Deserialize, TypedPath, ToParameters)]
+    #[salvo(typed_path(path = "users/{user_id}/posts/{post_id}"))]
+    struct UserPost {
+        /// Id of the user.
+        user_id: u64,
+        /// Id of the post.
+        post_id: u64,
+    }
+
+    #[endpoint]
+    async fn show_post(post: UserPost) -> String {
+        format!("{}/{}", post.user_id, post.post_id)
+    }
+
+    let router = Router::new().push(Router::with_typed_path::<UserPost>().get(show_post));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let operation =
+        &serde_json::to_value(&doc).unwrap()["paths"]["/users/{user_id}/posts/{post_id}"]["get"];
+    let parameters = operation["parameters"].as_array().unwrap();
+    assert_eq!(parameters.len(), 2);
+    assert_eq!(parameters[0]["name"], "user_id");
+    assert_eq!(parameters[0]["in"], "path");
+    assert_eq!(parameters[1]["name"], "post_id");
+    assert_eq!(parameters[1]["in"], "path");
+}
+
+#[test]
+fn test_endpoint_extensions() {
+    fn rate_limit() -> u32 {
+        100
+    }
+
+    #[endpoint(extensions(
+        "x-owner" = "pets-team",
+        "x-rate-limit" = rate_limit,
+        "x-gateway" = json!({"plugins": ["cors"]})
+    ))]
+    async fn list_pets() -> &'static str {
+        "pets"
+    }
+
+    let router = Router::new().push(Router::with_path("pets").get(list_pets));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let operation = &serde_json::to_value(&doc).unwrap()["paths"]["/pets"]["get"];
+    assert_eq!(operation["x-owner"], "pets-team");
+    assert_eq!(operation["x-rate-limit"], 100);
+    assert_eq!(operation["x-gateway"], json!({"plugins": ["cors"]}));
+}
+
+#[test]
+fn test_endpoint_error_responses_as_problem() {
+    #[allow(dead_code)]
+    #[derive(ToResponses, Debug)]
+    enum AppError {
+        /// Pet was not found.
+        #[salvo(response(status_code = 404, problem))]
+        NotFound(u64),
+        /// Database is not available.
+        #[salvo(response(status_code = 503, problem))]
+        Database { reason: String },
+    }
+    impl Scribe for AppError {
+        fn render(self, res: &mut Response) {
+            res.status_code(StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[endpoint]
+    async fn get_pet(id: PathParam<u64>) -> Result<String, AppError> {
+        Err(AppError::NotFound(*id))
+    }
+
+    let router = Router::new().push(Router::with_path("pets/{id}").get(get_pet));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let responses = &serde_json::to_value(&doc).unwrap()["paths"]["/pets/{id}"]["get"]["responses"];
+    assert!(responses.get("200").is_some());
+    assert_eq!(responses["404"]["description"], "Pet was not found.");
+    assert_eq!(
+        responses["503"]["description"],
+        "Database is not available."
+    );
+    for status in ["404", "503"] {
+        let content = responses[status]["content"].as_object().unwrap();
+        assert_eq!(
+            content.keys().collect::<Vec<_>>(),
+            vec!["application/problem+json"]
+        );
+        assert!(
+            content["application/problem+json"]["schema"]["$ref"]
+                .as_str()
+                .unwrap()
+                .starts_with("#/components/schemas/")
+        );
+    }
+}
+
+#[test]
+fn test_endpoint_examples_from_files() {
+    #[derive(ToSchema, serde::Serialize)]
+    struct Pet {
+        id: u64,
+        name: String,
+    }
+
+    #[endpoint(
+        request_body(content = Pet, example_file = "tests/examples/pet.json"),
+        responses(
+            (status_code = 200, body = Pet, examples(
+                ("Tom" = (summary = "A cat", value_file = "tests/examples/pet.json"))
+            ))
+        )
+    )]
+    async fn create_pet(res: &mut Response) {
+        res.render(Json(Pet {
+            id: 1,
+            name: "Tom".into(),
+        }));
+    }
+
+    let router = Router::new().push(Router::with_path("pets").post(create_pet));
+
+    let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    let operation = &serde_json::to_value(&doc).unwrap()["paths"]["/pets"]["post"];
+    assert_eq!(
+        operation["requestBody"]["content"]["application/json"]["example"],
+        json!({"id": 1, "name": "Tom"})
+    );
+    assert_eq!(
+        operation["responses"]["200"]["content"]["application/json"]["examples"]["Tom"]["value"],
+        json!({"id": 1, "name": "Tom"})
+    );
+}
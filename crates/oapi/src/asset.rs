@@ -0,0 +1,69 @@
+//! Serves javascript libraries of the doc UIs from memory, so they work without access to CDNs.
+#[cfg(any(
+    feature = "scalar-embed",
+    feature = "rapidoc-embed",
+    feature = "redoc-embed"
+))]
+use std::borrow::Cow;
+
+use bytes::Bytes;
+use salvo_core::http::{HeaderValue, ResBody, header};
+use salvo_core::{Depot, FlowCtrl, Handler, Request, Response, async_trait};
+
+/// Implements [`Handler`] for serving a javascript library of the doc UIs.
+#[derive(Clone, Debug)]
+pub(crate) struct LibAsset(pub(crate) Bytes);
+
+#[async_trait]
+impl Handler for LibAsset {
+    async fn handle(
+        &self,
+        _req: &mut Request,
+        _depot: &mut Depot,
+        res: &mut Response,
+        _ctrl: &mut FlowCtrl,
+    ) {
+        res.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/javascript; charset=utf-8"),
+        );
+        res.body(ResBody::Once(self.0.clone()));
+    }
+}
+
+/// Returns the url of the library served at `file_name` under the doc page, relative to the doc page
+/// requested with `req_path`.
+pub(crate) fn lib_url(req_path: &str, file_name: &str) -> String {
+    if req_path.ends_with('/') {
+        file_name.to_owned()
+    } else {
+        let segment = req_path.rsplit('/').next().unwrap_or_default();
+        format!("{segment}/{file_name}")
+    }
+}
+
+/// Converts the data of a file embedded by `rust-embed` into [`Bytes`], without copying it when
+/// the file is compiled into the binary.
+#[cfg(any(
+    feature = "scalar-embed",
+    feature = "rapidoc-embed",
+    feature = "redoc-embed"
+))]
+pub(crate) fn embedded_bytes(data: Cow<'static, [u8]>) -> Bytes {
+    match data {
+        Cow::Borrowed(data) => Bytes::from_static(data),
+        Cow::Owned(data) => Bytes::from(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lib_url() {
+        assert_eq!(lib_url("/docs/scalar", "scalar.js"), "scalar/scalar.js");
+        assert_eq!(lib_url("/docs/scalar/", "scalar.js"), "scalar.js");
+        assert_eq!(lib_url("/", "scalar.js"), "scalar.js");
+    }
+}
@@ -1,9 +1,11 @@
 use std::any::TypeId;
 
-use salvo_core::http::StatusCode;
+use salvo_core::http::{ParseError, StatusCode};
 use salvo_core::{prelude::StatusError, writing};
 
-use crate::{Components, Operation, Response, ToResponse, ToResponses, ToSchema};
+use crate::{
+    Components, Operation, ParameterIn, Required, Response, ToResponse, ToResponses, ToSchema,
+};
 
 /// Represents an endpoint.
 ///
@@ -30,6 +32,68 @@ pub trait EndpointArgRegister {
     /// Modify the OpenApi components section or current operation information with given argument. This function is called by macros internal.
     fn register(components: &mut Components, operation: &mut Operation, arg: &str);
 }
+impl<T> EndpointArgRegister for salvo_core::extract::State<T> {
+    fn register(_components: &mut Components, _operation: &mut Operation, _arg: &str) {}
+}
+#[cfg(feature = "validator")]
+impl<T> EndpointArgRegister for salvo_core::extract::Valid<T>
+where
+    T: EndpointArgRegister,
+{
+    fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
+        T::register(components, operation, arg);
+        operation
+            .responses
+            .insert("422", writing::Problem::to_response(components));
+    }
+}
+impl<T> EndpointArgRegister for Option<T>
+where
+    T: EndpointArgRegister,
+{
+    fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
+        register_optional::<T>(components, operation, arg);
+    }
+}
+impl<T> EndpointArgRegister for Result<T, ParseError>
+where
+    T: EndpointArgRegister,
+{
+    fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
+        register_optional::<T>(components, operation, arg);
+    }
+}
+impl<T> EndpointArgRegister for salvo_core::extract::Lazy<T>
+where
+    T: EndpointArgRegister,
+{
+    fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
+        register_optional::<T>(components, operation, arg);
+    }
+}
+
+/// Registers `T` as usual, but the parameters and request body it adds are not required, because the
+/// extraction failure is handled in handler.
+fn register_optional<T>(components: &mut Components, operation: &mut Operation, arg: &str)
+where
+    T: EndpointArgRegister,
+{
+    let mut optional = Operation::new();
+    T::register(components, &mut optional, arg);
+    for mut parameter in optional.parameters {
+        // Path parameters are always required.
+        if parameter.parameter_in != ParameterIn::Path {
+            parameter.required = Required::False;
+        }
+        operation.parameters.insert(parameter);
+    }
+    if let Some(mut request_body) = optional.request_body {
+        request_body.required = Some(Required::False);
+        operation.request_body = Some(request_body);
+    }
+    operation.responses.append(&mut optional.responses);
+}
+
 /// A trait for endpoint return type register.
 pub trait EndpointOutRegister {
     /// Modify the OpenApi components section or current operation information with given argument. This function is called by macros internal.
@@ -47,6 +111,54 @@ where
             .insert("200", Self::to_response(components));
     }
 }
+#[cfg(feature = "msgpack")]
+impl<C> EndpointOutRegister for writing::MsgPack<C>
+where
+    C: ToSchema,
+{
+    #[inline]
+    fn register(components: &mut Components, operation: &mut Operation) {
+        operation
+            .responses
+            .insert("200", Self::to_response(components));
+    }
+}
+#[cfg(feature = "cbor")]
+impl<C> EndpointOutRegister for writing::Cbor<C>
+where
+    C: ToSchema,
+{
+    #[inline]
+    fn register(components: &mut Components, operation: &mut Operation) {
+        operation
+            .responses
+            .insert("200", Self::to_response(components));
+    }
+}
+#[cfg(feature = "yaml")]
+impl<C> EndpointOutRegister for writing::Yaml<C>
+where
+    C: ToSchema,
+{
+    #[inline]
+    fn register(components: &mut Components, operation: &mut Operation) {
+        operation
+            .responses
+            .insert("200", Self::to_response(components));
+    }
+}
+#[cfg(feature = "protobuf")]
+impl<C> EndpointOutRegister for writing::ProtoBuf<C>
+where
+    C: ToSchema,
+{
+    #[inline]
+    fn register(components: &mut Components, operation: &mut Operation) {
+        operation
+            .responses
+            .insert("200", Self::to_response(components));
+    }
+}
 impl<T, E> EndpointOutRegister for Result<T, E>
 where
     T: EndpointOutRegister + Send,
@@ -77,6 +189,14 @@ impl EndpointOutRegister for StatusError {
             .append(&mut Self::to_responses(components));
     }
 }
+impl EndpointOutRegister for writing::Problem {
+    #[inline]
+    fn register(components: &mut Components, operation: &mut Operation) {
+        operation
+            .responses
+            .insert("default", Self::to_response(components));
+    }
+}
 impl EndpointOutRegister for StatusCode {
     fn register(components: &mut Components, operation: &mut Operation) {
         for code in [
@@ -1,7 +1,9 @@
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::{Deref, DerefMut};
 
+use salvo_core::extract::metadata::SourceFrom;
 use salvo_core::extract::{Extractible, Metadata};
+use salvo_core::http::errors::FieldError;
 use salvo_core::http::{ParseError, Request};
 use salvo_core::serde::from_str_val;
 use serde::{Deserialize, Deserializer};
@@ -10,6 +12,9 @@ use crate::endpoint::EndpointArgRegister;
 use crate::{Components, Operation, Parameter, ParameterIn, ToSchema};
 
 /// Represents the parameters passed by Cookie.
+///
+/// If `REQUIRED` is `true`, extraction fails when the cookie is missing. Otherwise a missing cookie is
+/// extracted as `None`. A cookie which can not be parsed to `T` is always an error.
 pub struct CookieParam<T, const REQUIRED: bool = true>(Option<T>);
 impl<T> CookieParam<T, true> {
     /// Consumes self and returns the value of the parameter.
@@ -96,17 +101,12 @@ where
     }
     #[allow(refining_impl_trait)]
     async fn extract_with_arg(req: &'ex mut Request, arg: &str) -> Result<Self, ParseError> {
-        let value = req
-            .cookies()
-            .get(arg)
-            .and_then(|v| from_str_val(v.value()).ok())
-            .ok_or_else(|| {
-                ParseError::other(format!(
-                    "cookie parameter {} not found or convert to type failed",
-                    arg
-                ))
-            })?;
-        Ok(Self(value))
+        let value = extract_cookie(req, arg)?.ok_or_else(|| {
+            ParseError::InvalidField(Box::new(
+                FieldError::new(arg, "missing cookie parameter").source_from(SourceFrom::Cookie),
+            ))
+        })?;
+        Ok(Self(Some(value)))
     }
 }
 
@@ -124,14 +124,24 @@ where
     }
     #[allow(refining_impl_trait)]
     async fn extract_with_arg(req: &'ex mut Request, arg: &str) -> Result<Self, ParseError> {
-        let value = req
-            .cookies()
-            .get(arg)
-            .and_then(|v| from_str_val(v.value()).ok());
-        Ok(Self(value))
+        extract_cookie(req, arg).map(Self)
     }
 }
 
+fn extract_cookie<'ex, T>(req: &'ex Request, arg: &str) -> Result<Option<T>, ParseError>
+where
+    T: Deserialize<'ex>,
+{
+    let Some(cookie) = req.cookies().get(arg) else {
+        return Ok(None);
+    };
+    from_str_val(cookie.value()).map(Some).map_err(|e| {
+        ParseError::InvalidField(Box::new(
+            FieldError::new(arg, e.to_string()).source_from(SourceFrom::Cookie),
+        ))
+    })
+}
+
 impl<T, const R: bool> EndpointArgRegister for CookieParam<T, R>
 where
     T: ToSchema,
@@ -267,6 +277,26 @@ mod tests {
         assert_eq!(result.unwrap().0.unwrap(), "param");
     }
 
+    #[tokio::test]
+    async fn test_cookie_prarm_extract_with_invalid_value() {
+        let mut req = TestClient::get("http://127.0.0.1:5801").build_hyper();
+        req.headers_mut()
+            .append("cookie", HeaderValue::from_static("param=abc"));
+        let schema = req.uri().scheme().cloned().unwrap();
+        let mut req = Request::from_hyper(req, schema);
+        let err = CookieParam::<u32, false>::extract_with_arg(&mut req, "param")
+            .await
+            .unwrap_err();
+        let field = err.field_error().unwrap();
+        assert_eq!(field.path, "param");
+        assert_eq!(field.source_from, Some(SourceFrom::Cookie));
+
+        let err = CookieParam::<u32, true>::extract_with_arg(&mut Request::new(), "param")
+            .await
+            .unwrap_err();
+        assert_eq!(err.field_error().unwrap().path, "param");
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn test_cookie_prarm_extract_with_value_panic() {
@@ -1,7 +1,9 @@
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ops::{Deref, DerefMut};
 
+use salvo_core::extract::metadata::SourceFrom;
 use salvo_core::extract::{Extractible, Metadata};
+use salvo_core::http::errors::FieldError;
 use salvo_core::http::{ParseError, Request};
 use serde::{Deserialize, Deserializer};
 
@@ -9,6 +11,9 @@ use crate::endpoint::EndpointArgRegister;
 use crate::{Components, Operation, Parameter, ParameterIn, ToSchema};
 
 /// Represents the parameters passed by header.
+///
+/// If `REQUIRED` is `true`, extraction fails when the header is missing. Otherwise a missing header is
+/// extracted as `None`. A header which can not be parsed to `T` is always an error.
 pub struct HeaderParam<T, const REQUIRED: bool = true>(Option<T>);
 impl<T> HeaderParam<T, true> {
     /// Consumes self and returns the value of the parameter.
@@ -108,13 +113,12 @@ where
     }
     #[allow(refining_impl_trait)]
     async fn extract_with_arg(req: &'ex mut Request, arg: &str) -> Result<Self, ParseError> {
-        let value = req.header(arg).ok_or_else(|| {
-            ParseError::other(format!(
-                "header parameter {} not found or convert to type failed",
-                arg
+        let value = extract_header(req, arg)?.ok_or_else(|| {
+            ParseError::InvalidField(Box::new(
+                FieldError::new(arg, "missing header parameter").source_from(SourceFrom::Header),
             ))
         })?;
-        Ok(Self(value))
+        Ok(Self(Some(value)))
     }
 }
 
@@ -132,10 +136,24 @@ where
     }
     #[allow(refining_impl_trait)]
     async fn extract_with_arg(req: &'ex mut Request, arg: &str) -> Result<Self, ParseError> {
-        Ok(Self(req.header(arg)))
+        extract_header(req, arg).map(Self)
     }
 }
 
+fn extract_header<'ex, T>(req: &'ex Request, arg: &str) -> Result<Option<T>, ParseError>
+where
+    T: Deserialize<'ex>,
+{
+    if !req.headers().contains_key(arg) {
+        return Ok(None);
+    }
+    req.try_header(arg).map(Some).map_err(|e| {
+        ParseError::InvalidField(Box::new(
+            FieldError::new(arg, e.to_string()).source_from(SourceFrom::Header),
+        ))
+    })
+}
+
 impl<T, const R: bool> EndpointArgRegister for HeaderParam<T, R>
 where
     T: ToSchema,
@@ -271,6 +289,26 @@ mod tests {
         assert_eq!(result.unwrap().0.unwrap(), "param");
     }
 
+    #[tokio::test]
+    async fn test_header_prarm_extract_with_invalid_value() {
+        let mut req = TestClient::get("http://127.0.0.1:5801").build_hyper();
+        req.headers_mut()
+            .append("param", HeaderValue::from_static("abc"));
+        let schema = req.uri().scheme().cloned().unwrap();
+        let mut req = Request::from_hyper(req, schema);
+        let err = HeaderParam::<u32, false>::extract_with_arg(&mut req, "param")
+            .await
+            .unwrap_err();
+        let field = err.field_error().unwrap();
+        assert_eq!(field.path, "param");
+        assert_eq!(field.source_from, Some(SourceFrom::Header));
+
+        let err = HeaderParam::<u32, true>::extract_with_arg(&mut Request::new(), "param")
+            .await
+            .unwrap_err();
+        assert_eq!(err.field_error().unwrap().path, "param");
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn test_header_prarm_extract_with_value_panic() {
@@ -7,4 +7,4 @@ pub use header::HeaderParam;
 mod path;
 pub use path::PathParam;
 mod query;
-pub use query::QueryParam;
+pub use query::{CommaDelimited, PipeDelimited, QueryParam, SpaceDelimited};
@@ -4,12 +4,26 @@ use std::ops::{Deref, DerefMut};
 use salvo_core::Request;
 use salvo_core::extract::{Extractible, Metadata};
 use salvo_core::http::ParseError;
+use salvo_core::serde::{from_str_multi_map, from_str_val};
+use serde::de::{DeserializeOwned, Error as _};
 use serde::{Deserialize, Deserializer};
 
 use crate::endpoint::EndpointArgRegister;
-use crate::{Components, Operation, Parameter, ParameterIn, ToSchema};
-
-/// Represents the parameters passed by the URI path.
+use crate::{
+    Array, BasicType, Components, Operation, Parameter, ParameterIn, ParameterStyle, RefOr, Schema,
+    SchemaType, ToSchema,
+};
+
+/// Represents the parameters passed by the URI query.
+///
+/// Besides plain `name=value` pairs, the following wire formats are supported:
+///
+/// * `Vec<T>` is parsed from repeated keys, e.g. `ids=1&ids=2` or `ids[]=1&ids[]=2`, and is
+///   documented as `form` style with `explode` enabled.
+/// * Struct `T` is parsed from `deepObject` style keys, e.g. `filter[name]=Tom&filter[age]=3`,
+///   and is documented as `deepObject` style.
+/// * [`CommaDelimited`], [`PipeDelimited`] and [`SpaceDelimited`] are parsed from a single value
+///   such as `ids=1|2|3`, and are documented with `explode` disabled.
 pub struct QueryParam<T, const REQUIRED: bool = true>(Option<T>);
 impl<T> QueryParam<T, true> {
     /// Consumes self and returns the value of the parameter.
@@ -95,13 +109,13 @@ where
     }
     #[allow(refining_impl_trait)]
     async fn extract_with_arg(req: &'ex mut Request, arg: &str) -> Result<Self, ParseError> {
-        let value = req.query(arg).ok_or_else(|| {
+        let value = extract_query::<T>(req, arg).ok().flatten().ok_or_else(|| {
             ParseError::other(format!(
                 "query parameter {} not found or convert to type failed",
                 arg
             ))
         })?;
-        Ok(Self(value))
+        Ok(Self(Some(value)))
     }
 }
 impl<'ex, T> Extractible<'ex> for QueryParam<T, false>
@@ -118,7 +132,62 @@ where
     }
     #[allow(refining_impl_trait)]
     async fn extract_with_arg(req: &'ex mut Request, arg: &str) -> Result<Self, ParseError> {
-        Ok(Self(req.query(arg)))
+        Ok(Self(extract_query(req, arg).ok().flatten()))
+    }
+}
+
+fn extract_query<'ex, T>(req: &'ex Request, arg: &str) -> Result<Option<T>, ParseError>
+where
+    T: Deserialize<'ex>,
+{
+    let queries = req.queries();
+    if queries.contains_key(arg) {
+        return req.try_query(arg).map(Some);
+    }
+    let array_key = format!("{arg}[]");
+    if queries.contains_key(&array_key) {
+        return req.try_query(&array_key).map(Some);
+    }
+
+    let prefix = format!("{arg}[");
+    let fields = queries
+        .iter_all()
+        .filter_map(|(key, values)| {
+            let name = key.strip_prefix(&prefix)?.strip_suffix(']')?;
+            if name.is_empty() || name.contains(['[', ']']) {
+                return None;
+            }
+            Some((name, values.iter().map(String::as_str).collect::<Vec<_>>()))
+        })
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        Ok(None)
+    } else {
+        from_str_multi_map(fields).map(Some).map_err(Into::into)
+    }
+}
+
+/// Returns the style used to document a query parameter with `schema`.
+fn query_style(schema: &RefOr<Schema>, components: &Components) -> Option<ParameterStyle> {
+    let schema = match schema {
+        RefOr::Type(schema) => schema,
+        RefOr::Ref(reference) => match components.schemas.get(
+            reference
+                .ref_location
+                .trim_start_matches("#/components/schemas/"),
+        )? {
+            RefOr::Type(schema) => schema,
+            RefOr::Ref(_) => return None,
+        },
+    };
+    match schema {
+        Schema::Array(_) => Some(ParameterStyle::Form),
+        Schema::Object(object)
+            if matches!(object.schema_type, SchemaType::Basic(BasicType::Object)) =>
+        {
+            Some(ParameterStyle::DeepObject)
+        }
+        _ => None,
     }
 }
 
@@ -127,15 +196,106 @@ where
     T: ToSchema,
 {
     fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
-        let parameter = Parameter::new(arg)
+        let schema = T::to_schema(components);
+        let mut parameter = Parameter::new(arg)
             .parameter_in(ParameterIn::Query)
             .description(format!("Get parameter `{arg}` from request url query."))
-            .schema(T::to_schema(components))
             .required(R);
-        operation.parameters.insert(parameter);
+        if let Some(style) = query_style(&schema, components) {
+            parameter = parameter.style(style).explode(true);
+        }
+        operation.parameters.insert(parameter.schema(schema));
     }
 }
 
+macro_rules! delimited_query_param {
+    ($(#[$meta:meta])* $name:ident, $delimiter:literal, $style:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, PartialEq, Eq, Default, Debug)]
+        pub struct $name<T>(pub Vec<T>);
+        impl<T> $name<T> {
+            /// Consumes self and returns the values.
+            pub fn into_inner(self) -> Vec<T> {
+                self.0
+            }
+        }
+
+        impl<T> Deref for $name<T> {
+            type Target = Vec<T>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+        impl<T> DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl<'de, T> Deserialize<'de> for $name<T>
+        where
+            T: DeserializeOwned,
+        {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+                if value.is_empty() {
+                    return Ok(Self(Vec::new()));
+                }
+                value
+                    .split($delimiter)
+                    .map(|item| from_str_val(item.to_owned()).map_err(D::Error::custom))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Self)
+            }
+        }
+
+        impl<T, const R: bool> EndpointArgRegister for QueryParam<$name<T>, R>
+        where
+            T: ToSchema,
+        {
+            fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
+                let parameter = Parameter::new(arg)
+                    .parameter_in(ParameterIn::Query)
+                    .description(format!("Get parameter `{arg}` from request url query."))
+                    .schema(Array::new().items(T::to_schema(components)))
+                    .required(R)
+                    .style($style)
+                    .explode(false);
+                operation.parameters.insert(parameter);
+            }
+        }
+    };
+}
+
+delimited_query_param!(
+    /// Array query parameter whose values are separated by commas, e.g. `ids=1,2,3`.
+    ///
+    /// It is documented as `form` style with `explode` disabled.
+    CommaDelimited,
+    ',',
+    ParameterStyle::Form
+);
+delimited_query_param!(
+    /// Array query parameter whose values are separated by pipes, e.g. `ids=1|2|3`.
+    ///
+    /// It is documented as `pipeDelimited` style.
+    PipeDelimited,
+    '|',
+    ParameterStyle::PipeDelimited
+);
+delimited_query_param!(
+    /// Array query parameter whose values are separated by spaces, e.g. `ids=1%202%203`.
+    ///
+    /// It is documented as `spaceDelimited` style.
+    SpaceDelimited,
+    ' ',
+    ParameterStyle::SpaceDelimited
+);
+
 #[cfg(test)]
 mod tests {
     use assert_json_diff::assert_json_eq;
@@ -290,4 +450,64 @@ mod tests {
             })
         )
     }
+
+    #[tokio::test]
+    async fn test_query_param_extract_array_and_deep_object() {
+        #[derive(Deserialize, ToSchema, PartialEq, Debug)]
+        struct Filter {
+            name: String,
+            age: u8,
+        }
+
+        let req = TestClient::get(
+            "http://127.0.0.1:5801?ids%5B%5D=1&ids%5B%5D=2&filter%5Bname%5D=Tom&filter%5Bage%5D=3&tags=a%7Cb",
+        )
+        .build_hyper();
+        let schema = req.uri().scheme().cloned().unwrap();
+        let mut req = Request::from_hyper(req, schema);
+
+        let ids = QueryParam::<Vec<u32>, true>::extract_with_arg(&mut req, "ids").await;
+        assert_eq!(ids.unwrap().into_inner(), vec![1, 2]);
+        let filter = QueryParam::<Filter, true>::extract_with_arg(&mut req, "filter").await;
+        assert_eq!(
+            filter.unwrap().into_inner(),
+            Filter {
+                name: "Tom".into(),
+                age: 3
+            }
+        );
+        let tags =
+            QueryParam::<PipeDelimited<String>, true>::extract_with_arg(&mut req, "tags").await;
+        assert_eq!(tags.unwrap().into_inner().into_inner(), vec!["a", "b"]);
+        let missing = QueryParam::<Filter, false>::extract_with_arg(&mut req, "missing").await;
+        assert!(missing.unwrap().into_inner().is_none());
+    }
+
+    #[test]
+    fn test_query_param_register_styles() {
+        #[allow(dead_code)]
+        #[derive(Deserialize, ToSchema)]
+        struct Filter {
+            name: String,
+        }
+
+        let mut components = Components::new();
+        let mut operation = Operation::new();
+        QueryParam::<Vec<u32>, true>::register(&mut components, &mut operation, "ids");
+        QueryParam::<Filter, true>::register(&mut components, &mut operation, "filter");
+        QueryParam::<PipeDelimited<String>, true>::register(
+            &mut components,
+            &mut operation,
+            "tags",
+        );
+
+        let parameters = serde_json::to_value(&operation).unwrap()["parameters"].clone();
+        assert_eq!(parameters[0]["style"], "form");
+        assert_eq!(parameters[0]["explode"], true);
+        assert_eq!(parameters[1]["style"], "deepObject");
+        assert_eq!(parameters[1]["explode"], true);
+        assert_eq!(parameters[2]["style"], "pipeDelimited");
+        assert_eq!(parameters[2]["explode"], false);
+        assert_eq!(parameters[2]["schema"]["type"], "array");
+    }
 }
@@ -0,0 +1,145 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use salvo_core::extract::{Extractible, Metadata};
+use salvo_core::{Request, Writer};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+
+use crate::endpoint::EndpointArgRegister;
+use crate::{Components, Content, Operation, RequestBody, ToRequestBody, ToSchema};
+
+/// Represents the CBOR format data in request body, the content type should be `application/cbor`.
+pub struct CborBody<T>(pub T);
+impl<T> CborBody<T> {
+    /// Consumes self and returns the value of the parameter.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for CborBody<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CborBody<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'de, T> ToRequestBody for CborBody<T>
+where
+    T: Deserialize<'de> + ToSchema,
+{
+    fn to_request_body(components: &mut Components) -> RequestBody {
+        RequestBody::new()
+            .description("Extract cbor format data from request.")
+            .add_content("application/cbor", Content::new(T::to_schema(components)))
+    }
+}
+
+impl<T> fmt::Debug for CborBody<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Display> Display for CborBody<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'ex, T> Extractible<'ex> for CborBody<T>
+where
+    T: DeserializeOwned + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    async fn extract(
+        req: &'ex mut Request,
+    ) -> Result<Self, impl Writer + Send + fmt::Debug + 'static> {
+        req.parse_cbor().await
+    }
+    async fn extract_with_arg(
+        req: &'ex mut Request,
+        _arg: &str,
+    ) -> Result<Self, impl Writer + Send + fmt::Debug + 'static> {
+        Self::extract(req).await
+    }
+}
+
+impl<'de, T> Deserialize<'de> for CborBody<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(CborBody)
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<T> validator::Validate for CborBody<T>
+where
+    T: validator::Validate,
+{
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        self.0.validate()
+    }
+}
+
+impl<'de, T> EndpointArgRegister for CborBody<T>
+where
+    T: Deserialize<'de> + ToSchema,
+{
+    fn register(components: &mut Components, operation: &mut Operation, _arg: &str) {
+        let request_body = Self::to_request_body(components);
+        let _ = <T as ToSchema>::to_schema(components);
+        operation.request_body = Some(request_body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_cbor_body_register() {
+        let mut components = Components::new();
+        let mut operation = Operation::new();
+        CborBody::<String>::register(&mut components, &mut operation, "arg");
+
+        assert_json_eq!(
+            operation,
+            json!({
+                "requestBody": {
+                    "content": {
+                        "application/cbor": {
+                            "schema": {
+                                "type": "string"
+                            }
+                        }
+                    },
+                    "description": "Extract cbor format data from request."
+                },
+                "responses": {}
+            })
+        );
+    }
+}
@@ -9,8 +9,8 @@ use salvo_core::{Request, async_trait};
 
 use crate::endpoint::EndpointArgRegister;
 use crate::{
-    Array, BasicType, Components, Content, KnownFormat, Object, Operation, RequestBody, Schema,
-    SchemaFormat,
+    Array, BasicType, Components, Content, KnownFormat, Object, Operation, RefOr, RequestBody,
+    Schema, SchemaFormat, ToSchema,
 };
 
 /// Represents the upload file.
@@ -93,27 +93,23 @@ impl<'ex> Extractible<'ex> for FormFile {
     }
 }
 
+impl ToSchema for FormFile {
+    fn to_schema(_components: &mut Components) -> RefOr<Schema> {
+        Object::with_type(BasicType::String)
+            .format(SchemaFormat::KnownFormat(KnownFormat::Binary))
+            .into()
+    }
+}
+
 #[async_trait]
 impl EndpointArgRegister for FormFile {
-    fn register(_components: &mut Components, operation: &mut Operation, arg: &str) {
-        let schema = Schema::from(
-            Object::new().property(
-                arg,
-                Object::with_type(BasicType::String)
-                    .format(SchemaFormat::KnownFormat(KnownFormat::Binary)),
-            ),
+    fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
+        register_file_property(
+            operation,
+            arg,
+            FormFile::to_schema(components),
+            "Upload a file.",
         );
-
-        if let Some(request_body) = &mut operation.request_body {
-            request_body
-                .contents
-                .insert("multipart/form-data".into(), Content::new(schema));
-        } else {
-            let request_body = RequestBody::new()
-                .description("Upload a file.")
-                .add_content("multipart/form-data", Content::new(schema));
-            operation.request_body = Some(request_body);
-        }
     }
 }
 
@@ -167,27 +163,93 @@ impl<'ex> Extractible<'ex> for FormFiles {
     }
 }
 
+impl ToSchema for FormFiles {
+    fn to_schema(components: &mut Components) -> RefOr<Schema> {
+        Array::new().items(FormFile::to_schema(components)).into()
+    }
+}
+
 #[async_trait]
 impl EndpointArgRegister for FormFiles {
-    fn register(_components: &mut Components, operation: &mut Operation, arg: &str) {
-        let schema = Schema::from(
-            Object::new().property(
-                arg,
-                Array::new().items(Schema::from(
-                    Object::with_type(BasicType::String)
-                        .format(SchemaFormat::KnownFormat(KnownFormat::Binary)),
-                )),
-            ),
+    fn register(components: &mut Components, operation: &mut Operation, arg: &str) {
+        register_file_property(
+            operation,
+            arg,
+            FormFiles::to_schema(components),
+            "Upload files.",
+        );
+    }
+}
+
+/// Add the file property to the `multipart/form-data` request body, so the files and other form fields of the
+/// handler are documented in the same schema.
+fn register_file_property(
+    operation: &mut Operation,
+    arg: &str,
+    property: RefOr<Schema>,
+    description: &str,
+) {
+    let request_body = operation
+        .request_body
+        .get_or_insert_with(|| RequestBody::new().description(description));
+    let content = request_body
+        .contents
+        .entry("multipart/form-data".into())
+        .or_insert_with(|| Content::new(Object::with_type(BasicType::Object)));
+    if let RefOr::Type(Schema::Object(object)) = &mut content.schema {
+        object.properties.insert(arg.into(), property);
+        object.required.insert(arg.into());
+    } else {
+        content.schema = Object::with_type(BasicType::Object)
+            .property(arg, property)
+            .required(arg)
+            .into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_form_files_register_merge_properties() {
+        let mut components = Components::new();
+        let mut operation = Operation::new();
+        FormFile::register(&mut components, &mut operation, "avatar");
+        FormFiles::register(&mut components, &mut operation, "photos");
+
+        assert_json_eq!(
+            operation,
+            json!({
+                "requestBody": {
+                    "content": {
+                        "multipart/form-data": {
+                            "schema": {
+                                "type": "object",
+                                "properties": {
+                                    "avatar": {
+                                        "type": "string",
+                                        "format": "binary"
+                                    },
+                                    "photos": {
+                                        "type": "array",
+                                        "items": {
+                                            "type": "string",
+                                            "format": "binary"
+                                        }
+                                    }
+                                },
+                                "required": ["avatar", "photos"]
+                            }
+                        }
+                    },
+                    "description": "Upload a file."
+                },
+                "responses": {}
+            })
         );
-        if let Some(request_body) = &mut operation.request_body {
-            request_body
-                .contents
-                .insert("multipart/form-data".into(), Content::new(schema));
-        } else {
-            let request_body = RequestBody::new()
-                .description("Upload files.")
-                .add_content("multipart/form-data", Content::new(schema));
-            operation.request_body = Some(request_body);
-        }
     }
 }
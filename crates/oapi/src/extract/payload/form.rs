@@ -94,6 +94,16 @@ where
     }
 }
 
+#[cfg(feature = "validator")]
+impl<T> validator::Validate for FormBody<T>
+where
+    T: validator::Validate,
+{
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        self.0.validate()
+    }
+}
+
 #[async_trait]
 impl<'de, T> EndpointArgRegister for FormBody<T>
 where
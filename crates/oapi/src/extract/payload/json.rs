@@ -90,6 +90,16 @@ where
     }
 }
 
+#[cfg(feature = "validator")]
+impl<T> validator::Validate for JsonBody<T>
+where
+    T: validator::Validate,
+{
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        self.0.validate()
+    }
+}
+
 impl<'de, T> EndpointArgRegister for JsonBody<T>
 where
     T: Deserialize<'de> + ToSchema,
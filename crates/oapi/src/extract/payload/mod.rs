@@ -6,3 +6,20 @@ mod json;
 pub use file::{FormFile, FormFiles};
 pub use form::FormBody;
 pub use json::JsonBody;
+
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+#[cfg(feature = "yaml")]
+mod yaml;
+#[cfg(feature = "cbor")]
+pub use cbor::CborBody;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgPackBody;
+#[cfg(feature = "protobuf")]
+pub use protobuf::ProtoBufBody;
+#[cfg(feature = "yaml")]
+pub use yaml::YamlBody;
@@ -0,0 +1,147 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use salvo_core::extract::{Extractible, Metadata};
+use salvo_core::{Request, Writer};
+use serde::{Deserialize, Deserializer};
+
+use crate::endpoint::EndpointArgRegister;
+use crate::{Components, Content, Operation, RequestBody, ToRequestBody, ToSchema};
+
+/// Represents the MessagePack format data in request body, the content type should be `application/msgpack`.
+pub struct MsgPackBody<T>(pub T);
+impl<T> MsgPackBody<T> {
+    /// Consumes self and returns the value of the parameter.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for MsgPackBody<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for MsgPackBody<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'de, T> ToRequestBody for MsgPackBody<T>
+where
+    T: Deserialize<'de> + ToSchema,
+{
+    fn to_request_body(components: &mut Components) -> RequestBody {
+        RequestBody::new()
+            .description("Extract msgpack format data from request.")
+            .add_content(
+                "application/msgpack",
+                Content::new(T::to_schema(components)),
+            )
+    }
+}
+
+impl<T> fmt::Debug for MsgPackBody<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Display> Display for MsgPackBody<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'ex, T> Extractible<'ex> for MsgPackBody<T>
+where
+    T: Deserialize<'ex> + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    async fn extract(
+        req: &'ex mut Request,
+    ) -> Result<Self, impl Writer + Send + fmt::Debug + 'static> {
+        req.parse_msgpack().await
+    }
+    async fn extract_with_arg(
+        req: &'ex mut Request,
+        _arg: &str,
+    ) -> Result<Self, impl Writer + Send + fmt::Debug + 'static> {
+        Self::extract(req).await
+    }
+}
+
+impl<'de, T> Deserialize<'de> for MsgPackBody<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(MsgPackBody)
+    }
+}
+
+#[cfg(feature = "validator")]
+impl<T> validator::Validate for MsgPackBody<T>
+where
+    T: validator::Validate,
+{
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        self.0.validate()
+    }
+}
+
+impl<'de, T> EndpointArgRegister for MsgPackBody<T>
+where
+    T: Deserialize<'de> + ToSchema,
+{
+    fn register(components: &mut Components, operation: &mut Operation, _arg: &str) {
+        let request_body = Self::to_request_body(components);
+        let _ = <T as ToSchema>::to_schema(components);
+        operation.request_body = Some(request_body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_msgpack_body_register() {
+        let mut components = Components::new();
+        let mut operation = Operation::new();
+        MsgPackBody::<String>::register(&mut components, &mut operation, "arg");
+
+        assert_json_eq!(
+            operation,
+            json!({
+                "requestBody": {
+                    "content": {
+                        "application/msgpack": {
+                            "schema": {
+                                "type": "string"
+                            }
+                        }
+                    },
+                    "description": "Extract msgpack format data from request."
+                },
+                "responses": {}
+            })
+        );
+    }
+}
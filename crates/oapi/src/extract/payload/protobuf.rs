@@ -0,0 +1,120 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use prost::Message;
+use salvo_core::extract::{Extractible, Metadata};
+use salvo_core::{Request, Writer};
+
+use crate::endpoint::EndpointArgRegister;
+use crate::{Components, Content, Operation, RequestBody, ToRequestBody, ToSchema};
+
+/// Represents the Protocol Buffers message in request body, the content type should be
+/// `application/x-protobuf`.
+pub struct ProtoBufBody<T>(pub T);
+impl<T> ProtoBufBody<T> {
+    /// Consumes self and returns the value of the parameter.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ProtoBufBody<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for ProtoBufBody<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> ToRequestBody for ProtoBufBody<T>
+where
+    T: Message + ToSchema,
+{
+    fn to_request_body(components: &mut Components) -> RequestBody {
+        RequestBody::new()
+            .description("Extract protobuf format data from request.")
+            .add_content(
+                "application/x-protobuf",
+                Content::new(T::to_schema(components)),
+            )
+    }
+}
+
+impl<T> fmt::Debug for ProtoBufBody<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'ex, T> Extractible<'ex> for ProtoBufBody<T>
+where
+    T: Message + Default + Send,
+{
+    fn metadata() -> &'ex Metadata {
+        static METADATA: Metadata = Metadata::new("");
+        &METADATA
+    }
+    async fn extract(
+        req: &'ex mut Request,
+    ) -> Result<Self, impl Writer + Send + fmt::Debug + 'static> {
+        req.parse_protobuf().await.map(ProtoBufBody)
+    }
+    async fn extract_with_arg(
+        req: &'ex mut Request,
+        _arg: &str,
+    ) -> Result<Self, impl Writer + Send + fmt::Debug + 'static> {
+        Self::extract(req).await
+    }
+}
+
+impl<T> EndpointArgRegister for ProtoBufBody<T>
+where
+    T: Message + ToSchema,
+{
+    fn register(components: &mut Components, operation: &mut Operation, _arg: &str) {
+        let request_body = Self::to_request_body(components);
+        let _ = <T as ToSchema>::to_schema(components);
+        operation.request_body = Some(request_body);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_protobuf_body_register() {
+        let mut components = Components::new();
+        let mut operation = Operation::new();
+        ProtoBufBody::<String>::register(&mut components, &mut operation, "arg");
+
+        assert_json_eq!(
+            operation,
+            json!({
+                "requestBody": {
+                    "content": {
+                        "application/x-protobuf": {
+                            "schema": {
+                                "type": "string"
+                            }
+                        }
+                    },
+                    "description": "Extract protobuf format data from request."
+                },
+                "responses": {}
+            })
+        );
+    }
+}
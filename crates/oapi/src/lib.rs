@@ -17,6 +17,7 @@ mod routing;
 pub use routing::RouterExt;
 /// Module for name schemas.
 pub mod naming;
+pub mod test;
 
 cfg_feature! {
     #![feature ="swagger-ui"]
@@ -34,6 +35,8 @@ cfg_feature! {
     #![feature ="redoc"]
     pub mod redoc;
 }
+#[cfg(any(feature = "scalar", feature = "rapidoc", feature = "redoc"))]
+mod asset;
 
 #[doc = include_str!("../docs/derive_to_parameters.md")]
 pub use salvo_oapi_macros::ToParameters;
@@ -47,7 +50,7 @@ pub use salvo_oapi_macros::ToSchema;
 pub use salvo_oapi_macros::endpoint;
 pub(crate) use salvo_oapi_macros::schema;
 
-use std::collections::{BTreeMap, HashMap, LinkedList};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, LinkedList};
 use std::marker::PhantomData;
 
 use salvo_core::http::StatusError;
@@ -182,7 +185,10 @@ pub mod oapi {
 #[doc(hidden)]
 pub mod __private {
     pub use inventory;
+    pub use salvo_core::writing::Problem;
     pub use serde_json;
+
+    pub use crate::schema::assert_content_examples;
 }
 
 #[rustfmt::skip]
@@ -212,6 +218,14 @@ impl<T: chrono::TimeZone> ToSchema for chrono::DateTime<T> {
         schema!(#[inline] DateTime<T>).into()
     }
 }
+#[cfg(feature = "chrono")]
+impl ToSchema for chrono::NaiveTime {
+    fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
+        Object::with_type(schema::BasicType::String)
+            .format(schema::SchemaFormat::KnownFormat(schema::KnownFormat::Time))
+            .into()
+    }
+}
 #[cfg(feature = "compact_str")]
 impl_to_schema_primitive!(compact_str::CompactString);
 #[cfg(any(feature = "decimal", feature = "decimal-float"))]
@@ -229,6 +243,38 @@ impl_to_schema_primitive!(
     time::OffsetDateTime,
     time::Duration
 );
+#[cfg(feature = "time")]
+impl ToSchema for time::Time {
+    fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
+        Object::with_type(schema::BasicType::String)
+            .format(schema::SchemaFormat::KnownFormat(schema::KnownFormat::Time))
+            .into()
+    }
+}
+#[cfg(feature = "ipnet")]
+impl ToSchema for ipnet::IpNet {
+    fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
+        Object::with_type(schema::BasicType::String)
+            .format(schema::SchemaFormat::Custom("cidr".into()))
+            .into()
+    }
+}
+#[cfg(feature = "ipnet")]
+impl ToSchema for ipnet::Ipv4Net {
+    fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
+        Object::with_type(schema::BasicType::String)
+            .format(schema::SchemaFormat::Custom("ipv4-cidr".into()))
+            .into()
+    }
+}
+#[cfg(feature = "ipnet")]
+impl ToSchema for ipnet::Ipv6Net {
+    fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
+        Object::with_type(schema::BasicType::String)
+            .format(schema::SchemaFormat::Custom("ipv6-cidr".into()))
+            .into()
+    }
+}
 #[cfg(feature = "smallvec")]
 impl<T: ToSchema + smallvec::Array> ToSchema for smallvec::SmallVec<T> {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
@@ -241,6 +287,30 @@ impl<K: ToSchema, V: ToSchema> ToSchema for indexmap::IndexMap<K, V> {
         schema!(#[inline] indexmap::IndexMap<K, V>).into()
     }
 }
+#[cfg(feature = "indexmap")]
+impl<T: ToSchema> ToSchema for indexmap::IndexSet<T> {
+    fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
+        schema::Array::new()
+            .items(T::to_schema(components))
+            .unique_items(true)
+            .into()
+    }
+}
+
+impl ToSchema for bytes::Bytes {
+    fn to_schema(_components: &mut Components) -> RefOr<schema::Schema> {
+        Object::with_type(schema::BasicType::String)
+            .format(schema::SchemaFormat::KnownFormat(
+                schema::KnownFormat::Binary,
+            ))
+            .into()
+    }
+}
+impl ToSchema for bytes::BytesMut {
+    fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
+        bytes::Bytes::to_schema(components)
+    }
+}
 
 impl<T: ToSchema> ToSchema for Vec<T> {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
@@ -283,6 +353,24 @@ impl<T: ToSchema> ToSchema for &[T] {
     }
 }
 
+impl<T: ToSchema> ToSchema for HashSet<T> {
+    fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
+        schema::Array::new()
+            .items(T::to_schema(components))
+            .unique_items(true)
+            .into()
+    }
+}
+
+impl<T: ToSchema> ToSchema for BTreeSet<T> {
+    fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
+        schema::Array::new()
+            .items(T::to_schema(components))
+            .unique_items(true)
+            .into()
+    }
+}
+
 impl<T: ToSchema> ToSchema for Option<T> {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
         schema!(#[inline] Option<T>).into()
@@ -330,6 +418,28 @@ impl ToSchema for StatusError {
         ref_or
     }
 }
+impl ToSchema for writing::Problem {
+    fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
+        let name = crate::naming::assign_name::<writing::Problem>(Default::default());
+        let ref_or = crate::RefOr::Ref(crate::Ref::new(format!("#/components/schemas/{}", name)));
+        if !components.schemas.contains_key(&name) {
+            components.schemas.insert(name.clone(), ref_or.clone());
+            let schema = Schema::from(
+                Object::new()
+                    .property("type", String::to_schema(components))
+                    .required("type")
+                    .property("title", String::to_schema(components))
+                    .required("title")
+                    .property("status", u16::to_schema(components))
+                    .required("status")
+                    .property("detail", String::to_schema(components))
+                    .property("instance", String::to_schema(components)),
+            );
+            components.schemas.insert(name, schema);
+        }
+        ref_or
+    }
+}
 impl ToSchema for salvo_core::Error {
     fn to_schema(components: &mut Components) -> RefOr<schema::Schema> {
         StatusError::to_schema(components)
@@ -534,6 +644,66 @@ where
     }
 }
 
+#[cfg(feature = "msgpack")]
+impl<C> ToResponses for writing::MsgPack<C>
+where
+    C: ToSchema,
+{
+    fn to_responses(components: &mut Components) -> Responses {
+        Responses::new().response(
+            "200",
+            Response::new("Response msgpack format data").add_content(
+                "application/msgpack",
+                Content::new(C::to_schema(components)),
+            ),
+        )
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<C> ToResponses for writing::Cbor<C>
+where
+    C: ToSchema,
+{
+    fn to_responses(components: &mut Components) -> Responses {
+        Responses::new().response(
+            "200",
+            Response::new("Response cbor format data")
+                .add_content("application/cbor", Content::new(C::to_schema(components))),
+        )
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<C> ToResponses for writing::Yaml<C>
+where
+    C: ToSchema,
+{
+    fn to_responses(components: &mut Components) -> Responses {
+        Responses::new().response(
+            "200",
+            Response::new("Response yaml format data")
+                .add_content("application/yaml", Content::new(C::to_schema(components))),
+        )
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl<C> ToResponses for writing::ProtoBuf<C>
+where
+    C: ToSchema,
+{
+    fn to_responses(components: &mut Components) -> Responses {
+        Responses::new().response(
+            "200",
+            Response::new("Response protobuf format data").add_content(
+                "application/x-protobuf",
+                Content::new(C::to_schema(components)),
+            ),
+        )
+    }
+}
+
 impl ToResponses for StatusError {
     fn to_responses(components: &mut Components) -> Responses {
         let mut responses = Responses::new();
@@ -590,6 +760,11 @@ impl ToResponses for StatusError {
         responses
     }
 }
+impl ToResponses for writing::Problem {
+    fn to_responses(components: &mut Components) -> Responses {
+        Responses::new().response("default", writing::Problem::to_response(components))
+    }
+}
 impl ToResponses for salvo_core::Error {
     fn to_responses(components: &mut Components) -> Responses {
         StatusError::to_responses(components)
@@ -632,6 +807,69 @@ where
     }
 }
 
+#[cfg(feature = "msgpack")]
+impl<C> ToResponse for writing::MsgPack<C>
+where
+    C: ToSchema,
+{
+    fn to_response(components: &mut Components) -> RefOr<Response> {
+        let schema = <C as ToSchema>::to_schema(components);
+        Response::new("Response with msgpack format data")
+            .add_content("application/msgpack", Content::new(schema))
+            .into()
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl<C> ToResponse for writing::Cbor<C>
+where
+    C: ToSchema,
+{
+    fn to_response(components: &mut Components) -> RefOr<Response> {
+        let schema = <C as ToSchema>::to_schema(components);
+        Response::new("Response with cbor format data")
+            .add_content("application/cbor", Content::new(schema))
+            .into()
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl<C> ToResponse for writing::Yaml<C>
+where
+    C: ToSchema,
+{
+    fn to_response(components: &mut Components) -> RefOr<Response> {
+        let schema = <C as ToSchema>::to_schema(components);
+        Response::new("Response with yaml format data")
+            .add_content("application/yaml", Content::new(schema))
+            .into()
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl<C> ToResponse for writing::ProtoBuf<C>
+where
+    C: ToSchema,
+{
+    fn to_response(components: &mut Components) -> RefOr<Response> {
+        let schema = <C as ToSchema>::to_schema(components);
+        Response::new("Response with protobuf format data")
+            .add_content("application/x-protobuf", Content::new(schema))
+            .into()
+    }
+}
+
+impl ToResponse for writing::Problem {
+    fn to_response(components: &mut Components) -> RefOr<Response> {
+        Response::new("Problem details")
+            .add_content(
+                "application/problem+json",
+                Content::new(Self::to_schema(components)),
+            )
+            .into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_json_diff::assert_json_eq;
@@ -742,4 +980,53 @@ mod tests {
             assert_json_eq!(schema, value);
         }
     }
+
+    #[test]
+    fn test_problem_responses() {
+        let mut components = Components::new();
+        let responses = writing::Problem::to_responses(&mut components);
+        let responses = serde_json::to_value(responses).unwrap();
+        let schema_ref =
+            &responses["default"]["content"]["application/problem+json"]["schema"]["$ref"];
+        let name = schema_ref
+            .as_str()
+            .unwrap()
+            .trim_start_matches("#/components/schemas/");
+        let schema = serde_json::to_value(&components.schemas[name]).unwrap();
+        assert_json_eq!(schema["required"], json!(["type", "title", "status"]));
+    }
+    #[test]
+    fn test_optional_arg_register() {
+        use salvo_core::extract::Lazy;
+        use salvo_core::http::ParseError;
+
+        use crate::extract::{JsonBody, QueryParam};
+
+        let mut components = Components::new();
+        let mut operation = Operation::new();
+        <Option<QueryParam<String>>>::register(&mut components, &mut operation, "name");
+        <Result<QueryParam<u32>, ParseError>>::register(&mut components, &mut operation, "age");
+        <Lazy<JsonBody<String>>>::register(&mut components, &mut operation, "body");
+        let operation = serde_json::to_value(operation).unwrap();
+        assert_json_eq!(operation["parameters"][0]["required"], json!(false));
+        assert_json_eq!(operation["parameters"][1]["required"], json!(false));
+        assert_json_eq!(operation["requestBody"]["required"], json!(false));
+    }
+
+    #[test]
+    fn test_set_and_bytes_schema() {
+        let mut components = Components::new();
+        assert_json_eq!(
+            <HashSet<String>>::to_schema(&mut components),
+            json!({"type": "array", "items": {"type": "string"}, "uniqueItems": true})
+        );
+        assert_json_eq!(
+            <BTreeSet<u8>>::to_schema(&mut components),
+            json!({"type": "array", "items": {"type": "integer", "format": "uint8", "minimum": 0.0}, "uniqueItems": true})
+        );
+        assert_json_eq!(
+            bytes::Bytes::to_schema(&mut components),
+            json!({"type": "string", "format": "binary"})
+        );
+    }
 }
@@ -5,6 +5,8 @@ use std::sync::LazyLock;
 use parking_lot::{RwLock, RwLockReadGuard};
 use regex::Regex;
 
+use crate::path::PathItemType;
+
 /// NameRule is used to specify the rule of naming.
 #[derive(Default, Debug, Clone, Copy)]
 pub enum NameRule {
@@ -89,6 +91,11 @@ pub fn get_name<T: 'static>() -> String {
     );
 }
 
+/// Remove the module paths of the type and its generic arguments, e.g. `a::Page<b::User>` to `Page<User>`.
+fn short_type_name(type_name: &str) -> String {
+    let re = Regex::new(r"([^<>, ]*::)+").expect("Invalid regex");
+    re.replace_all(type_name, "").to_string()
+}
 fn type_generic_part(type_name: &str) -> String {
     let re = Regex::new(r"^[^<]+").expect("Invalid regex");
     let result = re.replace_all(type_name, "");
@@ -105,6 +112,7 @@ pub trait Namer: Sync + Send + 'static {
 pub struct FlexNamer {
     short_mode: bool,
     generic_delimiter: Option<(String, String)>,
+    generic_separator: Option<String>,
 }
 impl FlexNamer {
     /// Create a new FlexNamer.
@@ -123,20 +131,43 @@ impl FlexNamer {
         self.generic_delimiter = Some((open.into(), close.into()));
         self
     }
+
+    /// Set the separator between the arguments of generic types, it replaces the `, ` in type name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use salvo_oapi::naming::FlexNamer;
+    /// // `Page<User>` is named `Page_User`, `ApiResult<User, Error>` is named `ApiResult_User_Error`.
+    /// let namer = FlexNamer::new()
+    ///     .short_mode(true)
+    ///     .generic_delimiter('_', "")
+    ///     .generic_separator('_');
+    /// ```
+    pub fn generic_separator(mut self, separator: impl Into<String>) -> Self {
+        self.generic_separator = Some(separator.into());
+        self
+    }
+
+    fn decorate_generics(&self, mut name: String) -> String {
+        if let Some(separator) = &self.generic_separator {
+            name = name.replace(", ", separator);
+        }
+        if let Some((open, close)) = &self.generic_delimiter {
+            name = name.replace('<', open).replace('>', close);
+        }
+        name
+    }
 }
 impl Namer for FlexNamer {
     fn assign_name(&self, type_id: TypeId, type_name: &'static str, rule: NameRule) -> String {
         let name = match rule {
             NameRule::Auto => {
-                let mut base = if self.short_mode {
-                    let re = Regex::new(r"([^<>]*::)+").expect("Invalid regex");
-                    re.replace_all(type_name, "").to_string()
+                let base = self.decorate_generics(if self.short_mode {
+                    short_type_name(type_name)
                 } else {
                     type_name.replace("::", ".")
-                };
-                if let Some((open, close)) = &self.generic_delimiter {
-                    base = base.replace('<', open).replace('>', close);
-                }
+                });
                 let mut name = base.to_string();
                 let mut count = 1;
                 while let Some(exist_id) = type_info_by_name(&name).map(|t| t.0) {
@@ -150,15 +181,11 @@ impl Namer for FlexNamer {
                 name
             }
             NameRule::Force(force_name) => {
-                let mut base = if self.short_mode {
-                    let re = Regex::new(r"([^<>]*::)+").expect("Invalid regex");
-                    re.replace_all(type_name, "").to_string()
+                let base = self.decorate_generics(if self.short_mode {
+                    short_type_name(type_name)
                 } else {
                     format! {"{}{}", force_name, type_generic_part(type_name).replace("::", ".")}
-                };
-                if let Some((open, close)) = &self.generic_delimiter {
-                    base = base.replace('<', open).replace('>', close).to_string();
-                }
+                });
                 let mut name = base.to_string();
                 let mut count = 1;
                 while let Some((exist_id, exist_name)) = type_info_by_name(&name) {
@@ -178,7 +205,111 @@ impl Namer for FlexNamer {
     }
 }
 
+/// Generates operation id for the operations in [`OpenApi`](crate::OpenApi), it is used by
+/// [`OpenApi::operation_ids`](crate::OpenApi::operation_ids).
+///
+/// It is implemented for closures like `Fn(Option<&str>, PathItemType, &str) -> String`.
+pub trait OperationIdGenerator {
+    /// Generate operation id from the current operation id which is named by [`Namer`] by default, the method
+    /// and the path of the operation.
+    fn generate(&self, operation_id: Option<&str>, method: PathItemType, path: &str) -> String;
+}
+impl<F> OperationIdGenerator for F
+where
+    F: Fn(Option<&str>, PathItemType, &str) -> String,
+{
+    fn generate(&self, operation_id: Option<&str>, method: PathItemType, path: &str) -> String {
+        self(operation_id, method, path)
+    }
+}
+
+/// Generates operation id in snake case and strips the module path, e.g. `my_app.user.listUsers` is converted
+/// to `list_users`. It falls back to [`MethodPathOperationId`] if the operation has no operation id.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct SnakeCaseOperationId;
+impl OperationIdGenerator for SnakeCaseOperationId {
+    fn generate(&self, operation_id: Option<&str>, method: PathItemType, path: &str) -> String {
+        match operation_id {
+            Some(operation_id) => {
+                let name = operation_id.split('<').next().unwrap_or_default();
+                to_snake_case(name.rsplit(['.', ':']).next().unwrap_or(name))
+            }
+            None => MethodPathOperationId.generate(operation_id, method, path),
+        }
+    }
+}
+
+/// Generates operation id in format `{method}_{path}`, e.g. `get_users_id` for `GET /users/{id}`.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct MethodPathOperationId;
+impl OperationIdGenerator for MethodPathOperationId {
+    fn generate(&self, _operation_id: Option<&str>, method: PathItemType, path: &str) -> String {
+        let method = match method {
+            PathItemType::Get => "get",
+            PathItemType::Post => "post",
+            PathItemType::Put => "put",
+            PathItemType::Delete => "delete",
+            PathItemType::Options => "options",
+            PathItemType::Head => "head",
+            PathItemType::Patch => "patch",
+            PathItemType::Trace => "trace",
+            PathItemType::Connect => "connect",
+        };
+        to_snake_case(&format!("{method}_{path}"))
+    }
+}
+
+/// Convert `name` to snake case, the characters which are not alphanumeric are converted to `_`.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for ch in name.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if ch.is_ascii_uppercase() && prev_lower {
+                snake.push('_');
+            }
+            prev_lower = ch.is_ascii_lowercase() || ch.is_ascii_digit();
+            snake.push(ch.to_ascii_lowercase());
+        } else {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    snake.trim_end_matches('_').to_owned()
+}
+
 mod tests {
+    #[test]
+    fn test_operation_id_generator() {
+        use super::*;
+
+        assert_eq!(
+            SnakeCaseOperationId.generate(
+                Some("my_app.user.listUsers"),
+                PathItemType::Get,
+                "/users"
+            ),
+            "list_users"
+        );
+        assert_eq!(
+            SnakeCaseOperationId.generate(None, PathItemType::Get, "/users/{id}"),
+            "get_users_id"
+        );
+        assert_eq!(
+            MethodPathOperationId.generate(Some("show"), PathItemType::Delete, "/users/{id}/pets"),
+            "delete_users_id_pets"
+        );
+        let generator = |operation_id: Option<&str>, _: PathItemType, _: &str| {
+            format!("api_{}", operation_id.unwrap_or_default())
+        };
+        assert_eq!(
+            generator.generate(Some("show"), PathItemType::Get, "/"),
+            "api_show"
+        );
+    }
+
     #[test]
     fn test_name() {
         use super::*;
@@ -198,6 +329,15 @@ mod tests {
         let name = assign_name::<nest::MyString>(NameRule::Auto);
         assert_eq!(name, "salvo_oapi.naming.tests.test_name.nest.MyString");
 
+        let namer = FlexNamer::new()
+            .short_mode(true)
+            .generic_delimiter('_', "")
+            .generic_separator('_');
+        let name = namer.decorate_generics(short_type_name(std::any::type_name::<
+            Result<Vec<MyString>, nest::MyString>,
+        >()));
+        assert_eq!(name, "Result_Vec_MyString_MyString");
+
         // let namer = FlexNamer::new().generic_delimiter('_', '_');
         // set_namer(namer);
 
@@ -0,0 +1,115 @@
+//! Implements [OpenAPI Callback Object][callback] types.
+//!
+//! [callback]: https://spec.openapis.org/oas/latest.html#callback-object
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+use super::{PathItem, PropMap, Ref, RefOr};
+
+/// Implements [OpenAPI Callback Object][callback].
+///
+/// A map of possible out-of band callbacks related to the parent [`Operation`][operation]. The key
+/// is a [runtime expression][expression] evaluated at runtime, e.g. `{$request.body#/callbackUrl}`,
+/// which identifies the URL the request is sent to. The value is the [`PathItem`] describing the
+/// request sent by the API provider and the expected responses.
+///
+/// [callback]: https://spec.openapis.org/oas/latest.html#callback-object
+/// [operation]: crate::Operation
+/// [expression]: https://spec.openapis.org/oas/latest.html#runtime-expressions
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct Callback(PropMap<String, PathItem>);
+
+impl Deref for Callback {
+    type Target = PropMap<String, PathItem>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+impl DerefMut for Callback {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Callback {
+    /// Construct a new empty [`Callback`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a [`PathItem`] for the given runtime expression and returns `Self`.
+    pub fn path<E: Into<String>, I: Into<PathItem>>(mut self, expression: E, item: I) -> Self {
+        let expression = expression.into();
+        let mut item = item.into();
+        if let Some(existing_item) = self.0.get_mut(&expression) {
+            existing_item.append(&mut item);
+        } else {
+            self.0.insert(expression, item);
+        }
+        self
+    }
+}
+
+impl From<Ref> for RefOr<Callback> {
+    fn from(r: Ref) -> Self {
+        Self::Ref(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Operation, PathItemType, RequestBody, Response};
+
+    #[test]
+    fn test_build_callback() {
+        let callback = Callback::new()
+            .path(
+                "{$request.body#/callbackUrl}",
+                PathItem::new(
+                    PathItemType::Post,
+                    Operation::new()
+                        .request_body(RequestBody::new().description("Payment event"))
+                        .add_response("200", Response::new("Event received")),
+                ),
+            )
+            .path(
+                "{$request.body#/callbackUrl}",
+                PathItem::new(
+                    PathItemType::Put,
+                    Operation::new().add_response("204", Response::new("Event updated")),
+                ),
+            );
+
+        assert_json_eq!(
+            callback,
+            json!({
+                "{$request.body#/callbackUrl}": {
+                    "post": {
+                        "requestBody": {
+                            "description": "Payment event",
+                            "content": {}
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "Event received"
+                            }
+                        }
+                    },
+                    "put": {
+                        "responses": {
+                            "204": {
+                                "description": "Event updated"
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
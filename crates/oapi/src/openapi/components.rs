@@ -4,7 +4,7 @@
 //! [schema]: https://spec.openapis.org/oas/latest.html#schema-object
 use serde::{Deserialize, Serialize};
 
-use crate::{PropMap, RefOr, Response, Responses, Schema, Schemas, SecurityScheme};
+use crate::{Callback, PropMap, RefOr, Response, Responses, Schema, Schemas, SecurityScheme};
 
 /// Implements [OpenAPI Components Object][components] which holds supported
 /// reusable objects.
@@ -36,6 +36,12 @@ pub struct Components {
     /// [security_scheme]: https://spec.openapis.org/oas/latest.html#security-scheme-object
     #[serde(skip_serializing_if = "PropMap::is_empty", default)]
     pub security_schemes: PropMap<String, SecurityScheme>,
+
+    /// Map of reusable [OpenAPI Callback Object][callback]s.
+    ///
+    /// [callback]: https://spec.openapis.org/oas/latest.html#callback-object
+    #[serde(skip_serializing_if = "PropMap::is_empty", default)]
+    pub callbacks: PropMap<String, RefOr<Callback>>,
 }
 
 impl Components {
@@ -153,6 +159,16 @@ impl Components {
         self
     }
 
+    /// Add a new reusable [`Callback`] and returns `self`.
+    pub fn add_callback<S: Into<String>, C: Into<RefOr<Callback>>>(
+        mut self,
+        name: S,
+        callback: C,
+    ) -> Self {
+        self.callbacks.insert(name.into(), callback.into());
+        self
+    }
+
     /// Moves all elements from `other` into `self`, leaving `other` empty.
     ///
     /// If a key from `other` is already present in `self`, the respective
@@ -172,10 +188,18 @@ impl Components {
             .security_schemes
             .retain(|name, _| !self.security_schemes.contains_key(name));
         self.security_schemes.append(&mut other.security_schemes);
+
+        other
+            .callbacks
+            .retain(|name, _| !self.callbacks.contains_key(name));
+        self.callbacks.append(&mut other.callbacks);
     }
 
     /// Returns `true` if instance contains no elements.
     pub fn is_empty(&self) -> bool {
-        self.schemas.is_empty() && self.responses.is_empty() && self.security_schemes.is_empty()
+        self.schemas.is_empty()
+            && self.responses.is_empty()
+            && self.security_schemes.is_empty()
+            && self.callbacks.is_empty()
     }
 }
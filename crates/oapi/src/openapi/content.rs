@@ -22,7 +22,7 @@ pub struct Content {
     /// media type and specified schema if present. [`Content::examples`] and
     /// [`Content::example`] are mutually exclusive. If both are defined `examples` will
     /// override value in `example`.
-    #[serde(skip_serializing_if = "PropMap::is_empty")]
+    #[serde(skip_serializing_if = "PropMap::is_empty", default)]
     pub examples: PropMap<String, RefOr<Example>>,
 
     /// A map between a property name and its encoding information.
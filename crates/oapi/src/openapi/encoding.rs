@@ -22,7 +22,7 @@ pub struct Encoding {
     /// A map allowing additional information to be provided as headers, for example
     /// Content-Disposition. Content-Type is described separately and SHALL be ignored in this
     /// section. This property SHALL be ignored if the request body media type is not a multipart.
-    #[serde(skip_serializing_if = "PropMap::is_empty")]
+    #[serde(skip_serializing_if = "PropMap::is_empty", default)]
     pub headers: PropMap<String, Header>,
 
     /// Describes how a specific property value will be serialized depending on its type. See
@@ -16,12 +16,12 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct Example {
     /// Short description for the [`Example`].
-    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(skip_serializing_if = "String::is_empty", default)]
     pub summary: String,
 
     /// Long description for the [`Example`]. Value supports markdown syntax for rich text
     /// representation.
-    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(skip_serializing_if = "String::is_empty", default)]
     pub description: String,
 
     /// Embedded literal example value. [`Example::value`] and [`Example::external_value`] are
@@ -32,7 +32,7 @@ pub struct Example {
     /// An URI that points to a literal example value. [`Example::external_value`] provides the
     /// capability to references an example that cannot be easily included in JSON or YAML.
     /// [`Example::value`] and [`Example::external_value`] are mutually exclusive.
-    #[serde(skip_serializing_if = "String::is_empty")]
+    #[serde(skip_serializing_if = "String::is_empty", default)]
     pub external_value: String,
 }
 
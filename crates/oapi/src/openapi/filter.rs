@@ -0,0 +1,295 @@
+//! Filtered views of [`OpenApi`] documents for multi-audience docs.
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use super::{OpenApi, Operation, PathItemType, Paths};
+
+/// The extension key used to store the visibility of an [`Operation`].
+///
+/// It is set by `#[endpoint(visibility = "...")]` and read by [`OpenApi::filter_visibility`].
+pub const VISIBILITY_EXTENSION: &str = "x-visibility";
+
+/// The visibility of [`Operation`]s which do not have [`VISIBILITY_EXTENSION`].
+pub const DEFAULT_VISIBILITY: &str = "public";
+
+impl OpenApi {
+    /// Returns a new [`OpenApi`] which only contains the operations of paths and webhooks accepted by
+    /// `predicate`.
+    ///
+    /// Path items without operations, tags which are not used by any remaining operation and
+    /// schemas, responses and callbacks which are no longer referenced are removed from the new
+    /// document, so one generated document can be served as multiple documents for different
+    /// audiences. Security schemes are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// # use salvo_oapi::OpenApi;
+    /// let doc = OpenApi::new("shop", "1.0.0");
+    /// let admin_doc = doc.filter(|path, _method, _operation| path.starts_with("/admin"));
+    /// ```
+    pub fn filter<F>(&self, mut predicate: F) -> OpenApi
+    where
+        F: FnMut(&str, PathItemType, &Operation) -> bool,
+    {
+        let mut doc = self.clone();
+        filter_paths(&mut doc.paths, &mut predicate);
+        filter_paths(&mut doc.webhooks, &mut predicate);
+
+        let used_tags = doc
+            .paths
+            .values()
+            .chain(doc.webhooks.values())
+            .flat_map(|item| item.operations.values())
+            .flat_map(|operation| operation.tags.iter())
+            .cloned()
+            .collect::<HashSet<_>>();
+        doc.tags.retain(|tag| used_tags.contains(&tag.name));
+
+        prune_components(&mut doc);
+        doc
+    }
+
+    /// Returns a new [`OpenApi`] which only contains the operations having any of the given `tags`.
+    ///
+    /// See [`OpenApi::filter`] for more details.
+    pub fn filter_tags<I, S>(&self, tags: I) -> OpenApi
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let tags = tags
+            .into_iter()
+            .map(Into::into)
+            .collect::<HashSet<String>>();
+        self.filter(|_, _, operation| operation.tags.iter().any(|tag| tags.contains(tag)))
+    }
+
+    /// Returns a new [`OpenApi`] which only contains the operations having any of the given
+    /// `visibilities`.
+    ///
+    /// The visibility of an operation is set by `#[endpoint(visibility = "...")]`, the operations
+    /// without visibility are [`DEFAULT_VISIBILITY`]. See [`OpenApi::filter`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// Serve public and internal documents at their own routes, a UI such as `SwaggerUi` can be
+    /// mounted for each of them.
+    /// ```
+    /// # use salvo_core::prelude::*;
+    /// # use salvo_oapi::{OpenApi, endpoint};
+    /// #[endpoint(visibility = "internal")]
+    /// async fn reindex() {}
+    ///
+    /// #[endpoint]
+    /// async fn hello() -> &'static str {
+    ///     "Hello"
+    /// }
+    ///
+    /// let router = Router::new()
+    ///     .push(Router::with_path("hello").get(hello))
+    ///     .push(Router::with_path("reindex").post(reindex));
+    /// let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+    /// let router = router
+    ///     .unshift(doc.filter_visibility(["public"]).into_router("/public/openapi.json"))
+    ///     .unshift(
+    ///         doc.filter_visibility(["public", "internal"])
+    ///             .into_router("/internal/openapi.json"),
+    ///     );
+    /// ```
+    pub fn filter_visibility<I, S>(&self, visibilities: I) -> OpenApi
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let visibilities = visibilities
+            .into_iter()
+            .map(Into::into)
+            .collect::<HashSet<String>>();
+        self.filter(|_, _, operation| {
+            let visibility = operation
+                .extensions
+                .get(VISIBILITY_EXTENSION)
+                .and_then(Value::as_str)
+                .unwrap_or(DEFAULT_VISIBILITY);
+            visibilities.contains(visibility)
+        })
+    }
+}
+
+fn filter_paths<F>(paths: &mut Paths, predicate: &mut F)
+where
+    F: FnMut(&str, PathItemType, &Operation) -> bool,
+{
+    for (path, item) in paths.iter_mut() {
+        item.operations
+            .retain(|method, operation| predicate(path, *method, operation));
+    }
+    paths.retain(|_, item| !item.operations.is_empty());
+}
+
+/// Remove the schemas, responses and callbacks which are not referenced by the paths and webhooks.
+fn prune_components(doc: &mut OpenApi) {
+    let Ok(value) = serde_json::to_value((&doc.paths, &doc.webhooks)) else {
+        return;
+    };
+    let mut pending = Vec::new();
+    collect_references(&value, &mut pending);
+
+    let components = &doc.components;
+    let mut used = HashSet::new();
+    while let Some(reference) = pending.pop() {
+        if used.contains(&reference) {
+            continue;
+        }
+        let value = if let Some(name) = reference.strip_prefix("#/components/schemas/") {
+            components.schemas.get(name).map(serde_json::to_value)
+        } else if let Some(name) = reference.strip_prefix("#/components/responses/") {
+            components.responses.get(name).map(serde_json::to_value)
+        } else if let Some(name) = reference.strip_prefix("#/components/callbacks/") {
+            components.callbacks.get(name).map(serde_json::to_value)
+        } else {
+            None
+        };
+        match value {
+            Some(Ok(value)) => collect_references(&value, &mut pending),
+            Some(Err(_)) => return,
+            None => {}
+        }
+        used.insert(reference);
+    }
+
+    let components = &mut doc.components;
+    components
+        .schemas
+        .retain(|name, _| used.contains(&format!("#/components/schemas/{name}")));
+    components
+        .responses
+        .retain(|name, _| used.contains(&format!("#/components/responses/{name}")));
+    components
+        .callbacks
+        .retain(|name, _| used.contains(&format!("#/components/callbacks/{name}")));
+}
+
+fn collect_references(value: &Value, references: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map {
+                if key == "$ref" {
+                    if let Value::String(reference) = item {
+                        references.push(reference.clone());
+                    }
+                } else {
+                    collect_references(item, references);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_references(item, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        BasicType, Components, Content, Object, PathItem, Ref, RequestBody, Response, Tag,
+    };
+
+    fn doc() -> OpenApi {
+        let mut audit = Operation::new()
+            .add_tag("admin")
+            .request_body(RequestBody::new().add_content(
+                "application/json",
+                Content::new(Ref::new("#/components/schemas/PetAudit")),
+            ))
+            .add_response("200", Response::new("Ok"));
+        audit
+            .extensions
+            .insert(VISIBILITY_EXTENSION.into(), "internal".into());
+
+        OpenApi::new("test api", "0.0.1")
+            .add_path(
+                "/pets",
+                PathItem::new(
+                    PathItemType::Get,
+                    Operation::new().add_tag("pets").add_response(
+                        "200",
+                        Response::new("Pets").add_content(
+                            "application/json",
+                            Content::new(Ref::new("#/components/schemas/Pet")),
+                        ),
+                    ),
+                ),
+            )
+            .add_path("/admin/pets", PathItem::new(PathItemType::Post, audit))
+            .components(
+                Components::new()
+                    .add_schema(
+                        "Pet",
+                        Object::with_type(BasicType::Object)
+                            .property("owner", Ref::new("#/components/schemas/Owner")),
+                    )
+                    .add_schema("Owner", Object::with_type(BasicType::String))
+                    .add_schema(
+                        "PetAudit",
+                        Object::with_type(BasicType::Object)
+                            .property("pet", Ref::new("#/components/schemas/Pet")),
+                    ),
+            )
+            .tags([Tag::new("pets"), Tag::new("admin")])
+    }
+
+    #[test]
+    fn test_filter_visibility() {
+        let public = doc().filter_visibility(["public"]);
+        let value = serde_json::to_value(&public).unwrap();
+        assert_json_eq!(
+            value["paths"],
+            json!({
+                "/pets": {
+                    "get": {
+                        "tags": ["pets"],
+                        "responses": {
+                            "200": {
+                                "description": "Pets",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {"$ref": "#/components/schemas/Pet"}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+        let mut schemas = public.components.schemas.keys().collect::<Vec<_>>();
+        schemas.sort();
+        assert_eq!(schemas, vec!["Owner", "Pet"]);
+        assert_json_eq!(value["tags"], json!([{"name": "pets"}]));
+
+        let internal = doc().filter_visibility(["public", "internal"]);
+        assert_eq!(internal, doc());
+    }
+
+    #[test]
+    fn test_filter_tags() {
+        let admin = doc().filter_tags(["admin"]);
+        assert_eq!(admin.paths.keys().collect::<Vec<_>>(), vec!["/admin/pets"]);
+        assert_eq!(admin.components.schemas.len(), 3);
+        assert_eq!(admin.tags.len(), 1);
+
+        let empty = doc().filter(|_, _, _| false);
+        assert!(empty.paths.is_empty());
+        assert!(empty.components.schemas.is_empty());
+        assert!(empty.tags.is_empty());
+    }
+}
@@ -117,6 +117,12 @@ impl Info {
         self.license = Some(license);
         self
     }
+
+    /// Add openapi extension (`x-something`) for [`Info`].
+    pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
 }
 
 /// OpenAPI [Contact][contact] information of the API.
@@ -0,0 +1,447 @@
+//! Merge [`OpenApi`] documents with conflict policies.
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use serde_json::Value;
+use thiserror::Error;
+
+use super::{OpenApi, PathItemType, Paths, PropMap, Tag};
+
+/// Policy used by [`OpenApi::try_merge`] to resolve the conflicts between two documents.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MergePolicy {
+    /// Return [`MergeError`] on the first conflict.
+    Error,
+    /// Keep the items of `self` and discard the conflicting items of `other`.
+    #[default]
+    PreferLeft,
+    /// Overwrite the items of `self` with the conflicting items of `other`.
+    PreferRight,
+    /// Rename the conflicting components of `other` with a numeric suffix, e.g. `Pet` to `Pet_2`, and
+    /// update all references to them in `other`.
+    ///
+    /// Operations and webhooks can not be renamed, a conflict on them returns [`MergeError`]. Tags
+    /// with the same name are treated as the same group and the tag of `self` is kept.
+    Rename,
+}
+
+/// Error returned by [`OpenApi::try_merge`].
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum MergeError {
+    /// Both documents define the same operation of a path or webhook.
+    #[error("conflicting operation `{method:?}` of `{path}`")]
+    Operation {
+        /// The path or webhook name.
+        path: String,
+        /// The method of the operation.
+        method: PathItemType,
+    },
+    /// Both documents define different components with the same name.
+    #[error("conflicting component `{name}` in `{kind}`")]
+    Component {
+        /// The kind of component, e.g. `schemas` or `responses`.
+        kind: &'static str,
+        /// The component name.
+        name: String,
+    },
+    /// Both documents define different tags with the same name.
+    #[error("conflicting tag `{0}`")]
+    Tag(String),
+    /// Renaming the components of `other` failed.
+    #[error("rename components failed: {0}")]
+    Rename(#[from] serde_json::Error),
+}
+
+impl OpenApi {
+    /// Merge `other` [`OpenApi`] into `self` and resolve the conflicts with the given [`MergePolicy`].
+    ///
+    /// This allows large applications split across crates to build a partial document in each crate
+    /// and combine them at startup. Two items conflict when they have the same path and method, or the
+    /// same name but different content. Identical items are merged silently. `servers` and `security`
+    /// requirements never conflict and are always combined.
+    ///
+    /// **Note!** `info`, `openapi`, `json_schema_dialect` and `external_docs` will not be merged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use salvo_oapi::{MergePolicy, OpenApi};
+    /// let users = OpenApi::new("users", "0.1.0");
+    /// let orders = OpenApi::new("orders", "0.1.0");
+    /// let doc = OpenApi::new("shop", "1.0.0")
+    ///     .try_merge(users, MergePolicy::Error)
+    ///     .and_then(|doc| doc.try_merge(orders, MergePolicy::Rename))
+    ///     .unwrap();
+    /// ```
+    pub fn try_merge(
+        mut self,
+        mut other: OpenApi,
+        policy: MergePolicy,
+    ) -> Result<Self, MergeError> {
+        if policy == MergePolicy::Rename {
+            other = rename_components(&self, other)?;
+        }
+
+        self.servers.append(&mut other.servers);
+        self.security.append(&mut other.security);
+        merge_paths(&mut self.paths, other.paths, policy)?;
+        merge_paths(&mut self.webhooks, other.webhooks, policy)?;
+
+        let components = &mut self.components;
+        let others = other.components;
+        merge_props(
+            "schemas",
+            &mut components.schemas.0,
+            others.schemas.0,
+            policy,
+        )?;
+        merge_props(
+            "responses",
+            &mut *components.responses,
+            others.responses.into(),
+            policy,
+        )?;
+        merge_props(
+            "securitySchemes",
+            &mut components.security_schemes,
+            others.security_schemes,
+            policy,
+        )?;
+        merge_props(
+            "callbacks",
+            &mut components.callbacks,
+            others.callbacks,
+            policy,
+        )?;
+        merge_tags(&mut self.tags, other.tags, policy)?;
+        Ok(self)
+    }
+}
+
+fn merge_paths(
+    paths: &mut Paths,
+    mut others: Paths,
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    for (path, mut other) in std::mem::take(&mut *others) {
+        let Some(item) = paths.get_mut(&path) else {
+            paths.insert(path, other);
+            continue;
+        };
+        for (method, operation) in std::mem::take(&mut other.operations.0) {
+            if item.operations.contains_key(&method) {
+                match policy {
+                    MergePolicy::PreferLeft => continue,
+                    MergePolicy::PreferRight => {}
+                    MergePolicy::Error | MergePolicy::Rename => {
+                        return Err(MergeError::Operation { path, method });
+                    }
+                }
+            }
+            item.operations.insert(method, operation);
+        }
+        item.servers.append(&mut other.servers);
+        item.parameters.append(&mut other.parameters);
+        if item.summary.is_none() || (policy == MergePolicy::PreferRight && other.summary.is_some())
+        {
+            item.summary = other.summary;
+        }
+        if item.description.is_none()
+            || (policy == MergePolicy::PreferRight && other.description.is_some())
+        {
+            item.description = other.description;
+        }
+    }
+    Ok(())
+}
+
+fn merge_tags(
+    tags: &mut BTreeSet<Tag>,
+    others: BTreeSet<Tag>,
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    for tag in others {
+        match tags.get(&tag) {
+            None => {
+                tags.insert(tag);
+            }
+            Some(existing) if *existing == tag => {}
+            Some(_) => match policy {
+                MergePolicy::PreferLeft | MergePolicy::Rename => {}
+                MergePolicy::PreferRight => {
+                    tags.replace(tag);
+                }
+                MergePolicy::Error => return Err(MergeError::Tag(tag.name)),
+            },
+        }
+    }
+    Ok(())
+}
+
+fn merge_props<V: PartialEq>(
+    kind: &'static str,
+    props: &mut PropMap<String, V>,
+    others: PropMap<String, V>,
+    policy: MergePolicy,
+) -> Result<(), MergeError> {
+    for (name, other) in others {
+        match props.get(&name) {
+            Some(existing) if *existing == other => {}
+            Some(_) => match policy {
+                MergePolicy::PreferLeft | MergePolicy::Rename => {}
+                MergePolicy::PreferRight => {
+                    props.insert(name, other);
+                }
+                MergePolicy::Error => return Err(MergeError::Component { kind, name }),
+            },
+            None => {
+                props.insert(name, other);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the new names of the components of `other` which conflict with the components of `left`.
+fn conflicting_names<V: PartialEq>(
+    left: &PropMap<String, V>,
+    other: &PropMap<String, V>,
+) -> HashMap<String, String> {
+    let mut taken: HashSet<String> = left.keys().chain(other.keys()).cloned().collect();
+    let mut renames = HashMap::new();
+    for (name, item) in other {
+        if left.get(name).is_some_and(|existing| existing != item) {
+            let new_name = (2..)
+                .map(|index| format!("{name}_{index}"))
+                .find(|new_name| !taken.contains(new_name))
+                .expect("new name should be found");
+            taken.insert(new_name.clone());
+            renames.insert(name.clone(), new_name);
+        }
+    }
+    renames
+}
+
+fn rename_components(left: &OpenApi, other: OpenApi) -> Result<OpenApi, MergeError> {
+    let kinds = [
+        (
+            "schemas",
+            conflicting_names(&left.components.schemas.0, &other.components.schemas.0),
+        ),
+        (
+            "responses",
+            conflicting_names(&*left.components.responses, &*other.components.responses),
+        ),
+        (
+            "callbacks",
+            conflicting_names(&left.components.callbacks, &other.components.callbacks),
+        ),
+        (
+            "securitySchemes",
+            conflicting_names(
+                &left.components.security_schemes,
+                &other.components.security_schemes,
+            ),
+        ),
+    ];
+    if kinds.iter().all(|(_, renames)| renames.is_empty()) {
+        return Ok(other);
+    }
+
+    let mut value = serde_json::to_value(other)?;
+    let mut references = HashMap::new();
+    let mut security_schemes = HashMap::new();
+    for (kind, renames) in kinds {
+        if let Some(Value::Object(components)) = value
+            .get_mut("components")
+            .and_then(|components| components.get_mut(kind))
+        {
+            for (name, new_name) in &renames {
+                if let Some(item) = components.remove(name) {
+                    components.insert(new_name.clone(), item);
+                }
+            }
+        }
+        if kind == "securitySchemes" {
+            security_schemes = renames;
+        } else {
+            references.extend(renames.into_iter().map(|(name, new_name)| {
+                (
+                    format!("#/components/{kind}/{name}"),
+                    format!("#/components/{kind}/{new_name}"),
+                )
+            }));
+        }
+    }
+    rename_references(&mut value, &references, &security_schemes);
+    Ok(serde_json::from_value(value)?)
+}
+
+fn rename_references(
+    value: &mut Value,
+    references: &HashMap<String, String>,
+    security_schemes: &HashMap<String, String>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                if key == "$ref" {
+                    if let Value::String(reference) = item {
+                        if let Some(new_reference) = references.get(reference) {
+                            *reference = new_reference.clone();
+                        }
+                    }
+                    continue;
+                }
+                if key == "security" {
+                    if let Value::Array(requirements) = item {
+                        for requirement in requirements.iter_mut() {
+                            if let Value::Object(requirement) = requirement {
+                                for (name, new_name) in security_schemes {
+                                    if let Some(scopes) = requirement.remove(name) {
+                                        requirement.insert(new_name.clone(), scopes);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                rename_references(item, references, security_schemes);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rename_references(item, references, security_schemes);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_json_diff::assert_json_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        BasicType, Components, Content, Object, Operation, PathItem, Ref, Response,
+        SecurityRequirement, SecurityScheme,
+        security::{Http, HttpAuthScheme},
+    };
+
+    fn doc(path: &str, description: &str) -> OpenApi {
+        OpenApi::new("test api", "0.0.1")
+            .add_path(
+                path,
+                PathItem::new(
+                    PathItemType::Get,
+                    Operation::new()
+                        .add_security(SecurityRequirement::new("auth", Vec::<String>::new()))
+                        .add_response(
+                            "200",
+                            Response::new("Pet").add_content(
+                                "application/json",
+                                Content::new(Ref::new("#/components/schemas/Pet")),
+                            ),
+                        ),
+                ),
+            )
+            .components(
+                Components::new()
+                    .add_schema(
+                        "Pet",
+                        Object::with_type(BasicType::Object).description(description),
+                    )
+                    .add_security_scheme(
+                        "auth",
+                        SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+                    ),
+            )
+            .tags([Tag::new("pets").description(description)])
+    }
+
+    #[test]
+    fn test_try_merge_error() {
+        let err = doc("/pets", "left")
+            .try_merge(doc("/animals", "right"), MergePolicy::Error)
+            .unwrap_err();
+        assert!(
+            matches!(err, MergeError::Component { kind: "schemas", ref name } if name == "Pet")
+        );
+
+        let err = doc("/pets", "same")
+            .try_merge(doc("/pets", "same"), MergePolicy::Error)
+            .unwrap_err();
+        assert!(
+            matches!(err, MergeError::Operation { method: PathItemType::Get, ref path } if path == "/pets")
+        );
+
+        let doc = doc("/pets", "same")
+            .try_merge(doc("/animals", "same"), MergePolicy::Error)
+            .unwrap();
+        assert_eq!(doc.paths.len(), 2);
+        assert_eq!(doc.components.schemas.len(), 1);
+    }
+
+    #[test]
+    fn test_try_merge_prefer() {
+        let left = doc("/pets", "left")
+            .try_merge(doc("/pets", "right"), MergePolicy::PreferLeft)
+            .unwrap();
+        assert_eq!(left, doc("/pets", "left"));
+
+        let right = doc("/pets", "left")
+            .try_merge(doc("/pets", "right"), MergePolicy::PreferRight)
+            .unwrap();
+        assert_eq!(right, doc("/pets", "right"));
+    }
+
+    #[test]
+    fn test_try_merge_rename() {
+        let other = doc("/animals", "right").components(
+            Components::new()
+                .add_schema(
+                    "Pet",
+                    Object::with_type(BasicType::Object).description("right"),
+                )
+                .add_schema("Pet_2", Object::with_type(BasicType::String))
+                .add_security_scheme(
+                    "auth",
+                    SecurityScheme::Http(Http::new(HttpAuthScheme::Basic)),
+                ),
+        );
+        let doc = doc("/pets", "left")
+            .try_merge(other, MergePolicy::Rename)
+            .unwrap();
+
+        let value = serde_json::to_value(&doc).unwrap();
+        assert_json_eq!(
+            value["components"]["schemas"],
+            json!({
+                "Pet": {"type": "object", "description": "left"},
+                "Pet_2": {"type": "string"},
+                "Pet_3": {"type": "object", "description": "right"}
+            })
+        );
+        assert_json_eq!(
+            value["components"]["securitySchemes"],
+            json!({
+                "auth": {"type": "http", "scheme": "bearer"},
+                "auth_2": {"type": "http", "scheme": "basic"}
+            })
+        );
+        let animals = &value["paths"]["/animals"]["get"];
+        assert_json_eq!(animals["security"], json!([{"auth_2": []}]));
+        assert_json_eq!(
+            animals["responses"]["200"]["content"]["application/json"]["schema"],
+            json!({"$ref": "#/components/schemas/Pet_3"})
+        );
+        let pets = &value["paths"]["/pets"]["get"];
+        assert_json_eq!(pets["security"], json!([{"auth": []}]));
+        assert_json_eq!(
+            value["tags"],
+            json!([{"name": "pets", "description": "left"}])
+        );
+    }
+}
@@ -1,13 +1,17 @@
 //! Rust implementation of Openapi Spec V3.1.
 
+mod callback;
 mod components;
 mod content;
 mod encoding;
 mod example;
 mod external_docs;
+mod filter;
 mod header;
 pub mod info;
 mod link;
+mod merge;
+mod modifier;
 pub mod operation;
 pub mod parameter;
 pub mod path;
@@ -19,7 +23,7 @@ pub mod server;
 mod tag;
 mod xml;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::Formatter;
 use std::sync::LazyLock;
 
@@ -29,12 +33,16 @@ use serde::de::{Error, Expected, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 pub use self::{
+    callback::Callback,
     components::Components,
     content::Content,
     example::Example,
     external_docs::ExternalDocs,
+    filter::{DEFAULT_VISIBILITY, VISIBILITY_EXTENSION},
     header::Header,
     info::{Contact, Info, License},
+    merge::{MergeError, MergePolicy},
+    modifier::OpenApiModifier,
     operation::{Operation, Operations},
     parameter::{Parameter, ParameterIn, ParameterStyle, Parameters},
     path::{PathItem, PathItemType, Paths},
@@ -49,6 +57,7 @@ pub use self::{
     tag::Tag,
     xml::Xml,
 };
+use crate::naming::OperationIdGenerator;
 use crate::{Endpoint, routing::NormNode};
 
 static PATH_PARAMETER_NAME_REGEX: LazyLock<Regex> =
@@ -93,7 +102,7 @@ pub struct OpenApi {
     /// This is implicitly one server with `url` set to `/`.
     ///
     /// See more details at <https://spec.openapis.org/oas/latest.html#server-object>.
-    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty", default)]
     pub servers: BTreeSet<Server>,
 
     /// Available paths and operations for the API.
@@ -101,12 +110,18 @@ pub struct OpenApi {
     /// See more details at <https://spec.openapis.org/oas/latest.html#paths-object>.
     pub paths: Paths,
 
+    /// Incoming webhooks that may be received as part of this API, the key is a unique name of the webhook.
+    ///
+    /// See more details at <https://spec.openapis.org/oas/latest.html#oasWebhooks>.
+    #[serde(default, skip_serializing_if = "Paths::is_empty")]
+    pub webhooks: Paths,
+
     /// Holds various reusable schemas for the OpenAPI document.
     ///
     /// Few of these elements are security schemas and object schemas.
     ///
     /// See more details at <https://spec.openapis.org/oas/latest.html#components-object>.
-    #[serde(skip_serializing_if = "Components::is_empty")]
+    #[serde(skip_serializing_if = "Components::is_empty", default)]
     pub components: Components,
 
     /// Declaration of global security mechanisms that can be used across the API. The individual operations
@@ -114,13 +129,13 @@ pub struct OpenApi {
     /// optional by adding it to the list of securities.
     ///
     /// See more details at <https://spec.openapis.org/oas/latest.html#security-requirement-object>.
-    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty", default)]
     pub security: BTreeSet<SecurityRequirement>,
 
     /// List of tags can be used to add additional documentation to matching tags of operations.
     ///
     /// See more details at <https://spec.openapis.org/oas/latest.html#tag-object>.
-    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    #[serde(skip_serializing_if = "BTreeSet::is_empty", default)]
     pub tags: BTreeSet<Tag>,
 
     /// Global additional documentation reference.
@@ -136,6 +151,12 @@ pub struct OpenApi {
     #[serde(rename = "$schema", default, skip_serializing_if = "String::is_empty")]
     pub schema: String,
 
+    /// The default value for the `$schema` keyword within [`Schema`]s contained within this document.
+    ///
+    /// See more details at <https://spec.openapis.org/oas/latest.html#fixed-fields>.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_schema_dialect: Option<String>,
+
     /// Optional extensions "x-something".
     #[serde(skip_serializing_if = "PropMap::is_empty", flatten)]
     pub extensions: PropMap<String, serde_json::Value>,
@@ -195,7 +216,7 @@ impl OpenApi {
 
     /// Merge `other` [`OpenApi`] consuming it and resuming it's content.
     ///
-    /// Merge function will take all `self` nonexistent _`servers`, `paths`, `schemas`, `responses`,
+    /// Merge function will take all `self` nonexistent _`servers`, `paths`, `webhooks`, `schemas`, `responses`,
     /// `security_schemes`, `security_requirements` and `tags`_ from _`other`_ [`OpenApi`].
     ///
     /// This function performs a shallow comparison for `paths`, `schemas`, `responses` and
@@ -206,9 +227,12 @@ impl OpenApi {
     /// comparison.
     ///
     /// **Note!** `info`, `openapi` and `external_docs` and `schema` will not be merged.
+    ///
+    /// Use [`OpenApi::try_merge`] to choose how the conflicts are resolved.
     pub fn merge(mut self, mut other: OpenApi) -> Self {
         self.servers.append(&mut other.servers);
         self.paths.append(&mut other.paths);
+        self.webhooks.append(&mut other.webhooks);
         self.components.append(&mut other.components);
         self.security.append(&mut other.security);
         self.tags.append(&mut other.tags);
@@ -250,6 +274,34 @@ impl OpenApi {
         self
     }
 
+    /// Set webhooks which may be received as part of this API.
+    pub fn webhooks<P: Into<Paths>>(mut self, webhooks: P) -> Self {
+        self.webhooks = webhooks.into();
+        self
+    }
+    /// Add a webhook [`PathItem`] with unique `name` and returns `Self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use salvo_oapi::{OpenApi, Operation, PathItem, PathItemType, Response};
+    /// let _ = OpenApi::new("pet api", "0.1.0").add_webhook(
+    ///     "newPet",
+    ///     PathItem::new(
+    ///         PathItemType::Post,
+    ///         Operation::new().add_response("200", Response::new("Webhook received")),
+    ///     ),
+    /// );
+    /// ```
+    pub fn add_webhook<N, I>(mut self, name: N, item: I) -> Self
+    where
+        N: Into<String>,
+        I: Into<PathItem>,
+    {
+        self.webhooks.insert(name.into(), item.into());
+        self
+    }
+
     /// Add [`Components`] to configure reusable schemas.
     pub fn components(mut self, components: impl Into<Components>) -> Self {
         self.components = components.into();
@@ -404,6 +456,20 @@ impl OpenApi {
         self
     }
 
+    /// Set the default `$schema` dialect for the [`Schema`]s contained within this document.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use salvo_oapi::OpenApi;
+    /// let _ = OpenApi::new("openapi", "0.1.0")
+    ///     .json_schema_dialect("https://json-schema.org/draft/2020-12/schema");
+    /// ```
+    pub fn json_schema_dialect<S: Into<String>>(mut self, dialect: S) -> Self {
+        self.json_schema_dialect = Some(dialect.into());
+        self
+    }
+
     /// Add openapi extension (`x-something`) for [`OpenApi`].
     pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
         self.extensions.insert(key.into(), value);
@@ -411,6 +477,10 @@ impl OpenApi {
     }
 
     /// Consusmes the [`OpenApi`] and returns [`Router`] with the [`OpenApi`] as handler.
+    ///
+    /// The document is served as JSON, add `?pretty` to the url to get pretty JSON. If feature `yaml` is
+    /// enabled, it is served as YAML when the path ends with `.yaml` or `.yml`, or the `Accept` header prefers
+    /// `application/yaml` to `application/json`.
     pub fn into_router(self, path: impl Into<String>) -> Router {
         Router::with_path(path.into()).goal(self)
     }
@@ -427,6 +497,74 @@ impl OpenApi {
         self
     }
 
+    /// Consusmes the [`OpenApi`] and informations from a [`Router`], only the routes of the API version are
+    /// included, so that separate documents can be generated for each version.
+    ///
+    /// View [`Router::version`] for more details about API versioning.
+    pub fn merge_router_version(mut self, router: &Router, version: &str) -> Self {
+        let mut node = NormNode::new(router, Default::default());
+        if node.version.as_deref().is_none_or(|v| v == version) {
+            node.retain_version(version);
+            self.merge_norm_node(&mut node, "/");
+        }
+        self
+    }
+
+    /// Regenerate the operation ids of all operations with the [`OperationIdGenerator`], it should be called
+    /// after the routers are merged.
+    ///
+    /// The duplicate operation ids are appended with suffix `_2`, `_3` and so on, in the order of paths and
+    /// methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use salvo_oapi::OpenApi;
+    /// # use salvo_oapi::naming::MethodPathOperationId;
+    /// # use salvo_core::Router;
+    /// let router = Router::new();
+    /// let doc = OpenApi::new("pet api", "0.1.0")
+    ///     .merge_router(&router)
+    ///     .operation_ids(MethodPathOperationId);
+    /// ```
+    pub fn operation_ids(mut self, generator: impl OperationIdGenerator) -> Self {
+        let mut exists = HashSet::new();
+        for (path, path_item) in self.paths.iter_mut() {
+            for (method, operation) in path_item.operations.iter_mut() {
+                let base = generator.generate(operation.operation_id.as_deref(), *method, path);
+                let mut operation_id = base.clone();
+                let mut count = 1;
+                while !exists.insert(operation_id.clone()) {
+                    count += 1;
+                    operation_id = format!("{base}_{count}");
+                }
+                operation.operation_id = Some(operation_id);
+            }
+        }
+        self
+    }
+
+    /// Modify the document with the [`OpenApiModifier`], it should be called after the routers are merged and
+    /// right before the document is served, e.g. to inject servers, strip internal tags or add license and
+    /// contact information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use salvo_oapi::{OpenApi, Server};
+    /// # use salvo_core::Router;
+    /// let router = Router::new();
+    /// let doc = OpenApi::new("pet api", "0.1.0")
+    ///     .merge_router(&router)
+    ///     .with_modifier(|doc: &mut OpenApi| {
+    ///         doc.servers.insert(Server::new("https://api.example.com"));
+    ///     });
+    /// ```
+    pub fn with_modifier(mut self, modifier: impl OpenApiModifier) -> Self {
+        modifier.modify(&mut self);
+        self
+    }
+
     fn merge_norm_node(&mut self, node: &mut NormNode, base_path: &str) {
         fn join_path(a: &str, b: &str) -> String {
             if a.is_empty() {
@@ -461,10 +599,34 @@ impl OpenApi {
                     mut components,
                     ..
                 } = (creator)();
+                for parameter in operation.parameters.0.iter_mut() {
+                    if parameter.parameter_in != ParameterIn::Path {
+                        continue;
+                    }
+                    let Some((_, pattern)) = node
+                        .path_patterns
+                        .iter()
+                        .rev()
+                        .find(|(name, _)| *name == parameter.name)
+                    else {
+                        continue;
+                    };
+                    if let Some(RefOr::Type(Schema::Object(object))) = &mut parameter.schema {
+                        if object.pattern.is_none() {
+                            object.pattern = Some(pattern.clone());
+                        }
+                    }
+                }
                 operation.tags.extend(node.metadata.tags.iter().cloned());
                 operation
                     .securities
                     .extend(node.metadata.securities.iter().cloned());
+                for (name, scheme) in &node.metadata.security_schemes {
+                    self.components
+                        .security_schemes
+                        .entry(name.clone())
+                        .or_insert_with(|| scheme.clone());
+                }
                 let methods = if let Some(method) = &node.method {
                     vec![*method]
                 } else {
@@ -499,7 +661,25 @@ impl OpenApi {
                     })
                     .collect::<Vec<_>>();
                 if !meta_not_exist_parameters.is_empty() {
-                    tracing::warn!(parameters = ?meta_not_exist_parameters, path, handler_name = node.handler_type_name, "parameters information not provided");
+                    tracing::warn!(parameters = ?meta_not_exist_parameters, path, handler_name = node.handler_type_name, "parameters information not provided, inferred from route path");
+                }
+                for name in meta_not_exist_parameters {
+                    let mut schema = Object::with_type(BasicType::String);
+                    if let Some((_, pattern)) = node
+                        .path_patterns
+                        .iter()
+                        .rev()
+                        .find(|(pattern_name, _)| pattern_name == name)
+                    {
+                        schema = schema.pattern(pattern.clone());
+                    }
+                    operation.parameters.insert(
+                        Parameter::new(name)
+                            .parameter_in(ParameterIn::Path)
+                            .description(format!("Get parameter `{name}` from request url path."))
+                            .schema(schema)
+                            .required(true),
+                    );
                 }
                 let path_item = self.paths.entry(path.clone()).or_default();
                 for method in methods {
@@ -531,6 +711,11 @@ impl Handler for OpenApi {
         res: &mut salvo_core::Response,
         _ctrl: &mut FlowCtrl,
     ) {
+        #[cfg(feature = "yaml")]
+        if wants_yaml(req) {
+            res.render(writing::Text::Yaml(self.to_yaml().unwrap_or_default()));
+            return;
+        }
         let pretty = req
             .queries()
             .get("pretty")
@@ -544,6 +729,28 @@ impl Handler for OpenApi {
         res.render(writing::Text::Json(&content));
     }
 }
+/// Returns `true` if the document is requested as YAML, by the `.yaml` or `.yml` extension of the request path,
+/// or by the `Accept` header which prefers YAML to JSON.
+#[cfg(feature = "yaml")]
+fn wants_yaml(req: &salvo_core::Request) -> bool {
+    let path = req.uri().path();
+    if path.ends_with(".yaml") || path.ends_with(".yml") {
+        return true;
+    }
+    if path.ends_with(".json") {
+        return false;
+    }
+    matches!(
+        req.accepts(&[
+            "application/json",
+            "application/yaml",
+            "application/x-yaml",
+            "text/yaml",
+        ]),
+        Some(accept) if accept != "application/json"
+    )
+}
+
 /// Represents available [OpenAPI versions][version].
 ///
 /// [version]: <https://spec.openapis.org/oas/latest.html#versions>
@@ -782,6 +989,89 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn serialize_deserialize_openapi_with_webhooks_success() -> Result<(), serde_json::Error> {
+        let doc = OpenApi::new("pet api", "0.1.0")
+            .json_schema_dialect("https://json-schema.org/draft/2020-12/schema")
+            .add_webhook(
+                "newPet",
+                PathItem::new(
+                    PathItemType::Post,
+                    Operation::new().add_response("200", Response::new("Webhook received")),
+                ),
+            );
+        let value = serde_json::to_value(&doc)?;
+        assert_eq!(
+            value,
+            json!({
+                "openapi": "3.1.0",
+                "info": {
+                    "title": "pet api",
+                    "version": "0.1.0"
+                },
+                "paths": {},
+                "webhooks": {
+                    "newPet": {
+                        "post": {
+                            "responses": {
+                                "200": {
+                                    "description": "Webhook received"
+                                }
+                            }
+                        }
+                    }
+                },
+                "jsonSchemaDialect": "https://json-schema.org/draft/2020-12/schema"
+            })
+        );
+        let deserialized: OpenApi = serde_json::from_value(value)?;
+        assert_eq!(deserialized, doc);
+
+        let merged = OpenApi::new("pet api", "0.1.0").merge(doc);
+        assert!(merged.webhooks.contains_key("newPet"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_openapi_operation_ids() {
+        let operation = || Operation::new().operation_id("my_app.pets.listPets");
+        let doc = OpenApi::new("pet api", "0.1.0")
+            .add_path("/pets", PathItem::new(PathItemType::Get, operation()))
+            .add_path("/pets", PathItem::new(PathItemType::Post, operation()))
+            .add_path(
+                "/pets/{id}",
+                PathItem::new(PathItemType::Get, Operation::new()),
+            );
+
+        let snake = doc
+            .clone()
+            .operation_ids(crate::naming::SnakeCaseOperationId);
+        let operation_id = |doc: &OpenApi, path: &str, method: PathItemType| {
+            doc.paths[path].operations[&method]
+                .operation_id
+                .clone()
+                .unwrap()
+        };
+        assert_eq!(
+            operation_id(&snake, "/pets", PathItemType::Get),
+            "list_pets"
+        );
+        assert_eq!(
+            operation_id(&snake, "/pets", PathItemType::Post),
+            "list_pets_2"
+        );
+        assert_eq!(
+            operation_id(&snake, "/pets/{id}", PathItemType::Get),
+            "get_pets_id"
+        );
+
+        let method_path = doc.operation_ids(crate::naming::MethodPathOperationId);
+        assert_eq!(
+            operation_id(&method_path, "/pets", PathItemType::Post),
+            "post_pets"
+        );
+    }
+
     #[test]
     fn serialize_openapi_json_with_paths_success() -> Result<(), serde_json::Error> {
         let doc = OpenApi::new("My big api", "1.1.0").paths(
@@ -1189,6 +1479,55 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(feature = "yaml")]
+    #[tokio::test]
+    async fn test_openapi_handler_yaml() {
+        use salvo_core::test::{ResponseExt, TestClient};
+
+        let router = Router::new()
+            .push(OpenApi::new("pet api", "0.1.0").into_router("openapi.json"))
+            .push(OpenApi::new("pet api", "0.1.0").into_router("openapi.yaml"));
+        let service = Service::new(router);
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/openapi.yaml")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/yaml; charset=utf-8"
+        );
+        let doc: OpenApi = serde_norway::from_str(&res.take_string().await.unwrap()).unwrap();
+        assert_eq!(doc, OpenApi::new("pet api", "0.1.0"));
+
+        let res = TestClient::get("http://127.0.0.1:5801/openapi.json")
+            .add_header("accept", "application/yaml", true)
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/json; charset=utf-8"
+        );
+
+        let router = OpenApi::new("pet api", "0.1.0").into_router("openapi");
+        let service = Service::new(router);
+        let res = TestClient::get("http://127.0.0.1:5801/openapi")
+            .add_header("accept", "application/yaml", true)
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/yaml; charset=utf-8"
+        );
+        let res = TestClient::get("http://127.0.0.1:5801/openapi")
+            .add_header("accept", "*/*", true)
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/json; charset=utf-8"
+        );
+    }
+
     #[test]
     fn test_deprecated_from_bool() {
         assert_eq!(Deprecated::True, Deprecated::from(true));
@@ -1468,4 +1807,156 @@ mod tests {
             Value::from_str(&doc.to_json().unwrap()).unwrap()
         );
     }
+
+    #[test]
+    fn test_path_param_pattern() {
+        #[salvo_oapi::endpoint]
+        async fn get_user(id: PathParam<u64>, name: PathParam<String>) -> String {
+            format!("{} {}", id.into_inner(), name.into_inner())
+        }
+
+        let router = Router::with_path(r"users/{id|\d+}")
+            .push(Router::with_path(r"names/prefix_{name:/[a-z]+/}").get(get_user));
+        let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+        let value = Value::from_str(&doc.to_json().unwrap()).unwrap();
+        let parameters = &value["paths"]["/users/{id}/names/prefix_{name}"]["get"]["parameters"];
+        assert_eq!(parameters[0]["name"], "id");
+        assert_eq!(parameters[0]["schema"]["pattern"], r"^\d+$");
+        assert_eq!(parameters[1]["name"], "name");
+        assert_eq!(parameters[1]["schema"]["pattern"], "^[a-z]+$");
+    }
+
+    #[test]
+    fn test_with_modifier() {
+        struct StripInternal;
+        impl OpenApiModifier for StripInternal {
+            fn modify(&self, doc: &mut OpenApi) {
+                doc.tags.retain(|tag| tag.name != "internal");
+            }
+        }
+
+        let doc = OpenApi::new("test api", "0.0.1")
+            .tags([Tag::new("internal"), Tag::new("pets")])
+            .with_modifier(StripInternal)
+            .with_modifier(|doc: &mut OpenApi| {
+                doc.servers.insert(Server::new("https://api.example.com"));
+                doc.info.license = Some(License::new("MIT"));
+            });
+        assert_eq!(doc.tags.len(), 1);
+        assert_eq!(doc.tags.first().unwrap().name, "pets");
+        assert_eq!(doc.servers.first().unwrap().url, "https://api.example.com");
+        assert_eq!(doc.info.license.unwrap().name, "MIT");
+    }
+
+    #[test]
+    fn test_infer_path_params_from_route() {
+        #[salvo_oapi::endpoint]
+        async fn get_file(id: PathParam<u64>) -> String {
+            id.into_inner().to_string()
+        }
+
+        let router = Router::with_path(r"users/{id}/{kind|[a-z]+}/files/{**rest}").get(get_file);
+        let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+        let value = Value::from_str(&doc.to_json().unwrap()).unwrap();
+        let parameters = &value["paths"]["/users/{id}/{kind}/files/{rest}"]["get"]["parameters"];
+        assert_eq!(parameters.as_array().unwrap().len(), 3);
+        assert_eq!(parameters[0]["name"], "id");
+        assert_eq!(parameters[0]["schema"]["type"], "integer");
+        assert_eq!(parameters[1]["name"], "kind");
+        assert_eq!(parameters[1]["in"], "path");
+        assert_eq!(parameters[1]["required"], true);
+        assert_eq!(parameters[1]["schema"]["pattern"], "^[a-z]+$");
+        assert_eq!(parameters[2]["name"], "rest");
+        assert_eq!(parameters[2]["schema"]["type"], "string");
+    }
+
+    #[test]
+    fn test_merge_router_version() {
+        #[salvo_oapi::endpoint]
+        async fn list_users_v1() -> &'static str {
+            "v1"
+        }
+        #[salvo_oapi::endpoint]
+        async fn list_users_v2() -> &'static str {
+            "v2"
+        }
+        #[salvo_oapi::endpoint]
+        async fn health() -> &'static str {
+            "ok"
+        }
+
+        let router = Router::new()
+            .push(
+                Router::with_version(salvo_core::routing::filters::version("2").path_prefix("v"))
+                    .push(Router::with_path("users").get(list_users_v2)),
+            )
+            .push(Router::with_version("1").push(Router::with_path("users").get(list_users_v1)))
+            .push(Router::with_path("health").get(health));
+
+        let doc = OpenApi::new("test api", "2").merge_router_version(&router, "2");
+        assert!(doc.paths.contains_key("/v2/users"));
+        assert!(doc.paths.contains_key("/health"));
+        assert!(!doc.paths.contains_key("/users"));
+
+        let doc = OpenApi::new("test api", "1").merge_router_version(&router, "1");
+        assert!(doc.paths.contains_key("/users"));
+        assert!(doc.paths.contains_key("/health"));
+        assert!(!doc.paths.contains_key("/v2/users"));
+    }
+
+    #[test]
+    fn test_merge_router_auth_hoops() {
+        use salvo_extra::basic_auth::{BasicAuth, BasicAuthValidator};
+        use salvo_jwt_auth::{ConstDecoder, JwtAuth};
+
+        struct Validator;
+        impl BasicAuthValidator for Validator {
+            async fn validate(&self, _username: &str, _password: &str, _depot: &mut Depot) -> bool {
+                true
+            }
+        }
+
+        #[salvo_oapi::endpoint]
+        async fn list_users() -> &'static str {
+            "users"
+        }
+        #[salvo_oapi::endpoint]
+        async fn list_admins() -> &'static str {
+            "admins"
+        }
+        #[salvo_oapi::endpoint]
+        async fn health() -> &'static str {
+            "ok"
+        }
+
+        let jwt_auth: JwtAuth<serde_json::Value, _> =
+            JwtAuth::new(ConstDecoder::from_secret(b"secret"));
+        let router = Router::new()
+            .push(Router::with_path("users").hoop(jwt_auth).get(list_users))
+            .push(
+                Router::with_path("admins")
+                    .hoop_when(BasicAuth::new(Validator), |_, _| true)
+                    .get(list_admins),
+            )
+            .push(Router::with_path("health").get(health));
+
+        let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+        let value = serde_json::to_value(&doc).unwrap();
+        assert_eq!(
+            value["paths"]["/users"]["get"]["security"],
+            json!([{"bearerAuth": []}])
+        );
+        assert_eq!(
+            value["paths"]["/admins"]["get"]["security"],
+            json!([{"basicAuth": []}])
+        );
+        assert!(value["paths"]["/health"]["get"].get("security").is_none());
+        assert_eq!(
+            value["components"]["securitySchemes"],
+            json!({
+                "bearerAuth": {"type": "http", "scheme": "bearer", "bearerFormat": "JWT"},
+                "basicAuth": {"type": "http", "scheme": "basic"}
+            })
+        );
+    }
 }
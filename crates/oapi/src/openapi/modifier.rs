@@ -0,0 +1,39 @@
+//! Implements post-processing of the generated [`OpenApi`] document.
+use super::OpenApi;
+
+/// Modifies the finished [`OpenApi`] document, it is used by [`OpenApi::with_modifier`].
+///
+/// It is implemented for closures like `Fn(&mut OpenApi)`.
+///
+/// # Examples
+///
+/// _**Remove the operations and tags which are only used internally.**_
+/// ```
+/// # use salvo_oapi::{OpenApi, OpenApiModifier};
+/// struct StripInternal;
+/// impl OpenApiModifier for StripInternal {
+///     fn modify(&self, doc: &mut OpenApi) {
+///         doc.tags.retain(|tag| tag.name != "internal");
+///         for path_item in doc.paths.values_mut() {
+///             path_item
+///                 .operations
+///                 .retain(|_, operation| !operation.tags.iter().any(|tag| tag == "internal"));
+///         }
+///         doc.paths.retain(|_, path_item| !path_item.operations.is_empty());
+///     }
+/// }
+///
+/// let doc = OpenApi::new("pet api", "0.1.0").with_modifier(StripInternal);
+/// ```
+pub trait OpenApiModifier {
+    /// Modify the [`OpenApi`] document.
+    fn modify(&self, doc: &mut OpenApi);
+}
+impl<F> OpenApiModifier for F
+where
+    F: Fn(&mut OpenApi),
+{
+    fn modify(&self, doc: &mut OpenApi) {
+        self(doc)
+    }
+}
@@ -6,7 +6,7 @@ use std::ops::{Deref, DerefMut};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    Deprecated, ExternalDocs, RefOr, SecurityRequirement, Server,
+    Callback, Deprecated, ExternalDocs, RefOr, SecurityRequirement, Server,
     request_body::RequestBody,
     response::{Response, Responses},
 };
@@ -98,7 +98,7 @@ pub struct Operation {
     ///
     /// [derive_path]: ../../attr.path.html
     /// [derive_openapi]: ../../derive.OpenApi.html
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub tags: Vec<String>,
 
     /// Short summary what [`Operation`] does.
@@ -133,7 +133,7 @@ pub struct Operation {
     pub external_docs: Option<ExternalDocs>,
 
     /// List of applicable parameters for this [`Operation`].
-    #[serde(skip_serializing_if = "Parameters::is_empty")]
+    #[serde(skip_serializing_if = "Parameters::is_empty", default)]
     pub parameters: Parameters,
 
     /// Optional request body for this [`Operation`].
@@ -143,9 +143,10 @@ pub struct Operation {
     /// List of possible responses returned by the [`Operation`].
     pub responses: Responses,
 
-    /// Callback information.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub callbacks: Option<String>,
+    /// Map of out-of band [`Callback`]s related to the [`Operation`], the key is a unique
+    /// identifier of the [`Callback`].
+    #[serde(skip_serializing_if = "PropMap::is_empty", default)]
+    pub callbacks: PropMap<String, RefOr<Callback>>,
 
     /// Define whether the operation is deprecated or not and thus should be avoided consuming.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -156,12 +157,12 @@ pub struct Operation {
     ///
     /// Security for the [`Operation`] can be set to optional by adding empty security with
     /// [`SecurityRequirement::default`].
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
     #[serde(rename = "security")]
     pub securities: Vec<SecurityRequirement>,
 
     /// Alternative [`Server`]s for this [`Operation`].
-    #[serde(skip_serializing_if = "Servers::is_empty")]
+    #[serde(skip_serializing_if = "Servers::is_empty", default)]
     pub servers: Servers,
 
     /// Optional extensions "x-something"
@@ -247,6 +248,29 @@ impl Operation {
         self
     }
 
+    /// Add or change callbacks of the [`Operation`].
+    pub fn callbacks<I, N, C>(mut self, callbacks: I) -> Self
+    where
+        I: IntoIterator<Item = (N, C)>,
+        N: Into<String>,
+        C: Into<RefOr<Callback>>,
+    {
+        self.callbacks = callbacks
+            .into_iter()
+            .map(|(name, callback)| (name.into(), callback.into()))
+            .collect();
+        self
+    }
+    /// Append a named [`Callback`] to the [`Operation`] callbacks and returns `Self`.
+    pub fn add_callback<N: Into<String>, C: Into<RefOr<Callback>>>(
+        mut self,
+        name: N,
+        callback: C,
+    ) -> Self {
+        self.callbacks.insert(name.into(), callback.into());
+        self
+    }
+
     /// Add or change deprecated status of the [`Operation`].
     pub fn deprecated<D: Into<Deprecated>>(mut self, deprecated: D) -> Self {
         self.deprecated = Some(deprecated.into());
@@ -278,6 +302,12 @@ impl Operation {
         self
     }
 
+    /// Add openapi extension (`x-something`) for [`Operation`].
+    pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
+
     /// For easy chaining of operations.
     pub fn then<F>(self, func: F) -> Self
     where
@@ -294,8 +324,8 @@ mod tests {
 
     use super::{Operation, Operations};
     use crate::{
-        Deprecated, Parameter, PathItemType, RequestBody, Responses, security::SecurityRequirement,
-        server::Server,
+        Callback, Deprecated, Parameter, PathItem, PathItemType, Ref, RequestBody, Response,
+        Responses, security::SecurityRequirement, server::Server,
     };
 
     #[test]
@@ -310,7 +340,7 @@ mod tests {
         assert!(operation.parameters.is_empty());
         assert!(operation.request_body.is_none());
         assert!(operation.responses.is_empty());
-        assert!(operation.callbacks.is_none());
+        assert!(operation.callbacks.is_empty());
         assert!(operation.deprecated.is_none());
         assert!(operation.securities.is_empty());
         assert!(operation.servers.is_empty());
@@ -420,4 +450,41 @@ mod tests {
 
         operation.then(print_operation);
     }
+
+    #[test]
+    fn test_operation_callbacks() {
+        let callback = Callback::new().path(
+            "{$request.query.callbackUrl}",
+            PathItem::new(
+                PathItemType::Post,
+                Operation::new().add_response("200", Response::new("Callback received")),
+            ),
+        );
+        let operation = Operation::new()
+            .add_callback("onPayment", callback)
+            .add_callback("onRefund", Ref::new("#/components/callbacks/onRefund"));
+
+        assert_json_eq!(
+            operation,
+            json!({
+                "responses": {},
+                "callbacks": {
+                    "onPayment": {
+                        "{$request.query.callbackUrl}": {
+                            "post": {
+                                "responses": {
+                                    "200": {
+                                        "description": "Callback received"
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "onRefund": {
+                        "$ref": "#/components/callbacks/onRefund"
+                    }
+                }
+            })
+        );
+    }
 }
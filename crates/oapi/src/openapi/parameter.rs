@@ -269,6 +269,12 @@ impl Parameter {
         self.example = Some(example);
         self
     }
+
+    /// Add openapi extension (`x-something`) for [`Parameter`].
+    pub fn add_extension<K: Into<String>>(mut self, key: K, value: Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
 }
 
 /// In definition of [`Parameter`].
@@ -30,6 +30,10 @@ impl Paths {
     pub fn new() -> Self {
         Default::default()
     }
+    /// Returns `true` if the instance contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
     /// Inserts a key-value pair into the instance and returns `self`.
     pub fn path<K: Into<String>, V: Into<PathItem>>(mut self, key: K, value: V) -> Self {
         self.insert(key, value);
@@ -96,14 +100,13 @@ pub struct PathItem {
 
     /// Alternative [`Server`] array to serve all [`Operation`]s in this [`PathItem`] overriding
     /// the global server array.
-    #[serde(skip_serializing_if = "Servers::is_empty")]
+    #[serde(skip_serializing_if = "Servers::is_empty", default)]
     pub servers: Servers,
 
     /// List of [`Parameter`]s common to all [`Operation`]s in this [`PathItem`]. Parameters cannot
     /// contain duplicate parameters. They can be overridden in [`Operation`] level but cannot be
     /// removed there.
-    #[serde(skip_serializing_if = "Parameters::is_empty")]
-    #[serde(flatten)]
+    #[serde(skip_serializing_if = "Parameters::is_empty", default)]
     pub parameters: Parameters,
 
     /// Map of operations in this [`PathItem`]. Operations can hold only one operation
@@ -43,6 +43,14 @@ pub struct AllOf {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub examples: Vec<Value>,
 
+    /// Write only property will be only sent in _write_ requests like _POST, PUT_.
+    #[serde(rename = "writeOnly", skip_serializing_if = "Option::is_none")]
+    pub write_only: Option<bool>,
+
+    /// Read only property will be only sent in _read_ requests like _GET_.
+    #[serde(rename = "readOnly", skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+
     /// Optional discriminator field can be used to aid deserialization, serialization and validation of a
     /// specific schema.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -62,6 +70,8 @@ impl Default for AllOf {
             description: Default::default(),
             default_value: Default::default(),
             examples: Default::default(),
+            write_only: Default::default(),
+            read_only: Default::default(),
             discriminator: Default::default(),
             extensions: Default::default(),
         }
@@ -132,6 +142,29 @@ impl AllOf {
         self
     }
 
+    /// Add or change example shown in UI of the value for richer documentation.
+    pub fn example<V: Into<Value>>(self, example: V) -> Self {
+        self.add_example(example)
+    }
+
+    /// Add or change examples shown in UI of the value for richer documentation.
+    pub fn examples<I: IntoIterator<Item = V>, V: Into<Value>>(mut self, examples: I) -> Self {
+        self.examples = examples.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add or change write only flag for [`AllOf`].
+    pub fn write_only(mut self, write_only: bool) -> Self {
+        self.write_only = Some(write_only);
+        self
+    }
+
+    /// Add or change read only flag for [`AllOf`].
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
     /// Add or change discriminator field of the composite [`AllOf`] type.
     pub fn discriminator(mut self, discriminator: Discriminator) -> Self {
         self.discriminator = Some(discriminator);
@@ -7,12 +7,15 @@ mod any_of;
 mod array;
 mod object;
 mod one_of;
+mod validate;
 
 pub use all_of::AllOf;
 pub use any_of::AnyOf;
 pub use array::{Array, ToArray};
 pub use object::Object;
 pub use one_of::OneOf;
+#[doc(hidden)]
+pub use validate::assert_content_examples;
 
 use std::ops::{Deref, DerefMut};
 
@@ -137,9 +140,6 @@ pub enum Schema {
     /// Defines array schema from another schema. Typically used with
     /// [`Schema::Object`]. Slice and Vec types are translated to [`Schema::Array`] types.
     Array(Array),
-    /// Defines object schema. Object is either `object` holding **properties** which are other [`Schema`]s
-    /// or can be a field within the [`Object`].
-    Object(Box<Object>),
     /// Creates a _OneOf_ type [composite Object][composite] schema. This schema
     /// is used to map multiple schemas together where API endpoint could return any of them.
     /// [`Schema::OneOf`] is created form complex enum where enum holds other than unit types.
@@ -156,6 +156,12 @@ pub enum Schema {
     ///
     /// [composite]: https://spec.openapis.org/oas/latest.html#components-object
     AnyOf(AnyOf),
+
+    // `Object` is the last variant, the `type` of it is optional, so composite schemas would be
+    // deserialized as objects if it is tried first.
+    /// Defines object schema. Object is either `object` holding **properties** which are other [`Schema`]s
+    /// or can be a field within the [`Object`].
+    Object(Box<Object>),
 }
 
 impl Default for Schema {
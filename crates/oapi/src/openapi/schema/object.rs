@@ -17,13 +17,21 @@ use crate::{Deprecated, PropMap, RefOr, Schema, SchemaFormat, SchemaType, ToArra
 pub struct Object {
     /// Type of [`Object`] e.g. [`Type::Object`] for `object` and [`Type::String`] for
     /// `string` types.
-    #[serde(rename = "type", skip_serializing_if = "SchemaType::is_any_value")]
+    #[serde(
+        rename = "type",
+        skip_serializing_if = "SchemaType::is_any_value",
+        default
+    )]
     pub schema_type: SchemaType,
 
     /// Changes the [`Object`] name.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
+    /// Title of the [`Object`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
     /// Additional format for detailing the schema type.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub format: Option<SchemaFormat>,
@@ -220,6 +228,12 @@ impl Object {
         self
     }
 
+    /// Add or change the title of the [`Object`].
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
     /// Add or change description of the property. Markdown syntax is supported.
     pub fn description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
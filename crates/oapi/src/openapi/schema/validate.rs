@@ -0,0 +1,189 @@
+//! Lightweight validation of example values against [`Schema`]s.
+use serde_json::Value;
+
+use super::{BasicType, Schema, SchemaType};
+use crate::{Components, Content, RefOr};
+
+/// Panics if the examples of `content` do not match its schema.
+///
+/// It is used by `#[endpoint]` in debug builds to check examples loaded from files.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_content_examples(content: &Content, components: &Components) {
+    let examples = content
+        .example
+        .iter()
+        .map(|value| ("example", value))
+        .chain(
+            content
+                .examples
+                .iter()
+                .filter_map(|(name, example)| match example {
+                    RefOr::Type(example) => {
+                        example.value.as_ref().map(|value| (name.as_str(), value))
+                    }
+                    RefOr::Ref(_) => None,
+                }),
+        );
+    for (name, value) in examples {
+        if let Err(e) = validate(&content.schema, value, components, "$") {
+            panic!("example `{name}` does not match the schema: {e}");
+        }
+    }
+}
+
+/// Validates `value` against `schema`, returns the json path and reason of the first mismatch.
+///
+/// Only types, enum values, required properties, array items and composite schemas are checked.
+pub(crate) fn validate(
+    schema: &RefOr<Schema>,
+    value: &Value,
+    components: &Components,
+    path: &str,
+) -> Result<(), String> {
+    let schema = match schema {
+        RefOr::Type(schema) => schema,
+        RefOr::Ref(reference) => {
+            let name = reference
+                .ref_location
+                .trim_start_matches("#/components/schemas/");
+            return match components.schemas.get(name) {
+                Some(schema) => validate(schema, value, components, path),
+                None => Ok(()),
+            };
+        }
+    };
+    match schema {
+        Schema::Object(object) => {
+            check_type(&object.schema_type, value, path)?;
+            if !object.enum_values.is_empty() && !object.enum_values.contains(value) {
+                return Err(format!("{path}: value is not one of the enum values"));
+            }
+            if let Value::Object(map) = value {
+                for name in &object.required {
+                    if !map.contains_key(name) {
+                        return Err(format!("{path}: missing required property `{name}`"));
+                    }
+                }
+                for (name, value) in map {
+                    if let Some(property) = object.properties.get(name) {
+                        validate(property, value, components, &format!("{path}.{name}"))?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        Schema::Array(array) => {
+            check_type(&array.schema_type, value, path)?;
+            if let Value::Array(items) = value {
+                for (index, item) in items.iter().enumerate() {
+                    validate(&array.items, item, components, &format!("{path}[{index}]"))?;
+                }
+            }
+            Ok(())
+        }
+        Schema::OneOf(one_of) => validate_any(&one_of.items, value, components, path),
+        Schema::AnyOf(any_of) => validate_any(&any_of.items, value, components, path),
+        Schema::AllOf(all_of) => all_of
+            .items
+            .iter()
+            .try_for_each(|item| validate(item, value, components, path)),
+    }
+}
+
+fn validate_any(
+    items: &[RefOr<Schema>],
+    value: &Value,
+    components: &Components,
+    path: &str,
+) -> Result<(), String> {
+    if items.is_empty()
+        || items
+            .iter()
+            .any(|item| validate(item, value, components, path).is_ok())
+    {
+        Ok(())
+    } else {
+        Err(format!("{path}: value does not match any of the schemas"))
+    }
+}
+
+fn check_type(schema_type: &SchemaType, value: &Value, path: &str) -> Result<(), String> {
+    let matches = |basic_type: &BasicType| match basic_type {
+        BasicType::Object => value.is_object(),
+        BasicType::String => value.is_string(),
+        BasicType::Integer => value.is_i64() || value.is_u64(),
+        BasicType::Number => value.is_number(),
+        BasicType::Boolean => value.is_boolean(),
+        BasicType::Array => value.is_array(),
+        BasicType::Null => value.is_null(),
+    };
+    let valid = match schema_type {
+        SchemaType::Basic(basic_type) => matches(basic_type),
+        SchemaType::Array(basic_types) => basic_types.iter().any(matches),
+        SchemaType::AnyValue => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "{path}: expected type {schema_type:?}, found `{value}`"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{Array, Object, Ref};
+
+    #[test]
+    fn test_validate_example() {
+        let components = Components::new().add_schema(
+            "Pet",
+            Object::with_type(BasicType::Object)
+                .property("name", Object::with_type(BasicType::String))
+                .required("name")
+                .property(
+                    "tags",
+                    Array::new().items(Object::with_type(BasicType::String)),
+                ),
+        );
+        let schema = RefOr::from(Ref::new("#/components/schemas/Pet"));
+
+        assert!(
+            validate(
+                &schema,
+                &json!({"name": "Tom", "tags": ["cat"]}),
+                &components,
+                "$"
+            )
+            .is_ok()
+        );
+        assert_eq!(
+            validate(&schema, &json!({"tags": []}), &components, "$").unwrap_err(),
+            "$: missing required property `name`"
+        );
+        assert!(
+            validate(
+                &schema,
+                &json!({"name": "Tom", "tags": [1]}),
+                &components,
+                "$"
+            )
+            .unwrap_err()
+            .starts_with("$.tags[0]: expected type")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "example `Tom` does not match the schema")]
+    fn test_assert_content_examples() {
+        let content = Content::new(Object::with_type(BasicType::String))
+            .example(json!("ok"))
+            .extend_examples([("Tom", crate::Example::new().value(json!(1)))]);
+        assert_content_examples(&content, &Components::new());
+    }
+}
@@ -179,6 +179,10 @@ pub struct ApiKeyValue {
     /// Description of the [`ApiKey`] [`SecurityScheme`]. Supports markdown syntax.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Optional extensions "x-something"
+    #[serde(skip_serializing_if = "PropMap::is_empty", flatten)]
+    pub extensions: PropMap<String, serde_json::Value>,
 }
 
 impl ApiKeyValue {
@@ -195,6 +199,7 @@ impl ApiKeyValue {
         Self {
             name: name.into(),
             description: None,
+            extensions: Default::default(),
         }
     }
 
@@ -211,8 +216,15 @@ impl ApiKeyValue {
         Self {
             name: name.into(),
             description: Some(description.into()),
+            extensions: Default::default(),
         }
     }
+
+    /// Add openapi extension (`x-something`) for [`ApiKeyValue`].
+    pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
 }
 
 /// Http authentication [`SecurityScheme`] builder.
@@ -232,6 +244,10 @@ pub struct Http {
     /// Optional description of [`Http`] [`SecurityScheme`] supporting markdown syntax.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Optional extensions "x-something"
+    #[serde(skip_serializing_if = "PropMap::is_empty", flatten)]
+    pub extensions: PropMap<String, serde_json::Value>,
 }
 
 impl Http {
@@ -251,6 +267,7 @@ impl Http {
             scheme,
             bearer_format: None,
             description: None,
+            extensions: Default::default(),
         }
     }
     /// Add or change http authentication scheme used.
@@ -284,6 +301,12 @@ impl Http {
 
         self
     }
+
+    /// Add openapi extension (`x-something`) for [`Http`].
+    pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
 }
 
 /// Implements types according [RFC7235](https://datatracker.ietf.org/doc/html/rfc7235#section-5.1).
@@ -333,6 +356,10 @@ pub struct OpenIdConnect {
     /// Description of [`OpenIdConnect`] [`SecurityScheme`] supporting markdown syntax.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+
+    /// Optional extensions "x-something"
+    #[serde(skip_serializing_if = "PropMap::is_empty", flatten)]
+    pub extensions: PropMap<String, serde_json::Value>,
 }
 
 impl OpenIdConnect {
@@ -348,6 +375,7 @@ impl OpenIdConnect {
         Self {
             open_id_connect_url: open_id_connect_url.into(),
             description: None,
+            extensions: Default::default(),
         }
     }
 
@@ -364,8 +392,15 @@ impl OpenIdConnect {
         Self {
             open_id_connect_url: open_id_connect_url.into(),
             description: Some(description.into()),
+            extensions: Default::default(),
         }
     }
+
+    /// Add openapi extension (`x-something`) for [`OpenIdConnect`].
+    pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
 }
 
 /// OAuth2 [`Flow`] configuration for [`SecurityScheme`].
@@ -468,6 +503,12 @@ impl OAuth2 {
             extensions: Default::default(),
         }
     }
+
+    /// Add openapi extension (`x-something`) for [`OAuth2`].
+    pub fn add_extension<K: Into<String>>(mut self, key: K, value: serde_json::Value) -> Self {
+        self.extensions.insert(key.into(), value);
+        self
+    }
 }
 
 /// [`OAuth2`] flow configuration object.
@@ -1369,4 +1410,41 @@ mod tests {
   "description": "authorization is performed with client side certificate"
 }"###
     }
+
+    test_fn! {
+        security_scheme_correct_http_with_extensions:
+        SecurityScheme::Http(
+            Http::new(HttpAuthScheme::Bearer).add_extension("x-gateway", serde_json::json!("kong"))
+        );
+        r###"{
+  "type": "http",
+  "scheme": "bearer",
+  "x-gateway": "kong"
+}"###
+    }
+
+    test_fn! {
+        security_scheme_correct_api_key_with_extensions:
+        SecurityScheme::ApiKey(ApiKey::Header(
+            ApiKeyValue::new("api_key").add_extension("x-key-prefix", serde_json::json!("Key"))
+        ));
+        r###"{
+  "type": "apiKey",
+  "in": "header",
+  "name": "api_key",
+  "x-key-prefix": "Key"
+}"###
+    }
+
+    #[test]
+    fn test_deserialize_security_scheme_with_extensions() {
+        let scheme = SecurityScheme::OpenIdConnect(
+            OpenIdConnect::new("https://localhost/openid")
+                .add_extension("x-audience", serde_json::json!(["api"])),
+        );
+        let value = serde_json::to_value(&scheme).unwrap();
+        assert_eq!(value["x-audience"], serde_json::json!(["api"]));
+        let deserialized: SecurityScheme = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, scheme);
+    }
 }
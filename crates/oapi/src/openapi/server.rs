@@ -148,7 +148,7 @@ pub struct Server {
     pub description: Option<String>,
 
     /// Optional map of variable name and its substitution value used in [`Server::url`].
-    #[serde(skip_serializing_if = "ServerVariables::is_empty")]
+    #[serde(skip_serializing_if = "ServerVariables::is_empty", default)]
     pub variables: ServerVariables,
 }
 
@@ -304,7 +304,7 @@ pub struct ServerVariable {
 
     /// Enum values can be used to limit possible options for substitution. If enum values is used
     /// the [`ServerVariable::default_value`] must contain one of the enum values.
-    #[serde(rename = "enum", skip_serializing_if = "BTreeSet::is_empty")]
+    #[serde(rename = "enum", skip_serializing_if = "BTreeSet::is_empty", default)]
     enum_values: BTreeSet<String>,
 }
 
@@ -0,0 +1,250 @@
+use serde::Serialize;
+
+/// Color theme of RapiDoc.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Theme {
+    /// Light theme.
+    Light,
+    /// Dark theme.
+    Dark,
+}
+
+/// Layout of RapiDoc.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Layout {
+    /// Request and response are placed side by side.
+    Row,
+    /// Request and response are placed one below the other.
+    Column,
+}
+
+/// Render style of RapiDoc.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum RenderStyle {
+    /// Operations are shown in one page and expanded, suitable for reading.
+    Read,
+    /// Operations are collapsed and expanded on demand.
+    View,
+    /// Only the selected operation is shown.
+    Focused,
+}
+
+/// Object used to alter RapiDoc settings.
+///
+/// Each setting is rendered as an attribute of the `<rapi-doc>` element, see
+/// [RapiDoc API](https://rapidocweb.com/api.html) for more details.
+///
+/// # Examples
+///
+/// ```rust
+/// # use salvo_oapi::rapidoc::{Config, Layout, RapiDoc, Theme};
+/// let doc = RapiDoc::new("/openapi.json").config(
+///     Config::new()
+///         .theme(Theme::Dark)
+///         .layout(Layout::Column)
+///         .allow_spec_file_download(false)
+///         .server_url("https://api.example.com"),
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Serialize, Default, Clone, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub struct Config {
+    /// Color theme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) theme: Option<Theme>,
+
+    /// Layout of request and response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) layout: Option<Layout>,
+
+    /// Render style of operations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) render_style: Option<RenderStyle>,
+
+    /// Whether the spec file can be downloaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) allow_spec_file_download: Option<bool>,
+
+    /// Whether the user can select the server to send requests to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) allow_server_selection: Option<bool>,
+
+    /// Url of the server requests are sent to, overriding the servers of the spec.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) server_url: Option<String>,
+
+    /// Url of the server selected by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) default_api_server: Option<String>,
+
+    /// Whether "Try" is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) allow_try: Option<bool>,
+
+    /// Whether the header bar is shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) show_header: Option<bool>,
+
+    /// Primary color of buttons, tabs and links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) primary_color: Option<String>,
+
+    /// Background color.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) bg_color: Option<String>,
+
+    /// Text color.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) text_color: Option<String>,
+
+    /// Custom css appended to the html page.
+    #[serde(skip)]
+    pub(crate) custom_css: Option<String>,
+
+    /// Custom javascript appended to the html page.
+    #[serde(skip)]
+    pub(crate) custom_js: Option<String>,
+}
+
+impl Config {
+    /// Create a new empty [`Config`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set color theme.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Set layout of request and response.
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Set render style of operations.
+    pub fn render_style(mut self, render_style: RenderStyle) -> Self {
+        self.render_style = Some(render_style);
+        self
+    }
+
+    /// Set whether the spec file can be downloaded.
+    pub fn allow_spec_file_download(mut self, allow_spec_file_download: bool) -> Self {
+        self.allow_spec_file_download = Some(allow_spec_file_download);
+        self
+    }
+
+    /// Set whether the user can select the server to send requests to.
+    pub fn allow_server_selection(mut self, allow_server_selection: bool) -> Self {
+        self.allow_server_selection = Some(allow_server_selection);
+        self
+    }
+
+    /// Set url of the server requests are sent to, overriding the servers of the spec.
+    pub fn server_url(mut self, server_url: impl Into<String>) -> Self {
+        self.server_url = Some(server_url.into());
+        self
+    }
+
+    /// Set url of the server selected by default.
+    pub fn default_api_server(mut self, default_api_server: impl Into<String>) -> Self {
+        self.default_api_server = Some(default_api_server.into());
+        self
+    }
+
+    /// Set whether "Try" is enabled.
+    pub fn allow_try(mut self, allow_try: bool) -> Self {
+        self.allow_try = Some(allow_try);
+        self
+    }
+
+    /// Set whether the header bar is shown.
+    pub fn show_header(mut self, show_header: bool) -> Self {
+        self.show_header = Some(show_header);
+        self
+    }
+
+    /// Set primary color of buttons, tabs and links, e.g. `"#3faf7c"`.
+    pub fn primary_color(mut self, primary_color: impl Into<String>) -> Self {
+        self.primary_color = Some(primary_color.into());
+        self
+    }
+
+    /// Set background color.
+    pub fn bg_color(mut self, bg_color: impl Into<String>) -> Self {
+        self.bg_color = Some(bg_color.into());
+        self
+    }
+
+    /// Set text color.
+    pub fn text_color(mut self, text_color: impl Into<String>) -> Self {
+        self.text_color = Some(text_color.into());
+        self
+    }
+
+    /// Set custom css appended to the html page.
+    pub fn custom_css(mut self, custom_css: impl Into<String>) -> Self {
+        self.custom_css = Some(custom_css.into());
+        self
+    }
+
+    /// Set custom javascript appended to the html page.
+    ///
+    /// It is written into the html page as is, so it must be trusted code.
+    pub fn custom_js(mut self, custom_js: impl Into<String>) -> Self {
+        self.custom_js = Some(custom_js.into());
+        self
+    }
+
+    /// Render the settings as attributes of the `<rapi-doc>` element.
+    pub(crate) fn to_attributes(&self) -> Result<String, serde_json::Error> {
+        let serde_json::Value::Object(map) = serde_json::to_value(self)? else {
+            return Ok(String::new());
+        };
+        let mut attributes = String::new();
+        for (name, value) in map {
+            let value = match value {
+                serde_json::Value::String(value) => value,
+                value => value.to_string(),
+            };
+            let value = value
+                .replace('&', "&amp;")
+                .replace('"', "&quot;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            attributes.push_str(&format!(" {name}=\"{value}\""));
+        }
+        Ok(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_to_attributes() {
+        let config = Config::new()
+            .theme(Theme::Dark)
+            .render_style(RenderStyle::Focused)
+            .allow_spec_file_download(false)
+            .server_url("https://api.example.com/?a=1&b=\"2\"")
+            .custom_css("body { margin: 0; }");
+        let attributes = config.to_attributes().unwrap();
+        assert!(attributes.contains(r#" theme="dark""#));
+        assert!(attributes.contains(r#" render-style="focused""#));
+        assert!(attributes.contains(r#" allow-spec-file-download="false""#));
+        assert!(attributes.contains(r#" server-url="https://api.example.com/?a=1&amp;b=&quot;2&quot;""#));
+        assert!(!attributes.contains("custom"));
+        assert!(Config::new().to_attributes().unwrap().is_empty());
+    }
+}
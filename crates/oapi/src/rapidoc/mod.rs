@@ -6,9 +6,37 @@
 //!
 use std::borrow::Cow;
 
+use bytes::Bytes;
+use salvo_core::http::StatusError;
 use salvo_core::writing::Text;
 use salvo_core::{async_trait, Depot, FlowCtrl, Handler, Request, Response, Router};
 
+use crate::asset::{self, LibAsset};
+
+/// The file name the lib is served as under the doc page when it is embedded or set by
+/// [`RapiDoc::lib_bytes`].
+const LIB_FILE_NAME: &str = "rapidoc-min.js";
+
+#[cfg(feature = "rapidoc-embed")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "src/rapidoc/v9.3.4"]
+struct RapiDocDist;
+
+/// Returns the lib embedded in the crate when the `rapidoc-embed` feature is enabled.
+fn embedded_lib() -> Option<Bytes> {
+    #[cfg(feature = "rapidoc-embed")]
+    {
+        RapiDocDist::get(LIB_FILE_NAME).map(|f| asset::embedded_bytes(f.data))
+    }
+    #[cfg(not(feature = "rapidoc-embed"))]
+    {
+        None
+    }
+}
+
+mod config;
+pub use config::{Config, Layout, RenderStyle, Theme};
+
 const INDEX_TMPL: &str = r#"
 <!doctype html>
 <html>
@@ -18,9 +46,11 @@ const INDEX_TMPL: &str = r#"
     {{description}}
     <meta charset="utf-8">
     <script type="module" src="{{lib_url}}"></script>
+    {{custom_css}}
   </head>
   <body>
-    <rapi-doc spec-url="{{spec_url}}"></rapi-doc>
+    <rapi-doc spec-url="{{spec_url}}"{{attributes}}></rapi-doc>
+    {{custom_js}}
   </body>
 </html>
 "#;
@@ -37,8 +67,13 @@ pub struct RapiDoc {
     pub description: Option<Cow<'static, str>>,
     /// The lib url path.
     pub lib_url: Cow<'static, str>,
+    /// The lib content served under the doc page instead of loading it from `lib_url`. It defaults
+    /// to the lib embedded in the crate when the `rapidoc-embed` feature is enabled.
+    pub lib_bytes: Option<Bytes>,
     /// The spec url path.
     pub spec_url: Cow<'static, str>,
+    /// The [`Config`] passed to RapiDoc.
+    pub config: Config,
 }
 impl RapiDoc {
     /// Create a new [`RapiDoc`] for given path.
@@ -58,7 +93,9 @@ impl RapiDoc {
             keywords: None,
             description: None,
             lib_url: "https://unpkg.com/rapidoc/dist/rapidoc-min.js".into(),
+            lib_bytes: embedded_lib(),
             spec_url: spec_url.into(),
+            config: Config::new(),
         }
     }
 
@@ -80,21 +117,54 @@ impl RapiDoc {
         self
     }
 
-    /// Set the lib url path.
+    /// Set the lib url path. The page loads the lib from it instead of the embedded or given lib bytes.
     pub fn lib_url(mut self, lib_url: impl Into<Cow<'static, str>>) -> Self {
         self.lib_url = lib_url.into();
+        self.lib_bytes = None;
+        self
+    }
+
+    /// Set the [`Config`] passed to RapiDoc, e.g. theme, layout and server selection.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Serve the lib from given content under the doc page instead of loading it from `lib_url`.
+    ///
+    /// This makes the doc page work in air-gapped environments without access to CDNs. Enable
+    /// the `rapidoc-embed` feature to serve the lib embedded in the crate, or use this to serve
+    /// another build of it vendored in the application.
+    ///
+    /// The lib is only served by the router returned from [`RapiDoc::into_router`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use salvo_oapi::rapidoc::RapiDoc;
+    /// let lib = std::fs::read("assets/rapidoc-min.js").expect("failed to read lib");
+    /// let doc = RapiDoc::new("/openapi.json").lib_bytes(lib);
+    /// ```
+    pub fn lib_bytes(mut self, lib_bytes: impl Into<Bytes>) -> Self {
+        self.lib_bytes = Some(lib_bytes.into());
         self
     }
 
     /// Consusmes the [`RapiDoc`] and returns [`Router`] with the [`RapiDoc`] as handler.
     pub fn into_router(self, path: impl Into<String>) -> Router {
-        Router::with_path(path.into()).goal(self)
+        let lib_bytes = self.lib_bytes.clone();
+        let router = Router::with_path(path.into()).goal(self);
+        if let Some(lib_bytes) = lib_bytes {
+            router.push(Router::with_path(LIB_FILE_NAME).goal(LibAsset(lib_bytes)))
+        } else {
+            router
+        }
     }
 }
 
 #[async_trait]
 impl Handler for RapiDoc {
-    async fn handle(&self, _req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
+    async fn handle(&self, req: &mut Request, _depot: &mut Depot, res: &mut Response, _ctrl: &mut FlowCtrl) {
         let keywords = self
             .keywords
             .as_ref()
@@ -110,12 +180,87 @@ impl Handler for RapiDoc {
             .as_ref()
             .map(|s| format!("<meta name=\"description\" content=\"{}\">", s))
             .unwrap_or_default();
+        let attributes = match self.config.to_attributes() {
+            Ok(attributes) => attributes,
+            Err(e) => {
+                tracing::error!(error = ?e, "failed to serialize rapidoc config");
+                res.render(StatusError::internal_server_error());
+                return;
+            }
+        };
+        let custom_css = self
+            .config
+            .custom_css
+            .as_ref()
+            .map(|s| format!("<style>{}</style>", s))
+            .unwrap_or_default();
+        let custom_js = self
+            .config
+            .custom_js
+            .as_ref()
+            .map(|s| format!("<script>{}</script>", s))
+            .unwrap_or_default();
+        let lib_url = if self.lib_bytes.is_some() {
+            Cow::Owned(asset::lib_url(req.uri().path(), LIB_FILE_NAME))
+        } else {
+            self.lib_url.clone()
+        };
         let html = INDEX_TMPL
             .replacen("{{spec_url}}", &self.spec_url, 1)
-            .replacen("{{lib_url}}", &self.lib_url, 1)
+            .replacen("{{attributes}}", &attributes, 1)
+            .replacen("{{lib_url}}", &lib_url, 1)
             .replacen("{{description}}", &description, 1)
             .replacen("{{keywords}}", &keywords, 1)
-            .replacen("{{title}}", &self.title, 1);
+            .replacen("{{title}}", &self.title, 1)
+            .replacen("{{custom_css}}", &custom_css, 1)
+            .replacen("{{custom_js}}", &custom_js, 1);
         res.render(Text::Html(html));
     }
 }
+
+#[cfg(all(test, feature = "rapidoc-embed"))]
+mod tests {
+    use salvo_core::http::header;
+    use salvo_core::prelude::*;
+    use salvo_core::test::{ResponseExt, TestClient};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_serve_embedded_lib() {
+        let router = Router::new().push(RapiDoc::new("/openapi.json").into_router("docs"));
+        let service = Service::new(router);
+
+        let html = TestClient::get("http://127.0.0.1:5801/docs")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert!(html.contains("src=\"docs/rapidoc-min.js\""));
+
+        let mut res = TestClient::get("http://127.0.0.1:5801/docs/rapidoc-min.js")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/javascript; charset=utf-8"
+        );
+        assert!(!res.take_bytes(None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_lib_url_overrides_embedded_lib() {
+        let doc = RapiDoc::new("/openapi.json").lib_url("https://example.com/rapidoc-min.js");
+        assert!(doc.lib_bytes.is_none());
+        let service = Service::new(Router::new().push(doc.into_router("docs")));
+        let html = TestClient::get("http://127.0.0.1:5801/docs")
+            .send(&service)
+            .await
+            .take_string()
+            .await
+            .unwrap();
+        assert!(html.contains("https://example.com/rapidoc-min.js"));
+    }
+}
@@ -0,0 +1,133 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Object used to alter ReDoc settings.
+///
+/// It is passed to `Redoc.init` as options, see
+/// [ReDoc configuration](https://redocly.com/docs/redoc/config) for more details.
+///
+/// # Examples
+///
+/// ```rust
+/// # use salvo_oapi::redoc::{Config, ReDoc};
+/// # use serde_json::json;
+/// let doc = ReDoc::new("/openapi.json").config(
+///     Config::new()
+///         .theme(json!({"colors": {"primary": {"main": "#3faf7c"}}}))
+///         .hide_download_button(true)
+///         .expand_responses("200,201"),
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Serialize, Default, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Theme object of ReDoc, e.g. colors, typography and sidebar settings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) theme: Option<Value>,
+
+    /// Whether the button to download the spec is hidden.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hide_download_button: Option<bool>,
+
+    /// Whether the protocol and hostname of the server are hidden in operation definitions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hide_hostname: Option<bool>,
+
+    /// Response codes expanded by default, e.g. `"200,201"` or `"all"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) expand_responses: Option<String>,
+
+    /// Whether the path is shown in the middle panel instead of the right panel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) path_in_middle_panel: Option<bool>,
+
+    /// Whether the native scrollbars are used instead of the custom ones.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) native_scrollbars: Option<bool>,
+
+    /// Whether the required properties are shown first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) required_props_first: Option<bool>,
+
+    /// Whether the search box is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) disable_search: Option<bool>,
+
+    /// Custom css appended to the html page.
+    #[serde(skip)]
+    pub(crate) custom_css: Option<String>,
+
+    /// Custom javascript appended to the html page.
+    #[serde(skip)]
+    pub(crate) custom_js: Option<String>,
+}
+
+impl Config {
+    /// Create a new empty [`Config`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set theme object of ReDoc.
+    pub fn theme(mut self, theme: Value) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Set whether the button to download the spec is hidden.
+    pub fn hide_download_button(mut self, hide_download_button: bool) -> Self {
+        self.hide_download_button = Some(hide_download_button);
+        self
+    }
+
+    /// Set whether the protocol and hostname of the server are hidden in operation definitions.
+    pub fn hide_hostname(mut self, hide_hostname: bool) -> Self {
+        self.hide_hostname = Some(hide_hostname);
+        self
+    }
+
+    /// Set response codes expanded by default, e.g. `"200,201"` or `"all"`.
+    pub fn expand_responses(mut self, expand_responses: impl Into<String>) -> Self {
+        self.expand_responses = Some(expand_responses.into());
+        self
+    }
+
+    /// Set whether the path is shown in the middle panel instead of the right panel.
+    pub fn path_in_middle_panel(mut self, path_in_middle_panel: bool) -> Self {
+        self.path_in_middle_panel = Some(path_in_middle_panel);
+        self
+    }
+
+    /// Set whether the native scrollbars are used instead of the custom ones.
+    pub fn native_scrollbars(mut self, native_scrollbars: bool) -> Self {
+        self.native_scrollbars = Some(native_scrollbars);
+        self
+    }
+
+    /// Set whether the required properties are shown first.
+    pub fn required_props_first(mut self, required_props_first: bool) -> Self {
+        self.required_props_first = Some(required_props_first);
+        self
+    }
+
+    /// Set whether the search box is disabled.
+    pub fn disable_search(mut self, disable_search: bool) -> Self {
+        self.disable_search = Some(disable_search);
+        self
+    }
+
+    /// Set custom css appended to the html page.
+    pub fn custom_css(mut self, custom_css: impl Into<String>) -> Self {
+        self.custom_css = Some(custom_css.into());
+        self
+    }
+
+    /// Set custom javascript appended to the html page.
+    ///
+    /// It is written into the html page as is, so it must be trusted code.
+    pub fn custom_js(mut self, custom_js: impl Into<String>) -> Self {
+        self.custom_js = Some(custom_js.into());
+        self
+    }
+}
@@ -4,8 +4,13 @@ use std::sync::{LazyLock, RwLock};
 
 use regex::Regex;
 use salvo_core::Router;
+use salvo_core::handler::AuthScheme;
+use salvo_core::http::Method;
+use salvo_core::routing::filters::{MethodFilter, PathFilter, VersionFilter};
 
-use crate::{SecurityRequirement, path::PathItemType};
+use crate::SecurityRequirement;
+use crate::path::PathItemType;
+use crate::security::{Http, HttpAuthScheme, SecurityScheme};
 
 #[derive(Debug, Default)]
 pub(crate) struct NormNode {
@@ -14,6 +19,8 @@ pub(crate) struct NormNode {
     pub(crate) handler_type_name: Option<&'static str>,
     pub(crate) method: Option<PathItemType>,
     pub(crate) path: Option<String>,
+    pub(crate) path_patterns: Vec<(String, String)>,
+    pub(crate) version: Option<String>,
     pub(crate) children: Vec<NormNode>,
     pub(crate) metadata: Metadata,
 }
@@ -34,46 +41,171 @@ impl NormNode {
                 .securities
                 .extend(metadata.securities.iter().cloned());
         }
+        for hoop in router.hoops() {
+            if let Some((name, scheme)) = auth_security_scheme(hoop.auth_scheme()) {
+                let security = SecurityRequirement::new(name, Vec::<String>::new());
+                if !node.metadata.securities.contains(&security) {
+                    node.metadata.securities.push(security);
+                }
+                if !node
+                    .metadata
+                    .security_schemes
+                    .iter()
+                    .any(|(n, _)| n == name)
+                {
+                    node.metadata
+                        .security_schemes
+                        .push((name.to_owned(), scheme));
+                }
+            }
+        }
 
         let regex = Regex::new(r#"<([^/:>]+)(:[^>]*)?>"#).expect("invalid regex");
+        let mut version_path = None;
         for filter in router.filters() {
-            let info = format!("{filter:?}");
-            if info.starts_with("path:") {
-                let path = info
-                    .split_once(':')
-                    .expect("split once by ':' should not be get `None`")
-                    .1;
-                node.path = Some(regex.replace_all(path, "{$1}").to_string());
-            } else if info.starts_with("method:") {
-                match info
-                    .split_once(':')
-                    .expect("split once by ':' should not be get `None`.")
-                    .1
-                {
-                    "GET" => node.method = Some(PathItemType::Get),
-                    "POST" => node.method = Some(PathItemType::Post),
-                    "PUT" => node.method = Some(PathItemType::Put),
-                    "DELETE" => node.method = Some(PathItemType::Delete),
-                    "HEAD" => node.method = Some(PathItemType::Head),
-                    "OPTIONS" => node.method = Some(PathItemType::Options),
-                    "CONNECT" => node.method = Some(PathItemType::Connect),
-                    "TRACE" => node.method = Some(PathItemType::Trace),
-                    "PATCH" => node.method = Some(PathItemType::Patch),
+            if let Some(filter) = filter.downcast_ref::<PathFilter>() {
+                node.path_patterns.extend(
+                    filter
+                        .param_patterns()
+                        .into_iter()
+                        .map(|(name, pattern)| (name.to_owned(), pattern.to_owned())),
+                );
+                node.path = Some(normalize_path(
+                    &regex.replace_all(filter.raw_value(), "{$1}"),
+                ));
+            } else if let Some(filter) = filter.downcast_ref::<VersionFilter>() {
+                if let Some(prefix) = &filter.path_prefix {
+                    version_path = Some(format!("{prefix}{}", filter.version));
+                }
+                node.version = Some(filter.version.clone());
+            } else if let Some(filter) = filter.downcast_ref::<MethodFilter>() {
+                match *filter.method() {
+                    Method::GET => node.method = Some(PathItemType::Get),
+                    Method::POST => node.method = Some(PathItemType::Post),
+                    Method::PUT => node.method = Some(PathItemType::Put),
+                    Method::DELETE => node.method = Some(PathItemType::Delete),
+                    Method::HEAD => node.method = Some(PathItemType::Head),
+                    Method::OPTIONS => node.method = Some(PathItemType::Options),
+                    Method::CONNECT => node.method = Some(PathItemType::Connect),
+                    Method::TRACE => node.method = Some(PathItemType::Trace),
+                    Method::PATCH => node.method = Some(PathItemType::Patch),
                     _ => {}
                 }
             }
         }
+        if let Some(version_path) = version_path {
+            node.path = Some(match node.path.take() {
+                Some(path) => format!("{}/{}", version_path, path.trim_start_matches('/')),
+                None => version_path,
+            });
+        }
         node.handler_type_id = router.goal.as_ref().map(|h| h.type_id());
         node.handler_type_name = router.goal.as_ref().map(|h| h.type_name());
         let routers = router.routers();
         if !routers.is_empty() {
             for router in routers {
-                node.children
-                    .push(NormNode::new(router, node.metadata.clone()));
+                let mut child = NormNode::new(router, node.metadata.clone());
+                child.inherit_path_patterns(&node.path_patterns);
+                node.children.push(child);
             }
         }
         node
     }
+
+    /// Prepend the path patterns of the parent to this node and all its descendants.
+    fn inherit_path_patterns(&mut self, patterns: &[(String, String)]) {
+        self.path_patterns.splice(0..0, patterns.iter().cloned());
+        for child in &mut self.children {
+            child.inherit_path_patterns(patterns);
+        }
+    }
+
+    /// Remove the children which are not belong to the API version.
+    pub(crate) fn retain_version(&mut self, version: &str) {
+        self.children
+            .retain(|child| child.version.as_deref().is_none_or(|v| v == version));
+        for child in &mut self.children {
+            child.retain_version(version);
+        }
+    }
+}
+
+/// Remove the constraints of the path parameters, `{id|\d+}`, `{id:/\d+/}` and `{id:num}` are all
+/// normalized to `{id}`, and named wildcards like `{**rest}` are normalized to `{rest}`.
+fn normalize_path(path: &str) -> String {
+    let mut normalized = String::with_capacity(path.len());
+    let mut chars = path.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' | '}' if chars.peek() == Some(&ch) => {
+                chars.next();
+                normalized.push(ch);
+                normalized.push(ch);
+            }
+            '{' => {
+                let mut spec = String::new();
+                let mut depth = 1;
+                let mut slashed = false;
+                let mut escaping = false;
+                for c in chars.by_ref() {
+                    if slashed {
+                        if escaping {
+                            escaping = false;
+                        } else if c == '\\' {
+                            escaping = true;
+                        } else if c == '/' {
+                            slashed = false;
+                        }
+                        continue;
+                    }
+                    if c == '/' && spec.ends_with(':') {
+                        slashed = true;
+                    } else if c == '{' {
+                        depth += 1;
+                    } else if c == '}' {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    spec.push(c);
+                }
+                let mut name = spec.split([':', '|']).next().unwrap_or_default().trim();
+                // Named wildcards like `{**rest}` are extracted as `rest`.
+                let wild_name = name.trim_start_matches(['*', '+', '?']);
+                if name.starts_with('*') && !wild_name.is_empty() {
+                    name = wild_name;
+                }
+                normalized.push('{');
+                normalized.push_str(name);
+                normalized.push('}');
+            }
+            _ => normalized.push(ch),
+        }
+    }
+    normalized
+}
+
+/// Returns the security scheme of the authentication middleware, so the endpoints under the router with
+/// a hoop returning [`Handler::auth_scheme`], such as `JwtAuth` or `BasicAuth`, will require `bearerAuth`
+/// or `basicAuth` security automatically.
+///
+/// [`Handler::auth_scheme`]: salvo_core::Handler::auth_scheme
+fn auth_security_scheme(scheme: Option<AuthScheme>) -> Option<(&'static str, SecurityScheme)> {
+    match scheme? {
+        AuthScheme::Basic => Some((
+            "basicAuth",
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Basic)),
+        )),
+        AuthScheme::Bearer(format) => {
+            let mut http = Http::new(HttpAuthScheme::Bearer);
+            if let Some(format) = format {
+                http = http.bearer_format(format);
+            }
+            Some(("bearerAuth", SecurityScheme::Http(http)))
+        }
+        _ => None,
+    }
 }
 
 /// A component for save router metadata.
@@ -81,6 +213,12 @@ type MetadataMap = RwLock<HashMap<usize, Metadata>>;
 static METADATA_REGISTRY: LazyLock<MetadataMap> = LazyLock::new(MetadataMap::default);
 
 /// Router extension trait for openapi metadata.
+///
+/// The endpoints under a router with an authentication hoop, such as `JwtAuth` or `BasicAuth`, inherit the
+/// `bearerAuth` or `basicAuth` security requirement automatically, and the security scheme is added to
+/// components if it is not declared. A custom middleware opts in by implementing [`Handler::auth_scheme`].
+///
+/// [`Handler::auth_scheme`]: salvo_core::Handler::auth_scheme
 pub trait RouterExt {
     /// Add security requirement to the router.
     ///
@@ -155,4 +293,21 @@ impl RouterExt for Router {
 pub(crate) struct Metadata {
     pub(crate) tags: BTreeSet<String>,
     pub(crate) securities: Vec<SecurityRequirement>,
+    pub(crate) security_schemes: Vec<(String, SecurityScheme)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(r"users/{id|\d+}/posts/{slug:/[a-z]+/}"),
+            "users/{id}/posts/{slug}"
+        );
+        assert_eq!(normalize_path("files/{**rest}"), "files/{rest}");
+        assert_eq!(normalize_path("files/{*+rest}"), "files/{rest}");
+        assert_eq!(normalize_path("files/{**}"), "files/{**}");
+    }
 }
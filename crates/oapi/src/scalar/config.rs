@@ -0,0 +1,190 @@
+use serde::Serialize;
+
+/// Color theme of Scalar.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Theme {
+    /// The default theme.
+    Default,
+    /// The alternate theme.
+    Alternate,
+    /// The moon theme.
+    Moon,
+    /// The purple theme.
+    Purple,
+    /// The solarized theme.
+    Solarized,
+    /// The blue planet theme.
+    BluePlanet,
+    /// The saturn theme.
+    Saturn,
+    /// The kepler theme.
+    Kepler,
+    /// The mars theme.
+    Mars,
+    /// The deep space theme.
+    DeepSpace,
+    /// No theme, useful when the page is fully styled with custom css.
+    None,
+}
+
+/// Layout of Scalar.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum Layout {
+    /// Modern layout with sidebar.
+    Modern,
+    /// Classic layout with operations listed in one page.
+    Classic,
+}
+
+/// A server which can be selected to send requests to in Scalar.
+#[derive(Serialize, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub struct Server {
+    /// Url of the server.
+    pub url: String,
+    /// Description of the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+impl Server {
+    /// Create a new [`Server`] with given url.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            description: None,
+        }
+    }
+
+    /// Set description of the server.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// Object used to alter Scalar settings.
+///
+/// See [Scalar configuration](https://github.com/scalar/scalar/blob/main/documentation/configuration.md)
+/// for more details.
+///
+/// # Examples
+///
+/// ```rust
+/// # use salvo_oapi::scalar::{Config, Layout, Scalar, Server, Theme};
+/// let doc = Scalar::new("/openapi.json").config(
+///     Config::new()
+///         .theme(Theme::Purple)
+///         .layout(Layout::Classic)
+///         .hide_download_button(true)
+///         .add_server(Server::new("https://api.example.com").description("Production")),
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Serialize, Default, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    /// Color theme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) theme: Option<Theme>,
+
+    /// Layout of the page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) layout: Option<Layout>,
+
+    /// Whether the page is in dark mode initially.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) dark_mode: Option<bool>,
+
+    /// Whether the button to download the spec is hidden.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hide_download_button: Option<bool>,
+
+    /// Whether the models section is hidden.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hide_models: Option<bool>,
+
+    /// Whether the sidebar is shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) show_sidebar: Option<bool>,
+
+    /// Servers which can be selected to send requests to, overriding the servers of the spec.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) servers: Vec<Server>,
+
+    /// Custom css injected into the page by Scalar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) custom_css: Option<String>,
+
+    /// Custom javascript appended to the html page.
+    #[serde(skip)]
+    pub(crate) custom_js: Option<String>,
+}
+
+impl Config {
+    /// Create a new empty [`Config`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set color theme.
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Set layout of the page.
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Set whether the page is in dark mode initially.
+    pub fn dark_mode(mut self, dark_mode: bool) -> Self {
+        self.dark_mode = Some(dark_mode);
+        self
+    }
+
+    /// Set whether the button to download the spec is hidden.
+    pub fn hide_download_button(mut self, hide_download_button: bool) -> Self {
+        self.hide_download_button = Some(hide_download_button);
+        self
+    }
+
+    /// Set whether the models section is hidden.
+    pub fn hide_models(mut self, hide_models: bool) -> Self {
+        self.hide_models = Some(hide_models);
+        self
+    }
+
+    /// Set whether the sidebar is shown.
+    pub fn show_sidebar(mut self, show_sidebar: bool) -> Self {
+        self.show_sidebar = Some(show_sidebar);
+        self
+    }
+
+    /// Add a [`Server`] which can be selected to send requests to.
+    ///
+    /// Once any server is added, the servers of the spec are not listed any more.
+    pub fn add_server(mut self, server: Server) -> Self {
+        self.servers.push(server);
+        self
+    }
+
+    /// Set custom css injected into the page by Scalar.
+    pub fn custom_css(mut self, custom_css: impl Into<String>) -> Self {
+        self.custom_css = Some(custom_css.into());
+        self
+    }
+
+    /// Set custom javascript appended to the html page.
+    ///
+    /// It is written into the html page as is, so it must be trusted code.
+    pub fn custom_js(mut self, custom_js: impl Into<String>) -> Self {
+        self.custom_js = Some(custom_js.into());
+        self
+    }
+}
@@ -156,6 +156,14 @@ pub struct Config<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) persist_authorization: Option<bool>,
 
+    /// Javascript function used to modify "Try it out" requests before they are sent.
+    #[serde(skip)]
+    pub(crate) request_interceptor: Option<String>,
+
+    /// Javascript function used to modify responses of "Try it out" requests before they are rendered.
+    #[serde(skip)]
+    pub(crate) response_interceptor: Option<String>,
+
     /// [`oauth::Config`] the Swagger UI is using for auth flow.
     #[serde(skip)]
     pub(crate) oauth: Option<oauth::Config>,
@@ -694,6 +702,43 @@ impl<'a> Config<'a> {
 
         self
     }
+
+    /// Set a javascript function used as `requestInterceptor` of Swagger UI.
+    ///
+    /// The function receives the request of "Try it out" and must return the request (or a promise
+    /// of it) which will be sent. It is written into the html page as is, so it must be trusted code.
+    ///
+    /// # Examples
+    ///
+    /// Add a header to every request sent from Swagger UI.
+    /// ```rust
+    /// # use salvo_oapi::swagger_ui::Config;
+    /// let config = Config::new(["/api-docs/openapi.json"])
+    ///     .request_interceptor("(req) => { req.headers['X-Client'] = 'swagger-ui'; return req; }");
+    /// ```
+    pub fn request_interceptor<S: Into<String>>(mut self, request_interceptor: S) -> Self {
+        self.request_interceptor = Some(request_interceptor.into());
+
+        self
+    }
+
+    /// Set a javascript function used as `responseInterceptor` of Swagger UI.
+    ///
+    /// The function receives the response of "Try it out" and must return the response (or a promise
+    /// of it) which will be rendered. It is written into the html page as is, so it must be trusted code.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use salvo_oapi::swagger_ui::Config;
+    /// let config = Config::new(["/api-docs/openapi.json"])
+    ///     .response_interceptor("(res) => { console.log(res.status); return res; }");
+    /// ```
+    pub fn response_interceptor<S: Into<String>>(mut self, response_interceptor: S) -> Self {
+        self.response_interceptor = Some(response_interceptor.into());
+
+        self
+    }
 }
 
 impl Default for Config<'_> {
@@ -724,6 +769,8 @@ impl Default for Config<'_> {
             validator_url: Default::default(),
             with_credentials: Default::default(),
             persist_authorization: Default::default(),
+            request_interceptor: Default::default(),
+            response_interceptor: Default::default(),
             oauth: Default::default(),
             layout: SWAGGER_STANDALONE_LAYOUT,
         }
@@ -66,7 +66,9 @@ const INDEX_TMPL: &str = r#"
             ],
             layout: "StandaloneLayout"
           };
-        window.ui = SwaggerUIBundle(Object.assign(config, {{config}}));
+        Object.assign(config, {{config}});
+        //{{interceptors}}
+        window.ui = SwaggerUIBundle(config);
         //{{oauth}}
     };
     </script>
@@ -75,6 +77,9 @@ const INDEX_TMPL: &str = r#"
 "#;
 
 /// Implements [`Handler`] for serving Swagger UI.
+///
+/// The Swagger UI assets are embedded in the crate and served under the doc page, so it works
+/// without access to CDNs.
 #[derive(Clone, Debug)]
 pub struct SwaggerUi {
     config: Config<'static>,
@@ -193,6 +198,82 @@ impl SwaggerUi {
         self
     }
 
+    /// Set oauth redirect url of Swagger UI.
+    ///
+    /// See [`Config::oauth2_redirect_url`] for more details.
+    pub fn oauth2_redirect_url(mut self, oauth2_redirect_url: impl Into<String>) -> Self {
+        self.config.oauth2_redirect_url = Some(oauth2_redirect_url.into());
+        self
+    }
+
+    /// Set whether authorizations are persisted throughout browser refresh and close.
+    ///
+    /// See [`Config::persist_authorization`] for more details.
+    pub fn persist_authorization(mut self, persist_authorization: bool) -> Self {
+        self.config.persist_authorization = Some(persist_authorization);
+        self
+    }
+
+    /// Set whether "Try it out" section is enabled by default.
+    ///
+    /// See [`Config::try_it_out_enabled`] for more details.
+    pub fn try_it_out_enabled(mut self, try_it_out_enabled: bool) -> Self {
+        self.config.try_it_out_enabled = Some(try_it_out_enabled);
+        self
+    }
+
+    /// Set whether credentials are passed to CORS requests.
+    ///
+    /// See [`Config::with_credentials`] for more details.
+    pub fn with_credentials(mut self, with_credentials: bool) -> Self {
+        self.config.with_credentials = Some(with_credentials);
+        self
+    }
+
+    /// Set default models expansion depth, -1 will completely hide the models.
+    ///
+    /// See [`Config::default_models_expand_depth`] for more details.
+    pub fn default_models_expand_depth(mut self, default_models_expand_depth: isize) -> Self {
+        self.config.default_models_expand_depth = Some(default_models_expand_depth);
+        self
+    }
+
+    /// Set default expansion for operations and tags, one of `"list"`, `"full"` or `"none"`.
+    ///
+    /// See [`Config::doc_expansion`] for more details.
+    pub fn doc_expansion(mut self, doc_expansion: impl Into<String>) -> Self {
+        self.config.doc_expansion = Some(doc_expansion.into());
+        self
+    }
+
+    /// Set a javascript function used as `requestInterceptor` of Swagger UI.
+    ///
+    /// See [`Config::request_interceptor`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// Send an api key with every "Try it out" request.
+    /// ```rust
+    /// # use salvo_oapi::swagger_ui::{SwaggerUi, oauth};
+    /// let swagger = SwaggerUi::new("/api-docs/openapi.json")
+    ///     .oauth2_redirect_url("http://localhost:8698/swagger-ui/oauth2-redirect.html")
+    ///     .oauth(oauth::Config::new().client_id("client-id").use_pkce_with_authorization_code_grant(true))
+    ///     .persist_authorization(true)
+    ///     .request_interceptor("(req) => { req.headers['X-Api-Key'] = 'demo'; return req; }");
+    /// ```
+    pub fn request_interceptor(mut self, request_interceptor: impl Into<String>) -> Self {
+        self.config.request_interceptor = Some(request_interceptor.into());
+        self
+    }
+
+    /// Set a javascript function used as `responseInterceptor` of Swagger UI.
+    ///
+    /// See [`Config::response_interceptor`] for more details.
+    pub fn response_interceptor(mut self, response_interceptor: impl Into<String>) -> Self {
+        self.config.response_interceptor = Some(response_interceptor.into());
+        self
+    }
+
     /// Consusmes the [`SwaggerUi`] and returns [`Router`] with the [`SwaggerUi`] as handler.
     pub fn into_router(self, path: impl Into<String>) -> Router {
         Router::with_path(format!("{}/{{**}}", path.into())).goal(self)
@@ -404,6 +485,17 @@ pub fn serve<'a>(
             .replacen("{{keywords}}", keywords, 1)
             .replacen("{{title}}", title, 1);
 
+        let mut interceptors = Vec::new();
+        if let Some(request_interceptor) = &config.request_interceptor {
+            interceptors.push(format!("config.requestInterceptor = {};", request_interceptor));
+        }
+        if let Some(response_interceptor) = &config.response_interceptor {
+            interceptors.push(format!("config.responseInterceptor = {};", response_interceptor));
+        }
+        if !interceptors.is_empty() {
+            index = index.replace("//{{interceptors}}", &interceptors.join("\n        "));
+        }
+
         if let Some(oauth) = &config.oauth {
             let oauth_json = serde_json::to_string(oauth)?;
             index = index.replace("//{{oauth}}", &format!("window.ui.initOAuth({});", &oauth_json));
@@ -419,3 +511,25 @@ pub fn serve<'a>(
 
     Ok(file)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serve_index_with_interceptors() {
+        let config = Config::new(["/api-docs/openapi.json"])
+            .oauth2_redirect_url("http://localhost/oauth2-redirect.html")
+            .persist_authorization(true)
+            .request_interceptor("(req) => req")
+            .response_interceptor("(res) => res");
+        let file = serve("", "Swagger UI", "", "", &config).unwrap().unwrap();
+        let index = String::from_utf8(file.bytes.to_vec()).unwrap();
+
+        assert!(index.contains(r#""oauth2RedirectUrl":"http://localhost/oauth2-redirect.html""#));
+        assert!(index.contains(r#""persistAuthorization":true"#));
+        assert!(index.contains("config.requestInterceptor = (req) => req;"));
+        assert!(index.contains("config.responseInterceptor = (res) => res;"));
+        assert!(!index.contains("requestInterceptor\":"));
+    }
+}
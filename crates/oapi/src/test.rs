@@ -0,0 +1,169 @@
+//! Utilities for testing generated [`OpenApi`] documents.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # use salvo_core::prelude::*;
+//! # use salvo_oapi::OpenApi;
+//! # use salvo_oapi::test::assert_snapshot;
+//! #[salvo_oapi::endpoint]
+//! async fn hello() -> &'static str {
+//!     "Hello"
+//! }
+//!
+//! let router = Router::new().push(Router::with_path("hello").get(hello));
+//! let doc = OpenApi::new("test api", "0.0.1").merge_router(&router);
+//! assert_snapshot(&doc, "tests/snapshots/openapi.json");
+//! ```
+use std::fmt::Write;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::OpenApi;
+
+/// Set this environment variable to `1` to write the current documents into the snapshot files
+/// instead of comparing with them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "SALVO_UPDATE_SNAPSHOTS";
+
+impl OpenApi {
+    /// Converts this [`OpenApi`] to pretty JSON String with sorted object keys.
+    ///
+    /// The output does not depend on the insertion order of paths, components and properties, so
+    /// it is stable across builds and suitable for snapshot testing.
+    pub fn to_snapshot(&self) -> Result<String, serde_json::Error> {
+        let value = sort_keys(serde_json::to_value(self)?);
+        let mut snapshot = serde_json::to_string_pretty(&value)?;
+        snapshot.push('\n');
+        Ok(snapshot)
+    }
+}
+
+/// Asserts the [`OpenApi`] document is the same as the snapshot stored in file at `path`.
+///
+/// The snapshot is created by [`OpenApi::to_snapshot`]. If the file does not exist or
+/// [`UPDATE_SNAPSHOTS_ENV`] is set to `1`, the snapshot file is written and the assertion passes.
+///
+/// # Panics
+///
+/// Panics with a line based diff if the document differs from the snapshot.
+#[track_caller]
+pub fn assert_snapshot(doc: &OpenApi, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let actual = doc
+        .to_snapshot()
+        .expect("failed to serialize openapi document");
+    let update = std::env::var(UPDATE_SNAPSHOTS_ENV).is_ok_and(|value| value == "1");
+    if update || !path.exists() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("failed to create snapshot directory");
+        }
+        std::fs::write(path, &actual).expect("failed to write snapshot file");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).expect("failed to read snapshot file");
+    let expected = expected.replace("\r\n", "\n");
+    if expected != actual {
+        panic!(
+            "openapi document does not match snapshot `{}`, set `{UPDATE_SNAPSHOTS_ENV}=1` to update it:\n{}",
+            path.display(),
+            diff_lines(&expected, &actual)
+        );
+    }
+}
+
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        value => value,
+    }
+}
+
+/// Renders the changed lines between `expected` and `actual` with a few lines of context.
+fn diff_lines(expected: &str, actual: &str) -> String {
+    const CONTEXT: usize = 3;
+
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut diff = String::new();
+    let start = prefix.saturating_sub(CONTEXT);
+    let _ = writeln!(diff, "@@ line {} @@", start + 1);
+    for line in &expected[start..prefix] {
+        let _ = writeln!(diff, "  {line}");
+    }
+    for line in &expected[prefix..expected.len() - suffix] {
+        let _ = writeln!(diff, "- {line}");
+    }
+    for line in &actual[prefix..actual.len() - suffix] {
+        let _ = writeln!(diff, "+ {line}");
+    }
+    let end = (actual.len() - suffix + CONTEXT).min(actual.len());
+    for line in &actual[actual.len() - suffix..end] {
+        let _ = writeln!(diff, "  {line}");
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BasicType, Components, Object};
+
+    #[test]
+    fn test_snapshot_sorts_keys() {
+        let doc = OpenApi::new("test api", "0.0.1").components(
+            Components::new()
+                .add_schema("Zoo", Object::with_type(BasicType::String))
+                .add_schema("Ant", Object::with_type(BasicType::Integer)),
+        );
+        let snapshot = doc.to_snapshot().unwrap();
+        assert!(snapshot.find("\"Ant\"").unwrap() < snapshot.find("\"Zoo\"").unwrap());
+        assert!(snapshot.find("\"components\"").unwrap() < snapshot.find("\"info\"").unwrap());
+        assert!(snapshot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = diff_lines("a\nb\nc\nd\n", "a\nb\nx\nd\n");
+        assert_eq!(diff, "@@ line 1 @@\n  a\n  b\n- c\n+ x\n  d\n");
+    }
+
+    #[test]
+    fn test_assert_snapshot() {
+        let path =
+            std::env::temp_dir().join(format!("salvo-oapi-snapshot-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let doc = OpenApi::new("test api", "0.0.1");
+        assert_snapshot(&doc, &path);
+        assert_snapshot(&doc, &path);
+
+        let result = std::panic::catch_unwind(|| {
+            assert_snapshot(&OpenApi::new("test api", "0.0.2"), &path);
+        });
+        let _ = std::fs::remove_file(&path);
+        assert!(result.is_err());
+    }
+}
@@ -57,6 +57,12 @@ impl Handler for Metrics {
             trace::HTTP_REQUEST_METHOD,
             req.method().to_string(),
         ));
+        // Label by the matched route pattern to avoid unbounded cardinality, unmatched requests have no route.
+        #[cfg(feature = "matched-path")]
+        if let Some(route) = req.matched_route() {
+            labels.push(KeyValue::new(trace::HTTP_ROUTE, route));
+        }
+        #[cfg(not(feature = "matched-path"))]
         labels.push(KeyValue::new(trace::URL_FULL, req.uri().to_string()));
 
         let s = Instant::now();
@@ -1,11 +1,33 @@
-use opentelemetry::trace::{FutureExt, Span, SpanKind, TraceContextExt, Tracer};
+use opentelemetry::trace::{FutureExt, Span, SpanKind, Status, TraceContextExt, Tracer};
 use opentelemetry::{Context, KeyValue, global};
 use opentelemetry_http::HeaderExtractor;
 use opentelemetry_semantic_conventions::{resource, trace};
+use salvo_core::http::ResBody;
+use salvo_core::http::header::{HOST, USER_AGENT};
 use salvo_core::http::headers::{self, HeaderMap, HeaderMapExt, HeaderName, HeaderValue};
 use salvo_core::prelude::*;
 
 /// Middleware for tracing with OpenTelemetry.
+///
+/// A server span is started for every request following the HTTP semantic conventions. The
+/// parent context and the baggage are extracted from the request headers by the global text map
+/// propagator, e.g. W3C `traceparent` and `baggage` when it is set to:
+///
+/// ```ignore
+/// use opentelemetry::propagation::TextMapCompositePropagator;
+/// use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+///
+/// opentelemetry::global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
+///     Box::new(TraceContextPropagator::new()),
+///     Box::new(BaggagePropagator::new()),
+/// ]));
+/// ```
+///
+/// The span is the current context while the following handlers are running, so the `otel`
+/// feature of `salvo-proxy` injects it into the proxied requests.
+///
+/// Responses with server error status mark the span as error, and the error of the response
+/// body is recorded as an `exception` event.
 pub struct Tracing<T> {
     tracer: T,
 }
@@ -30,7 +52,10 @@ where
         res: &mut Response,
         ctrl: &mut FlowCtrl,
     ) {
-        let remote_addr = req.remote_addr().to_string();
+        let client_addr = req
+            .client_ip()
+            .map(|ip| ip.to_string())
+            .unwrap_or_else(|| req.remote_addr().to_string());
 
         //TODO: Will remove after opentelemetry_http updated
         let mut headers = HeaderMap::with_capacity(req.headers().len());
@@ -59,14 +84,37 @@ where
             req.method().to_string(),
         ));
         attributes.push(KeyValue::new(trace::URL_FULL, req.uri().to_string()));
-        attributes.push(KeyValue::new(trace::CLIENT_ADDRESS, remote_addr));
+        attributes.push(KeyValue::new(trace::URL_PATH, req.uri().path().to_owned()));
+        if let Some(query) = req.uri().query() {
+            attributes.push(KeyValue::new(trace::URL_QUERY, query.to_owned()));
+        }
+        attributes.push(KeyValue::new(trace::URL_SCHEME, req.scheme().to_string()));
+        if let Some(host) = req.header::<String>(HOST) {
+            attributes.push(KeyValue::new(trace::SERVER_ADDRESS, host));
+        }
+        if let Some(user_agent) = req.header::<String>(USER_AGENT) {
+            attributes.push(KeyValue::new(trace::USER_AGENT_ORIGINAL, user_agent));
+        }
+        // Unmatched requests have no route, the span name is only the method as the semantic conventions suggest.
+        #[cfg(feature = "matched-path")]
+        let span_name = match req.matched_route() {
+            Some(route) => {
+                let span_name = format!("{} {}", req.method(), route);
+                attributes.push(KeyValue::new(trace::HTTP_ROUTE, route));
+                span_name
+            }
+            None => req.method().to_string(),
+        };
+        #[cfg(not(feature = "matched-path"))]
+        let span_name = format!("{} {}", req.method(), req.uri());
+        attributes.push(KeyValue::new(trace::CLIENT_ADDRESS, client_addr));
         attributes.push(KeyValue::new(
             trace::NETWORK_PROTOCOL_VERSION,
             format!("{:?}", req.version()),
         ));
         let mut span = self
             .tracer
-            .span_builder(format!("{} {}", req.method(), req.uri()))
+            .span_builder(span_name)
             .with_kind(SpanKind::Server)
             .with_attributes(attributes)
             .start_with_context(&self.tracer, &parent_cx);
@@ -92,6 +140,23 @@ where
                 trace::HTTP_RESPONSE_STATUS_CODE,
                 status.as_u16() as i64,
             ));
+            if status.is_server_error() {
+                span.set_attribute(KeyValue::new(trace::ERROR_TYPE, status.as_str().to_owned()));
+                span.set_status(Status::error(status.to_string()));
+            }
+            if let ResBody::Error(e) = &res.body {
+                let message = match &e.cause {
+                    Some(cause) => format!("{e}: {cause}"),
+                    None => e.to_string(),
+                };
+                span.add_event(
+                    "exception".to_string(),
+                    vec![
+                        KeyValue::new(trace::EXCEPTION_TYPE, "StatusError"),
+                        KeyValue::new(trace::EXCEPTION_MESSAGE, message),
+                    ],
+                );
+            }
             if let Some(content_length) = res.headers().typed_get::<headers::ContentLength>() {
                 span.set_attribute(KeyValue::new(
                     "http.response.header.content-length",
@@ -99,7 +164,7 @@ where
                 ));
             }
         }
-        .with_context(Context::current_with_span(span))
+        .with_context(parent_cx.with_span(span))
         .await
     }
 }
@@ -34,6 +34,9 @@
 //!     Server::new(acceptor).serve(router).await;
 //! }
 //! ```
+//!
+//! With the `otel` feature, the current OpenTelemetry context, e.g. the server span started by
+//! `salvo_otel::Tracing`, is injected into the headers of the proxied requests.
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
 #![doc(html_logo_url = "https://salvo.rs/images/logo.svg")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -74,6 +77,27 @@ pub(crate) fn encode_url_path(path: &str) -> String {
         .join("/")
 }
 
+/// Injects the current OpenTelemetry context into the headers of the proxied request by the
+/// global text map propagator, replacing the propagation headers of the incoming request.
+#[cfg(feature = "otel")]
+fn inject_otel_context(headers: &mut HeaderMap) {
+    struct HeaderInjector<'a>(&'a mut HeaderMap);
+    impl opentelemetry::propagation::Injector for HeaderInjector<'_> {
+        fn set(&mut self, key: &str, value: String) {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(key.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                self.0.insert(name, value);
+            }
+        }
+    }
+    let cx = opentelemetry::Context::current();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&cx, &mut HeaderInjector(headers));
+    });
+}
+
 /// Client trait for implementing different HTTP clients for proxying.
 ///
 /// Implement this trait to create custom proxy clients with different
@@ -146,10 +170,11 @@ pub type UrlPartGetter = Box<dyn Fn(&Request, &Depot) -> Option<String> + Send +
 
 /// Default url path getter.
 ///
-/// This getter will get the last param as the rest url path from request.
+/// This getter will get the raw rest url path captured by the wildcard param from request,
+/// the percent-encoded characters are kept as they are in the request uri.
 /// In most case you should use wildcard param, like `{**rest}`, `{*+rest}`.
 pub fn default_url_path_getter(req: &Request, _depot: &Depot) -> Option<String> {
-    req.params().tail().map(encode_url_path)
+    req.raw_tail()
 }
 /// Default url query getter. This getter just return the query string from request uri.
 pub fn default_url_query_getter(req: &Request, _depot: &Depot) -> Option<String> {
@@ -157,6 +182,9 @@ pub fn default_url_query_getter(req: &Request, _depot: &Depot) -> Option<String>
 }
 
 /// Handler that can proxy request to other server.
+///
+/// If the matched router has a [timeout](salvo_core::Router::timeout), the upstream request is aborted when
+/// the request deadline is reached and a `504 Gateway Timeout` is responded.
 #[non_exhaustive]
 pub struct Proxy<U, C>
 where
@@ -277,6 +305,10 @@ where
         {
             build = build.header(HeaderName::from_static("host"), host);
         }
+        #[cfg(feature = "otel")]
+        if let Some(headers) = build.headers_mut() {
+            inject_otel_context(headers);
+        }
         // let x_forwarded_for_header_name = "x-forwarded-for";
         // // Add forwarding information in the headers
         // match request.headers_mut().entry(x_forwarded_for_header_name) {
@@ -315,11 +347,22 @@ where
     ) {
         match self.build_proxied_request(req, depot).await {
             Ok(proxied_request) => {
-                match self
+                let execute = self
                     .client
-                    .execute(proxied_request, req.extensions_mut().remove())
-                    .await
-                {
+                    .execute(proxied_request, req.extensions_mut().remove());
+                let result = if let Some(deadline) = req.deadline() {
+                    match tokio::time::timeout_at(deadline.into(), execute).await {
+                        Ok(result) => result,
+                        Err(_) => {
+                            tracing::error!(uri = ?req.uri(), "proxied request timeout");
+                            res.status_code(StatusCode::GATEWAY_TIMEOUT);
+                            return;
+                        }
+                    }
+                } else {
+                    execute.await
+                };
+                match result {
                     Ok(response) => {
                         let (
                             salvo_core::http::response::Parts {
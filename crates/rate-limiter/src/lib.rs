@@ -4,7 +4,9 @@
 //! from a particular IP or id within a time period.
 //!
 //! [`RateIssuer`] is used to issue a key to request, your can define your custom `RateIssuer`.
-//! If you want just identify user by IP address, you can use [`RemoteIpIssuer`].
+//! If you want just identify user by IP address, you can use [`RemoteIpIssuer`], it uses the client
+//! IP resolved by the [`RealIp`](salvo_core::http::RealIp) of the service when the server is
+//! behind trusted proxies.
 //!
 //! [`QuotaGetter`] is used to get quota for every key.
 //!
@@ -19,7 +21,6 @@ use std::borrow::Borrow;
 use std::error::Error as StdError;
 use std::hash::Hash;
 
-use salvo_core::conn::SocketAddr;
 use salvo_core::handler::{Skipper, none_skipper};
 use salvo_core::http::{HeaderValue, Request, Response, StatusCode, StatusError};
 use salvo_core::{Depot, FlowCtrl, Handler, async_trait};
@@ -72,16 +73,12 @@ where
     }
 }
 
-/// Identify user by IP address.
+/// Identify user by client IP address, see [`Request::client_ip`].
 pub struct RemoteIpIssuer;
 impl RateIssuer for RemoteIpIssuer {
     type Key = String;
     async fn issue(&self, req: &mut Request, _depot: &Depot) -> Option<Self::Key> {
-        match req.remote_addr() {
-            SocketAddr::IPv4(addr) => Some(addr.ip().to_string()),
-            SocketAddr::IPv6(addr) => Some(addr.ip().to_string()),
-            _ => None,
-        }
+        req.client_ip().map(|ip| ip.to_string())
     }
 }
 
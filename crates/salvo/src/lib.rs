@@ -15,25 +15,45 @@
 //! | `quinn` | Use [quinn](https://crates.io/crates/quinn) to support HTTP 3 protocol | ❌ |
 //! | `test` | Utilities for testing application | ✔️ |
 //! | `acme` | Automatically obtain certificates through ACME | ❌ |
+//! | `acme-cloudflare` | Cloudflare DNS provider for ACME `DNS-01` challenge | ❌ |
+//! | `acme-route53` | AWS Route 53 DNS provider for ACME `DNS-01` challenge | ❌ |
 //! | `rustls` | TLS built on [`rustls`](https://crates.io/crates/rustls) | ❌ |
 //! | `openssl` | TLS built on [`openssl-tls`](https://crates.io/crates/openssl) | ❌ |
 //! | `native-tls` | TLS built on [`native-tls`](https://crates.io/crates/native-tls) | ❌ |
 //! | `unix` | Listener based on Unix socket | ❌ |
+//! | `msgpack` | Support for [MessagePack](https://msgpack.org) request and response body | ❌ |
+//! | `cbor` | Support for [CBOR](https://cbor.io) request and response body | ❌ |
+//! | `yaml` | Support for YAML request and response body | ❌ |
+//! | `protobuf` | Support for [Protocol Buffers](https://protobuf.dev) request and response body built on [`prost`](https://crates.io/crates/prost) | ❌ |
+//! | `validator` | Validate extracted data with the [`validator`](https://crates.io/crates/validator) crate | ❌ |
 //! | `tower-compat` | Adapters for `tower::Layer` and `tower::Service` | ❌ |
 //! | `anyhow` | Integrate with the [`anyhow`](https://crates.io/crates/anyhow) crate | ❌ |
 //! | `eyre` | Integrate with the [`eyre`](https://crates.io/crates/eyre) crate | ❌ |
+//! | `access-log` | Middleware for structured access logging | ❌ |
 //! | `affix-state` | Middleware for adding prefix and suffix to the request path | ❌ |
 //! | `craft` | Generate handlers or endpoints with shared data | ❌ |
 //! | `basic-auth` | Middleware for basic authentication | ❌ |
+//! | `basic-auth-bcrypt` | Support bcrypt hashes in htpasswd files of basic authentication | ❌ |
 //! | `caching-headers` | Middleware for setting caching headers | ❌ |
 //! | `catch-panic` | Middleware for catching panics | ❌ |
+//! | `circuit-breaker` | Circuit breaker middleware | ❌ |
 //! | `concurrency-limiter` | Middleware for limiting concurrency | ❌ |
 //! | `force-https` | Middleware for forcing HTTPS | ❌ |
+//! | `health` | Health check and readiness endpoints | ❌ |
+//! | `ip-filter` | IP allow/deny list middleware | ❌ |
 //! | `logging` | Middleware for logging requests and responses | ❌ |
+//! | `maintenance` | Maintenance mode middleware with bypass rules | ❌ |
+//! | `metrics` | Prometheus metrics middleware and exposition endpoint | ❌ |
 //! | `request-id` | Middleware for setting a request ID | ❌ |
+//! | `retry` | Retry middleware for idempotent requests | ❌ |
+//! | `secure-headers` | Middleware for setting security headers | ❌ |
+//! | `singleflight` | Middleware for coalescing concurrent identical requests | ❌ |
 //! | `size-limiter` | Middleware for limiting request size | ❌ |
+//! | `slow-request` | Slow request detection and `Server-Timing` header | ❌ |
 //! | `sse` | Server-Sent Events (SSE) middleware | ❌ |
 //! | `timeout` | Middleware for setting a timeout | ❌ |
+//! | `token-auth` | Middleware for opaque token and API key authentication | ❌ |
+//! | `webhook` | Webhook signature verification middleware | ❌ |
 //! | `trailing-slash` | Middleware for handling trailing slashes | ❌ |
 //! | `websocket` | WebSocket implementation | ❌ |
 #![doc(html_favicon_url = "https://salvo.rs/favicon-32x32.png")]
@@ -68,6 +88,11 @@ cfg_feature! {
     // #[doc(no_inline)]
     pub use salvo_extra::catch_panic;
 }
+cfg_feature! {
+    #![feature ="circuit-breaker"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::circuit_breaker;
+}
 cfg_feature! {
     #![feature ="force-https"]
     // #[doc(no_inline)]
@@ -78,6 +103,16 @@ cfg_feature! {
     // #[doc(no_inline)]
     pub use salvo_extra::logging;
 }
+cfg_feature! {
+    #![feature ="access-log"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::access_log;
+}
+cfg_feature! {
+    #![feature ="metrics"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::metrics;
+}
 cfg_feature! {
     #![feature ="concurrency-limiter"]
     // #[doc(no_inline)]
@@ -113,6 +148,51 @@ cfg_feature! {
     // #[doc(no_inline)]
     pub use salvo_extra::request_id;
 }
+cfg_feature! {
+    #![feature ="ip-filter"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::ip_filter;
+}
+cfg_feature! {
+    #![feature ="maintenance"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::maintenance;
+}
+cfg_feature! {
+    #![feature ="retry"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::retry;
+}
+cfg_feature! {
+    #![feature ="secure-headers"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::secure_headers;
+}
+cfg_feature! {
+    #![feature ="token-auth"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::token_auth;
+}
+cfg_feature! {
+    #![feature ="webhook"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::webhook;
+}
+cfg_feature! {
+    #![feature ="singleflight"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::singleflight;
+}
+cfg_feature! {
+    #![feature ="slow-request"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::slow_request;
+}
+cfg_feature! {
+    #![feature ="health"]
+    // #[doc(no_inline)]
+    pub use salvo_extra::health;
+}
 cfg_feature! {
     #![feature ="cache"]
     #[doc(no_inline)]
@@ -223,6 +303,10 @@ pub mod prelude {
         #![feature ="logging"]
         pub use salvo_extra::logging::Logger;
     }
+    cfg_feature! {
+        #![feature ="access-log"]
+        pub use salvo_extra::access_log::AccessLog;
+    }
     cfg_feature! {
         #![feature ="proxy"]
         pub use salvo_proxy::Proxy;
@@ -263,6 +347,10 @@ pub mod prelude {
         #![feature ="request-id"]
         pub use salvo_extra::request_id::RequestId;
     }
+    cfg_feature! {
+        #![feature ="health"]
+        pub use salvo_extra::health::HealthCheck;
+    }
     cfg_feature! {
         #![feature ="serve-static"]
         pub use salvo_serve_static::{StaticFile, StaticDir};
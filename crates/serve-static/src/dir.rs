@@ -128,6 +128,9 @@ where
 /// with support for directory listing, compressed file variants,
 /// and default files.
 ///
+/// When the matched router has a [timeout](salvo_core::Router::timeout), looking up the file and
+/// listing the directory are cancelled once the timeout elapsed.
+///
 /// # Examples
 ///
 /// ```